@@ -0,0 +1,30 @@
+//! # Access Control Building Blocks
+//!
+//! Reusable access-control modules for Soroban contracts. Each module is a
+//! set of free functions over `&Env` with its own storage keys and error
+//! type, so a contract opts in by calling them from its entry points:
+//!
+//! - [`ownable`] – single owner, ownership transfer, and delayed two-step
+//!   renouncement
+//! - [`pausable`] – contract-wide pause switch for blocking new activity
+//!
+//! ```ignore
+//! use cookbook_access::ownable;
+//!
+//! ownable::init_owner(&env, &owner)?;   // once, from initialize
+//! ownable::require_owner(&env)?;        // guard privileged functions
+//! ```
+//!
+//! This crate defines no contract of its own, so depending on it adds no
+//! exported functions to the dependent's wasm. See
+//! `examples/advanced/07-access-control` for a contract wired to it.
+
+#![no_std]
+
+pub mod ownable;
+pub mod pausable;
+
+pub use ownable::OwnableError;
+pub use pausable::PausableError;
+
+mod test;
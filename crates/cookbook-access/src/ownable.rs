@@ -0,0 +1,146 @@
+//! Single-owner access control.
+//!
+//! Free functions over `&Env` so any contract can embed them: call
+//! [`init_owner`] once, guard privileged entry points with
+//! [`require_owner`], and expose whichever management functions you need.
+//!
+//! ## Renouncing ownership
+//!
+//! Giving up ownership is irreversible, so it takes two calls separated by
+//! [`RENOUNCE_DELAY`]:
+//!
+//! ```text
+//! begin_renounce ──(RENOUNCE_DELAY)──▶ confirm_renounce
+//!        └──────── cancel_renounce ───────┘
+//! ```
+//!
+//! There is no single `renounce_ownership` call: renouncing ownership is
+//! [`begin_renounce`] followed by [`confirm_renounce`], so a mistaken or
+//! malicious call can still be undone with [`cancel_renounce`].
+//!
+//! Each step emits an `(owner, <step>)` event so watchers can react while the
+//! delay is running. After confirmation every owner-gated call fails with
+//! [`OwnableError::Renounced`], and [`init_owner`] refuses to run again, so
+//! nobody can reclaim the contract.
+
+use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env};
+
+/// Time between `begin_renounce` and the earliest `confirm_renounce` (7 days).
+pub const RENOUNCE_DELAY: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum OwnableKey {
+    Owner,
+    /// Earliest timestamp at which a pending renouncement can be confirmed.
+    RenounceAt,
+    /// Set permanently once ownership has been renounced.
+    Renounced,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OwnableError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// Ownership was renounced; owner-gated calls are disabled forever.
+    Renounced = 3,
+    NoPendingRenounce = 4,
+    RenouncePending = 5,
+    RenounceDelayNotElapsed = 6,
+}
+
+/// Set the initial owner. Fails if an owner exists or ownership was renounced.
+pub fn init_owner(env: &Env, owner: &Address) -> Result<(), OwnableError> {
+    let storage = env.storage().instance();
+    if storage.has(&OwnableKey::Owner) || storage.has(&OwnableKey::Renounced) {
+        return Err(OwnableError::AlreadyInitialized);
+    }
+    storage.set(&OwnableKey::Owner, owner);
+    Ok(())
+}
+
+/// Current owner, or `None` if uninitialized or renounced.
+pub fn owner(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&OwnableKey::Owner)
+}
+
+pub fn is_renounced(env: &Env) -> bool {
+    env.storage().instance().has(&OwnableKey::Renounced)
+}
+
+/// Authenticate the owner and return its address.
+pub fn require_owner(env: &Env) -> Result<Address, OwnableError> {
+    if is_renounced(env) {
+        return Err(OwnableError::Renounced);
+    }
+    let owner = owner(env).ok_or(OwnableError::NotInitialized)?;
+    owner.require_auth();
+    Ok(owner)
+}
+
+/// Hand ownership to `new_owner`. Not allowed while a renouncement is pending.
+pub fn transfer_ownership(env: &Env, new_owner: &Address) -> Result<(), OwnableError> {
+    let previous = require_owner(env)?;
+    if pending_renounce(env).is_some() {
+        return Err(OwnableError::RenouncePending);
+    }
+    env.storage().instance().set(&OwnableKey::Owner, new_owner);
+    env.events().publish(
+        (symbol_short!("owner"), symbol_short!("transfer")),
+        (previous, new_owner.clone()),
+    );
+    Ok(())
+}
+
+/// Step 1: announce the renouncement. Returns the earliest confirm time.
+pub fn begin_renounce(env: &Env) -> Result<u64, OwnableError> {
+    let owner = require_owner(env)?;
+    if pending_renounce(env).is_some() {
+        return Err(OwnableError::RenouncePending);
+    }
+    let renounce_at = env.ledger().timestamp() + RENOUNCE_DELAY;
+    env.storage()
+        .instance()
+        .set(&OwnableKey::RenounceAt, &renounce_at);
+    env.events().publish(
+        (symbol_short!("owner"), symbol_short!("ren_start")),
+        (owner, renounce_at),
+    );
+    Ok(renounce_at)
+}
+
+/// Abort a pending renouncement.
+pub fn cancel_renounce(env: &Env) -> Result<(), OwnableError> {
+    let owner = require_owner(env)?;
+    if pending_renounce(env).is_none() {
+        return Err(OwnableError::NoPendingRenounce);
+    }
+    env.storage().instance().remove(&OwnableKey::RenounceAt);
+    env.events()
+        .publish((symbol_short!("owner"), symbol_short!("ren_cncl")), owner);
+    Ok(())
+}
+
+/// Step 2: permanently give up ownership once the delay has elapsed.
+pub fn confirm_renounce(env: &Env) -> Result<(), OwnableError> {
+    let owner = require_owner(env)?;
+    let renounce_at = pending_renounce(env).ok_or(OwnableError::NoPendingRenounce)?;
+    if env.ledger().timestamp() < renounce_at {
+        return Err(OwnableError::RenounceDelayNotElapsed);
+    }
+
+    let storage = env.storage().instance();
+    storage.remove(&OwnableKey::Owner);
+    storage.remove(&OwnableKey::RenounceAt);
+    storage.set(&OwnableKey::Renounced, &true);
+    env.events()
+        .publish((symbol_short!("owner"), symbol_short!("renounced")), owner);
+    Ok(())
+}
+
+/// Earliest confirm timestamp of a pending renouncement, if any.
+pub fn pending_renounce(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&OwnableKey::RenounceAt)
+}
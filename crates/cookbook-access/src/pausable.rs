@@ -0,0 +1,72 @@
+//! Contract-wide pause switch.
+//!
+//! Free functions over `&Env`, like [`ownable`](crate::ownable). The module
+//! only stores the flag; who may flip it is the contract's decision, so guard
+//! [`pause`] and [`unpause`] with [`require_owner`](crate::ownable::require_owner)
+//! or an admin check of your own:
+//!
+//! ```ignore
+//! pub fn deposit(env: Env, user: Address, amount: i128) -> Result<(), Error> {
+//!     pausable::require_not_paused(&env)?;
+//!     // ...
+//! }
+//!
+//! pub fn pause(env: Env) -> Result<(), Error> {
+//!     ownable::require_owner(&env)?;
+//!     pausable::pause(&env)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Pausing blocks new activity only. Leave exits such as `withdraw`
+//! unguarded, or users' funds are trapped for as long as the pause lasts.
+
+use soroban_sdk::{contracterror, contracttype, symbol_short, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PausableKey {
+    Paused,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PausableError {
+    /// The contract is paused.
+    Paused = 1,
+    /// `unpause` was called while the contract was not paused.
+    NotPaused = 2,
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().has(&PausableKey::Paused)
+}
+
+/// Fail with [`PausableError::Paused`] while the contract is paused.
+pub fn require_not_paused(env: &Env) -> Result<(), PausableError> {
+    if is_paused(env) {
+        return Err(PausableError::Paused);
+    }
+    Ok(())
+}
+
+/// Pause the contract. Emits `(pausable, paused)`.
+pub fn pause(env: &Env) -> Result<(), PausableError> {
+    require_not_paused(env)?;
+    env.storage().instance().set(&PausableKey::Paused, &true);
+    env.events()
+        .publish((symbol_short!("pausable"), symbol_short!("paused")), ());
+    Ok(())
+}
+
+/// Lift the pause. Emits `(pausable, unpaused)`.
+pub fn unpause(env: &Env) -> Result<(), PausableError> {
+    if !is_paused(env) {
+        return Err(PausableError::NotPaused);
+    }
+    env.storage().instance().remove(&PausableKey::Paused);
+    env.events()
+        .publish((symbol_short!("pausable"), symbol_short!("unpaused")), ());
+    Ok(())
+}
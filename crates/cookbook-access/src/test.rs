@@ -0,0 +1,84 @@
+#![cfg(test)]
+
+use super::*;
+use ownable::RENOUNCE_DELAY;
+use soroban_sdk::{
+    contract,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+#[contract]
+struct Owned;
+
+fn env_with_contract() -> (Env, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Owned);
+    (env, contract_id)
+}
+
+#[test]
+fn test_init_owner_only_once() {
+    let (env, contract_id) = env_with_contract();
+    let owner = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        assert_eq!(ownable::init_owner(&env, &owner), Ok(()));
+        assert_eq!(
+            ownable::init_owner(&env, &owner),
+            Err(OwnableError::AlreadyInitialized)
+        );
+        assert_eq!(ownable::require_owner(&env), Ok(owner.clone()));
+    });
+}
+
+#[test]
+fn test_require_owner_before_init_fails() {
+    let (env, contract_id) = env_with_contract();
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            ownable::require_owner(&env),
+            Err(OwnableError::NotInitialized)
+        );
+    });
+}
+
+#[test]
+fn test_renounce_is_final() {
+    let (env, contract_id) = env_with_contract();
+    let owner = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        ownable::init_owner(&env, &owner).unwrap();
+        ownable::begin_renounce(&env).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp += RENOUNCE_DELAY);
+    env.as_contract(&contract_id, || {
+        ownable::confirm_renounce(&env).unwrap();
+        assert_eq!(ownable::owner(&env), None);
+        assert_eq!(ownable::require_owner(&env), Err(OwnableError::Renounced));
+        assert_eq!(
+            ownable::init_owner(&env, &owner),
+            Err(OwnableError::AlreadyInitialized)
+        );
+    });
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    let (env, contract_id) = env_with_contract();
+    env.as_contract(&contract_id, || {
+        assert_eq!(pausable::require_not_paused(&env), Ok(()));
+        assert_eq!(pausable::unpause(&env), Err(PausableError::NotPaused));
+
+        assert_eq!(pausable::pause(&env), Ok(()));
+        assert!(pausable::is_paused(&env));
+        assert_eq!(
+            pausable::require_not_paused(&env),
+            Err(PausableError::Paused)
+        );
+        assert_eq!(pausable::pause(&env), Err(PausableError::Paused));
+
+        assert_eq!(pausable::unpause(&env), Ok(()));
+        assert_eq!(pausable::require_not_paused(&env), Ok(()));
+    });
+}
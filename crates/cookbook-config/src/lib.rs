@@ -0,0 +1,108 @@
+//! # Typed Config Accessors
+//!
+//! Examples that keep operator-tunable parameters in instance storage tend to
+//! repeat the same code for every key:
+//!
+//! ```text
+//! env.storage().instance().get(&symbol_short!("fee_bps")).unwrap_or(30)
+//! ```
+//!
+//! The key is a bare `Symbol`, the value type is only known at the call site,
+//! and the default is copy-pasted wherever the key is read. A typo in any of
+//! the three compiles fine and fails at runtime.
+//!
+//! [`typed_config!`] declares each key once, with its type and default, and
+//! generates typed accessors:
+//!
+//! ```ignore
+//! use cookbook_config::typed_config;
+//!
+//! typed_config! {
+//!     /// Parameters of the fee module.
+//!     pub struct FeeConfig {
+//!         /// Fee charged on transfers, in basis points.
+//!         fee_bps: u32 = 30,
+//!         /// Seconds between two actions of the same account.
+//!         cooldown: u64 = 60,
+//!     }
+//! }
+//!
+//! let fee: u32 = FeeConfig::fee_bps(&env); // 30 until set
+//! FeeConfig::set_fee_bps(&env, 25);
+//! FeeConfig::reset_cooldown(&env); // back to 60
+//! ```
+//!
+//! Values live in instance storage under a `Symbol` equal to the field name,
+//! so field names must be valid symbols (`[a-zA-Z0-9_]`, at most 32 chars).
+
+#![no_std]
+
+#[doc(hidden)]
+pub use paste;
+#[doc(hidden)]
+pub use soroban_sdk;
+
+/// Declares a set of config keys and generates typed accessors for them.
+///
+/// For every `name: Type = default` field, the struct gets:
+///
+/// - `name(env) -> Type` — the stored value, or `default` if unset
+/// - `set_name(env, value)` — store a new value
+/// - `reset_name(env)` — remove the stored value so `default` applies again
+///
+/// and the struct itself gets `KEYS`, the names of all declared fields.
+///
+/// See the crate docs for an example.
+#[macro_export]
+macro_rules! typed_config {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident : $ty:ty = $default:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name;
+
+        $crate::paste::paste! {
+            #[allow(dead_code)]
+            impl $name {
+                /// Names of all keys declared in this config.
+                pub const KEYS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+                $(
+                    $(#[$field_meta])*
+                    pub fn $field(env: &$crate::soroban_sdk::Env) -> $ty {
+                        env.storage()
+                            .instance()
+                            .get(&$crate::soroban_sdk::Symbol::new(env, stringify!($field)))
+                            .unwrap_or($default)
+                    }
+
+                    #[doc = concat!("Stores a new value for `", stringify!($field), "`.")]
+                    pub fn [<set_ $field>](env: &$crate::soroban_sdk::Env, value: $ty) {
+                        env.storage().instance().set(
+                            &$crate::soroban_sdk::Symbol::new(env, stringify!($field)),
+                            &value,
+                        );
+                    }
+
+                    #[doc = concat!(
+                        "Removes the stored `", stringify!($field),
+                        "` so its default applies again."
+                    )]
+                    pub fn [<reset_ $field>](env: &$crate::soroban_sdk::Env) {
+                        env.storage()
+                            .instance()
+                            .remove(&$crate::soroban_sdk::Symbol::new(env, stringify!($field)));
+                    }
+                )*
+            }
+        }
+    };
+}
+
+mod test;
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, Env, Symbol};
+
+typed_config! {
+    /// Config used by the test contract.
+    pub struct TestConfig {
+        /// Fee in basis points.
+        fee_bps: u32 = 30,
+        cooldown: u64 = 60,
+        paused: bool = false,
+    }
+}
+
+#[contract]
+struct ConfigContract;
+
+#[contractimpl]
+impl ConfigContract {
+    pub fn fee_bps(env: Env) -> u32 {
+        TestConfig::fee_bps(&env)
+    }
+
+    pub fn set_fee_bps(env: Env, value: u32) {
+        TestConfig::set_fee_bps(&env, value);
+    }
+
+    pub fn reset_fee_bps(env: Env) {
+        TestConfig::reset_fee_bps(&env);
+    }
+
+    pub fn cooldown(env: Env) -> u64 {
+        TestConfig::cooldown(&env)
+    }
+
+    pub fn set_cooldown(env: Env, value: u64) {
+        TestConfig::set_cooldown(&env, value);
+    }
+
+    pub fn paused(env: Env) -> bool {
+        TestConfig::paused(&env)
+    }
+
+    /// Raw read of the underlying storage entry.
+    pub fn raw(env: Env, key: Symbol) -> Option<u32> {
+        env.storage().instance().get(&key)
+    }
+}
+
+fn setup() -> (Env, ConfigContractClient<'static>) {
+    let env = Env::default();
+    let id = env.register_contract(None, ConfigContract);
+    let client = ConfigContractClient::new(&env, &id);
+    (env, client)
+}
+
+#[test]
+fn test_defaults_apply_when_unset() {
+    let (_env, client) = setup();
+    assert_eq!(client.fee_bps(), 30);
+    assert_eq!(client.cooldown(), 60);
+    assert!(!client.paused());
+}
+
+#[test]
+fn test_set_and_reset() {
+    let (_env, client) = setup();
+    client.set_fee_bps(&25);
+    client.set_cooldown(&120);
+    assert_eq!(client.fee_bps(), 25);
+    assert_eq!(client.cooldown(), 120);
+
+    client.reset_fee_bps();
+    assert_eq!(client.fee_bps(), 30);
+    assert_eq!(client.cooldown(), 120, "other keys are untouched");
+}
+
+#[test]
+fn test_values_stored_under_field_name() {
+    let (env, client) = setup();
+    client.set_fee_bps(&25);
+    assert_eq!(client.raw(&Symbol::new(&env, "fee_bps")), Some(25));
+}
+
+#[test]
+fn test_keys_lists_declared_fields() {
+    assert_eq!(TestConfig::KEYS, &["fee_bps", "cooldown", "paused"]);
+}
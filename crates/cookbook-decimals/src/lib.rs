@@ -0,0 +1,129 @@
+//! # Decimal Normalization
+//!
+//! Token amounts are integers with an implied number of decimals: `1_500_000`
+//! is 1.5 of a 6-decimal token and 0.15 of a 7-decimal one. Comparing or
+//! adding amounts of different tokens, or applying a price, first needs them
+//! on a common scale. Getting the exponent wrong is silent — the result is
+//! off by a power of ten — and doing the rescale in `i128` overflows long
+//! before the result itself would:
+//!
+//! ```text
+//! 1e24 (a million 18-decimal tokens) × 1e16 ($100 with 14 decimals) > i128::MAX
+//! ```
+//!
+//! These helpers keep the intermediate product in a host `U256`, so only
+//! the final result has to fit in an `i128`:
+//!
+//! ```ignore
+//! use cookbook_decimals::{scale_amount, mul_div, Rounding};
+//!
+//! // 1.5 USDC (6 decimals) as an 18-decimal amount.
+//! let wad = scale_amount(&env, 1_500_000, 6, 18, Rounding::Down)?;
+//!
+//! // amount × price / 10^price_decimals, with no i128 intermediate.
+//! let value = mul_div(&env, amount, price, pow10(price_decimals)?, Rounding::Down)?;
+//! ```
+//!
+//! Every function takes an explicit [`Rounding`] so the caller decides who
+//! keeps the remainder: round amounts owed *to* the contract up and amounts
+//! paid *out* down.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, Env, U256};
+
+/// Largest decimals count supported. `10^38` is the largest power of ten
+/// that fits in an `i128`, and no real token uses more than 18.
+pub const MAX_DECIMALS: u32 = 38;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DecimalsError {
+    /// The result does not fit in an `i128`.
+    Overflow = 1,
+    /// An amount or factor below zero.
+    NegativeAmount = 2,
+    /// A zero or negative denominator.
+    InvalidDenominator = 3,
+    /// A decimals count above `MAX_DECIMALS`.
+    UnsupportedDecimals = 4,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    /// Towards zero: drop the remainder.
+    Down,
+    /// Away from zero: any remainder adds one unit.
+    Up,
+}
+
+/// `10^exp` for `exp <= MAX_DECIMALS`.
+pub fn pow10(exp: u32) -> Result<i128, DecimalsError> {
+    if exp > MAX_DECIMALS {
+        return Err(DecimalsError::UnsupportedDecimals);
+    }
+    Ok(10i128.pow(exp))
+}
+
+/// Re-express `amount` from `from_decimals` to `to_decimals`.
+///
+/// Scaling up is exact or fails with `Overflow`; scaling down drops digits,
+/// rounded as requested.
+pub fn scale_amount(
+    env: &Env,
+    amount: i128,
+    from_decimals: u32,
+    to_decimals: u32,
+    rounding: Rounding,
+) -> Result<i128, DecimalsError> {
+    if from_decimals > MAX_DECIMALS || to_decimals > MAX_DECIMALS {
+        return Err(DecimalsError::UnsupportedDecimals);
+    }
+    if to_decimals >= from_decimals {
+        mul_div(
+            env,
+            amount,
+            pow10(to_decimals - from_decimals)?,
+            1,
+            rounding,
+        )
+    } else {
+        mul_div(
+            env,
+            amount,
+            1,
+            pow10(from_decimals - to_decimals)?,
+            rounding,
+        )
+    }
+}
+
+/// `a × b / denominator` for non-negative `a` and `b`, with the product kept
+/// in a `U256` so it cannot overflow.
+pub fn mul_div(
+    env: &Env,
+    a: i128,
+    b: i128,
+    denominator: i128,
+    rounding: Rounding,
+) -> Result<i128, DecimalsError> {
+    if a < 0 || b < 0 {
+        return Err(DecimalsError::NegativeAmount);
+    }
+    if denominator <= 0 {
+        return Err(DecimalsError::InvalidDenominator);
+    }
+    let product = U256::from_u128(env, a as u128).mul(&U256::from_u128(env, b as u128));
+    let denominator = U256::from_u128(env, denominator as u128);
+    let mut quotient = product.div(&denominator);
+    if rounding == Rounding::Up && product.rem_euclid(&denominator) != U256::from_u32(env, 0) {
+        quotient = quotient.add(&U256::from_u32(env, 1));
+    }
+    quotient
+        .to_u128()
+        .and_then(|q| i128::try_from(q).ok())
+        .ok_or(DecimalsError::Overflow)
+}
+
+mod test;
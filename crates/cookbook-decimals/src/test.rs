@@ -0,0 +1,168 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::Env;
+
+/// The scales the cookbook's tokens actually use: whole units, Stellar
+/// assets, and 18-decimal tokens bridged from EVM chains.
+const SCALES: [u32; 3] = [0, 7, 18];
+
+#[test]
+fn test_scale_exhaustive_pairs() {
+    let env = Env::default();
+    for from in SCALES {
+        for to in SCALES {
+            // 123.0 at `from` decimals.
+            let amount = 123 * pow10(from).unwrap();
+            for rounding in [Rounding::Down, Rounding::Up] {
+                assert_eq!(
+                    scale_amount(&env, amount, from, to, rounding),
+                    Ok(123 * pow10(to).unwrap()),
+                    "{from} -> {to} {rounding:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_scale_round_trip_is_exact_when_scaling_up_first() {
+    let env = Env::default();
+    for from in SCALES {
+        for to in SCALES.into_iter().filter(|to| *to >= from) {
+            for amount in [0, 1, 9, 1_234_567, i64::MAX as i128] {
+                let up = scale_amount(&env, amount, from, to, Rounding::Down).unwrap();
+                assert_eq!(
+                    scale_amount(&env, up, to, from, Rounding::Down),
+                    Ok(amount),
+                    "{amount} {from} -> {to} -> {from}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_scale_down_rounding() {
+    let env = Env::default();
+    // 1.000000000000000001 (18 decimals) → 7 decimals: the 1e-18 is dropped
+    // or rounded up to a whole 1e-7.
+    let amount = 1_000_000_000_000_000_001;
+    assert_eq!(
+        scale_amount(&env, amount, 18, 7, Rounding::Down),
+        Ok(10_000_000)
+    );
+    assert_eq!(
+        scale_amount(&env, amount, 18, 7, Rounding::Up),
+        Ok(10_000_001)
+    );
+    // Below one unit of the target scale.
+    assert_eq!(scale_amount(&env, 99, 18, 0, Rounding::Down), Ok(0));
+    assert_eq!(scale_amount(&env, 99, 18, 0, Rounding::Up), Ok(1));
+    assert_eq!(scale_amount(&env, 0, 18, 0, Rounding::Up), Ok(0));
+    // 4_9999999 (7 decimals) → 0 decimals.
+    assert_eq!(scale_amount(&env, 49_999_999, 7, 0, Rounding::Down), Ok(4));
+    assert_eq!(scale_amount(&env, 49_999_999, 7, 0, Rounding::Up), Ok(5));
+}
+
+#[test]
+fn test_scale_up_overflow() {
+    let env = Env::default();
+    // i128::MAX is about 1.7e38: 1.7e20 whole units fit at 18 decimals.
+    let max_whole = i128::MAX / pow10(18).unwrap();
+    assert_eq!(
+        scale_amount(&env, max_whole, 0, 18, Rounding::Down),
+        Ok(max_whole * pow10(18).unwrap())
+    );
+    assert_eq!(
+        scale_amount(&env, max_whole + 1, 0, 18, Rounding::Down),
+        Err(DecimalsError::Overflow)
+    );
+    assert_eq!(
+        scale_amount(&env, i128::MAX, 7, 18, Rounding::Down),
+        Err(DecimalsError::Overflow)
+    );
+    // Scaling i128::MAX down never overflows.
+    assert_eq!(
+        scale_amount(&env, i128::MAX, 18, 0, Rounding::Down),
+        Ok(max_whole)
+    );
+}
+
+#[test]
+fn test_scale_rejects_bad_input() {
+    let env = Env::default();
+    assert_eq!(
+        scale_amount(&env, -1, 7, 18, Rounding::Down),
+        Err(DecimalsError::NegativeAmount)
+    );
+    assert_eq!(
+        scale_amount(&env, 1, 39, 7, Rounding::Down),
+        Err(DecimalsError::UnsupportedDecimals)
+    );
+    assert_eq!(
+        scale_amount(&env, 1, 7, 39, Rounding::Down),
+        Err(DecimalsError::UnsupportedDecimals)
+    );
+    assert_eq!(
+        scale_amount(&env, 1, 38, 0, Rounding::Up),
+        Ok(1),
+        "MAX_DECIMALS itself is supported"
+    );
+}
+
+#[test]
+fn test_mul_div_wide_intermediate() {
+    let env = Env::default();
+    // A million 18-decimal tokens at $100 (14 decimals): the 1e40 product
+    // overflows i128, but the $100M result fits.
+    let amount = pow10(24).unwrap();
+    let price = 100 * pow10(14).unwrap();
+    assert!(amount.checked_mul(price).is_none());
+    assert_eq!(
+        mul_div(
+            &env,
+            amount,
+            price,
+            pow10(18 + 14 - 7).unwrap(),
+            Rounding::Down
+        ),
+        Ok(100_000_000 * pow10(7).unwrap())
+    );
+    // The largest possible product, (2^127 - 1)^2, still divides back.
+    assert_eq!(
+        mul_div(&env, i128::MAX, i128::MAX, i128::MAX, Rounding::Up),
+        Ok(i128::MAX)
+    );
+    assert_eq!(
+        mul_div(&env, i128::MAX, 2, 1, Rounding::Down),
+        Err(DecimalsError::Overflow)
+    );
+}
+
+#[test]
+fn test_mul_div_rounding() {
+    let env = Env::default();
+    assert_eq!(mul_div(&env, 10, 1, 3, Rounding::Down), Ok(3));
+    assert_eq!(mul_div(&env, 10, 1, 3, Rounding::Up), Ok(4));
+    assert_eq!(mul_div(&env, 9, 1, 3, Rounding::Up), Ok(3));
+    assert_eq!(mul_div(&env, 0, 5, 3, Rounding::Up), Ok(0));
+}
+
+#[test]
+fn test_mul_div_rejects_bad_input() {
+    let env = Env::default();
+    assert_eq!(
+        mul_div(&env, -1, 1, 1, Rounding::Down),
+        Err(DecimalsError::NegativeAmount)
+    );
+    assert_eq!(
+        mul_div(&env, 1, -1, 1, Rounding::Down),
+        Err(DecimalsError::NegativeAmount)
+    );
+    assert_eq!(
+        mul_div(&env, 1, 1, 0, Rounding::Down),
+        Err(DecimalsError::InvalidDenominator)
+    );
+    assert_eq!(pow10(39), Err(DecimalsError::UnsupportedDecimals));
+}
@@ -0,0 +1,235 @@
+//! # Pluggable Fee Strategies
+//!
+//! A marketplace takes a cut of each sale, an AMM a cut of each swap, and
+//! both eventually want to change how that cut is computed: a flat fee
+//! for a launch, basis points later, cheaper rates for large trades. Hard-
+//! coding `amount * fee_bps / 10_000` makes each of those a code upgrade.
+//!
+//! Here the computation is a trait, [`FeeStrategy`], with one small struct
+//! per strategy. The operator's choice is a [`FeeKind`] stored in instance
+//! storage, so switching strategy is a storage write:
+//!
+//! ```ignore
+//! use cookbook_fees::{charge, set_fee_kind, BpsFee, FeeKind};
+//!
+//! // Admin, once or whenever the policy changes:
+//! set_fee_kind(&env, &FeeKind::Bps(BpsFee { bps: 30 }))?;
+//!
+//! // On every sale or swap:
+//! let (fee, net) = charge(&env, amount)?;
+//! ```
+//!
+//! ## Polymorphism without an allocator
+//!
+//! Contracts are `no_std` with no heap, so there is no `Box<dyn Trait>`.
+//! Each `FeeKind` variant holds its strategy struct by value, and
+//! [`FeeKind::strategy`] hands out a `&dyn FeeStrategy` borrowed from it.
+//! Calling code depends on the trait only; adding a strategy means a new
+//! struct, a new variant and one new match arm.
+//!
+//! ## Rounding
+//!
+//! Percentage fees round up, so a fee is never zero on a non-zero rate and
+//! dust trades cannot avoid it. A fee larger than the amount it is charged
+//! on fails with `FeeExceedsAmount` rather than being capped.
+
+#![no_std]
+
+use cookbook_decimals::{mul_div, DecimalsError, Rounding};
+use soroban_sdk::{contracterror, contracttype, symbol_short, Env, Symbol, Vec};
+
+/// 100%, in basis points.
+pub const MAX_BPS: u32 = 10_000;
+/// Upper bound on the tiers of a `TieredFee`.
+pub const MAX_TIERS: u32 = 8;
+
+/// Instance-storage key of the configured `FeeKind`.
+const FEE_KIND: Symbol = symbol_short!("fee_kind");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FeeError {
+    /// A negative amount.
+    InvalidAmount = 1,
+    /// A negative flat fee, a rate above `MAX_BPS`, or malformed tiers.
+    InvalidConfig = 2,
+    /// The fee would be larger than the amount it is charged on.
+    FeeExceedsAmount = 3,
+    Overflow = 4,
+    /// No `FeeKind` is stored.
+    NotConfigured = 5,
+}
+
+impl From<DecimalsError> for FeeError {
+    fn from(err: DecimalsError) -> Self {
+        match err {
+            DecimalsError::NegativeAmount => FeeError::InvalidAmount,
+            _ => FeeError::Overflow,
+        }
+    }
+}
+
+/// How a fee is computed from the amount it is charged on.
+pub trait FeeStrategy {
+    /// The fee on `amount`, which is non-negative. Implementations may
+    /// assume `validate` passed.
+    fn fee(&self, env: &Env, amount: i128) -> Result<i128, FeeError>;
+
+    /// Reject parameters that make no sense, before they are stored.
+    fn validate(&self) -> Result<(), FeeError>;
+}
+
+/// The same fee whatever the amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlatFee {
+    pub amount: i128,
+}
+
+impl FeeStrategy for FlatFee {
+    fn fee(&self, _env: &Env, _amount: i128) -> Result<i128, FeeError> {
+        Ok(self.amount)
+    }
+
+    fn validate(&self) -> Result<(), FeeError> {
+        if self.amount < 0 {
+            return Err(FeeError::InvalidConfig);
+        }
+        Ok(())
+    }
+}
+
+/// A share of the amount, in basis points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BpsFee {
+    pub bps: u32,
+}
+
+impl FeeStrategy for BpsFee {
+    fn fee(&self, env: &Env, amount: i128) -> Result<i128, FeeError> {
+        Ok(mul_div(
+            env,
+            amount,
+            self.bps as i128,
+            MAX_BPS as i128,
+            Rounding::Up,
+        )?)
+    }
+
+    fn validate(&self) -> Result<(), FeeError> {
+        if self.bps > MAX_BPS {
+            return Err(FeeError::InvalidConfig);
+        }
+        Ok(())
+    }
+}
+
+/// One rate of a `TieredFee`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tier {
+    /// Smallest amount this rate applies to.
+    pub min_amount: i128,
+    pub bps: u32,
+}
+
+/// Volume pricing: the whole amount is charged at the rate of the highest
+/// tier it reaches.
+///
+/// Tiers are sorted by strictly increasing `min_amount`, and the first one
+/// starts at 0 so every amount has a rate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TieredFee {
+    pub tiers: Vec<Tier>,
+}
+
+impl FeeStrategy for TieredFee {
+    fn fee(&self, env: &Env, amount: i128) -> Result<i128, FeeError> {
+        let mut bps = 0;
+        for tier in self.tiers.iter() {
+            if amount < tier.min_amount {
+                break;
+            }
+            bps = tier.bps;
+        }
+        BpsFee { bps }.fee(env, amount)
+    }
+
+    fn validate(&self) -> Result<(), FeeError> {
+        if self.tiers.is_empty() || self.tiers.len() > MAX_TIERS {
+            return Err(FeeError::InvalidConfig);
+        }
+        let mut previous: Option<i128> = None;
+        for tier in self.tiers.iter() {
+            let ordered = match previous {
+                None => tier.min_amount == 0,
+                Some(min) => tier.min_amount > min,
+            };
+            if !ordered || tier.bps > MAX_BPS {
+                return Err(FeeError::InvalidConfig);
+            }
+            previous = Some(tier.min_amount);
+        }
+        Ok(())
+    }
+}
+
+/// The strategy an operator selected, as stored.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeKind {
+    Flat(FlatFee),
+    Bps(BpsFee),
+    Tiered(TieredFee),
+}
+
+impl FeeKind {
+    /// The selected strategy, as a trait object.
+    pub fn strategy(&self) -> &dyn FeeStrategy {
+        match self {
+            FeeKind::Flat(strategy) => strategy,
+            FeeKind::Bps(strategy) => strategy,
+            FeeKind::Tiered(strategy) => strategy,
+        }
+    }
+}
+
+/// Validate `kind` and store it as the contract's fee strategy. The caller
+/// is responsible for authorization.
+pub fn set_fee_kind(env: &Env, kind: &FeeKind) -> Result<(), FeeError> {
+    kind.strategy().validate()?;
+    env.storage().instance().set(&FEE_KIND, kind);
+    Ok(())
+}
+
+/// The stored fee strategy, if any.
+pub fn fee_kind(env: &Env) -> Option<FeeKind> {
+    env.storage().instance().get(&FEE_KIND)
+}
+
+/// `(fee, amount - fee)` for `amount` under the stored strategy.
+pub fn charge(env: &Env, amount: i128) -> Result<(i128, i128), FeeError> {
+    let kind = fee_kind(env).ok_or(FeeError::NotConfigured)?;
+    charge_with(env, kind.strategy(), amount)
+}
+
+/// `(fee, amount - fee)` for `amount` under any strategy.
+pub fn charge_with(
+    env: &Env,
+    strategy: &dyn FeeStrategy,
+    amount: i128,
+) -> Result<(i128, i128), FeeError> {
+    if amount < 0 {
+        return Err(FeeError::InvalidAmount);
+    }
+    let fee = strategy.fee(env, amount)?;
+    if fee > amount {
+        return Err(FeeError::FeeExceedsAmount);
+    }
+    Ok((fee, amount - fee))
+}
+
+mod test;
@@ -0,0 +1,146 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{contract, vec, Address, Env};
+
+#[contract]
+struct Market;
+
+fn env_with_contract() -> (Env, Address) {
+    let env = Env::default();
+    let id = env.register_contract(None, Market);
+    (env, id)
+}
+
+fn tiers(env: &Env, tiers: &[(i128, u32)]) -> TieredFee {
+    let mut out = Vec::new(env);
+    for (min_amount, bps) in tiers {
+        out.push_back(Tier {
+            min_amount: *min_amount,
+            bps: *bps,
+        });
+    }
+    TieredFee { tiers: out }
+}
+
+#[test]
+fn test_flat_fee() {
+    let env = Env::default();
+    let flat = FlatFee { amount: 5 };
+    assert_eq!(charge_with(&env, &flat, 100), Ok((5, 95)));
+    assert_eq!(charge_with(&env, &flat, 5), Ok((5, 0)));
+    assert_eq!(charge_with(&env, &flat, 4), Err(FeeError::FeeExceedsAmount));
+}
+
+#[test]
+fn test_bps_fee_rounds_up() {
+    let env = Env::default();
+    let bps = BpsFee { bps: 30 };
+    assert_eq!(charge_with(&env, &bps, 10_000), Ok((30, 9_970)));
+    // 0.3% of 1 is 0.003: still a whole unit, so dust pays too.
+    assert_eq!(charge_with(&env, &bps, 1), Ok((1, 0)));
+    assert_eq!(charge_with(&env, &bps, 0), Ok((0, 0)));
+    assert_eq!(
+        charge_with(&env, &BpsFee { bps: MAX_BPS }, i128::MAX),
+        Ok((i128::MAX, 0))
+    );
+    assert_eq!(charge_with(&env, &bps, -1), Err(FeeError::InvalidAmount));
+}
+
+#[test]
+fn test_tiered_fee_uses_highest_tier_reached() {
+    let env = Env::default();
+    let tiered = tiers(&env, &[(0, 50), (1_000, 30), (100_000, 10)]);
+    assert_eq!(tiered.validate(), Ok(()));
+    assert_eq!(charge_with(&env, &tiered, 999), Ok((5, 994)));
+    assert_eq!(charge_with(&env, &tiered, 1_000), Ok((3, 997)));
+    assert_eq!(charge_with(&env, &tiered, 100_000), Ok((100, 99_900)));
+}
+
+#[test]
+fn test_invalid_configs_rejected() {
+    let env = Env::default();
+    assert_eq!(
+        FlatFee { amount: -1 }.validate(),
+        Err(FeeError::InvalidConfig)
+    );
+    assert_eq!(
+        BpsFee { bps: MAX_BPS + 1 }.validate(),
+        Err(FeeError::InvalidConfig)
+    );
+    for bad in [
+        &[][..],
+        &[(1, 10)],
+        &[(0, 10), (0, 5)],
+        &[(0, 10), (500, 20), (100, 5)],
+        &[(0, MAX_BPS + 1)],
+    ] {
+        assert_eq!(
+            tiers(&env, bad).validate(),
+            Err(FeeError::InvalidConfig),
+            "{bad:?}"
+        );
+    }
+    let too_many: std::vec::Vec<(i128, u32)> =
+        (0..=MAX_TIERS as i128).map(|i| (i * 10, 1)).collect();
+    assert_eq!(
+        tiers(&env, &too_many).validate(),
+        Err(FeeError::InvalidConfig)
+    );
+}
+
+/// The operator switches strategy with a storage write; callers of `charge`
+/// do not change.
+#[test]
+fn test_stored_strategy_is_switchable() {
+    let (env, id) = env_with_contract();
+    env.as_contract(&id, || {
+        assert_eq!(charge(&env, 1_000), Err(FeeError::NotConfigured));
+
+        let kinds = [
+            (FeeKind::Flat(FlatFee { amount: 7 }), 7),
+            (FeeKind::Bps(BpsFee { bps: 100 }), 10),
+            (FeeKind::Tiered(tiers(&env, &[(0, 200), (500, 50)])), 5),
+        ];
+        for (kind, expected_fee) in kinds {
+            set_fee_kind(&env, &kind).unwrap();
+            assert_eq!(fee_kind(&env), Some(kind));
+            assert_eq!(
+                charge(&env, 1_000),
+                Ok((expected_fee, 1_000 - expected_fee))
+            );
+        }
+
+        // An invalid strategy is not stored; the previous one stays.
+        let bad = FeeKind::Tiered(TieredFee { tiers: vec![&env] });
+        assert_eq!(set_fee_kind(&env, &bad), Err(FeeError::InvalidConfig));
+        assert_eq!(charge(&env, 1_000), Ok((5, 995)));
+    });
+}
+
+/// A strategy defined outside this crate works with `charge_with`.
+struct CappedBps {
+    bps: u32,
+    cap: i128,
+}
+
+impl FeeStrategy for CappedBps {
+    fn fee(&self, env: &Env, amount: i128) -> Result<i128, FeeError> {
+        Ok(BpsFee { bps: self.bps }.fee(env, amount)?.min(self.cap))
+    }
+
+    fn validate(&self) -> Result<(), FeeError> {
+        BpsFee { bps: self.bps }.validate()
+    }
+}
+
+#[test]
+fn test_custom_strategy() {
+    let env = Env::default();
+    let capped = CappedBps { bps: 100, cap: 50 };
+    assert_eq!(capped.validate(), Ok(()));
+    assert_eq!(charge_with(&env, &capped, 1_000), Ok((10, 990)));
+    assert_eq!(charge_with(&env, &capped, 1_000_000), Ok((50, 999_950)));
+}
@@ -0,0 +1,58 @@
+//! # Slippage and Deadline Guards
+//!
+//! Swaps, routed deposits and zaps all take two user-supplied safety limits:
+//!
+//! - a **deadline** — the transaction may sit in a mempool or be replayed
+//!   later, when prices have moved; past the deadline it must fail;
+//! - a **minimum output** — the amount received may be worse than quoted
+//!   (price movement, sandwiching); below the minimum it must fail.
+//!
+//! Each example re-implementing these checks is how off-by-one bugs creep in
+//! (`>` vs `>=` on the deadline). These two functions are the single shared
+//! version:
+//!
+//! ```ignore
+//! use cookbook_guards::{check_deadline, check_min_out};
+//!
+//! check_deadline(&env, deadline)?;   // before doing any work
+//! let out = do_swap(...);
+//! check_min_out(out, min_out)?;      // before committing the result
+//! ```
+//!
+//! Contracts usually fold [`GuardError`] into their own error enum with a
+//! `From` impl so `?` works directly.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, Env};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum GuardError {
+    /// The ledger timestamp is past the caller's deadline.
+    DeadlinePassed = 1,
+    /// The output amount is below the caller's minimum.
+    SlippageExceeded = 2,
+}
+
+/// Fail if the current ledger timestamp is after `deadline`.
+///
+/// The deadline itself is still valid: a call in the ledger whose timestamp
+/// equals `deadline` succeeds.
+pub fn check_deadline(env: &Env, deadline: u64) -> Result<(), GuardError> {
+    if env.ledger().timestamp() > deadline {
+        return Err(GuardError::DeadlinePassed);
+    }
+    Ok(())
+}
+
+/// Fail if `actual` is below `min`. Receiving exactly `min` succeeds.
+pub fn check_min_out(actual: i128, min: i128) -> Result<(), GuardError> {
+    if actual < min {
+        return Err(GuardError::SlippageExceeded);
+    }
+    Ok(())
+}
+
+mod test;
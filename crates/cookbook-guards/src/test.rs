@@ -0,0 +1,40 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Ledger, Env};
+
+fn env_at(timestamp: u64) -> Env {
+    let env = Env::default();
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+    env
+}
+
+#[test]
+fn test_deadline_boundaries() {
+    let env = env_at(1_000);
+    assert_eq!(check_deadline(&env, 1_001), Ok(()));
+    assert_eq!(check_deadline(&env, 1_000), Ok(()), "deadline is inclusive");
+    assert_eq!(check_deadline(&env, 999), Err(GuardError::DeadlinePassed));
+}
+
+#[test]
+fn test_deadline_zero_only_valid_at_genesis() {
+    assert_eq!(check_deadline(&env_at(0), 0), Ok(()));
+    assert_eq!(
+        check_deadline(&env_at(1), 0),
+        Err(GuardError::DeadlinePassed)
+    );
+}
+
+#[test]
+fn test_min_out_boundaries() {
+    assert_eq!(check_min_out(101, 100), Ok(()));
+    assert_eq!(check_min_out(100, 100), Ok(()), "minimum is inclusive");
+    assert_eq!(check_min_out(99, 100), Err(GuardError::SlippageExceeded));
+}
+
+#[test]
+fn test_min_out_zero_accepts_anything_non_negative() {
+    assert_eq!(check_min_out(0, 0), Ok(()));
+    assert_eq!(check_min_out(-1, 0), Err(GuardError::SlippageExceeded));
+}
@@ -0,0 +1,79 @@
+//! # Cookbook Prelude
+//!
+//! The shared cookbook crates each solve one problem, and a contract that
+//! uses several of them ends up with one dependency line and one import
+//! line per crate:
+//!
+//! ```text
+//! cookbook-config   = { path = "../../../crates/cookbook-config" }
+//! cookbook-decimals = { path = "../../../crates/cookbook-decimals" }
+//! cookbook-guards   = { path = "../../../crates/cookbook-guards" }
+//! ```
+//!
+//! This crate re-exports them behind one feature flag each, so a contract
+//! depends on a single crate and imports what it needs in one line:
+//!
+//! ```ignore
+//! use cookbook_prelude::prelude::*;
+//!
+//! check_deadline(&env, deadline)?;
+//! let wad = scale_amount(&env, amount, 6, 18, Rounding::Down)?;
+//! ```
+//!
+//! | Feature | Module | Crate | Default |
+//! |---------|--------|-------|---------|
+//! | `access` | [`access`] | `cookbook-access` | yes |
+//! | `config` | [`config`] | `cookbook-config` | yes |
+//! | `math` | [`math`] | `cookbook-decimals` | yes |
+//! | `guards` | [`guards`] | `cookbook-guards` | yes |
+//! | `fees` | [`fees`] | `cookbook-fees` | yes |
+//! | `state` | [`state`] | `cookbook-state` | yes |
+//! | `testing` | [`testing`] | `cookbook-testing` | no |
+//!
+//! `testing` needs `std`, so enable it only in `[dev-dependencies]`.
+//!
+//! Time and validation checks (deadlines, minimum outputs) are in `guards`.
+//! There is no `events` feature: each example names its own event topics,
+//! and the shared crates emit theirs internally.
+//!
+//! Each module is the underlying crate itself, so its docs and error types
+//! apply unchanged: `guards::GuardError` is `cookbook_guards::GuardError`.
+
+#![no_std]
+
+#[cfg(feature = "access")]
+pub use cookbook_access as access;
+#[cfg(feature = "config")]
+pub use cookbook_config as config;
+#[cfg(feature = "math")]
+pub use cookbook_decimals as math;
+#[cfg(feature = "fees")]
+pub use cookbook_fees as fees;
+#[cfg(feature = "guards")]
+pub use cookbook_guards as guards;
+#[cfg(feature = "state")]
+pub use cookbook_state as state;
+#[cfg(feature = "testing")]
+pub use cookbook_testing as testing;
+
+/// The commonly used items of every enabled module.
+pub mod prelude {
+    #[cfg(feature = "access")]
+    pub use cookbook_access::{ownable, pausable, OwnableError, PausableError};
+    #[cfg(feature = "config")]
+    pub use cookbook_config::typed_config;
+    #[cfg(feature = "math")]
+    pub use cookbook_decimals::{mul_div, pow10, scale_amount, DecimalsError, Rounding};
+    #[cfg(feature = "fees")]
+    pub use cookbook_fees::{
+        charge, charge_with, set_fee_kind, BpsFee, FeeError, FeeKind, FeeStrategy,
+    };
+    #[cfg(feature = "guards")]
+    pub use cookbook_guards::{check_deadline, check_min_out, GuardError};
+    #[cfg(feature = "state")]
+    pub use cookbook_state::{state_machine, transition, transition_to, StateError, StateMachine};
+    #[cfg(feature = "testing")]
+    pub use cookbook_testing::{assert_conserved, Balances};
+}
+
+mod test;
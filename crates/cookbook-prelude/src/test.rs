@@ -0,0 +1,59 @@
+#![cfg(all(
+    test,
+    feature = "access",
+    feature = "config",
+    feature = "math",
+    feature = "guards"
+))]
+
+use super::prelude::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+typed_config! {
+    struct Limits {
+        cap: u32 = 7,
+    }
+}
+
+#[soroban_sdk::contract]
+struct Holder;
+
+/// The prelude items are the underlying crates' items, not copies.
+#[test]
+fn test_prelude_reexports_shared_crates() {
+    let env = Env::default();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    assert_eq!(check_deadline(&env, 999), Err(GuardError::DeadlinePassed));
+    assert_eq!(
+        check_min_out(10, 11),
+        Err(crate::guards::GuardError::SlippageExceeded)
+    );
+    assert_eq!(
+        scale_amount(&env, 1_500_000, 6, 18, Rounding::Down),
+        Ok(1_500_000_000_000_000_000)
+    );
+    assert_eq!(
+        pow10(39),
+        Err(crate::math::DecimalsError::UnsupportedDecimals)
+    );
+
+    let id = env.register_contract(None, Holder);
+    env.as_contract(&id, || {
+        assert_eq!(Limits::cap(&env), 7);
+        Limits::set_cap(&env, 9);
+        assert_eq!(Limits::cap(&env), 9);
+
+        let owner = Address::generate(&env);
+        assert_eq!(ownable::init_owner(&env, &owner), Ok(()));
+        assert_eq!(ownable::owner(&env), Some(owner));
+        assert_eq!(pausable::pause(&env), Ok(()));
+        assert_eq!(
+            pausable::require_not_paused(&env),
+            Err(crate::access::PausableError::Paused)
+        );
+    });
+}
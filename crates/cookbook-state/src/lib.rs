@@ -0,0 +1,186 @@
+//! # Declarative State Machines
+//!
+//! Contracts with an operational state (`Active`, `Paused`, `Frozen`, ...)
+//! usually store the enum and overwrite it from whichever function changes
+//! it. Which transitions are legal is then spread over those functions, and
+//! nothing stops a new one from thawing a frozen contract straight back to
+//! `Active`.
+//!
+//! [`state_machine!`] declares the states' transitions once, next to the
+//! enum:
+//!
+//! ```ignore
+//! use cookbook_state::{state_machine, transition, StateError};
+//!
+//! #[contracttype]
+//! #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+//! pub enum ContractState {
+//!     Active = 0,
+//!     Paused = 1,
+//!     Frozen = 2,
+//! }
+//!
+//! state_machine! {
+//!     ContractState, key = DataKey::State, initial = Active;
+//!     Active => [Paused, Frozen],
+//!     Paused => [Active, Frozen],
+//!     Frozen => [Paused],
+//! }
+//!
+//! // A fixed edge: does not compile unless `Active => Paused` is declared,
+//! // and fails with `WrongState` unless the contract is `Active`.
+//! transition!(&env, ContractState::Active => ContractState::Paused)?;
+//!
+//! // A target chosen at runtime: fails with `InvalidTransition` if the
+//! // current state has no edge to it.
+//! cookbook_state::transition_to(&env, target)?;
+//!
+//! let state = ContractState::current(&env);
+//! ```
+//!
+//! Every state must appear on the left exactly once, so adding a variant
+//! without deciding its transitions is a compile error. Each transition is
+//! stored under the given key and emits `(state,)` with `(from, to)`.
+
+#![no_std]
+
+use soroban_sdk::{contracterror, symbol_short, Env, IntoVal, TryFromVal, Val};
+
+#[doc(hidden)]
+pub use soroban_sdk;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StateError {
+    /// The current state has no declared transition to the target.
+    InvalidTransition = 1,
+    /// The contract is not in the state the caller requires.
+    WrongState = 2,
+}
+
+/// A state enum with declared transitions. Implemented by [`state_machine!`].
+pub trait StateMachine: Copy + Eq + IntoVal<Env, Val> + TryFromVal<Env, Val> {
+    type Key: IntoVal<Env, Val>;
+
+    /// State before anything is stored.
+    const INITIAL: Self;
+
+    /// Instance-storage key of the current state.
+    fn key() -> Self::Key;
+
+    /// Whether `from → to` is declared.
+    fn allowed(from: Self, to: Self) -> bool;
+}
+
+/// The stored state, or `S::INITIAL`.
+pub fn current_state<S: StateMachine>(env: &Env) -> S {
+    env.storage()
+        .instance()
+        .get(&S::key())
+        .unwrap_or(S::INITIAL)
+}
+
+/// `WrongState` unless the current state is `expected`.
+pub fn require_state<S: StateMachine>(env: &Env, expected: S) -> Result<(), StateError> {
+    if current_state::<S>(env) != expected {
+        return Err(StateError::WrongState);
+    }
+    Ok(())
+}
+
+/// Move to `to` if the current state declares it, and return the state left.
+///
+/// Moving to the current state is a no-op: it succeeds without an event.
+pub fn transition_to<S: StateMachine>(env: &Env, to: S) -> Result<S, StateError> {
+    let from = current_state::<S>(env);
+    if from == to {
+        return Ok(from);
+    }
+    if !S::allowed(from, to) {
+        return Err(StateError::InvalidTransition);
+    }
+    env.storage().instance().set(&S::key(), &to);
+    let data: (Val, Val) = (from.into_val(env), to.into_val(env));
+    env.events().publish((symbol_short!("state"),), data);
+    Ok(from)
+}
+
+/// Declares the transitions of a `#[contracttype]` unit enum. The key is a
+/// unit variant of the contract's storage-key enum.
+///
+/// ```ignore
+/// state_machine! {
+///     ContractState, key = DataKey::State, initial = Active;
+///     Active => [Paused, Frozen],
+///     Paused => [Active],
+///     Frozen => [],
+/// }
+/// ```
+///
+/// Generates:
+///
+/// - `ContractState::can_transition(from, to)`, a `const fn`
+/// - `ContractState::current(env)`, the stored state
+/// - `impl StateMachine for ContractState`
+#[macro_export]
+macro_rules! state_machine {
+    (
+        $ty:ident, key = $key_ty:ident :: $key:ident, initial = $initial:ident;
+        $( $from:ident => [ $( $to:ident ),* $(,)? ] ),+ $(,)?
+    ) => {
+        #[allow(dead_code)]
+        impl $ty {
+            /// Whether `from → to` is a declared transition.
+            pub const fn can_transition(from: $ty, to: $ty) -> bool {
+                match from {
+                    $( $ty::$from => false $( || matches!(to, $ty::$to) )*, )+
+                }
+            }
+
+            /// The stored state, or the initial one.
+            pub fn current(env: &$crate::soroban_sdk::Env) -> $ty {
+                $crate::current_state::<$ty>(env)
+            }
+        }
+
+        impl $crate::StateMachine for $ty {
+            type Key = $key_ty;
+
+            const INITIAL: Self = $ty::$initial;
+
+            fn key() -> Self::Key {
+                $key_ty::$key
+            }
+
+            fn allowed(from: Self, to: Self) -> bool {
+                $ty::can_transition(from, to)
+            }
+        }
+    };
+}
+
+/// Move along a fixed edge: from `$from`, which must be the current state,
+/// to `$to`.
+///
+/// The edge is checked at compile time, so a function that hard-codes an
+/// undeclared transition does not build.
+#[macro_export]
+macro_rules! transition {
+    ($env:expr, $ty:ident :: $from:ident => $to_ty:ident :: $to:ident) => {{
+        const _: () = assert!(
+            $ty::can_transition($ty::$from, $to_ty::$to),
+            concat!(
+                "undeclared transition ",
+                stringify!($from),
+                " => ",
+                stringify!($to)
+            )
+        );
+        $crate::require_state($env, $ty::$from)
+            .and_then(|()| $crate::transition_to($env, $to_ty::$to))
+            .map(|_| ())
+    }};
+}
+
+mod test;
@@ -0,0 +1,125 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{contract, contracttype, testutils::Events, vec, Address, Env, IntoVal};
+
+#[contract]
+struct Vault;
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    State,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum VaultState {
+    Active = 0,
+    Paused = 1,
+    Frozen = 2,
+}
+
+state_machine! {
+    VaultState, key = DataKey::State, initial = Active;
+    Active => [Paused, Frozen],
+    Paused => [Active, Frozen],
+    Frozen => [Paused],
+}
+
+fn env_with_contract() -> (Env, Address) {
+    let env = Env::default();
+    let id = env.register_contract(None, Vault);
+    (env, id)
+}
+
+#[test]
+fn test_declared_transitions() {
+    use VaultState::*;
+    assert!(VaultState::can_transition(Active, Paused));
+    assert!(VaultState::can_transition(Paused, Active));
+    assert!(VaultState::can_transition(Frozen, Paused));
+    assert!(!VaultState::can_transition(Frozen, Active));
+    assert!(!VaultState::can_transition(Active, Active));
+}
+
+#[test]
+fn test_initial_state() {
+    let (env, id) = env_with_contract();
+    env.as_contract(&id, || {
+        assert_eq!(VaultState::current(&env), VaultState::Active);
+        assert!(!env.storage().instance().has(&DataKey::State));
+    });
+}
+
+#[test]
+fn test_transition_stores_and_emits() {
+    let (env, id) = env_with_contract();
+    env.as_contract(&id, || {
+        assert_eq!(
+            transition_to(&env, VaultState::Frozen),
+            Ok(VaultState::Active)
+        );
+        assert_eq!(VaultState::current(&env), VaultState::Frozen);
+    });
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                id.clone(),
+                (symbol_short!("state"),).into_val(&env),
+                (VaultState::Active, VaultState::Frozen).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_undeclared_transition_rejected() {
+    let (env, id) = env_with_contract();
+    env.as_contract(&id, || {
+        transition_to(&env, VaultState::Frozen).unwrap();
+        assert_eq!(
+            transition_to(&env, VaultState::Active),
+            Err(StateError::InvalidTransition)
+        );
+        assert_eq!(VaultState::current(&env), VaultState::Frozen);
+
+        // Thawing goes through Paused.
+        transition_to(&env, VaultState::Paused).unwrap();
+        transition_to(&env, VaultState::Active).unwrap();
+    });
+}
+
+#[test]
+fn test_same_state_is_noop() {
+    let (env, id) = env_with_contract();
+    env.as_contract(&id, || {
+        assert_eq!(
+            transition_to(&env, VaultState::Active),
+            Ok(VaultState::Active)
+        );
+    });
+    assert!(env.events().all().is_empty());
+}
+
+#[test]
+fn test_transition_macro_requires_from_state() {
+    let (env, id) = env_with_contract();
+    env.as_contract(&id, || {
+        assert_eq!(
+            transition!(&env, VaultState::Paused => VaultState::Active),
+            Err(StateError::WrongState)
+        );
+        assert_eq!(
+            transition!(&env, VaultState::Active => VaultState::Paused),
+            Ok(())
+        );
+        assert_eq!(
+            transition!(&env, VaultState::Active => VaultState::Paused),
+            Err(StateError::WrongState)
+        );
+        assert_eq!(require_state(&env, VaultState::Paused), Ok(()));
+    });
+}
@@ -0,0 +1,130 @@
+//! # Conservation Assertions
+//!
+//! Most accounting bugs in a contract that holds tokens have the same
+//! symptom: after some operation, the tokens no longer add up. A refund pays
+//! twice, a rounding remainder disappears, a fee is charged but never
+//! credited. Checking each balance by hand in every test catches the cases
+//! the author thought of; checking that the *total* across every account the
+//! operation can touch is unchanged catches the rest.
+//!
+//! ```ignore
+//! use cookbook_testing::assert_conserved;
+//!
+//! // Token balances of every listed account must sum to the same total
+//! // before and after the operation. Returns the operation's result.
+//! let dust = assert_conserved!(env, token_id, [alice, bob, vault_id], {
+//!     vault.withdraw(&alice, &960)
+//! });
+//!
+//! // Any balance function works, e.g. a contract's internal ledger.
+//! assert_conserved!(balance = |a| vault.balance(a), [alice, bob, collector], {
+//!     vault.transfer(&alice, &bob, &500)
+//! });
+//! ```
+//!
+//! The list must include every account the operation may legitimately move
+//! funds between, contracts included. Leaving one out turns a correct
+//! transfer into a failure; that is the point — the test states exactly
+//! where funds are allowed to go.
+//!
+//! This crate is for tests only. Add it under `[dev-dependencies]`.
+
+use soroban_sdk::{token::TokenClient, Address, Env};
+
+extern crate std;
+use std::vec::Vec;
+
+/// Balances of a fixed list of accounts at one moment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Balances {
+    entries: Vec<(Address, i128)>,
+}
+
+impl Balances {
+    /// Read `balance` for each of `accounts`.
+    pub fn capture(balance: impl Fn(&Address) -> i128, accounts: &[&Address]) -> Self {
+        Balances {
+            entries: accounts
+                .iter()
+                .map(|account| ((*account).clone(), balance(account)))
+                .collect(),
+        }
+    }
+
+    /// Token balances of each of `accounts`.
+    pub fn of_token(env: &Env, token: &Address, accounts: &[&Address]) -> Self {
+        Self::capture(token_balance(env, token), accounts)
+    }
+
+    /// Sum of all captured balances.
+    pub fn total(&self) -> i128 {
+        self.entries.iter().map(|(_, balance)| balance).sum()
+    }
+
+    /// The captured balance of `account`. Panics if it was not captured.
+    pub fn get(&self, account: &Address) -> i128 {
+        self.entries
+            .iter()
+            .find(|(a, _)| a == account)
+            .map(|(_, balance)| *balance)
+            .expect("account was not captured")
+    }
+
+    /// Panic unless `self`, taken after an operation, has the same total as
+    /// `before`. The message lists every account's change.
+    #[track_caller]
+    pub fn assert_same_total(&self, before: &Balances) {
+        if self.total() == before.total() {
+            return;
+        }
+        let mut changes = std::string::String::new();
+        for ((account, old), (_, new)) in before.entries.iter().zip(&self.entries) {
+            changes += &std::format!("\n  {account:?}: {old} -> {new} ({:+})", new - old);
+        }
+        panic!(
+            "funds not conserved: total {} -> {}{changes}",
+            before.total(),
+            self.total()
+        );
+    }
+}
+
+/// A balance function reading `token` through the standard token interface.
+pub fn token_balance(env: &Env, token: &Address) -> impl Fn(&Address) -> i128 {
+    let client = TokenClient::new(env, token);
+    move |account| client.balance(account)
+}
+
+/// Pins a closure to `Fn(&Address) -> i128` so `|a| ...` infers its types.
+#[doc(hidden)]
+pub fn balance_fn<F: Fn(&Address) -> i128>(f: F) -> F {
+    f
+}
+
+/// Run an operation and assert the listed accounts hold the same total
+/// before and after it. Evaluates to the operation's result.
+///
+/// ```ignore
+/// assert_conserved!(env, token_id, [a, b, c], op);
+/// assert_conserved!(balance = |a| client.balance(a), [a, b, c], op);
+/// ```
+#[macro_export]
+macro_rules! assert_conserved {
+    (balance = $balance:expr, [$($account:expr),+ $(,)?], $op:expr $(,)?) => {{
+        let balance = $crate::balance_fn($balance);
+        let accounts = [$(&$account),+];
+        let before = $crate::Balances::capture(&balance, &accounts);
+        let result = $op;
+        $crate::Balances::capture(&balance, &accounts).assert_same_total(&before);
+        result
+    }};
+    ($env:expr, $token:expr, [$($account:expr),+ $(,)?], $op:expr $(,)?) => {
+        $crate::assert_conserved!(
+            balance = $crate::token_balance(&$env, &$token),
+            [$($account),+],
+            $op
+        )
+    };
+}
+
+mod test;
@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+};
+
+struct Setup {
+    env: Env,
+    token: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&alice, &1_000);
+    Setup {
+        env,
+        token,
+        alice,
+        bob,
+    }
+}
+
+#[test]
+fn test_transfer_is_conserved() {
+    let s = setup();
+    let client = TokenClient::new(&s.env, &s.token);
+    let result = assert_conserved!(s.env, s.token, [s.alice, s.bob], {
+        client.transfer(&s.alice, &s.bob, &400);
+        "done"
+    });
+    assert_eq!(result, "done");
+    assert_eq!(client.balance(&s.bob), 400);
+}
+
+#[test]
+#[should_panic(expected = "funds not conserved: total 1000 -> 1500")]
+fn test_mint_is_not_conserved() {
+    let s = setup();
+    assert_conserved!(s.env, s.token, [s.alice, s.bob], {
+        StellarAssetClient::new(&s.env, &s.token).mint(&s.bob, &500)
+    });
+}
+
+#[test]
+#[should_panic(expected = "funds not conserved")]
+fn test_unlisted_account_is_a_leak() {
+    let s = setup();
+    let carol = Address::generate(&s.env);
+    assert_conserved!(s.env, s.token, [s.alice, s.bob], {
+        TokenClient::new(&s.env, &s.token).transfer(&s.alice, &carol, &1)
+    });
+}
+
+#[test]
+fn test_custom_balance_function() {
+    let s = setup();
+    let mut ledger = std::vec![(s.alice.clone(), 70), (s.bob.clone(), 30)];
+    let lookup =
+        |ledger: &[(Address, i128)], a: &Address| ledger.iter().find(|(k, _)| k == a).unwrap().1;
+    let accounts = [&s.alice, &s.bob];
+    let before = Balances::capture(|a| lookup(&ledger, a), &accounts);
+    ledger[0].1 -= 25;
+    ledger[1].1 += 25;
+    let after = Balances::capture(|a| lookup(&ledger, a), &accounts);
+    after.assert_same_total(&before);
+    assert_eq!(before.get(&s.alice) - after.get(&s.alice), 25);
+    assert_eq!(after.total(), 100);
+}
@@ -1,10 +1,33 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address,
+    Env, Map, Symbol, Val, Vec,
+};
 
 #[contract]
 pub struct MultiPartyAuthContract;
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MultiPartyError {
+    /// The same address appears twice in a signer or approver list.
+    DuplicateApprover = 1,
+    /// The proposal has already been executed once.
+    AlreadyExecuted = 2,
+}
+
+/// Audit record of a proposal, kept in persistent storage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalStatus {
+    /// Approvers whose authorization executed the proposal; empty until then.
+    pub approvals: Vec<Address>,
+    pub threshold: u32,
+    pub executed: bool,
+}
+
 /// Example custom types for storage matching.
 #[contracttype]
 pub enum DataKey {
@@ -16,6 +39,8 @@ pub enum DataKey {
     Threshold(Symbol),
     // Allowed signers for a specific proposal
     Signers(Symbol),
+    // Lifecycle record of a proposal (persistent)
+    Status(Symbol),
 }
 
 #[contractimpl]
@@ -28,12 +53,15 @@ impl MultiPartyAuthContract {
     /// - Order of auth checks doesn't matter since they are collected and verified by the host environment.
     /// - Be careful with dynamic signer lists: anyone calling the contract could pass a list sizes,
     ///   so `signers` should typically be bounded or verified.
+    /// - A repeated signer fails with `DuplicateApprover`.
     ///
     /// # Gas cost
     /// Scales linearly with the number of authorizations since each signer verification has a cost.
-    pub fn multi_sig_transfer(_env: Env, signers: Vec<Address>, _to: Address, _amount: i128) {
-        // Require authorization from all signers
+    pub fn multi_sig_transfer(env: Env, signers: Vec<Address>, _to: Address, _amount: i128) {
+        // Require authorization from all signers, each exactly once
+        let mut seen = Map::new(&env);
         for signer in signers.iter() {
+            Self::mark_seen(&env, &mut seen, &signer);
             signer.require_auth();
         }
 
@@ -45,46 +73,38 @@ impl MultiPartyAuthContract {
     /// Requires that at least `threshold` parties from a known group of `approvers`
     /// authorize this action.
     ///
+    /// A proposal executes once: a second call fails with `AlreadyExecuted`.
+    ///
     /// Real world use-case: DAO voting thresholds or multisig wallets
     pub fn proposal_approval(env: Env, proposal_id: Symbol, approvers: Vec<Address>) {
-        // Load the required threshold and the legitimate signatories list
-        let required_threshold: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Threshold(proposal_id.clone()))
-            .unwrap_or(2);
-        let valid_signers: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Signers(proposal_id))
-            .unwrap_or_else(|| {
-                // Provide a default empty vector if not configured.
-                // In a real app we'd likely panic if the proposal wasn't initialized.
-                Vec::new(&env)
-            });
-
-        // Ensure we don't have duplicate approvals to cheat the threshold
-        // By checking everyone and verifying they are in the valid_signers list.
-        let mut valid_approval_count = 0;
+        Self::execute_proposal(&env, proposal_id, approvers);
 
-        // For each passed approver
-        for approver in approvers.iter() {
-            // Must be a recognized signer
-            if valid_signers.contains(&approver) {
-                // Must have actually authorized the call
-                approver.require_auth();
-                valid_approval_count += 1;
-            } else {
-                panic!("Approver not in the list of valid signers!");
-            }
-        }
+        // ... Execute proposal
+    }
 
-        // Check if M-of-N was met
-        if valid_approval_count < required_threshold {
-            panic!("Threshold not met");
-        }
+    /// Threshold authorization that actually executes the approved action.
+    ///
+    /// Once `threshold` valid signers have authorized, the contract calls
+    /// `target.function(args)` *as itself*. Any contract that stores this
+    /// multisig's address as its admin (for example the timelock) sees the
+    /// call as authorized, because a contract is always authorized for calls
+    /// it makes directly.
+    ///
+    /// The proposal is marked executed before the call, so the target cannot
+    /// re-run it.
+    ///
+    /// Real world use-case: a multisig that owns a timelock or an upgradeable contract
+    pub fn approve_and_call(
+        env: Env,
+        proposal_id: Symbol,
+        approvers: Vec<Address>,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) -> Val {
+        Self::execute_proposal(&env, proposal_id, approvers);
 
-        // ... Execute proposal
+        env.invoke_contract(&target, &function, args)
     }
 
     /// Demonstrates an Escrow using Sequential logic.
@@ -126,6 +146,106 @@ impl MultiPartyAuthContract {
         }
     }
 
+    /// Checks the threshold and records the proposal as executed.
+    ///
+    /// Emits `(approved, proposal_id)` with each approver, then
+    /// `(executed, proposal_id)` with the number of approvals.
+    fn execute_proposal(env: &Env, proposal_id: Symbol, approvers: Vec<Address>) {
+        let mut status = Self::get_proposal_status(env.clone(), proposal_id.clone())
+            .unwrap_or_else(|| ProposalStatus {
+                approvals: Vec::new(env),
+                threshold: Self::threshold(env, &proposal_id),
+                executed: false,
+            });
+        if status.executed {
+            panic_with_error!(env, MultiPartyError::AlreadyExecuted);
+        }
+        Self::require_threshold(env, proposal_id.clone(), approvers.clone());
+
+        for approver in approvers.iter() {
+            env.events()
+                .publish((symbol_short!("approved"), proposal_id.clone()), approver);
+        }
+        env.events().publish(
+            (symbol_short!("executed"), proposal_id.clone()),
+            approvers.len(),
+        );
+        status.approvals = approvers;
+        status.executed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Status(proposal_id), &status);
+    }
+
+    /// Approvals collected, threshold and executed flag of a proposal, or
+    /// `None` if it was never set up or executed.
+    pub fn get_proposal_status(env: Env, proposal_id: Symbol) -> Option<ProposalStatus> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Status(proposal_id))
+    }
+
+    fn threshold(env: &Env, proposal_id: &Symbol) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold(proposal_id.clone()))
+            .unwrap_or(2)
+    }
+
+    /// Checks that at least the stored threshold of valid signers approved.
+    fn require_threshold(env: &Env, proposal_id: Symbol, approvers: Vec<Address>) {
+        // Load the required threshold and the legitimate signatories list
+        let required_threshold = Self::threshold(env, &proposal_id);
+        let valid_signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers(proposal_id))
+            .unwrap_or_else(|| {
+                // Provide a default empty vector if not configured.
+                // In a real app we'd likely panic if the proposal wasn't initialized.
+                Vec::new(env)
+            });
+
+        // Ensure we don't have duplicate approvals to cheat the threshold
+        // By checking everyone and verifying they are in the valid_signers list.
+        let mut valid_approval_count = 0;
+        let mut seen = Map::new(env);
+
+        // For each passed approver
+        for approver in approvers.iter() {
+            // Must not be counted twice: passing the same address again
+            // would otherwise add to the count for free
+            Self::mark_seen(env, &mut seen, &approver);
+
+            // Must be a recognized signer
+            if valid_signers.contains(&approver) {
+                // Must have actually authorized the call
+                approver.require_auth();
+                valid_approval_count += 1;
+            } else {
+                panic!("Approver not in the list of valid signers!");
+            }
+        }
+
+        // Check if M-of-N was met
+        if valid_approval_count < required_threshold {
+            panic!("Threshold not met");
+        }
+    }
+
+    /// Records `address` in `seen`, failing with `DuplicateApprover` if it
+    /// was already there.
+    ///
+    /// `require_auth` cannot catch a repeat on its own: whether a second
+    /// call for the same address passes depends on the authorization
+    /// entries supplied with the transaction, and mocked auth always passes.
+    fn mark_seen(env: &Env, seen: &mut Map<Address, ()>, address: &Address) {
+        if seen.contains_key(address.clone()) {
+            panic_with_error!(env, MultiPartyError::DuplicateApprover);
+        }
+        seen.set(address.clone(), ());
+    }
+
     /// Helper for setting threshold and signers to easily test proposal approval
     pub fn setup_proposal(env: Env, proposal_id: Symbol, threshold: u32, signers: Vec<Address>) {
         env.storage()
@@ -133,7 +253,15 @@ impl MultiPartyAuthContract {
             .set(&DataKey::Threshold(proposal_id.clone()), &threshold);
         env.storage()
             .instance()
-            .set(&DataKey::Signers(proposal_id), &signers);
+            .set(&DataKey::Signers(proposal_id.clone()), &signers);
+        env.storage().persistent().set(
+            &DataKey::Status(proposal_id),
+            &ProposalStatus {
+                approvals: Vec::new(&env),
+                threshold,
+                executed: false,
+            },
+        );
     }
 }
 
@@ -4,8 +4,9 @@ extern crate std;
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
-    Address, Env, IntoVal, Symbol, Vec,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    vec, Address, Env, IntoVal, Symbol, Vec,
 };
 
 #[test]
@@ -167,6 +168,80 @@ fn test_proposal_approval_fails_invalid_signer() {
     client.proposal_approval(&proposal_id, &approvers);
 }
 
+#[test]
+fn test_proposal_approval_rejects_duplicate_approver() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MultiPartyAuthContract);
+    let client = MultiPartyAuthContractClient::new(&env, &contract_id);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let signer3 = Address::generate(&env);
+
+    let all_signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+    let proposal_id = Symbol::new(&env, "prop4");
+    client.setup_proposal(&proposal_id, &2u32, &all_signers);
+
+    // One signer listed twice must not count as two approvals
+    let approvers = Vec::from_array(&env, [signer1.clone(), signer1.clone()]);
+    assert_eq!(
+        client.try_proposal_approval(&proposal_id, &approvers),
+        Err(Ok(MultiPartyError::DuplicateApprover.into()))
+    );
+
+    // Also when the duplicate comes after other signers
+    let approvers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer1.clone()]);
+    assert_eq!(
+        client.try_proposal_approval(&proposal_id, &approvers),
+        Err(Ok(MultiPartyError::DuplicateApprover.into()))
+    );
+
+    // Two distinct signers still pass
+    let approvers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+    client.proposal_approval(&proposal_id, &approvers);
+}
+
+#[test]
+fn test_duplicate_approver_rejected_everywhere() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MultiPartyAuthContract);
+    let client = MultiPartyAuthContractClient::new(&env, &contract_id);
+    let target = env.register_contract(None, AdminGuarded);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    let twice = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer1.clone()]);
+    assert_eq!(
+        client.try_multi_sig_transfer(&twice, &to, &100i128),
+        Err(Ok(MultiPartyError::DuplicateApprover.into()))
+    );
+
+    let proposal_id = Symbol::new(&env, "set_val");
+    client.setup_proposal(
+        &proposal_id,
+        &2u32,
+        &Vec::from_array(&env, [signer1.clone(), signer2.clone()]),
+    );
+    let args = vec![&env, contract_id.into_val(&env), 42u32.into_val(&env)];
+    let result = client.try_approve_and_call(
+        &proposal_id,
+        &Vec::from_array(&env, [signer1.clone(), signer1]),
+        &target,
+        &symbol_short!("set_value"),
+        &args,
+    );
+    assert!(matches!(
+        result,
+        Err(Ok(err)) if err == MultiPartyError::DuplicateApprover.into()
+    ));
+}
+
 #[test]
 fn test_sequential_auth_escrow() {
     let env = Env::default();
@@ -259,3 +334,194 @@ fn test_sequential_auth_escrow_unauthorized_step2() {
 
     client.sequential_auth_escrow(&buyer, &seller, &1000i128);
 }
+
+/// Target contract administered by the multisig contract itself.
+#[contract]
+pub struct AdminGuarded;
+
+#[contractimpl]
+impl AdminGuarded {
+    pub fn set_value(env: Env, admin: Address, value: u32) -> u32 {
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("value"), &value);
+        value
+    }
+}
+
+#[test]
+fn test_approve_and_call_executes_as_multisig() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MultiPartyAuthContract);
+    let client = MultiPartyAuthContractClient::new(&env, &contract_id);
+    let target = env.register_contract(None, AdminGuarded);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let proposal_id = Symbol::new(&env, "set_val");
+    client.setup_proposal(
+        &proposal_id,
+        &2u32,
+        &Vec::from_array(&env, [signer1.clone(), signer2.clone()]),
+    );
+
+    let approvers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+    let function = symbol_short!("set_value");
+    let args = vec![&env, contract_id.into_val(&env), 42u32.into_val(&env)];
+
+    let result = client.approve_and_call(&proposal_id, &approvers, &target, &function, &args);
+    let value: u32 = result.into_val(&env);
+    assert_eq!(value, 42);
+
+    // Only the human signers needed to sign: the multisig's own auth on the
+    // target is implied because it is the direct caller.
+    let auths = env.auths();
+    assert_eq!(auths.len(), 2);
+    assert_eq!(auths[0].0, signer1);
+    assert_eq!(auths[1].0, signer2);
+}
+
+#[test]
+#[should_panic(expected = "Threshold not met")]
+fn test_approve_and_call_fails_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MultiPartyAuthContract);
+    let client = MultiPartyAuthContractClient::new(&env, &contract_id);
+    let target = env.register_contract(None, AdminGuarded);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let proposal_id = Symbol::new(&env, "set_val");
+    client.setup_proposal(
+        &proposal_id,
+        &2u32,
+        &Vec::from_array(&env, [signer1.clone(), signer2.clone()]),
+    );
+
+    let args = vec![&env, contract_id.into_val(&env), 42u32.into_val(&env)];
+    client.approve_and_call(
+        &proposal_id,
+        &Vec::from_array(&env, [signer1]),
+        &target,
+        &symbol_short!("set_value"),
+        &args,
+    );
+}
+
+#[test]
+fn test_proposal_status_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MultiPartyAuthContract);
+    let client = MultiPartyAuthContractClient::new(&env, &contract_id);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let signer3 = Address::generate(&env);
+    let proposal_id = Symbol::new(&env, "prop5");
+    assert_eq!(client.get_proposal_status(&proposal_id), None);
+
+    client.setup_proposal(
+        &proposal_id,
+        &2u32,
+        &Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]),
+    );
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        Some(ProposalStatus {
+            approvals: Vec::new(&env),
+            threshold: 2,
+            executed: false,
+        })
+    );
+
+    // A failed attempt leaves no trace
+    let too_few = Vec::from_array(&env, [signer1.clone()]);
+    assert!(client
+        .try_proposal_approval(&proposal_id, &too_few)
+        .is_err());
+    assert!(!client.get_proposal_status(&proposal_id).unwrap().executed);
+
+    let approvers = Vec::from_array(&env, [signer1.clone(), signer3.clone()]);
+    client.proposal_approval(&proposal_id, &approvers);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approved"), proposal_id.clone()).into_val(&env),
+                signer1.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("approved"), proposal_id.clone()).into_val(&env),
+                signer3.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("executed"), proposal_id.clone()).into_val(&env),
+                2u32.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        Some(ProposalStatus {
+            approvals: approvers,
+            threshold: 2,
+            executed: true,
+        })
+    );
+
+    // Executed proposals cannot be replayed, even by other signers
+    let others = Vec::from_array(&env, [signer2.clone(), signer3]);
+    assert_eq!(
+        client.try_proposal_approval(&proposal_id, &others),
+        Err(Ok(MultiPartyError::AlreadyExecuted.into()))
+    );
+}
+
+#[test]
+fn test_approve_and_call_executes_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MultiPartyAuthContract);
+    let client = MultiPartyAuthContractClient::new(&env, &contract_id);
+    let target = env.register_contract(None, AdminGuarded);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let approvers = Vec::from_array(&env, [signer1, signer2]);
+    let proposal_id = Symbol::new(&env, "set_val");
+    client.setup_proposal(&proposal_id, &2u32, &approvers);
+
+    let args = vec![&env, contract_id.into_val(&env), 42u32.into_val(&env)];
+    client.approve_and_call(
+        &proposal_id,
+        &approvers,
+        &target,
+        &symbol_short!("set_value"),
+        &args,
+    );
+    assert!(client.get_proposal_status(&proposal_id).unwrap().executed);
+
+    let result = client.try_approve_and_call(
+        &proposal_id,
+        &approvers,
+        &target,
+        &symbol_short!("set_value"),
+        &args,
+    );
+    assert!(matches!(
+        result,
+        Err(Ok(err)) if err == MultiPartyError::AlreadyExecuted.into()
+    ));
+}
@@ -1,6 +1,8 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, Env, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, Env, Symbol, Val, Vec,
+};
 
 /// Minimum delay (in seconds) that must pass before execution
 const MIN_DELAY: u64 = 60;
@@ -56,7 +58,7 @@ impl TimelockContract {
             .expect("Not initialized");
         admin.require_auth();
 
-        if delay < MIN_DELAY || delay > MAX_DELAY {
+        if !(MIN_DELAY..=MAX_DELAY).contains(&delay) {
             panic!("Delay out of range");
         }
 
@@ -103,6 +105,34 @@ impl TimelockContract {
             .publish((Symbol::new(&env, "executed"),), (operation_id, now));
     }
 
+    /// Derive the operation id for a contract call.
+    ///
+    /// The id is `sha256(xdr(target, function, args))`, so the exact call that
+    /// will run is fixed at queue time: queue the returned id with `queue`,
+    /// then run it with `execute_call` using the same arguments.
+    pub fn call_id(env: Env, target: Address, function: Symbol, args: Vec<Val>) -> Bytes {
+        let payload = (target, function, args).to_xdr(&env);
+        env.crypto().sha256(&payload).to_bytes().into()
+    }
+
+    /// Execute a queued contract call after its delay has passed.
+    ///
+    /// The operation id is recomputed from the arguments, so a call can only
+    /// run if exactly this `(target, function, args)` triple was queued. The
+    /// timelock invokes the target itself, which means the target can gate
+    /// sensitive entry points with `timelock.require_auth()`.
+    ///
+    /// Emits an `executed` event on success and returns the target's result.
+    pub fn execute_call(env: Env, target: Address, function: Symbol, args: Vec<Val>) -> Val {
+        let operation_id =
+            Self::call_id(env.clone(), target.clone(), function.clone(), args.clone());
+
+        // Same checks and replay protection as `execute`.
+        Self::execute(env.clone(), operation_id);
+
+        env.invoke_contract(&target, &function, args)
+    }
+
     /// Cancel a queued operation before it is executed.
     ///
     /// Emits a `cancelled` event on success.
@@ -3,7 +3,11 @@
 extern crate std;
 
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Bytes, Env};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Bytes, Env, IntoVal,
+};
 
 fn setup() -> (Env, Address, TimelockContractClient<'static>) {
     let env = Env::default();
@@ -98,6 +102,73 @@ fn test_execute_replay() {
     client.execute(&id); // replay — must panic
 }
 
+// ── execute_call ─────────────────────────────────────────────────────────────
+
+/// Minimal target that only accepts calls authorized by its timelock.
+#[contract]
+pub struct GuardedTarget;
+
+#[contractimpl]
+impl GuardedTarget {
+    pub fn set_value(env: Env, timelock: Address, value: u32) {
+        timelock.require_auth();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("value"), &value);
+    }
+
+    pub fn value(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("value"))
+            .unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_execute_call_invokes_target() {
+    let (env, _admin, client) = setup();
+    let target = env.register_contract(None, GuardedTarget);
+    let function = symbol_short!("set_value");
+    let args = vec![&env, client.address.into_val(&env), 7u32.into_val(&env)];
+
+    let id = client.call_id(&target, &function, &args);
+    client.queue(&id, &MIN_DELAY);
+    env.ledger().with_mut(|l| l.timestamp += MIN_DELAY);
+
+    client.execute_call(&target, &function, &args);
+
+    assert_eq!(GuardedTargetClient::new(&env, &target).value(), 7);
+    assert_eq!(client.get_state(&id), OperationState::Unknown);
+}
+
+#[test]
+#[should_panic(expected = "Operation not found")]
+fn test_execute_call_args_must_match_queue() {
+    let (env, _admin, client) = setup();
+    let target = env.register_contract(None, GuardedTarget);
+    let function = symbol_short!("set_value");
+    let queued = vec![&env, client.address.into_val(&env), 7u32.into_val(&env)];
+    let tampered = vec![&env, client.address.into_val(&env), 8u32.into_val(&env)];
+
+    client.queue(&client.call_id(&target, &function, &queued), &MIN_DELAY);
+    env.ledger().with_mut(|l| l.timestamp += MIN_DELAY);
+
+    client.execute_call(&target, &function, &tampered);
+}
+
+#[test]
+#[should_panic(expected = "Too early")]
+fn test_execute_call_too_early() {
+    let (env, _admin, client) = setup();
+    let target = env.register_contract(None, GuardedTarget);
+    let function = symbol_short!("set_value");
+    let args = vec![&env, client.address.into_val(&env), 7u32.into_val(&env)];
+
+    client.queue(&client.call_id(&target, &function, &args), &MIN_DELAY);
+    client.execute_call(&target, &function, &args);
+}
+
 // ── cancel ───────────────────────────────────────────────────────────────────
 
 #[test]
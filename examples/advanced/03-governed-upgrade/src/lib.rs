@@ -0,0 +1,86 @@
+//! # Governed Upgrade
+//!
+//! An upgradeable contract whose `upgrade()` entry point can only be reached
+//! through a governance pipeline:
+//!
+//! ```text
+//! multisig (01-multi-party-auth)  ──approve_and_call──▶  timelock (02-timelock)
+//!                                                             │ execute_call (after delay)
+//!                                                             ▼
+//!                                                   GovernedUpgradeContract::upgrade
+//!                                                             │
+//!                                                             ▼
+//!                                     env.deployer().update_current_contract_wasm
+//! ```
+//!
+//! The contract stores a single privileged address — the timelock — and calls
+//! `timelock.require_auth()` before swapping its Wasm. A contract address is
+//! authorized for any call it makes *directly*, so the check passes only when
+//! the timelock itself is the caller, i.e. after an operation was queued by the
+//! timelock's admin (the multisig) and its delay has elapsed.
+//!
+//! The new Wasm lives in the `v2/` crate next to this one; the full
+//! propose → approve → queue → execute-upgrade walk-through is in
+//! `tests/integration`.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+
+/// Version reported by this Wasm. The `v2` crate reports `2`.
+pub const VERSION: u32 = 1;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The only address allowed to trigger an upgrade.
+    Timelock,
+}
+
+#[contract]
+pub struct GovernedUpgradeContract;
+
+#[contractimpl]
+impl GovernedUpgradeContract {
+    /// Initialize the contract with the timelock that governs upgrades.
+    pub fn initialize(env: Env, timelock: Address) {
+        if env.storage().instance().has(&DataKey::Timelock) {
+            panic!("Already initialized");
+        }
+        env.storage().instance().set(&DataKey::Timelock, &timelock);
+    }
+
+    /// Replace this contract's Wasm with `new_wasm_hash`.
+    ///
+    /// The hash must refer to Wasm already uploaded to the network. Storage is
+    /// untouched by the swap, so the timelock address stays in place and keeps
+    /// governing future upgrades.
+    ///
+    /// Emits an `upgraded` event with the new hash.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let timelock = Self::timelock(env.clone());
+        // Only satisfied when the timelock contract is the direct caller.
+        timelock.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+    }
+
+    /// Return the address allowed to upgrade this contract.
+    pub fn timelock(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Timelock)
+            .expect("Not initialized")
+    }
+
+    /// Return the version of the currently running Wasm.
+    pub fn version() -> u32 {
+        VERSION
+    }
+}
+
+mod test;
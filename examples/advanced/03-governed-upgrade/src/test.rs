@@ -0,0 +1,58 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal,
+};
+
+fn setup() -> (Env, Address, GovernedUpgradeContractClient<'static>) {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, GovernedUpgradeContract);
+    let client = GovernedUpgradeContractClient::new(&env, &contract_id);
+    let timelock = Address::generate(&env);
+    client.initialize(&timelock);
+    (env, timelock, client)
+}
+
+#[test]
+fn test_initialize_stores_timelock() {
+    let (_env, timelock, client) = setup();
+    assert_eq!(client.timelock(), timelock);
+    assert_eq!(client.version(), VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice() {
+    let (env, _timelock, client) = setup();
+    client.initialize(&Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_upgrade_without_timelock_auth() {
+    let (env, _timelock, client) = setup();
+    client.upgrade(&BytesN::from_array(&env, &[0; 32]));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_upgrade_authorized_by_someone_else() {
+    let (env, _timelock, client) = setup();
+    let attacker = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[0; 32]);
+
+    // The attacker signs the exact invocation, but is not the timelock.
+    client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "upgrade",
+                args: (hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .upgrade(&hash);
+}
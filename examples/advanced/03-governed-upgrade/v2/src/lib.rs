@@ -0,0 +1,51 @@
+//! # Governed Upgrade — v2
+//!
+//! The Wasm that `GovernedUpgradeContract` is upgraded to in the integration
+//! test. It keeps the same storage layout and the same timelock-gated
+//! `upgrade()` entry point, so governance stays in control after the swap;
+//! only `version()` changes, which makes the new code easy to observe.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+
+/// Version reported by this Wasm.
+pub const VERSION: u32 = 2;
+
+/// Must match the v1 layout so existing entries are still readable.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Timelock,
+}
+
+#[contract]
+pub struct GovernedUpgradeContract;
+
+#[contractimpl]
+impl GovernedUpgradeContract {
+    /// Replace this contract's Wasm with `new_wasm_hash` (timelock only).
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let timelock = Self::timelock(env.clone());
+        timelock.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+    }
+
+    /// Return the address allowed to upgrade this contract.
+    pub fn timelock(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Timelock)
+            .expect("Not initialized")
+    }
+
+    /// Return the version of the currently running Wasm.
+    pub fn version() -> u32 {
+        VERSION
+    }
+}
@@ -0,0 +1,145 @@
+//! Reference callee implementations, one per interface generation, plus
+//! two whose `interface_version` exists but does not answer with a `u32`.
+//!
+//! They are only compiled for tests: in a real deployment each would be its
+//! own crate (and its own Wasm). Here each one sits in its own module because
+//! `#[contractimpl]` generates per-function items that would otherwise clash.
+
+#![cfg(test)]
+
+use soroban_sdk::{contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum VaultKey {
+    Total(Address),
+    LastMemo(Address),
+}
+
+pub(crate) fn credit(env: &Env, from: &Address, amount: i128) -> i128 {
+    let key = VaultKey::Total(from.clone());
+    let total: i128 = env.storage().persistent().get(&key).unwrap_or(0) + amount;
+    env.storage().persistent().set(&key, &total);
+    total
+}
+
+pub mod legacy {
+    use super::credit;
+    use crate::DepositV1;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    /// Contract deployed before `interface_version` existed.
+    #[contract]
+    pub struct LegacyVault;
+
+    #[contractimpl]
+    impl LegacyVault {
+        pub fn deposit(env: Env, req: DepositV1) -> i128 {
+            credit(&env, &req.from, req.amount)
+        }
+    }
+}
+
+pub mod v1 {
+    use super::credit;
+    use crate::{DepositV1, INTERFACE_V1};
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    /// First versioned release.
+    #[contract]
+    pub struct VaultV1;
+
+    #[contractimpl]
+    impl VaultV1 {
+        pub fn interface_version() -> u32 {
+            INTERFACE_V1
+        }
+
+        pub fn deposit(env: Env, req: DepositV1) -> i128 {
+            credit(&env, &req.from, req.amount)
+        }
+    }
+}
+
+pub mod v2 {
+    use super::{credit, VaultKey};
+    use crate::{DepositV2, INTERFACE_V2};
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    /// Second release: the argument struct gained `memo` and `min_credit`.
+    #[contract]
+    pub struct VaultV2;
+
+    #[contractimpl]
+    impl VaultV2 {
+        pub fn interface_version() -> u32 {
+            INTERFACE_V2
+        }
+
+        pub fn deposit(env: Env, req: DepositV2) -> i128 {
+            if req.amount < req.min_credit {
+                panic!("Below minimum credit");
+            }
+            env.storage()
+                .persistent()
+                .set(&VaultKey::LastMemo(req.from.clone()), &req.memo);
+            credit(&env, &req.from, req.amount)
+        }
+
+        pub fn last_memo(env: Env, from: Address) -> Option<Symbol> {
+            env.storage().persistent().get(&VaultKey::LastMemo(from))
+        }
+    }
+}
+
+pub mod v3 {
+    use soroban_sdk::{contract, contractimpl};
+
+    /// A release from the future that the integrator has not been updated for.
+    #[contract]
+    pub struct VaultV3;
+
+    #[contractimpl]
+    impl VaultV3 {
+        pub fn interface_version() -> u32 {
+            3
+        }
+    }
+}
+
+pub mod broken {
+    use soroban_sdk::{contract, contracterror, contractimpl};
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum VaultError {
+        Paused = 1,
+    }
+
+    /// Implements `interface_version` but answers with a contract error.
+    #[contract]
+    pub struct BrokenVault;
+
+    #[contractimpl]
+    impl BrokenVault {
+        pub fn interface_version() -> Result<u32, VaultError> {
+            Err(VaultError::Paused)
+        }
+    }
+}
+
+pub mod mistyped {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Symbol};
+
+    /// Implements `interface_version` with the wrong return type.
+    #[contract]
+    pub struct MistypedVault;
+
+    #[contractimpl]
+    impl MistypedVault {
+        pub fn interface_version() -> Symbol {
+            symbol_short!("v2")
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! # Versioned Interface Negotiation
+//!
+//! Cross-contract interfaces change over time: a vault adds a `memo`, a DEX
+//! adds a `min_out`. If the callee simply changes the argument struct of an
+//! existing function, every integrator that still sends the old struct starts
+//! failing with a decoding error.
+//!
+//! This example shows the caller side of a simple negotiation protocol:
+//!
+//! 1. Every callee exposes `interface_version() -> u32`.
+//! 2. The caller asks for the version *before* building its call.
+//! 3. The caller encodes the argument struct the callee understands
+//!    (`DepositV1` or `DepositV2`). A request that uses a v2 feature is
+//!    refused for a v1 callee rather than silently losing it.
+//! 4. Contracts deployed before the negotiation protocol existed do not have
+//!    `interface_version` at all; the failed call is caught with
+//!    `try_invoke_contract` and treated as version 1. Any other answer that
+//!    is not a `u32`, such as a contract error, fails the negotiation.
+//! 5. Versions newer than the caller knows about are rejected explicitly
+//!    instead of being guessed.
+//!
+//! The reference callee implementations (v1, v2, legacy, future) live in
+//! `callee.rs` and are only compiled for tests.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short,
+    xdr::{ScErrorCode, ScErrorType},
+    Address, Env, Error, IntoVal, Symbol, Val, Vec,
+};
+
+/// First published interface: `deposit(DepositV1)`.
+pub const INTERFACE_V1: u32 = 1;
+/// Second interface: `deposit(DepositV2)` adds a memo and a minimum credit.
+pub const INTERFACE_V2: u32 = 2;
+/// Newest interface this caller knows how to speak.
+pub const LATEST_SUPPORTED: u32 = INTERFACE_V2;
+
+/// The `memo` to pass when there is none; the only memo a v1 callee accepts.
+pub const NO_MEMO: Symbol = symbol_short!("");
+
+/// Argument struct understood by v1 callees.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositV1 {
+    pub from: Address,
+    pub amount: i128,
+}
+
+/// Argument struct understood by v2 callees.
+///
+/// `#[contracttype]` structs are encoded as maps keyed by field name, so a v1
+/// callee cannot decode this value — which is exactly why the caller must ask
+/// first.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositV2 {
+    pub from: Address,
+    pub amount: i128,
+    pub memo: Symbol,
+    pub min_credit: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NegotiationError {
+    /// The callee reports an interface newer than this caller understands.
+    UnsupportedVersion = 1,
+    /// The amount must be positive.
+    InvalidAmount = 2,
+    /// `interface_version` exists but failed or did not return a `u32`.
+    VersionQueryFailed = 3,
+    /// A `memo` or `min_credit` was given for a v1 callee, which would drop it.
+    FeatureUnsupported = 4,
+}
+
+#[contract]
+pub struct IntegratorContract;
+
+#[contractimpl]
+impl IntegratorContract {
+    /// Return the interface version this caller will use with `callee`.
+    ///
+    /// # Errors
+    /// * `NegotiationError::UnsupportedVersion` - If the callee is newer than
+    ///   `LATEST_SUPPORTED`
+    /// * `NegotiationError::VersionQueryFailed` - If `interface_version`
+    ///   returned a contract error or a value that is not a `u32`
+    pub fn negotiate(env: Env, callee: Address) -> Result<u32, NegotiationError> {
+        let version = Self::query_version(&env, &callee)?;
+        if version > LATEST_SUPPORTED {
+            return Err(NegotiationError::UnsupportedVersion);
+        }
+        Ok(version)
+    }
+
+    /// Deposit into `callee` using whichever encoding it understands.
+    ///
+    /// `memo` and `min_credit` are only understood by v2 callees. For a v1
+    /// callee they must be `NO_MEMO` and `0`; otherwise the deposit fails
+    /// with `FeatureUnsupported` instead of going through without them.
+    ///
+    /// Returns the callee's running total for `from`.
+    pub fn deposit(
+        env: Env,
+        callee: Address,
+        from: Address,
+        amount: i128,
+        memo: Symbol,
+        min_credit: i128,
+    ) -> Result<i128, NegotiationError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(NegotiationError::InvalidAmount);
+        }
+
+        let version = Self::negotiate(env.clone(), callee.clone())?;
+        let request: Val = if version >= INTERFACE_V2 {
+            DepositV2 {
+                from,
+                amount,
+                memo,
+                min_credit,
+            }
+            .into_val(&env)
+        } else {
+            if memo != NO_MEMO || min_credit != 0 {
+                return Err(NegotiationError::FeatureUnsupported);
+            }
+            DepositV1 { from, amount }.into_val(&env)
+        };
+
+        let args: Vec<Val> = Vec::from_array(&env, [request]);
+        Ok(env.invoke_contract(&callee, &Symbol::new(&env, "deposit"), args))
+    }
+
+    /// Ask the callee for its version, treating contracts that predate the
+    /// negotiation protocol as version 1.
+    fn query_version(env: &Env, callee: &Address) -> Result<u32, NegotiationError> {
+        let result = env.try_invoke_contract::<u32, Error>(
+            callee,
+            &Symbol::new(env, "interface_version"),
+            Vec::new(env),
+        );
+        // The host reports a call to a missing function as this error. It
+        // collapses a trap into the same code, so a panicking
+        // `interface_version` also reads as v1; the v1-only checks in
+        // `deposit` keep that from dropping fields.
+        let missing = Error::from_type_and_code(ScErrorType::Context, ScErrorCode::InvalidAction);
+        match result {
+            Ok(Ok(version)) => Ok(version),
+            Err(Ok(err)) if err == missing => Ok(INTERFACE_V1),
+            // A contract error or a return value that is not a `u32`: the
+            // function exists, so guessing v1 would be wrong.
+            _ => Err(NegotiationError::VersionQueryFailed),
+        }
+    }
+}
+
+mod callee;
+mod test;
@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+use super::callee::{
+    broken::BrokenVault, legacy::LegacyVault, mistyped::MistypedVault, v1::VaultV1, v2::VaultV2,
+    v2::VaultV2Client, v3::VaultV3,
+};
+use super::*;
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env};
+
+fn setup() -> (Env, IntegratorContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, IntegratorContract);
+    let client = IntegratorContractClient::new(&env, &contract_id);
+    (env, client)
+}
+
+// ── negotiation ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_negotiate_reads_reported_version() {
+    let (env, client) = setup();
+    let v1 = env.register_contract(None, VaultV1);
+    let v2 = env.register_contract(None, VaultV2);
+
+    assert_eq!(client.negotiate(&v1), INTERFACE_V1);
+    assert_eq!(client.negotiate(&v2), INTERFACE_V2);
+}
+
+#[test]
+fn test_negotiate_legacy_contract_defaults_to_v1() {
+    let (env, client) = setup();
+    let legacy = env.register_contract(None, LegacyVault);
+
+    assert_eq!(client.negotiate(&legacy), INTERFACE_V1);
+}
+
+#[test]
+fn test_negotiate_rejects_future_version() {
+    let (env, client) = setup();
+    let v3 = env.register_contract(None, VaultV3);
+
+    assert_eq!(
+        client.try_negotiate(&v3),
+        Err(Ok(NegotiationError::UnsupportedVersion))
+    );
+}
+
+#[test]
+fn test_negotiate_fails_when_version_query_errors() {
+    let (env, client) = setup();
+    let broken = env.register_contract(None, BrokenVault);
+    let mistyped = env.register_contract(None, MistypedVault);
+
+    // The function exists, so neither answer is read as "legacy, v1".
+    assert_eq!(
+        client.try_negotiate(&broken),
+        Err(Ok(NegotiationError::VersionQueryFailed))
+    );
+    assert_eq!(
+        client.try_negotiate(&mistyped),
+        Err(Ok(NegotiationError::VersionQueryFailed))
+    );
+}
+
+// ── deposit encoding ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_deposit_v1_encoding() {
+    let (env, client) = setup();
+    let v1 = env.register_contract(None, VaultV1);
+    let user = Address::generate(&env);
+
+    assert_eq!(client.deposit(&v1, &user, &100, &NO_MEMO, &0), 100);
+    assert_eq!(client.deposit(&v1, &user, &50, &NO_MEMO, &0), 150);
+}
+
+#[test]
+fn test_deposit_v1_refuses_v2_fields() {
+    let (env, client) = setup();
+    let user = Address::generate(&env);
+
+    for callee in [
+        env.register_contract(None, VaultV1),
+        env.register_contract(None, LegacyVault),
+    ] {
+        // A v1 callee cannot honour a memo or a minimum credit, so the
+        // deposit fails instead of going through without them.
+        assert_eq!(
+            client.try_deposit(&callee, &user, &100, &symbol_short!("rent"), &0),
+            Err(Ok(NegotiationError::FeatureUnsupported))
+        );
+        assert_eq!(
+            client.try_deposit(&callee, &user, &100, &NO_MEMO, &10),
+            Err(Ok(NegotiationError::FeatureUnsupported))
+        );
+    }
+}
+
+#[test]
+fn test_deposit_v2_encoding_forwards_new_fields() {
+    let (env, client) = setup();
+    let v2 = env.register_contract(None, VaultV2);
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        client.deposit(&v2, &user, &100, &symbol_short!("rent"), &10),
+        100
+    );
+    assert_eq!(
+        VaultV2Client::new(&env, &v2).last_memo(&user),
+        Some(symbol_short!("rent"))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Below minimum credit")]
+fn test_deposit_v2_enforces_new_field() {
+    let (env, client) = setup();
+    let v2 = env.register_contract(None, VaultV2);
+    let user = Address::generate(&env);
+
+    client.deposit(&v2, &user, &5, &symbol_short!("rent"), &10);
+}
+
+#[test]
+fn test_deposit_legacy_contract() {
+    let (env, client) = setup();
+    let legacy = env.register_contract(None, LegacyVault);
+    let user = Address::generate(&env);
+
+    assert_eq!(client.deposit(&legacy, &user, &70, &NO_MEMO, &0), 70);
+}
+
+#[test]
+fn test_deposit_future_version_is_refused() {
+    let (env, client) = setup();
+    let v3 = env.register_contract(None, VaultV3);
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        client.try_deposit(&v3, &user, &70, &symbol_short!("x"), &0),
+        Err(Ok(NegotiationError::UnsupportedVersion))
+    );
+}
+
+#[test]
+fn test_deposit_rejects_non_positive_amount() {
+    let (env, client) = setup();
+    let v1 = env.register_contract(None, VaultV1);
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        client.try_deposit(&v1, &user, &0, &symbol_short!("x"), &0),
+        Err(Ok(NegotiationError::InvalidAmount))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(WasmVm, InvalidAction)")]
+fn test_wrong_encoding_breaks_old_callee() {
+    // What happens without negotiation: a v2 struct sent to a v1 callee fails
+    // to decode on the callee side.
+    let env = Env::default();
+    let v1 = env.register_contract(None, VaultV1);
+    let req = DepositV2 {
+        from: Address::generate(&env),
+        amount: 1,
+        memo: symbol_short!("x"),
+        min_credit: 0,
+    };
+    env.invoke_contract::<i128>(
+        &v1,
+        &symbol_short!("deposit"),
+        Vec::from_array(&env, [req.into_val(&env)]),
+    );
+}
@@ -0,0 +1,288 @@
+//! # Kill Switch with Fund Recovery
+//!
+//! Contracts cannot be deleted, and simply "pausing" a contract that holds
+//! user funds traps those funds. This example shows a responsible
+//! decommissioning flow for a token vault:
+//!
+//! ```text
+//!  Active ──propose_shutdown──▶ WindingDown ──finalize_shutdown──▶ Terminated
+//!    ▲                              │  (after the window)              │
+//!    └──────cancel_shutdown─────────┘                             sweep_residual
+//! ```
+//!
+//! | Lifecycle     | deposit | withdraw | sweep_residual |
+//! |---------------|---------|----------|----------------|
+//! | `Active`      | ✅      | ✅       | ❌             |
+//! | `WindingDown` | ❌      | ✅       | ❌             |
+//! | `Terminated`  | ❌      | ❌       | ✅ (admin)     |
+//!
+//! The withdrawal-only window gives every depositor a guaranteed chance to
+//! exit before the terminal state. Once terminated, the contract rejects every
+//! mutation except sweeping the residual balance to a recovery address chosen
+//! by the admin (for example a treasury that honours late claims off-chain).
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+/// Shortest withdrawal window the admin may choose (1 day).
+pub const MIN_WINDOW: u64 = 86_400;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Lifecycle {
+    Active = 0,
+    WindingDown = 1,
+    Terminated = 2,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    Window,
+    Lifecycle,
+    /// Timestamp at which the withdrawal-only window closes.
+    WindowEnd,
+    Balance(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum KillSwitchError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    WindowTooShort = 5,
+    /// The action requires the `Active` lifecycle.
+    NotActive = 6,
+    /// The action requires the `WindingDown` lifecycle.
+    NotWindingDown = 7,
+    /// The withdrawal-only window has not closed yet.
+    WindowStillOpen = 8,
+    /// The contract is terminated and only accepts residual sweeps.
+    Terminated = 9,
+    /// Residual sweeps are only allowed once terminated.
+    NotTerminated = 10,
+}
+
+#[contract]
+pub struct KillSwitchVault;
+
+#[contractimpl]
+impl KillSwitchVault {
+    /// Initialize the vault.
+    ///
+    /// * `window` - Length of the withdrawal-only window in seconds (at least `MIN_WINDOW`)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        window: u64,
+    ) -> Result<(), KillSwitchError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(KillSwitchError::AlreadyInitialized);
+        }
+        if window < MIN_WINDOW {
+            return Err(KillSwitchError::WindowTooShort);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Window, &window);
+        env.storage()
+            .instance()
+            .set(&DataKey::Lifecycle, &Lifecycle::Active);
+        Ok(())
+    }
+
+    // ==================== USER FLOWS ====================
+
+    /// Deposit tokens. Only allowed while `Active`.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<i128, KillSwitchError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(KillSwitchError::InvalidAmount);
+        }
+        match Self::lifecycle(env.clone()) {
+            Lifecycle::Active => {}
+            Lifecycle::WindingDown => return Err(KillSwitchError::NotActive),
+            Lifecycle::Terminated => return Err(KillSwitchError::Terminated),
+        }
+
+        Self::token_client(&env)?.transfer(&user, &env.current_contract_address(), &amount);
+
+        let balance = Self::balance(env.clone(), user.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user), &balance);
+        Ok(balance)
+    }
+
+    /// Withdraw tokens. Allowed while `Active` and during the wind-down window.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<i128, KillSwitchError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(KillSwitchError::InvalidAmount);
+        }
+        if Self::lifecycle(env.clone()) == Lifecycle::Terminated {
+            return Err(KillSwitchError::Terminated);
+        }
+
+        let balance = Self::balance(env.clone(), user.clone());
+        if balance < amount {
+            return Err(KillSwitchError::InsufficientBalance);
+        }
+
+        // Effects before interaction
+        let remaining = balance - amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone()), &remaining);
+
+        Self::token_client(&env)?.transfer(&env.current_contract_address(), &user, &amount);
+        Ok(remaining)
+    }
+
+    // ==================== DECOMMISSIONING ====================
+
+    /// Start the shutdown: deposits stop, withdrawals keep working until the
+    /// window closes.
+    ///
+    /// Emits `(shutdown, proposed)` with the window end timestamp.
+    pub fn propose_shutdown(env: Env) -> Result<u64, KillSwitchError> {
+        Self::require_admin(&env)?;
+        if Self::lifecycle(env.clone()) != Lifecycle::Active {
+            return Err(KillSwitchError::NotActive);
+        }
+
+        let window: u64 = env.storage().instance().get(&DataKey::Window).unwrap();
+        let window_end = env.ledger().timestamp() + window;
+        env.storage()
+            .instance()
+            .set(&DataKey::Lifecycle, &Lifecycle::WindingDown);
+        env.storage()
+            .instance()
+            .set(&DataKey::WindowEnd, &window_end);
+
+        env.events().publish(
+            (symbol_short!("shutdown"), symbol_short!("proposed")),
+            window_end,
+        );
+        Ok(window_end)
+    }
+
+    /// Abort a pending shutdown and return to `Active`.
+    ///
+    /// Emits `(shutdown, cancelled)`.
+    pub fn cancel_shutdown(env: Env) -> Result<(), KillSwitchError> {
+        Self::require_admin(&env)?;
+        if Self::lifecycle(env.clone()) != Lifecycle::WindingDown {
+            return Err(KillSwitchError::NotWindingDown);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Lifecycle, &Lifecycle::Active);
+        env.storage().instance().remove(&DataKey::WindowEnd);
+
+        env.events()
+            .publish((symbol_short!("shutdown"), symbol_short!("cancelled")), ());
+        Ok(())
+    }
+
+    /// Enter the terminal state once the withdrawal window has closed.
+    ///
+    /// Anyone may call this: the window end is public and no discretion is
+    /// involved, so the shutdown cannot be stalled by an absent admin.
+    ///
+    /// Emits `(shutdown, final)`.
+    pub fn finalize_shutdown(env: Env) -> Result<(), KillSwitchError> {
+        if Self::lifecycle(env.clone()) != Lifecycle::WindingDown {
+            return Err(KillSwitchError::NotWindingDown);
+        }
+        let window_end: u64 = env.storage().instance().get(&DataKey::WindowEnd).unwrap();
+        if env.ledger().timestamp() < window_end {
+            return Err(KillSwitchError::WindowStillOpen);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Lifecycle, &Lifecycle::Terminated);
+
+        env.events()
+            .publish((symbol_short!("shutdown"), symbol_short!("final")), ());
+        Ok(())
+    }
+
+    /// Move whatever the contract still holds to `recovery`.
+    ///
+    /// This is the only mutation accepted after termination. It can be called
+    /// repeatedly, e.g. if someone sends tokens to the dead contract later.
+    ///
+    /// Emits `(sweep, recovery)` with the swept amount.
+    pub fn sweep_residual(env: Env, recovery: Address) -> Result<i128, KillSwitchError> {
+        Self::require_admin(&env)?;
+        if Self::lifecycle(env.clone()) != Lifecycle::Terminated {
+            return Err(KillSwitchError::NotTerminated);
+        }
+
+        let token = Self::token_client(&env)?;
+        let residual = token.balance(&env.current_contract_address());
+        if residual > 0 {
+            token.transfer(&env.current_contract_address(), &recovery, &residual);
+        }
+
+        env.events()
+            .publish((symbol_short!("sweep"), recovery), residual);
+        Ok(residual)
+    }
+
+    // ==================== QUERIES ====================
+
+    pub fn lifecycle(env: Env) -> Lifecycle {
+        env.storage()
+            .instance()
+            .get(&DataKey::Lifecycle)
+            .unwrap_or(Lifecycle::Active)
+    }
+
+    /// Timestamp at which the withdrawal window closes, if a shutdown is pending.
+    pub fn window_end(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::WindowEnd)
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn require_admin(env: &Env) -> Result<(), KillSwitchError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(KillSwitchError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn token_client(env: &Env) -> Result<token::Client<'_>, KillSwitchError> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(KillSwitchError::NotInitialized)?;
+        Ok(token::Client::new(env, &token))
+    }
+}
+
+mod test;
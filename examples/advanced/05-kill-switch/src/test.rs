@@ -0,0 +1,221 @@
+#![cfg(test)]
+
+use super::*;
+use cookbook_testing::assert_conserved;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const WINDOW: u64 = MIN_WINDOW;
+
+struct Setup {
+    env: Env,
+    client: KillSwitchVaultClient<'static>,
+    token: TokenClient<'static>,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let minter = StellarAssetClient::new(&env, &token_id);
+
+    let contract_id = env.register_contract(None, KillSwitchVault);
+    let client = KillSwitchVaultClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_id, &WINDOW);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    minter.mint(&alice, &1_000);
+    minter.mint(&bob, &1_000);
+
+    let token = TokenClient::new(&env, &token_id);
+    Setup {
+        env,
+        client,
+        token,
+        alice,
+        bob,
+    }
+}
+
+// ── normal operation ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_deposit_and_withdraw_while_active() {
+    let s = setup();
+    assert_eq!(s.client.deposit(&s.alice, &400), 400);
+    assert_eq!(s.client.withdraw(&s.alice, &150), 250);
+    assert_eq!(s.token.balance(&s.alice), 750);
+    assert_eq!(s.token.balance(&s.client.address), 250);
+}
+
+#[test]
+fn test_initialize_rejects_short_window() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, KillSwitchVault);
+    let client = KillSwitchVaultClient::new(&env, &contract_id);
+    let result = client.try_initialize(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &(MIN_WINDOW - 1),
+    );
+    assert_eq!(result, Err(Ok(KillSwitchError::WindowTooShort)));
+}
+
+#[test]
+fn test_withdraw_more_than_balance() {
+    let s = setup();
+    s.client.deposit(&s.alice, &100);
+    assert_eq!(
+        s.client.try_withdraw(&s.alice, &101),
+        Err(Ok(KillSwitchError::InsufficientBalance))
+    );
+}
+
+// ── wind-down window ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_propose_shutdown_blocks_deposits_but_not_withdrawals() {
+    let s = setup();
+    s.client.deposit(&s.alice, &400);
+
+    let window_end = s.client.propose_shutdown();
+    assert_eq!(window_end, s.env.ledger().timestamp() + WINDOW);
+    assert_eq!(s.client.lifecycle(), Lifecycle::WindingDown);
+
+    assert_eq!(
+        s.client.try_deposit(&s.bob, &100),
+        Err(Ok(KillSwitchError::NotActive))
+    );
+    assert_eq!(s.client.withdraw(&s.alice, &400), 0);
+    assert_eq!(s.token.balance(&s.alice), 1_000);
+}
+
+#[test]
+fn test_finalize_before_window_closes() {
+    let s = setup();
+    s.client.propose_shutdown();
+    s.env.ledger().with_mut(|l| l.timestamp += WINDOW - 1);
+    assert_eq!(
+        s.client.try_finalize_shutdown(),
+        Err(Ok(KillSwitchError::WindowStillOpen))
+    );
+}
+
+#[test]
+fn test_cancel_shutdown_restores_active() {
+    let s = setup();
+    s.client.propose_shutdown();
+    s.client.cancel_shutdown();
+
+    assert_eq!(s.client.lifecycle(), Lifecycle::Active);
+    assert_eq!(s.client.window_end(), None);
+    assert_eq!(s.client.deposit(&s.bob, &10), 10);
+}
+
+#[test]
+fn test_propose_twice_fails() {
+    let s = setup();
+    s.client.propose_shutdown();
+    assert_eq!(
+        s.client.try_propose_shutdown(),
+        Err(Ok(KillSwitchError::NotActive))
+    );
+}
+
+// ── terminal state ───────────────────────────────────────────────────────────
+
+fn terminate(s: &Setup) {
+    s.client.propose_shutdown();
+    s.env.ledger().with_mut(|l| l.timestamp += WINDOW);
+    s.client.finalize_shutdown();
+}
+
+#[test]
+fn test_terminated_rejects_user_mutations() {
+    let s = setup();
+    s.client.deposit(&s.alice, &300);
+    terminate(&s);
+
+    assert_eq!(s.client.lifecycle(), Lifecycle::Terminated);
+    assert_eq!(
+        s.client.try_deposit(&s.alice, &1),
+        Err(Ok(KillSwitchError::Terminated))
+    );
+    assert_eq!(
+        s.client.try_withdraw(&s.alice, &1),
+        Err(Ok(KillSwitchError::Terminated))
+    );
+    assert_eq!(
+        s.client.try_cancel_shutdown(),
+        Err(Ok(KillSwitchError::NotWindingDown))
+    );
+}
+
+#[test]
+fn test_full_decommission_recovers_all_funds() {
+    let s = setup();
+    let recovery = Address::generate(&s.env);
+
+    // Every token that enters the vault ends up with a user or recovery.
+    let swept = assert_conserved!(
+        s.env,
+        s.token.address,
+        [s.alice, s.bob, s.client.address, recovery],
+        {
+            s.client.deposit(&s.alice, &300);
+            s.client.deposit(&s.bob, &200);
+
+            // Alice exits during the window, Bob does not.
+            s.client.propose_shutdown();
+            s.client.withdraw(&s.alice, &300);
+            s.env.ledger().with_mut(|l| l.timestamp += WINDOW);
+            s.client.finalize_shutdown();
+
+            s.client.sweep_residual(&recovery)
+        }
+    );
+    assert_eq!(swept, 200);
+    assert_eq!(s.token.balance(&recovery), 200);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    // Tokens sent to the dead contract later can still be recovered.
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.client.address, &5);
+    assert_eq!(s.client.sweep_residual(&recovery), 5);
+    assert_eq!(s.token.balance(&recovery), 205);
+}
+
+#[test]
+fn test_sweep_requires_termination() {
+    let s = setup();
+    s.client.deposit(&s.alice, &300);
+    let recovery = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client.try_sweep_residual(&recovery),
+        Err(Ok(KillSwitchError::NotTerminated))
+    );
+    s.client.propose_shutdown();
+    assert_eq!(
+        s.client.try_sweep_residual(&recovery),
+        Err(Ok(KillSwitchError::NotTerminated))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_propose_shutdown_requires_admin() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.client.propose_shutdown();
+}
@@ -0,0 +1,211 @@
+//! # Tombstone and Redirect
+//!
+//! Soroban has no `selfdestruct`: a deployed contract and its address live
+//! forever. When a contract is replaced by a new deployment, the old address
+//! keeps receiving calls from integrators that have not migrated yet.
+//!
+//! Instead of silently continuing to operate (and splitting state between two
+//! deployments) the old contract is turned into a *tombstone*:
+//!
+//! ```text
+//!                 deprecate(successor)
+//!   Live ─────────────────────────────────▶ MovedTo(successor)
+//!
+//!   business calls (credit, transfer) ──▶ Err(LedgerError::MovedTo)
+//!   read-only queries (balance, ...)  ──▶ forwarded to the successor
+//!   status()                          ──▶ Status::MovedTo(successor)
+//! ```
+//!
+//! Contract errors are plain `u32` codes, so the successor address cannot
+//! travel inside the error itself. Integrators that receive `MovedTo` read the
+//! address from `status()` (or from the `tombstone` event) and switch over.
+//! Read-only queries keep working through the old address, so dashboards and
+//! off-chain readers do not break on the day of the migration.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The contract is the current deployment and accepts all calls.
+    Live,
+    /// The contract is retired; the successor holds the canonical state.
+    MovedTo(Address),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Set once by `deprecate`; its presence marks the contract as a tombstone.
+    Successor,
+    TotalSupply,
+    Balance(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LedgerError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    /// The contract has been deprecated; call `status()` for the successor.
+    MovedTo = 5,
+    /// `deprecate` can only be called once.
+    AlreadyDeprecated = 6,
+    /// A contract cannot name itself as its successor.
+    InvalidSuccessor = 7,
+}
+
+/// A minimal points ledger that can be retired in favour of a new deployment.
+#[contract]
+pub struct PointsLedger;
+
+#[contractimpl]
+impl PointsLedger {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), LedgerError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(LedgerError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    // ==================== BUSINESS CALLS ====================
+
+    /// Mint points to `to`. Admin only.
+    pub fn credit(env: Env, to: Address, amount: i128) -> Result<i128, LedgerError> {
+        Self::require_live(&env)?;
+        Self::require_admin(&env)?;
+        if amount <= 0 {
+            return Err(LedgerError::InvalidAmount);
+        }
+
+        let balance = Self::read_balance(&env, &to) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to), &balance);
+        let supply = Self::read_total_supply(&env) + amount;
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+        Ok(balance)
+    }
+
+    /// Move points between users.
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), LedgerError> {
+        Self::require_live(&env)?;
+        from.require_auth();
+        if amount <= 0 {
+            return Err(LedgerError::InvalidAmount);
+        }
+
+        let from_balance = Self::read_balance(&env, &from);
+        if from_balance < amount {
+            return Err(LedgerError::InsufficientBalance);
+        }
+        let to_balance = Self::read_balance(&env, &to);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(from), &(from_balance - amount));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to), &(to_balance + amount));
+        Ok(())
+    }
+
+    // ==================== TOMBSTONE ====================
+
+    /// Retire this deployment in favour of `successor`. One-way and admin only.
+    ///
+    /// State is not copied: migrate balances to the successor *before*
+    /// deprecating, because from this point on every query is answered by the
+    /// successor.
+    ///
+    /// Emits `(tombstone,)` with the successor address.
+    pub fn deprecate(env: Env, successor: Address) -> Result<(), LedgerError> {
+        Self::require_admin(&env)?;
+        if env.storage().instance().has(&DataKey::Successor) {
+            return Err(LedgerError::AlreadyDeprecated);
+        }
+        if successor == env.current_contract_address() {
+            return Err(LedgerError::InvalidSuccessor);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Successor, &successor);
+        env.events()
+            .publish((symbol_short!("tombstone"),), successor);
+        Ok(())
+    }
+
+    /// Report whether this deployment is live or where it moved to.
+    pub fn status(env: Env) -> Status {
+        match Self::successor(&env) {
+            Some(successor) => Status::MovedTo(successor),
+            None => Status::Live,
+        }
+    }
+
+    // ==================== READ-ONLY QUERIES ====================
+
+    /// Balance of `user`, answered by the successor once deprecated.
+    pub fn balance(env: Env, user: Address) -> i128 {
+        match Self::successor(&env) {
+            Some(successor) => PointsLedgerClient::new(&env, &successor).balance(&user),
+            None => Self::read_balance(&env, &user),
+        }
+    }
+
+    /// Total points issued, answered by the successor once deprecated.
+    pub fn total_supply(env: Env) -> i128 {
+        match Self::successor(&env) {
+            Some(successor) => PointsLedgerClient::new(&env, &successor).total_supply(),
+            None => Self::read_total_supply(&env),
+        }
+    }
+
+    // ==================== HELPERS ====================
+
+    fn successor(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Successor)
+    }
+
+    fn require_live(env: &Env) -> Result<(), LedgerError> {
+        if Self::successor(env).is_some() {
+            return Err(LedgerError::MovedTo);
+        }
+        Ok(())
+    }
+
+    fn require_admin(env: &Env) -> Result<(), LedgerError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(LedgerError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn read_balance(env: &Env, user: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user.clone()))
+            .unwrap_or(0)
+    }
+
+    fn read_total_supply(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+}
+
+mod test;
@@ -0,0 +1,148 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn deploy(env: &Env, admin: &Address) -> PointsLedgerClient<'static> {
+    let contract_id = env.register_contract(None, PointsLedger);
+    let client = PointsLedgerClient::new(env, &contract_id);
+    client.initialize(admin);
+    client
+}
+
+/// Old deployment with balances, a successor holding the migrated balances,
+/// and the old deployment already deprecated.
+fn setup_migrated() -> (
+    Env,
+    PointsLedgerClient<'static>,
+    PointsLedgerClient<'static>,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    let old = deploy(&env, &admin);
+    old.credit(&alice, &100);
+
+    let new = deploy(&env, &admin);
+    new.credit(&alice, &old.balance(&alice));
+    old.deprecate(&new.address);
+
+    (env, old, new, admin, alice)
+}
+
+#[test]
+fn test_live_contract_operates_normally() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let ledger = deploy(&env, &admin);
+    ledger.credit(&alice, &100);
+    ledger.transfer(&alice, &bob, &40);
+
+    assert_eq!(ledger.status(), Status::Live);
+    assert_eq!(ledger.balance(&alice), 60);
+    assert_eq!(ledger.balance(&bob), 40);
+    assert_eq!(ledger.total_supply(), 100);
+}
+
+#[test]
+fn test_status_reports_successor() {
+    let (_env, old, new, _admin, _alice) = setup_migrated();
+    assert_eq!(old.status(), Status::MovedTo(new.address.clone()));
+    assert_eq!(new.status(), Status::Live);
+}
+
+#[test]
+fn test_business_calls_rejected_with_moved_to() {
+    let (env, old, _new, _admin, alice) = setup_migrated();
+    let bob = Address::generate(&env);
+
+    assert_eq!(old.try_credit(&alice, &10), Err(Ok(LedgerError::MovedTo)));
+    assert_eq!(
+        old.try_transfer(&alice, &bob, &10),
+        Err(Ok(LedgerError::MovedTo))
+    );
+}
+
+#[test]
+fn test_queries_forwarded_to_successor() {
+    let (env, old, new, _admin, alice) = setup_migrated();
+    let bob = Address::generate(&env);
+
+    // Activity on the successor is visible through the old address.
+    new.transfer(&alice, &bob, &30);
+    assert_eq!(old.balance(&alice), 70);
+    assert_eq!(old.balance(&bob), 30);
+    assert_eq!(old.total_supply(), new.total_supply());
+}
+
+#[test]
+fn test_integrator_follows_redirect() {
+    let (env, old, _new, _admin, alice) = setup_migrated();
+    let bob = Address::generate(&env);
+
+    // An integrator that still points at the old address discovers the
+    // successor from the error and retries there.
+    let result = old.try_transfer(&alice, &bob, &25);
+    assert_eq!(result, Err(Ok(LedgerError::MovedTo)));
+
+    let Status::MovedTo(successor) = old.status() else {
+        panic!("expected a tombstone");
+    };
+    PointsLedgerClient::new(&env, &successor).transfer(&alice, &bob, &25);
+    assert_eq!(old.balance(&bob), 25);
+}
+
+#[test]
+fn test_deprecate_is_one_way() {
+    let (env, old, _new, admin, _alice) = setup_migrated();
+    let other = deploy(&env, &admin);
+    assert_eq!(
+        old.try_deprecate(&other.address),
+        Err(Ok(LedgerError::AlreadyDeprecated))
+    );
+}
+
+#[test]
+fn test_cannot_redirect_to_self() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let ledger = deploy(&env, &Address::generate(&env));
+    assert_eq!(
+        ledger.try_deprecate(&ledger.address),
+        Err(Ok(LedgerError::InvalidSuccessor))
+    );
+}
+
+#[test]
+fn test_redirects_chain_through_multiple_generations() {
+    let (env, v1, v2, admin, alice) = setup_migrated();
+
+    let v3 = deploy(&env, &admin);
+    v3.credit(&alice, &v2.balance(&alice));
+    v2.deprecate(&v3.address);
+    v3.credit(&alice, &5);
+
+    assert_eq!(v1.balance(&alice), 105);
+    assert_eq!(v2.status(), Status::MovedTo(v3.address.clone()));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_deprecate_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let old = deploy(&env, &admin);
+    let new = deploy(&env, &admin);
+
+    env.set_auths(&[]);
+    old.deprecate(&new.address);
+}
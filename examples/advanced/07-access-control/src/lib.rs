@@ -0,0 +1,81 @@
+//! # Access Control
+//!
+//! `ManagedConfig` is a minimal contract wired to the shared
+//! `cookbook-access` modules, imported through `cookbook-prelude`; it is what
+//! the tests exercise:
+//!
+//! - [`ownable`] – single owner, ownership transfer, and delayed two-step
+//!   renouncement
+//!
+//! The modules live in their own crate because a contract crate cannot be
+//! reused as a library: its `#[contractimpl]` functions would be exported by
+//! every dependent and clash with the dependent's own `initialize`.
+
+#![no_std]
+
+use cookbook_prelude::prelude::*;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+pub use cookbook_prelude::access::OwnableError;
+
+typed_config! {
+    /// Owner-tunable parameters of `ManagedConfig`.
+    pub struct Config {
+        /// Fee in basis points.
+        fee_bps: u32 = 0,
+    }
+}
+
+#[contract]
+pub struct ManagedConfig;
+
+#[contractimpl]
+impl ManagedConfig {
+    pub fn initialize(env: Env, owner: Address) -> Result<(), OwnableError> {
+        ownable::init_owner(&env, &owner)
+    }
+
+    // ==================== OWNER-GATED ====================
+
+    pub fn set_fee_bps(env: Env, fee_bps: u32) -> Result<(), OwnableError> {
+        ownable::require_owner(&env)?;
+        Config::set_fee_bps(&env, fee_bps);
+        Ok(())
+    }
+
+    pub fn transfer_ownership(env: Env, new_owner: Address) -> Result<(), OwnableError> {
+        ownable::transfer_ownership(&env, &new_owner)
+    }
+
+    pub fn begin_renounce(env: Env) -> Result<u64, OwnableError> {
+        ownable::begin_renounce(&env)
+    }
+
+    pub fn cancel_renounce(env: Env) -> Result<(), OwnableError> {
+        ownable::cancel_renounce(&env)
+    }
+
+    pub fn confirm_renounce(env: Env) -> Result<(), OwnableError> {
+        ownable::confirm_renounce(&env)
+    }
+
+    // ==================== QUERIES ====================
+
+    pub fn fee_bps(env: Env) -> u32 {
+        Config::fee_bps(&env)
+    }
+
+    pub fn owner(env: Env) -> Option<Address> {
+        ownable::owner(&env)
+    }
+
+    pub fn is_renounced(env: Env) -> bool {
+        ownable::is_renounced(&env)
+    }
+
+    pub fn pending_renounce(env: Env) -> Option<u64> {
+        ownable::pending_renounce(&env)
+    }
+}
+
+mod test;
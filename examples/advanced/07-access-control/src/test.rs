@@ -0,0 +1,183 @@
+#![cfg(test)]
+
+use super::*;
+use ownable::RENOUNCE_DELAY;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, Symbol, TryFromVal,
+};
+
+fn setup() -> (Env, ManagedConfigClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ManagedConfig);
+    let client = ManagedConfigClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    client.initialize(&owner);
+    (env, client, owner)
+}
+
+/// Second topic of the most recent event, e.g. `ren_start`.
+fn last_event_action(env: &Env) -> Symbol {
+    let (_id, topics, _data) = env.events().all().last().unwrap();
+    Symbol::try_from_val(env, &topics.get(1).unwrap()).unwrap()
+}
+
+fn renounce(env: &Env, client: &ManagedConfigClient) {
+    client.begin_renounce();
+    env.ledger().with_mut(|l| l.timestamp += RENOUNCE_DELAY);
+    client.confirm_renounce();
+}
+
+#[test]
+fn test_owner_can_call_gated_functions() {
+    let (_env, client, owner) = setup();
+    client.set_fee_bps(&30);
+    assert_eq!(client.fee_bps(), 30);
+    assert_eq!(client.owner(), Some(owner));
+}
+
+#[test]
+fn test_transfer_ownership() {
+    let (env, client, _owner) = setup();
+    let new_owner = Address::generate(&env);
+    client.transfer_ownership(&new_owner);
+    assert_eq!(client.owner(), Some(new_owner));
+}
+
+// ── renouncement flow ────────────────────────────────────────────────────────
+
+#[test]
+fn test_renounce_requires_delay() {
+    let (env, client, _owner) = setup();
+
+    let renounce_at = client.begin_renounce();
+    assert_eq!(renounce_at, env.ledger().timestamp() + RENOUNCE_DELAY);
+    assert_eq!(client.pending_renounce(), Some(renounce_at));
+
+    env.ledger().with_mut(|l| l.timestamp += RENOUNCE_DELAY - 1);
+    assert_eq!(
+        client.try_confirm_renounce(),
+        Err(Ok(OwnableError::RenounceDelayNotElapsed))
+    );
+    assert!(!client.is_renounced());
+}
+
+#[test]
+fn test_confirm_without_begin_fails() {
+    let (_env, client, _owner) = setup();
+    assert_eq!(
+        client.try_confirm_renounce(),
+        Err(Ok(OwnableError::NoPendingRenounce))
+    );
+}
+
+#[test]
+fn test_begin_twice_fails() {
+    let (_env, client, _owner) = setup();
+    client.begin_renounce();
+    assert_eq!(
+        client.try_begin_renounce(),
+        Err(Ok(OwnableError::RenouncePending))
+    );
+}
+
+#[test]
+fn test_cancel_renounce_keeps_owner() {
+    let (env, client, owner) = setup();
+    client.begin_renounce();
+    client.cancel_renounce();
+
+    assert_eq!(client.pending_renounce(), None);
+    env.ledger().with_mut(|l| l.timestamp += RENOUNCE_DELAY);
+    assert_eq!(
+        client.try_confirm_renounce(),
+        Err(Ok(OwnableError::NoPendingRenounce))
+    );
+    assert_eq!(client.owner(), Some(owner));
+}
+
+#[test]
+fn test_transfer_blocked_while_renounce_pending() {
+    let (env, client, _owner) = setup();
+    client.begin_renounce();
+    assert_eq!(
+        client.try_transfer_ownership(&Address::generate(&env)),
+        Err(Ok(OwnableError::RenouncePending))
+    );
+}
+
+#[test]
+fn test_renounce_event_trail() {
+    let (env, client, _owner) = setup();
+
+    client.begin_renounce();
+    assert_eq!(last_event_action(&env), symbol_short!("ren_start"));
+    client.cancel_renounce();
+    assert_eq!(last_event_action(&env), symbol_short!("ren_cncl"));
+    client.begin_renounce();
+    env.ledger().with_mut(|l| l.timestamp += RENOUNCE_DELAY);
+    client.confirm_renounce();
+    assert_eq!(last_event_action(&env), symbol_short!("renounced"));
+}
+
+// ── renounced contracts ──────────────────────────────────────────────────────
+
+#[test]
+fn test_renounced_contract_rejects_every_owner_call() {
+    let (env, client, _owner) = setup();
+    client.set_fee_bps(&30);
+    renounce(&env, &client);
+
+    assert!(client.is_renounced());
+    assert_eq!(client.owner(), None);
+
+    let renounced = Err(Ok(OwnableError::Renounced));
+    assert_eq!(client.try_set_fee_bps(&0), renounced);
+    assert_eq!(
+        client.try_transfer_ownership(&Address::generate(&env)),
+        renounced
+    );
+    assert_eq!(
+        client.try_begin_renounce(),
+        Err(Ok(OwnableError::Renounced))
+    );
+    assert_eq!(client.try_cancel_renounce(), renounced);
+    assert_eq!(client.try_confirm_renounce(), renounced);
+
+    // Configuration is frozen at its last value.
+    assert_eq!(client.fee_bps(), 30);
+}
+
+#[test]
+fn test_renounced_contract_cannot_be_reinitialized() {
+    let (env, client, _owner) = setup();
+    renounce(&env, &client);
+
+    let attacker = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&attacker),
+        Err(Ok(OwnableError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_renouncement_is_permanent() {
+    let (env, client, _owner) = setup();
+    renounce(&env, &client);
+
+    // Long after the fact, nothing has changed.
+    env.ledger()
+        .with_mut(|l| l.timestamp += 10 * RENOUNCE_DELAY);
+    assert_eq!(client.try_set_fee_bps(&1), Err(Ok(OwnableError::Renounced)));
+    assert!(client.is_renounced());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_begin_renounce_requires_owner_auth() {
+    let (env, client, _owner) = setup();
+    env.set_auths(&[]);
+    client.begin_renounce();
+}
@@ -0,0 +1,323 @@
+//! # Role-Based Dispatch Table
+//!
+//! Most contracts hard-code authorization next to each function:
+//! `require_role(Operator)` at the top of `credit`, `require_role(Admin)` at
+//! the top of `set_fee`, and so on. Changing who may call what then requires
+//! a code upgrade.
+//!
+//! This example moves that decision into data. A single entry point,
+//!
+//! ```text
+//! dispatch(caller, op: Symbol, args: Vec<Val>) -> Val
+//! ```
+//!
+//! looks up `op` in a stored **op → required role** table, checks that the
+//! caller holds that role, decodes `args`, and routes to an internal handler.
+//!
+//! | op        | args                  | default role |
+//! |-----------|-----------------------|--------------|
+//! | `credit`  | `(to, amount)`        | `operator`   |
+//! | `debit`   | `(from, amount)`      | `operator`   |
+//! | `set_fee` | `(fee_bps)`           | `admin`      |
+//! | `pause`   | `()`                  | `guardian`   |
+//! | `unpause` | `()`                  | `admin`      |
+//!
+//! The admin edits the table with `set_op_role` / `remove_op` — requiring a
+//! different role, opening an op to everyone with the `public` role, or
+//! disabling an op entirely — without redeploying. Only the *set of handlers*
+//! is fixed in code; `set_op_role` refuses ops that have no handler so the
+//! table never contains dead entries.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, IntoVal,
+    Symbol, TryFromVal, Val, Vec,
+};
+
+/// Role name that every authenticated caller implicitly holds.
+pub const PUBLIC_ROLE: Symbol = symbol_short!("public");
+
+pub const OP_CREDIT: Symbol = symbol_short!("credit");
+pub const OP_DEBIT: Symbol = symbol_short!("debit");
+pub const OP_SET_FEE: Symbol = symbol_short!("set_fee");
+pub const OP_PAUSE: Symbol = symbol_short!("pause");
+pub const OP_UNPAUSE: Symbol = symbol_short!("unpause");
+
+/// Maximum fee accepted by the `set_fee` handler (100%).
+pub const MAX_FEE_BPS: u32 = 10_000;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Role required to run an op.
+    OpRole(Symbol),
+    /// Whether an account holds a role.
+    HasRole(Address, Symbol),
+    Paused,
+    FeeBps,
+    Balance(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DispatchError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The op is not in the dispatch table (never added or removed).
+    UnknownOp = 3,
+    /// The caller does not hold the role the table requires for this op.
+    MissingRole = 4,
+    /// `args` has the wrong length or types for this op.
+    InvalidArgs = 5,
+    /// The contract is paused; only `unpause` is routed.
+    Paused = 6,
+    InsufficientBalance = 7,
+    /// `set_op_role` was given an op that has no handler in code.
+    NoHandler = 8,
+    InvalidAmount = 9,
+}
+
+#[contract]
+pub struct DispatchContract;
+
+#[contractimpl]
+impl DispatchContract {
+    /// Initialize with the default table shown in the module docs. The admin
+    /// is granted the `admin` role.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), DispatchError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(DispatchError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        let operator = symbol_short!("operator");
+        let admin_role = symbol_short!("admin");
+        let guardian = symbol_short!("guardian");
+        for (op, role) in [
+            (OP_CREDIT, operator.clone()),
+            (OP_DEBIT, operator),
+            (OP_SET_FEE, admin_role.clone()),
+            (OP_PAUSE, guardian),
+            (OP_UNPAUSE, admin_role.clone()),
+        ] {
+            env.storage().persistent().set(&DataKey::OpRole(op), &role);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::HasRole(admin, admin_role), &true);
+        Ok(())
+    }
+
+    // ==================== DISPATCH ====================
+
+    /// Authorize `caller` for `op` using the stored table and run its handler.
+    pub fn dispatch(
+        env: Env,
+        caller: Address,
+        op: Symbol,
+        args: Vec<Val>,
+    ) -> Result<Val, DispatchError> {
+        caller.require_auth();
+
+        let required: Symbol = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpRole(op.clone()))
+            .ok_or(DispatchError::UnknownOp)?;
+        if !Self::has_role(env.clone(), caller, required) {
+            return Err(DispatchError::MissingRole);
+        }
+
+        if op == OP_UNPAUSE {
+            return Self::handle_set_paused(&env, &args, false);
+        }
+        if Self::is_paused(env.clone()) {
+            return Err(DispatchError::Paused);
+        }
+
+        if op == OP_CREDIT {
+            Self::handle_credit(&env, &args)
+        } else if op == OP_DEBIT {
+            Self::handle_debit(&env, &args)
+        } else if op == OP_SET_FEE {
+            Self::handle_set_fee(&env, &args)
+        } else if op == OP_PAUSE {
+            Self::handle_set_paused(&env, &args, true)
+        } else {
+            // Unreachable while `set_op_role` only accepts handled ops.
+            Err(DispatchError::NoHandler)
+        }
+    }
+
+    // ==================== TABLE MANAGEMENT ====================
+
+    /// Require `role` for `op`, adding the op to the table if needed.
+    ///
+    /// Emits `(table, op)` with the new role.
+    pub fn set_op_role(env: Env, op: Symbol, role: Symbol) -> Result<(), DispatchError> {
+        Self::require_admin(&env)?;
+        if !Self::has_handler(&op) {
+            return Err(DispatchError::NoHandler);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpRole(op.clone()), &role);
+        env.events().publish((symbol_short!("table"), op), role);
+        Ok(())
+    }
+
+    /// Disable `op`: `dispatch` rejects it with `UnknownOp` until re-added.
+    pub fn remove_op(env: Env, op: Symbol) -> Result<(), DispatchError> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::OpRole(op.clone()));
+        env.events()
+            .publish((symbol_short!("table"), op), symbol_short!("removed"));
+        Ok(())
+    }
+
+    pub fn op_role(env: Env, op: Symbol) -> Option<Symbol> {
+        env.storage().persistent().get(&DataKey::OpRole(op))
+    }
+
+    // ==================== ROLE MANAGEMENT ====================
+
+    pub fn grant_role(env: Env, account: Address, role: Symbol) -> Result<(), DispatchError> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::HasRole(account, role), &true);
+        Ok(())
+    }
+
+    pub fn revoke_role(env: Env, account: Address, role: Symbol) -> Result<(), DispatchError> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::HasRole(account, role));
+        Ok(())
+    }
+
+    pub fn has_role(env: Env, account: Address, role: Symbol) -> bool {
+        role == PUBLIC_ROLE
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::HasRole(account, role))
+    }
+
+    // ==================== QUERIES ====================
+
+    pub fn balance(env: Env, account: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(account))
+            .unwrap_or(0)
+    }
+
+    pub fn fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    // ==================== HANDLERS ====================
+
+    fn has_handler(op: &Symbol) -> bool {
+        [OP_CREDIT, OP_DEBIT, OP_SET_FEE, OP_PAUSE, OP_UNPAUSE].contains(op)
+    }
+
+    /// `credit(to: Address, amount: i128) -> i128`
+    fn handle_credit(env: &Env, args: &Vec<Val>) -> Result<Val, DispatchError> {
+        Self::expect_len(args, 2)?;
+        let to: Address = Self::arg(env, args, 0)?;
+        let amount: i128 = Self::arg(env, args, 1)?;
+        if amount <= 0 {
+            return Err(DispatchError::InvalidAmount);
+        }
+
+        let balance = Self::balance(env.clone(), to.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to), &balance);
+        Ok(balance.into_val(env))
+    }
+
+    /// `debit(from: Address, amount: i128) -> i128`
+    fn handle_debit(env: &Env, args: &Vec<Val>) -> Result<Val, DispatchError> {
+        Self::expect_len(args, 2)?;
+        let from: Address = Self::arg(env, args, 0)?;
+        let amount: i128 = Self::arg(env, args, 1)?;
+        if amount <= 0 {
+            return Err(DispatchError::InvalidAmount);
+        }
+
+        let balance = Self::balance(env.clone(), from.clone());
+        if balance < amount {
+            return Err(DispatchError::InsufficientBalance);
+        }
+        let remaining = balance - amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(from), &remaining);
+        Ok(remaining.into_val(env))
+    }
+
+    /// `set_fee(fee_bps: u32) -> ()`
+    fn handle_set_fee(env: &Env, args: &Vec<Val>) -> Result<Val, DispatchError> {
+        Self::expect_len(args, 1)?;
+        let fee_bps: u32 = Self::arg(env, args, 0)?;
+        if fee_bps > MAX_FEE_BPS {
+            return Err(DispatchError::InvalidArgs);
+        }
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(().into_val(env))
+    }
+
+    /// `pause() -> ()` / `unpause() -> ()`
+    fn handle_set_paused(env: &Env, args: &Vec<Val>, paused: bool) -> Result<Val, DispatchError> {
+        Self::expect_len(args, 0)?;
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        Ok(().into_val(env))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn expect_len(args: &Vec<Val>, len: u32) -> Result<(), DispatchError> {
+        if args.len() != len {
+            return Err(DispatchError::InvalidArgs);
+        }
+        Ok(())
+    }
+
+    /// Decode argument `index`, mapping type mismatches to `InvalidArgs`.
+    fn arg<T: TryFromVal<Env, Val>>(
+        env: &Env,
+        args: &Vec<Val>,
+        index: u32,
+    ) -> Result<T, DispatchError> {
+        let val = args.get(index).ok_or(DispatchError::InvalidArgs)?;
+        T::try_from_val(env, &val).map_err(|_| DispatchError::InvalidArgs)
+    }
+
+    fn require_admin(env: &Env) -> Result<(), DispatchError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(DispatchError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+mod test;
@@ -0,0 +1,241 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+struct Setup {
+    env: Env,
+    client: DispatchContractClient<'static>,
+    admin: Address,
+    operator: Address,
+    guardian: Address,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, DispatchContract);
+    let client = DispatchContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin);
+    client.grant_role(&operator, &symbol_short!("operator"));
+    client.grant_role(&guardian, &symbol_short!("guardian"));
+
+    Setup {
+        env,
+        client,
+        admin,
+        operator,
+        guardian,
+        user,
+    }
+}
+
+/// The contract error returned by `dispatch`, if any. (`Val` results are not
+/// comparable, so tests match on the error alone.)
+fn dispatch_err(
+    s: &Setup,
+    caller: &Address,
+    op: &Symbol,
+    args: &Vec<Val>,
+) -> Option<DispatchError> {
+    match s.client.try_dispatch(caller, op, args) {
+        Err(Ok(err)) => Some(err),
+        _ => None,
+    }
+}
+
+fn credit_args(env: &Env, to: &Address, amount: i128) -> Vec<Val> {
+    vec![env, to.into_val(env), amount.into_val(env)]
+}
+
+// ── routing ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_dispatch_routes_to_handlers() {
+    let s = setup();
+    let args = credit_args(&s.env, &s.user, 100);
+    let balance: i128 = s
+        .client
+        .dispatch(&s.operator, &OP_CREDIT, &args)
+        .into_val(&s.env);
+    assert_eq!(balance, 100);
+
+    let args = credit_args(&s.env, &s.user, 40);
+    s.client.dispatch(&s.operator, &OP_DEBIT, &args);
+    assert_eq!(s.client.balance(&s.user), 60);
+
+    let args = vec![&s.env, 25u32.into_val(&s.env)];
+    s.client.dispatch(&s.admin, &OP_SET_FEE, &args);
+    assert_eq!(s.client.fee_bps(), 25);
+}
+
+#[test]
+fn test_unknown_op_rejected() {
+    let s = setup();
+    assert_eq!(
+        dispatch_err(&s, &s.admin, &symbol_short!("mint"), &Vec::new(&s.env)),
+        Some(DispatchError::UnknownOp)
+    );
+}
+
+#[test]
+fn test_invalid_args_rejected() {
+    let s = setup();
+    // Wrong arity.
+    let args = vec![&s.env, s.user.into_val(&s.env)];
+    assert_eq!(
+        dispatch_err(&s, &s.operator, &OP_CREDIT, &args),
+        Some(DispatchError::InvalidArgs)
+    );
+    // Wrong types: amount passed as a Symbol.
+    let args = vec![
+        &s.env,
+        s.user.into_val(&s.env),
+        symbol_short!("ten").into_val(&s.env),
+    ];
+    assert_eq!(
+        dispatch_err(&s, &s.operator, &OP_CREDIT, &args),
+        Some(DispatchError::InvalidArgs)
+    );
+}
+
+// ── authorization from the table ─────────────────────────────────────────────
+
+#[test]
+fn test_default_table_authorization_matrix() {
+    let s = setup();
+    let credit = credit_args(&s.env, &s.user, 1);
+    let fee = vec![&s.env, 1u32.into_val(&s.env)];
+    let none: Vec<Val> = Vec::new(&s.env);
+
+    let cases = [
+        (&s.operator, OP_CREDIT, &credit, true),
+        (&s.guardian, OP_CREDIT, &credit, false),
+        (&s.user, OP_CREDIT, &credit, false),
+        (&s.admin, OP_SET_FEE, &fee, true),
+        (&s.operator, OP_SET_FEE, &fee, false),
+        (&s.user, OP_PAUSE, &none, false),
+        (&s.operator, OP_PAUSE, &none, false),
+        (&s.guardian, OP_UNPAUSE, &none, false),
+    ];
+    for (caller, op, args, allowed) in cases {
+        let result = s.client.try_dispatch(caller, &op, args);
+        if allowed {
+            assert!(result.is_ok(), "{op:?} should be allowed");
+        } else {
+            assert!(
+                matches!(result, Err(Ok(DispatchError::MissingRole))),
+                "{op:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pause_blocks_everything_but_unpause() {
+    let s = setup();
+    let none: Vec<Val> = Vec::new(&s.env);
+    s.client.dispatch(&s.guardian, &OP_PAUSE, &none);
+    assert!(s.client.is_paused());
+
+    let args = credit_args(&s.env, &s.user, 1);
+    assert_eq!(
+        dispatch_err(&s, &s.operator, &OP_CREDIT, &args),
+        Some(DispatchError::Paused)
+    );
+
+    s.client.dispatch(&s.admin, &OP_UNPAUSE, &none);
+    s.client.dispatch(&s.operator, &OP_CREDIT, &args);
+}
+
+// ── updating the table without code changes ──────────────────────────────────
+
+#[test]
+fn test_reassign_op_to_new_role() {
+    let s = setup();
+    let treasurer = Address::generate(&s.env);
+    let role = symbol_short!("treasury");
+    s.client.grant_role(&treasurer, &role);
+
+    s.client.set_op_role(&OP_DEBIT, &role);
+    assert_eq!(s.client.op_role(&OP_DEBIT), Some(role));
+
+    s.client
+        .dispatch(&s.operator, &OP_CREDIT, &credit_args(&s.env, &s.user, 50));
+    let debit = credit_args(&s.env, &s.user, 10);
+    assert_eq!(
+        dispatch_err(&s, &s.operator, &OP_DEBIT, &debit),
+        Some(DispatchError::MissingRole)
+    );
+    s.client.dispatch(&treasurer, &OP_DEBIT, &debit);
+    assert_eq!(s.client.balance(&s.user), 40);
+}
+
+#[test]
+fn test_open_op_to_public() {
+    let s = setup();
+    s.client.set_op_role(&OP_PAUSE, &PUBLIC_ROLE);
+    s.client.dispatch(&s.user, &OP_PAUSE, &Vec::new(&s.env));
+    assert!(s.client.is_paused());
+}
+
+#[test]
+fn test_remove_op_disables_it() {
+    let s = setup();
+    s.client.remove_op(&OP_CREDIT);
+    assert_eq!(s.client.op_role(&OP_CREDIT), None);
+    assert_eq!(
+        dispatch_err(
+            &s,
+            &s.operator,
+            &OP_CREDIT,
+            &credit_args(&s.env, &s.user, 1)
+        ),
+        Some(DispatchError::UnknownOp)
+    );
+
+    // Re-adding restores it.
+    s.client.set_op_role(&OP_CREDIT, &symbol_short!("operator"));
+    s.client
+        .dispatch(&s.operator, &OP_CREDIT, &credit_args(&s.env, &s.user, 1));
+}
+
+#[test]
+fn test_table_rejects_ops_without_handler() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_set_op_role(&symbol_short!("mint"), &symbol_short!("admin")),
+        Err(Ok(DispatchError::NoHandler))
+    );
+}
+
+#[test]
+fn test_revoked_role_loses_access() {
+    let s = setup();
+    s.client
+        .revoke_role(&s.operator, &symbol_short!("operator"));
+    assert_eq!(
+        dispatch_err(
+            &s,
+            &s.operator,
+            &OP_CREDIT,
+            &credit_args(&s.env, &s.user, 1)
+        ),
+        Some(DispatchError::MissingRole)
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_table_update_requires_admin() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.client.set_op_role(&OP_DEBIT, &PUBLIC_ROLE);
+}
@@ -0,0 +1,300 @@
+//! # Capability Tokens
+//!
+//! Some authorization checks are expensive: scanning a membership list,
+//! loading per-member permissions, checking suspension flags. Paying that cost
+//! on every call is wasteful when the same account performs many operations
+//! in a short period.
+//!
+//! A *capability* is a short-lived receipt that the expensive check already
+//! passed:
+//!
+//! ```text
+//! issue_capability(holder, scope)      // heavyweight check, once
+//!     └─▶ temporary storage: Cap(id) = { holder, scope, expires_at }
+//!
+//! record_usage(holder, id, units)       // cheap check, many times
+//!     └─▶ one temporary read + holder.require_auth()
+//! ```
+//!
+//! Capabilities live in **temporary storage**: they are cheap to write, and
+//! the network deletes them on its own once their TTL runs out, so nothing has
+//! to clean them up. The stored `expires_at` ledger is still checked
+//! explicitly, because the TTL only bounds how long the entry *may* live.
+//!
+//! A capability is not a bearer token: every use still calls
+//! `holder.require_auth()`, so a leaked id is useless to anyone else.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN,
+    Env, Symbol, Vec,
+};
+
+/// Ledgers a capability stays valid after issuance (~8 minutes at 5s/ledger).
+pub const CAP_TTL_LEDGERS: u32 = 100;
+
+/// Scope accepted by `record_usage`.
+pub const SCOPE_USAGE: Symbol = symbol_short!("usage");
+/// Scope accepted by `submit_report`.
+pub const SCOPE_REPORT: Symbol = symbol_short!("report");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Capability {
+    pub holder: Address,
+    pub scope: Symbol,
+    /// Last ledger sequence at which the capability is accepted.
+    pub expires_at: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Registered members, scanned linearly by the heavyweight check.
+    Members,
+    /// Scopes a member may request capabilities for.
+    MemberScopes(Address),
+    Suspended(Address),
+    /// Counter mixed into capability ids so each issuance is unique.
+    Nonce,
+    /// Temporary: an issued capability.
+    Cap(BytesN<32>),
+    Usage(Address),
+    LastReport(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CapError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotMember = 3,
+    Suspended = 4,
+    ScopeNotAllowed = 5,
+    /// No such capability, or it was revoked or evicted.
+    UnknownCapability = 6,
+    Expired = 7,
+    WrongHolder = 8,
+    WrongScope = 9,
+}
+
+#[contract]
+pub struct CapabilityContract;
+
+#[contractimpl]
+impl CapabilityContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CapError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(CapError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Members, &Vec::<Address>::new(&env));
+        Ok(())
+    }
+
+    // ==================== MEMBERSHIP (ADMIN) ====================
+
+    pub fn add_member(env: Env, member: Address, scopes: Vec<Symbol>) -> Result<(), CapError> {
+        Self::require_admin(&env)?;
+        let mut members = Self::members(&env);
+        if !members.contains(&member) {
+            members.push_back(member.clone());
+            env.storage().persistent().set(&DataKey::Members, &members);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::MemberScopes(member), &scopes);
+        Ok(())
+    }
+
+    pub fn set_suspended(env: Env, member: Address, suspended: bool) -> Result<(), CapError> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Suspended(member), &suspended);
+        Ok(())
+    }
+
+    // ==================== CAPABILITIES ====================
+
+    /// Run the full authorization check and issue a capability for `scope`.
+    ///
+    /// Returns the capability id the holder passes to scoped calls until
+    /// `CAP_TTL_LEDGERS` ledgers have passed.
+    ///
+    /// Emits `(cap, issued)` with `(holder, scope, expires_at)`.
+    pub fn issue_capability(
+        env: Env,
+        holder: Address,
+        scope: Symbol,
+    ) -> Result<BytesN<32>, CapError> {
+        holder.require_auth();
+        Self::heavyweight_check(&env, &holder, &scope)?;
+
+        let nonce: u64 = env.storage().instance().get(&DataKey::Nonce).unwrap_or(0);
+        env.storage().instance().set(&DataKey::Nonce, &(nonce + 1));
+        let id: BytesN<32> = env
+            .crypto()
+            .sha256(&(holder.clone(), scope.clone(), nonce).to_xdr(&env))
+            .into();
+
+        let expires_at = env.ledger().sequence() + CAP_TTL_LEDGERS;
+        let cap = Capability {
+            holder: holder.clone(),
+            scope: scope.clone(),
+            expires_at,
+        };
+        let key = DataKey::Cap(id.clone());
+        env.storage().temporary().set(&key, &cap);
+        // Let the network evict the entry once it can no longer be used.
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, CAP_TTL_LEDGERS, CAP_TTL_LEDGERS);
+
+        env.events().publish(
+            (symbol_short!("cap"), symbol_short!("issued")),
+            (holder, scope, expires_at),
+        );
+        Ok(id)
+    }
+
+    /// Invalidate a capability before it expires. Callable by its holder or
+    /// the admin.
+    pub fn revoke_capability(env: Env, caller: Address, id: BytesN<32>) -> Result<(), CapError> {
+        caller.require_auth();
+        let key = DataKey::Cap(id);
+        let cap: Capability = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(CapError::UnknownCapability)?;
+        if caller != cap.holder && caller != Self::admin(&env)? {
+            return Err(CapError::WrongHolder);
+        }
+        env.storage().temporary().remove(&key);
+        Ok(())
+    }
+
+    pub fn capability(env: Env, id: BytesN<32>) -> Option<Capability> {
+        env.storage().temporary().get(&DataKey::Cap(id))
+    }
+
+    // ==================== SCOPED OPERATIONS ====================
+
+    /// Add `units` to the holder's usage counter. Requires a `usage` capability.
+    pub fn record_usage(
+        env: Env,
+        holder: Address,
+        cap_id: BytesN<32>,
+        units: u64,
+    ) -> Result<u64, CapError> {
+        Self::check_capability(&env, &holder, &cap_id, &SCOPE_USAGE)?;
+        let usage = Self::usage(env.clone(), holder.clone()) + units;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Usage(holder), &usage);
+        Ok(usage)
+    }
+
+    /// Store the holder's latest report. Requires a `report` capability.
+    pub fn submit_report(
+        env: Env,
+        holder: Address,
+        cap_id: BytesN<32>,
+        value: u64,
+    ) -> Result<(), CapError> {
+        Self::check_capability(&env, &holder, &cap_id, &SCOPE_REPORT)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastReport(holder), &value);
+        Ok(())
+    }
+
+    pub fn usage(env: Env, holder: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Usage(holder))
+            .unwrap_or(0)
+    }
+
+    pub fn last_report(env: Env, holder: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::LastReport(holder))
+    }
+
+    // ==================== HELPERS ====================
+
+    /// The expensive path: membership scan, suspension flag and scope list.
+    fn heavyweight_check(env: &Env, holder: &Address, scope: &Symbol) -> Result<(), CapError> {
+        if !Self::members(env).contains(holder) {
+            return Err(CapError::NotMember);
+        }
+        let suspended: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Suspended(holder.clone()))
+            .unwrap_or(false);
+        if suspended {
+            return Err(CapError::Suspended);
+        }
+        let scopes: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MemberScopes(holder.clone()))
+            .unwrap_or_else(|| Vec::new(env));
+        if !scopes.contains(scope) {
+            return Err(CapError::ScopeNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// The cheap path: a single temporary-storage read.
+    fn check_capability(
+        env: &Env,
+        holder: &Address,
+        id: &BytesN<32>,
+        scope: &Symbol,
+    ) -> Result<(), CapError> {
+        holder.require_auth();
+        let cap: Capability = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Cap(id.clone()))
+            .ok_or(CapError::UnknownCapability)?;
+        if cap.holder != *holder {
+            return Err(CapError::WrongHolder);
+        }
+        if cap.scope != *scope {
+            return Err(CapError::WrongScope);
+        }
+        if env.ledger().sequence() > cap.expires_at {
+            return Err(CapError::Expired);
+        }
+        Ok(())
+    }
+
+    fn members(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Members)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn admin(env: &Env) -> Result<Address, CapError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CapError::NotInitialized)
+    }
+
+    fn require_admin(env: &Env) -> Result<(), CapError> {
+        Self::admin(env)?.require_auth();
+        Ok(())
+    }
+}
+
+mod test;
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+struct Setup {
+    env: Env,
+    client: CapabilityContractClient<'static>,
+    member: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, CapabilityContract);
+    let client = CapabilityContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let member = Address::generate(&env);
+    client.add_member(&member, &vec![&env, SCOPE_USAGE]);
+    Setup {
+        env,
+        client,
+        member,
+    }
+}
+
+// ── issuance ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_issue_and_reuse_capability() {
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+
+    for _ in 0..5 {
+        s.client.record_usage(&s.member, &cap, &10);
+    }
+    assert_eq!(s.client.usage(&s.member), 50);
+
+    let stored = s.client.capability(&cap).unwrap();
+    assert_eq!(stored.holder, s.member);
+    assert_eq!(stored.scope, SCOPE_USAGE);
+    assert_eq!(
+        stored.expires_at,
+        s.env.ledger().sequence() + CAP_TTL_LEDGERS
+    );
+}
+
+#[test]
+fn test_each_issuance_has_unique_id() {
+    let s = setup();
+    let first = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    let second = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_heavyweight_check_failures() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_issue_capability(&outsider, &SCOPE_USAGE),
+        Err(Ok(CapError::NotMember))
+    );
+    assert_eq!(
+        s.client.try_issue_capability(&s.member, &SCOPE_REPORT),
+        Err(Ok(CapError::ScopeNotAllowed))
+    );
+
+    s.client.set_suspended(&s.member, &true);
+    assert_eq!(
+        s.client.try_issue_capability(&s.member, &SCOPE_USAGE),
+        Err(Ok(CapError::Suspended))
+    );
+}
+
+// ── presenting capabilities ──────────────────────────────────────────────────
+
+#[test]
+fn test_capability_is_bound_to_scope() {
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    assert_eq!(
+        s.client.try_submit_report(&s.member, &cap, &1),
+        Err(Ok(CapError::WrongScope))
+    );
+}
+
+#[test]
+fn test_capability_is_bound_to_holder() {
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    let thief = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_record_usage(&thief, &cap, &1),
+        Err(Ok(CapError::WrongHolder))
+    );
+}
+
+#[test]
+fn test_unknown_capability_rejected() {
+    let s = setup();
+    let forged = BytesN::from_array(&s.env, &[7; 32]);
+    assert_eq!(
+        s.client.try_record_usage(&s.member, &forged, &1),
+        Err(Ok(CapError::UnknownCapability))
+    );
+}
+
+#[test]
+fn test_capability_expires() {
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    let expires_at = s.client.capability(&cap).unwrap().expires_at;
+
+    // Still valid on its last ledger.
+    s.env.ledger().with_mut(|l| l.sequence_number = expires_at);
+    s.client.record_usage(&s.member, &cap, &1);
+
+    // One ledger later it is rejected. The network may already have evicted
+    // the temporary entry, in which case it is simply unknown.
+    s.env
+        .ledger()
+        .with_mut(|l| l.sequence_number = expires_at + 1);
+    let result = s.client.try_record_usage(&s.member, &cap, &1);
+    assert!(matches!(
+        result,
+        Err(Ok(CapError::Expired | CapError::UnknownCapability))
+    ));
+}
+
+#[test]
+fn test_revoke_capability() {
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    s.client.revoke_capability(&s.member, &cap);
+    assert_eq!(s.client.capability(&cap), None);
+    assert_eq!(
+        s.client.try_record_usage(&s.member, &cap, &1),
+        Err(Ok(CapError::UnknownCapability))
+    );
+}
+
+#[test]
+fn test_stranger_cannot_revoke() {
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    assert_eq!(
+        s.client
+            .try_revoke_capability(&Address::generate(&s.env), &cap),
+        Err(Ok(CapError::WrongHolder))
+    );
+}
+
+#[test]
+fn test_suspension_does_not_affect_live_capabilities() {
+    // Trade-off of the pattern: a capability outlives changes to the
+    // heavyweight inputs until it expires or is revoked.
+    let s = setup();
+    let cap = s.client.issue_capability(&s.member, &SCOPE_USAGE);
+    s.client.set_suspended(&s.member, &true);
+    s.client.record_usage(&s.member, &cap, &1);
+}
+
+// ── cost ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_capability_check_is_cheaper_than_full_check() {
+    let s = setup();
+    for _ in 0..50 {
+        s.client
+            .add_member(&Address::generate(&s.env), &vec![&s.env, SCOPE_USAGE]);
+    }
+    // Register the member under test last so the scan walks the whole list.
+    let late = Address::generate(&s.env);
+    s.client.add_member(&late, &vec![&s.env, SCOPE_USAGE]);
+
+    s.env.budget().reset_default();
+    let cap = s.client.issue_capability(&late, &SCOPE_USAGE);
+    let issue_cost = s.env.budget().cpu_instruction_cost();
+
+    s.env.budget().reset_default();
+    s.client.record_usage(&late, &cap, &1);
+    let use_cost = s.env.budget().cpu_instruction_cost();
+
+    assert!(
+        use_cost < issue_cost,
+        "use {use_cost} should be cheaper than issue {issue_cost}"
+    );
+}
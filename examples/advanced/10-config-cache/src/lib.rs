@@ -0,0 +1,146 @@
+//! # Read-Through Config Cache
+//!
+//! Contract B reads a parameter owned by contract A (say, `fee_bps`) on every
+//! call. A cross-contract call on each read costs an invocation plus A's own
+//! storage read. Parameters like this change rarely, so B keeps a copy:
+//!
+//! ```text
+//! get_config(key)
+//!     ├─ hit:  temporary storage Cached(key), not yet expired  → return it
+//!     └─ miss: ConfigSourceClient(A).get_config(key)            → store, return
+//! ```
+//!
+//! Cached entries live in **temporary storage** with a TTL of
+//! `CACHE_TTL_LEDGERS`, so stale copies disappear on their own. The stored
+//! `expires_at` is also checked explicitly, because the TTL only bounds how
+//! long an entry *may* live.
+//!
+//! Waiting out the TTL is fine for most reads, but A can also push an update:
+//! after changing a value it calls `invalidate(key)` on B, and the next read
+//! fetches the new value. Only A may invalidate — B checks
+//! `source.require_auth()`, which a direct contract caller satisfies without
+//! any signature.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    Env, Symbol,
+};
+
+/// Ledgers a cached value is served before it is re-fetched (~100 seconds).
+pub const CACHE_TTL_LEDGERS: u32 = 20;
+
+/// Config key read by `quote_fee`.
+pub const FEE_BPS: Symbol = symbol_short!("fee_bps");
+
+/// The interface B needs from the contract that owns the config.
+#[contractclient(name = "ConfigSourceClient")]
+pub trait ConfigSource {
+    fn get_config(env: Env, key: Symbol) -> Option<u64>;
+}
+
+/// A cached copy of one of the source's values.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedValue {
+    pub value: Option<u64>,
+    /// Last ledger sequence at which the copy is served.
+    pub expires_at: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Address of the contract that owns the config.
+    Source,
+    /// Temporary: cached copy of the source's value for a key.
+    Cached(Symbol),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CacheError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+}
+
+#[contract]
+pub struct ConfigCache;
+
+#[contractimpl]
+impl ConfigCache {
+    pub fn initialize(env: Env, source: Address) -> Result<(), CacheError> {
+        if env.storage().instance().has(&DataKey::Source) {
+            return Err(CacheError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Source, &source);
+        Ok(())
+    }
+
+    // ==================== READ-THROUGH ====================
+
+    /// Return the source's value for `key`, from the cache when possible.
+    ///
+    /// Unset keys are cached too (as `None`), so repeated reads of a missing
+    /// key do not each cost a cross-contract call.
+    pub fn get_config(env: Env, key: Symbol) -> Result<Option<u64>, CacheError> {
+        let cache_key = DataKey::Cached(key.clone());
+        let cached: Option<CachedValue> = env.storage().temporary().get(&cache_key);
+        if let Some(cached) = cached {
+            if env.ledger().sequence() <= cached.expires_at {
+                return Ok(cached.value);
+            }
+        }
+
+        let value = ConfigSourceClient::new(&env, &Self::source(&env)?).get_config(&key);
+        let entry = CachedValue {
+            value,
+            expires_at: env.ledger().sequence() + CACHE_TTL_LEDGERS,
+        };
+        env.storage().temporary().set(&cache_key, &entry);
+        env.storage()
+            .temporary()
+            .extend_ttl(&cache_key, CACHE_TTL_LEDGERS, CACHE_TTL_LEDGERS);
+        Ok(value)
+    }
+
+    /// Drop the cached copy of `key`. Only callable by the source.
+    ///
+    /// Emits `(cache, invalid)` with the key.
+    pub fn invalidate(env: Env, key: Symbol) -> Result<(), CacheError> {
+        Self::source(&env)?.require_auth();
+        env.storage()
+            .temporary()
+            .remove(&DataKey::Cached(key.clone()));
+        env.events()
+            .publish((symbol_short!("cache"), symbol_short!("invalid")), key);
+        Ok(())
+    }
+
+    /// The cached entry for `key`, without fetching on a miss.
+    pub fn cached(env: Env, key: Symbol) -> Option<CachedValue> {
+        env.storage().temporary().get(&DataKey::Cached(key))
+    }
+
+    // ==================== BUSINESS LOGIC ====================
+
+    /// Fee owed on `amount` at the source's current `fee_bps` (0 if unset).
+    pub fn quote_fee(env: Env, amount: i128) -> Result<i128, CacheError> {
+        let fee_bps = Self::get_config(env, FEE_BPS)?.unwrap_or(0);
+        Ok(amount * fee_bps as i128 / 10_000)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn source(env: &Env) -> Result<Address, CacheError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Source)
+            .ok_or(CacheError::NotInitialized)
+    }
+}
+
+mod source;
+mod test;
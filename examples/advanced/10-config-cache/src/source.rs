@@ -0,0 +1,81 @@
+//! Reference config owner (contract A).
+//!
+//! Only compiled for tests: in a real deployment it would be its own crate
+//! and Wasm. It counts how often `get_config` is called so tests can tell
+//! cache hits from cross-contract reads.
+
+#![cfg(test)]
+
+use crate::ConfigCacheClient;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum SourceKey {
+    Admin,
+    Config(Symbol),
+    /// Caches to invalidate whenever a value changes.
+    Subscribers,
+    Reads,
+}
+
+#[contract]
+pub struct ConfigSourceContract;
+
+#[contractimpl]
+impl ConfigSourceContract {
+    pub fn initialize(env: Env, admin: Address) {
+        env.storage().instance().set(&SourceKey::Admin, &admin);
+    }
+
+    pub fn subscribe(env: Env, cache: Address) {
+        Self::admin(&env).require_auth();
+        let mut subscribers = Self::subscribers(&env);
+        subscribers.push_back(cache);
+        env.storage()
+            .instance()
+            .set(&SourceKey::Subscribers, &subscribers);
+    }
+
+    /// Update a value and push an invalidation to every subscribed cache.
+    pub fn set_config(env: Env, key: Symbol, value: u64) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&SourceKey::Config(key.clone()), &value);
+        for cache in Self::subscribers(&env).iter() {
+            ConfigCacheClient::new(&env, &cache).invalidate(&key);
+        }
+    }
+
+    /// Update a value without notifying caches.
+    pub fn set_config_silently(env: Env, key: Symbol, value: u64) {
+        Self::admin(&env).require_auth();
+        env.storage()
+            .instance()
+            .set(&SourceKey::Config(key), &value);
+    }
+
+    pub fn get_config(env: Env, key: Symbol) -> Option<u64> {
+        let reads: u32 = env.storage().instance().get(&SourceKey::Reads).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&SourceKey::Reads, &(reads + 1));
+        env.storage().instance().get(&SourceKey::Config(key))
+    }
+
+    pub fn reads(env: Env) -> u32 {
+        env.storage().instance().get(&SourceKey::Reads).unwrap_or(0)
+    }
+
+    fn admin(env: &Env) -> Address {
+        env.storage().instance().get(&SourceKey::Admin).unwrap()
+    }
+
+    fn subscribers(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&SourceKey::Subscribers)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+}
@@ -0,0 +1,160 @@
+#![cfg(test)]
+
+use super::*;
+use crate::source::{ConfigSourceContract, ConfigSourceContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    cache: ConfigCacheClient<'static>,
+    source: ConfigSourceContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let source_id = env.register_contract(None, ConfigSourceContract);
+    let source = ConfigSourceContractClient::new(&env, &source_id);
+    source.initialize(&Address::generate(&env));
+    source.set_config(&FEE_BPS, &30);
+
+    let cache_id = env.register_contract(None, ConfigCache);
+    let cache = ConfigCacheClient::new(&env, &cache_id);
+    cache.initialize(&source_id);
+    source.subscribe(&cache_id);
+
+    Setup { env, cache, source }
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|l| l.sequence_number += ledgers);
+}
+
+// ── read-through ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_miss_fetches_then_hits_serve_from_cache() {
+    let s = setup();
+    assert_eq!(s.cache.cached(&FEE_BPS), None);
+
+    assert_eq!(s.cache.get_config(&FEE_BPS), Some(30));
+    assert_eq!(s.source.reads(), 1);
+    assert_eq!(
+        s.cache.cached(&FEE_BPS),
+        Some(CachedValue {
+            value: Some(30),
+            expires_at: s.env.ledger().sequence() + CACHE_TTL_LEDGERS,
+        })
+    );
+
+    for _ in 0..5 {
+        assert_eq!(s.cache.quote_fee(&10_000), 30);
+    }
+    assert_eq!(s.source.reads(), 1, "hits never call the source");
+}
+
+#[test]
+fn test_missing_keys_are_cached() {
+    let s = setup();
+    let key = symbol_short!("unset");
+    assert_eq!(s.cache.get_config(&key), None);
+    assert_eq!(s.cache.get_config(&key), None);
+    assert_eq!(s.source.reads(), 1);
+}
+
+#[test]
+fn test_entry_refetched_after_expiry() {
+    let s = setup();
+    s.cache.get_config(&FEE_BPS);
+    s.source.set_config_silently(&FEE_BPS, &50);
+
+    // Stale until the copy expires...
+    advance_ledgers(&s.env, CACHE_TTL_LEDGERS);
+    assert_eq!(s.cache.get_config(&FEE_BPS), Some(30));
+    assert_eq!(s.source.reads(), 1);
+
+    // ...then fetched again.
+    advance_ledgers(&s.env, 1);
+    assert_eq!(s.cache.get_config(&FEE_BPS), Some(50));
+    assert_eq!(s.source.reads(), 2);
+}
+
+// ── invalidation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_source_update_invalidates_cache() {
+    let s = setup();
+    s.cache.get_config(&FEE_BPS);
+
+    s.source.set_config(&FEE_BPS, &50);
+    assert_eq!(s.cache.cached(&FEE_BPS), None);
+    assert_eq!(s.cache.get_config(&FEE_BPS), Some(50));
+    assert_eq!(s.source.reads(), 2);
+}
+
+#[test]
+fn test_invalidate_emits_event() {
+    let s = setup();
+    s.cache.get_config(&FEE_BPS);
+    s.source.set_config(&FEE_BPS, &50);
+
+    let event = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(id, _, _)| *id == s.cache.address)
+        .unwrap();
+    assert_eq!(
+        event.1,
+        (symbol_short!("cache"), symbol_short!("invalid")).into_val(&s.env)
+    );
+    let key: Symbol = event.2.into_val(&s.env);
+    assert_eq!(key, FEE_BPS);
+}
+
+#[test]
+fn test_invalidate_only_affects_its_key() {
+    let s = setup();
+    let other = symbol_short!("cooldown");
+    s.source.set_config(&other, &60);
+    s.cache.get_config(&FEE_BPS);
+    s.cache.get_config(&other);
+
+    s.source.set_config(&FEE_BPS, &50);
+    assert_eq!(s.cache.cached(&FEE_BPS), None);
+    assert!(s.cache.cached(&other).is_some());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_source_can_invalidate() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.cache.invalidate(&FEE_BPS);
+}
+
+// ── setup ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_initialize_once() {
+    let s = setup();
+    assert_eq!(
+        s.cache.try_initialize(&Address::generate(&s.env)),
+        Err(Ok(CacheError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_uninitialized_cache_errors() {
+    let env = Env::default();
+    let cache = ConfigCacheClient::new(&env, &env.register_contract(None, ConfigCache));
+    assert_eq!(
+        cache.try_get_config(&FEE_BPS),
+        Err(Ok(CacheError::NotInitialized))
+    );
+}
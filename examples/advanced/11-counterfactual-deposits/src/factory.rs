@@ -0,0 +1,90 @@
+//! Factory that hands out wallet addresses before deploying them.
+
+use crate::CounterfactualWalletClient;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN,
+    Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum FactoryKey {
+    /// Wasm every wallet is deployed with.
+    WalletWasm,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FactoryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+}
+
+#[contract]
+pub struct WalletFactory;
+
+#[contractimpl]
+impl WalletFactory {
+    /// Fix the wallet Wasm. It cannot be changed afterwards: a predicted
+    /// address is only safe to fund if the code that will run there is known.
+    pub fn initialize(env: Env, wallet_wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        if env.storage().instance().has(&FactoryKey::WalletWasm) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&FactoryKey::WalletWasm, &wallet_wasm_hash);
+        Ok(())
+    }
+
+    /// Address `deploy_wallet(owner, salt)` will deploy to. Pure computation:
+    /// the result is the same before and after deployment.
+    pub fn predict_address(env: Env, owner: Address, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(wallet_salt(&env, &owner, &salt))
+            .deployed_address()
+    }
+
+    /// Deploy the wallet for `owner` at its predicted address and initialize
+    /// it. Anyone may call this (e.g. a relayer paying the fee): the owner is
+    /// part of the address, so the caller cannot change who controls it.
+    ///
+    /// Emits `(deployed, owner)` with the wallet address.
+    pub fn deploy_wallet(
+        env: Env,
+        owner: Address,
+        salt: BytesN<32>,
+    ) -> Result<Address, FactoryError> {
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&FactoryKey::WalletWasm)
+            .ok_or(FactoryError::NotInitialized)?;
+
+        let wallet = env
+            .deployer()
+            .with_current_contract(wallet_salt(&env, &owner, &salt))
+            .deploy(wasm_hash);
+        CounterfactualWalletClient::new(&env, &wallet).init(&owner);
+
+        env.events()
+            .publish((symbol_short!("deployed"), owner), wallet.clone());
+        Ok(wallet)
+    }
+
+    pub fn wallet_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&FactoryKey::WalletWasm)
+    }
+}
+
+/// Deployment salt for `owner`'s wallet.
+///
+/// Hashing the owner in means a salt alone never identifies an address, so
+/// nobody can deploy a wallet with a different owner at an address someone
+/// else has already funded.
+pub fn wallet_salt(env: &Env, owner: &Address, salt: &BytesN<32>) -> BytesN<32> {
+    env.crypto()
+        .sha256(&(owner.clone(), salt.clone()).to_xdr(env))
+        .into()
+}
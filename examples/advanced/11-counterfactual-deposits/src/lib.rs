@@ -0,0 +1,98 @@
+//! # Counterfactual Deposits
+//!
+//! A contract's address is fixed by *who deploys it* and *with which salt*,
+//! not by its code or by when it is deployed:
+//!
+//! ```text
+//! address = f(deployer, salt)
+//! ```
+//!
+//! So the address of a contract that does not exist yet can be computed and
+//! handed out. Tokens sent there sit in the token contract's balance table
+//! under that address until the contract is deployed and moves them — the
+//! *counterfactual* pattern. A user can be given a deposit address for free,
+//! and the deployment fee is only paid once funds actually arrive.
+//!
+//! ```text
+//! 1. predict_address(owner, salt)  → A   (no deployment, no fees)
+//! 2. anyone: token.transfer(_, A, amount)
+//! 3. deploy_wallet(owner, salt)    → A   (deploys + init(owner) atomically)
+//! 4. owner:  wallet.sweep(token, to)
+//! ```
+//!
+//! [`factory::WalletFactory`] predicts and deploys; [`CounterfactualWallet`]
+//! is the contract deployed at the predicted address. In a real deployment
+//! they would be separate crates; here they share one so the example is
+//! self-contained, and their function names are kept distinct so both fit in
+//! one Wasm.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+pub mod factory;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum WalletKey {
+    Owner,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WalletError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The wallet holds no balance of the requested token.
+    NothingToSweep = 3,
+}
+
+/// Minimal wallet deployed at a predicted address.
+#[contract]
+pub struct CounterfactualWallet;
+
+#[contractimpl]
+impl CounterfactualWallet {
+    /// Set the owner. The factory calls this in the same transaction as the
+    /// deployment, so there is no window in which someone else could claim it.
+    pub fn init(env: Env, owner: Address) -> Result<(), WalletError> {
+        if env.storage().instance().has(&WalletKey::Owner) {
+            return Err(WalletError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&WalletKey::Owner, &owner);
+        Ok(())
+    }
+
+    pub fn owner(env: Env) -> Option<Address> {
+        env.storage().instance().get(&WalletKey::Owner)
+    }
+
+    /// Move the wallet's whole balance of `token` to `to`, including anything
+    /// deposited before the wallet existed. Owner only.
+    ///
+    /// Emits `(sweep, token)` with `(to, amount)`.
+    pub fn sweep(env: Env, token: Address, to: Address) -> Result<i128, WalletError> {
+        let owner: Address = env
+            .storage()
+            .instance()
+            .get(&WalletKey::Owner)
+            .ok_or(WalletError::NotInitialized)?;
+        owner.require_auth();
+
+        let client = token::Client::new(&env, &token);
+        let amount = client.balance(&env.current_contract_address());
+        if amount <= 0 {
+            return Err(WalletError::NothingToSweep);
+        }
+        client.transfer(&env.current_contract_address(), &to, &amount);
+
+        env.events()
+            .publish((symbol_short!("sweep"), token), (to, amount));
+        Ok(amount)
+    }
+}
+
+mod test;
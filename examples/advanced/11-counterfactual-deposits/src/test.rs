@@ -0,0 +1,167 @@
+#![cfg(test)]
+
+use super::*;
+use factory::{wallet_salt, FactoryError, WalletFactory, WalletFactoryClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env,
+};
+
+struct Setup {
+    env: Env,
+    factory: WalletFactoryClient<'static>,
+    token: TokenClient<'static>,
+    owner: Address,
+    salt: BytesN<32>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory_id = env.register_contract(None, WalletFactory);
+    let factory = WalletFactoryClient::new(&env, &factory_id);
+    factory.initialize(&BytesN::from_array(&env, &[1; 32]));
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token = TokenClient::new(&env, &token_id);
+
+    Setup {
+        owner: Address::generate(&env),
+        salt: BytesN::from_array(&env, &[7; 32]),
+        env,
+        factory,
+        token,
+    }
+}
+
+/// Stands in for `deploy_wallet`: native tests cannot deploy from a Wasm
+/// hash, so register the wallet at the predicted address directly. The full
+/// factory deployment runs in `tests/integration`.
+fn deploy_at(s: &Setup, address: &Address) -> CounterfactualWalletClient<'static> {
+    s.env.register_contract(address, CounterfactualWallet);
+    let wallet = CounterfactualWalletClient::new(&s.env, address);
+    wallet.init(&s.owner);
+    wallet
+}
+
+// ── address prediction ───────────────────────────────────────────────────────
+
+#[test]
+fn test_prediction_is_deterministic() {
+    let s = setup();
+    let predicted = s.factory.predict_address(&s.owner, &s.salt);
+    assert_eq!(s.factory.predict_address(&s.owner, &s.salt), predicted);
+
+    // Anyone can compute the same address off-chain from (factory, salt).
+    let offchain = s
+        .env
+        .deployer()
+        .with_address(
+            s.factory.address.clone(),
+            wallet_salt(&s.env, &s.owner, &s.salt),
+        )
+        .deployed_address();
+    assert_eq!(offchain, predicted);
+}
+
+#[test]
+fn test_prediction_depends_on_owner_and_salt() {
+    let s = setup();
+    let predicted = s.factory.predict_address(&s.owner, &s.salt);
+    let other_owner = s
+        .factory
+        .predict_address(&Address::generate(&s.env), &s.salt);
+    let other_salt = s
+        .factory
+        .predict_address(&s.owner, &BytesN::from_array(&s.env, &[8; 32]));
+    assert_ne!(predicted, other_owner);
+    assert_ne!(predicted, other_salt);
+}
+
+// ── deposits before deployment ───────────────────────────────────────────────
+
+#[test]
+fn test_deposit_then_deploy_then_sweep() {
+    let s = setup();
+    let predicted = s.factory.predict_address(&s.owner, &s.salt);
+
+    // Two deposits land before any code exists at the address.
+    let payer = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&payer, &1_000);
+    s.token.transfer(&payer, &predicted, &600);
+    s.token.transfer(&payer, &predicted, &150);
+    assert_eq!(s.token.balance(&predicted), 750);
+
+    let wallet = deploy_at(&s, &predicted);
+    assert_eq!(wallet.owner(), Some(s.owner.clone()));
+
+    let dest = Address::generate(&s.env);
+    assert_eq!(wallet.sweep(&s.token.address, &dest), 750);
+    assert_eq!(s.token.balance(&dest), 750);
+    assert_eq!(s.token.balance(&predicted), 0);
+}
+
+#[test]
+fn test_sweep_with_empty_balance_fails() {
+    let s = setup();
+    let wallet = deploy_at(&s, &s.factory.predict_address(&s.owner, &s.salt));
+    assert_eq!(
+        wallet.try_sweep(&s.token.address, &s.owner),
+        Err(Ok(WalletError::NothingToSweep))
+    );
+}
+
+#[test]
+fn test_wallet_init_only_once() {
+    let s = setup();
+    let wallet = deploy_at(&s, &s.factory.predict_address(&s.owner, &s.salt));
+    assert_eq!(
+        wallet.try_init(&Address::generate(&s.env)),
+        Err(Ok(WalletError::AlreadyInitialized))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_sweep_requires_owner_auth() {
+    let s = setup();
+    let predicted = s.factory.predict_address(&s.owner, &s.salt);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&predicted, &10);
+    let wallet = deploy_at(&s, &predicted);
+
+    s.env.set_auths(&[]);
+    wallet.sweep(&s.token.address, &Address::generate(&s.env));
+}
+
+// ── factory setup ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_factory_wasm_is_fixed() {
+    let s = setup();
+    assert_eq!(
+        s.factory
+            .try_initialize(&BytesN::from_array(&s.env, &[2; 32])),
+        Err(Ok(FactoryError::AlreadyInitialized))
+    );
+    assert_eq!(
+        s.factory.wallet_wasm_hash(),
+        Some(BytesN::from_array(&s.env, &[1; 32]))
+    );
+}
+
+#[test]
+fn test_uninitialized_factory_cannot_deploy() {
+    let env = Env::default();
+    let factory = WalletFactoryClient::new(&env, &env.register_contract(None, WalletFactory));
+    assert_eq!(
+        factory.try_deploy_wallet(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0; 32])
+        ),
+        Err(Ok(FactoryError::NotInitialized))
+    );
+}
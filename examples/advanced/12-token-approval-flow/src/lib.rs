@@ -0,0 +1,154 @@
+//! # Contract-to-Contract Token Approval Flow
+//!
+//! The standard two-contract DeFi interaction: a user deposits into a *core*
+//! contract (here a `Pool`) through a *periphery* contract (a `Router`) that
+//! does the bookkeeping around it. The router never holds the user's tokens;
+//! it pulls them straight into the pool with `transfer_from`:
+//!
+//! ```text
+//! user:   token.approve(user, router, amount, expiration_ledger)
+//! user:   router.deposit(user, amount, min_shares, deadline)
+//!           ├─ token.transfer_from(router, user → pool, amount)
+//!           └─ pool.record_deposit(user, amount)   // router-only
+//! ```
+//!
+//! ## Exact-approval hygiene
+//!
+//! A leftover allowance is a standing permission for the spender to take
+//! funds later. The router therefore only accepts an allowance of *exactly*
+//! `amount`: after the pull it is back to zero, so nothing lingers. Users who
+//! approved too much reset the allowance to zero with
+//! `token.approve(user, router, 0, 0)` and approve again.
+//!
+//! ## Don't trust the periphery
+//!
+//! The pool only credits deposits reported by its registered router, and it
+//! checks its own token balance to confirm the tokens actually arrived.
+//!
+//! `Pool` and [`periphery::Router`] would normally be separate crates; they
+//! share one here, with distinct function names so both fit in one Wasm.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+pub mod periphery;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PoolKey {
+    Token,
+    /// The only address allowed to report deposits.
+    Router,
+    /// Token balance the pool has accounted for.
+    Reserve,
+    Shares(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PoolError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// The pool's token balance does not cover the reported deposit.
+    FundsNotReceived = 4,
+    InsufficientShares = 5,
+}
+
+/// Core contract: holds the tokens and tracks each user's shares (1:1).
+#[contract]
+pub struct Pool;
+
+#[contractimpl]
+impl Pool {
+    pub fn initialize(env: Env, token: Address, router: Address) -> Result<(), PoolError> {
+        if env.storage().instance().has(&PoolKey::Token) {
+            return Err(PoolError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&PoolKey::Token, &token);
+        env.storage().instance().set(&PoolKey::Router, &router);
+        env.storage().instance().set(&PoolKey::Reserve, &0i128);
+        Ok(())
+    }
+
+    /// Credit `user` for tokens the router has just moved into the pool and
+    /// return the shares minted. Router only.
+    ///
+    /// Emits `(deposit, user)` with the amount.
+    pub fn record_deposit(env: Env, user: Address, amount: i128) -> Result<i128, PoolError> {
+        let router: Address = env
+            .storage()
+            .instance()
+            .get(&PoolKey::Router)
+            .ok_or(PoolError::NotInitialized)?;
+        router.require_auth();
+        if amount <= 0 {
+            return Err(PoolError::InvalidAmount);
+        }
+
+        let reserve = Self::reserve(env.clone()) + amount;
+        let balance =
+            token::Client::new(&env, &Self::token(&env)?).balance(&env.current_contract_address());
+        if balance < reserve {
+            return Err(PoolError::FundsNotReceived);
+        }
+        env.storage().instance().set(&PoolKey::Reserve, &reserve);
+
+        let shares = Self::shares(env.clone(), user.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&PoolKey::Shares(user.clone()), &shares);
+
+        env.events()
+            .publish((symbol_short!("deposit"), user), amount);
+        Ok(amount)
+    }
+
+    /// Burn `amount` shares and send the same amount of tokens to `user`.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<(), PoolError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(PoolError::InvalidAmount);
+        }
+        let shares = Self::shares(env.clone(), user.clone());
+        if shares < amount {
+            return Err(PoolError::InsufficientShares);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&PoolKey::Shares(user.clone()), &(shares - amount));
+        let reserve = Self::reserve(env.clone()) - amount;
+        env.storage().instance().set(&PoolKey::Reserve, &reserve);
+        token::Client::new(&env, &Self::token(&env)?).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        Ok(())
+    }
+
+    pub fn shares(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::Shares(user))
+            .unwrap_or(0)
+    }
+
+    pub fn reserve(env: Env) -> i128 {
+        env.storage().instance().get(&PoolKey::Reserve).unwrap_or(0)
+    }
+
+    fn token(env: &Env) -> Result<Address, PoolError> {
+        env.storage()
+            .instance()
+            .get(&PoolKey::Token)
+            .ok_or(PoolError::NotInitialized)
+    }
+}
+
+mod test;
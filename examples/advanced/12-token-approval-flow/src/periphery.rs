@@ -0,0 +1,95 @@
+//! Periphery contract that pulls a user's tokens into the pool.
+
+use crate::PoolClient;
+use cookbook_prelude::prelude::*;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum RouterKey {
+    Token,
+    Pool,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RouterError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// The user's allowance for the router is not exactly the deposit amount.
+    InexactApproval = 4,
+    /// The call landed after the caller's deadline.
+    DeadlinePassed = 5,
+    /// The pool minted fewer shares than the caller's minimum.
+    SlippageExceeded = 6,
+}
+
+impl From<GuardError> for RouterError {
+    fn from(err: GuardError) -> Self {
+        match err {
+            GuardError::DeadlinePassed => RouterError::DeadlinePassed,
+            GuardError::SlippageExceeded => RouterError::SlippageExceeded,
+        }
+    }
+}
+
+#[contract]
+pub struct Router;
+
+#[contractimpl]
+impl Router {
+    pub fn configure(env: Env, token: Address, pool: Address) -> Result<(), RouterError> {
+        if env.storage().instance().has(&RouterKey::Token) {
+            return Err(RouterError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&RouterKey::Token, &token);
+        env.storage().instance().set(&RouterKey::Pool, &pool);
+        Ok(())
+    }
+
+    /// Pull `amount` from `user` into the pool and credit them there.
+    ///
+    /// The user must have approved the router for exactly `amount`, so the
+    /// allowance is zero again once the call returns. Fails after `deadline`
+    /// or if the pool mints fewer than `min_shares`. Returns the shares minted.
+    pub fn deposit(
+        env: Env,
+        user: Address,
+        amount: i128,
+        min_shares: i128,
+        deadline: u64,
+    ) -> Result<i128, RouterError> {
+        user.require_auth();
+        check_deadline(&env, deadline)?;
+        if amount <= 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+        let (token, pool) = Self::config(&env)?;
+        let token = token::Client::new(&env, &token);
+        let router = env.current_contract_address();
+
+        if token.allowance(&user, &router) != amount {
+            return Err(RouterError::InexactApproval);
+        }
+        token.transfer_from(&router, &user, &pool, &amount);
+        let shares = PoolClient::new(&env, &pool).record_deposit(&user, &amount);
+        check_min_out(shares, min_shares)?;
+        Ok(shares)
+    }
+
+    fn config(env: &Env) -> Result<(Address, Address), RouterError> {
+        let token = env
+            .storage()
+            .instance()
+            .get(&RouterKey::Token)
+            .ok_or(RouterError::NotInitialized)?;
+        let pool = env
+            .storage()
+            .instance()
+            .get(&RouterKey::Pool)
+            .ok_or(RouterError::NotInitialized)?;
+        Ok((token, pool))
+    }
+}
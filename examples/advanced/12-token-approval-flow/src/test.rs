@@ -0,0 +1,218 @@
+#![cfg(test)]
+
+use super::*;
+use periphery::{Router, RouterClient, RouterError};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct Setup {
+    env: Env,
+    token: TokenClient<'static>,
+    pool: PoolClient<'static>,
+    router: RouterClient<'static>,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let pool = PoolClient::new(&env, &env.register_contract(None, Pool));
+    let router = RouterClient::new(&env, &env.register_contract(None, Router));
+    pool.initialize(&token_id, &router.address);
+    router.configure(&token_id, &pool.address);
+
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&user, &1_000);
+
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        pool,
+        router,
+        user,
+    }
+}
+
+impl Setup {
+    fn approve(&self, amount: i128) {
+        let expiration = self.env.ledger().sequence() + 100;
+        self.token
+            .approve(&self.user, &self.router.address, &amount, &expiration);
+    }
+
+    /// A deadline one minute from now.
+    fn deadline(&self) -> u64 {
+        self.env.ledger().timestamp() + 60
+    }
+
+    fn allowance(&self) -> i128 {
+        self.token.allowance(&self.user, &self.router.address)
+    }
+}
+
+// ── happy path ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_deposit_pulls_into_pool_and_clears_allowance() {
+    let s = setup();
+    s.approve(300);
+    assert_eq!(s.allowance(), 300);
+
+    s.router.deposit(&s.user, &300, &300, &s.deadline());
+
+    assert_eq!(s.allowance(), 0, "no allowance left behind");
+    assert_eq!(s.token.balance(&s.user), 700);
+    assert_eq!(s.token.balance(&s.pool.address), 300);
+    assert_eq!(
+        s.token.balance(&s.router.address),
+        0,
+        "router never holds funds"
+    );
+    assert_eq!(s.pool.shares(&s.user), 300);
+    assert_eq!(s.pool.reserve(), 300);
+}
+
+#[test]
+fn test_each_deposit_needs_a_fresh_approval() {
+    let s = setup();
+    s.approve(100);
+    s.router.deposit(&s.user, &100, &100, &s.deadline());
+    assert_eq!(
+        s.router.try_deposit(&s.user, &100, &100, &s.deadline()),
+        Err(Ok(RouterError::InexactApproval))
+    );
+
+    s.approve(100);
+    s.router.deposit(&s.user, &100, &100, &s.deadline());
+    assert_eq!(s.pool.shares(&s.user), 200);
+}
+
+#[test]
+fn test_withdraw_returns_tokens() {
+    let s = setup();
+    s.approve(400);
+    s.router.deposit(&s.user, &400, &400, &s.deadline());
+
+    s.pool.withdraw(&s.user, &150);
+    assert_eq!(s.pool.shares(&s.user), 250);
+    assert_eq!(s.pool.reserve(), 250);
+    assert_eq!(s.token.balance(&s.user), 750);
+    assert_eq!(
+        s.pool.try_withdraw(&s.user, &251),
+        Err(Ok(PoolError::InsufficientShares))
+    );
+}
+
+// ── allowance lifecycle ──────────────────────────────────────────────────────
+
+#[test]
+fn test_over_approval_rejected_until_reset() {
+    let s = setup();
+    s.approve(1_000);
+    assert_eq!(
+        s.router.try_deposit(&s.user, &300, &300, &s.deadline()),
+        Err(Ok(RouterError::InexactApproval))
+    );
+
+    // Reset to zero, then approve the exact amount.
+    s.token.approve(&s.user, &s.router.address, &0, &0);
+    assert_eq!(s.allowance(), 0);
+    s.approve(300);
+    s.router.deposit(&s.user, &300, &300, &s.deadline());
+    assert_eq!(s.allowance(), 0);
+}
+
+#[test]
+fn test_under_approval_rejected() {
+    let s = setup();
+    s.approve(299);
+    assert_eq!(
+        s.router.try_deposit(&s.user, &300, &300, &s.deadline()),
+        Err(Ok(RouterError::InexactApproval))
+    );
+    assert_eq!(s.allowance(), 299, "failed deposit leaves allowance as is");
+}
+
+#[test]
+fn test_expired_approval_rejected() {
+    let s = setup();
+    s.approve(300);
+    s.env.ledger().with_mut(|l| l.sequence_number += 101);
+
+    assert_eq!(s.allowance(), 0);
+    assert_eq!(
+        s.router.try_deposit(&s.user, &300, &300, &s.deadline()),
+        Err(Ok(RouterError::InexactApproval))
+    );
+    assert_eq!(s.token.balance(&s.user), 1_000);
+}
+
+// ── slippage and deadline guards ─────────────────────────────────────────────
+
+#[test]
+fn test_deposit_after_deadline_rejected() {
+    let s = setup();
+    s.approve(100);
+    let deadline = s.env.ledger().timestamp() + 60;
+    s.env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+
+    assert_eq!(
+        s.router.try_deposit(&s.user, &100, &100, &deadline),
+        Err(Ok(RouterError::DeadlinePassed))
+    );
+    assert_eq!(s.allowance(), 100, "nothing was pulled");
+
+    // On the deadline itself the deposit still goes through.
+    s.env.ledger().with_mut(|l| l.timestamp = deadline);
+    s.router.deposit(&s.user, &100, &100, &deadline);
+}
+
+#[test]
+fn test_deposit_below_min_shares_rejected() {
+    let s = setup();
+    s.approve(100);
+    assert_eq!(
+        s.router.try_deposit(&s.user, &100, &101, &s.deadline()),
+        Err(Ok(RouterError::SlippageExceeded))
+    );
+    // The whole call reverted: tokens and allowance are untouched.
+    assert_eq!(s.token.balance(&s.user), 1_000);
+    assert_eq!(s.allowance(), 100);
+}
+
+// ── pool defenses ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_pool_checks_funds_arrived() {
+    let s = setup();
+    // Even with the router's authorization, a report without tokens fails.
+    assert_eq!(
+        s.pool.try_record_deposit(&s.user, &50),
+        Err(Ok(PoolError::FundsNotReceived))
+    );
+    assert_eq!(s.pool.shares(&s.user), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_router_can_record_deposits() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.pool.record_deposit(&s.user, &50);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_deposit_requires_user_auth() {
+    let s = setup();
+    s.approve(100);
+    s.env.set_auths(&[]);
+    s.router.deposit(&s.user, &100, &100, &s.deadline());
+}
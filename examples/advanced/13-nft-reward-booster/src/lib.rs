@@ -0,0 +1,293 @@
+//! # NFT Reward Booster
+//!
+//! Stakers who hold an NFT from a partner collection earn boosted rewards.
+//! Ownership lives in the NFT contract, so the staking contract has to ask:
+//!
+//! ```text
+//! claim(staker)
+//!     └─ boosted?
+//!          ├─ BoostVerified(staker) cached and fresh  → yes, no call
+//!          └─ otherwise: nft.owner_of(token_id) == staker ?
+//!                          ├─ yes → cache for BOOST_CACHE_LEDGERS
+//!                          └─ no  → drop the registration
+//! ```
+//!
+//! Querying the NFT contract on every claim is always correct but costs a
+//! cross-contract call each time. Caching the answer is cheaper but can go
+//! stale the moment the NFT is sold. Two things keep the staleness bounded:
+//!
+//! - the cache entry lives in temporary storage and expires after
+//!   `BOOST_CACHE_LEDGERS`, after which ownership is checked again;
+//! - the NFT contract can call `on_nft_transfer` when a token moves, which
+//!   drops the previous owner's cached boost immediately. Contracts cannot
+//!   subscribe to events, so this has to be an explicit hook; an off-chain
+//!   keeper watching the NFT's transfer events can get the same effect by
+//!   calling the public `refresh_boost`.
+//!
+//! The boost applies to the whole period since the last claim. Claim before
+//! selling the NFT, or after buying one, to get the exact split.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+/// Ledgers a successful ownership check is trusted (~5 minutes).
+pub const BOOST_CACHE_LEDGERS: u32 = 60;
+
+/// Reward per staked unit per second, in basis points.
+pub const REWARD_RATE_BPS: i128 = 10;
+
+/// Multiplier denominator: a boost of 15_000 means 1.5x.
+pub const BOOST_DENOMINATOR: u32 = 10_000;
+
+/// What the staking contract needs from the NFT collection.
+#[contractclient(name = "NftClient")]
+pub trait NftOwnership {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stake {
+    pub amount: i128,
+    pub last_claim: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Nft,
+    BoostBps,
+    Stake(Address),
+    Rewards(Address),
+    /// Token a staker registered for the boost.
+    BoostToken(Address),
+    /// Reverse index so a transfer hook can find the staker to invalidate.
+    BoostHolder(u32),
+    /// Temporary: ownership was verified; value is the last trusted ledger.
+    BoostVerified(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BoostError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// The multiplier is below 1x.
+    InvalidBoost = 4,
+    /// The staker does not own the token they tried to register.
+    NotOwner = 5,
+}
+
+#[contract]
+pub struct BoostedStaking;
+
+#[contractimpl]
+impl BoostedStaking {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        nft: Address,
+        boost_bps: u32,
+    ) -> Result<(), BoostError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(BoostError::AlreadyInitialized);
+        }
+        if boost_bps < BOOST_DENOMINATOR {
+            return Err(BoostError::InvalidBoost);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Nft, &nft);
+        env.storage().instance().set(&DataKey::BoostBps, &boost_bps);
+        Ok(())
+    }
+
+    // ==================== STAKING ====================
+
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<(), BoostError> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(BoostError::InvalidAmount);
+        }
+        Self::settle(&env, &staker)?;
+        let mut stake = Self::stake_of(&env, &staker);
+        stake.amount += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stake(staker), &stake);
+        Ok(())
+    }
+
+    /// Accrue rewards since the last claim (boosted if the staker currently
+    /// qualifies) and return the staker's total rewards.
+    pub fn claim(env: Env, staker: Address) -> Result<i128, BoostError> {
+        staker.require_auth();
+        Self::settle(&env, &staker)?;
+        Ok(Self::rewards(env, staker))
+    }
+
+    pub fn rewards(env: Env, staker: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Rewards(staker))
+            .unwrap_or(0)
+    }
+
+    // ==================== BOOST ====================
+
+    /// Register `token_id` as the staker's boost NFT. Ownership is checked
+    /// against the NFT contract right away.
+    pub fn register_boost(env: Env, staker: Address, token_id: u32) -> Result<(), BoostError> {
+        staker.require_auth();
+        if Self::nft(&env)?.owner_of(&token_id) != staker {
+            return Err(BoostError::NotOwner);
+        }
+        // Accrue the unboosted period before the boost starts applying.
+        Self::settle(&env, &staker)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BoostToken(staker.clone()), &token_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BoostHolder(token_id), &staker);
+        Self::cache_verified(&env, &staker);
+        Ok(())
+    }
+
+    /// Whether `staker` currently gets the boost. Uses the cached check when
+    /// fresh; otherwise asks the NFT contract and re-caches.
+    pub fn is_boosted(env: Env, staker: Address) -> Result<bool, BoostError> {
+        Self::check_boost(&env, &staker)
+    }
+
+    /// Drop the cached check so the next read asks the NFT contract. Anyone
+    /// may call this, e.g. a keeper reacting to the NFT's transfer events.
+    pub fn refresh_boost(env: Env, staker: Address) -> Result<bool, BoostError> {
+        env.storage()
+            .temporary()
+            .remove(&DataKey::BoostVerified(staker.clone()));
+        Self::check_boost(&env, &staker)
+    }
+
+    /// Transfer hook for the NFT contract: `token_id` just left `from`.
+    /// Only the NFT contract may call it.
+    ///
+    /// Emits `(boost, revoked)` with the staker if a boost was dropped.
+    pub fn on_nft_transfer(env: Env, token_id: u32, from: Address) -> Result<(), BoostError> {
+        Self::nft(&env)?.address.require_auth();
+        let holder: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BoostHolder(token_id));
+        if holder == Some(from.clone()) {
+            Self::drop_boost(&env, &from, token_id);
+        }
+        Ok(())
+    }
+
+    // ==================== HELPERS ====================
+
+    fn check_boost(env: &Env, staker: &Address) -> Result<bool, BoostError> {
+        let Some(token_id) = env
+            .storage()
+            .persistent()
+            .get::<_, u32>(&DataKey::BoostToken(staker.clone()))
+        else {
+            return Ok(false);
+        };
+
+        let verified: Option<u32> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::BoostVerified(staker.clone()));
+        if matches!(verified, Some(until) if env.ledger().sequence() <= until) {
+            return Ok(true);
+        }
+
+        if Self::nft(env)?.owner_of(&token_id) == *staker {
+            Self::cache_verified(env, staker);
+            Ok(true)
+        } else {
+            Self::drop_boost(env, staker, token_id);
+            Ok(false)
+        }
+    }
+
+    fn cache_verified(env: &Env, staker: &Address) {
+        let key = DataKey::BoostVerified(staker.clone());
+        let until = env.ledger().sequence() + BOOST_CACHE_LEDGERS;
+        env.storage().temporary().set(&key, &until);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, BOOST_CACHE_LEDGERS, BOOST_CACHE_LEDGERS);
+    }
+
+    fn drop_boost(env: &Env, staker: &Address, token_id: u32) {
+        env.storage()
+            .temporary()
+            .remove(&DataKey::BoostVerified(staker.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BoostToken(staker.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BoostHolder(token_id));
+        env.events().publish(
+            (symbol_short!("boost"), symbol_short!("revoked")),
+            staker.clone(),
+        );
+    }
+
+    /// Move rewards accrued since `last_claim` into `Rewards(staker)`.
+    fn settle(env: &Env, staker: &Address) -> Result<(), BoostError> {
+        let mut stake = Self::stake_of(env, staker);
+        let now = env.ledger().timestamp();
+        let elapsed = (now - stake.last_claim) as i128;
+
+        if stake.amount > 0 && elapsed > 0 {
+            let mut reward = stake.amount * elapsed * REWARD_RATE_BPS / 10_000;
+            if Self::check_boost(env, staker)? {
+                let boost_bps: u32 = env.storage().instance().get(&DataKey::BoostBps).unwrap();
+                reward = reward * boost_bps as i128 / BOOST_DENOMINATOR as i128;
+            }
+            let total = Self::rewards(env.clone(), staker.clone()) + reward;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Rewards(staker.clone()), &total);
+        }
+
+        stake.last_claim = now;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stake(staker.clone()), &stake);
+        Ok(())
+    }
+
+    fn stake_of(env: &Env, staker: &Address) -> Stake {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stake(staker.clone()))
+            .unwrap_or(Stake {
+                amount: 0,
+                last_claim: env.ledger().timestamp(),
+            })
+    }
+
+    fn nft(env: &Env) -> Result<NftClient<'_>, BoostError> {
+        let nft: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Nft)
+            .ok_or(BoostError::NotInitialized)?;
+        Ok(NftClient::new(env, &nft))
+    }
+}
+
+mod nft;
+mod test;
@@ -0,0 +1,74 @@
+//! Reference NFT collection.
+//!
+//! Only compiled for tests: in a real deployment it would be its own crate
+//! and Wasm. It counts `owner_of` calls so tests can tell cache hits from
+//! cross-contract reads.
+
+#![cfg(test)]
+
+use crate::BoostedStakingClient;
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum NftKey {
+    Owner(u32),
+    /// Staking contract notified on every transfer.
+    Hook,
+    Reads,
+}
+
+#[contract]
+pub struct TestNft;
+
+#[contractimpl]
+impl TestNft {
+    pub fn mint(env: Env, to: Address, token_id: u32) {
+        env.storage().instance().set(&NftKey::Owner(token_id), &to);
+    }
+
+    pub fn set_hook(env: Env, staking: Address) {
+        env.storage().instance().set(&NftKey::Hook, &staking);
+    }
+
+    /// Move a token, emit `(transfer, from, to)` and call the hook.
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
+        Self::move_token(&env, &from, &to, token_id);
+        if let Some(staking) = env.storage().instance().get::<_, Address>(&NftKey::Hook) {
+            BoostedStakingClient::new(&env, &staking).on_nft_transfer(&token_id, &from);
+        }
+    }
+
+    /// Move a token without calling the hook.
+    pub fn transfer_silently(env: Env, from: Address, to: Address, token_id: u32) {
+        Self::move_token(&env, &from, &to, token_id);
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        let reads: u32 = env.storage().instance().get(&NftKey::Reads).unwrap_or(0);
+        env.storage().instance().set(&NftKey::Reads, &(reads + 1));
+        env.storage()
+            .instance()
+            .get(&NftKey::Owner(token_id))
+            .unwrap()
+    }
+
+    pub fn reads(env: Env) -> u32 {
+        env.storage().instance().get(&NftKey::Reads).unwrap_or(0)
+    }
+
+    fn move_token(env: &Env, from: &Address, to: &Address, token_id: u32) {
+        from.require_auth();
+        let owner: Address = env
+            .storage()
+            .instance()
+            .get(&NftKey::Owner(token_id))
+            .unwrap();
+        assert!(owner == *from, "not owner");
+        env.storage().instance().set(&NftKey::Owner(token_id), to);
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone(), to.clone()),
+            token_id,
+        );
+    }
+}
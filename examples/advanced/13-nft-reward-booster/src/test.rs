@@ -0,0 +1,195 @@
+#![cfg(test)]
+
+use super::*;
+use crate::nft::{TestNft, TestNftClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal,
+};
+
+const TOKEN: u32 = 7;
+
+struct Setup {
+    env: Env,
+    staking: BoostedStakingClient<'static>,
+    nft: TestNftClient<'static>,
+    staker: Address,
+}
+
+/// 1.5x boost; `staker` owns `TOKEN` and has 1_000 staked.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let nft_id = env.register_contract(None, TestNft);
+    let nft = TestNftClient::new(&env, &nft_id);
+
+    let staking_id = env.register_contract(None, BoostedStaking);
+    let staking = BoostedStakingClient::new(&env, &staking_id);
+    staking.initialize(&Address::generate(&env), &nft_id, &15_000);
+    nft.set_hook(&staking_id);
+
+    let staker = Address::generate(&env);
+    nft.mint(&staker, &TOKEN);
+    staking.stake(&staker, &1_000);
+
+    Setup {
+        env,
+        staking,
+        nft,
+        staker,
+    }
+}
+
+fn advance(env: &Env, seconds: u64, ledgers: u32) {
+    env.ledger().with_mut(|l| {
+        l.timestamp += seconds;
+        l.sequence_number += ledgers;
+    });
+}
+
+// ── rewards ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_unboosted_rewards() {
+    let s = setup();
+    advance(&s.env, 100, 1);
+    // 1_000 * 100s * 10 bps
+    assert_eq!(s.staking.claim(&s.staker), 100);
+}
+
+#[test]
+fn test_boosted_rewards() {
+    let s = setup();
+    s.staking.register_boost(&s.staker, &TOKEN);
+    advance(&s.env, 100, 1);
+    assert_eq!(s.staking.claim(&s.staker), 150);
+}
+
+#[test]
+fn test_register_settles_unboosted_period_first() {
+    let s = setup();
+    advance(&s.env, 100, 1);
+    s.staking.register_boost(&s.staker, &TOKEN);
+    advance(&s.env, 100, 1);
+    assert_eq!(s.staking.claim(&s.staker), 100 + 150);
+}
+
+#[test]
+fn test_cannot_register_token_owned_by_someone_else() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.staking.try_register_boost(&other, &TOKEN),
+        Err(Ok(BoostError::NotOwner))
+    );
+    assert!(!s.staking.is_boosted(&other));
+}
+
+#[test]
+fn test_boost_below_one_x_rejected() {
+    let env = Env::default();
+    let staking_id = env.register_contract(None, BoostedStaking);
+    let staking = BoostedStakingClient::new(&env, &staking_id);
+    let nft = Address::generate(&env);
+    assert_eq!(
+        staking.try_initialize(&Address::generate(&env), &nft, &9_999),
+        Err(Ok(BoostError::InvalidBoost))
+    );
+}
+
+// ── caching ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_cached_check_skips_ownership_query() {
+    let s = setup();
+    s.staking.register_boost(&s.staker, &TOKEN);
+    let reads = s.nft.reads();
+
+    for _ in 0..3 {
+        advance(&s.env, 10, 1);
+        s.staking.claim(&s.staker);
+    }
+    assert_eq!(s.nft.reads(), reads);
+}
+
+#[test]
+fn test_expired_cache_requeries_owner() {
+    let s = setup();
+    s.staking.register_boost(&s.staker, &TOKEN);
+    let reads = s.nft.reads();
+
+    advance(&s.env, 10, BOOST_CACHE_LEDGERS + 1);
+    assert!(s.staking.is_boosted(&s.staker));
+    assert_eq!(s.nft.reads(), reads + 1);
+
+    // Re-cached: the next check is free again.
+    assert!(s.staking.is_boosted(&s.staker));
+    assert_eq!(s.nft.reads(), reads + 1);
+}
+
+// ── invalidation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_transfer_hook_revokes_boost_immediately() {
+    let s = setup();
+    s.staking.register_boost(&s.staker, &TOKEN);
+
+    let buyer = Address::generate(&s.env);
+    s.nft.transfer(&s.staker, &buyer, &TOKEN);
+
+    let event = s.env.events().all().last().unwrap();
+    assert_eq!(event.0, s.staking.address);
+    assert_eq!(
+        event.1,
+        (symbol_short!("boost"), symbol_short!("revoked")).into_val(&s.env)
+    );
+
+    assert!(!s.staking.is_boosted(&s.staker));
+    advance(&s.env, 100, 1);
+    assert_eq!(s.staking.claim(&s.staker), 100);
+}
+
+#[test]
+fn test_hook_ignores_tokens_not_registered() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    s.nft.mint(&other, &99);
+    s.staking.register_boost(&s.staker, &TOKEN);
+
+    s.nft.transfer(&other, &Address::generate(&s.env), &99);
+    assert!(s.staking.is_boosted(&s.staker));
+}
+
+#[test]
+fn test_silent_transfer_is_stale_until_cache_expires() {
+    // The cost of caching: without the hook the seller keeps the boost
+    // until the cached check runs out.
+    let s = setup();
+    s.staking.register_boost(&s.staker, &TOKEN);
+    s.nft
+        .transfer_silently(&s.staker, &Address::generate(&s.env), &TOKEN);
+    assert!(s.staking.is_boosted(&s.staker));
+
+    advance(&s.env, 10, BOOST_CACHE_LEDGERS + 1);
+    assert!(!s.staking.is_boosted(&s.staker));
+}
+
+#[test]
+fn test_refresh_boost_clears_stale_cache() {
+    let s = setup();
+    s.staking.register_boost(&s.staker, &TOKEN);
+    s.nft
+        .transfer_silently(&s.staker, &Address::generate(&s.env), &TOKEN);
+
+    assert!(!s.staking.refresh_boost(&s.staker));
+    assert!(!s.staking.is_boosted(&s.staker));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_nft_contract_can_call_hook() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.staking.on_nft_transfer(&TOKEN, &s.staker);
+}
@@ -0,0 +1,244 @@
+//! # Dispute Bonds
+//!
+//! An escrow where either party can escalate to an arbiter, but only by
+//! putting money on the line. Opening a dispute locks a bond from the
+//! disputer; the arbiter's ruling decides who gets the escrowed funds *and*
+//! what happens to the bond:
+//!
+//! ```text
+//! Funded ──release (buyer)──▶ Released      amount → seller
+//!    │   ──refund (seller)──▶ Refunded      amount → buyer
+//!    │
+//!    └─open_dispute(party) + bond ──▶ Disputed
+//!                                        │
+//!                        resolve(arbiter, to_buyer)
+//!                                        ▼
+//!                               Resolved  amount + bond → winner
+//! ```
+//!
+//! Without a bond a losing party can dispute for free, stalling the escrow
+//! and wasting the arbiter's time. With one, a dispute only pays off for a
+//! party who expects to win. The bond always follows the ruling: a winning
+//! disputer gets it back, and a losing one forfeits it to the counterparty,
+//! who was made to wait.
+//!
+//! The contract holds every escrowed amount plus every posted bond; the
+//! accounting below keeps `held()` equal to the contract's token balance.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowState {
+    Funded,
+    Released,
+    Refunded,
+    Disputed,
+    Resolved,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Escrow {
+    pub buyer: Address,
+    pub seller: Address,
+    pub arbiter: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Bond required to open a dispute.
+    pub bond: i128,
+    pub state: EscrowState,
+    /// Party that opened the dispute and posted the bond.
+    pub disputer: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextId,
+    Escrow(u32),
+    /// Total escrowed amounts and bonds held per token.
+    Held(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EscrowError {
+    NotFound = 1,
+    InvalidAmount = 2,
+    /// The escrow is not in a state that allows this call.
+    InvalidState = 3,
+    /// The caller is neither the buyer nor the seller.
+    NotParty = 4,
+}
+
+#[contract]
+pub struct DisputeBondEscrow;
+
+#[contractimpl]
+impl DisputeBondEscrow {
+    /// Lock `amount` from the buyer. `bond` is what either party must post to
+    /// open a dispute later.
+    ///
+    /// Emits `(escrow, created)` with the escrow id.
+    pub fn create(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+        bond: i128,
+    ) -> Result<u32, EscrowError> {
+        buyer.require_auth();
+        if amount <= 0 || bond <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        Self::pull(&env, &token, &buyer, amount);
+
+        let id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        Self::save(
+            &env,
+            id,
+            &Escrow {
+                buyer,
+                seller,
+                arbiter,
+                token,
+                amount,
+                bond,
+                state: EscrowState::Funded,
+                disputer: None,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("escrow"), symbol_short!("created")), id);
+        Ok(id)
+    }
+
+    /// Buyer is satisfied: pay the seller.
+    pub fn release(env: Env, id: u32) -> Result<(), EscrowError> {
+        let mut escrow = Self::load_in(&env, id, EscrowState::Funded)?;
+        escrow.buyer.require_auth();
+        Self::push(&env, &escrow.token, &escrow.seller, escrow.amount);
+        escrow.state = EscrowState::Released;
+        Self::save(&env, id, &escrow);
+        env.events()
+            .publish((symbol_short!("escrow"), symbol_short!("released")), id);
+        Ok(())
+    }
+
+    /// Seller gives up: refund the buyer.
+    pub fn refund(env: Env, id: u32) -> Result<(), EscrowError> {
+        let mut escrow = Self::load_in(&env, id, EscrowState::Funded)?;
+        escrow.seller.require_auth();
+        Self::push(&env, &escrow.token, &escrow.buyer, escrow.amount);
+        escrow.state = EscrowState::Refunded;
+        Self::save(&env, id, &escrow);
+        env.events()
+            .publish((symbol_short!("escrow"), symbol_short!("refunded")), id);
+        Ok(())
+    }
+
+    /// Escalate to the arbiter. `disputer` must be the buyer or the seller
+    /// and posts the escrow's bond.
+    ///
+    /// Emits `(dispute, opened)` with `(id, disputer, bond)`.
+    pub fn open_dispute(env: Env, id: u32, disputer: Address) -> Result<(), EscrowError> {
+        let mut escrow = Self::load_in(&env, id, EscrowState::Funded)?;
+        if disputer != escrow.buyer && disputer != escrow.seller {
+            return Err(EscrowError::NotParty);
+        }
+        disputer.require_auth();
+        Self::pull(&env, &escrow.token, &disputer, escrow.bond);
+
+        escrow.state = EscrowState::Disputed;
+        escrow.disputer = Some(disputer.clone());
+        Self::save(&env, id, &escrow);
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("opened")),
+            (id, disputer, escrow.bond),
+        );
+        Ok(())
+    }
+
+    /// Arbiter's ruling. The escrowed amount goes to the buyer if `to_buyer`,
+    /// otherwise to the seller. The bond goes back to the disputer if they
+    /// won and to the counterparty if they lost.
+    ///
+    /// Emits `(dispute, resolved)` with `(id, winner, Some(disputer))`.
+    pub fn resolve(env: Env, id: u32, to_buyer: bool) -> Result<(), EscrowError> {
+        let mut escrow = Self::load_in(&env, id, EscrowState::Disputed)?;
+        escrow.arbiter.require_auth();
+
+        let winner = if to_buyer {
+            escrow.buyer.clone()
+        } else {
+            escrow.seller.clone()
+        };
+        // A winning disputer gets their bond back; a losing one forfeits it
+        // to the counterparty. Either way it goes to the winner.
+        Self::push(&env, &escrow.token, &winner, escrow.amount + escrow.bond);
+
+        escrow.state = EscrowState::Resolved;
+        Self::save(&env, id, &escrow);
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("resolved")),
+            (id, winner, escrow.disputer),
+        );
+        Ok(())
+    }
+
+    pub fn escrow(env: Env, id: u32) -> Option<Escrow> {
+        env.storage().persistent().get(&DataKey::Escrow(id))
+    }
+
+    /// Escrowed amounts plus bonds the contract currently holds in `token`.
+    pub fn held(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Held(token))
+            .unwrap_or(0)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn load_in(env: &Env, id: u32, state: EscrowState) -> Result<Escrow, EscrowError> {
+        let escrow = Self::escrow(env.clone(), id).ok_or(EscrowError::NotFound)?;
+        if escrow.state != state {
+            return Err(EscrowError::InvalidState);
+        }
+        Ok(escrow)
+    }
+
+    fn save(env: &Env, id: u32, escrow: &Escrow) {
+        env.storage().persistent().set(&DataKey::Escrow(id), escrow);
+    }
+
+    /// Move tokens in and add them to `Held(token)`.
+    fn pull(env: &Env, token: &Address, from: &Address, amount: i128) {
+        token::Client::new(env, token).transfer(from, &env.current_contract_address(), &amount);
+        Self::adjust_held(env, token, amount);
+    }
+
+    /// Move tokens out and subtract them from `Held(token)`.
+    fn push(env: &Env, token: &Address, to: &Address, amount: i128) {
+        token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+        Self::adjust_held(env, token, -amount);
+    }
+
+    fn adjust_held(env: &Env, token: &Address, delta: i128) {
+        let held = Self::held(env.clone(), token.clone()) + delta;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Held(token.clone()), &held);
+    }
+}
+
+mod test;
@@ -0,0 +1,259 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, IntoVal,
+};
+
+const AMOUNT: i128 = 1_000;
+const BOND: i128 = 100;
+
+struct Setup {
+    env: Env,
+    escrow: DisputeBondEscrowClient<'static>,
+    token: TokenClient<'static>,
+    buyer: Address,
+    seller: Address,
+    arbiter: Address,
+    id: u32,
+}
+
+/// One funded escrow; both parties hold enough to post a bond.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let escrow =
+        DisputeBondEscrowClient::new(&env, &env.register_contract(None, DisputeBondEscrow));
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let minter = StellarAssetClient::new(&env, &token_id);
+    minter.mint(&buyer, &(AMOUNT + BOND));
+    minter.mint(&seller, &BOND);
+
+    let id = escrow.create(&buyer, &seller, &arbiter, &token_id, &AMOUNT, &BOND);
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        escrow,
+        buyer,
+        seller,
+        arbiter,
+        id,
+    }
+}
+
+impl Setup {
+    fn balance(&self, who: &Address) -> i128 {
+        self.token.balance(who)
+    }
+
+    fn state(&self) -> EscrowState {
+        self.escrow.escrow(&self.id).unwrap().state
+    }
+
+    /// The contract's token balance matches its own accounting.
+    fn assert_conserved(&self) {
+        assert_eq!(
+            self.token.balance(&self.escrow.address),
+            self.escrow.held(&self.token.address)
+        );
+    }
+}
+
+// ── base escrow ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_release_pays_seller() {
+    let s = setup();
+    assert_eq!(s.escrow.held(&s.token.address), AMOUNT);
+    s.escrow.release(&s.id);
+    assert_eq!(s.balance(&s.seller), BOND + AMOUNT);
+    assert_eq!(s.state(), EscrowState::Released);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_refund_pays_buyer() {
+    let s = setup();
+    s.escrow.refund(&s.id);
+    assert_eq!(s.balance(&s.buyer), AMOUNT + BOND);
+    assert_eq!(s.state(), EscrowState::Refunded);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_invalid_amounts_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.escrow
+            .try_create(&s.buyer, &s.seller, &s.arbiter, &s.token.address, &0, &BOND),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+    assert_eq!(
+        s.escrow.try_create(
+            &s.buyer,
+            &s.seller,
+            &s.arbiter,
+            &s.token.address,
+            &AMOUNT,
+            &0
+        ),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+}
+
+// ── opening disputes ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_open_dispute_locks_bond() {
+    let s = setup();
+    s.escrow.open_dispute(&s.id, &s.seller);
+
+    assert_eq!(s.balance(&s.seller), 0);
+    assert_eq!(s.escrow.held(&s.token.address), AMOUNT + BOND);
+    assert_eq!(s.state(), EscrowState::Disputed);
+    assert_eq!(
+        s.escrow.escrow(&s.id).unwrap().disputer,
+        Some(s.seller.clone())
+    );
+    s.assert_conserved();
+
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("dispute"), symbol_short!("opened")).into_val(&s.env)
+    );
+    let data: (u32, Address, i128) = data.into_val(&s.env);
+    assert_eq!(data, (s.id, s.seller.clone(), BOND));
+}
+
+#[test]
+fn test_outsider_cannot_dispute() {
+    let s = setup();
+    assert_eq!(
+        s.escrow.try_open_dispute(&s.id, &s.arbiter),
+        Err(Ok(EscrowError::NotParty))
+    );
+}
+
+#[test]
+fn test_dispute_without_bond_funds_fails() {
+    let s = setup();
+    s.token.transfer(&s.seller, &s.buyer, &BOND);
+    assert!(s.escrow.try_open_dispute(&s.id, &s.seller).is_err());
+    assert_eq!(s.state(), EscrowState::Funded);
+}
+
+#[test]
+fn test_dispute_freezes_release_and_refund() {
+    let s = setup();
+    s.escrow.open_dispute(&s.id, &s.buyer);
+    assert_eq!(
+        s.escrow.try_release(&s.id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.escrow.try_refund(&s.id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.escrow.try_open_dispute(&s.id, &s.seller),
+        Err(Ok(EscrowError::InvalidState))
+    );
+}
+
+// ── rulings and bond accounting ──────────────────────────────────────────────
+
+#[test]
+fn test_disputer_wins_bond_returned() {
+    let s = setup();
+    s.escrow.open_dispute(&s.id, &s.buyer);
+    s.escrow.resolve(&s.id, &true);
+
+    assert_eq!(s.balance(&s.buyer), AMOUNT + BOND);
+    assert_eq!(s.balance(&s.seller), BOND);
+    assert_eq!(s.state(), EscrowState::Resolved);
+    assert_eq!(s.escrow.held(&s.token.address), 0);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_disputer_loses_bond_forfeited_to_counterparty() {
+    let s = setup();
+    s.escrow.open_dispute(&s.id, &s.buyer);
+    s.escrow.resolve(&s.id, &false);
+
+    assert_eq!(s.balance(&s.buyer), 0);
+    assert_eq!(s.balance(&s.seller), BOND + AMOUNT + BOND);
+    s.assert_conserved();
+
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("dispute"), symbol_short!("resolved")).into_val(&s.env)
+    );
+    let data: (u32, Address, Address) = data.into_val(&s.env);
+    assert_eq!(data, (s.id, s.seller.clone(), s.buyer.clone()));
+}
+
+#[test]
+fn test_seller_disputer_loses_bond_to_buyer() {
+    let s = setup();
+    s.escrow.open_dispute(&s.id, &s.seller);
+    s.escrow.resolve(&s.id, &true);
+
+    assert_eq!(s.balance(&s.buyer), BOND + AMOUNT + BOND);
+    assert_eq!(s.balance(&s.seller), 0);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_bonds_tracked_across_escrows() {
+    let s = setup();
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.buyer, &AMOUNT);
+    let second = s.escrow.create(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &s.token.address,
+        &AMOUNT,
+        &BOND,
+    );
+    s.escrow.open_dispute(&s.id, &s.seller);
+    s.escrow.open_dispute(&second, &s.buyer);
+    assert_eq!(s.escrow.held(&s.token.address), 2 * AMOUNT + 2 * BOND);
+
+    s.escrow.resolve(&s.id, &false);
+    assert_eq!(s.escrow.held(&s.token.address), AMOUNT + BOND);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_resolve_requires_dispute() {
+    let s = setup();
+    assert_eq!(
+        s.escrow.try_resolve(&s.id, &true),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.escrow.try_resolve(&99, &true),
+        Err(Ok(EscrowError::NotFound))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_arbiter_can_resolve() {
+    let s = setup();
+    s.escrow.open_dispute(&s.id, &s.buyer);
+    s.env.set_auths(&[]);
+    s.escrow.resolve(&s.id, &true);
+}
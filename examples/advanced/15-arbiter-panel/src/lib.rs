@@ -0,0 +1,412 @@
+//! # Arbiter Panel
+//!
+//! Disputes are decided by one arbiter drawn at random from a staked panel,
+//! rather than by a single fixed arbiter:
+//!
+//! ```text
+//! join(arbiter, stake)            stake ≥ min_stake → eligible
+//!
+//! open_dispute(claimant, respondent, amount)
+//!     └─ amount escrowed, arbiter = prng pick from eligible panel
+//!
+//! assigned arbiter:  rule(for_claimant)   → amount to the winner
+//!                    recuse()             → redraw, excluding them
+//! anyone, after RULING_TIMEOUT:
+//!                    reassign()           → slash SLASH_BPS of the stake,
+//!                                           redraw, excluding them
+//! ```
+//!
+//! A random draw means neither party can pick a friendly arbiter, and an
+//! arbiter cannot know in advance which disputes they will get. The stake is
+//! what makes ignoring an assignment costly: a timed-out arbiter loses part
+//! of it, and the slashed amount is added to the disputed funds.
+//!
+//! `env.prng()` is seeded by the network per transaction. It is fine for
+//! spreading work across a panel but is not unpredictable to validators;
+//! do not use it where a manipulated draw is worth more than the cost of
+//! influencing a ledger.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec,
+};
+
+/// Seconds an assigned arbiter has to rule before anyone may reassign.
+pub const RULING_TIMEOUT: u64 = 3 * 24 * 60 * 60;
+
+/// Share of a timed-out arbiter's stake that is slashed, in basis points.
+pub const SLASH_BPS: i128 = 1_000;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Ruled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub claimant: Address,
+    pub respondent: Address,
+    /// Escrowed funds, plus any stake slashed from timed-out arbiters.
+    pub amount: i128,
+    pub arbiter: Address,
+    pub assigned_at: u64,
+    /// Arbiters who recused or timed out on this dispute.
+    pub excluded: Vec<Address>,
+    pub status: DisputeStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    MinStake,
+    /// Registered arbiters, eligible or not.
+    Panel,
+    Stake(Address),
+    /// Open disputes assigned to an arbiter.
+    Assignments(Address),
+    NextId,
+    Dispute(u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PanelError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientStake = 4,
+    AlreadyArbiter = 5,
+    NotArbiter = 6,
+    /// The arbiter still has open disputes assigned.
+    HasOpenDisputes = 7,
+    /// No staked arbiter is left who is not a party or excluded.
+    NoEligibleArbiter = 8,
+    NotFound = 9,
+    AlreadyRuled = 10,
+    TimeoutNotReached = 11,
+}
+
+#[contract]
+pub struct ArbiterPanel;
+
+#[contractimpl]
+impl ArbiterPanel {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_stake: i128,
+    ) -> Result<(), PanelError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PanelError::AlreadyInitialized);
+        }
+        if min_stake <= 0 {
+            return Err(PanelError::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::MinStake, &min_stake);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Panel, &Vec::<Address>::new(&env));
+        Ok(())
+    }
+
+    // ==================== PANEL ====================
+
+    /// Stake at least `min_stake` and join the panel.
+    ///
+    /// Emits `(arbiter, joined)` with the stake.
+    pub fn join(env: Env, arbiter: Address, stake: i128) -> Result<(), PanelError> {
+        arbiter.require_auth();
+        if stake < Self::min_stake(&env)? {
+            return Err(PanelError::InsufficientStake);
+        }
+        let mut panel = Self::panel(env.clone());
+        if panel.contains(&arbiter) {
+            return Err(PanelError::AlreadyArbiter);
+        }
+        Self::token(&env)?.transfer(&arbiter, &env.current_contract_address(), &stake);
+
+        panel.push_back(arbiter.clone());
+        env.storage().persistent().set(&DataKey::Panel, &panel);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stake(arbiter.clone()), &stake);
+        env.events().publish(
+            (symbol_short!("arbiter"), symbol_short!("joined")),
+            (arbiter, stake),
+        );
+        Ok(())
+    }
+
+    /// Leave the panel and withdraw the remaining stake. Only allowed with
+    /// no open disputes assigned.
+    pub fn leave(env: Env, arbiter: Address) -> Result<i128, PanelError> {
+        arbiter.require_auth();
+        let mut panel = Self::panel(env.clone());
+        let index = panel
+            .first_index_of(&arbiter)
+            .ok_or(PanelError::NotArbiter)?;
+        if Self::assignments(env.clone(), arbiter.clone()) > 0 {
+            return Err(PanelError::HasOpenDisputes);
+        }
+
+        let stake = Self::stake(env.clone(), arbiter.clone());
+        panel.remove(index);
+        env.storage().persistent().set(&DataKey::Panel, &panel);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Stake(arbiter.clone()));
+        Self::token(&env)?.transfer(&env.current_contract_address(), &arbiter, &stake);
+        env.events().publish(
+            (symbol_short!("arbiter"), symbol_short!("left")),
+            (arbiter, stake),
+        );
+        Ok(stake)
+    }
+
+    // ==================== DISPUTES ====================
+
+    /// Escrow `amount` from the claimant and draw an arbiter.
+    ///
+    /// Emits `(dispute, assigned)` with `(id, arbiter)`.
+    pub fn open_dispute(
+        env: Env,
+        claimant: Address,
+        respondent: Address,
+        amount: i128,
+    ) -> Result<u32, PanelError> {
+        claimant.require_auth();
+        if amount <= 0 {
+            return Err(PanelError::InvalidAmount);
+        }
+
+        let id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        let excluded = Vec::new(&env);
+        let arbiter = Self::draw(&env, id, &claimant, &respondent, &excluded)?;
+        Self::token(&env)?.transfer(&claimant, &env.current_contract_address(), &amount);
+
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        Self::save(
+            &env,
+            id,
+            &Dispute {
+                claimant,
+                respondent,
+                amount,
+                arbiter,
+                assigned_at: env.ledger().timestamp(),
+                excluded,
+                status: DisputeStatus::Open,
+            },
+        );
+        Ok(id)
+    }
+
+    /// The assigned arbiter's ruling. Pays the escrowed amount to the
+    /// claimant if `for_claimant`, otherwise to the respondent.
+    ///
+    /// Emits `(dispute, ruled)` with `(id, winner)`.
+    pub fn rule(env: Env, id: u32, for_claimant: bool) -> Result<(), PanelError> {
+        let mut dispute = Self::load_open(&env, id)?;
+        dispute.arbiter.require_auth();
+
+        let winner = if for_claimant {
+            dispute.claimant.clone()
+        } else {
+            dispute.respondent.clone()
+        };
+        dispute.status = DisputeStatus::Ruled;
+        Self::release_assignment(&env, &dispute.arbiter);
+        Self::save(&env, id, &dispute);
+        Self::token(&env)?.transfer(&env.current_contract_address(), &winner, &dispute.amount);
+
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("ruled")),
+            (id, winner),
+        );
+        Ok(())
+    }
+
+    /// The assigned arbiter steps aside (e.g. a conflict of interest). A new
+    /// arbiter is drawn; the recused one is never drawn for this dispute
+    /// again.
+    ///
+    /// Emits `(dispute, recused)` with `(id, arbiter)`.
+    pub fn recuse(env: Env, id: u32) -> Result<Address, PanelError> {
+        let mut dispute = Self::load_open(&env, id)?;
+        let arbiter = dispute.arbiter.clone();
+        arbiter.require_auth();
+
+        Self::release_assignment(&env, &arbiter);
+        dispute.excluded.push_back(arbiter.clone());
+        Self::redraw(&env, id, &mut dispute)?;
+        Self::save(&env, id, &dispute);
+
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("recused")),
+            (id, arbiter),
+        );
+        Ok(dispute.arbiter)
+    }
+
+    /// Anyone may call this once the assigned arbiter has let
+    /// `RULING_TIMEOUT` pass. Slashes `SLASH_BPS` of their stake into the
+    /// dispute and draws someone else.
+    ///
+    /// Emits `(dispute, timeout)` with `(id, arbiter, slashed)`.
+    pub fn reassign(env: Env, id: u32) -> Result<Address, PanelError> {
+        let mut dispute = Self::load_open(&env, id)?;
+        if env.ledger().timestamp() < dispute.assigned_at + RULING_TIMEOUT {
+            return Err(PanelError::TimeoutNotReached);
+        }
+        let arbiter = dispute.arbiter.clone();
+
+        let stake = Self::stake(env.clone(), arbiter.clone());
+        let slashed = stake * SLASH_BPS / 10_000;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stake(arbiter.clone()), &(stake - slashed));
+        dispute.amount += slashed;
+
+        Self::release_assignment(&env, &arbiter);
+        dispute.excluded.push_back(arbiter.clone());
+        Self::redraw(&env, id, &mut dispute)?;
+        Self::save(&env, id, &dispute);
+
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("timeout")),
+            (id, arbiter, slashed),
+        );
+        Ok(dispute.arbiter)
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn dispute(env: Env, id: u32) -> Option<Dispute> {
+        env.storage().persistent().get(&DataKey::Dispute(id))
+    }
+
+    pub fn panel(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Panel)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn stake(env: Env, arbiter: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stake(arbiter))
+            .unwrap_or(0)
+    }
+
+    pub fn assignments(env: Env, arbiter: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Assignments(arbiter))
+            .unwrap_or(0)
+    }
+
+    // ==================== HELPERS ====================
+
+    /// Draw an arbiter from the panel members who are staked at least
+    /// `min_stake`, are not a party, and are not excluded, and count the
+    /// assignment against them.
+    ///
+    /// Emits `(dispute, assigned)` with `(id, arbiter)`.
+    fn draw(
+        env: &Env,
+        id: u32,
+        claimant: &Address,
+        respondent: &Address,
+        excluded: &Vec<Address>,
+    ) -> Result<Address, PanelError> {
+        let min_stake = Self::min_stake(env)?;
+        let mut eligible = Vec::new(env);
+        for arbiter in Self::panel(env.clone()).iter() {
+            if arbiter != *claimant
+                && arbiter != *respondent
+                && !excluded.contains(&arbiter)
+                && Self::stake(env.clone(), arbiter.clone()) >= min_stake
+            {
+                eligible.push_back(arbiter);
+            }
+        }
+        if eligible.is_empty() {
+            return Err(PanelError::NoEligibleArbiter);
+        }
+
+        let index = env.prng().gen_range::<u64>(0..eligible.len() as u64) as u32;
+        let arbiter = eligible.get_unchecked(index);
+        let open = Self::assignments(env.clone(), arbiter.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Assignments(arbiter.clone()), &(open + 1));
+
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("assigned")),
+            (id, arbiter.clone()),
+        );
+        Ok(arbiter)
+    }
+
+    fn redraw(env: &Env, id: u32, dispute: &mut Dispute) -> Result<(), PanelError> {
+        dispute.arbiter = Self::draw(
+            env,
+            id,
+            &dispute.claimant,
+            &dispute.respondent,
+            &dispute.excluded,
+        )?;
+        dispute.assigned_at = env.ledger().timestamp();
+        Ok(())
+    }
+
+    fn release_assignment(env: &Env, arbiter: &Address) {
+        let open = Self::assignments(env.clone(), arbiter.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Assignments(arbiter.clone()), &(open - 1));
+    }
+
+    fn load_open(env: &Env, id: u32) -> Result<Dispute, PanelError> {
+        let dispute = Self::dispute(env.clone(), id).ok_or(PanelError::NotFound)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(PanelError::AlreadyRuled);
+        }
+        Ok(dispute)
+    }
+
+    fn save(env: &Env, id: u32, dispute: &Dispute) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(id), dispute);
+    }
+
+    fn min_stake(env: &Env) -> Result<i128, PanelError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinStake)
+            .ok_or(PanelError::NotInitialized)
+    }
+
+    fn token(env: &Env) -> Result<token::Client<'_>, PanelError> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(PanelError::NotInitialized)?;
+        Ok(token::Client::new(env, &token))
+    }
+}
+
+mod test;
@@ -0,0 +1,262 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const MIN_STAKE: i128 = 100;
+const AMOUNT: i128 = 1_000;
+
+struct Setup {
+    env: Env,
+    panel: ArbiterPanelClient<'static>,
+    token: TokenClient<'static>,
+    arbiters: [Address; 3],
+    claimant: Address,
+    respondent: Address,
+}
+
+/// Three arbiters staked at exactly `MIN_STAKE`; the claimant can fund
+/// several disputes.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let minter = StellarAssetClient::new(&env, &token_id);
+    let panel = ArbiterPanelClient::new(&env, &env.register_contract(None, ArbiterPanel));
+    panel.initialize(&Address::generate(&env), &token_id, &MIN_STAKE);
+
+    let arbiters = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for arbiter in arbiters.iter() {
+        minter.mint(arbiter, &MIN_STAKE);
+        panel.join(arbiter, &MIN_STAKE);
+    }
+    let claimant = Address::generate(&env);
+    minter.mint(&claimant, &(100 * AMOUNT));
+
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        respondent: Address::generate(&env),
+        env,
+        panel,
+        arbiters,
+        claimant,
+    }
+}
+
+impl Setup {
+    fn open(&self) -> u32 {
+        self.panel
+            .open_dispute(&self.claimant, &self.respondent, &AMOUNT)
+    }
+
+    fn arbiter_of(&self, id: u32) -> Address {
+        self.panel.dispute(&id).unwrap().arbiter
+    }
+
+    fn pass_timeout(&self) {
+        self.env
+            .ledger()
+            .with_mut(|l| l.timestamp += RULING_TIMEOUT);
+    }
+}
+
+// ── panel ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_join_locks_stake() {
+    let s = setup();
+    assert_eq!(s.panel.panel().len(), 3);
+    assert_eq!(s.panel.stake(&s.arbiters[0]), MIN_STAKE);
+    assert_eq!(s.token.balance(&s.panel.address), 3 * MIN_STAKE);
+    assert_eq!(
+        s.panel.try_join(&s.arbiters[0], &MIN_STAKE),
+        Err(Ok(PanelError::AlreadyArbiter))
+    );
+    assert_eq!(
+        s.panel
+            .try_join(&Address::generate(&s.env), &(MIN_STAKE - 1)),
+        Err(Ok(PanelError::InsufficientStake))
+    );
+}
+
+#[test]
+fn test_leave_blocked_while_assigned() {
+    let s = setup();
+    let id = s.open();
+    let arbiter = s.arbiter_of(id);
+    assert_eq!(
+        s.panel.try_leave(&arbiter),
+        Err(Ok(PanelError::HasOpenDisputes))
+    );
+
+    s.panel.rule(&id, &true);
+    assert_eq!(s.panel.leave(&arbiter), MIN_STAKE);
+    assert_eq!(s.token.balance(&arbiter), MIN_STAKE);
+    assert!(!s.panel.panel().contains(&arbiter));
+}
+
+// ── random assignment ────────────────────────────────────────────────────────
+
+#[test]
+fn test_assignment_comes_from_panel() {
+    let s = setup();
+    let id = s.open();
+    let arbiter = s.arbiter_of(id);
+    assert!(s.arbiters.contains(&arbiter));
+    assert_eq!(s.panel.assignments(&arbiter), 1);
+    assert_eq!(s.token.balance(&s.panel.address), 3 * MIN_STAKE + AMOUNT);
+}
+
+#[test]
+fn test_assignments_spread_across_panel() {
+    let s = setup();
+    for _ in 0..30 {
+        s.open();
+    }
+    for arbiter in s.arbiters.iter() {
+        assert!(s.panel.assignments(arbiter) > 0);
+    }
+}
+
+#[test]
+fn test_parties_are_never_drawn() {
+    let s = setup();
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.respondent, &MIN_STAKE);
+    s.panel.join(&s.respondent, &MIN_STAKE);
+    for _ in 0..20 {
+        let id = s.open();
+        assert_ne!(s.arbiter_of(id), s.respondent);
+    }
+}
+
+#[test]
+fn test_no_eligible_arbiter() {
+    let s = setup();
+    let empty = ArbiterPanelClient::new(&s.env, &s.env.register_contract(None, ArbiterPanel));
+    empty.initialize(&Address::generate(&s.env), &s.token.address, &MIN_STAKE);
+    assert_eq!(
+        empty.try_open_dispute(&s.claimant, &s.respondent, &AMOUNT),
+        Err(Ok(PanelError::NoEligibleArbiter))
+    );
+}
+
+// ── rulings ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_rule_pays_winner() {
+    let s = setup();
+    let id = s.open();
+    s.panel.rule(&id, &false);
+
+    assert_eq!(s.token.balance(&s.respondent), AMOUNT);
+    assert_eq!(s.panel.dispute(&id).unwrap().status, DisputeStatus::Ruled);
+    assert_eq!(s.panel.assignments(&s.arbiter_of(id)), 0);
+    assert_eq!(
+        s.panel.try_rule(&id, &true),
+        Err(Ok(PanelError::AlreadyRuled))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_assigned_arbiter_can_rule() {
+    let s = setup();
+    let id = s.open();
+    s.env.set_auths(&[]);
+    s.panel.rule(&id, &true);
+}
+
+// ── recusal ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_recuse_redraws_without_recused_arbiter() {
+    let s = setup();
+    let id = s.open();
+    let first = s.arbiter_of(id);
+
+    let second = s.panel.recuse(&id);
+    assert_ne!(second, first);
+    assert_eq!(s.panel.assignments(&first), 0);
+    assert_eq!(s.panel.assignments(&second), 1);
+
+    let third = s.panel.recuse(&id);
+    assert!(third != first && third != second);
+
+    // Everyone has recused: nobody is left to draw.
+    assert_eq!(
+        s.panel.try_recuse(&id),
+        Err(Ok(PanelError::NoEligibleArbiter))
+    );
+    assert_eq!(s.arbiter_of(id), third);
+}
+
+// ── timeout ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_reassign_before_timeout_rejected() {
+    let s = setup();
+    let id = s.open();
+    s.env
+        .ledger()
+        .with_mut(|l| l.timestamp += RULING_TIMEOUT - 1);
+    assert_eq!(
+        s.panel.try_reassign(&id),
+        Err(Ok(PanelError::TimeoutNotReached))
+    );
+}
+
+#[test]
+fn test_reassign_slashes_and_redraws() {
+    let s = setup();
+    let id = s.open();
+    let idle = s.arbiter_of(id);
+
+    s.pass_timeout();
+    let replacement = s.panel.reassign(&id);
+    assert_ne!(replacement, idle);
+
+    let slashed = MIN_STAKE * SLASH_BPS / 10_000;
+    assert_eq!(s.panel.stake(&idle), MIN_STAKE - slashed);
+    assert_eq!(s.panel.dispute(&id).unwrap().amount, AMOUNT + slashed);
+
+    // The slash is paid out with the ruling.
+    s.panel.rule(&id, &true);
+    assert_eq!(s.token.balance(&s.claimant), 100 * AMOUNT + slashed);
+}
+
+#[test]
+fn test_timeout_restarts_for_replacement() {
+    let s = setup();
+    let id = s.open();
+    s.pass_timeout();
+    s.panel.reassign(&id);
+    assert_eq!(
+        s.panel.try_reassign(&id),
+        Err(Ok(PanelError::TimeoutNotReached))
+    );
+}
+
+#[test]
+fn test_slashed_arbiter_below_min_stake_not_drawn() {
+    let s = setup();
+    let id = s.open();
+    let idle = s.arbiter_of(id);
+    s.pass_timeout();
+    s.panel.reassign(&id);
+
+    for _ in 0..20 {
+        let id = s.open();
+        assert_ne!(s.arbiter_of(id), idle);
+    }
+}
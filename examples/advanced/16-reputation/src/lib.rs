@@ -0,0 +1,211 @@
+//! # Reputation with Decay
+//!
+//! Scores rise when a trusted verifier records a positive action and halve
+//! every `HALF_LIFE` of inactivity. Decay is *lazy*: nothing runs on a
+//! schedule. The stored score and its timestamp are enough to compute the
+//! current value whenever it is read:
+//!
+//! ```text
+//! current = stored >> ((now - updated_at) / HALF_LIFE)
+//! ```
+//!
+//! When a new action is recorded, the decayed score is written back. The
+//! timestamp only advances by whole half-lives, so time already spent
+//! toward the next halving is not forgotten.
+//!
+//! A top-`LEADERBOARD_SIZE` board is kept with the bounded sorted list in
+//! `sorted_list` and read a page at a time.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Vec,
+};
+
+mod sorted_list;
+
+pub use sorted_list::Entry;
+
+/// Seconds of inactivity after which a score halves.
+pub const HALF_LIFE: u64 = 30 * 24 * 60 * 60;
+/// Scores never exceed this.
+pub const MAX_SCORE: u32 = 10_000;
+/// Upper bound on the points a single action may award.
+pub const MAX_POINTS: u32 = 100;
+/// Entries kept on the leaderboard.
+pub const LEADERBOARD_SIZE: u32 = 20;
+/// Largest page `leaderboard` returns.
+pub const MAX_PAGE: u32 = 10;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reputation {
+    /// Score as of `updated_at`.
+    pub score: u32,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Address allowed to record actions.
+    Verifier,
+    Reputation(Address),
+    Leaderboard,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ReputationError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// Points must be between 1 and `MAX_POINTS`.
+    InvalidPoints = 3,
+}
+
+#[contract]
+pub struct ReputationContract;
+
+#[contractimpl]
+impl ReputationContract {
+    pub fn initialize(env: Env, admin: Address, verifier: Address) -> Result<(), ReputationError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ReputationError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Verifier, &verifier);
+        Ok(())
+    }
+
+    pub fn set_verifier(env: Env, verifier: Address) -> Result<(), ReputationError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ReputationError::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Verifier, &verifier);
+        Ok(())
+    }
+
+    /// Award `points` to `subject` for a verified positive action. Verifier
+    /// only. Returns the new score.
+    ///
+    /// Emits `(rep, action)` with `(subject, points, score)`.
+    pub fn record_action(env: Env, subject: Address, points: u32) -> Result<u32, ReputationError> {
+        let verifier: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Verifier)
+            .ok_or(ReputationError::NotInitialized)?;
+        verifier.require_auth();
+        if points == 0 || points > MAX_POINTS {
+            return Err(ReputationError::InvalidPoints);
+        }
+
+        let now = env.ledger().timestamp();
+        let (decayed, anchor) = match Self::reputation(env.clone(), subject.clone()) {
+            Some(rep) => decay(&rep, now),
+            None => (0, now),
+        };
+        let score = (decayed + points).min(MAX_SCORE);
+        env.storage().persistent().set(
+            &DataKey::Reputation(subject.clone()),
+            &Reputation {
+                score,
+                updated_at: anchor,
+            },
+        );
+        Self::refresh_leaderboard(&env, &subject, score);
+
+        env.events().publish(
+            (symbol_short!("rep"), symbol_short!("action")),
+            (subject, points, score),
+        );
+        Ok(score)
+    }
+
+    /// Current score with decay applied.
+    pub fn score(env: Env, subject: Address) -> u32 {
+        Self::current_score(&env, &subject)
+    }
+
+    /// Raw stored state, before decay.
+    pub fn reputation(env: Env, subject: Address) -> Option<Reputation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reputation(subject))
+    }
+
+    /// One page of the leaderboard, highest current score first. `limit` is
+    /// capped at `MAX_PAGE`.
+    pub fn leaderboard(env: Env, offset: u32, limit: u32) -> Vec<Entry> {
+        let board = Self::current_board(&env);
+        sorted_list::page(&board, offset, limit.min(MAX_PAGE))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn current_score(env: &Env, subject: &Address) -> u32 {
+        Self::reputation(env.clone(), subject.clone())
+            .map(|rep| decay(&rep, env.ledger().timestamp()).0)
+            .unwrap_or(0)
+    }
+
+    /// The stored board re-sorted by current scores. Scores decay at
+    /// different moments, so the stored order can be out of date; rebuilding
+    /// is cheap because the board is bounded.
+    fn current_board(env: &Env) -> Vec<Entry> {
+        let stored: Vec<Entry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Leaderboard)
+            .unwrap_or_else(|| Vec::new(env));
+        let mut board = Vec::new(env);
+        for entry in stored.iter() {
+            let score = Self::current_score(env, &entry.subject);
+            if score > 0 {
+                sorted_list::insert(
+                    &mut board,
+                    Entry {
+                        subject: entry.subject,
+                        score,
+                    },
+                    LEADERBOARD_SIZE,
+                );
+            }
+        }
+        board
+    }
+
+    fn refresh_leaderboard(env: &Env, subject: &Address, score: u32) {
+        let mut board = Self::current_board(env);
+        sorted_list::remove(&mut board, subject);
+        sorted_list::insert(
+            &mut board,
+            Entry {
+                subject: subject.clone(),
+                score,
+            },
+            LEADERBOARD_SIZE,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Leaderboard, &board);
+    }
+}
+
+/// Score after decay, and the timestamp it is valid from. The timestamp
+/// moves by whole half-lives only, keeping partial progress toward the next
+/// halving.
+fn decay(rep: &Reputation, now: u64) -> (u32, u64) {
+    let halvings = now.saturating_sub(rep.updated_at) / HALF_LIFE;
+    if halvings >= u32::BITS as u64 {
+        return (0, now);
+    }
+    (rep.score >> halvings, rep.updated_at + halvings * HALF_LIFE)
+}
+
+mod test;
@@ -0,0 +1,45 @@
+//! Bounded list kept in descending score order.
+//!
+//! Inserting walks the list to the first lower score, so the cost is linear
+//! in the capacity. Keep the capacity small; this is for a top-N board, not
+//! an index of every account.
+
+use soroban_sdk::{contracttype, Address, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub subject: Address,
+    pub score: u32,
+}
+
+/// Insert `entry` after every entry with an equal or higher score, then drop
+/// whatever falls past `capacity`. Ties keep insertion order.
+pub fn insert(list: &mut Vec<Entry>, entry: Entry, capacity: u32) {
+    let position = list
+        .iter()
+        .position(|e| e.score < entry.score)
+        .map(|i| i as u32)
+        .unwrap_or(list.len());
+    if position >= capacity {
+        return;
+    }
+    list.insert(position, entry);
+    while list.len() > capacity {
+        list.pop_back();
+    }
+}
+
+/// Remove `subject`'s entry, if present.
+pub fn remove(list: &mut Vec<Entry>, subject: &Address) {
+    if let Some(i) = list.iter().position(|e| e.subject == *subject) {
+        list.remove(i as u32);
+    }
+}
+
+/// Entries `offset..offset + limit`, clipped to the list.
+pub fn page(list: &Vec<Entry>, offset: u32, limit: u32) -> Vec<Entry> {
+    let start = offset.min(list.len());
+    let end = offset.saturating_add(limit).min(list.len());
+    list.slice(start..end)
+}
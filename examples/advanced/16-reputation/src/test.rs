@@ -0,0 +1,228 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+struct Setup {
+    env: Env,
+    rep: ReputationContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let rep = ReputationContractClient::new(&env, &env.register_contract(None, ReputationContract));
+    rep.initialize(&Address::generate(&env), &Address::generate(&env));
+    Setup { env, rep }
+}
+
+impl Setup {
+    fn advance(&self, seconds: u64) {
+        self.env.ledger().with_mut(|l| l.timestamp += seconds);
+    }
+
+    /// A new subject with `points` recorded now.
+    fn subject_with(&self, points: u32) -> Address {
+        let subject = Address::generate(&self.env);
+        self.rep.record_action(&subject, &points);
+        subject
+    }
+}
+
+// ── scoring ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_actions_accumulate() {
+    let s = setup();
+    let alice = s.subject_with(40);
+    assert_eq!(s.rep.record_action(&alice, &60), 100);
+    assert_eq!(s.rep.score(&alice), 100);
+    assert_eq!(s.rep.score(&Address::generate(&s.env)), 0);
+}
+
+#[test]
+fn test_points_bounded() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    assert_eq!(
+        s.rep.try_record_action(&alice, &0),
+        Err(Ok(ReputationError::InvalidPoints))
+    );
+    assert_eq!(
+        s.rep.try_record_action(&alice, &(MAX_POINTS + 1)),
+        Err(Ok(ReputationError::InvalidPoints))
+    );
+}
+
+#[test]
+fn test_score_capped_at_max() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    for _ in 0..(MAX_SCORE / MAX_POINTS + 5) {
+        s.rep.record_action(&alice, &MAX_POINTS);
+    }
+    assert_eq!(s.rep.score(&alice), MAX_SCORE);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_verifier_records_actions() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.rep.record_action(&Address::generate(&s.env), &10);
+}
+
+// ── lazy decay ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_score_halves_each_half_life() {
+    let s = setup();
+    let alice = s.subject_with(80);
+
+    s.advance(HALF_LIFE - 1);
+    assert_eq!(s.rep.score(&alice), 80);
+    s.advance(1);
+    assert_eq!(s.rep.score(&alice), 40);
+    s.advance(2 * HALF_LIFE);
+    assert_eq!(s.rep.score(&alice), 10);
+
+    // Reading does not write: the stored state is untouched.
+    assert_eq!(s.rep.reputation(&alice).unwrap().score, 80);
+}
+
+#[test]
+fn test_long_inactivity_decays_to_zero() {
+    let s = setup();
+    let alice = s.subject_with(100);
+    s.advance(40 * HALF_LIFE);
+    assert_eq!(s.rep.score(&alice), 0);
+    assert_eq!(s.rep.record_action(&alice, &5), 5);
+}
+
+#[test]
+fn test_write_keeps_partial_half_life() {
+    let s = setup();
+    let alice = s.subject_with(80);
+    let start = s.env.ledger().timestamp();
+
+    // 1.5 half-lives later: one halving applied, half a period carried over.
+    s.advance(HALF_LIFE + HALF_LIFE / 2);
+    assert_eq!(s.rep.record_action(&alice, &10), 50);
+    assert_eq!(
+        s.rep.reputation(&alice).unwrap().updated_at,
+        start + HALF_LIFE
+    );
+
+    // So the next halving comes after another half period, not a full one.
+    s.advance(HALF_LIFE / 2);
+    assert_eq!(s.rep.score(&alice), 25);
+}
+
+// ── leaderboard ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_leaderboard_sorted_and_paged() {
+    let s = setup();
+    let low = s.subject_with(10);
+    let high = s.subject_with(90);
+    let mid = s.subject_with(50);
+
+    let top = s.rep.leaderboard(&0, &2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(
+        top.get(0).unwrap(),
+        Entry {
+            subject: high,
+            score: 90
+        }
+    );
+    assert_eq!(
+        top.get(1).unwrap(),
+        Entry {
+            subject: mid,
+            score: 50
+        }
+    );
+
+    let rest = s.rep.leaderboard(&2, &2);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(
+        rest.get(0).unwrap(),
+        Entry {
+            subject: low,
+            score: 10
+        }
+    );
+    assert_eq!(s.rep.leaderboard(&5, &2).len(), 0);
+}
+
+#[test]
+fn test_leaderboard_updates_existing_entry() {
+    let s = setup();
+    let alice = s.subject_with(10);
+    let bob = s.subject_with(50);
+    s.rep.record_action(&alice, &60);
+
+    let board = s.rep.leaderboard(&0, &MAX_PAGE);
+    assert_eq!(board.len(), 2);
+    assert_eq!(board.get(0).unwrap().subject, alice);
+    assert_eq!(board.get(1).unwrap().subject, bob);
+}
+
+#[test]
+fn test_leaderboard_reflects_decay() {
+    let s = setup();
+    let early = s.subject_with(80);
+    s.advance(HALF_LIFE);
+    let late = s.subject_with(60);
+
+    // `early` stored 80, but has halved to 40 since.
+    let board = s.rep.leaderboard(&0, &MAX_PAGE);
+    assert_eq!(
+        board.get(0).unwrap(),
+        Entry {
+            subject: late,
+            score: 60
+        }
+    );
+    assert_eq!(
+        board.get(1).unwrap(),
+        Entry {
+            subject: early,
+            score: 40
+        }
+    );
+}
+
+#[test]
+fn test_leaderboard_bounded() {
+    let s = setup();
+    for points in 1..=(LEADERBOARD_SIZE + 5) {
+        s.subject_with(points);
+    }
+    let mut seen = 0;
+    let mut offset = 0;
+    loop {
+        let page = s.rep.leaderboard(&offset, &100);
+        assert!(page.len() <= MAX_PAGE);
+        if page.is_empty() {
+            break;
+        }
+        seen += page.len();
+        offset += page.len();
+    }
+    assert_eq!(seen, LEADERBOARD_SIZE);
+    // The lowest scorers fell off.
+    assert_eq!(
+        s.rep
+            .leaderboard(&(LEADERBOARD_SIZE - 1), &1)
+            .get(0)
+            .unwrap()
+            .score,
+        6
+    );
+}
@@ -0,0 +1,383 @@
+//! # Faucet
+//!
+//! A testnet-style faucet that hands out a fixed `drip` of a token, with
+//! several independent limits between a bot and an empty faucet:
+//!
+//! | Limit | Scope | On hit |
+//! |-------|-------|--------|
+//! | `cooldown` | per address | claim refused until it passes |
+//! | `daily_cap` | all addresses, per UTC day | claim refused until tomorrow |
+//! | attestation registry (optional) | per address | level 0 refused; higher levels get their tier's drip and cooldown |
+//! | `burst_limit` | all addresses, per hour | breaker trips, every claim refused until the admin resets it |
+//!
+//! Cooldowns and the day/hour counters live in temporary storage: they only
+//! matter for a bounded time, and the network evicts them afterwards.
+//! Operator parameters are declared once with `cookbook_config::typed_config!`.
+//!
+//! The breaker is the drain protection. The daily cap only slows a drain
+//! down; a burst that large within one hour means the other limits are
+//! being bypassed (e.g. many fresh addresses), so the faucet stops entirely
+//! until a human looks.
+//!
+//! ## Policy from another contract
+//!
+//! With a registry set, the caller's attestation level selects a
+//! `LevelTier`: its own drip and cooldown, so a verified developer can claim
+//! more and sooner than a fresh account. The tiers are data the admin
+//! stores, not code. The level is a cross-contract read, so it is cached per
+//! address for `level_cache_ttl` seconds; a level 0 is never cached, so a
+//! newly attested address does not wait out a stale "no".
+
+#![no_std]
+
+use cookbook_config::typed_config;
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Env, Vec,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+const HOUR: u64 = 60 * 60;
+/// Ledgers a temporary counter is kept (~2 days at 5s/ledger).
+const COUNTER_TTL_LEDGERS: u32 = 2 * 17_280;
+
+/// Upper bound on `LevelTier`s.
+pub const MAX_TIERS: u32 = 8;
+/// Longest tier cooldown: a `LastClaim` entry is only kept for
+/// `COUNTER_TTL_LEDGERS`, and an evicted one would let the address claim
+/// early.
+pub const MAX_TIER_COOLDOWN: u64 = 2 * DAY;
+
+typed_config! {
+    /// Admin-tunable faucet limits.
+    pub struct FaucetConfig {
+        /// Tokens paid per claim.
+        drip: i128 = 100,
+        /// Seconds an address waits between claims.
+        cooldown: u64 = DAY,
+        /// Tokens paid out per UTC day, across all addresses.
+        daily_cap: i128 = 10_000,
+        /// Tokens per hour that trip the breaker.
+        burst_limit: i128 = 2_000,
+        /// Seconds a registry level is reused before it is read again.
+        level_cache_ttl: u64 = HOUR,
+    }
+}
+
+/// What the faucet needs from an attestation registry.
+#[contractclient(name = "RegistryClient")]
+pub trait AttestationRegistry {
+    /// Attestation level of `subject`; 0 means not attested.
+    fn level(env: Env, subject: Address) -> u32;
+}
+
+/// Claim terms for callers at `min_level` or above, up to the next tier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LevelTier {
+    pub min_level: u32,
+    pub drip: i128,
+    pub cooldown: u64,
+}
+
+/// A registry level as read at `fetched_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedLevel {
+    pub level: u32,
+    pub fetched_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    /// Optional attestation registry gating claims.
+    Registry,
+    /// `Vec<LevelTier>`, sorted by `min_level`.
+    Tiers,
+    Tripped,
+    /// Temporary: timestamp of an address's last claim.
+    LastClaim(Address),
+    /// Temporary: amount paid out in a day (timestamp / DAY).
+    DayTotal(u64),
+    /// Temporary: amount paid out in an hour (timestamp / HOUR).
+    HourTotal(u64),
+    /// Temporary: an address's `CachedLevel`.
+    Level(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FaucetError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    CoolingDown = 3,
+    DailyCapReached = 4,
+    NotAttested = 5,
+    BreakerTripped = 6,
+    InvalidConfig = 7,
+}
+
+#[contract]
+pub struct Faucet;
+
+#[contractimpl]
+impl Faucet {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), FaucetError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FaucetError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    /// Pay `drip` to `recipient` if every limit allows it. With a registry
+    /// and level tiers set, the drip and cooldown are the recipient's tier's.
+    ///
+    /// Emits `(faucet, drip)` with `(recipient, amount)`, and
+    /// `(breaker, tripped)` if this claim reached the burst limit.
+    pub fn claim(env: Env, recipient: Address) -> Result<i128, FaucetError> {
+        recipient.require_auth();
+        if Self::is_tripped(env.clone()) {
+            return Err(FaucetError::BreakerTripped);
+        }
+        let (drip, cooldown) = Self::terms(&env, &recipient)?;
+
+        let now = env.ledger().timestamp();
+        let last_key = DataKey::LastClaim(recipient.clone());
+        let last: Option<u64> = env.storage().temporary().get(&last_key);
+        if matches!(last, Some(at) if now < at + cooldown) {
+            return Err(FaucetError::CoolingDown);
+        }
+
+        let day_key = DataKey::DayTotal(now / DAY);
+        let day_total = Self::counter(&env, &day_key) + drip;
+        if day_total > FaucetConfig::daily_cap(&env) {
+            return Err(FaucetError::DailyCapReached);
+        }
+        let hour_key = DataKey::HourTotal(now / HOUR);
+        let hour_total = Self::counter(&env, &hour_key) + drip;
+
+        Self::write_temporary(&env, &last_key, &now);
+        Self::write_temporary(&env, &day_key, &day_total);
+        Self::write_temporary(&env, &hour_key, &hour_total);
+        token::Client::new(&env, &Self::token(&env)?).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &drip,
+        );
+        env.events().publish(
+            (symbol_short!("faucet"), symbol_short!("drip")),
+            (recipient, drip),
+        );
+
+        // Trip on the claim that reaches the limit. Failing this claim
+        // instead would roll the flag back with it.
+        if hour_total >= FaucetConfig::burst_limit(&env) {
+            env.storage().instance().set(&DataKey::Tripped, &true);
+            env.events().publish(
+                (symbol_short!("breaker"), symbol_short!("tripped")),
+                hour_total,
+            );
+        }
+        Ok(drip)
+    }
+
+    // ==================== ADMIN ====================
+
+    pub fn configure(
+        env: Env,
+        drip: i128,
+        cooldown: u64,
+        daily_cap: i128,
+        burst_limit: i128,
+    ) -> Result<(), FaucetError> {
+        Self::require_admin(&env)?;
+        if drip <= 0 || daily_cap < drip || burst_limit < drip {
+            return Err(FaucetError::InvalidConfig);
+        }
+        FaucetConfig::set_drip(&env, drip);
+        FaucetConfig::set_cooldown(&env, cooldown);
+        FaucetConfig::set_daily_cap(&env, daily_cap);
+        FaucetConfig::set_burst_limit(&env, burst_limit);
+        Ok(())
+    }
+
+    /// Require claimants to be attested by `registry`, or remove the
+    /// requirement with `None`.
+    pub fn set_registry(env: Env, registry: Option<Address>) -> Result<(), FaucetError> {
+        Self::require_admin(&env)?;
+        match registry {
+            Some(registry) => env.storage().instance().set(&DataKey::Registry, &registry),
+            None => env.storage().instance().remove(&DataKey::Registry),
+        }
+        Ok(())
+    }
+
+    /// Scale drip and cooldown with the registry level: a caller gets the
+    /// tier with the highest `min_level` at or below their level, and the
+    /// `configure`d drip and cooldown below the first tier. Levels are
+    /// cached for `level_cache_ttl` seconds.
+    ///
+    /// Tiers need a `min_level` of at least 1 and strictly increasing, a
+    /// positive drip, and a cooldown of at most `MAX_TIER_COOLDOWN`. An
+    /// empty list turns scaling off.
+    pub fn set_level_tiers(
+        env: Env,
+        tiers: Vec<LevelTier>,
+        level_cache_ttl: u64,
+    ) -> Result<(), FaucetError> {
+        Self::require_admin(&env)?;
+        if tiers.len() > MAX_TIERS {
+            return Err(FaucetError::InvalidConfig);
+        }
+        let mut previous = 0;
+        for tier in tiers.iter() {
+            if tier.min_level <= previous || tier.drip <= 0 || tier.cooldown > MAX_TIER_COOLDOWN {
+                return Err(FaucetError::InvalidConfig);
+            }
+            previous = tier.min_level;
+        }
+        env.storage().instance().set(&DataKey::Tiers, &tiers);
+        FaucetConfig::set_level_cache_ttl(&env, level_cache_ttl);
+        Ok(())
+    }
+
+    /// Drop `subject`'s cached level, e.g. after the registry revoked it.
+    pub fn clear_level_cache(env: Env, subject: Address) -> Result<(), FaucetError> {
+        Self::require_admin(&env)?;
+        env.storage().temporary().remove(&DataKey::Level(subject));
+        Ok(())
+    }
+
+    /// Re-open a tripped faucet. The current hour's counter is cleared too,
+    /// or the next claim would trip it again.
+    pub fn reset_breaker(env: Env) -> Result<(), FaucetError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().remove(&DataKey::Tripped);
+        let hour = env.ledger().timestamp() / HOUR;
+        env.storage().temporary().remove(&DataKey::HourTotal(hour));
+        env.events()
+            .publish((symbol_short!("breaker"), symbol_short!("reset")), ());
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn is_tripped(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tripped)
+            .unwrap_or(false)
+    }
+
+    pub fn registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Registry)
+    }
+
+    pub fn level_tiers(env: Env) -> Vec<LevelTier> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tiers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// The cached level of `subject`, even if it is stale.
+    pub fn cached_level(env: Env, subject: Address) -> Option<CachedLevel> {
+        env.storage().temporary().get(&DataKey::Level(subject))
+    }
+
+    pub fn dispensed_today(env: Env) -> i128 {
+        let day = env.ledger().timestamp() / DAY;
+        Self::counter(&env, &DataKey::DayTotal(day))
+    }
+
+    // ==================== HELPERS ====================
+
+    /// `(drip, cooldown)` for `recipient`, or `NotAttested` if a registry
+    /// is set and reports level 0.
+    fn terms(env: &Env, recipient: &Address) -> Result<(i128, u64), FaucetError> {
+        let mut terms = (FaucetConfig::drip(env), FaucetConfig::cooldown(env));
+        let Some(registry) = Self::registry(env.clone()) else {
+            return Ok(terms);
+        };
+        let level = Self::level(env, &registry, recipient);
+        if level == 0 {
+            return Err(FaucetError::NotAttested);
+        }
+        for tier in Self::level_tiers(env.clone()).iter() {
+            if tier.min_level > level {
+                break;
+            }
+            terms = (tier.drip, tier.cooldown);
+        }
+        Ok(terms)
+    }
+
+    /// `subject`'s level: from the cache while it is fresh, otherwise from
+    /// the registry.
+    fn level(env: &Env, registry: &Address, subject: &Address) -> u32 {
+        let now = env.ledger().timestamp();
+        let key = DataKey::Level(subject.clone());
+        let cached: Option<CachedLevel> = env.storage().temporary().get(&key);
+        if let Some(cached) = cached {
+            if now < cached.fetched_at + FaucetConfig::level_cache_ttl(env) {
+                return cached.level;
+            }
+        }
+
+        let level = RegistryClient::new(env, registry).level(subject);
+        if level > 0 {
+            Self::write_temporary(
+                env,
+                &key,
+                &CachedLevel {
+                    level,
+                    fetched_at: now,
+                },
+            );
+        } else {
+            env.storage().temporary().remove(&key);
+        }
+        level
+    }
+
+    fn counter(env: &Env, key: &DataKey) -> i128 {
+        env.storage().temporary().get(key).unwrap_or(0)
+    }
+
+    fn write_temporary<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+        env: &Env,
+        key: &DataKey,
+        value: &V,
+    ) {
+        env.storage().temporary().set(key, value);
+        env.storage()
+            .temporary()
+            .extend_ttl(key, COUNTER_TTL_LEDGERS, COUNTER_TTL_LEDGERS);
+    }
+
+    fn token(env: &Env) -> Result<Address, FaucetError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(FaucetError::NotInitialized)
+    }
+
+    fn require_admin(env: &Env) -> Result<(), FaucetError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FaucetError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+mod registry;
+mod test;
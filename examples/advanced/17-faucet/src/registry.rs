@@ -0,0 +1,39 @@
+//! Reference attestation registry.
+//!
+//! Only compiled for tests: in a real deployment it would be its own
+//! contract, e.g. one that attests addresses after a captcha or a social
+//! login.
+
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum RegistryKey {
+    Level(Address),
+}
+
+#[contract]
+pub struct TestRegistry;
+
+#[contractimpl]
+impl TestRegistry {
+    /// Attest `subject` at level 1.
+    pub fn attest(env: Env, subject: Address) {
+        Self::set_level(env, subject, 1);
+    }
+
+    pub fn set_level(env: Env, subject: Address, level: u32) {
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::Level(subject), &level);
+    }
+
+    pub fn level(env: Env, subject: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::Level(subject))
+            .unwrap_or(0)
+    }
+}
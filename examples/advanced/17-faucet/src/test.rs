@@ -0,0 +1,342 @@
+#![cfg(test)]
+
+use super::*;
+use crate::registry::{TestRegistry, TestRegistryClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    IntoVal, TryFromVal,
+};
+
+struct Setup<'a> {
+    env: Env,
+    faucet: FaucetClient<'a>,
+    token: TokenClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 10 * DAY);
+
+    let admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let faucet_id = env.register_contract(None, Faucet);
+    StellarAssetClient::new(&env, &token_id).mint(&faucet_id, &1_000_000);
+
+    let faucet = FaucetClient::new(&env, &faucet_id);
+    faucet.initialize(&admin, &token_id);
+    let token = TokenClient::new(&env, &token_id);
+    Setup { env, faucet, token }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_claim_pays_drip() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+
+    assert_eq!(s.faucet.claim(&user), 100);
+    assert_eq!(s.token.balance(&user), 100);
+    assert_eq!(s.faucet.dispensed_today(), 100);
+}
+
+#[test]
+fn test_cooldown_blocks_second_claim() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    s.faucet.claim(&user);
+
+    advance(&s.env, DAY - 1);
+    assert_eq!(s.faucet.try_claim(&user), Err(Ok(FaucetError::CoolingDown)));
+
+    advance(&s.env, 1);
+    s.faucet.claim(&user);
+    assert_eq!(s.token.balance(&user), 200);
+}
+
+#[test]
+fn test_cooldown_is_per_address() {
+    let s = setup();
+    s.faucet.claim(&Address::generate(&s.env));
+    s.faucet.claim(&Address::generate(&s.env));
+    assert_eq!(s.faucet.dispensed_today(), 200);
+}
+
+#[test]
+fn test_daily_cap() {
+    let s = setup();
+    s.faucet.configure(&100, &DAY, &300, &10_000);
+    for _ in 0..3 {
+        s.faucet.claim(&Address::generate(&s.env));
+    }
+    let late = Address::generate(&s.env);
+    assert_eq!(
+        s.faucet.try_claim(&late),
+        Err(Ok(FaucetError::DailyCapReached))
+    );
+
+    // A new UTC day starts a new bucket.
+    advance(&s.env, DAY);
+    s.faucet.claim(&late);
+    assert_eq!(s.faucet.dispensed_today(), 100);
+}
+
+#[test]
+fn test_registry_gates_claims() {
+    let s = setup();
+    let registry_id = s.env.register_contract(None, TestRegistry);
+    let registry = TestRegistryClient::new(&s.env, &registry_id);
+    s.faucet.set_registry(&Some(registry_id.clone()));
+    assert_eq!(s.faucet.registry(), Some(registry_id));
+
+    let user = Address::generate(&s.env);
+    assert_eq!(s.faucet.try_claim(&user), Err(Ok(FaucetError::NotAttested)));
+
+    // A level of 0 is not cached, so attesting takes effect at once.
+    registry.attest(&user);
+    s.faucet.claim(&user);
+
+    // Removing the registry opens the faucet to everyone again.
+    s.faucet.set_registry(&None);
+    s.faucet.claim(&Address::generate(&s.env));
+}
+
+fn with_registry(s: &Setup) -> TestRegistryClient<'static> {
+    let registry_id = s.env.register_contract(None, TestRegistry);
+    s.faucet.set_registry(&Some(registry_id.clone()));
+    TestRegistryClient::new(&s.env, &registry_id)
+}
+
+fn tier(env: &Env, min_level: u32, drip: i128, cooldown: u64) -> Vec<LevelTier> {
+    Vec::from_array(
+        env,
+        [LevelTier {
+            min_level,
+            drip,
+            cooldown,
+        }],
+    )
+}
+
+#[test]
+fn test_level_tiers_scale_drip_and_cooldown() {
+    let s = setup();
+    let registry = with_registry(&s);
+    let mut tiers = tier(&s.env, 2, 300, 12 * HOUR);
+    tiers.append(&tier(&s.env, 5, 1_000, HOUR));
+    s.faucet.set_level_tiers(&tiers, &HOUR);
+    assert_eq!(s.faucet.level_tiers(), tiers);
+
+    let (basic, verified, trusted) = (
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    );
+    registry.set_level(&basic, &1);
+    registry.set_level(&verified, &3);
+    registry.set_level(&trusted, &9);
+
+    // Below the first tier the configured drip applies.
+    assert_eq!(s.faucet.claim(&basic), 100);
+    assert_eq!(s.faucet.claim(&verified), 300);
+    assert_eq!(s.faucet.claim(&trusted), 1_000);
+
+    advance(&s.env, HOUR);
+    assert_eq!(s.faucet.claim(&trusted), 1_000);
+    assert_eq!(
+        s.faucet.try_claim(&verified),
+        Err(Ok(FaucetError::CoolingDown))
+    );
+
+    advance(&s.env, 11 * HOUR);
+    assert_eq!(s.faucet.claim(&verified), 300);
+    assert_eq!(
+        s.faucet.try_claim(&basic),
+        Err(Ok(FaucetError::CoolingDown))
+    );
+}
+
+#[test]
+fn test_level_is_cached() {
+    let s = setup();
+    let registry = with_registry(&s);
+    s.faucet.configure(&100, &HOUR, &10_000, &2_000);
+    s.faucet
+        .set_level_tiers(&tier(&s.env, 2, 300, HOUR), &(2 * HOUR));
+
+    let user = Address::generate(&s.env);
+    registry.set_level(&user, &2);
+    assert_eq!(s.faucet.claim(&user), 300);
+    assert_eq!(
+        s.faucet.cached_level(&user),
+        Some(CachedLevel {
+            level: 2,
+            fetched_at: 10 * DAY,
+        })
+    );
+
+    // The registry lowers the level, but the faucet keeps the cached one
+    // until it expires.
+    registry.set_level(&user, &1);
+    advance(&s.env, HOUR);
+    assert_eq!(s.faucet.claim(&user), 300);
+    advance(&s.env, HOUR);
+    assert_eq!(s.faucet.claim(&user), 100);
+
+    // Clearing the cache applies a revocation right away.
+    registry.set_level(&user, &0);
+    s.faucet.clear_level_cache(&user);
+    advance(&s.env, DAY);
+    assert_eq!(s.faucet.try_claim(&user), Err(Ok(FaucetError::NotAttested)));
+    assert_eq!(s.faucet.cached_level(&user), None);
+}
+
+#[test]
+fn test_level_tiers_validate() {
+    let s = setup();
+    let invalid = [
+        tier(&s.env, 0, 100, HOUR),
+        tier(&s.env, 1, 0, HOUR),
+        tier(&s.env, 1, 100, MAX_TIER_COOLDOWN + 1),
+    ];
+    for tiers in invalid {
+        assert_eq!(
+            s.faucet.try_set_level_tiers(&tiers, &HOUR),
+            Err(Ok(FaucetError::InvalidConfig))
+        );
+    }
+
+    let mut unsorted = tier(&s.env, 3, 100, HOUR);
+    unsorted.append(&tier(&s.env, 3, 200, HOUR));
+    assert_eq!(
+        s.faucet.try_set_level_tiers(&unsorted, &HOUR),
+        Err(Ok(FaucetError::InvalidConfig))
+    );
+
+    let mut too_many = Vec::new(&s.env);
+    for level in 1..=MAX_TIERS + 1 {
+        too_many.append(&tier(&s.env, level, 100, HOUR));
+    }
+    assert_eq!(
+        s.faucet.try_set_level_tiers(&too_many, &HOUR),
+        Err(Ok(FaucetError::InvalidConfig))
+    );
+}
+
+#[test]
+fn test_breaker_trips_on_burst() {
+    let s = setup();
+    s.faucet.configure(&100, &DAY, &10_000, &300);
+    s.faucet.claim(&Address::generate(&s.env));
+    s.faucet.claim(&Address::generate(&s.env));
+    assert!(!s.faucet.is_tripped());
+
+    // The claim that reaches the limit is paid and trips the breaker.
+    let third = Address::generate(&s.env);
+    s.faucet.claim(&third);
+    assert_eq!(s.token.balance(&third), 100);
+    assert!(s.faucet.is_tripped());
+    let event = s.env.events().all().last().unwrap();
+    assert_eq!(event.0, s.faucet.address);
+    assert_eq!(
+        event.1,
+        (symbol_short!("breaker"), symbol_short!("tripped")).into_val(&s.env)
+    );
+    assert_eq!(i128::try_from_val(&s.env, &event.2).unwrap(), 300);
+
+    assert_eq!(
+        s.faucet.try_claim(&Address::generate(&s.env)),
+        Err(Ok(FaucetError::BreakerTripped))
+    );
+}
+
+#[test]
+fn test_breaker_stays_tripped_until_reset() {
+    let s = setup();
+    s.faucet.configure(&100, &DAY, &10_000, &100);
+    s.faucet.claim(&Address::generate(&s.env));
+    assert!(s.faucet.is_tripped());
+
+    // Time passing alone does not re-open it.
+    advance(&s.env, 2 * DAY);
+    let user = Address::generate(&s.env);
+    assert_eq!(
+        s.faucet.try_claim(&user),
+        Err(Ok(FaucetError::BreakerTripped))
+    );
+
+    s.faucet.reset_breaker();
+    assert!(!s.faucet.is_tripped());
+}
+
+#[test]
+fn test_reset_clears_current_hour() {
+    let s = setup();
+    s.faucet.configure(&100, &DAY, &10_000, &200);
+    s.faucet.claim(&Address::generate(&s.env));
+    s.faucet.claim(&Address::generate(&s.env));
+    assert!(s.faucet.is_tripped());
+
+    s.faucet.reset_breaker();
+    s.faucet.claim(&Address::generate(&s.env));
+    assert!(!s.faucet.is_tripped());
+}
+
+#[test]
+fn test_hour_window_rolls_over() {
+    let s = setup();
+    s.faucet.configure(&100, &DAY, &10_000, &200);
+    s.faucet.claim(&Address::generate(&s.env));
+    advance(&s.env, HOUR);
+    s.faucet.claim(&Address::generate(&s.env));
+    assert!(!s.faucet.is_tripped());
+}
+
+#[test]
+fn test_configure_validates() {
+    let s = setup();
+    assert_eq!(
+        s.faucet.try_configure(&0, &DAY, &100, &100),
+        Err(Ok(FaucetError::InvalidConfig))
+    );
+    assert_eq!(
+        s.faucet.try_configure(&100, &DAY, &50, &100),
+        Err(Ok(FaucetError::InvalidConfig))
+    );
+    assert_eq!(
+        s.faucet.try_configure(&100, &DAY, &100, &50),
+        Err(Ok(FaucetError::InvalidConfig))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_reset_breaker_requires_admin() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.faucet.reset_breaker();
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_claim_requires_recipient_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.faucet.claim(&Address::generate(&s.env));
+}
+
+#[test]
+fn test_initialize_once() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.faucet.try_initialize(&other, &s.token.address),
+        Err(Ok(FaucetError::AlreadyInitialized))
+    );
+}
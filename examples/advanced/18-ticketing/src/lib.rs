@@ -0,0 +1,355 @@
+//! # Ticketing
+//!
+//! Event tickets with a sale cap, a transfer lock before doors open,
+//! check-in by validators, and a partial refund for tickets that were never
+//! used:
+//!
+//! ```text
+//!        sale + transfers         transfers locked        after the event
+//! ──────────────────────────┬──────────────────────┬─────────────────────────▶
+//!                  starts_at - transfer_lock   starts_at ... ends_at
+//!
+//! Valid ──check_in──▶ CheckedIn          (validator, until ends_at)
+//! Valid ──refund────▶ Refunded           (owner, after ends_at)
+//! ```
+//!
+//! - **Cap.** `buy` fails with `SoldOut` once `capacity` tickets are sold.
+//! - **Transfer lock.** Tickets move freely until `transfer_lock` seconds
+//!   before the start, which stops last-minute scalping and door-side
+//!   resale of screenshots.
+//! - **Validators.** The organizer names the addresses allowed to check
+//!   tickets in. A checked-in ticket cannot be checked in, transferred or
+//!   refunded again.
+//! - **No-show refund.** After the event, the owner of an unused ticket gets
+//!   `refund_bps` of the price back; the organizer keeps the rest.
+//!
+//! Ticket payments stay in the contract until they are earned: at check-in
+//! or refund the organizer's share moves to `earned`, which the organizer
+//! withdraws.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+const BPS: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventTerms {
+    pub token: Address,
+    pub price: i128,
+    pub capacity: u32,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    /// Seconds before `starts_at` from which tickets cannot be transferred.
+    pub transfer_lock: u64,
+    /// Share of the price returned for an unused ticket, in basis points.
+    pub refund_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Event {
+    pub organizer: Address,
+    pub terms: EventTerms,
+    pub sold: u32,
+    /// Organizer's share of checked-in and refunded tickets, not yet withdrawn.
+    pub earned: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TicketStatus {
+    Valid,
+    CheckedIn,
+    Refunded,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ticket {
+    pub event_id: u32,
+    pub owner: Address,
+    pub status: TicketStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextEventId,
+    NextTicketId,
+    Event(u32),
+    Ticket(u32),
+    Validator(u32, Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TicketingError {
+    EventNotFound = 1,
+    TicketNotFound = 2,
+    /// Price, capacity, times or refund share are out of range.
+    InvalidTerms = 3,
+    SoldOut = 4,
+    SalesClosed = 5,
+    NotOwner = 6,
+    TransferLocked = 7,
+    NotValidator = 8,
+    /// The ticket was already checked in or refunded.
+    TicketUsed = 9,
+    EventOver = 10,
+    EventNotOver = 11,
+    NothingToWithdraw = 12,
+}
+
+#[contract]
+pub struct TicketingContract;
+
+#[contractimpl]
+impl TicketingContract {
+    /// Emits `(event, created)` with `(event_id, organizer)`.
+    pub fn create_event(
+        env: Env,
+        organizer: Address,
+        terms: EventTerms,
+    ) -> Result<u32, TicketingError> {
+        organizer.require_auth();
+        if terms.price < 0
+            || terms.capacity == 0
+            || terms.ends_at <= terms.starts_at
+            || terms.refund_bps as i128 > BPS
+        {
+            return Err(TicketingError::InvalidTerms);
+        }
+        let id = Self::next_id(&env, DataKey::NextEventId);
+        Self::save_event(
+            &env,
+            id,
+            &Event {
+                organizer: organizer.clone(),
+                terms,
+                sold: 0,
+                earned: 0,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("event"), symbol_short!("created")),
+            (id, organizer),
+        );
+        Ok(id)
+    }
+
+    /// Allow or disallow `validator` to check in tickets for `event_id`.
+    pub fn set_validator(
+        env: Env,
+        event_id: u32,
+        validator: Address,
+        allowed: bool,
+    ) -> Result<(), TicketingError> {
+        let event = Self::event(env.clone(), event_id)?;
+        event.organizer.require_auth();
+        let key = DataKey::Validator(event_id, validator);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Sell one ticket to `buyer` at the event price. Sales close when the
+    /// event starts.
+    ///
+    /// Emits `(ticket, sold)` with `(ticket_id, event_id, buyer)`.
+    pub fn buy(env: Env, event_id: u32, buyer: Address) -> Result<u32, TicketingError> {
+        buyer.require_auth();
+        let mut event = Self::event(env.clone(), event_id)?;
+        if env.ledger().timestamp() >= event.terms.starts_at {
+            return Err(TicketingError::SalesClosed);
+        }
+        if event.sold >= event.terms.capacity {
+            return Err(TicketingError::SoldOut);
+        }
+        if event.terms.price > 0 {
+            token::Client::new(&env, &event.terms.token).transfer(
+                &buyer,
+                &env.current_contract_address(),
+                &event.terms.price,
+            );
+        }
+        event.sold += 1;
+        Self::save_event(&env, event_id, &event);
+
+        let ticket_id = Self::next_id(&env, DataKey::NextTicketId);
+        Self::save_ticket(
+            &env,
+            ticket_id,
+            &Ticket {
+                event_id,
+                owner: buyer.clone(),
+                status: TicketStatus::Valid,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("ticket"), symbol_short!("sold")),
+            (ticket_id, event_id, buyer),
+        );
+        Ok(ticket_id)
+    }
+
+    /// Hand a valid ticket to `to`, before the transfer lock starts.
+    ///
+    /// Emits `(ticket, transfer)` with `(ticket_id, from, to)`.
+    pub fn transfer(
+        env: Env,
+        ticket_id: u32,
+        from: Address,
+        to: Address,
+    ) -> Result<(), TicketingError> {
+        from.require_auth();
+        let mut ticket = Self::load_valid(&env, ticket_id)?;
+        if ticket.owner != from {
+            return Err(TicketingError::NotOwner);
+        }
+        let terms = Self::event(env.clone(), ticket.event_id)?.terms;
+        let lock_at = terms.starts_at.saturating_sub(terms.transfer_lock);
+        if env.ledger().timestamp() >= lock_at {
+            return Err(TicketingError::TransferLocked);
+        }
+        ticket.owner = to.clone();
+        Self::save_ticket(&env, ticket_id, &ticket);
+        env.events().publish(
+            (symbol_short!("ticket"), symbol_short!("transfer")),
+            (ticket_id, from, to),
+        );
+        Ok(())
+    }
+
+    /// Mark a ticket used at the door. The full price becomes the
+    /// organizer's.
+    ///
+    /// Emits `(ticket, checkin)` with `(ticket_id, validator)`.
+    pub fn check_in(env: Env, ticket_id: u32, validator: Address) -> Result<(), TicketingError> {
+        validator.require_auth();
+        let mut ticket = Self::load_valid(&env, ticket_id)?;
+        if !Self::is_validator(env.clone(), ticket.event_id, validator.clone()) {
+            return Err(TicketingError::NotValidator);
+        }
+        let mut event = Self::event(env.clone(), ticket.event_id)?;
+        if env.ledger().timestamp() >= event.terms.ends_at {
+            return Err(TicketingError::EventOver);
+        }
+        ticket.status = TicketStatus::CheckedIn;
+        Self::save_ticket(&env, ticket_id, &ticket);
+        event.earned += event.terms.price;
+        Self::save_event(&env, ticket.event_id, &event);
+        env.events().publish(
+            (symbol_short!("ticket"), symbol_short!("checkin")),
+            (ticket_id, validator),
+        );
+        Ok(())
+    }
+
+    /// After the event, refund `refund_bps` of the price for an unused
+    /// ticket. Returns the amount refunded.
+    ///
+    /// Emits `(ticket, refund)` with `(ticket_id, amount)`.
+    pub fn refund(env: Env, ticket_id: u32) -> Result<i128, TicketingError> {
+        let mut ticket = Self::load_valid(&env, ticket_id)?;
+        ticket.owner.require_auth();
+        let mut event = Self::event(env.clone(), ticket.event_id)?;
+        if env.ledger().timestamp() < event.terms.ends_at {
+            return Err(TicketingError::EventNotOver);
+        }
+        let amount = event.terms.price * event.terms.refund_bps as i128 / BPS;
+        ticket.status = TicketStatus::Refunded;
+        Self::save_ticket(&env, ticket_id, &ticket);
+        event.earned += event.terms.price - amount;
+        Self::save_event(&env, ticket.event_id, &event);
+        if amount > 0 {
+            token::Client::new(&env, &event.terms.token).transfer(
+                &env.current_contract_address(),
+                &ticket.owner,
+                &amount,
+            );
+        }
+        env.events().publish(
+            (symbol_short!("ticket"), symbol_short!("refund")),
+            (ticket_id, amount),
+        );
+        Ok(amount)
+    }
+
+    /// Pay the organizer everything earned so far.
+    pub fn withdraw(env: Env, event_id: u32) -> Result<i128, TicketingError> {
+        let mut event = Self::event(env.clone(), event_id)?;
+        event.organizer.require_auth();
+        let amount = event.earned;
+        if amount == 0 {
+            return Err(TicketingError::NothingToWithdraw);
+        }
+        event.earned = 0;
+        Self::save_event(&env, event_id, &event);
+        token::Client::new(&env, &event.terms.token).transfer(
+            &env.current_contract_address(),
+            &event.organizer,
+            &amount,
+        );
+        Ok(amount)
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn event(env: Env, event_id: u32) -> Result<Event, TicketingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Event(event_id))
+            .ok_or(TicketingError::EventNotFound)
+    }
+
+    pub fn ticket(env: Env, ticket_id: u32) -> Result<Ticket, TicketingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ticket(ticket_id))
+            .ok_or(TicketingError::TicketNotFound)
+    }
+
+    pub fn is_validator(env: Env, event_id: u32, validator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Validator(event_id, validator))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn load_valid(env: &Env, ticket_id: u32) -> Result<Ticket, TicketingError> {
+        let ticket = Self::ticket(env.clone(), ticket_id)?;
+        if ticket.status != TicketStatus::Valid {
+            return Err(TicketingError::TicketUsed);
+        }
+        Ok(ticket)
+    }
+
+    fn next_id(env: &Env, key: DataKey) -> u32 {
+        let id: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(id + 1));
+        id
+    }
+
+    fn save_event(env: &Env, event_id: u32, event: &Event) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(event_id), event);
+    }
+
+    fn save_ticket(env: &Env, ticket_id: u32, ticket: &Ticket) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Ticket(ticket_id), ticket);
+    }
+}
+
+mod test;
@@ -0,0 +1,258 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+};
+
+const START: u64 = 10_000;
+const END: u64 = 20_000;
+
+struct Setup<'a> {
+    env: Env,
+    client: TicketingContractClient<'a>,
+    token: TokenClient<'a>,
+    organizer: Address,
+    validator: Address,
+    event_id: u32,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let client =
+        TicketingContractClient::new(&env, &env.register_contract(None, TicketingContract));
+    let organizer = Address::generate(&env);
+    let validator = Address::generate(&env);
+
+    let event_id = client.create_event(&organizer, &terms(&token_id, 2));
+    client.set_validator(&event_id, &validator, &true);
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        client,
+        organizer,
+        validator,
+        event_id,
+    }
+}
+
+fn terms(token: &Address, capacity: u32) -> EventTerms {
+    EventTerms {
+        token: token.clone(),
+        price: 100,
+        capacity,
+        starts_at: START,
+        ends_at: END,
+        transfer_lock: 1_000,
+        refund_bps: 5_000,
+    }
+}
+
+fn funded_buyer(s: &Setup) -> Address {
+    let buyer = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&buyer, &1_000);
+    buyer
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+}
+
+#[test]
+fn test_buy_escrows_price() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    let ticket = s.client.ticket(&ticket_id);
+    assert_eq!(ticket.owner, buyer);
+    assert_eq!(ticket.status, TicketStatus::Valid);
+    assert_eq!(s.token.balance(&buyer), 900);
+    assert_eq!(s.token.balance(&s.client.address), 100);
+    assert_eq!(s.client.event(&s.event_id).sold, 1);
+}
+
+#[test]
+fn test_create_event_validates_terms() {
+    let s = setup();
+    let mut bad = terms(&s.token.address, 0);
+    assert_eq!(
+        s.client.try_create_event(&s.organizer, &bad),
+        Err(Ok(TicketingError::InvalidTerms))
+    );
+    bad.capacity = 1;
+    bad.ends_at = START;
+    assert_eq!(
+        s.client.try_create_event(&s.organizer, &bad),
+        Err(Ok(TicketingError::InvalidTerms))
+    );
+    bad.ends_at = END;
+    bad.refund_bps = 10_001;
+    assert_eq!(
+        s.client.try_create_event(&s.organizer, &bad),
+        Err(Ok(TicketingError::InvalidTerms))
+    );
+}
+
+#[test]
+fn test_sold_out() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    s.client.buy(&s.event_id, &buyer);
+    s.client.buy(&s.event_id, &buyer);
+    assert_eq!(
+        s.client.try_buy(&s.event_id, &buyer),
+        Err(Ok(TicketingError::SoldOut))
+    );
+}
+
+#[test]
+fn test_sales_close_at_start() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    set_time(&s.env, START);
+    assert_eq!(
+        s.client.try_buy(&s.event_id, &buyer),
+        Err(Ok(TicketingError::SalesClosed))
+    );
+}
+
+#[test]
+fn test_transfer_before_lock() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let friend = Address::generate(&s.env);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    s.client.transfer(&ticket_id, &buyer, &friend);
+    assert_eq!(s.client.ticket(&ticket_id).owner, friend);
+    assert_eq!(
+        s.client.try_transfer(&ticket_id, &buyer, &friend),
+        Err(Ok(TicketingError::NotOwner))
+    );
+}
+
+#[test]
+fn test_transfer_locked_before_start() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let friend = Address::generate(&s.env);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    set_time(&s.env, START - 1_000);
+    assert_eq!(
+        s.client.try_transfer(&ticket_id, &buyer, &friend),
+        Err(Ok(TicketingError::TransferLocked))
+    );
+}
+
+#[test]
+fn test_check_in_marks_ticket_used() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    set_time(&s.env, START);
+    s.client.check_in(&ticket_id, &s.validator);
+    assert_eq!(s.client.ticket(&ticket_id).status, TicketStatus::CheckedIn);
+    assert_eq!(s.client.event(&s.event_id).earned, 100);
+    assert_eq!(
+        s.client.try_check_in(&ticket_id, &s.validator),
+        Err(Ok(TicketingError::TicketUsed))
+    );
+}
+
+#[test]
+fn test_check_in_requires_validator() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    assert_eq!(
+        s.client.try_check_in(&ticket_id, &buyer),
+        Err(Ok(TicketingError::NotValidator))
+    );
+    s.client.set_validator(&s.event_id, &s.validator, &false);
+    assert_eq!(
+        s.client.try_check_in(&ticket_id, &s.validator),
+        Err(Ok(TicketingError::NotValidator))
+    );
+}
+
+#[test]
+fn test_check_in_closes_at_end() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    set_time(&s.env, END);
+    assert_eq!(
+        s.client.try_check_in(&ticket_id, &s.validator),
+        Err(Ok(TicketingError::EventOver))
+    );
+}
+
+#[test]
+fn test_refund_unused_ticket_after_event() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+
+    assert_eq!(
+        s.client.try_refund(&ticket_id),
+        Err(Ok(TicketingError::EventNotOver))
+    );
+    set_time(&s.env, END);
+    assert_eq!(s.client.refund(&ticket_id), 50);
+    assert_eq!(s.token.balance(&buyer), 950);
+    assert_eq!(s.client.ticket(&ticket_id).status, TicketStatus::Refunded);
+    assert_eq!(
+        s.client.try_refund(&ticket_id),
+        Err(Ok(TicketingError::TicketUsed))
+    );
+}
+
+#[test]
+fn test_checked_in_ticket_cannot_be_refunded() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let ticket_id = s.client.buy(&s.event_id, &buyer);
+    set_time(&s.env, START);
+    s.client.check_in(&ticket_id, &s.validator);
+
+    set_time(&s.env, END);
+    assert_eq!(
+        s.client.try_refund(&ticket_id),
+        Err(Ok(TicketingError::TicketUsed))
+    );
+}
+
+#[test]
+fn test_organizer_withdraws_earned() {
+    let s = setup();
+    let buyer = funded_buyer(&s);
+    let used = s.client.buy(&s.event_id, &buyer);
+    let unused = s.client.buy(&s.event_id, &buyer);
+    assert_eq!(
+        s.client.try_withdraw(&s.event_id),
+        Err(Ok(TicketingError::NothingToWithdraw))
+    );
+
+    set_time(&s.env, START);
+    s.client.check_in(&used, &s.validator);
+    set_time(&s.env, END);
+    s.client.refund(&unused);
+
+    // 100 for the used ticket + the kept half of the refunded one.
+    assert_eq!(s.client.withdraw(&s.event_id), 150);
+    assert_eq!(s.token.balance(&s.organizer), 150);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
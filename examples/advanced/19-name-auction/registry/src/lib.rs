@@ -0,0 +1,168 @@
+//! Name registry for the name auction example.
+//!
+//! Maps names to owners for a limited term. Ordinary names that were never
+//! registered can be claimed directly. Premium (short) names and names whose
+//! term expired can only be assigned by the registrar, which is the auction
+//! contract.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
+};
+
+/// Names of at most this many bytes are premium.
+pub const PREMIUM_MAX_LEN: u32 = 3;
+/// Registration term of a directly claimed name (365 days).
+pub const CLAIM_TERM: u64 = 365 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameRecord {
+    pub owner: Address,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Registrar,
+    Name(String),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RegistryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidName = 3,
+    /// The name is registered and its term has not expired.
+    Unavailable = 4,
+    /// Premium names are only assigned by the registrar.
+    Premium = 5,
+    /// Expired names are only re-assigned by the registrar.
+    Expired = 6,
+    NotOwner = 7,
+}
+
+#[contract]
+pub struct NameRegistry;
+
+#[contractimpl]
+impl NameRegistry {
+    pub fn init(env: Env, registrar: Address) -> Result<(), RegistryError> {
+        if env.storage().instance().has(&DataKey::Registrar) {
+            return Err(RegistryError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Registrar, &registrar);
+        Ok(())
+    }
+
+    /// Claim an ordinary, never-registered name for `CLAIM_TERM`.
+    pub fn claim(env: Env, name: String, owner: Address) -> Result<u64, RegistryError> {
+        owner.require_auth();
+        if name.is_empty() {
+            return Err(RegistryError::InvalidName);
+        }
+        if Self::is_premium(env.clone(), name.clone()) {
+            return Err(RegistryError::Premium);
+        }
+        if let Some(record) = Self::record(env.clone(), name.clone()) {
+            return Err(if Self::is_active(&env, &record) {
+                RegistryError::Unavailable
+            } else {
+                RegistryError::Expired
+            });
+        }
+        let expires_at = env.ledger().timestamp() + CLAIM_TERM;
+        Self::assign(&env, name, owner, expires_at);
+        Ok(expires_at)
+    }
+
+    /// Assign any available name until `expires_at`. Registrar only.
+    pub fn register(
+        env: Env,
+        name: String,
+        owner: Address,
+        expires_at: u64,
+    ) -> Result<(), RegistryError> {
+        let registrar: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Registrar)
+            .ok_or(RegistryError::NotInitialized)?;
+        registrar.require_auth();
+        if name.is_empty() || expires_at <= env.ledger().timestamp() {
+            return Err(RegistryError::InvalidName);
+        }
+        if !Self::is_available(env.clone(), name.clone()) {
+            return Err(RegistryError::Unavailable);
+        }
+        Self::assign(&env, name, owner, expires_at);
+        Ok(())
+    }
+
+    pub fn transfer(
+        env: Env,
+        name: String,
+        from: Address,
+        to: Address,
+    ) -> Result<(), RegistryError> {
+        from.require_auth();
+        if Self::resolve(env.clone(), name.clone()) != Some(from) {
+            return Err(RegistryError::NotOwner);
+        }
+        let mut record = Self::record(env.clone(), name.clone()).unwrap();
+        record.owner = to;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Name(name), &record);
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    /// Current owner, or `None` if the name is unregistered or expired.
+    pub fn resolve(env: Env, name: String) -> Option<Address> {
+        Self::record(env.clone(), name)
+            .filter(|record| Self::is_active(&env, record))
+            .map(|record| record.owner)
+    }
+
+    pub fn record(env: Env, name: String) -> Option<NameRecord> {
+        env.storage().persistent().get(&DataKey::Name(name))
+    }
+
+    pub fn is_available(env: Env, name: String) -> bool {
+        Self::resolve(env, name).is_none()
+    }
+
+    pub fn is_premium(_env: Env, name: String) -> bool {
+        name.len() <= PREMIUM_MAX_LEN
+    }
+
+    // ==================== HELPERS ====================
+
+    fn is_active(env: &Env, record: &NameRecord) -> bool {
+        env.ledger().timestamp() < record.expires_at
+    }
+
+    fn assign(env: &Env, name: String, owner: Address, expires_at: u64) {
+        let record = NameRecord {
+            owner: owner.clone(),
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Name(name.clone()), &record);
+        env.events().publish(
+            (symbol_short!("name"), symbol_short!("assigned")),
+            (name, owner, expires_at),
+        );
+    }
+}
+
+mod test;
@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+
+fn setup() -> (Env, NameRegistryClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = NameRegistryClient::new(&env, &env.register_contract(None, NameRegistry));
+    let registrar = Address::generate(&env);
+    client.init(&registrar);
+    (env, client, registrar)
+}
+
+#[test]
+fn test_claim_ordinary_name() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    let name = String::from_str(&env, "alice");
+
+    assert_eq!(client.claim(&name, &alice), CLAIM_TERM);
+    assert_eq!(client.resolve(&name), Some(alice.clone()));
+    assert_eq!(
+        client.try_claim(&name, &alice),
+        Err(Ok(RegistryError::Unavailable))
+    );
+}
+
+#[test]
+fn test_premium_and_expired_names_are_not_claimable() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    assert_eq!(
+        client.try_claim(&String::from_str(&env, "abc"), &alice),
+        Err(Ok(RegistryError::Premium))
+    );
+
+    let name = String::from_str(&env, "alice");
+    client.claim(&name, &alice);
+    env.ledger().with_mut(|l| l.timestamp = CLAIM_TERM);
+    assert!(client.is_available(&name));
+    assert_eq!(
+        client.try_claim(&name, &alice),
+        Err(Ok(RegistryError::Expired))
+    );
+}
+
+#[test]
+fn test_registrar_assigns_available_names() {
+    let (env, client, _) = setup();
+    let bob = Address::generate(&env);
+    let name = String::from_str(&env, "abc");
+
+    client.register(&name, &bob, &100);
+    assert_eq!(client.resolve(&name), Some(bob.clone()));
+    assert_eq!(
+        client.try_register(&name, &bob, &200),
+        Err(Ok(RegistryError::Unavailable))
+    );
+}
+
+#[test]
+fn test_transfer_requires_active_owner() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let name = String::from_str(&env, "alice");
+    client.claim(&name, &alice);
+
+    assert_eq!(
+        client.try_transfer(&name, &bob, &alice),
+        Err(Ok(RegistryError::NotOwner))
+    );
+    client.transfer(&name, &alice, &bob);
+    assert_eq!(client.resolve(&name), Some(bob));
+}
@@ -0,0 +1,247 @@
+//! # Name Auction
+//!
+//! Sells names the registry will not hand out directly (premium names and
+//! names whose term expired) in English auctions, and registers the winner
+//! in the registry when the auction settles:
+//!
+//! ```text
+//! start("abc")              registry.is_available("abc")?
+//! bid(bidder, amount)       amount escrowed here, previous bidder refunded
+//! ... ends_at ...
+//! settle("abc")             registry.register("abc", winner, now + term)
+//!                             Ok  → winning bid to the treasury
+//!                             Err → winning bid back to the winner
+//! ```
+//!
+//! The two contracts live in separate crates (`registry/` holds the
+//! registry). This crate does not link the registry; it declares the two
+//! functions it calls with `#[contractclient]`. The registry only lets its
+//! registrar call `register`, and the auction contract is that registrar: a
+//! contract's own address is authorized for calls it makes directly.
+//!
+//! Bids never leave escrow until the outcome is known, so a failed
+//! registration cannot leave the winner paying for nothing.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Env, String,
+};
+
+/// A new bid must beat the current one by at least this much, in basis
+/// points.
+pub const MIN_INCREMENT_BPS: i128 = 500;
+
+/// The part of the registry's interface the auction uses.
+#[contractclient(name = "RegistryClient")]
+pub trait NameRegistryInterface {
+    fn is_available(env: Env, name: String) -> bool;
+    fn register(env: Env, name: String, owner: Address, expires_at: u64);
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionConfig {
+    pub registry: Address,
+    pub token: Address,
+    /// Receives winning bids.
+    pub treasury: Address,
+    pub min_bid: i128,
+    /// Seconds an auction runs.
+    pub duration: u64,
+    /// Seconds a won name stays registered.
+    pub term: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub ends_at: u64,
+    pub bidder: Option<Address>,
+    pub bid: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Auction(String),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuctionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    /// The registry reports the name as taken.
+    NameUnavailable = 4,
+    AuctionExists = 5,
+    NoAuction = 6,
+    AuctionEnded = 7,
+    AuctionNotEnded = 8,
+    BidTooLow = 9,
+}
+
+#[contract]
+pub struct NameAuction;
+
+#[contractimpl]
+impl NameAuction {
+    pub fn initialize(env: Env, config: AuctionConfig) -> Result<(), AuctionError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(AuctionError::AlreadyInitialized);
+        }
+        if config.min_bid <= 0 || config.duration == 0 || config.term == 0 {
+            return Err(AuctionError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Open an auction for a name the registry reports as available.
+    /// Anyone can start one.
+    ///
+    /// Emits `(auction, started)` with `(name, ends_at)`.
+    pub fn start(env: Env, name: String) -> Result<u64, AuctionError> {
+        let config = Self::config(env.clone())?;
+        if Self::auction(env.clone(), name.clone()).is_some() {
+            return Err(AuctionError::AuctionExists);
+        }
+        if !RegistryClient::new(&env, &config.registry).is_available(&name) {
+            return Err(AuctionError::NameUnavailable);
+        }
+        let ends_at = env.ledger().timestamp() + config.duration;
+        Self::save(
+            &env,
+            &name,
+            &Auction {
+                ends_at,
+                bidder: None,
+                bid: 0,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("auction"), symbol_short!("started")),
+            (name, ends_at),
+        );
+        Ok(ends_at)
+    }
+
+    /// Escrow `amount` as the new highest bid and refund the bid it beats.
+    ///
+    /// Emits `(auction, bid)` with `(name, bidder, amount)`.
+    pub fn bid(env: Env, name: String, bidder: Address, amount: i128) -> Result<(), AuctionError> {
+        bidder.require_auth();
+        let config = Self::config(env.clone())?;
+        let mut auction =
+            Self::auction(env.clone(), name.clone()).ok_or(AuctionError::NoAuction)?;
+        if env.ledger().timestamp() >= auction.ends_at {
+            return Err(AuctionError::AuctionEnded);
+        }
+        if amount < Self::min_next_bid(&config, &auction) {
+            return Err(AuctionError::BidTooLow);
+        }
+
+        let token = token::Client::new(&env, &config.token);
+        token.transfer(&bidder, &env.current_contract_address(), &amount);
+        if let Some(previous) = auction.bidder.take() {
+            token.transfer(&env.current_contract_address(), &previous, &auction.bid);
+        }
+        auction.bidder = Some(bidder.clone());
+        auction.bid = amount;
+        Self::save(&env, &name, &auction);
+        env.events().publish(
+            (symbol_short!("auction"), symbol_short!("bid")),
+            (name, bidder, amount),
+        );
+        Ok(())
+    }
+
+    /// Close an ended auction and register the winner. Anyone can settle.
+    /// Returns the new owner, or `None` if nobody bid or the registry
+    /// refused the registration.
+    ///
+    /// Emits `(auction, settled)` with `(name, winner, bid)`, or
+    /// `(auction, refunded)` with `(name, bidder, bid)` when the registry
+    /// refuses.
+    pub fn settle(env: Env, name: String) -> Result<Option<Address>, AuctionError> {
+        let config = Self::config(env.clone())?;
+        let auction = Self::auction(env.clone(), name.clone()).ok_or(AuctionError::NoAuction)?;
+        let now = env.ledger().timestamp();
+        if now < auction.ends_at {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Auction(name.clone()));
+
+        let Some(winner) = auction.bidder else {
+            return Ok(None);
+        };
+        let token = token::Client::new(&env, &config.token);
+        let registered = RegistryClient::new(&env, &config.registry)
+            .try_register(&name, &winner, &(now + config.term))
+            .is_ok();
+        if registered {
+            token.transfer(
+                &env.current_contract_address(),
+                &config.treasury,
+                &auction.bid,
+            );
+            env.events().publish(
+                (symbol_short!("auction"), symbol_short!("settled")),
+                (name, winner.clone(), auction.bid),
+            );
+            Ok(Some(winner))
+        } else {
+            token.transfer(&env.current_contract_address(), &winner, &auction.bid);
+            env.events().publish(
+                (symbol_short!("auction"), symbol_short!("refunded")),
+                (name, winner, auction.bid),
+            );
+            Ok(None)
+        }
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn config(env: Env) -> Result<AuctionConfig, AuctionError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(AuctionError::NotInitialized)
+    }
+
+    pub fn auction(env: Env, name: String) -> Option<Auction> {
+        env.storage().persistent().get(&DataKey::Auction(name))
+    }
+
+    /// Smallest bid `bid` would currently accept for `name`.
+    pub fn min_bid_for(env: Env, name: String) -> Result<i128, AuctionError> {
+        let config = Self::config(env.clone())?;
+        let auction = Self::auction(env, name).ok_or(AuctionError::NoAuction)?;
+        Ok(Self::min_next_bid(&config, &auction))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn min_next_bid(config: &AuctionConfig, auction: &Auction) -> i128 {
+        if auction.bidder.is_none() {
+            return config.min_bid;
+        }
+        // Round the increment up so tiny bids still have to grow.
+        let increment = (auction.bid * MIN_INCREMENT_BPS + 9_999) / 10_000;
+        auction.bid + increment
+    }
+
+    fn save(env: &Env, name: &String, auction: &Auction) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(name.clone()), auction);
+    }
+}
+
+mod test;
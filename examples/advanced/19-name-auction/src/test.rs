@@ -0,0 +1,208 @@
+#![cfg(test)]
+
+use super::*;
+use name_registry::{NameRegistry, NameRegistryClient, RegistryError, CLAIM_TERM};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+};
+
+const DURATION: u64 = 1_000;
+const TERM: u64 = 100_000;
+
+struct Setup<'a> {
+    env: Env,
+    auction: NameAuctionClient<'a>,
+    registry: NameRegistryClient<'a>,
+    token: TokenClient<'a>,
+    treasury: Address,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let registry = NameRegistryClient::new(&env, &env.register_contract(None, NameRegistry));
+    let auction = NameAuctionClient::new(&env, &env.register_contract(None, NameAuction));
+    let treasury = Address::generate(&env);
+
+    registry.init(&auction.address);
+    auction.initialize(&AuctionConfig {
+        registry: registry.address.clone(),
+        token: token_id.clone(),
+        treasury: treasury.clone(),
+        min_bid: 100,
+        duration: DURATION,
+        term: TERM,
+    });
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        auction,
+        registry,
+        treasury,
+    }
+}
+
+fn bidder(s: &Setup) -> Address {
+    let bidder = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&bidder, &10_000);
+    bidder
+}
+
+fn name(s: &Setup, name: &str) -> String {
+    String::from_str(&s.env, name)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_premium_name_goes_to_winner() {
+    let s = setup();
+    let abc = name(&s, "abc");
+    let alice = bidder(&s);
+
+    assert_eq!(s.auction.start(&abc), DURATION);
+    s.auction.bid(&abc, &alice, &300);
+    assert_eq!(s.token.balance(&s.auction.address), 300);
+
+    advance(&s.env, DURATION);
+    assert_eq!(s.auction.settle(&abc), Some(alice.clone()));
+    assert_eq!(s.registry.resolve(&abc), Some(alice));
+    assert_eq!(s.registry.record(&abc).unwrap().expires_at, DURATION + TERM);
+    assert_eq!(s.token.balance(&s.treasury), 300);
+    assert_eq!(s.auction.auction(&abc), None);
+}
+
+#[test]
+fn test_outbid_refunds_previous_bidder() {
+    let s = setup();
+    let abc = name(&s, "abc");
+    let alice = bidder(&s);
+    let bob = bidder(&s);
+    s.auction.start(&abc);
+
+    s.auction.bid(&abc, &alice, &200);
+    // 5% above 200.
+    assert_eq!(s.auction.min_bid_for(&abc), 210);
+    assert_eq!(
+        s.auction.try_bid(&abc, &bob, &209),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.auction.bid(&abc, &bob, &210);
+
+    assert_eq!(s.token.balance(&alice), 10_000);
+    assert_eq!(s.token.balance(&bob), 9_790);
+    assert_eq!(s.token.balance(&s.auction.address), 210);
+}
+
+#[test]
+fn test_first_bid_needs_minimum() {
+    let s = setup();
+    let abc = name(&s, "abc");
+    s.auction.start(&abc);
+    assert_eq!(
+        s.auction.try_bid(&abc, &bidder(&s), &99),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+}
+
+#[test]
+fn test_taken_name_cannot_be_auctioned() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let taken = name(&s, "alice");
+    s.registry.claim(&taken, &alice);
+
+    assert_eq!(
+        s.auction.try_start(&taken),
+        Err(Ok(AuctionError::NameUnavailable))
+    );
+}
+
+#[test]
+fn test_expired_name_is_re_auctioned() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = bidder(&s);
+    let expired = name(&s, "alice");
+    s.registry.claim(&expired, &alice);
+
+    advance(&s.env, CLAIM_TERM);
+    assert_eq!(
+        s.registry.try_claim(&expired, &alice),
+        Err(Ok(RegistryError::Expired))
+    );
+    s.auction.start(&expired);
+    s.auction.bid(&expired, &bob, &100);
+    advance(&s.env, DURATION);
+    s.auction.settle(&expired);
+    assert_eq!(s.registry.resolve(&expired), Some(bob));
+}
+
+#[test]
+fn test_duplicate_auction_rejected() {
+    let s = setup();
+    let abc = name(&s, "abc");
+    s.auction.start(&abc);
+    assert_eq!(
+        s.auction.try_start(&abc),
+        Err(Ok(AuctionError::AuctionExists))
+    );
+}
+
+#[test]
+fn test_timing_rules() {
+    let s = setup();
+    let abc = name(&s, "abc");
+    let alice = bidder(&s);
+    s.auction.start(&abc);
+    s.auction.bid(&abc, &alice, &100);
+
+    assert_eq!(
+        s.auction.try_settle(&abc),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    advance(&s.env, DURATION);
+    assert_eq!(
+        s.auction.try_bid(&abc, &alice, &500),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+}
+
+#[test]
+fn test_settle_without_bids() {
+    let s = setup();
+    let abc = name(&s, "abc");
+    s.auction.start(&abc);
+    advance(&s.env, DURATION);
+
+    assert_eq!(s.auction.settle(&abc), None);
+    assert!(s.registry.is_available(&abc));
+    // The name can be auctioned again.
+    s.auction.start(&abc);
+}
+
+#[test]
+fn test_failed_registration_refunds_winner() {
+    let s = setup();
+    let alice = bidder(&s);
+    let carol = Address::generate(&s.env);
+    let ordinary = name(&s, "ordinary");
+    s.auction.start(&ordinary);
+    s.auction.bid(&ordinary, &alice, &400);
+
+    // An ordinary name can still be claimed directly while it is auctioned.
+    s.registry.claim(&ordinary, &carol);
+    advance(&s.env, DURATION);
+
+    assert_eq!(s.auction.settle(&ordinary), None);
+    assert_eq!(s.registry.resolve(&ordinary), Some(carol));
+    assert_eq!(s.token.balance(&alice), 10_000);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+}
@@ -0,0 +1,454 @@
+//! # Streaming Rewards
+//!
+//! A staking pool that streams `rate` reward tokens per second, split
+//! between stakers in proportion to their stake, with a global pause for
+//! incident response:
+//!
+//! ```text
+//!         accruing           paused            accruing
+//! ──────────────────────┬───────────────┬──────────────────────▶ time
+//!                     pause()        resume()
+//!                  checkpoint       checkpoint
+//!                  (accrue to t)    (skip to t, accrue nothing)
+//! ```
+//!
+//! ## The accumulator
+//!
+//! The pool keeps one number, `acc_per_share`: the reward a single staked
+//! unit has earned since deployment, scaled by `ACC_SCALE`. A staker's
+//! position remembers the value it last saw, so what they earned since then
+//! is `amount * (acc_per_share - snapshot)`. Every change to `total_staked`
+//! (or to the rate) is preceded by a checkpoint that brings the accumulator
+//! up to the current time, so each stretch of time is divided by the stake
+//! that was actually present.
+//!
+//! ## Pausing
+//!
+//! Pausing and resuming are checkpoints too. `pause` accrues everything up
+//! to the pause and flags the pool; while paused, a checkpoint moves
+//! `last_update` forward without accruing; `resume` does the same once more
+//! before clearing the flag. The paused window therefore never enters the
+//! accumulator, and stakers who join, leave or claim during it are settled
+//! exactly as if the window had been cut out of the timeline.
+//!
+//! Pausing stops accrual only. Stakers can still unstake and claim what
+//! they earned before the pause.
+//!
+//! ## Emergency withdrawal
+//!
+//! `unstake` settles rewards first. If the reward math is broken (an
+//! overflow, a bad rate) or the reward token misbehaves, settling fails
+//! and stake is stuck with it. The safety hatch is a second exit that
+//! touches nothing but the stake:
+//!
+//! - A guardian, set by the admin, turns emergency mode on with
+//!   `set_emergency(true)`. Doing so pauses the pool without a checkpoint,
+//!   so accrual stops and no reward math runs.
+//! - In emergency mode, `emergency_withdraw` returns a staker's whole stake
+//!   and deletes their position. Unclaimed rewards and anything accrued
+//!   since the last checkpoint are forfeited.
+//! - New stakes are refused, and the admin cannot `resume` until the
+//!   guardian turns emergency mode off.
+//!
+//! ## Precision
+//!
+//! `rate * elapsed * ACC_SCALE / total_staked` rarely divides evenly. The
+//! remainder is carried in `acc_carry` and added to the next checkpoint
+//! instead of being dropped, so splitting time into many short checkpoints
+//! (pause/resume, frequent stakes) distributes the same total as one long
+//! one. Individual claims still round down to whole token units.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+/// Fixed-point scale of `acc_per_share`.
+pub const ACC_SCALE: i128 = 1_000_000_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub stake_token: Address,
+    /// Paid out of this contract's balance; the admin funds it.
+    pub reward_token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    /// Reward tokens streamed per second across all stakers.
+    pub rate: i128,
+    pub total_staked: i128,
+    /// Reward per staked unit since deployment, times `ACC_SCALE`.
+    pub acc_per_share: i128,
+    /// Scaled reward left over from the last division, carried into the
+    /// next checkpoint.
+    pub acc_carry: i128,
+    /// Time the accumulator was last brought up to date.
+    pub last_update: u64,
+    pub paused: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub amount: i128,
+    /// `acc_per_share` when this position was last settled.
+    pub snapshot: i128,
+    /// Settled but unclaimed rewards.
+    pub unclaimed: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Pool,
+    Position(Address),
+    /// May turn emergency mode on and off.
+    Guardian,
+    Emergency,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RewardsError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidRate = 4,
+    InsufficientStake = 5,
+    AlreadyPaused = 6,
+    NotPaused = 7,
+    GuardianNotSet = 8,
+    /// `stake` or `resume` while emergency mode is on.
+    EmergencyActive = 9,
+    /// `emergency_withdraw` while emergency mode is off.
+    EmergencyNotActive = 10,
+}
+
+#[contract]
+pub struct StreamingRewards;
+
+#[contractimpl]
+impl StreamingRewards {
+    pub fn initialize(env: Env, config: Config, rate: i128) -> Result<(), RewardsError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(RewardsError::AlreadyInitialized);
+        }
+        if rate < 0 {
+            return Err(RewardsError::InvalidRate);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(
+            &DataKey::Pool,
+            &Pool {
+                rate,
+                total_staked: 0,
+                acc_per_share: 0,
+                acc_carry: 0,
+                last_update: env.ledger().timestamp(),
+                paused: false,
+            },
+        );
+        Ok(())
+    }
+
+    // ==================== STAKING ====================
+
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<(), RewardsError> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(RewardsError::InvalidAmount);
+        }
+        if Self::emergency(env.clone()) {
+            return Err(RewardsError::EmergencyActive);
+        }
+        let config = Self::config(env.clone())?;
+        let mut pool = Self::checkpoint(&env)?;
+        let mut position = Self::settle(&env, &pool, &staker);
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &staker,
+            &env.current_contract_address(),
+            &amount,
+        );
+        position.amount += amount;
+        pool.total_staked += amount;
+        Self::save(&env, &pool, &staker, &position);
+        Ok(())
+    }
+
+    pub fn unstake(env: Env, staker: Address, amount: i128) -> Result<(), RewardsError> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(RewardsError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let mut pool = Self::checkpoint(&env)?;
+        let mut position = Self::settle(&env, &pool, &staker);
+        if amount > position.amount {
+            return Err(RewardsError::InsufficientStake);
+        }
+
+        position.amount -= amount;
+        pool.total_staked -= amount;
+        Self::save(&env, &pool, &staker, &position);
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+        Ok(())
+    }
+
+    /// Pay out everything `staker` has earned so far and return the amount.
+    ///
+    /// Emits `(rewards, claimed)` with `(staker, amount)`.
+    pub fn claim(env: Env, staker: Address) -> Result<i128, RewardsError> {
+        staker.require_auth();
+        let config = Self::config(env.clone())?;
+        let pool = Self::checkpoint(&env)?;
+        let mut position = Self::settle(&env, &pool, &staker);
+        let amount = position.unclaimed;
+        position.unclaimed = 0;
+        Self::save(&env, &pool, &staker, &position);
+
+        if amount > 0 {
+            token::Client::new(&env, &config.reward_token).transfer(
+                &env.current_contract_address(),
+                &staker,
+                &amount,
+            );
+            env.events().publish(
+                (symbol_short!("rewards"), symbol_short!("claimed")),
+                (staker, amount),
+            );
+        }
+        Ok(amount)
+    }
+
+    /// Return `staker`'s whole stake without settling rewards, and return
+    /// the amount. Only in emergency mode. Rewards are forfeited.
+    ///
+    /// Emits `(rewards, emergency)` with `(staker, amount)`.
+    pub fn emergency_withdraw(env: Env, staker: Address) -> Result<i128, RewardsError> {
+        staker.require_auth();
+        if !Self::emergency(env.clone()) {
+            return Err(RewardsError::EmergencyNotActive);
+        }
+        let config = Self::config(env.clone())?;
+        let amount = Self::position_of(&env, &staker).amount;
+        if amount == 0 {
+            return Err(RewardsError::InsufficientStake);
+        }
+
+        // No checkpoint: the pool is paused, so the stored accumulator is
+        // final and only the stake total changes.
+        let mut pool = Self::pool(env.clone())?;
+        pool.total_staked -= amount;
+        env.storage().instance().set(&DataKey::Pool, &pool);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Position(staker.clone()));
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+        env.events().publish(
+            (symbol_short!("rewards"), symbol_short!("emergency")),
+            (staker, amount),
+        );
+        Ok(amount)
+    }
+
+    // ==================== ADMIN ====================
+
+    /// Stop accrual. Everything earned up to now is checkpointed first.
+    ///
+    /// Emits `(rewards, paused)` with the pause time.
+    pub fn pause(env: Env) -> Result<(), RewardsError> {
+        Self::require_admin(&env)?;
+        let mut pool = Self::checkpoint(&env)?;
+        if pool.paused {
+            return Err(RewardsError::AlreadyPaused);
+        }
+        pool.paused = true;
+        env.storage().instance().set(&DataKey::Pool, &pool);
+        env.events().publish(
+            (symbol_short!("rewards"), symbol_short!("paused")),
+            pool.last_update,
+        );
+        Ok(())
+    }
+
+    /// Restart accrual from now. The checkpoint skips the paused window.
+    ///
+    /// Emits `(rewards, resumed)` with the resume time.
+    pub fn resume(env: Env) -> Result<(), RewardsError> {
+        Self::require_admin(&env)?;
+        if Self::emergency(env.clone()) {
+            return Err(RewardsError::EmergencyActive);
+        }
+        let mut pool = Self::checkpoint(&env)?;
+        if !pool.paused {
+            return Err(RewardsError::NotPaused);
+        }
+        pool.paused = false;
+        env.storage().instance().set(&DataKey::Pool, &pool);
+        env.events().publish(
+            (symbol_short!("rewards"), symbol_short!("resumed")),
+            pool.last_update,
+        );
+        Ok(())
+    }
+
+    /// Change the stream rate from now on. Time before the change keeps the
+    /// old rate.
+    pub fn set_rate(env: Env, rate: i128) -> Result<(), RewardsError> {
+        Self::require_admin(&env)?;
+        if rate < 0 {
+            return Err(RewardsError::InvalidRate);
+        }
+        let mut pool = Self::checkpoint(&env)?;
+        pool.rate = rate;
+        env.storage().instance().set(&DataKey::Pool, &pool);
+        Ok(())
+    }
+
+    pub fn set_guardian(env: Env, guardian: Address) -> Result<(), RewardsError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+        Ok(())
+    }
+
+    // ==================== GUARDIAN ====================
+
+    /// Turn emergency mode on or off. Turning it on pauses the pool
+    /// without a checkpoint, so no reward math runs. Turning it off leaves
+    /// the pool paused for the admin to `resume`.
+    ///
+    /// Emits `(rewards, emerg_on)` or `(rewards, emerg_off)`.
+    pub fn set_emergency(env: Env, active: bool) -> Result<(), RewardsError> {
+        let guardian: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Guardian)
+            .ok_or(RewardsError::GuardianNotSet)?;
+        guardian.require_auth();
+
+        if active {
+            let mut pool = Self::pool(env.clone())?;
+            pool.paused = true;
+            env.storage().instance().set(&DataKey::Pool, &pool);
+        }
+        env.storage().instance().set(&DataKey::Emergency, &active);
+        let topic = if active {
+            symbol_short!("emerg_on")
+        } else {
+            symbol_short!("emerg_off")
+        };
+        env.events()
+            .publish((symbol_short!("rewards"), topic), guardian);
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn config(env: Env) -> Result<Config, RewardsError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(RewardsError::NotInitialized)
+    }
+
+    pub fn emergency(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Emergency)
+            .unwrap_or(false)
+    }
+
+    /// The pool as last checkpointed.
+    pub fn pool(env: Env) -> Result<Pool, RewardsError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pool)
+            .ok_or(RewardsError::NotInitialized)
+    }
+
+    pub fn position(env: Env, staker: Address) -> Position {
+        Self::position_of(&env, &staker)
+    }
+
+    /// What `staker` could claim right now, without writing anything.
+    pub fn pending(env: Env, staker: Address) -> Result<i128, RewardsError> {
+        let pool = Self::accrued(&env, Self::pool(env.clone())?);
+        Ok(Self::earned(&pool, &Self::position_of(&env, &staker)))
+    }
+
+    // ==================== HELPERS ====================
+
+    /// `pool` brought up to the current time: accrued if running, just
+    /// moved forward if paused or empty.
+    fn accrued(env: &Env, mut pool: Pool) -> Pool {
+        let now = env.ledger().timestamp();
+        let elapsed = (now - pool.last_update) as i128;
+        if !pool.paused && pool.total_staked > 0 && elapsed > 0 {
+            let scaled = pool.rate * elapsed * ACC_SCALE + pool.acc_carry;
+            pool.acc_per_share += scaled / pool.total_staked;
+            pool.acc_carry = scaled % pool.total_staked;
+        }
+        pool.last_update = now;
+        pool
+    }
+
+    /// Bring the stored accumulator up to date and return it.
+    fn checkpoint(env: &Env) -> Result<Pool, RewardsError> {
+        let pool = Self::accrued(env, Self::pool(env.clone())?);
+        env.storage().instance().set(&DataKey::Pool, &pool);
+        Ok(pool)
+    }
+
+    /// Move what the position earned up to `pool` into `unclaimed`.
+    fn settle(env: &Env, pool: &Pool, staker: &Address) -> Position {
+        let mut position = Self::position_of(env, staker);
+        position.unclaimed = Self::earned(pool, &position);
+        position.snapshot = pool.acc_per_share;
+        position
+    }
+
+    fn earned(pool: &Pool, position: &Position) -> i128 {
+        position.unclaimed + position.amount * (pool.acc_per_share - position.snapshot) / ACC_SCALE
+    }
+
+    fn position_of(env: &Env, staker: &Address) -> Position {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Position(staker.clone()))
+            .unwrap_or(Position {
+                amount: 0,
+                snapshot: 0,
+                unclaimed: 0,
+            })
+    }
+
+    fn save(env: &Env, pool: &Pool, staker: &Address, position: &Position) {
+        env.storage().instance().set(&DataKey::Pool, pool);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Position(staker.clone()), position);
+    }
+
+    fn require_admin(env: &Env) -> Result<(), RewardsError> {
+        Self::config(env.clone())?.admin.require_auth();
+        Ok(())
+    }
+}
+
+mod test;
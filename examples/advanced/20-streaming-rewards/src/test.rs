@@ -0,0 +1,316 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+};
+
+const RATE: i128 = 10;
+
+struct Setup<'a> {
+    env: Env,
+    pool: StreamingRewardsClient<'a>,
+    stake_token: TokenClient<'a>,
+    reward_token: TokenClient<'a>,
+}
+
+fn setup_with_rate(rate: i128) -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let stake_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let reward_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let pool = StreamingRewardsClient::new(&env, &env.register_contract(None, StreamingRewards));
+    pool.initialize(
+        &Config {
+            admin: Address::generate(&env),
+            stake_token: stake_id.clone(),
+            reward_token: reward_id.clone(),
+        },
+        &rate,
+    );
+    StellarAssetClient::new(&env, &reward_id).mint(&pool.address, &1_000_000_000);
+
+    Setup {
+        stake_token: TokenClient::new(&env, &stake_id),
+        reward_token: TokenClient::new(&env, &reward_id),
+        env,
+        pool,
+    }
+}
+
+fn setup() -> Setup<'static> {
+    setup_with_rate(RATE)
+}
+
+fn staker(s: &Setup, amount: i128) -> Address {
+    let staker = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.stake_token.address).mint(&staker, &amount);
+    s.pool.stake(&staker, &amount);
+    staker
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_single_staker_earns_full_stream() {
+    let s = setup();
+    let alice = staker(&s, 1_000);
+    advance(&s.env, 100);
+
+    assert_eq!(s.pool.pending(&alice), 100 * RATE);
+    assert_eq!(s.pool.claim(&alice), 100 * RATE);
+    assert_eq!(s.reward_token.balance(&alice), 100 * RATE);
+    assert_eq!(s.pool.pending(&alice), 0);
+}
+
+#[test]
+fn test_stream_split_by_stake() {
+    let s = setup();
+    let alice = staker(&s, 300);
+    advance(&s.env, 100);
+    // Bob joins halfway: the first 100 seconds are all Alice's.
+    let bob = staker(&s, 100);
+    advance(&s.env, 100);
+
+    // 1_000 + 750, less the sub-unit rounding of 1/300 per unit.
+    assert_eq!(s.pool.pending(&alice), 1_749);
+    assert_eq!(s.pool.pending(&bob), 250);
+}
+
+#[test]
+fn test_paused_window_accrues_nothing() {
+    let s = setup();
+    let alice = staker(&s, 1_000);
+    advance(&s.env, 100);
+    s.pool.pause();
+    assert!(s.pool.pool().paused);
+
+    advance(&s.env, 500);
+    assert_eq!(s.pool.pending(&alice), 100 * RATE);
+
+    s.pool.resume();
+    advance(&s.env, 50);
+    assert_eq!(s.pool.pending(&alice), 150 * RATE);
+}
+
+#[test]
+fn test_multiple_pause_windows() {
+    let s = setup();
+    let alice = staker(&s, 1_000);
+
+    // 3 running stretches of 40s separated by 2 pauses of 1000s.
+    for _ in 0..2 {
+        advance(&s.env, 40);
+        s.pool.pause();
+        advance(&s.env, 1_000);
+        s.pool.resume();
+    }
+    advance(&s.env, 40);
+    assert_eq!(s.pool.claim(&alice), 120 * RATE);
+}
+
+#[test]
+fn test_stake_and_unstake_during_pause() {
+    let s = setup();
+    let alice = staker(&s, 100);
+    advance(&s.env, 10);
+    s.pool.pause();
+
+    // Bob joins and Alice leaves while nothing accrues.
+    advance(&s.env, 10);
+    let bob = staker(&s, 100);
+    s.pool.unstake(&alice, &100);
+    assert_eq!(s.stake_token.balance(&alice), 100);
+    advance(&s.env, 10);
+    s.pool.resume();
+    advance(&s.env, 10);
+
+    // Alice keeps the 10 seconds before the pause, Bob gets everything after.
+    assert_eq!(s.pool.claim(&alice), 10 * RATE);
+    assert_eq!(s.pool.claim(&bob), 10 * RATE);
+}
+
+#[test]
+fn test_claim_during_pause_pays_earlier_rewards() {
+    let s = setup();
+    let alice = staker(&s, 500);
+    advance(&s.env, 30);
+    s.pool.pause();
+    advance(&s.env, 30);
+
+    assert_eq!(s.pool.claim(&alice), 30 * RATE);
+    assert_eq!(s.pool.claim(&alice), 0);
+}
+
+/// A run with a pause window earns exactly what a run without it earns,
+/// even with a rate that does not divide evenly between the stakers.
+#[test]
+fn test_pause_keeps_precision() {
+    let run = |pause: bool| {
+        let s = setup_with_rate(7);
+        let alice = staker(&s, 3);
+        let bob = staker(&s, 11);
+        for step in 0..10 {
+            advance(&s.env, 13);
+            if pause && step % 3 == 0 {
+                s.pool.pause();
+                advance(&s.env, 101);
+                s.pool.resume();
+            }
+        }
+        (s.pool.claim(&alice), s.pool.claim(&bob))
+    };
+
+    let (alice, bob) = run(false);
+    assert_eq!(run(true), (alice, bob));
+    // 130 seconds at 7/s; each claim rounds down by less than one unit.
+    assert!(7 * 130 - (alice + bob) < 2, "{alice} + {bob}");
+}
+
+#[test]
+fn test_carry_prevents_rounding_loss() {
+    let s = setup_with_rate(1);
+    let alice = staker(&s, 3);
+
+    // Each second is worth 1/3 per unit; checkpointing every second must
+    // not lose the remainder.
+    for _ in 0..30 {
+        advance(&s.env, 1);
+        s.pool.pause();
+        s.pool.resume();
+    }
+    assert_eq!(s.pool.claim(&alice), 30);
+}
+
+#[test]
+fn test_set_rate_checkpoints_old_rate() {
+    let s = setup();
+    let alice = staker(&s, 1_000);
+    advance(&s.env, 100);
+    s.pool.set_rate(&2);
+    advance(&s.env, 100);
+
+    assert_eq!(s.pool.pending(&alice), 100 * RATE + 100 * 2);
+    assert_eq!(s.pool.try_set_rate(&-1), Err(Ok(RewardsError::InvalidRate)));
+}
+
+#[test]
+fn test_pause_state_errors() {
+    let s = setup();
+    assert_eq!(s.pool.try_resume(), Err(Ok(RewardsError::NotPaused)));
+    s.pool.pause();
+    assert_eq!(s.pool.try_pause(), Err(Ok(RewardsError::AlreadyPaused)));
+}
+
+#[test]
+fn test_unstake_limits() {
+    let s = setup();
+    let alice = staker(&s, 100);
+    assert_eq!(
+        s.pool.try_unstake(&alice, &101),
+        Err(Ok(RewardsError::InsufficientStake))
+    );
+    assert_eq!(
+        s.pool.try_stake(&alice, &0),
+        Err(Ok(RewardsError::InvalidAmount))
+    );
+    s.pool.unstake(&alice, &100);
+    assert_eq!(s.pool.pool().total_staked, 0);
+}
+
+fn enable_emergency(s: &Setup) -> Address {
+    let guardian = Address::generate(&s.env);
+    s.pool.set_guardian(&guardian);
+    s.pool.set_emergency(&true);
+    guardian
+}
+
+#[test]
+fn test_emergency_withdraw_when_reward_math_breaks() {
+    let s = setup();
+    let alice = staker(&s, 1_000);
+    // A rate this large overflows the accumulator on the next checkpoint.
+    s.pool.set_rate(&(i128::MAX / ACC_SCALE));
+    advance(&s.env, 100);
+    assert!(s.pool.try_unstake(&alice, &1_000).is_err());
+    assert!(s.pool.try_claim(&alice).is_err());
+
+    enable_emergency(&s);
+    assert_eq!(s.pool.emergency_withdraw(&alice), 1_000);
+    assert_eq!(s.stake_token.balance(&alice), 1_000);
+    assert_eq!(s.pool.pool().total_staked, 0);
+}
+
+#[test]
+fn test_emergency_withdraw_forfeits_rewards() {
+    let s = setup();
+    let alice = staker(&s, 1_000);
+    let bob = staker(&s, 1_000);
+    advance(&s.env, 100);
+    assert_eq!(s.pool.claim(&bob), 50 * RATE);
+
+    // Enabling emergency mode stops accrual where it stands.
+    advance(&s.env, 100);
+    enable_emergency(&s);
+    assert!(s.pool.pool().paused);
+    advance(&s.env, 100);
+
+    assert_eq!(s.pool.emergency_withdraw(&alice), 1_000);
+    assert_eq!(s.reward_token.balance(&alice), 0);
+    assert_eq!(s.pool.position(&alice).unclaimed, 0);
+    assert_eq!(s.pool.pending(&alice), 0);
+    assert_eq!(
+        s.pool.try_emergency_withdraw(&alice),
+        Err(Ok(RewardsError::InsufficientStake))
+    );
+    // The 100 seconds before emergency mode were never checkpointed, so
+    // Bob forfeits them too, but keeps his stake in the pool.
+    assert_eq!(s.pool.pending(&bob), 0);
+    assert_eq!(s.pool.pool().total_staked, 1_000);
+}
+
+#[test]
+fn test_emergency_mode_is_guardian_only() {
+    let s = setup();
+    let alice = staker(&s, 100);
+    assert_eq!(
+        s.pool.try_set_emergency(&true),
+        Err(Ok(RewardsError::GuardianNotSet))
+    );
+    assert_eq!(
+        s.pool.try_emergency_withdraw(&alice),
+        Err(Ok(RewardsError::EmergencyNotActive))
+    );
+
+    let guardian = enable_emergency(&s);
+    assert_eq!(s.env.auths()[0].0, guardian);
+    assert!(s.pool.emergency());
+    assert_eq!(
+        s.pool.try_stake(&alice, &10),
+        Err(Ok(RewardsError::EmergencyActive))
+    );
+    assert_eq!(s.pool.try_resume(), Err(Ok(RewardsError::EmergencyActive)));
+
+    // Turning it off leaves the pool paused until the admin resumes.
+    s.pool.set_emergency(&false);
+    assert!(s.pool.pool().paused);
+    s.pool.resume();
+    s.pool.unstake(&alice, &100);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_emergency_requires_guardian_auth() {
+    let s = setup();
+    s.pool.set_guardian(&Address::generate(&s.env));
+    s.env.set_auths(&[]);
+    s.pool.set_emergency(&true);
+}
@@ -0,0 +1,282 @@
+//! # NFT-Collateralized Loan
+//!
+//! Lock an NFT from an accepted collection, borrow a small fixed amount with
+//! no interest, and get the NFT back by repaying before a hard deadline:
+//!
+//! ```text
+//! borrow(borrower, collection, id)   NFT  borrower → this contract
+//!                                    loan this contract → borrower
+//! repay(loan_id)         (≤ due_at)  loan borrower → this contract
+//!                                    NFT  this contract → borrower
+//! liquidate(loan_id)     (> due_at)  NFT  this contract → treasury
+//! ```
+//!
+//! There is no price oracle. Instead of valuing each NFT, the admin accepts
+//! whole collections whose floor value comfortably exceeds the fixed
+//! `loan_amount` (reputation badges, membership passes). Every loan is the
+//! same size, so the only question at borrow time is "is this token from an
+//! accepted collection and does the borrower own it".
+//!
+//! A missed deadline is final: `repay` refuses overdue loans, and anyone can
+//! call `liquidate` to move the NFT to the treasury. The borrower keeps the
+//! borrowed tokens, so a default costs the pool `loan_amount` and gains it
+//! the NFT.
+//!
+//! Each borrower can have one open loan at a time, which keeps the pool's
+//! exposure to a single borrower at `loan_amount`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Env,
+};
+
+/// The part of an NFT collection's interface the loan contract uses.
+#[contractclient(name = "NftClient")]
+pub trait NftInterface {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn transfer(env: Env, from: Address, to: Address, token_id: u32);
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanConfig {
+    pub admin: Address,
+    /// Token lent out; this contract's balance is the pool's liquidity.
+    pub token: Address,
+    /// Receives the NFTs of defaulted loans.
+    pub treasury: Address,
+    /// Fixed amount every loan pays out and must repay.
+    pub loan_amount: i128,
+    /// Seconds from borrowing to the repayment deadline.
+    pub duration: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Loan {
+    pub borrower: Address,
+    pub collection: Address,
+    pub token_id: u32,
+    pub amount: i128,
+    /// Last second at which `repay` is accepted.
+    pub due_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    NextLoanId,
+    /// Collections accepted as collateral.
+    Collection(Address),
+    Loan(u64),
+    /// Open loan id of a borrower.
+    OpenLoan(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LoanError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    CollectionNotAccepted = 4,
+    /// The borrower does not own the NFT they offered.
+    NotOwner = 5,
+    /// The borrower already has an open loan.
+    HasOpenLoan = 6,
+    InsufficientLiquidity = 7,
+    LoanNotFound = 8,
+    /// `repay` after `due_at`.
+    LoanOverdue = 9,
+    /// `liquidate` at or before `due_at`.
+    LoanNotDue = 10,
+}
+
+#[contract]
+pub struct NftLoan;
+
+#[contractimpl]
+impl NftLoan {
+    pub fn initialize(env: Env, config: LoanConfig) -> Result<(), LoanError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(LoanError::AlreadyInitialized);
+        }
+        if config.loan_amount <= 0 || config.duration == 0 {
+            return Err(LoanError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Accept or stop accepting a collection. Open loans are unaffected.
+    pub fn set_collection(env: Env, collection: Address, accepted: bool) -> Result<(), LoanError> {
+        Self::config(env.clone())?.admin.require_auth();
+        let key = DataKey::Collection(collection);
+        if accepted {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    // ==================== BORROWER ====================
+
+    /// Lock `token_id` and receive `loan_amount`. Returns the loan id.
+    ///
+    /// Emits `(loan, opened)` with `(loan_id, borrower, due_at)`.
+    pub fn borrow(
+        env: Env,
+        borrower: Address,
+        collection: Address,
+        token_id: u32,
+    ) -> Result<u64, LoanError> {
+        borrower.require_auth();
+        let config = Self::config(env.clone())?;
+        if !Self::is_accepted(env.clone(), collection.clone()) {
+            return Err(LoanError::CollectionNotAccepted);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::OpenLoan(borrower.clone()))
+        {
+            return Err(LoanError::HasOpenLoan);
+        }
+        let nft = NftClient::new(&env, &collection);
+        if nft.owner_of(&token_id) != borrower {
+            return Err(LoanError::NotOwner);
+        }
+        let token = token::Client::new(&env, &config.token);
+        if token.balance(&env.current_contract_address()) < config.loan_amount {
+            return Err(LoanError::InsufficientLiquidity);
+        }
+
+        // The borrower's authorization of `borrow` covers this transfer.
+        nft.transfer(&borrower, &env.current_contract_address(), &token_id);
+        token.transfer(
+            &env.current_contract_address(),
+            &borrower,
+            &config.loan_amount,
+        );
+
+        let loan_id = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextLoanId)
+            .unwrap_or(0u64);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextLoanId, &(loan_id + 1));
+        let due_at = env.ledger().timestamp() + config.duration;
+        let loan = Loan {
+            borrower: borrower.clone(),
+            collection,
+            token_id,
+            amount: config.loan_amount,
+            due_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Loan(loan_id), &loan);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpenLoan(borrower.clone()), &loan_id);
+        env.events().publish(
+            (symbol_short!("loan"), symbol_short!("opened")),
+            (loan_id, borrower, due_at),
+        );
+        Ok(loan_id)
+    }
+
+    /// Pay the loan back and unlock the NFT. Only until `due_at`.
+    ///
+    /// Emits `(loan, repaid)` with `loan_id`.
+    pub fn repay(env: Env, loan_id: u64) -> Result<(), LoanError> {
+        let config = Self::config(env.clone())?;
+        let loan = Self::loan(env.clone(), loan_id).ok_or(LoanError::LoanNotFound)?;
+        loan.borrower.require_auth();
+        if env.ledger().timestamp() > loan.due_at {
+            return Err(LoanError::LoanOverdue);
+        }
+
+        Self::close(&env, loan_id, &loan);
+        token::Client::new(&env, &config.token).transfer(
+            &loan.borrower,
+            &env.current_contract_address(),
+            &loan.amount,
+        );
+        NftClient::new(&env, &loan.collection).transfer(
+            &env.current_contract_address(),
+            &loan.borrower,
+            &loan.token_id,
+        );
+        env.events()
+            .publish((symbol_short!("loan"), symbol_short!("repaid")), loan_id);
+        Ok(())
+    }
+
+    // ==================== DEFAULT ====================
+
+    /// Forfeit the NFT of an overdue loan to the treasury. Anyone can call.
+    ///
+    /// Emits `(loan, default)` with `(loan_id, borrower)`.
+    pub fn liquidate(env: Env, loan_id: u64) -> Result<(), LoanError> {
+        let config = Self::config(env.clone())?;
+        let loan = Self::loan(env.clone(), loan_id).ok_or(LoanError::LoanNotFound)?;
+        if env.ledger().timestamp() <= loan.due_at {
+            return Err(LoanError::LoanNotDue);
+        }
+
+        Self::close(&env, loan_id, &loan);
+        NftClient::new(&env, &loan.collection).transfer(
+            &env.current_contract_address(),
+            &config.treasury,
+            &loan.token_id,
+        );
+        env.events().publish(
+            (symbol_short!("loan"), symbol_short!("default")),
+            (loan_id, loan.borrower),
+        );
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn config(env: Env) -> Result<LoanConfig, LoanError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(LoanError::NotInitialized)
+    }
+
+    pub fn is_accepted(env: Env, collection: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Collection(collection))
+    }
+
+    pub fn loan(env: Env, loan_id: u64) -> Option<Loan> {
+        env.storage().persistent().get(&DataKey::Loan(loan_id))
+    }
+
+    pub fn open_loan(env: Env, borrower: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::OpenLoan(borrower))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn close(env: &Env, loan_id: u64, loan: &Loan) {
+        env.storage().persistent().remove(&DataKey::Loan(loan_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::OpenLoan(loan.borrower.clone()));
+    }
+}
+
+mod nft;
+mod test;
@@ -0,0 +1,37 @@
+//! Minimal NFT collection used as collateral in the tests.
+//!
+//! Only compiled for tests: in a real deployment it would be its own crate
+//! and Wasm. It implements just the two calls the loan contract makes.
+
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum NftKey {
+    Owner(u32),
+}
+
+#[contract]
+pub struct TestNft;
+
+#[contractimpl]
+impl TestNft {
+    pub fn mint(env: Env, to: Address, token_id: u32) {
+        env.storage().instance().set(&NftKey::Owner(token_id), &to);
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        env.storage()
+            .instance()
+            .get(&NftKey::Owner(token_id))
+            .unwrap()
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
+        from.require_auth();
+        assert!(Self::owner_of(env.clone(), token_id) == from, "not owner");
+        env.storage().instance().set(&NftKey::Owner(token_id), &to);
+    }
+}
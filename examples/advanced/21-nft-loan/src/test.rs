@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use super::*;
+use crate::nft::{TestNft, TestNftClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+};
+
+const TOKEN_ID: u32 = 7;
+const LOAN: i128 = 500;
+const DURATION: u64 = 1_000;
+
+struct Setup<'a> {
+    env: Env,
+    loans: NftLoanClient<'a>,
+    nft: TestNftClient<'a>,
+    token: TokenClient<'a>,
+    treasury: Address,
+    borrower: Address,
+}
+
+/// An accepted collection, 10 loans of liquidity, and a borrower who owns
+/// `TOKEN_ID` and holds exactly one loan's worth of tokens for repayment.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let nft = TestNftClient::new(&env, &env.register_contract(None, TestNft));
+    let loans = NftLoanClient::new(&env, &env.register_contract(None, NftLoan));
+    let treasury = Address::generate(&env);
+    loans.initialize(&LoanConfig {
+        admin: Address::generate(&env),
+        token: token_id.clone(),
+        treasury: treasury.clone(),
+        loan_amount: LOAN,
+        duration: DURATION,
+    });
+    loans.set_collection(&nft.address, &true);
+
+    let asset = StellarAssetClient::new(&env, &token_id);
+    asset.mint(&loans.address, &(10 * LOAN));
+    let borrower = Address::generate(&env);
+    nft.mint(&borrower, &TOKEN_ID);
+
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        loans,
+        nft,
+        treasury,
+        borrower,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_borrow_locks_nft_and_pays_out() {
+    let s = setup();
+    let loan_id = s.loans.borrow(&s.borrower, &s.nft.address, &TOKEN_ID);
+
+    assert_eq!(s.nft.owner_of(&TOKEN_ID), s.loans.address);
+    assert_eq!(s.token.balance(&s.borrower), LOAN);
+    assert_eq!(s.loans.open_loan(&s.borrower), Some(loan_id));
+    let loan = s.loans.loan(&loan_id).unwrap();
+    assert_eq!(loan.amount, LOAN);
+    assert_eq!(loan.due_at, DURATION);
+}
+
+#[test]
+fn test_repay_unlocks_nft() {
+    let s = setup();
+    let loan_id = s.loans.borrow(&s.borrower, &s.nft.address, &TOKEN_ID);
+    // Repaying on the deadline itself is still in time.
+    advance(&s.env, DURATION);
+    s.loans.repay(&loan_id);
+
+    assert_eq!(s.nft.owner_of(&TOKEN_ID), s.borrower);
+    assert_eq!(s.token.balance(&s.borrower), 0);
+    assert_eq!(s.token.balance(&s.loans.address), 10 * LOAN);
+    assert_eq!(s.loans.loan(&loan_id), None);
+    assert_eq!(s.loans.open_loan(&s.borrower), None);
+}
+
+#[test]
+fn test_deadline_is_hard() {
+    let s = setup();
+    let loan_id = s.loans.borrow(&s.borrower, &s.nft.address, &TOKEN_ID);
+    assert_eq!(
+        s.loans.try_liquidate(&loan_id),
+        Err(Ok(LoanError::LoanNotDue))
+    );
+
+    advance(&s.env, DURATION + 1);
+    assert_eq!(s.loans.try_repay(&loan_id), Err(Ok(LoanError::LoanOverdue)));
+}
+
+#[test]
+fn test_default_moves_nft_to_treasury() {
+    let s = setup();
+    let loan_id = s.loans.borrow(&s.borrower, &s.nft.address, &TOKEN_ID);
+    advance(&s.env, DURATION + 1);
+    s.loans.liquidate(&loan_id);
+
+    assert_eq!(s.nft.owner_of(&TOKEN_ID), s.treasury);
+    // The borrower keeps the loan; the pool is down one loan.
+    assert_eq!(s.token.balance(&s.borrower), LOAN);
+    assert_eq!(s.token.balance(&s.loans.address), 9 * LOAN);
+    assert_eq!(
+        s.loans.try_liquidate(&loan_id),
+        Err(Ok(LoanError::LoanNotFound))
+    );
+    // The borrower is free to borrow again with another NFT.
+    assert_eq!(s.loans.open_loan(&s.borrower), None);
+}
+
+#[test]
+fn test_only_accepted_collections() {
+    let s = setup();
+    let other = TestNftClient::new(&s.env, &s.env.register_contract(None, TestNft));
+    other.mint(&s.borrower, &1);
+    assert_eq!(
+        s.loans.try_borrow(&s.borrower, &other.address, &1),
+        Err(Ok(LoanError::CollectionNotAccepted))
+    );
+
+    s.loans.set_collection(&s.nft.address, &false);
+    assert_eq!(
+        s.loans.try_borrow(&s.borrower, &s.nft.address, &TOKEN_ID),
+        Err(Ok(LoanError::CollectionNotAccepted))
+    );
+}
+
+#[test]
+fn test_borrower_must_own_nft() {
+    let s = setup();
+    let mallory = Address::generate(&s.env);
+    assert_eq!(
+        s.loans.try_borrow(&mallory, &s.nft.address, &TOKEN_ID),
+        Err(Ok(LoanError::NotOwner))
+    );
+}
+
+#[test]
+fn test_one_open_loan_per_borrower() {
+    let s = setup();
+    s.nft.mint(&s.borrower, &8);
+    s.loans.borrow(&s.borrower, &s.nft.address, &TOKEN_ID);
+    assert_eq!(
+        s.loans.try_borrow(&s.borrower, &s.nft.address, &8),
+        Err(Ok(LoanError::HasOpenLoan))
+    );
+}
+
+#[test]
+fn test_insufficient_liquidity() {
+    let s = setup();
+    // Drain the pool down to less than one loan.
+    for i in 0..10 {
+        let borrower = Address::generate(&s.env);
+        s.nft.mint(&borrower, &(100 + i));
+        s.loans.borrow(&borrower, &s.nft.address, &(100 + i));
+    }
+    assert_eq!(
+        s.loans.try_borrow(&s.borrower, &s.nft.address, &TOKEN_ID),
+        Err(Ok(LoanError::InsufficientLiquidity))
+    );
+    // The failed attempt left the NFT with its owner.
+    assert_eq!(s.nft.owner_of(&TOKEN_ID), s.borrower);
+}
+
+#[test]
+fn test_invalid_config() {
+    let env = Env::default();
+    let loans = NftLoanClient::new(&env, &env.register_contract(None, NftLoan));
+    let config = LoanConfig {
+        admin: Address::generate(&env),
+        token: Address::generate(&env),
+        treasury: Address::generate(&env),
+        loan_amount: 0,
+        duration: DURATION,
+    };
+    assert_eq!(
+        loans.try_initialize(&config),
+        Err(Ok(LoanError::InvalidConfig))
+    );
+}
@@ -0,0 +1,63 @@
+//! USD ↔ token conversions at an oracle price.
+//!
+//! Every amount here is an integer paired with a decimals count: `1_2345678`
+//! with 7 decimals is 1.2345678. Mixing two amounts is only meaningful once
+//! they share a scale, and forgetting to rescale is the classic bug — a
+//! 6-decimal stablecoin priced with 18-decimal math is off by 10^12.
+//!
+//! The arithmetic is delegated to `cookbook-decimals`, which keeps the
+//! intermediate product in a `U256`; only the result has to fit in an
+//! `i128`. Both functions take an explicit [`Rounding`] so each caller
+//! decides who the remainder favours.
+
+use cookbook_prelude::prelude::*;
+use soroban_sdk::Env;
+
+pub use cookbook_prelude::math::Rounding;
+
+/// Token units (`token_decimals`) worth `usd` (`usd_decimals`) at `price`,
+/// the USD price of one whole token with `price_decimals`.
+///
+/// `token = usd * 10^(price_decimals + token_decimals) / (price * 10^usd_decimals)`,
+/// with the powers of ten netted out first.
+pub fn usd_to_token(
+    env: &Env,
+    usd: i128,
+    usd_decimals: u32,
+    price: i128,
+    price_decimals: u32,
+    token_decimals: u32,
+    rounding: Rounding,
+) -> Result<i128, DecimalsError> {
+    let up = price_decimals + token_decimals;
+    if up >= usd_decimals {
+        mul_div(env, usd, pow10(up - usd_decimals)?, price, rounding)
+    } else {
+        let denominator = price
+            .checked_mul(pow10(usd_decimals - up)?)
+            .ok_or(DecimalsError::Overflow)?;
+        mul_div(env, usd, 1, denominator, rounding)
+    }
+}
+
+/// USD value (`usd_decimals`) of `amount` token units, the inverse of
+/// [`usd_to_token`].
+pub fn token_to_usd(
+    env: &Env,
+    amount: i128,
+    token_decimals: u32,
+    price: i128,
+    price_decimals: u32,
+    usd_decimals: u32,
+    rounding: Rounding,
+) -> Result<i128, DecimalsError> {
+    let down = price_decimals + token_decimals;
+    if usd_decimals >= down {
+        let factor = price
+            .checked_mul(pow10(usd_decimals - down)?)
+            .ok_or(DecimalsError::Overflow)?;
+        mul_div(env, amount, factor, 1, rounding)
+    } else {
+        mul_div(env, amount, price, pow10(down - usd_decimals)?, rounding)
+    }
+}
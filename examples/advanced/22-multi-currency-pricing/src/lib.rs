@@ -0,0 +1,533 @@
+//! # Multi-Currency Pricing
+//!
+//! Items are priced once in USD and can be paid for in any whitelisted
+//! token. At payment time the contract asks a price oracle for the token's
+//! USD price and converts:
+//!
+//! ```text
+//! item price (USD, 7 dp) ──÷ oracle price (USD per token, oracle dp)──▶ token units (token dp)
+//! ```
+//!
+//! Three different scales meet in that one line, and getting any of them
+//! wrong is the most common pricing bug: a 6-decimal stablecoin and an
+//! 18-decimal token differ by 10^12 for the same face value. The contract
+//! never mixes raw amounts; every conversion goes through the helpers in
+//! [`convert`] with all three decimals counts spelled out, on top of the
+//! shared `cookbook-decimals` crate.
+//!
+//! - **USD prices** use `USD_DECIMALS` (7, like Stellar assets).
+//! - **Oracle prices** use whatever the oracle reports from `decimals()`.
+//! - **Token amounts** use the token's own `decimals()`, read once when the
+//!   admin whitelists it.
+//!
+//! Rounding always favours the seller: `quote` rounds the token amount up,
+//! so a payment is never worth less than the USD price.
+//!
+//! ## Trusting the oracle
+//!
+//! Prices older than `max_price_age` seconds are refused, as are
+//! non-positive prices. Because the price can move between a quote and the
+//! payment, `pay` takes a `max_amount` the buyer is willing to spend.
+//!
+//! ## Failover
+//!
+//! An oracle that goes silent would otherwise stop every payment. Each
+//! price lookup walks down a list of sources and uses the first one that
+//! works:
+//!
+//! ```text
+//! Primary    config.oracle, fresh and positive
+//! Secondary  the fallback oracle, if the admin set one, same checks
+//! SafeMode   a fixed USD price the admin set for the token, if any
+//! ```
+//!
+//! If none works, the primary's error is returned. Per token, the contract
+//! records the source in use and the timestamp of the latest price seen
+//! from each oracle (`feed_status`). When the source changes it emits
+//! `(degraded, token)` or `(recovered, token)` with the new source, so
+//! monitoring sees a feed go silent before users do.
+//!
+//! ## Deviation circuit breaker
+//!
+//! A manipulated oracle usually shows up as a spike. When the admin
+//! configures a `Breaker`, every price the contract uses is compared with
+//! the last one it accepted for that token. If that reference is less than
+//! `window` seconds old and the new price is more than `max_deviation_bps`
+//! away from it, the action fails with `PriceDeviation`. A genuine move is
+//! accepted once the reference is older than `window`, or right away if
+//! governance (the admin) calls `override_breaker`.
+
+#![no_std]
+
+pub mod convert;
+
+use convert::Rounding;
+use cookbook_prelude::prelude::*;
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Env, Symbol,
+};
+
+/// Decimals of every USD amount this contract stores or returns.
+pub const USD_DECIMALS: u32 = 7;
+
+/// Decimals reference prices are stored with, whatever their source.
+pub const REFERENCE_DECIMALS: u32 = 18;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// USD per whole token, scaled by the oracle's `decimals()`.
+    pub price: i128,
+    /// Ledger timestamp the price was recorded at.
+    pub timestamp: u64,
+}
+
+/// The part of a SEP-40 style price oracle this contract uses.
+#[contractclient(name = "OracleClient")]
+pub trait OracleInterface {
+    fn decimals(env: Env) -> u32;
+    fn lastprice(env: Env, asset: Address) -> Option<PriceData>;
+}
+
+/// Where a token's price came from, best first.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PriceSource {
+    Primary = 0,
+    Secondary = 1,
+    /// The admin's fixed price: both oracles are stale or failing.
+    SafeMode = 2,
+}
+
+/// Heartbeat of a token's price feeds, as of the last lookup.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeedStatus {
+    pub source: PriceSource,
+    /// Timestamp of the latest price seen from the primary oracle.
+    pub primary_updated: Option<u64>,
+    /// Timestamp of the latest price seen from the fallback oracle.
+    pub secondary_updated: Option<u64>,
+}
+
+/// Deviation circuit breaker settings.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breaker {
+    /// Largest accepted move from the reference price, in basis points.
+    pub max_deviation_bps: u32,
+    /// Seconds a reference price stays binding.
+    pub window: u64,
+}
+
+/// A price accepted by the breaker, with `REFERENCE_DECIMALS`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reference {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricingConfig {
+    pub admin: Address,
+    pub oracle: Address,
+    /// Receives every payment.
+    pub treasury: Address,
+    /// Oldest oracle price, in seconds, a payment may use.
+    pub max_price_age: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    /// Whitelisted token → its decimals.
+    Token(Address),
+    /// Item → USD price with `USD_DECIMALS`.
+    Price(Symbol),
+    FallbackOracle,
+    /// Token → safe-mode USD price with `USD_DECIMALS`.
+    SafePrice(Address),
+    /// Token → its `FeedStatus`.
+    Feed(Address),
+    Breaker,
+    /// Token → the last price the breaker accepted.
+    Reference(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PricingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    TokenNotAccepted = 4,
+    ItemNotFound = 5,
+    /// A USD or oracle price that is zero or negative.
+    InvalidPrice = 6,
+    /// The oracle has no price for the token.
+    PriceUnavailable = 7,
+    /// The oracle's price is older than `max_price_age`.
+    StalePrice = 8,
+    /// The quote exceeds the buyer's `max_amount`.
+    SlippageExceeded = 9,
+    /// The conversion does not fit in an `i128`.
+    Overflow = 10,
+    InvalidAmount = 11,
+    /// A token or the oracle uses more decimals than the conversion supports.
+    UnsupportedDecimals = 12,
+    /// The price moved more than the breaker allows within its window.
+    PriceDeviation = 13,
+}
+
+impl From<DecimalsError> for PricingError {
+    fn from(err: DecimalsError) -> Self {
+        match err {
+            DecimalsError::Overflow => PricingError::Overflow,
+            DecimalsError::NegativeAmount => PricingError::InvalidAmount,
+            DecimalsError::InvalidDenominator => PricingError::InvalidPrice,
+            DecimalsError::UnsupportedDecimals => PricingError::UnsupportedDecimals,
+        }
+    }
+}
+
+#[contract]
+pub struct MultiCurrencyPricing;
+
+#[contractimpl]
+impl MultiCurrencyPricing {
+    pub fn initialize(env: Env, config: PricingConfig) -> Result<(), PricingError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(PricingError::AlreadyInitialized);
+        }
+        if config.max_price_age == 0 {
+            return Err(PricingError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    // ==================== ADMIN ====================
+
+    /// Whitelist `token` for payments and return its decimals.
+    ///
+    /// Decimals are read from the token contract here, once, rather than
+    /// passed in by the admin, so they cannot be mistyped.
+    pub fn accept_token(env: Env, token: Address) -> Result<u32, PricingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        let decimals = token::Client::new(&env, &token).decimals();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Token(token), &decimals);
+        Ok(decimals)
+    }
+
+    pub fn remove_token(env: Env, token: Address) -> Result<(), PricingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        env.storage().persistent().remove(&DataKey::Token(token));
+        Ok(())
+    }
+
+    /// Set the USD price of `item`, with `USD_DECIMALS`.
+    pub fn set_price(env: Env, item: Symbol, usd: i128) -> Result<(), PricingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        if usd <= 0 {
+            return Err(PricingError::InvalidPrice);
+        }
+        env.storage().persistent().set(&DataKey::Price(item), &usd);
+        Ok(())
+    }
+
+    /// Set or clear the deviation circuit breaker.
+    pub fn set_breaker(env: Env, breaker: Option<Breaker>) -> Result<(), PricingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        match breaker {
+            Some(breaker) if breaker.max_deviation_bps == 0 || breaker.window == 0 => {
+                return Err(PricingError::InvalidConfig)
+            }
+            Some(breaker) => env.storage().instance().set(&DataKey::Breaker, &breaker),
+            None => env.storage().instance().remove(&DataKey::Breaker),
+        }
+        Ok(())
+    }
+
+    /// Governance override: accept the current price of `token` as the
+    /// breaker's reference, however far it moved, and return it with
+    /// `REFERENCE_DECIMALS`.
+    ///
+    /// Emits `(override, token)` with the accepted price.
+    pub fn override_breaker(env: Env, token: Address) -> Result<i128, PricingError> {
+        let config = Self::config(env.clone())?;
+        config.admin.require_auth();
+        let (price, decimals) = Self::resolve_price(&env, &config, &token)?;
+        let price = scale_amount(&env, price, decimals, REFERENCE_DECIMALS, Rounding::Down)?;
+        Self::set_reference(&env, &token, price);
+        env.events()
+            .publish((symbol_short!("override"), token), price);
+        Ok(price)
+    }
+
+    /// Set or clear the oracle used when the primary one is stale.
+    pub fn set_fallback_oracle(env: Env, oracle: Option<Address>) -> Result<(), PricingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        match oracle {
+            Some(oracle) => env
+                .storage()
+                .instance()
+                .set(&DataKey::FallbackOracle, &oracle),
+            None => env.storage().instance().remove(&DataKey::FallbackOracle),
+        }
+        Ok(())
+    }
+
+    /// Set or clear the USD price of one whole `token`, with
+    /// `USD_DECIMALS`, used when every oracle is stale. Pick it
+    /// conservatively: it does not move with the market.
+    pub fn set_safe_price(env: Env, token: Address, usd: Option<i128>) -> Result<(), PricingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        let key = DataKey::SafePrice(token);
+        match usd {
+            Some(usd) if usd <= 0 => return Err(PricingError::InvalidPrice),
+            Some(usd) => env.storage().persistent().set(&key, &usd),
+            None => env.storage().persistent().remove(&key),
+        }
+        Ok(())
+    }
+
+    // ==================== BUYER ====================
+
+    /// Units of `token` that currently pay for `item`, rounded up.
+    pub fn quote(env: Env, item: Symbol, token: Address) -> Result<i128, PricingError> {
+        let config = Self::config(env.clone())?;
+        let usd = Self::price(env.clone(), item).ok_or(PricingError::ItemNotFound)?;
+        let token_decimals = Self::token_decimals(env.clone(), token.clone())
+            .ok_or(PricingError::TokenNotAccepted)?;
+        let (price, price_decimals) = Self::oracle_price(&env, &config, &token)?;
+        Ok(convert::usd_to_token(
+            &env,
+            usd,
+            USD_DECIMALS,
+            price,
+            price_decimals,
+            token_decimals,
+            Rounding::Up,
+        )?)
+    }
+
+    /// Pay for `item` in `token`, spending at most `max_amount`. Returns the
+    /// amount paid.
+    ///
+    /// Emits `(paid, item)` with `(buyer, token, amount)`.
+    pub fn pay(
+        env: Env,
+        buyer: Address,
+        item: Symbol,
+        token: Address,
+        max_amount: i128,
+    ) -> Result<i128, PricingError> {
+        buyer.require_auth();
+        let amount = Self::quote(env.clone(), item.clone(), token.clone())?;
+        if amount > max_amount {
+            return Err(PricingError::SlippageExceeded);
+        }
+        let treasury = Self::config(env.clone())?.treasury;
+        token::Client::new(&env, &token).transfer(&buyer, &treasury, &amount);
+        env.events()
+            .publish((symbol_short!("paid"), item), (buyer, token, amount));
+        Ok(amount)
+    }
+
+    // ==================== VIEWS ====================
+
+    /// USD value, with `USD_DECIMALS`, of `amount` units of `token`,
+    /// rounded down.
+    pub fn value_usd(env: Env, token: Address, amount: i128) -> Result<i128, PricingError> {
+        if amount < 0 {
+            return Err(PricingError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let token_decimals = Self::token_decimals(env.clone(), token.clone())
+            .ok_or(PricingError::TokenNotAccepted)?;
+        let (price, price_decimals) = Self::oracle_price(&env, &config, &token)?;
+        Ok(convert::token_to_usd(
+            &env,
+            amount,
+            token_decimals,
+            price,
+            price_decimals,
+            USD_DECIMALS,
+            Rounding::Down,
+        )?)
+    }
+
+    pub fn config(env: Env) -> Result<PricingConfig, PricingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(PricingError::NotInitialized)
+    }
+
+    /// Decimals of a whitelisted token, or `None` if it is not accepted.
+    pub fn token_decimals(env: Env, token: Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::Token(token))
+    }
+
+    pub fn price(env: Env, item: Symbol) -> Option<i128> {
+        env.storage().persistent().get(&DataKey::Price(item))
+    }
+
+    pub fn fallback_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::FallbackOracle)
+    }
+
+    pub fn safe_price(env: Env, token: Address) -> Option<i128> {
+        env.storage().persistent().get(&DataKey::SafePrice(token))
+    }
+
+    pub fn breaker(env: Env) -> Option<Breaker> {
+        env.storage().instance().get(&DataKey::Breaker)
+    }
+
+    /// The last price of `token` the breaker accepted.
+    pub fn reference(env: Env, token: Address) -> Option<Reference> {
+        env.storage().persistent().get(&DataKey::Reference(token))
+    }
+
+    /// Source and heartbeats recorded by the last price lookup for `token`.
+    pub fn feed_status(env: Env, token: Address) -> FeedStatus {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Feed(token))
+            .unwrap_or(FeedStatus {
+                source: PriceSource::Primary,
+                primary_updated: None,
+                secondary_updated: None,
+            })
+    }
+
+    // ==================== HELPERS ====================
+
+    /// A price for `token` and its decimals that passes the breaker, if
+    /// one is set. An accepted price becomes the new reference.
+    fn oracle_price(
+        env: &Env,
+        config: &PricingConfig,
+        token: &Address,
+    ) -> Result<(i128, u32), PricingError> {
+        let (price, decimals) = Self::resolve_price(env, config, token)?;
+        let Some(breaker) = Self::breaker(env.clone()) else {
+            return Ok((price, decimals));
+        };
+
+        let scaled = scale_amount(env, price, decimals, REFERENCE_DECIMALS, Rounding::Down)?;
+        if let Some(reference) = Self::reference(env.clone(), token.clone()) {
+            let age = env.ledger().timestamp().saturating_sub(reference.timestamp);
+            let deviation_bps = mul_div(
+                env,
+                (scaled - reference.price).abs(),
+                10_000,
+                reference.price,
+                Rounding::Up,
+            )?;
+            if age < breaker.window && deviation_bps > breaker.max_deviation_bps as i128 {
+                return Err(PricingError::PriceDeviation);
+            }
+        }
+        Self::set_reference(env, token, scaled);
+        Ok((price, decimals))
+    }
+
+    fn set_reference(env: &Env, token: &Address, price: i128) {
+        env.storage().persistent().set(
+            &DataKey::Reference(token.clone()),
+            &Reference {
+                price,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// A price for `token` and its decimals, from the best working source.
+    /// Records the feed status and emits an event when the source changes.
+    ///
+    /// Emits `(degraded, token)` or `(recovered, token)` with the new
+    /// `PriceSource`.
+    fn resolve_price(
+        env: &Env,
+        config: &PricingConfig,
+        token: &Address,
+    ) -> Result<(i128, u32), PricingError> {
+        let previous = Self::feed_status(env.clone(), token.clone());
+        let mut status = previous.clone();
+
+        let (primary, seen) = Self::read_feed(env, config, &config.oracle, token);
+        status.primary_updated = seen.or(status.primary_updated);
+        let mut resolved = primary.map(|price| (price, PriceSource::Primary));
+        if resolved.is_err() {
+            if let Some(fallback) = Self::fallback_oracle(env.clone()) {
+                let (secondary, seen) = Self::read_feed(env, config, &fallback, token);
+                status.secondary_updated = seen.or(status.secondary_updated);
+                if let Ok(price) = secondary {
+                    resolved = Ok((price, PriceSource::Secondary));
+                }
+            }
+        }
+        if resolved.is_err() {
+            if let Some(usd) = Self::safe_price(env.clone(), token.clone()) {
+                resolved = Ok(((usd, USD_DECIMALS), PriceSource::SafeMode));
+            }
+        }
+        let (price, source) = resolved?;
+
+        status.source = source;
+        if status != previous {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Feed(token.clone()), &status);
+        }
+        if source > previous.source {
+            env.events()
+                .publish((symbol_short!("degraded"), token.clone()), source);
+        } else if source < previous.source {
+            env.events()
+                .publish((symbol_short!("recovered"), token.clone()), source);
+        }
+        Ok(price)
+    }
+
+    /// A fresh, positive price for `token` from `oracle` and the oracle's
+    /// decimals, plus the timestamp of whatever price the oracle has. An
+    /// oracle whose calls fail counts as having no price.
+    fn read_feed(
+        env: &Env,
+        config: &PricingConfig,
+        oracle: &Address,
+        token: &Address,
+    ) -> (Result<(i128, u32), PricingError>, Option<u64>) {
+        let oracle = OracleClient::new(env, oracle);
+        let Ok(Ok(Some(data))) = oracle.try_lastprice(token) else {
+            return (Err(PricingError::PriceUnavailable), None);
+        };
+        let seen = Some(data.timestamp);
+        if data.price <= 0 {
+            return (Err(PricingError::InvalidPrice), seen);
+        }
+        let age = env.ledger().timestamp().saturating_sub(data.timestamp);
+        if age > config.max_price_age {
+            return (Err(PricingError::StalePrice), seen);
+        }
+        match oracle.try_decimals() {
+            Ok(Ok(decimals)) => (Ok((data.price, decimals)), seen),
+            _ => (Err(PricingError::PriceUnavailable), seen),
+        }
+    }
+}
+
+mod oracle;
+mod test;
+mod test_token;
@@ -0,0 +1,51 @@
+//! Minimal price oracle used in the tests.
+//!
+//! Only compiled for tests: in a real deployment the contract would point
+//! at a SEP-40 oracle or an aggregator over several of them. It implements
+//! just the two calls the pricing contract makes.
+
+#![cfg(test)]
+
+use crate::PriceData;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum OracleKey {
+    Decimals,
+    Price(Address),
+}
+
+#[contract]
+pub struct TestOracle;
+
+#[contractimpl]
+impl TestOracle {
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&OracleKey::Decimals, &decimals);
+    }
+
+    /// Record `price` for `asset` at the current ledger timestamp.
+    pub fn set_price(env: Env, asset: Address, price: i128) {
+        let data = PriceData {
+            price,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .instance()
+            .set(&OracleKey::Price(asset), &data);
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&OracleKey::Decimals)
+            .unwrap_or(14)
+    }
+
+    pub fn lastprice(env: Env, asset: Address) -> Option<PriceData> {
+        env.storage().instance().get(&OracleKey::Price(asset))
+    }
+}
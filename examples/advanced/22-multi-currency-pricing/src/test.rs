@@ -0,0 +1,523 @@
+#![cfg(test)]
+
+use super::*;
+use crate::convert::{token_to_usd, usd_to_token};
+use crate::oracle::{TestOracle, TestOracleClient};
+use crate::test_token::{TestToken, TestTokenClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    IntoVal,
+};
+
+const MAX_AGE: u64 = 300;
+/// The test oracle reports prices with 14 decimals.
+const ORACLE_ONE: i128 = 100_000_000_000_000;
+/// $25.00 with `USD_DECIMALS`.
+const TICKET_USD: i128 = 250_000_000;
+
+struct Setup<'a> {
+    env: Env,
+    shop: MultiCurrencyPricingClient<'a>,
+    oracle: TestOracleClient<'a>,
+    treasury: Address,
+    /// 6 decimals, $1.
+    usdc: TestTokenClient<'a>,
+    /// 18 decimals, $2,000.
+    weth: TestTokenClient<'a>,
+    /// 7 decimals, $0.10.
+    xlm: TestTokenClient<'a>,
+}
+
+fn create_token<'a>(env: &Env, decimals: u32) -> TestTokenClient<'a> {
+    let token = TestTokenClient::new(env, &env.register_contract(None, TestToken));
+    token.init(&decimals);
+    token
+}
+
+/// Three accepted tokens with different decimals, all priced by the oracle,
+/// and a `ticket` item priced at $25.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let oracle = TestOracleClient::new(&env, &env.register_contract(None, TestOracle));
+    let shop =
+        MultiCurrencyPricingClient::new(&env, &env.register_contract(None, MultiCurrencyPricing));
+    let treasury = Address::generate(&env);
+    shop.initialize(&PricingConfig {
+        admin: Address::generate(&env),
+        oracle: oracle.address.clone(),
+        treasury: treasury.clone(),
+        max_price_age: MAX_AGE,
+    });
+
+    let usdc = create_token(&env, 6);
+    let weth = create_token(&env, 18);
+    let xlm = create_token(&env, 7);
+    for (token, price) in [
+        (&usdc, ORACLE_ONE),
+        (&weth, 2_000 * ORACLE_ONE),
+        (&xlm, ORACLE_ONE / 10),
+    ] {
+        shop.accept_token(&token.address);
+        oracle.set_price(&token.address, &price);
+    }
+    shop.set_price(&symbol_short!("ticket"), &TICKET_USD);
+
+    Setup {
+        env,
+        shop,
+        oracle,
+        treasury,
+        usdc,
+        weth,
+        xlm,
+    }
+}
+
+// ==================== CONVERSION HELPERS ====================
+
+#[test]
+fn test_conversion_helpers() {
+    let env = Env::default();
+    // $25 at $2,000 (14 decimals) is 0.0125 of an 18-decimal token, and back.
+    let amount = usd_to_token(
+        &env,
+        TICKET_USD,
+        7,
+        2_000 * ORACLE_ONE,
+        14,
+        18,
+        Rounding::Up,
+    );
+    assert_eq!(amount, Ok(12_500_000_000_000_000));
+    assert_eq!(
+        token_to_usd(
+            &env,
+            amount.unwrap(),
+            18,
+            2_000 * ORACLE_ONE,
+            14,
+            7,
+            Rounding::Down
+        ),
+        Ok(TICKET_USD)
+    );
+}
+
+#[test]
+fn test_conversion_large_amounts() {
+    let env = Env::default();
+    // A million 18-decimal tokens at $2,000: amount × price overflows an
+    // i128, but the intermediate is a U256 and the $2B result fits.
+    let amount: i128 = 1_000_000 * 1_000_000_000_000_000_000;
+    assert!(amount.checked_mul(2_000 * ORACLE_ONE).is_none());
+    assert_eq!(
+        token_to_usd(&env, amount, 18, 2_000 * ORACLE_ONE, 14, 7, Rounding::Down),
+        Ok(2_000_000_000 * 10_000_000)
+    );
+    assert_eq!(
+        usd_to_token(&env, 1, 7, 1, 30, 18, Rounding::Up),
+        Err(DecimalsError::UnsupportedDecimals)
+    );
+}
+
+// ==================== QUOTES ====================
+
+#[test]
+fn test_quote_normalizes_decimals() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    // $25 is 25 USDC, 0.0125 WETH and 250 XLM, each in its own decimals.
+    assert_eq!(s.shop.quote(&ticket, &s.usdc.address), 25_000_000);
+    assert_eq!(
+        s.shop.quote(&ticket, &s.weth.address),
+        12_500_000_000_000_000
+    );
+    assert_eq!(s.shop.quote(&ticket, &s.xlm.address), 2_500_000_000);
+    assert_eq!(s.shop.token_decimals(&s.weth.address), Some(18));
+}
+
+#[test]
+fn test_quote_rounds_up() {
+    let s = setup();
+    // At $0.30, $25 is 83.33333333... XLM: the buyer pays the next unit up.
+    s.oracle.set_price(&s.xlm.address, &(3 * ORACLE_ONE / 10));
+    let amount = s.shop.quote(&symbol_short!("ticket"), &s.xlm.address);
+    assert_eq!(amount, 833_333_334);
+    assert!(s.shop.value_usd(&s.xlm.address, &amount) >= TICKET_USD);
+    assert!(s.shop.value_usd(&s.xlm.address, &(amount - 1)) < TICKET_USD);
+}
+
+#[test]
+fn test_value_usd() {
+    let s = setup();
+    // 1.5 WETH at $2,000 is $3,000.
+    assert_eq!(
+        s.shop
+            .value_usd(&s.weth.address, &1_500_000_000_000_000_000),
+        30_000_000_000
+    );
+    assert_eq!(s.shop.value_usd(&s.usdc.address, &1), 10);
+    assert_eq!(
+        s.shop.try_value_usd(&s.usdc.address, &-1),
+        Err(Ok(PricingError::InvalidAmount))
+    );
+}
+
+// ==================== PAYMENTS ====================
+
+#[test]
+fn test_pay_transfers_to_treasury() {
+    let s = setup();
+    let buyer = Address::generate(&s.env);
+    s.weth.mint(&buyer, &1_000_000_000_000_000_000);
+
+    let paid = s.shop.pay(
+        &buyer,
+        &symbol_short!("ticket"),
+        &s.weth.address,
+        &12_500_000_000_000_000,
+    );
+    assert_eq!(paid, 12_500_000_000_000_000);
+    assert_eq!(s.weth.balance(&s.treasury), paid);
+    assert_eq!(s.weth.balance(&buyer), 1_000_000_000_000_000_000 - paid);
+}
+
+#[test]
+fn test_slippage_bound() {
+    let s = setup();
+    let buyer = Address::generate(&s.env);
+    s.xlm.mint(&buyer, &10_000_000_000);
+    let ticket = symbol_short!("ticket");
+    let quoted = s.shop.quote(&ticket, &s.xlm.address);
+
+    // XLM drops to $0.08 before the payment lands: 312.5 XLM instead of 250.
+    s.oracle.set_price(&s.xlm.address, &(8 * ORACLE_ONE / 100));
+    assert_eq!(
+        s.shop.try_pay(&buyer, &ticket, &s.xlm.address, &quoted),
+        Err(Ok(PricingError::SlippageExceeded))
+    );
+    assert_eq!(s.xlm.balance(&buyer), 10_000_000_000);
+}
+
+// ==================== ORACLE CHECKS ====================
+
+#[test]
+fn test_stale_price_refused() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    s.env.ledger().with_mut(|l| l.timestamp += MAX_AGE);
+    s.shop.quote(&ticket, &s.usdc.address);
+
+    s.env.ledger().with_mut(|l| l.timestamp += 1);
+    assert_eq!(
+        s.shop.try_quote(&ticket, &s.usdc.address),
+        Err(Ok(PricingError::StalePrice))
+    );
+    // A fresh update makes the token usable again.
+    s.oracle.set_price(&s.usdc.address, &ORACLE_ONE);
+    s.shop.quote(&ticket, &s.usdc.address);
+}
+
+#[test]
+fn test_missing_or_invalid_oracle_price() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    let unpriced = create_token(&s.env, 7);
+    s.shop.accept_token(&unpriced.address);
+    assert_eq!(
+        s.shop.try_quote(&ticket, &unpriced.address),
+        Err(Ok(PricingError::PriceUnavailable))
+    );
+
+    s.oracle.set_price(&unpriced.address, &0);
+    assert_eq!(
+        s.shop.try_quote(&ticket, &unpriced.address),
+        Err(Ok(PricingError::InvalidPrice))
+    );
+}
+
+// ==================== FAILOVER ====================
+
+/// A second oracle, set as the fallback, that prices WETH at $1,900.
+fn add_fallback<'a>(s: &Setup<'a>) -> TestOracleClient<'a> {
+    let fallback = TestOracleClient::new(&s.env, &s.env.register_contract(None, TestOracle));
+    s.shop.set_fallback_oracle(&Some(fallback.address.clone()));
+    fallback.set_price(&s.weth.address, &(1_900 * ORACLE_ONE));
+    fallback
+}
+
+/// The primary oracle goes silent for longer than `MAX_AGE`.
+fn primary_goes_silent(s: &Setup) {
+    s.env.ledger().with_mut(|l| l.timestamp += MAX_AGE + 1);
+}
+
+fn last_event(s: &Setup) -> (Symbol, Address, PriceSource) {
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    let (name, token): (Symbol, Address) = topics.to_val().into_val(&s.env);
+    (name, token, data.into_val(&s.env))
+}
+
+#[test]
+fn test_failover_to_secondary_when_primary_silent() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    let fallback = add_fallback(&s);
+    assert_eq!(
+        s.shop.quote(&ticket, &s.weth.address),
+        12_500_000_000_000_000
+    );
+    assert_eq!(
+        s.shop.feed_status(&s.weth.address).source,
+        PriceSource::Primary
+    );
+
+    primary_goes_silent(&s);
+    fallback.set_price(&s.weth.address, &(1_900 * ORACLE_ONE));
+    // $25 at $1,900, rounded up.
+    assert_eq!(
+        s.shop.quote(&ticket, &s.weth.address),
+        13_157_894_736_842_106
+    );
+    assert_eq!(
+        last_event(&s),
+        (
+            symbol_short!("degraded"),
+            s.weth.address.clone(),
+            PriceSource::Secondary
+        )
+    );
+    assert_eq!(
+        s.shop.feed_status(&s.weth.address),
+        FeedStatus {
+            source: PriceSource::Secondary,
+            primary_updated: Some(1_000),
+            secondary_updated: Some(1_000 + MAX_AGE + 1),
+        }
+    );
+}
+
+#[test]
+fn test_safe_mode_when_every_feed_silent() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    add_fallback(&s);
+    // $0.08 per XLM, below the last oracle price of $0.10.
+    s.shop.set_safe_price(&s.xlm.address, &Some(800_000));
+
+    primary_goes_silent(&s);
+    // The fallback has no XLM price at all, so safe mode takes over.
+    assert_eq!(s.shop.quote(&ticket, &s.xlm.address), 3_125_000_000);
+    assert_eq!(
+        last_event(&s),
+        (
+            symbol_short!("degraded"),
+            s.xlm.address.clone(),
+            PriceSource::SafeMode
+        )
+    );
+    let status = s.shop.feed_status(&s.xlm.address);
+    assert_eq!(status.source, PriceSource::SafeMode);
+    assert_eq!(status.primary_updated, Some(1_000));
+    assert_eq!(status.secondary_updated, None);
+
+    // Without a safe price, the primary's error is reported.
+    assert_eq!(
+        s.shop.try_quote(&ticket, &s.usdc.address),
+        Err(Ok(PricingError::StalePrice))
+    );
+}
+
+#[test]
+fn test_recovers_when_primary_resumes() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    let fallback = add_fallback(&s);
+    primary_goes_silent(&s);
+    fallback.set_price(&s.weth.address, &(1_900 * ORACLE_ONE));
+    s.shop.quote(&ticket, &s.weth.address);
+
+    s.oracle.set_price(&s.weth.address, &(2_000 * ORACLE_ONE));
+    assert_eq!(
+        s.shop.quote(&ticket, &s.weth.address),
+        12_500_000_000_000_000
+    );
+    assert_eq!(
+        last_event(&s),
+        (
+            symbol_short!("recovered"),
+            s.weth.address.clone(),
+            PriceSource::Primary
+        )
+    );
+    assert_eq!(
+        s.shop.feed_status(&s.weth.address).primary_updated,
+        Some(1_000 + MAX_AGE + 1)
+    );
+}
+
+#[test]
+fn test_failover_settings() {
+    let s = setup();
+    assert_eq!(
+        s.shop.try_set_safe_price(&s.usdc.address, &Some(0)),
+        Err(Ok(PricingError::InvalidPrice))
+    );
+    let fallback = add_fallback(&s);
+    assert_eq!(s.shop.fallback_oracle(), Some(fallback.address));
+    s.shop.set_fallback_oracle(&None);
+    assert_eq!(s.shop.fallback_oracle(), None);
+
+    s.shop.set_safe_price(&s.usdc.address, &Some(10_000_000));
+    assert_eq!(s.shop.safe_price(&s.usdc.address), Some(10_000_000));
+    s.shop.set_safe_price(&s.usdc.address, &None);
+    assert_eq!(s.shop.safe_price(&s.usdc.address), None);
+}
+
+// ==================== CIRCUIT BREAKER ====================
+
+/// 5% within 10 minutes.
+fn set_breaker(s: &Setup) {
+    s.shop.set_breaker(&Some(Breaker {
+        max_deviation_bps: 500,
+        window: 600,
+    }));
+}
+
+#[test]
+fn test_breaker_off_by_default() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    s.shop.quote(&ticket, &s.weth.address);
+    s.oracle.set_price(&s.weth.address, &(4_000 * ORACLE_ONE));
+    s.shop.quote(&ticket, &s.weth.address);
+    assert_eq!(s.shop.reference(&s.weth.address), None);
+}
+
+#[test]
+fn test_breaker_rejects_spike_within_window() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    set_breaker(&s);
+    s.shop.quote(&ticket, &s.weth.address);
+    assert_eq!(
+        s.shop.reference(&s.weth.address),
+        Some(Reference {
+            price: 2_000 * 1_000_000_000_000_000_000,
+            timestamp: 1_000,
+        })
+    );
+
+    // +10% in one update: refused, for payments too.
+    s.oracle.set_price(&s.weth.address, &(2_200 * ORACLE_ONE));
+    assert_eq!(
+        s.shop.try_quote(&ticket, &s.weth.address),
+        Err(Ok(PricingError::PriceDeviation))
+    );
+    let buyer = Address::generate(&s.env);
+    s.weth.mint(&buyer, &1_000_000_000_000_000_000);
+    assert_eq!(
+        s.shop.try_pay(&buyer, &ticket, &s.weth.address, &i128::MAX),
+        Err(Ok(PricingError::PriceDeviation))
+    );
+
+    // +5% is within bounds and becomes the new reference.
+    s.oracle.set_price(&s.weth.address, &(2_100 * ORACLE_ONE));
+    s.shop.quote(&ticket, &s.weth.address);
+    assert_eq!(
+        s.shop.reference(&s.weth.address).unwrap().price,
+        2_100 * 1_000_000_000_000_000_000
+    );
+}
+
+#[test]
+fn test_breaker_accepts_move_after_window() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    set_breaker(&s);
+    s.shop.quote(&ticket, &s.weth.address);
+
+    s.env.ledger().with_mut(|l| l.timestamp += 599);
+    s.oracle.set_price(&s.weth.address, &(2_500 * ORACLE_ONE));
+    assert_eq!(
+        s.shop.try_quote(&ticket, &s.weth.address),
+        Err(Ok(PricingError::PriceDeviation))
+    );
+
+    // The reference is now older than the window: the move stands.
+    s.env.ledger().with_mut(|l| l.timestamp += 1);
+    assert_eq!(
+        s.shop.quote(&ticket, &s.weth.address),
+        10_000_000_000_000_000
+    );
+}
+
+#[test]
+fn test_governance_override() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    set_breaker(&s);
+    s.shop.quote(&ticket, &s.weth.address);
+    s.oracle.set_price(&s.weth.address, &(2_500 * ORACLE_ONE));
+    assert_eq!(
+        s.shop.try_quote(&ticket, &s.weth.address),
+        Err(Ok(PricingError::PriceDeviation))
+    );
+
+    assert_eq!(
+        s.shop.override_breaker(&s.weth.address),
+        2_500 * 1_000_000_000_000_000_000
+    );
+    assert_eq!(s.env.auths()[0].0, s.shop.config().admin);
+    assert_eq!(
+        s.shop.quote(&ticket, &s.weth.address),
+        10_000_000_000_000_000
+    );
+
+    assert_eq!(
+        s.shop.try_set_breaker(&Some(Breaker {
+            max_deviation_bps: 0,
+            window: 600,
+        })),
+        Err(Ok(PricingError::InvalidConfig))
+    );
+    s.shop.set_breaker(&None);
+    assert_eq!(s.shop.breaker(), None);
+}
+
+// ==================== WHITELIST AND ITEMS ====================
+
+#[test]
+fn test_only_accepted_tokens() {
+    let s = setup();
+    let ticket = symbol_short!("ticket");
+    let other = create_token(&s.env, 7);
+    s.oracle.set_price(&other.address, &ORACLE_ONE);
+    assert_eq!(
+        s.shop.try_quote(&ticket, &other.address),
+        Err(Ok(PricingError::TokenNotAccepted))
+    );
+
+    s.shop.remove_token(&s.usdc.address);
+    assert_eq!(s.shop.token_decimals(&s.usdc.address), None);
+    assert_eq!(
+        s.shop.try_quote(&ticket, &s.usdc.address),
+        Err(Ok(PricingError::TokenNotAccepted))
+    );
+}
+
+#[test]
+fn test_item_prices() {
+    let s = setup();
+    assert_eq!(
+        s.shop.try_quote(&symbol_short!("none"), &s.usdc.address),
+        Err(Ok(PricingError::ItemNotFound))
+    );
+    assert_eq!(
+        s.shop.try_set_price(&symbol_short!("free"), &0),
+        Err(Ok(PricingError::InvalidPrice))
+    );
+    assert_eq!(s.shop.price(&symbol_short!("ticket")), Some(TICKET_USD));
+}
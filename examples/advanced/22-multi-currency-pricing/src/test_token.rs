@@ -0,0 +1,54 @@
+//! Minimal token with configurable decimals, used in the tests.
+//!
+//! Stellar asset contracts always report 7 decimals; this stands in for
+//! the 6- and 18-decimal tokens the conversion has to handle. It implements
+//! just the calls the pricing contract and the tests make.
+
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum TokenKey {
+    Decimals,
+    Balance(Address),
+}
+
+#[contract]
+pub struct TestToken;
+
+#[contractimpl]
+impl TestToken {
+    pub fn init(env: Env, decimals: u32) {
+        env.storage().instance().set(&TokenKey::Decimals, &decimals);
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(to), &(balance + amount));
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&TokenKey::Decimals).unwrap()
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&TokenKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        assert!(from_balance >= amount, "insufficient balance");
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(from), &(from_balance - amount));
+        Self::mint(env, to, amount);
+    }
+}
@@ -0,0 +1,267 @@
+//! # Dust Sweeping and Minimum Amounts
+//!
+//! A vault holding one token, with internal balances that users deposit
+//! into, transfer between and withdraw from. Two limits keep the ledger
+//! free of economically meaningless entries:
+//!
+//! - **`min_amount`** – deposits, withdrawals, transfers and each payout of a
+//!   distribution must be at least this large. Moving your *entire* balance
+//!   is always allowed, so nothing can get stuck.
+//! - **`dust_threshold`** – when an operation leaves an account with a
+//!   positive balance below this, the residual is swept to the `collector`
+//!   account instead of lingering as dust.
+//!
+//! Dust costs real resources: every tiny balance is a persistent entry that
+//! has to be rent-bumped, and a flood of 1-unit deposits can bloat storage
+//! for almost nothing. Sweeping keeps every balance either zero or
+//! meaningful.
+//!
+//! ## Conservation
+//!
+//! Sweeping moves dust, it never burns or mints it. Every operation credits
+//! exactly what it debits, so at all times:
+//!
+//! ```text
+//! token.balance(vault) == Σ balance(account)   (including the collector)
+//! ```
+//!
+//! Distributions round each share down; the rounding remainder and any share
+//! below `min_amount` go to the collector, so the shares plus the dust add
+//! up to exactly the distributed amount.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustConfig {
+    pub admin: Address,
+    pub token: Address,
+    /// Receives swept dust as an internal balance.
+    pub collector: Address,
+    /// Smallest deposit, withdrawal, transfer or payout.
+    pub min_amount: i128,
+    /// Residual balances below this are swept to the collector.
+    pub dust_threshold: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Balance(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DustError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// A non-positive `min_amount` or a negative `dust_threshold`.
+    InvalidConfig = 3,
+    /// The amount is below `min_amount` and is not the whole balance.
+    BelowMinimum = 4,
+    InsufficientBalance = 5,
+    /// Empty recipients, mismatched weights, or a zero total weight.
+    InvalidRecipients = 6,
+}
+
+#[contract]
+pub struct DustVault;
+
+#[contractimpl]
+impl DustVault {
+    pub fn initialize(env: Env, config: DustConfig) -> Result<(), DustError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(DustError::AlreadyInitialized);
+        }
+        Self::check_limits(config.min_amount, config.dust_threshold)?;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Change both limits. Existing balances below the new threshold are
+    /// swept the next time they are touched, or by `sweep`.
+    pub fn set_limits(env: Env, min_amount: i128, dust_threshold: i128) -> Result<(), DustError> {
+        let mut config = Self::config(env.clone())?;
+        config.admin.require_auth();
+        Self::check_limits(min_amount, dust_threshold)?;
+        config.min_amount = min_amount;
+        config.dust_threshold = dust_threshold;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    // ==================== USER ====================
+
+    /// Deposit at least `min_amount`. Returns the new balance.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<i128, DustError> {
+        user.require_auth();
+        let config = Self::config(env.clone())?;
+        if amount < config.min_amount {
+            return Err(DustError::BelowMinimum);
+        }
+        token::Client::new(&env, &config.token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+        Ok(Self::credit(&env, &user, amount))
+    }
+
+    /// Withdraw `amount`. Returns the dust swept from what was left.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<i128, DustError> {
+        user.require_auth();
+        let config = Self::config(env.clone())?;
+        Self::debit(&env, &config, &user, amount)?;
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        Ok(Self::sweep_residual(&env, &config, &user))
+    }
+
+    /// Move `amount` between internal balances. Returns the dust swept from
+    /// the sender.
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<i128, DustError> {
+        from.require_auth();
+        let config = Self::config(env.clone())?;
+        Self::debit(&env, &config, &from, amount)?;
+        Self::credit(&env, &to, amount);
+        Ok(Self::sweep_residual(&env, &config, &from))
+    }
+
+    /// Settle `amount` from `from` across `recipients` in proportion to
+    /// `weights`. Returns the total dust sent to the collector.
+    ///
+    /// Each share is rounded down. Shares below `min_amount` are not paid
+    /// out; they, the rounding remainder and the sender's residual all go
+    /// to the collector.
+    pub fn distribute(
+        env: Env,
+        from: Address,
+        recipients: Vec<Address>,
+        weights: Vec<u32>,
+        amount: i128,
+    ) -> Result<i128, DustError> {
+        from.require_auth();
+        let config = Self::config(env.clone())?;
+        let total_weight: u64 = weights.iter().map(u64::from).sum();
+        if recipients.is_empty() || recipients.len() != weights.len() || total_weight == 0 {
+            return Err(DustError::InvalidRecipients);
+        }
+        Self::debit(&env, &config, &from, amount)?;
+
+        let mut paid = 0;
+        for (recipient, weight) in recipients.iter().zip(weights.iter()) {
+            let share = amount * weight as i128 / total_weight as i128;
+            if share >= config.min_amount {
+                Self::credit(&env, &recipient, share);
+                paid += share;
+            }
+        }
+        let mut dust = amount - paid;
+        if dust > 0 {
+            Self::credit(&env, &config.collector, dust);
+            Self::emit_swept(&env, &from, dust);
+        }
+        dust += Self::sweep_residual(&env, &config, &from);
+        Ok(dust)
+    }
+
+    /// Sweep `user`'s balance if it is dust, e.g. after the threshold was
+    /// raised. Anyone can call. Returns the amount swept.
+    pub fn sweep(env: Env, user: Address) -> Result<i128, DustError> {
+        let config = Self::config(env.clone())?;
+        Ok(Self::sweep_residual(&env, &config, &user))
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn config(env: Env) -> Result<DustConfig, DustError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(DustError::NotInitialized)
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn check_limits(min_amount: i128, dust_threshold: i128) -> Result<(), DustError> {
+        if min_amount <= 0 || dust_threshold < 0 {
+            return Err(DustError::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    /// Take `amount` from `user`: at least `min_amount`, unless it is the
+    /// whole balance.
+    fn debit(
+        env: &Env,
+        config: &DustConfig,
+        user: &Address,
+        amount: i128,
+    ) -> Result<(), DustError> {
+        let balance = Self::balance(env.clone(), user.clone());
+        if amount <= 0 || (amount < config.min_amount && amount != balance) {
+            return Err(DustError::BelowMinimum);
+        }
+        if amount > balance {
+            return Err(DustError::InsufficientBalance);
+        }
+        Self::set_balance(env, user, balance - amount);
+        Ok(())
+    }
+
+    fn credit(env: &Env, user: &Address, amount: i128) -> i128 {
+        let balance = Self::balance(env.clone(), user.clone()) + amount;
+        Self::set_balance(env, user, balance);
+        balance
+    }
+
+    /// Move `user`'s balance to the collector if it is positive and below
+    /// the threshold. Returns the amount moved.
+    ///
+    /// Emits `(dust, swept)` with `(user, amount)`.
+    fn sweep_residual(env: &Env, config: &DustConfig, user: &Address) -> i128 {
+        let balance = Self::balance(env.clone(), user.clone());
+        if balance == 0 || balance >= config.dust_threshold || *user == config.collector {
+            return 0;
+        }
+        Self::set_balance(env, user, 0);
+        Self::credit(env, &config.collector, balance);
+        Self::emit_swept(env, user, balance);
+        balance
+    }
+
+    fn emit_swept(env: &Env, from: &Address, amount: i128) {
+        env.events().publish(
+            (symbol_short!("dust"), symbol_short!("swept")),
+            (from.clone(), amount),
+        );
+    }
+
+    /// Zero balances are removed rather than stored.
+    fn set_balance(env: &Env, user: &Address, balance: i128) {
+        let key = DataKey::Balance(user.clone());
+        if balance == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &balance);
+        }
+    }
+}
+
+mod test;
@@ -0,0 +1,304 @@
+#![cfg(test)]
+
+use super::*;
+use cookbook_testing::assert_conserved;
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    token::{StellarAssetClient, TokenClient},
+    vec,
+};
+
+const MIN: i128 = 100;
+const DUST: i128 = 50;
+
+struct Setup<'a> {
+    env: Env,
+    vault: DustVaultClient<'a>,
+    token: TokenClient<'a>,
+    collector: Address,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+fn setup() -> Setup<'static> {
+    setup_in(Env::default())
+}
+
+/// `min_amount` 100, `dust_threshold` 50, and three users holding 10,000
+/// tokens each outside the vault.
+fn setup_in(env: Env) -> Setup<'static> {
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let vault = DustVaultClient::new(&env, &env.register_contract(None, DustVault));
+    let collector = Address::generate(&env);
+    vault.initialize(&DustConfig {
+        admin: Address::generate(&env),
+        token: token_id.clone(),
+        collector: collector.clone(),
+        min_amount: MIN,
+        dust_threshold: DUST,
+    });
+
+    let asset = StellarAssetClient::new(&env, &token_id);
+    let [alice, bob, carol] = [0; 3].map(|_| Address::generate(&env));
+    for user in [&alice, &bob, &carol] {
+        asset.mint(user, &10_000);
+    }
+
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        vault,
+        collector,
+        alice,
+        bob,
+        carol,
+    }
+}
+
+impl Setup<'_> {
+    fn accounts(&self) -> [&Address; 4] {
+        [&self.alice, &self.bob, &self.carol, &self.collector]
+    }
+
+    /// The vault holds exactly the sum of the internal balances, and every
+    /// user's tokens are either in their wallet or in the vault.
+    fn assert_conserved(&self) {
+        let internal: i128 = self.accounts().iter().map(|a| self.vault.balance(a)).sum();
+        assert_eq!(self.token.balance(&self.vault.address), internal);
+        let wallets: i128 = self.accounts().iter().map(|a| self.token.balance(a)).sum();
+        assert_eq!(wallets + internal, 30_000);
+    }
+}
+
+// ==================== MINIMUM AMOUNTS ====================
+
+#[test]
+fn test_deposit_minimum() {
+    let s = setup();
+    assert_eq!(
+        s.vault.try_deposit(&s.alice, &(MIN - 1)),
+        Err(Ok(DustError::BelowMinimum))
+    );
+    assert_eq!(s.vault.deposit(&s.alice, &MIN), MIN);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_whole_balance_can_always_leave() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    // Leaves 80: above the dust threshold, so it stays.
+    assert_eq!(s.vault.withdraw(&s.alice, &920), 0);
+    assert_eq!(s.vault.balance(&s.alice), 80);
+
+    // 60 is below the minimum and is not the whole balance.
+    assert_eq!(
+        s.vault.try_withdraw(&s.alice, &60),
+        Err(Ok(DustError::BelowMinimum))
+    );
+    // 80 is below the minimum too, but it empties the account.
+    s.vault.withdraw(&s.alice, &80);
+    assert_eq!(s.vault.balance(&s.alice), 0);
+    assert_eq!(s.token.balance(&s.alice), 10_000);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_transfer_minimum_and_balance() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    assert_eq!(
+        s.vault.try_transfer(&s.alice, &s.bob, &(MIN - 1)),
+        Err(Ok(DustError::BelowMinimum))
+    );
+    assert_eq!(
+        s.vault.try_transfer(&s.alice, &s.bob, &1_001),
+        Err(Ok(DustError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.vault.try_transfer(&s.alice, &s.bob, &0),
+        Err(Ok(DustError::BelowMinimum))
+    );
+}
+
+// ==================== SWEEPING ====================
+
+#[test]
+fn test_withdraw_sweeps_residual() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    // Leaves 40, below the threshold of 50.
+    assert_eq!(s.vault.withdraw(&s.alice, &960), 40);
+    assert_eq!(s.vault.balance(&s.alice), 0);
+    assert_eq!(s.vault.balance(&s.collector), 40);
+    assert_eq!(s.token.balance(&s.alice), 9_960);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_transfer_sweeps_sender_residual() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    assert_eq!(s.vault.transfer(&s.alice, &s.bob, &951), 49);
+    assert_eq!(s.vault.balance(&s.alice), 0);
+    assert_eq!(s.vault.balance(&s.bob), 951);
+    assert_eq!(s.vault.balance(&s.collector), 49);
+
+    // Exactly the threshold is not dust.
+    assert_eq!(s.vault.transfer(&s.bob, &s.carol, &901), 0);
+    assert_eq!(s.vault.balance(&s.bob), DUST);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_sweep_after_threshold_raised() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    s.vault.withdraw(&s.alice, &920);
+    assert_eq!(s.vault.sweep(&s.alice), 0);
+
+    s.vault.set_limits(&MIN, &(DUST * 2));
+    assert_eq!(s.vault.sweep(&s.alice), 80);
+    assert_eq!(s.vault.balance(&s.collector), 80);
+    // The collector itself is never swept.
+    assert_eq!(s.vault.sweep(&s.collector), 0);
+    assert_eq!(s.vault.balance(&s.collector), 80);
+    s.assert_conserved();
+}
+
+// ==================== DISTRIBUTION ====================
+
+#[test]
+fn test_distribute_rounding_goes_to_collector() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &2_000);
+    let recipients = vec![&s.env, s.alice.clone(), s.bob.clone(), s.carol.clone()];
+    // 1000 / 3 = 333 each, remainder 1. A distribution only moves internal
+    // balances, so their total is unchanged.
+    let dust = assert_conserved!(
+        balance = |a| s.vault.balance(a),
+        [s.alice, s.bob, s.carol, s.collector],
+        {
+            s.vault
+                .distribute(&s.alice, &recipients, &vec![&s.env, 1, 1, 1], &1_000)
+        }
+    );
+    assert_eq!(dust, 1);
+    assert_eq!(s.vault.balance(&s.alice), 1_333);
+    assert_eq!(s.vault.balance(&s.bob), 333);
+    assert_eq!(s.vault.balance(&s.carol), 333);
+    assert_eq!(s.vault.balance(&s.collector), 1);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_distribute_skips_sub_minimum_shares() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    let recipients = vec![&s.env, s.bob.clone(), s.carol.clone(), s.collector.clone()];
+    // 1000 × 10/21 = 476, 476; 1000 × 1/21 = 47 is below the minimum.
+    let dust = assert_conserved!(
+        balance = |a| s.vault.balance(a),
+        [s.alice, s.bob, s.carol, s.collector],
+        {
+            s.vault
+                .distribute(&s.alice, &recipients, &vec![&s.env, 10, 10, 1], &1_000)
+        }
+    );
+    assert_eq!(dust, 48);
+    assert_eq!(s.vault.balance(&s.bob), 476);
+    assert_eq!(s.vault.balance(&s.carol), 476);
+    assert_eq!(s.vault.balance(&s.collector), 48);
+    s.assert_conserved();
+}
+
+#[test]
+fn test_distribute_invalid_recipients() {
+    let s = setup();
+    s.vault.deposit(&s.alice, &1_000);
+    let one = vec![&s.env, s.bob.clone()];
+    for (recipients, weights) in [
+        (Vec::new(&s.env), Vec::new(&s.env)),
+        (one.clone(), vec![&s.env, 1, 1]),
+        (one, vec![&s.env, 0]),
+    ] {
+        assert_eq!(
+            s.vault
+                .try_distribute(&s.alice, &recipients, &weights, &500),
+            Err(Ok(DustError::InvalidRecipients))
+        );
+    }
+    assert_eq!(s.vault.balance(&s.alice), 1_000);
+}
+
+// ==================== CONSERVATION ====================
+
+#[test]
+fn test_random_operations_conserve_funds() {
+    // Hundreds of calls would make a multi-megabyte snapshot; skip it.
+    let s = setup_in(Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    }));
+    let users = [&s.alice, &s.bob, &s.carol];
+    // Deterministic pseudo-random sequence (64-bit LCG).
+    let mut seed: u64 = 0x5eed;
+    let mut next = |bound: u64| {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        (seed >> 33) % bound
+    };
+
+    for _ in 0..200 {
+        // The test budget is cumulative; give each step a fresh one.
+        s.env.budget().reset_default();
+        let user = users[next(3) as usize];
+        let other = users[next(3) as usize];
+        let amount = next(1_200) as i128;
+        // Failures (below minimum, insufficient balance) are part of the
+        // sequence: a failed call must not move anything either.
+        let _ = assert_conserved!(
+            s.env,
+            s.token.address,
+            [s.alice, s.bob, s.carol, s.collector, s.vault.address],
+            {
+                match next(4) {
+                    0 => s.vault.try_deposit(user, &amount).map(|_| Ok(0)),
+                    1 => s.vault.try_withdraw(user, &amount),
+                    2 => s.vault.try_transfer(user, other, &amount),
+                    _ => s.vault.try_distribute(
+                        user,
+                        &vec![&s.env, s.alice.clone(), s.bob.clone(), s.carol.clone()],
+                        &vec![&s.env, next(5) as u32, next(5) as u32, 1],
+                        &amount,
+                    ),
+                }
+            }
+        );
+        s.assert_conserved();
+        for user in users {
+            let balance = s.vault.balance(user);
+            assert!(balance == 0 || balance >= DUST, "dust left behind");
+        }
+    }
+}
+
+#[test]
+fn test_invalid_config() {
+    let env = Env::default();
+    let vault = DustVaultClient::new(&env, &env.register_contract(None, DustVault));
+    let config = DustConfig {
+        admin: Address::generate(&env),
+        token: Address::generate(&env),
+        collector: Address::generate(&env),
+        min_amount: 0,
+        dust_threshold: DUST,
+    };
+    assert_eq!(
+        vault.try_initialize(&config),
+        Err(Ok(DustError::InvalidConfig))
+    );
+}
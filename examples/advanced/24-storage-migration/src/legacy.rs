@@ -0,0 +1,28 @@
+//! The v1 contract, as it was before the upgrade.
+//!
+//! Only compiled for tests. It writes `DataV1` with no version tag, exactly
+//! like the old Wasm did, so the tests can register it, fill storage, then
+//! register `DataStore` at the same address in its place.
+
+#![cfg(test)]
+
+use crate::schema::{DataKey, DataV1};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+#[contract]
+pub struct DataStoreV1;
+
+#[contractimpl]
+impl DataStoreV1 {
+    pub fn deposit(env: Env, user: Address, amount: i128) -> DataV1 {
+        let key = DataKey::Data(user);
+        let mut data = env.storage().persistent().get(&key).unwrap_or(DataV1 {
+            balance: 0,
+            points: 0,
+        });
+        data.balance += amount;
+        data.points += 1;
+        env.storage().persistent().set(&key, &data);
+        data
+    }
+}
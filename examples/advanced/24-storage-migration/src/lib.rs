@@ -0,0 +1,111 @@
+//! # Storage Migration Between Schema Versions
+//!
+//! `upgradeable-contract/v2` migrates a single instance value in one admin
+//! call. That does not scale to per-user persistent entries: there can be
+//! millions of them, one transaction cannot touch them all, and each one
+//! costs a write.
+//!
+//! This contract migrates **lazily** instead. After an upgrade, v1 entries
+//! stay exactly as they were. Each entry carries a version tag (see
+//! [`schema`]), and:
+//!
+//! - **reads** of an old entry convert it in memory and write nothing;
+//! - **writes** always store the current layout and tag, so an entry is
+//!   migrated the first time its owner uses it;
+//! - **`migrate_entry` / `migrate_entries`** let anyone push migration
+//!   forward for idle entries, e.g. before a future v3 drops support for v1.
+//!
+//! The cost of migration is spread across the users who touch their data,
+//! and no call ever has to know how many old entries remain.
+//!
+//! ## Rules for the new layout
+//!
+//! - Never change an old struct in place. Keep `DataV1` exactly as the v1
+//!   Wasm defined it, add `DataV2` next to it, and write a conversion.
+//! - Tag on every write. Entries without a tag are, by definition, the
+//!   layout that predates tagging.
+//! - Keep reading every layout that may still be in storage. Drop `DataV1`
+//!   only once nothing can be tagged below 2.
+
+#![no_std]
+
+pub mod schema;
+
+pub use schema::{DataV1, DataV2, CURRENT_VERSION};
+use soroban_sdk::{contract, contracterror, contractimpl, Address, Env, Vec};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MigrationError {
+    InvalidAmount = 1,
+    InsufficientBalance = 2,
+}
+
+#[contract]
+pub struct DataStore;
+
+#[contractimpl]
+impl DataStore {
+    /// Add `amount` and one point. Migrates the entry if it is old.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<DataV2, MigrationError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(MigrationError::InvalidAmount);
+        }
+        let mut data = schema::load(&env, &user).unwrap_or(DataV2 {
+            balance: 0,
+            points: 0,
+            last_active: 0,
+        });
+        data.balance += amount;
+        data.points += 1;
+        data.last_active = env.ledger().timestamp();
+        schema::store(&env, &user, &data);
+        Ok(data)
+    }
+
+    /// Take `amount` out. Migrates the entry if it is old.
+    pub fn withdraw(env: Env, user: Address, amount: i128) -> Result<DataV2, MigrationError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(MigrationError::InvalidAmount);
+        }
+        let mut data = schema::load(&env, &user).ok_or(MigrationError::InsufficientBalance)?;
+        if amount > data.balance {
+            return Err(MigrationError::InsufficientBalance);
+        }
+        data.balance -= amount;
+        data.last_active = env.ledger().timestamp();
+        schema::store(&env, &user, &data);
+        Ok(data)
+    }
+
+    /// `user`'s entry in the current layout. Read-only, even for old entries.
+    pub fn get(env: Env, user: Address) -> Option<DataV2> {
+        schema::load(&env, &user)
+    }
+
+    /// Layout version `user`'s entry is stored in, or 0 if there is none.
+    pub fn version(env: Env, user: Address) -> u32 {
+        schema::version(&env, &user).unwrap_or(0)
+    }
+
+    /// Migrate one entry. Anyone can call: the result is the same data in
+    /// the current layout. Returns whether it was migrated.
+    pub fn migrate_entry(env: Env, user: Address) -> bool {
+        schema::migrate_entry(&env, &user)
+    }
+
+    /// Migrate a batch of entries. Returns how many were migrated; entries
+    /// that are missing or already current are skipped.
+    pub fn migrate_entries(env: Env, users: Vec<Address>) -> u32 {
+        users
+            .iter()
+            .filter(|user| schema::migrate_entry(&env, user))
+            .count() as u32
+    }
+}
+
+mod legacy;
+mod test;
@@ -0,0 +1,105 @@
+//! Stored layouts and the helpers that move entries between them.
+//!
+//! Every user has one entry under `DataKey::Data(user)`. Its layout is given
+//! by `DataKey::Version(user)`:
+//!
+//! | Tag     | Layout   | Written by                    |
+//! |---------|----------|-------------------------------|
+//! | absent  | `DataV1` | the v1 Wasm, which had no tag |
+//! | `2`     | `DataV2` | this Wasm                     |
+//!
+//! The tag has to live under its own key. A `#[contracttype]` struct is
+//! stored as a map of its fields, and decoding v1 bytes as `DataV2` does
+//! not return an error to handle: it fails the whole call. The reader must
+//! know which layout to ask for *before* it reads the entry.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+/// Layout written by this Wasm.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Layout of entries that have no version tag.
+pub const UNTAGGED_VERSION: u32 = 1;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Data(Address),
+    Version(Address),
+}
+
+/// v1 layout. Kept only so old entries can still be decoded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataV1 {
+    pub balance: i128,
+    pub points: u32,
+}
+
+/// Current layout: `points` widened to `u64`, `last_active` added.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataV2 {
+    pub balance: i128,
+    pub points: u64,
+    /// Ledger timestamp of the last deposit or withdrawal; 0 if it happened
+    /// before v2, when it was not recorded.
+    pub last_active: u64,
+}
+
+impl From<DataV1> for DataV2 {
+    fn from(old: DataV1) -> Self {
+        DataV2 {
+            balance: old.balance,
+            points: old.points.into(),
+            last_active: 0,
+        }
+    }
+}
+
+/// Layout version of `user`'s entry, or `None` if there is no entry.
+pub fn version(env: &Env, user: &Address) -> Option<u32> {
+    let storage = env.storage().persistent();
+    if !storage.has(&DataKey::Data(user.clone())) {
+        return None;
+    }
+    Some(
+        storage
+            .get(&DataKey::Version(user.clone()))
+            .unwrap_or(UNTAGGED_VERSION),
+    )
+}
+
+/// `user`'s entry as a `DataV2`, whatever layout it is stored in. Does not
+/// write: an old entry is converted in memory only.
+pub fn load(env: &Env, user: &Address) -> Option<DataV2> {
+    let key = DataKey::Data(user.clone());
+    let storage = env.storage().persistent();
+    match version(env, user)? {
+        UNTAGGED_VERSION => storage.get::<_, DataV1>(&key).map(DataV2::from),
+        _ => storage.get(&key),
+    }
+}
+
+/// Write `data` in the current layout, with its tag.
+pub fn store(env: &Env, user: &Address, data: &DataV2) {
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::Data(user.clone()), data);
+    storage.set(&DataKey::Version(user.clone()), &CURRENT_VERSION);
+}
+
+/// Rewrite `user`'s entry in the current layout if it is older. Returns
+/// whether anything was written.
+///
+/// Emits `(migrated, user)` with the version it was migrated from.
+pub fn migrate_entry(env: &Env, user: &Address) -> bool {
+    let from = match version(env, user) {
+        Some(v) if v < CURRENT_VERSION => v,
+        _ => return false,
+    };
+    let data = load(env, user).unwrap();
+    store(env, user, &data);
+    env.events()
+        .publish((symbol_short!("migrated"), user.clone()), from);
+    true
+}
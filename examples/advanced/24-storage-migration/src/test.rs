@@ -0,0 +1,181 @@
+#![cfg(test)]
+
+use super::*;
+use crate::legacy::DataStoreV1Client;
+use schema::DataKey;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, IntoVal, TryFromVal, Val,
+};
+
+struct Setup {
+    env: Env,
+    client: DataStoreClient<'static>,
+    alice: Address,
+    bob: Address,
+}
+
+/// Alice and Bob deposit through the v1 contract, then `DataStore` is
+/// registered at the same address: the state an upgrade leaves behind.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, legacy::DataStoreV1);
+    let v1 = DataStoreV1Client::new(&env, &id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    v1.deposit(&alice, &300);
+    v1.deposit(&alice, &200);
+    v1.deposit(&bob, &100);
+
+    // The upgrade: new code, same address, same storage.
+    env.register_contract(Some(&id), DataStore);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    Setup {
+        client: DataStoreClient::new(&env, &id),
+        env,
+        alice,
+        bob,
+    }
+}
+
+fn v2(balance: i128, points: u64, last_active: u64) -> DataV2 {
+    DataV2 {
+        balance,
+        points,
+        last_active,
+    }
+}
+
+// ==================== READING OLD ENTRIES ====================
+
+#[test]
+fn test_v1_entry_reads_through_v2() {
+    let s = setup();
+    assert_eq!(s.client.version(&s.alice), 1);
+    assert_eq!(s.client.get(&s.alice), Some(v2(500, 2, 0)));
+    // Reading converts in memory only.
+    assert_eq!(s.client.version(&s.alice), 1);
+}
+
+#[test]
+fn test_v1_bytes_decode_as_v1() {
+    let s = setup();
+    let key = DataKey::Data(s.alice.clone());
+    let raw: Val = s
+        .env
+        .as_contract(&s.client.address, || s.env.storage().persistent().get(&key))
+        .unwrap();
+    assert_eq!(
+        DataV1::try_from_val(&s.env, &raw),
+        Ok(DataV1 {
+            balance: 500,
+            points: 2
+        })
+    );
+}
+
+/// Without the tag the reader would have to guess, and guessing the new
+/// layout fails the whole call.
+#[test]
+#[should_panic(expected = "Error(Object, UnexpectedSize)")]
+fn test_v1_bytes_do_not_decode_as_v2() {
+    let s = setup();
+    let key = DataKey::Data(s.alice.clone());
+    s.env.as_contract(&s.client.address, || {
+        let _: Option<DataV2> = s.env.storage().persistent().get(&key);
+    });
+}
+
+#[test]
+fn test_missing_entry() {
+    let s = setup();
+    let carol = Address::generate(&s.env);
+    assert_eq!(s.client.version(&carol), 0);
+    assert_eq!(s.client.get(&carol), None);
+}
+
+// ==================== LAZY MIGRATION ON WRITE ====================
+
+#[test]
+fn test_deposit_migrates_entry() {
+    let s = setup();
+    assert_eq!(s.client.deposit(&s.alice, &100), v2(600, 3, 1_000));
+    assert_eq!(s.client.version(&s.alice), CURRENT_VERSION);
+    assert_eq!(s.client.get(&s.alice), Some(v2(600, 3, 1_000)));
+    // Bob has not done anything yet; his entry is untouched.
+    assert_eq!(s.client.version(&s.bob), 1);
+}
+
+#[test]
+fn test_withdraw_migrates_entry() {
+    let s = setup();
+    assert_eq!(s.client.withdraw(&s.bob, &40), v2(60, 1, 1_000));
+    assert_eq!(s.client.version(&s.bob), CURRENT_VERSION);
+}
+
+#[test]
+fn test_failed_write_leaves_entry_old() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_withdraw(&s.bob, &101),
+        Err(Ok(MigrationError::InsufficientBalance))
+    );
+    assert_eq!(s.client.version(&s.bob), 1);
+    assert_eq!(
+        s.client.try_deposit(&s.bob, &0),
+        Err(Ok(MigrationError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_new_entry_is_current() {
+    let s = setup();
+    let carol = Address::generate(&s.env);
+    s.client.deposit(&carol, &50);
+    assert_eq!(s.client.version(&carol), CURRENT_VERSION);
+    assert_eq!(s.client.get(&carol), Some(v2(50, 1, 1_000)));
+}
+
+// ==================== EXPLICIT MIGRATION ====================
+
+#[test]
+fn test_migrate_entry() {
+    let s = setup();
+    assert!(s.client.migrate_entry(&s.bob));
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            (
+                s.client.address.clone(),
+                (symbol_short!("migrated"), s.bob.clone()).into_val(&s.env),
+                1u32.into_val(&s.env)
+            )
+        ]
+    );
+    assert_eq!(s.client.version(&s.bob), CURRENT_VERSION);
+    // Same data, new layout; `last_active` was never recorded in v1.
+    assert_eq!(s.client.get(&s.bob), Some(v2(100, 1, 0)));
+
+    // Already current, or nothing there: nothing to do.
+    assert!(!s.client.migrate_entry(&s.bob));
+    assert!(!s.client.migrate_entry(&Address::generate(&s.env)));
+}
+
+#[test]
+fn test_migrate_entries() {
+    let s = setup();
+    s.client.deposit(&s.alice, &1);
+    let users = vec![
+        &s.env,
+        s.alice.clone(),
+        s.bob.clone(),
+        Address::generate(&s.env),
+    ];
+    // Alice was migrated by her deposit, the third user has no entry.
+    assert_eq!(s.client.migrate_entries(&users), 1);
+    assert_eq!(s.client.migrate_entries(&users), 0);
+    assert_eq!(s.client.version(&s.bob), CURRENT_VERSION);
+}
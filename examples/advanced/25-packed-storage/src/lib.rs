@@ -0,0 +1,202 @@
+//! # Packed Storage for Small Records
+//!
+//! Every ledger entry a call touches costs a read or write fee plus the
+//! bytes of its key and value, and every persistent entry pays rent on its
+//! own. A record of five small fields stored one key per field is five
+//! entries, each repeating the user's address in its key. The same record
+//! packed into one `u128` is a single entry:
+//!
+//! | Layout | Entries per user | `visit` reads | `visit` writes |
+//! |--------|------------------|---------------|----------------|
+//! | Naive: one key per field | 5 | 2 | 3 |
+//! | Packed: one `u128` | 1 | 1 | 1 |
+//!
+//! The contract implements both layouts side by side (`*_naive` functions
+//! use one key per field) so the benchmark tests in `test.rs` can measure
+//! the difference. The bit layout and accessors are in [`packed`].
+//!
+//! ## When packing is worth it
+//!
+//! - Fields are small and bounded: counters, flags, levels, timestamps.
+//! - They are usually read or written together.
+//! - The number of records grows with users, so per-entry overhead adds up.
+//!
+//! Packing costs readability and flexibility: the layout is fixed, fields
+//! cannot grow past their width without a migration, and explorers show a
+//! number instead of named fields. Past 128 bits, the same technique works
+//! on a `BytesN<32>` with byte offsets.
+
+#![no_std]
+
+pub mod packed;
+
+pub use packed::PackedRecord;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+
+pub const FLAG_VERIFIED: u32 = 1 << 0;
+pub const FLAG_PREMIUM: u32 = 1 << 1;
+pub const FLAG_FROZEN: u32 = 1 << 2;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Packed layout: the whole record as a `u128`.
+    Packed(Address),
+    // Naive layout: one entry per field.
+    Visits(Address),
+    Points(Address),
+    LastSeen(Address),
+    Level(Address),
+    Flags(Address),
+}
+
+/// A record with named fields, as both layouts return it.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Record {
+    pub visits: u32,
+    pub points: u32,
+    pub last_seen: u64,
+    pub level: u32,
+    pub flags: u32,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PackedError {
+    /// A value does not fit its field, or a counter would overflow.
+    FieldOverflow = 1,
+    /// Not a single bit within the 16 flag bits.
+    InvalidFlag = 2,
+}
+
+#[contract]
+pub struct PackedStorage;
+
+#[contractimpl]
+impl PackedStorage {
+    // ── packed ─────────────────────────────────────────────────────────────
+
+    /// Count a visit worth `points` and stamp the time. One read, one write.
+    pub fn visit(env: Env, user: Address, points: u32) -> Result<Record, PackedError> {
+        user.require_auth();
+        let mut record = load(&env, &user);
+        record.set_visits(checked_add(record.visits(), 1)?)?;
+        record.set_points(checked_add(record.points(), points)?)?;
+        record.set_last_seen(env.ledger().timestamp())?;
+        save(&env, &user, record);
+        Ok(unpack(record))
+    }
+
+    pub fn set_level(env: Env, user: Address, level: u32) -> Result<(), PackedError> {
+        user.require_auth();
+        let mut record = load(&env, &user);
+        record.set_level(level)?;
+        save(&env, &user, record);
+        Ok(())
+    }
+
+    pub fn set_flag(env: Env, user: Address, flag: u32, on: bool) -> Result<(), PackedError> {
+        user.require_auth();
+        check_flag(flag)?;
+        let mut record = load(&env, &user);
+        record.set_flag(flag, on)?;
+        save(&env, &user, record);
+        Ok(())
+    }
+
+    pub fn record(env: Env, user: Address) -> Record {
+        unpack(load(&env, &user))
+    }
+
+    /// The raw stored value, for inspecting the layout.
+    pub fn raw(env: Env, user: Address) -> u128 {
+        load(&env, &user).0
+    }
+
+    // ── naive ──────────────────────────────────────────────────────────────
+
+    /// Same as `visit`, with one entry per field. Two reads, three writes.
+    pub fn visit_naive(env: Env, user: Address, points: u32) -> Result<Record, PackedError> {
+        user.require_auth();
+        let storage = env.storage().persistent();
+        let visits: u32 = storage.get(&DataKey::Visits(user.clone())).unwrap_or(0);
+        let total: u32 = storage.get(&DataKey::Points(user.clone())).unwrap_or(0);
+        storage.set(&DataKey::Visits(user.clone()), &checked_add(visits, 1)?);
+        storage.set(&DataKey::Points(user.clone()), &checked_add(total, points)?);
+        storage.set(&DataKey::LastSeen(user.clone()), &env.ledger().timestamp());
+        Ok(Self::record_naive(env, user))
+    }
+
+    pub fn set_level_naive(env: Env, user: Address, level: u32) -> Result<(), PackedError> {
+        user.require_auth();
+        if u128::from(level) > packed::LEVEL.max() {
+            return Err(PackedError::FieldOverflow);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Level(user), &level);
+        Ok(())
+    }
+
+    pub fn set_flag_naive(env: Env, user: Address, flag: u32, on: bool) -> Result<(), PackedError> {
+        user.require_auth();
+        check_flag(flag)?;
+        let key = DataKey::Flags(user);
+        let flags: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let flags = if on { flags | flag } else { flags & !flag };
+        env.storage().persistent().set(&key, &flags);
+        Ok(())
+    }
+
+    /// Five reads to assemble what `record` gets from one.
+    pub fn record_naive(env: Env, user: Address) -> Record {
+        let storage = env.storage().persistent();
+        Record {
+            visits: storage.get(&DataKey::Visits(user.clone())).unwrap_or(0),
+            points: storage.get(&DataKey::Points(user.clone())).unwrap_or(0),
+            last_seen: storage.get(&DataKey::LastSeen(user.clone())).unwrap_or(0),
+            level: storage.get(&DataKey::Level(user.clone())).unwrap_or(0),
+            flags: storage.get(&DataKey::Flags(user)).unwrap_or(0),
+        }
+    }
+}
+
+fn load(env: &Env, user: &Address) -> PackedRecord {
+    PackedRecord(
+        env.storage()
+            .persistent()
+            .get(&DataKey::Packed(user.clone()))
+            .unwrap_or(0),
+    )
+}
+
+fn save(env: &Env, user: &Address, record: PackedRecord) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Packed(user.clone()), &record.0);
+}
+
+fn unpack(record: PackedRecord) -> Record {
+    Record {
+        visits: record.visits(),
+        points: record.points(),
+        last_seen: record.last_seen(),
+        level: record.level(),
+        flags: record.flags(),
+    }
+}
+
+fn checked_add(a: u32, b: u32) -> Result<u32, PackedError> {
+    a.checked_add(b).ok_or(PackedError::FieldOverflow)
+}
+
+fn check_flag(flag: u32) -> Result<(), PackedError> {
+    if !flag.is_power_of_two() || u128::from(flag) > packed::FLAGS.max() {
+        return Err(PackedError::InvalidFlag);
+    }
+    Ok(())
+}
+
+mod test;
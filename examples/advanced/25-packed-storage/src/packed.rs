@@ -0,0 +1,129 @@
+//! Bit-level layout of a packed record.
+//!
+//! ```text
+//!  127        112 111    104 103                 64 63         32 31          0
+//! +--------------+----------+---------------------+-------------+-------------+
+//! |  flags (16)  | level(8) |   last_seen (40)    | points (32) | visits (32) |
+//! +--------------+----------+---------------------+-------------+-------------+
+//! ```
+//!
+//! 40 bits of seconds reach the year 36812, so a ledger timestamp loses
+//! nothing. Every setter checks that the value fits its field: a value that
+//! silently spilled into its neighbour would corrupt the record.
+
+use crate::PackedError;
+
+/// A run of `width` bits starting at bit `offset`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Field {
+    pub offset: u32,
+    pub width: u32,
+}
+
+pub const VISITS: Field = Field {
+    offset: 0,
+    width: 32,
+};
+pub const POINTS: Field = Field {
+    offset: 32,
+    width: 32,
+};
+pub const LAST_SEEN: Field = Field {
+    offset: 64,
+    width: 40,
+};
+pub const LEVEL: Field = Field {
+    offset: 104,
+    width: 8,
+};
+pub const FLAGS: Field = Field {
+    offset: 112,
+    width: 16,
+};
+
+/// Every field, lowest bits first.
+pub const FIELDS: [Field; 5] = [VISITS, POINTS, LAST_SEEN, LEVEL, FLAGS];
+
+impl Field {
+    /// Largest value the field can hold.
+    pub const fn max(self) -> u128 {
+        if self.width >= 128 {
+            u128::MAX
+        } else {
+            (1 << self.width) - 1
+        }
+    }
+
+    pub const fn get(self, word: u128) -> u128 {
+        (word >> self.offset) & self.max()
+    }
+
+    /// `word` with this field replaced by `value`, or `FieldOverflow` if
+    /// `value` has more bits than the field.
+    pub fn set(self, word: u128, value: u128) -> Result<u128, PackedError> {
+        if value > self.max() {
+            return Err(PackedError::FieldOverflow);
+        }
+        Ok((word & !(self.max() << self.offset)) | (value << self.offset))
+    }
+}
+
+/// One user's record in a single `u128`. Stored as the bare integer.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PackedRecord(pub u128);
+
+impl PackedRecord {
+    pub fn visits(self) -> u32 {
+        VISITS.get(self.0) as u32
+    }
+
+    pub fn points(self) -> u32 {
+        POINTS.get(self.0) as u32
+    }
+
+    pub fn last_seen(self) -> u64 {
+        LAST_SEEN.get(self.0) as u64
+    }
+
+    pub fn level(self) -> u32 {
+        LEVEL.get(self.0) as u32
+    }
+
+    pub fn flags(self) -> u32 {
+        FLAGS.get(self.0) as u32
+    }
+
+    pub fn has_flag(self, flag: u32) -> bool {
+        self.flags() & flag != 0
+    }
+
+    pub fn set_visits(&mut self, visits: u32) -> Result<(), PackedError> {
+        self.set(VISITS, visits.into())
+    }
+
+    pub fn set_points(&mut self, points: u32) -> Result<(), PackedError> {
+        self.set(POINTS, points.into())
+    }
+
+    pub fn set_last_seen(&mut self, timestamp: u64) -> Result<(), PackedError> {
+        self.set(LAST_SEEN, timestamp.into())
+    }
+
+    pub fn set_level(&mut self, level: u32) -> Result<(), PackedError> {
+        self.set(LEVEL, level.into())
+    }
+
+    pub fn set_flag(&mut self, flag: u32, on: bool) -> Result<(), PackedError> {
+        let flags = if on {
+            self.flags() | flag
+        } else {
+            self.flags() & !flag
+        };
+        self.set(FLAGS, flags.into())
+    }
+
+    fn set(&mut self, field: Field, value: u128) -> Result<(), PackedError> {
+        self.0 = field.set(self.0, value)?;
+        Ok(())
+    }
+}
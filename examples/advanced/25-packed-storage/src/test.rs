@@ -0,0 +1,258 @@
+#![cfg(test)]
+
+use super::*;
+use packed::{Field, FIELDS, FLAGS, LAST_SEEN, LEVEL, POINTS, VISITS};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    xdr::ToXdr,
+    Val,
+};
+
+struct Setup {
+    env: Env,
+    client: PackedStorageClient<'static>,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    let client = PackedStorageClient::new(&env, &env.register_contract(None, PackedStorage));
+    Setup {
+        user: Address::generate(&env),
+        env,
+        client,
+    }
+}
+
+// ==================== LAYOUT ====================
+
+#[test]
+fn test_fields_tile_128_bits() {
+    let mut next = 0;
+    for field in FIELDS {
+        assert_eq!(field.offset, next, "gap or overlap at {field:?}");
+        next += field.width;
+    }
+    assert_eq!(next, 128);
+}
+
+#[test]
+fn test_fields_are_isolated() {
+    for field in FIELDS {
+        // Filling one field touches no other...
+        let word = field.set(0, field.max()).unwrap();
+        for other in FIELDS.iter().filter(|f| **f != field) {
+            assert_eq!(other.get(word), 0, "{field:?} spilled into {other:?}");
+        }
+        // ...and clearing it leaves every other bit set.
+        let word = field.set(u128::MAX, 0).unwrap();
+        assert_eq!(word, !(field.max() << field.offset));
+    }
+}
+
+#[test]
+fn test_field_overflow() {
+    assert_eq!(LEVEL.set(0, 255), Ok(255 << 104));
+    assert_eq!(LEVEL.set(0, 256), Err(PackedError::FieldOverflow));
+    let wide = Field {
+        offset: 0,
+        width: 128,
+    };
+    assert_eq!(wide.set(0, u128::MAX), Ok(u128::MAX));
+}
+
+#[test]
+fn test_record_accessors() {
+    let mut record = PackedRecord::default();
+    record.set_visits(3).unwrap();
+    record.set_points(0xdead_beef).unwrap();
+    record.set_last_seen(1_700_000_000).unwrap();
+    record.set_level(42).unwrap();
+    record.set_flag(FLAG_PREMIUM, true).unwrap();
+
+    // flags 0002 | level 2a | last_seen 006553f100 | points deadbeef | visits 00000003
+    assert_eq!(record.0, 0x0002_2a00_6553_f100_dead_beef_0000_0003);
+    assert_eq!(record.visits(), 3);
+    assert_eq!(record.points(), 0xdead_beef);
+    assert_eq!(record.last_seen(), 1_700_000_000);
+    assert_eq!(record.level(), 42);
+    assert!(record.has_flag(FLAG_PREMIUM));
+    assert!(!record.has_flag(FLAG_VERIFIED));
+
+    // Past 2^40 seconds does not fit.
+    assert_eq!(
+        record.set_last_seen(1 << 40),
+        Err(PackedError::FieldOverflow)
+    );
+}
+
+// ==================== CONTRACT ====================
+
+#[test]
+fn test_visit() {
+    let s = setup();
+    s.client.visit(&s.user, &10);
+    let record = s.client.visit(&s.user, &5);
+    assert_eq!(
+        record,
+        Record {
+            visits: 2,
+            points: 15,
+            last_seen: 1_700_000_000,
+            level: 0,
+            flags: 0,
+        }
+    );
+    let raw = s.client.raw(&s.user);
+    assert_eq!(VISITS.get(raw), 2);
+    assert_eq!(POINTS.get(raw), 15);
+    assert_eq!(LAST_SEEN.get(raw), 1_700_000_000);
+}
+
+#[test]
+fn test_level_and_flags() {
+    let s = setup();
+    s.client.set_level(&s.user, &255);
+    assert_eq!(
+        s.client.try_set_level(&s.user, &256),
+        Err(Ok(PackedError::FieldOverflow))
+    );
+
+    s.client.set_flag(&s.user, &FLAG_VERIFIED, &true);
+    s.client.set_flag(&s.user, &FLAG_FROZEN, &true);
+    s.client.set_flag(&s.user, &FLAG_VERIFIED, &false);
+    assert_eq!(s.client.record(&s.user).flags, FLAG_FROZEN);
+    assert_eq!(s.client.record(&s.user).level, 255);
+    assert_eq!(FLAGS.get(s.client.raw(&s.user)), FLAG_FROZEN as u128);
+
+    for bad in [0, 3, 1 << 16] {
+        assert_eq!(
+            s.client.try_set_flag(&s.user, &bad, &true),
+            Err(Ok(PackedError::InvalidFlag)),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn test_counter_overflow() {
+    let s = setup();
+    s.client.visit(&s.user, &u32::MAX);
+    assert_eq!(
+        s.client.try_visit(&s.user, &1),
+        Err(Ok(PackedError::FieldOverflow))
+    );
+    s.client.visit_naive(&s.user, &u32::MAX);
+    assert_eq!(
+        s.client.try_visit_naive(&s.user, &1),
+        Err(Ok(PackedError::FieldOverflow))
+    );
+}
+
+/// Runs the same operations through both layouts.
+fn fill_both(s: &Setup) {
+    for points in [10, 20, 30] {
+        s.client.visit(&s.user, &points);
+        s.client.visit_naive(&s.user, &points);
+    }
+    s.client.set_level(&s.user, &7);
+    s.client.set_level_naive(&s.user, &7);
+    for flag in [FLAG_VERIFIED, FLAG_PREMIUM] {
+        s.client.set_flag(&s.user, &flag, &true);
+        s.client.set_flag_naive(&s.user, &flag, &true);
+    }
+}
+
+#[test]
+fn test_layouts_agree() {
+    let s = setup();
+    fill_both(&s);
+    let record = s.client.record(&s.user);
+    assert_eq!(record, s.client.record_naive(&s.user));
+    assert_eq!(record.visits, 3);
+    assert_eq!(record.points, 60);
+    assert_eq!(record.flags, FLAG_VERIFIED | FLAG_PREMIUM);
+}
+
+// ==================== COST BENCHMARKS ====================
+//
+// What packing changes is the number of ledger entries and their bytes:
+// each entry a call touches is charged per entry and per byte, and each
+// persistent entry pays rent. These tests measure both, plus the budget of
+// a single `visit` on an existing record.
+
+fn naive_keys(user: &Address) -> [DataKey; 5] {
+    [
+        DataKey::Visits(user.clone()),
+        DataKey::Points(user.clone()),
+        DataKey::LastSeen(user.clone()),
+        DataKey::Level(user.clone()),
+        DataKey::Flags(user.clone()),
+    ]
+}
+
+/// XDR size of a storage entry's key plus value.
+fn entry_bytes(env: &Env, key: &DataKey) -> u32 {
+    let value: Val = env.storage().persistent().get(key).unwrap();
+    key.clone().to_xdr(env).len() + value.to_xdr(env).len()
+}
+
+#[test]
+fn test_bench_entry_count_and_bytes() {
+    let s = setup();
+    fill_both(&s);
+    let (packed, naive) = s.env.as_contract(&s.client.address, || {
+        let naive = naive_keys(&s.user);
+        assert!(naive.iter().all(|k| s.env.storage().persistent().has(k)));
+        (
+            entry_bytes(&s.env, &DataKey::Packed(s.user.clone())),
+            naive.iter().map(|k| entry_bytes(&s.env, k)).sum::<u32>(),
+        )
+    });
+    // Most of each naive entry is the key, which repeats the address.
+    assert!(packed * 3 < naive, "{packed} vs {naive}");
+}
+
+/// CPU instructions and memory for one `visit` on a record that already
+/// exists, in a fresh environment.
+fn visit_cost(naive: bool) -> (u64, u64) {
+    let s = setup();
+    let visit = |points| {
+        if naive {
+            s.client.visit_naive(&s.user, &points);
+        } else {
+            s.client.visit(&s.user, &points);
+        }
+    };
+    visit(1);
+    s.env.budget().reset_default();
+    visit(2);
+    (
+        s.env.budget().cpu_instruction_cost(),
+        s.env.budget().memory_bytes_cost(),
+    )
+}
+
+#[test]
+fn test_bench_visit_budget() {
+    let (packed_cpu, packed_mem) = visit_cost(false);
+    let (naive_cpu, naive_mem) = visit_cost(true);
+    assert!(packed_cpu < naive_cpu, "{packed_cpu} vs {naive_cpu}");
+    assert!(packed_mem < naive_mem, "{packed_mem} vs {naive_mem}");
+}
+
+#[test]
+fn test_bench_read_budget() {
+    let s = setup();
+    fill_both(&s);
+    let cost = |read: &dyn Fn() -> Record| {
+        s.env.budget().reset_default();
+        read();
+        s.env.budget().cpu_instruction_cost()
+    };
+    let packed = cost(&|| s.client.record(&s.user));
+    let naive = cost(&|| s.client.record_naive(&s.user));
+    assert!(packed < naive, "{packed} vs {naive}");
+}
@@ -0,0 +1,29 @@
+//! Read-or-create for persistent entries.
+//!
+//! Use `get_or_init` only in calls that are going to write anyway. The
+//! created entry becomes part of the call's writes and rolls back with them
+//! if the call fails. Writing it again later in the same call adds no
+//! ledger entry: the host keeps one pending value per key and commits only
+//! the last one.
+//!
+//! Read-only calls should use `storage.get(key).unwrap_or_else(default)`
+//! instead. A view that creates entries would make looking a user up cost a
+//! write and start the entry's rent.
+
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+/// The value stored under `key`. If there is none, `init()` is stored there
+/// first. The flag is `true` when this call created the entry.
+pub fn get_or_init<K, V>(env: &Env, key: &K, init: impl FnOnce() -> V) -> (V, bool)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let storage = env.storage().persistent();
+    if let Some(value) = storage.get(key) {
+        return (value, false);
+    }
+    let value = init();
+    storage.set(key, &value);
+    (value, true)
+}
@@ -0,0 +1,154 @@
+//! # Lazy Initialization of Per-User Records
+//!
+//! Many contracts require a `register()` call before a user can do anything
+//! else. That costs the user an extra transaction and fee before they can
+//! do what they came for. It also makes every other function handle "not
+//! registered".
+//!
+//! This contract has no `register`. The first call that changes a user's
+//! [`Profile`] creates it with defaults:
+//!
+//! - **Writes** (`earn`, `spend`) go through `load_or_init`. It creates the
+//!   profile if there is none, then the call applies its change. The new
+//!   entry is one of the call's writes, so if the call fails the user stays
+//!   unknown.
+//! - **Reads** (`profile`, `is_member`) return the defaults for an unknown
+//!   user and write nothing.
+//!
+//! Joining has side effects that must happen exactly once: the member
+//! count, the `(joined, user)` event and the welcome credits. They live only
+//! on the init path, so no caller can forget them or run them twice. The
+//! generic helper is in [`lazy`].
+
+#![no_std]
+
+pub mod lazy;
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+/// Credits every profile starts with.
+pub const WELCOME_CREDITS: i128 = 100;
+
+/// XP needed per level above 1.
+pub const XP_PER_LEVEL: u64 = 1_000;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Profile(Address),
+    MemberCount,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Profile {
+    /// Ledger timestamp of the user's first write; 0 if they have not
+    /// joined.
+    pub joined_at: u64,
+    pub level: u32,
+    pub xp: u64,
+    pub credits: i128,
+}
+
+impl Profile {
+    /// The defaults a user starts with.
+    pub fn new(joined_at: u64) -> Self {
+        Profile {
+            joined_at,
+            level: 1,
+            xp: 0,
+            credits: WELCOME_CREDITS,
+        }
+    }
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProfileError {
+    InvalidAmount = 1,
+    InsufficientCredits = 2,
+}
+
+#[contract]
+pub struct LazyProfiles;
+
+#[contractimpl]
+impl LazyProfiles {
+    /// Add `xp` and recompute the level. Creates the profile on first use.
+    pub fn earn(env: Env, user: Address, xp: u64) -> Result<Profile, ProfileError> {
+        user.require_auth();
+        if xp == 0 {
+            return Err(ProfileError::InvalidAmount);
+        }
+        let mut profile = load_or_init(&env, &user);
+        profile.xp += xp;
+        profile.level = 1 + (profile.xp / XP_PER_LEVEL) as u32;
+        save(&env, &user, &profile);
+        Ok(profile)
+    }
+
+    /// Spend `amount` credits. A new user can spend their welcome credits
+    /// in their very first call.
+    pub fn spend(env: Env, user: Address, amount: i128) -> Result<Profile, ProfileError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(ProfileError::InvalidAmount);
+        }
+        let mut profile = load_or_init(&env, &user);
+        if amount > profile.credits {
+            return Err(ProfileError::InsufficientCredits);
+        }
+        profile.credits -= amount;
+        save(&env, &user, &profile);
+        Ok(profile)
+    }
+
+    /// `user`'s profile, or the defaults with `joined_at == 0` if they have
+    /// not joined. Never writes.
+    pub fn profile(env: Env, user: Address) -> Profile {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Profile(user))
+            .unwrap_or_else(|| Profile::new(0))
+    }
+
+    pub fn is_member(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Profile(user))
+    }
+
+    pub fn member_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MemberCount)
+            .unwrap_or(0)
+    }
+}
+
+/// `user`'s profile, created with defaults if this is their first call.
+/// Joining counts the member and emits `(joined, user)` with the welcome
+/// credits.
+fn load_or_init(env: &Env, user: &Address) -> Profile {
+    let key = DataKey::Profile(user.clone());
+    let (profile, created) =
+        lazy::get_or_init(env, &key, || Profile::new(env.ledger().timestamp()));
+    if created {
+        let count = LazyProfiles::member_count(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::MemberCount, &(count + 1));
+        env.events()
+            .publish((symbol_short!("joined"), user.clone()), WELCOME_CREDITS);
+    }
+    profile
+}
+
+fn save(env: &Env, user: &Address, profile: &Profile) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Profile(user.clone()), profile);
+}
+
+mod test;
@@ -0,0 +1,158 @@
+#![cfg(test)]
+
+use super::*;
+use core::cell::Cell;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    client: LazyProfilesClient<'static>,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let client = LazyProfilesClient::new(&env, &env.register_contract(None, LazyProfiles));
+    Setup {
+        user: Address::generate(&env),
+        env,
+        client,
+    }
+}
+
+/// The only events so far are these users joining, in order.
+fn assert_joined(s: &Setup, users: &[&Address]) {
+    let mut expected = vec![&s.env];
+    for user in users {
+        expected.push_back((
+            s.client.address.clone(),
+            (symbol_short!("joined"), (*user).clone()).into_val(&s.env),
+            WELCOME_CREDITS.into_val(&s.env),
+        ));
+    }
+    assert_eq!(s.env.events().all(), expected);
+}
+
+// ==================== COLD START ====================
+
+#[test]
+fn test_unknown_user_reads_defaults() {
+    let s = setup();
+    assert_eq!(s.client.profile(&s.user), Profile::new(0));
+    assert!(!s.client.is_member(&s.user));
+    assert_eq!(s.client.member_count(), 0);
+    // Reading wrote nothing and announced nothing.
+    assert_joined(&s, &[]);
+    let stored = s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .has(&DataKey::Profile(s.user.clone()))
+    });
+    assert!(!stored);
+}
+
+#[test]
+fn test_first_write_creates_profile() {
+    let s = setup();
+    let profile = s.client.earn(&s.user, &250);
+    assert_joined(&s, &[&s.user]);
+    assert_eq!(
+        profile,
+        Profile {
+            joined_at: 1_000,
+            level: 1,
+            xp: 250,
+            credits: WELCOME_CREDITS,
+        }
+    );
+    assert_eq!(s.client.profile(&s.user), profile);
+    assert!(s.client.is_member(&s.user));
+    assert_eq!(s.client.member_count(), 1);
+}
+
+#[test]
+fn test_first_call_can_spend_welcome_credits() {
+    let s = setup();
+    let profile = s.client.spend(&s.user, &WELCOME_CREDITS);
+    assert_eq!(profile.credits, 0);
+    assert_eq!(profile.joined_at, 1_000);
+    assert_eq!(s.client.member_count(), 1);
+}
+
+#[test]
+fn test_failed_first_call_leaves_user_unknown() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_spend(&s.user, &(WELCOME_CREDITS + 1)),
+        Err(Ok(ProfileError::InsufficientCredits))
+    );
+    assert_eq!(
+        s.client.try_earn(&s.user, &0),
+        Err(Ok(ProfileError::InvalidAmount))
+    );
+    assert!(!s.client.is_member(&s.user));
+    assert_eq!(s.client.member_count(), 0);
+}
+
+// ==================== WARM PATH ====================
+
+#[test]
+fn test_init_runs_once() {
+    let s = setup();
+    s.client.earn(&s.user, &10);
+    s.env.ledger().with_mut(|l| l.timestamp = 5_000);
+    s.client.spend(&s.user, &30);
+    // No second `joined` event from the warm call.
+    assert_joined(&s, &[&s.user]);
+
+    let profile = s.client.earn(&s.user, &2_000);
+    assert_eq!(
+        profile,
+        Profile {
+            joined_at: 1_000,
+            level: 3,
+            xp: 2_010,
+            credits: WELCOME_CREDITS - 30,
+        }
+    );
+    assert_eq!(s.client.member_count(), 1);
+}
+
+#[test]
+fn test_users_join_independently() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    s.client.earn(&s.user, &1);
+    s.env.ledger().with_mut(|l| l.timestamp = 2_000);
+    s.client.earn(&other, &1);
+    assert_joined(&s, &[&s.user, &other]);
+    assert_eq!(s.client.profile(&s.user).joined_at, 1_000);
+    assert_eq!(s.client.profile(&other).joined_at, 2_000);
+    assert_eq!(s.client.member_count(), 2);
+}
+
+// ==================== HELPER ====================
+
+#[test]
+fn test_get_or_init_calls_init_once() {
+    let s = setup();
+    let calls = Cell::new(0);
+    let init = || {
+        calls.set(calls.get() + 1);
+        7u32
+    };
+    s.env.as_contract(&s.client.address, || {
+        let key = DataKey::Profile(s.user.clone());
+        assert_eq!(lazy::get_or_init(&s.env, &key, init), (7, true));
+        assert_eq!(lazy::get_or_init(&s.env, &key, init), (7, false));
+        s.env.storage().persistent().set(&key, &9u32);
+        assert_eq!(lazy::get_or_init(&s.env, &key, init), (9, false));
+    });
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,201 @@
+//! # Idempotency Keys for Safe Retries
+//!
+//! A client that submits a transaction and then times out cannot tell
+//! whether it landed. If it resubmits and both land, a plain `withdraw`
+//! pays out twice. The usual fix from web APIs works on-chain too: the
+//! client picks a random `op_key` once per logical operation, and reuses it
+//! on every retry of that operation.
+//!
+//! The first call with a key applies the operation. It stores a [`Receipt`]
+//! under `DataKey::Op(user, op_key)` in the same call, so the receipt and
+//! the operation land together or not at all. Any later call with the same
+//! key returns that receipt and changes nothing. The same key with
+//! different arguments fails with `KeyReused`: the client has a bug, and
+//! guessing which operation it meant would be worse.
+//!
+//! ## Why temporary storage
+//!
+//! A key only matters while a retry of its operation can still land. After
+//! that the receipt is dead weight. Temporary entries are the cheapest to
+//! write and simply disappear after their TTL, [`OP_KEY_LEDGERS`], with no
+//! rent and no cleanup. Clients must make retries expire before the key
+//! does: set the transaction's max ledger bound well inside the window.
+//!
+//! Keys are scoped to the user, who has to authorize the call. Nobody else
+//! can use up, or read back, someone else's key.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env,
+};
+
+/// Ledgers an operation key is remembered for, about one day.
+pub const OP_KEY_LEDGERS: u32 = 17_280;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Token,
+    Balance(Address),
+    /// Receipt of `user`'s operation with this key. Temporary.
+    Op(Address, BytesN<32>),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Deposit(i128),
+    Withdraw(i128),
+}
+
+/// What an operation did, returned on the first call and every retry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub op: Operation,
+    /// The user's vault balance right after the operation.
+    pub balance: i128,
+    /// Ledger sequence the operation was applied in.
+    pub ledger: u32,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum IdempotencyError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    /// The key was already used for a different operation.
+    KeyReused = 5,
+}
+
+#[contract]
+pub struct IdempotentVault;
+
+#[contractimpl]
+impl IdempotentVault {
+    pub fn initialize(env: Env, token: Address) -> Result<(), IdempotencyError> {
+        if env.storage().instance().has(&DataKey::Token) {
+            return Err(IdempotencyError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    /// Move `amount` of the token into the vault, once per `op_key`.
+    ///
+    /// Emits `(deposit, user)` with the amount when applied, not on retries.
+    pub fn deposit(
+        env: Env,
+        user: Address,
+        amount: i128,
+        op_key: BytesN<32>,
+    ) -> Result<Receipt, IdempotencyError> {
+        user.require_auth();
+        apply_once(&env, &user, op_key, Operation::Deposit(amount))
+    }
+
+    /// Pay `amount` out of the vault, once per `op_key`.
+    ///
+    /// Emits `(withdraw, user)` with the amount when applied, not on retries.
+    pub fn withdraw(
+        env: Env,
+        user: Address,
+        amount: i128,
+        op_key: BytesN<32>,
+    ) -> Result<Receipt, IdempotencyError> {
+        user.require_auth();
+        apply_once(&env, &user, op_key, Operation::Withdraw(amount))
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    /// The receipt stored for `op_key`, if it was applied and has not
+    /// expired. Lets a client check the outcome without retrying.
+    pub fn receipt(env: Env, user: Address, op_key: BytesN<32>) -> Option<Receipt> {
+        env.storage().temporary().get(&DataKey::Op(user, op_key))
+    }
+}
+
+/// Apply `op` unless `op_key` already has a receipt, then return the
+/// receipt. Errors roll the call back, so a failed operation leaves the key
+/// unused and a retry runs it again.
+fn apply_once(
+    env: &Env,
+    user: &Address,
+    op_key: BytesN<32>,
+    op: Operation,
+) -> Result<Receipt, IdempotencyError> {
+    let key = DataKey::Op(user.clone(), op_key);
+    if let Some(receipt) = env.storage().temporary().get::<_, Receipt>(&key) {
+        if receipt.op != op {
+            return Err(IdempotencyError::KeyReused);
+        }
+        return Ok(receipt);
+    }
+
+    let balance = apply(env, user, &op)?;
+    let receipt = Receipt {
+        op,
+        balance,
+        ledger: env.ledger().sequence(),
+    };
+    env.storage().temporary().set(&key, &receipt);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, OP_KEY_LEDGERS, OP_KEY_LEDGERS);
+    Ok(receipt)
+}
+
+/// Apply `op` and return the user's new balance.
+fn apply(env: &Env, user: &Address, op: &Operation) -> Result<i128, IdempotencyError> {
+    let token: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(IdempotencyError::NotInitialized)?;
+    let token = token::Client::new(env, &token);
+    let vault = env.current_contract_address();
+    let balance = IdempotentVault::balance(env.clone(), user.clone());
+
+    let balance = match *op {
+        Operation::Deposit(amount) => {
+            check_amount(amount)?;
+            token.transfer(user, &vault, &amount);
+            env.events()
+                .publish((symbol_short!("deposit"), user.clone()), amount);
+            balance + amount
+        }
+        Operation::Withdraw(amount) => {
+            check_amount(amount)?;
+            if amount > balance {
+                return Err(IdempotencyError::InsufficientBalance);
+            }
+            token.transfer(&vault, user, &amount);
+            env.events()
+                .publish((symbol_short!("withdraw"), user.clone()), amount);
+            balance - amount
+        }
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(user.clone()), &balance);
+    Ok(balance)
+}
+
+fn check_amount(amount: i128) -> Result<(), IdempotencyError> {
+    if amount <= 0 {
+        return Err(IdempotencyError::InvalidAmount);
+    }
+    Ok(())
+}
+
+mod test;
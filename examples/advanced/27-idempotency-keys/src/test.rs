@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    IntoVal,
+};
+
+struct Setup<'a> {
+    env: Env,
+    vault: IdempotentVaultClient<'a>,
+    token: TokenClient<'a>,
+    user: Address,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| {
+        l.sequence_number = 100;
+        // Keep the contracts and balances alive past the key window, so the
+        // only entries that expire are the receipts.
+        l.min_persistent_entry_ttl = 2 * OP_KEY_LEDGERS;
+    });
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let vault = IdempotentVaultClient::new(&env, &env.register_contract(None, IdempotentVault));
+    vault.initialize(&token_id);
+
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&user, &1_000);
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        vault,
+        user,
+    }
+}
+
+fn op_key(env: &Env, n: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[n; 32])
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|l| l.sequence_number += ledgers);
+}
+
+/// Number of events the vault itself has emitted.
+fn vault_events(s: &Setup) -> u32 {
+    s.env
+        .events()
+        .all()
+        .iter()
+        .filter(|(id, _, _)| *id == s.vault.address)
+        .count() as u32
+}
+
+// ==================== DUPLICATE SUBMISSIONS ====================
+
+#[test]
+fn test_first_call_applies() {
+    let s = setup();
+    let key = op_key(&s.env, 1);
+    let receipt = s.vault.deposit(&s.user, &300, &key);
+    assert_eq!(
+        receipt,
+        Receipt {
+            op: Operation::Deposit(300),
+            balance: 300,
+            ledger: 100,
+        }
+    );
+    assert_eq!(s.vault.receipt(&s.user, &key), Some(receipt));
+    assert_eq!(s.token.balance(&s.user), 700);
+    let (_, topics, amount) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("deposit"), s.user.clone()).into_val(&s.env)
+    );
+    let amount: i128 = amount.into_val(&s.env);
+    assert_eq!(amount, 300);
+}
+
+/// The client timed out and resubmitted; both transactions landed.
+#[test]
+fn test_duplicate_deposit_applies_once() {
+    let s = setup();
+    let key = op_key(&s.env, 1);
+    let first = s.vault.deposit(&s.user, &300, &key);
+    advance_ledgers(&s.env, 5);
+    let retry = s.vault.deposit(&s.user, &300, &key);
+
+    // The retry gets the original receipt, from the original ledger.
+    assert_eq!(retry, first);
+    assert_eq!(s.vault.balance(&s.user), 300);
+    assert_eq!(s.token.balance(&s.user), 700);
+    assert_eq!(vault_events(&s), 1);
+}
+
+#[test]
+fn test_duplicate_withdraw_pays_once() {
+    let s = setup();
+    s.vault.deposit(&s.user, &500, &op_key(&s.env, 1));
+    let key = op_key(&s.env, 2);
+    for _ in 0..3 {
+        let receipt = s.vault.withdraw(&s.user, &200, &key);
+        assert_eq!(receipt.balance, 300);
+    }
+    assert_eq!(s.vault.balance(&s.user), 300);
+    assert_eq!(s.token.balance(&s.user), 700);
+    assert_eq!(vault_events(&s), 2);
+}
+
+#[test]
+fn test_new_key_applies_again() {
+    let s = setup();
+    s.vault.deposit(&s.user, &100, &op_key(&s.env, 1));
+    s.vault.deposit(&s.user, &100, &op_key(&s.env, 2));
+    assert_eq!(s.vault.balance(&s.user), 200);
+}
+
+#[test]
+fn test_key_reused_for_different_operation() {
+    let s = setup();
+    let key = op_key(&s.env, 1);
+    s.vault.deposit(&s.user, &300, &key);
+    assert_eq!(
+        s.vault.try_deposit(&s.user, &301, &key),
+        Err(Ok(IdempotencyError::KeyReused))
+    );
+    assert_eq!(
+        s.vault.try_withdraw(&s.user, &300, &key),
+        Err(Ok(IdempotencyError::KeyReused))
+    );
+    assert_eq!(s.vault.balance(&s.user), 300);
+}
+
+#[test]
+fn test_failed_operation_leaves_key_unused() {
+    let s = setup();
+    let key = op_key(&s.env, 1);
+    assert_eq!(
+        s.vault.try_withdraw(&s.user, &100, &key),
+        Err(Ok(IdempotencyError::InsufficientBalance))
+    );
+    assert_eq!(s.vault.receipt(&s.user, &key), None);
+
+    // Once it can succeed, the retry with the same key goes through.
+    s.vault.deposit(&s.user, &100, &op_key(&s.env, 2));
+    assert_eq!(s.vault.withdraw(&s.user, &100, &key).balance, 0);
+}
+
+#[test]
+fn test_keys_are_scoped_to_user() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&other, &1_000);
+    let key = op_key(&s.env, 1);
+
+    s.vault.deposit(&s.user, &100, &key);
+    s.vault.deposit(&other, &250, &key);
+    assert_eq!(s.vault.balance(&other), 250);
+    assert_eq!(s.vault.receipt(&s.user, &key).unwrap().balance, 100);
+}
+
+// ==================== KEY WINDOW ====================
+
+#[test]
+fn test_key_remembered_for_window() {
+    let s = setup();
+    let key = op_key(&s.env, 1);
+    s.vault.deposit(&s.user, &300, &key);
+
+    advance_ledgers(&s.env, OP_KEY_LEDGERS);
+    s.vault.deposit(&s.user, &300, &key);
+    assert_eq!(s.vault.balance(&s.user), 300);
+}
+
+/// Past the window the key is gone, so a retry would apply again. Clients
+/// must make their transactions expire first.
+#[test]
+fn test_key_expires_after_window() {
+    let s = setup();
+    let key = op_key(&s.env, 1);
+    s.vault.deposit(&s.user, &300, &key);
+
+    advance_ledgers(&s.env, OP_KEY_LEDGERS + 1);
+    assert_eq!(s.vault.receipt(&s.user, &key), None);
+    s.vault.deposit(&s.user, &300, &key);
+    assert_eq!(s.vault.balance(&s.user), 600);
+}
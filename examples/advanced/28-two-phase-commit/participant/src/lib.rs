@@ -0,0 +1,178 @@
+//! Participant ledger for the two-phase commit example.
+//!
+//! A plain balance ledger whose transfers can also run in two phases for a
+//! coordinator. `prepare` is the participant's vote. It checks the transfer
+//! and moves the amount out of the sender's balance into a hold, so nothing
+//! that happens afterwards can make the transfer impossible. Once `prepare`
+//! has succeeded, neither `commit` (pay the hold to the recipient) nor
+//! `abort` (return it to the sender) can fail.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+
+/// An amount taken from `from` by `prepare`, waiting for the outcome.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hold {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// The only address allowed to prepare, commit and abort.
+    Coordinator,
+    Balance(Address),
+    Hold(u64),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ParticipantError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    /// `prepare` was already called for this transaction.
+    AlreadyPrepared = 5,
+    /// Nothing is held for this transaction.
+    NoHold = 6,
+}
+
+#[contract]
+pub struct Participant;
+
+#[contractimpl]
+impl Participant {
+    pub fn init(env: Env, admin: Address, coordinator: Address) -> Result<(), ParticipantError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ParticipantError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Coordinator, &coordinator);
+        Ok(())
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ParticipantError> {
+        read_address(&env, &DataKey::Admin)?.require_auth();
+        check_amount(amount)?;
+        add(&env, &to, amount);
+        Ok(())
+    }
+
+    /// An ordinary one-phase transfer. Held amounts cannot be spent.
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ParticipantError> {
+        from.require_auth();
+        check_amount(amount)?;
+        take(&env, &from, amount)?;
+        add(&env, &to, amount);
+        Ok(())
+    }
+
+    /// Vote yes on moving `amount` from `from` to `to` in `tx_id`, by
+    /// holding it. Needs the coordinator's and `from`'s authorization.
+    pub fn prepare(
+        env: Env,
+        tx_id: u64,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ParticipantError> {
+        read_address(&env, &DataKey::Coordinator)?.require_auth();
+        from.require_auth();
+        check_amount(amount)?;
+        let key = DataKey::Hold(tx_id);
+        if env.storage().persistent().has(&key) {
+            return Err(ParticipantError::AlreadyPrepared);
+        }
+        take(&env, &from, amount)?;
+        env.storage()
+            .persistent()
+            .set(&key, &Hold { from, to, amount });
+        Ok(())
+    }
+
+    /// Pay the hold for `tx_id` to its recipient.
+    pub fn commit(env: Env, tx_id: u64) -> Result<(), ParticipantError> {
+        let hold = release(&env, tx_id)?;
+        add(&env, &hold.to, hold.amount);
+        Ok(())
+    }
+
+    /// Return the hold for `tx_id` to its sender.
+    pub fn abort(env: Env, tx_id: u64) -> Result<(), ParticipantError> {
+        let hold = release(&env, tx_id)?;
+        add(&env, &hold.from, hold.amount);
+        Ok(())
+    }
+
+    pub fn balance(env: Env, owner: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(owner))
+            .unwrap_or(0)
+    }
+
+    pub fn hold(env: Env, tx_id: u64) -> Option<Hold> {
+        env.storage().persistent().get(&DataKey::Hold(tx_id))
+    }
+}
+
+fn read_address(env: &Env, key: &DataKey) -> Result<Address, ParticipantError> {
+    env.storage()
+        .instance()
+        .get(key)
+        .ok_or(ParticipantError::NotInitialized)
+}
+
+fn check_amount(amount: i128) -> Result<(), ParticipantError> {
+    if amount <= 0 {
+        return Err(ParticipantError::InvalidAmount);
+    }
+    Ok(())
+}
+
+fn add(env: &Env, owner: &Address, amount: i128) {
+    let balance = Participant::balance(env.clone(), owner.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &(balance + amount));
+}
+
+fn take(env: &Env, owner: &Address, amount: i128) -> Result<(), ParticipantError> {
+    let balance = Participant::balance(env.clone(), owner.clone());
+    if amount > balance {
+        return Err(ParticipantError::InsufficientBalance);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &(balance - amount));
+    Ok(())
+}
+
+/// Remove and return the hold for `tx_id`. Coordinator only.
+fn release(env: &Env, tx_id: u64) -> Result<Hold, ParticipantError> {
+    read_address(env, &DataKey::Coordinator)?.require_auth();
+    let key = DataKey::Hold(tx_id);
+    let hold = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(ParticipantError::NoHold)?;
+    env.storage().persistent().remove(&key);
+    Ok(hold)
+}
+
+mod test;
@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::Address as _;
+
+struct Setup {
+    env: Env,
+    client: ParticipantClient<'static>,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ParticipantClient::new(&env, &env.register_contract(None, Participant));
+    client.init(&Address::generate(&env), &Address::generate(&env));
+    let alice = Address::generate(&env);
+    client.mint(&alice, &100);
+    Setup {
+        bob: Address::generate(&env),
+        env,
+        client,
+        alice,
+    }
+}
+
+#[test]
+fn test_prepare_holds_amount() {
+    let s = setup();
+    s.client.prepare(&1, &s.alice, &s.bob, &60);
+    assert_eq!(s.client.balance(&s.alice), 40);
+    assert_eq!(s.client.balance(&s.bob), 0);
+    assert_eq!(
+        s.client.hold(&1),
+        Some(Hold {
+            from: s.alice.clone(),
+            to: s.bob.clone(),
+            amount: 60,
+        })
+    );
+
+    // Held funds cannot be spent a second time.
+    assert_eq!(
+        s.client.try_transfer(&s.alice, &s.bob, &50),
+        Err(Ok(ParticipantError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.client.try_prepare(&1, &s.alice, &s.bob, &10),
+        Err(Ok(ParticipantError::AlreadyPrepared))
+    );
+}
+
+#[test]
+fn test_prepare_votes_no() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_prepare(&1, &s.alice, &s.bob, &101),
+        Err(Ok(ParticipantError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.client.try_prepare(&1, &s.alice, &s.bob, &0),
+        Err(Ok(ParticipantError::InvalidAmount))
+    );
+    assert_eq!(s.client.hold(&1), None);
+}
+
+#[test]
+fn test_commit_and_abort_release_hold() {
+    let s = setup();
+    s.client.prepare(&1, &s.alice, &s.bob, &60);
+    s.client.commit(&1);
+    assert_eq!(s.client.balance(&s.bob), 60);
+    assert_eq!(s.client.hold(&1), None);
+
+    s.client.prepare(&2, &s.alice, &s.bob, &30);
+    s.client.abort(&2);
+    assert_eq!(s.client.balance(&s.alice), 40);
+    assert_eq!(s.client.balance(&s.bob), 60);
+
+    for tx_id in [1, 2, 3] {
+        assert_eq!(
+            s.client.try_commit(&tx_id),
+            Err(Ok(ParticipantError::NoHold))
+        );
+        assert_eq!(
+            s.client.try_abort(&tx_id),
+            Err(Ok(ParticipantError::NoHold))
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_only_coordinator_commits() {
+    let s = setup();
+    s.client.prepare(&1, &s.alice, &s.bob, &60);
+    s.env.set_auths(&[]);
+    s.client.commit(&1);
+}
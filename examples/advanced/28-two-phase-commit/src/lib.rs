@@ -0,0 +1,258 @@
+//! # Two-Phase Commit Across Contracts
+//!
+//! Within one Soroban transaction, cross-contract calls are already atomic.
+//! If any call fails, every change in the transaction is rolled back. The
+//! hard case is a change that cannot fit in one transaction. Here each leg
+//! of a cross-ledger swap needs its own sender's signature, and the senders
+//! sign at different times. Alice moves gold on one ledger, Bob moves
+//! silver on another, and neither should move unless both do.
+//!
+//! The coordinator ties separate transactions into one all-or-nothing
+//! outcome:
+//!
+//! ```text
+//! begin(legs, deadline)        → tx_id                      (anyone)
+//! prepare(tx_id, 0)            ledger A holds Alice's gold  (Alice signs)
+//! prepare(tx_id, 1)            ledger B holds Bob's silver  (Bob signs)
+//! commit(tx_id)                every hold paid out          (anyone, once all prepared)
+//! abort(tx_id)                 every hold returned          (anyone, after the deadline,
+//!                                                            if not all prepared)
+//! ```
+//!
+//! ## Why it is safe
+//!
+//! - **Prepare removes the failure modes.** A participant's `prepare` checks
+//!   everything and moves the amount into a hold. After that, its `commit`
+//!   and `abort` cannot fail, so the decision is all that remains.
+//! - **Exactly one decision.** `commit` needs every leg prepared. `abort`
+//!   needs at least one leg unprepared after the deadline, and nothing can
+//!   be prepared after the deadline. The two conditions never hold at
+//!   once.
+//! - **The decision and its effects land together.** `commit` and `abort`
+//!   record the new status and call every participant in the same
+//!   transaction. If any call fails, none of it happens.
+//!
+//! The cost is liveness. A party who prepared has funds locked until the
+//! others prepare or the deadline passes. Keep deadlines short.
+//!
+//! The participant ledger is in `participant/`. This crate declares the
+//! calls it makes with `#[contractclient]`. Each participant accepts
+//! prepare, commit and abort only from its configured coordinator: a
+//! contract's own address is authorized for calls it makes directly.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    Env, Vec,
+};
+
+/// The part of the participant interface the coordinator uses.
+#[contractclient(name = "ParticipantClient")]
+pub trait ParticipantInterface {
+    fn prepare(env: Env, tx_id: u64, from: Address, to: Address, amount: i128);
+    fn commit(env: Env, tx_id: u64);
+    fn abort(env: Env, tx_id: u64);
+}
+
+/// One transfer on one participant ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Leg {
+    pub participant: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Pending,
+    Committed,
+    Aborted,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    pub legs: Vec<Leg>,
+    /// `prepared[i]` is set once leg `i`'s participant has voted yes.
+    pub prepared: Vec<bool>,
+    /// Ledger timestamp from which no leg can be prepared and an
+    /// unfinished transaction can be aborted.
+    pub deadline: u64,
+    pub status: Status,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextId,
+    Tx(u64),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CoordinatorError {
+    /// No legs, or two legs on the same participant.
+    InvalidLegs = 1,
+    InvalidDeadline = 2,
+    UnknownTx = 3,
+    UnknownLeg = 4,
+    /// Already committed or aborted.
+    NotPending = 5,
+    DeadlinePassed = 6,
+    AlreadyPrepared = 7,
+    /// The participant's `prepare` failed: it voted no.
+    PrepareRefused = 8,
+    /// `commit` before every leg is prepared.
+    NotAllPrepared = 9,
+    /// `abort` once every leg is prepared: the outcome is commit.
+    AllPrepared = 10,
+    DeadlineNotReached = 11,
+}
+
+#[contract]
+pub struct Coordinator;
+
+#[contractimpl]
+impl Coordinator {
+    /// Propose a transaction. Moves nothing: each leg moves only when its
+    /// sender authorizes `prepare`.
+    ///
+    /// Emits `(begin, tx_id)` with the deadline.
+    pub fn begin(env: Env, legs: Vec<Leg>, deadline: u64) -> Result<u64, CoordinatorError> {
+        if legs.is_empty() {
+            return Err(CoordinatorError::InvalidLegs);
+        }
+        for (i, leg) in legs.iter().enumerate() {
+            if legs
+                .iter()
+                .skip(i + 1)
+                .any(|other| other.participant == leg.participant)
+            {
+                return Err(CoordinatorError::InvalidLegs);
+            }
+        }
+        if deadline <= env.ledger().timestamp() {
+            return Err(CoordinatorError::InvalidDeadline);
+        }
+
+        let tx_id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(tx_id + 1));
+        let mut prepared = Vec::new(&env);
+        for _ in legs.iter() {
+            prepared.push_back(false);
+        }
+        save(
+            &env,
+            tx_id,
+            &Transaction {
+                legs,
+                prepared,
+                deadline,
+                status: Status::Pending,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("begin"), tx_id), deadline);
+        Ok(tx_id)
+    }
+
+    /// Ask leg `leg`'s participant to vote. The leg's sender signs this
+    /// call, and the signature covers the participant's `prepare` beneath
+    /// it.
+    ///
+    /// Emits `(prepared, tx_id)` with the leg index.
+    pub fn prepare(env: Env, tx_id: u64, leg: u32) -> Result<(), CoordinatorError> {
+        let mut tx = load(&env, tx_id)?;
+        if tx.status != Status::Pending {
+            return Err(CoordinatorError::NotPending);
+        }
+        if env.ledger().timestamp() >= tx.deadline {
+            return Err(CoordinatorError::DeadlinePassed);
+        }
+        let details = tx.legs.get(leg).ok_or(CoordinatorError::UnknownLeg)?;
+        if tx.prepared.get_unchecked(leg) {
+            return Err(CoordinatorError::AlreadyPrepared);
+        }
+        details.from.require_auth();
+
+        let voted = ParticipantClient::new(&env, &details.participant).try_prepare(
+            &tx_id,
+            &details.from,
+            &details.to,
+            &details.amount,
+        );
+        if !matches!(voted, Ok(Ok(()))) {
+            return Err(CoordinatorError::PrepareRefused);
+        }
+        tx.prepared.set(leg, true);
+        save(&env, tx_id, &tx);
+        env.events()
+            .publish((symbol_short!("prepared"), tx_id), leg);
+        Ok(())
+    }
+
+    /// Pay out every hold. Anyone may call it once every leg is prepared.
+    ///
+    /// Emits `(commit, tx_id)`.
+    pub fn commit(env: Env, tx_id: u64) -> Result<(), CoordinatorError> {
+        let mut tx = load(&env, tx_id)?;
+        if tx.status != Status::Pending {
+            return Err(CoordinatorError::NotPending);
+        }
+        if tx.prepared.contains(false) {
+            return Err(CoordinatorError::NotAllPrepared);
+        }
+        tx.status = Status::Committed;
+        save(&env, tx_id, &tx);
+        for leg in tx.legs.iter() {
+            ParticipantClient::new(&env, &leg.participant).commit(&tx_id);
+        }
+        env.events().publish((symbol_short!("commit"), tx_id), ());
+        Ok(())
+    }
+
+    /// Return every hold. Anyone may call it after the deadline if some
+    /// leg never prepared.
+    ///
+    /// Emits `(abort, tx_id)`.
+    pub fn abort(env: Env, tx_id: u64) -> Result<(), CoordinatorError> {
+        let mut tx = load(&env, tx_id)?;
+        if tx.status != Status::Pending {
+            return Err(CoordinatorError::NotPending);
+        }
+        if !tx.prepared.contains(false) {
+            return Err(CoordinatorError::AllPrepared);
+        }
+        if env.ledger().timestamp() < tx.deadline {
+            return Err(CoordinatorError::DeadlineNotReached);
+        }
+        tx.status = Status::Aborted;
+        save(&env, tx_id, &tx);
+        for (leg, prepared) in tx.legs.iter().zip(tx.prepared.iter()) {
+            if prepared {
+                ParticipantClient::new(&env, &leg.participant).abort(&tx_id);
+            }
+        }
+        env.events().publish((symbol_short!("abort"), tx_id), ());
+        Ok(())
+    }
+
+    pub fn transaction(env: Env, tx_id: u64) -> Option<Transaction> {
+        env.storage().persistent().get(&DataKey::Tx(tx_id))
+    }
+}
+
+fn load(env: &Env, tx_id: u64) -> Result<Transaction, CoordinatorError> {
+    Coordinator::transaction(env.clone(), tx_id).ok_or(CoordinatorError::UnknownTx)
+}
+
+fn save(env: &Env, tx_id: u64, tx: &Transaction) {
+    env.storage().persistent().set(&DataKey::Tx(tx_id), tx);
+}
+
+mod test;
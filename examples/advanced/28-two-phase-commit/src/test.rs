@@ -0,0 +1,257 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger},
+    vec, IntoVal, Symbol,
+};
+use two_phase_participant::{Hold, Participant, ParticipantClient as LedgerClient};
+
+const DEADLINE: u64 = 1_000 + 3_600;
+
+struct Setup<'a> {
+    env: Env,
+    coordinator: CoordinatorClient<'a>,
+    gold: LedgerClient<'a>,
+    silver: LedgerClient<'a>,
+    alice: Address,
+    bob: Address,
+}
+
+/// Alice holds 100 gold, Bob holds 50 silver. Both ledgers trust the
+/// coordinator.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let coordinator = CoordinatorClient::new(&env, &env.register_contract(None, Coordinator));
+    let ledger = || {
+        let client = LedgerClient::new(&env, &env.register_contract(None, Participant));
+        client.init(&Address::generate(&env), &coordinator.address);
+        client
+    };
+    let gold = ledger();
+    let silver = ledger();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    gold.mint(&alice, &100);
+    silver.mint(&bob, &50);
+    Setup {
+        env,
+        coordinator,
+        gold,
+        silver,
+        alice,
+        bob,
+    }
+}
+
+/// Begin the swap: Alice's 100 gold for Bob's 50 silver.
+fn begin_swap(s: &Setup, gold_amount: i128) -> u64 {
+    let legs = vec![
+        &s.env,
+        Leg {
+            participant: s.gold.address.clone(),
+            from: s.alice.clone(),
+            to: s.bob.clone(),
+            amount: gold_amount,
+        },
+        Leg {
+            participant: s.silver.address.clone(),
+            from: s.bob.clone(),
+            to: s.alice.clone(),
+            amount: 50,
+        },
+    ];
+    s.coordinator.begin(&legs, &DEADLINE)
+}
+
+fn past_deadline(env: &Env) {
+    env.ledger().with_mut(|l| l.timestamp = DEADLINE);
+}
+
+fn status(s: &Setup, tx_id: u64) -> Status {
+    s.coordinator.transaction(&tx_id).unwrap().status
+}
+
+// ==================== COMMIT ====================
+
+/// Each leg is prepared in its own transaction, signed by its own sender.
+#[test]
+fn test_swap_commits_across_transactions() {
+    let s = setup();
+    let tx_id = begin_swap(&s, 100);
+
+    s.coordinator.prepare(&tx_id, &0);
+    assert_eq!(
+        s.gold.hold(&tx_id),
+        Some(Hold {
+            from: s.alice.clone(),
+            to: s.bob.clone(),
+            amount: 100,
+        })
+    );
+    assert_eq!(s.gold.balance(&s.alice), 0);
+    assert_eq!(
+        s.coordinator.try_commit(&tx_id),
+        Err(Ok(CoordinatorError::NotAllPrepared))
+    );
+
+    s.coordinator.prepare(&tx_id, &1);
+    s.coordinator.commit(&tx_id);
+    assert_eq!(status(&s, tx_id), Status::Committed);
+    assert_eq!(s.gold.balance(&s.bob), 100);
+    assert_eq!(s.silver.balance(&s.alice), 50);
+    assert_eq!(s.silver.balance(&s.bob), 0);
+    assert_eq!(s.gold.hold(&tx_id), None);
+    assert_eq!(
+        s.coordinator.try_commit(&tx_id),
+        Err(Ok(CoordinatorError::NotPending))
+    );
+}
+
+/// The sender signs the coordinator's `prepare`, and that signature covers
+/// their ledger's `prepare` beneath it. The coordinator needs no signature:
+/// a contract authorizes the calls it makes.
+#[test]
+fn test_prepare_is_authorized_by_sender() {
+    let s = setup();
+    let tx_id = begin_swap(&s, 100);
+    s.coordinator.prepare(&tx_id, &0);
+
+    assert_eq!(
+        s.env.auths(),
+        std::vec![(
+            s.alice.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    s.coordinator.address.clone(),
+                    Symbol::new(&s.env, "prepare"),
+                    (tx_id, 0u32).into_val(&s.env),
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        s.gold.address.clone(),
+                        Symbol::new(&s.env, "prepare"),
+                        (tx_id, s.alice.clone(), s.bob.clone(), 100i128).into_val(&s.env),
+                    )),
+                    sub_invocations: std::vec![],
+                }],
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_cannot_abort_once_all_prepared() {
+    let s = setup();
+    let tx_id = begin_swap(&s, 100);
+    s.coordinator.prepare(&tx_id, &0);
+    s.coordinator.prepare(&tx_id, &1);
+
+    // The deadline only limits preparing. Once everyone voted yes, the
+    // outcome is commit, whenever someone calls it.
+    past_deadline(&s.env);
+    assert_eq!(
+        s.coordinator.try_abort(&tx_id),
+        Err(Ok(CoordinatorError::AllPrepared))
+    );
+    s.coordinator.commit(&tx_id);
+    assert_eq!(s.silver.balance(&s.alice), 50);
+}
+
+// ==================== ABORT ====================
+
+#[test]
+fn test_refused_vote_leaves_leg_unprepared() {
+    let s = setup();
+    // Alice does not have 150 gold, so her ledger votes no.
+    let tx_id = begin_swap(&s, 150);
+    assert_eq!(
+        s.coordinator.try_prepare(&tx_id, &0),
+        Err(Ok(CoordinatorError::PrepareRefused))
+    );
+    let tx = s.coordinator.transaction(&tx_id).unwrap();
+    assert_eq!(tx.prepared, vec![&s.env, false, false]);
+    assert_eq!(s.gold.hold(&tx_id), None);
+}
+
+#[test]
+fn test_abort_after_deadline_returns_holds() {
+    let s = setup();
+    let tx_id = begin_swap(&s, 100);
+    s.coordinator.prepare(&tx_id, &0);
+    assert_eq!(
+        s.coordinator.try_abort(&tx_id),
+        Err(Ok(CoordinatorError::DeadlineNotReached))
+    );
+
+    // Bob never prepared in time.
+    past_deadline(&s.env);
+    assert_eq!(
+        s.coordinator.try_prepare(&tx_id, &1),
+        Err(Ok(CoordinatorError::DeadlinePassed))
+    );
+    s.coordinator.abort(&tx_id);
+    assert_eq!(status(&s, tx_id), Status::Aborted);
+    assert_eq!(s.gold.balance(&s.alice), 100);
+    assert_eq!(s.gold.hold(&tx_id), None);
+    assert_eq!(s.silver.balance(&s.bob), 50);
+
+    assert_eq!(
+        s.coordinator.try_commit(&tx_id),
+        Err(Ok(CoordinatorError::NotPending))
+    );
+    assert_eq!(
+        s.coordinator.try_abort(&tx_id),
+        Err(Ok(CoordinatorError::NotPending))
+    );
+}
+
+// ==================== VALIDATION ====================
+
+#[test]
+fn test_begin_validation() {
+    let s = setup();
+    assert_eq!(
+        s.coordinator.try_begin(&vec![&s.env], &DEADLINE),
+        Err(Ok(CoordinatorError::InvalidLegs))
+    );
+    let leg = Leg {
+        participant: s.gold.address.clone(),
+        from: s.alice.clone(),
+        to: s.bob.clone(),
+        amount: 1,
+    };
+    assert_eq!(
+        s.coordinator
+            .try_begin(&vec![&s.env, leg.clone(), leg.clone()], &DEADLINE),
+        Err(Ok(CoordinatorError::InvalidLegs))
+    );
+    assert_eq!(
+        s.coordinator.try_begin(&vec![&s.env, leg], &1_000),
+        Err(Ok(CoordinatorError::InvalidDeadline))
+    );
+}
+
+#[test]
+fn test_prepare_validation() {
+    let s = setup();
+    let tx_id = begin_swap(&s, 100);
+    assert_eq!(
+        s.coordinator.try_prepare(&(tx_id + 1), &0),
+        Err(Ok(CoordinatorError::UnknownTx))
+    );
+    assert_eq!(
+        s.coordinator.try_prepare(&tx_id, &2),
+        Err(Ok(CoordinatorError::UnknownLeg))
+    );
+    s.coordinator.prepare(&tx_id, &0);
+    assert_eq!(
+        s.coordinator.try_prepare(&tx_id, &0),
+        Err(Ok(CoordinatorError::AlreadyPrepared))
+    );
+}
@@ -0,0 +1,306 @@
+//! # Multisig Wallet with Stored Proposals
+//!
+//! `01-multi-party-auth` checks an M-of-N threshold inside one invocation,
+//! so every signer has to sign the same transaction. That works when the
+//! signers coordinate off-chain first. This wallet keeps the coordination
+//! on-chain instead. Each signer approves in their own transaction, and
+//! the wallet stores the approvals until the threshold is met:
+//!
+//! ```text
+//! propose(alice, action)  → id      alice signs; her approval is counted
+//! approve(bob, id)                  bob signs, later, separately
+//! execute(id)                       anyone, once approvals ≥ threshold
+//! ```
+//!
+//! An action is either a call the wallet makes as itself or a change to the
+//! wallet's own signers and threshold. The wallet cannot call itself, since
+//! the host forbids re-entry, so signer rotation is built into `Action`
+//! rather than routed through `Call`.
+//!
+//! ## Counting at execution time
+//!
+//! Approvals are stored as addresses and counted when `execute` runs,
+//! against the signers and threshold of that moment:
+//!
+//! - A signer removed after approving no longer counts.
+//! - Raising the threshold also raises it for pending proposals.
+//!
+//! Otherwise a rotated-out key could still push through an old proposal.
+//!
+//! ## Expiry
+//!
+//! Proposals expire `PROPOSAL_LIFETIME` seconds after they are made. An
+//! expired proposal can be neither approved nor executed, so a forgotten
+//! proposal cannot be revived months later by a newly added signer.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Val,
+    Vec,
+};
+
+/// Seconds a proposal stays open (seven days).
+pub const PROPOSAL_LIFETIME: u64 = 7 * 24 * 60 * 60;
+/// Upper bound on signers, which bounds every loop over them.
+pub const MAX_SIGNERS: u32 = 20;
+
+/// A contract call the wallet makes as itself.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Call {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    Call(Call),
+    AddSigner(Address),
+    RemoveSigner(Address),
+    SetThreshold(u32),
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Pending,
+    Executed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub action: Action,
+    /// Signers who approved, in order. The proposer is first.
+    pub approvals: Vec<Address>,
+    /// Ledger timestamp after which the proposal is dead.
+    pub expires_at: u64,
+    pub status: Status,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Signers,
+    Threshold,
+    NextId,
+    Proposal(u64),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MultisigError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// Zero, or more than the number of signers.
+    InvalidThreshold = 3,
+    DuplicateSigner = 4,
+    TooManySigners = 5,
+    NotSigner = 6,
+    UnknownProposal = 7,
+    /// Already executed.
+    NotPending = 8,
+    Expired = 9,
+    AlreadyApproved = 10,
+    ThresholdNotMet = 11,
+}
+
+#[contract]
+pub struct MultisigWallet;
+
+#[contractimpl]
+impl MultisigWallet {
+    pub fn initialize(
+        env: Env,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), MultisigError> {
+        if env.storage().instance().has(&DataKey::Signers) {
+            return Err(MultisigError::AlreadyInitialized);
+        }
+        if signers.len() > MAX_SIGNERS {
+            return Err(MultisigError::TooManySigners);
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers.iter().skip(i + 1).any(|other| other == signer) {
+                return Err(MultisigError::DuplicateSigner);
+            }
+        }
+        check_threshold(threshold, signers.len())?;
+        env.storage().instance().set(&DataKey::Signers, &signers);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        Ok(())
+    }
+
+    // ==================== PROPOSALS ====================
+
+    /// Store `action` for approval and return its id. The proposer's
+    /// approval is counted.
+    ///
+    /// Emits `(proposed, id)` with the proposer.
+    pub fn propose(env: Env, proposer: Address, action: Action) -> Result<u64, MultisigError> {
+        proposer.require_auth();
+        require_signer(&env, &proposer)?;
+
+        let id: u64 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        let mut approvals = Vec::new(&env);
+        approvals.push_back(proposer.clone());
+        save(
+            &env,
+            id,
+            &Proposal {
+                action,
+                approvals,
+                expires_at: env.ledger().timestamp() + PROPOSAL_LIFETIME,
+                status: Status::Pending,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("proposed"), id), proposer);
+        Ok(id)
+    }
+
+    /// Add `signer`'s approval to proposal `id`.
+    ///
+    /// Emits `(approved, id)` with the signer.
+    pub fn approve(env: Env, signer: Address, id: u64) -> Result<(), MultisigError> {
+        signer.require_auth();
+        require_signer(&env, &signer)?;
+        let mut proposal = load_open(&env, id)?;
+        if proposal.approvals.contains(&signer) {
+            return Err(MultisigError::AlreadyApproved);
+        }
+        proposal.approvals.push_back(signer.clone());
+        save(&env, id, &proposal);
+        env.events()
+            .publish((symbol_short!("approved"), id), signer);
+        Ok(())
+    }
+
+    /// Carry out proposal `id`. Anyone may call it once enough current
+    /// signers approved.
+    ///
+    /// Emits `(executed, id)`.
+    pub fn execute(env: Env, id: u64) -> Result<(), MultisigError> {
+        let mut proposal = load_open(&env, id)?;
+        let signers = Self::signers(env.clone())?;
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|approver| signers.contains(approver))
+            .count() as u32;
+        if approvals < Self::threshold(env.clone())? {
+            return Err(MultisigError::ThresholdNotMet);
+        }
+
+        // Mark it executed before acting: the status is final before control
+        // passes to another contract. If the action fails, both revert.
+        proposal.status = Status::Executed;
+        save(&env, id, &proposal);
+        match proposal.action {
+            Action::Call(call) => {
+                env.invoke_contract::<Val>(&call.target, &call.function, call.args);
+            }
+            Action::AddSigner(signer) => add_signer(&env, signers, signer)?,
+            Action::RemoveSigner(signer) => remove_signer(&env, signers, &signer)?,
+            Action::SetThreshold(threshold) => {
+                check_threshold(threshold, signers.len())?;
+                env.storage()
+                    .instance()
+                    .set(&DataKey::Threshold, &threshold);
+            }
+        }
+        env.events().publish((symbol_short!("executed"), id), ());
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn signers(env: Env) -> Result<Vec<Address>, MultisigError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .ok_or(MultisigError::NotInitialized)
+    }
+
+    pub fn threshold(env: Env) -> Result<u32, MultisigError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .ok_or(MultisigError::NotInitialized)
+    }
+
+    pub fn proposal(env: Env, id: u64) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(id))
+    }
+}
+
+fn check_threshold(threshold: u32, signers: u32) -> Result<(), MultisigError> {
+    if threshold == 0 || threshold > signers {
+        return Err(MultisigError::InvalidThreshold);
+    }
+    Ok(())
+}
+
+fn require_signer(env: &Env, address: &Address) -> Result<(), MultisigError> {
+    if !MultisigWallet::signers(env.clone())?.contains(address) {
+        return Err(MultisigError::NotSigner);
+    }
+    Ok(())
+}
+
+/// A pending, unexpired proposal.
+fn load_open(env: &Env, id: u64) -> Result<Proposal, MultisigError> {
+    let proposal =
+        MultisigWallet::proposal(env.clone(), id).ok_or(MultisigError::UnknownProposal)?;
+    if proposal.status != Status::Pending {
+        return Err(MultisigError::NotPending);
+    }
+    if env.ledger().timestamp() > proposal.expires_at {
+        return Err(MultisigError::Expired);
+    }
+    Ok(proposal)
+}
+
+fn save(env: &Env, id: u64, proposal: &Proposal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proposal(id), proposal);
+}
+
+fn add_signer(env: &Env, mut signers: Vec<Address>, signer: Address) -> Result<(), MultisigError> {
+    if signers.contains(&signer) {
+        return Err(MultisigError::DuplicateSigner);
+    }
+    if signers.len() >= MAX_SIGNERS {
+        return Err(MultisigError::TooManySigners);
+    }
+    signers.push_back(signer);
+    env.storage().instance().set(&DataKey::Signers, &signers);
+    Ok(())
+}
+
+/// Fails with `InvalidThreshold` if too few signers would remain to meet
+/// the threshold; lower it first.
+fn remove_signer(
+    env: &Env,
+    mut signers: Vec<Address>,
+    signer: &Address,
+) -> Result<(), MultisigError> {
+    let index = signers
+        .first_index_of(signer)
+        .ok_or(MultisigError::NotSigner)?;
+    signers.remove(index);
+    check_threshold(MultisigWallet::threshold(env.clone())?, signers.len())?;
+    env.storage().instance().set(&DataKey::Signers, &signers);
+    Ok(())
+}
+
+mod test;
@@ -0,0 +1,315 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    vec, IntoVal,
+};
+
+/// A contract that only its admin may change. The wallet is its admin.
+#[contract]
+pub struct Counter;
+
+#[contractimpl]
+impl Counter {
+    pub fn init(env: Env, admin: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+    }
+
+    pub fn bump(env: Env, by: u32) -> u32 {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .unwrap();
+        admin.require_auth();
+        let count = Self::count(env.clone()) + by;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("count"), &count);
+        count
+    }
+
+    pub fn count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("count"))
+            .unwrap_or(0)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    wallet: MultisigWalletClient<'a>,
+    counter: CounterClient<'a>,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+/// A 2-of-3 wallet that administers a counter.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let wallet = MultisigWalletClient::new(&env, &env.register_contract(None, MultisigWallet));
+    let counter = CounterClient::new(&env, &env.register_contract(None, Counter));
+    counter.init(&wallet.address);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    wallet.initialize(&vec![&env, alice.clone(), bob.clone(), carol.clone()], &2);
+    Setup {
+        env,
+        wallet,
+        counter,
+        alice,
+        bob,
+        carol,
+    }
+}
+
+fn bump(s: &Setup, by: u32) -> Action {
+    Action::Call(Call {
+        target: s.counter.address.clone(),
+        function: symbol_short!("bump"),
+        args: vec![&s.env, by.into_val(&s.env)],
+    })
+}
+
+// ==================== PROPOSALS ====================
+
+/// Each step is its own transaction, signed by one signer.
+#[test]
+fn test_propose_approve_execute() {
+    let s = setup();
+    let id = s.wallet.propose(&s.alice, &bump(&s, 5));
+    assert_eq!(
+        s.wallet.try_execute(&id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+
+    s.wallet.approve(&s.bob, &id);
+    s.wallet.execute(&id);
+    assert_eq!(s.counter.count(), 5);
+
+    let proposal = s.wallet.proposal(&id).unwrap();
+    assert_eq!(proposal.status, Status::Executed);
+    assert_eq!(
+        proposal.approvals,
+        vec![&s.env, s.alice.clone(), s.bob.clone()]
+    );
+    assert_eq!(
+        s.wallet.try_execute(&id),
+        Err(Ok(MultisigError::NotPending))
+    );
+    assert_eq!(
+        s.wallet.try_approve(&s.carol, &id),
+        Err(Ok(MultisigError::NotPending))
+    );
+}
+
+#[test]
+fn test_double_approval_rejected() {
+    let s = setup();
+    let id = s.wallet.propose(&s.alice, &bump(&s, 1));
+    // The proposer's approval is already counted.
+    assert_eq!(
+        s.wallet.try_approve(&s.alice, &id),
+        Err(Ok(MultisigError::AlreadyApproved))
+    );
+    s.wallet.approve(&s.bob, &id);
+    assert_eq!(
+        s.wallet.try_approve(&s.bob, &id),
+        Err(Ok(MultisigError::AlreadyApproved))
+    );
+}
+
+#[test]
+fn test_only_signers_propose_and_approve() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.wallet.try_propose(&outsider, &bump(&s, 1)),
+        Err(Ok(MultisigError::NotSigner))
+    );
+    let id = s.wallet.propose(&s.alice, &bump(&s, 1));
+    assert_eq!(
+        s.wallet.try_approve(&outsider, &id),
+        Err(Ok(MultisigError::NotSigner))
+    );
+    assert_eq!(
+        s.wallet.try_approve(&s.bob, &(id + 1)),
+        Err(Ok(MultisigError::UnknownProposal))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_approve_requires_signer_auth() {
+    let s = setup();
+    let id = s.wallet.propose(&s.alice, &bump(&s, 1));
+    s.env.set_auths(&[]);
+    s.wallet.approve(&s.bob, &id);
+}
+
+#[test]
+fn test_proposal_expires() {
+    let s = setup();
+    let id = s.wallet.propose(&s.alice, &bump(&s, 1));
+    let expires_at = s.wallet.proposal(&id).unwrap().expires_at;
+    assert_eq!(expires_at, 1_000 + PROPOSAL_LIFETIME);
+
+    // Still open on its last second...
+    s.env.ledger().with_mut(|l| l.timestamp = expires_at);
+    s.wallet.approve(&s.bob, &id);
+
+    // ...and dead after it, even with enough approvals.
+    s.env.ledger().with_mut(|l| l.timestamp = expires_at + 1);
+    assert_eq!(s.wallet.try_execute(&id), Err(Ok(MultisigError::Expired)));
+    assert_eq!(
+        s.wallet.try_approve(&s.carol, &id),
+        Err(Ok(MultisigError::Expired))
+    );
+    assert_eq!(s.counter.count(), 0);
+}
+
+// ==================== SIGNER ROTATION ====================
+
+#[test]
+fn test_rotate_signer() {
+    let s = setup();
+    let dave = Address::generate(&s.env);
+    let add = s.wallet.propose(&s.alice, &Action::AddSigner(dave.clone()));
+    s.wallet.approve(&s.bob, &add);
+    s.wallet.execute(&add);
+
+    let remove = s
+        .wallet
+        .propose(&dave, &Action::RemoveSigner(s.carol.clone()));
+    s.wallet.approve(&s.alice, &remove);
+    s.wallet.execute(&remove);
+    assert_eq!(
+        s.wallet.signers(),
+        vec![&s.env, s.alice.clone(), s.bob.clone(), dave.clone()]
+    );
+    assert_eq!(
+        s.wallet.try_propose(&s.carol, &bump(&s, 1)),
+        Err(Ok(MultisigError::NotSigner))
+    );
+}
+
+#[test]
+fn test_removed_signer_approval_no_longer_counts() {
+    let s = setup();
+    let id = s.wallet.propose(&s.carol, &bump(&s, 1));
+
+    let remove = s
+        .wallet
+        .propose(&s.alice, &Action::RemoveSigner(s.carol.clone()));
+    s.wallet.approve(&s.bob, &remove);
+    s.wallet.execute(&remove);
+
+    // Carol's approval is stored, but she is no longer a signer.
+    assert_eq!(
+        s.wallet.try_execute(&id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+    s.wallet.approve(&s.alice, &id);
+    s.wallet.approve(&s.bob, &id);
+    s.wallet.execute(&id);
+    assert_eq!(s.counter.count(), 1);
+}
+
+#[test]
+fn test_threshold_change_applies_to_pending_proposals() {
+    let s = setup();
+    let pending = s.wallet.propose(&s.alice, &bump(&s, 1));
+    s.wallet.approve(&s.bob, &pending);
+
+    let raise = s.wallet.propose(&s.alice, &Action::SetThreshold(3));
+    s.wallet.approve(&s.bob, &raise);
+    s.wallet.execute(&raise);
+    assert_eq!(s.wallet.threshold(), 3);
+
+    // Two approvals were enough when they were given, not any more.
+    assert_eq!(
+        s.wallet.try_execute(&pending),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+    s.wallet.approve(&s.carol, &pending);
+    s.wallet.execute(&pending);
+    assert_eq!(s.counter.count(), 1);
+}
+
+/// An action that would break the wallet fails at execution and leaves
+/// the proposal pending.
+#[test]
+fn test_invalid_rotation_rejected() {
+    let s = setup();
+    let too_high = s.wallet.propose(&s.alice, &Action::SetThreshold(4));
+    s.wallet.approve(&s.bob, &too_high);
+    assert_eq!(
+        s.wallet.try_execute(&too_high),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    assert_eq!(
+        s.wallet.proposal(&too_high).unwrap().status,
+        Status::Pending
+    );
+
+    // Removing a signer from a 2-of-2 would leave the threshold unreachable.
+    let shrink = s
+        .wallet
+        .propose(&s.alice, &Action::RemoveSigner(s.carol.clone()));
+    s.wallet.approve(&s.bob, &shrink);
+    s.wallet.execute(&shrink);
+    let remove = s
+        .wallet
+        .propose(&s.alice, &Action::RemoveSigner(s.bob.clone()));
+    s.wallet.approve(&s.bob, &remove);
+    assert_eq!(
+        s.wallet.try_execute(&remove),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+
+    let duplicate = s
+        .wallet
+        .propose(&s.alice, &Action::AddSigner(s.bob.clone()));
+    s.wallet.approve(&s.bob, &duplicate);
+    assert_eq!(
+        s.wallet.try_execute(&duplicate),
+        Err(Ok(MultisigError::DuplicateSigner))
+    );
+}
+
+// ==================== INITIALIZE ====================
+
+#[test]
+fn test_initialize_validation() {
+    let env = Env::default();
+    let wallet = MultisigWalletClient::new(&env, &env.register_contract(None, MultisigWallet));
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    assert_eq!(
+        wallet.try_initialize(&vec![&env, alice.clone(), bob.clone()], &0),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    assert_eq!(
+        wallet.try_initialize(&vec![&env, alice.clone(), bob.clone()], &3),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    assert_eq!(
+        wallet.try_initialize(&vec![&env, alice.clone(), alice.clone()], &1),
+        Err(Ok(MultisigError::DuplicateSigner))
+    );
+    wallet.initialize(&vec![&env, alice.clone(), bob], &2);
+    assert_eq!(
+        wallet.try_initialize(&vec![&env, alice], &1),
+        Err(Ok(MultisigError::AlreadyInitialized))
+    );
+}
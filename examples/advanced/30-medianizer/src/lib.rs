@@ -0,0 +1,279 @@
+//! # Medianizer with Signed, Relayed Reports
+//!
+//! A push oracle makes every reporter send a transaction for every update,
+//! so each reporter needs a funded account and pays fees forever. Here
+//! reporters only sign. Each one signs `(contract, asset, price, timestamp)`
+//! off-chain with an ed25519 key, and anyone can relay a batch of signed
+//! reports in one transaction:
+//!
+//! ```text
+//! reporter A ─sign─┐
+//! reporter B ─sign─┼─▶ relayer ── submit([A, B, C]) ──▶ Medianizer
+//! reporter C ─sign─┘   (no auth, pays the fee)           verifies each signature,
+//!                                                          keeps each reporter's latest
+//! ```
+//!
+//! The contract trusts keys, not callers. `submit` needs no authorization:
+//! a report is accepted because the signature verifies against a registered
+//! reporter key, whoever relays it.
+//!
+//! ## The median
+//!
+//! `lastprice(asset)` takes the latest report of every reporter, keeps the
+//! ones younger than `max_age`, and returns their median once at least
+//! `min_reports` remain. One reporter cannot move the median on their own:
+//! with three reporters, a single lying one is always outvoted. The
+//! timestamp returned is the oldest report used, so a consumer's freshness
+//! check stays conservative.
+//!
+//! `decimals` and `lastprice` have the shape of a SEP-40 oracle, so any
+//! consumer of that interface can read from a medianizer.
+//!
+//! ## Replay protection
+//!
+//! The signed payload includes this contract's address, so a report cannot
+//! be replayed into another medianizer. A reporter's timestamps must
+//! increase: a report no newer than the one stored is skipped, so relaying
+//! the same payload twice changes nothing.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Vec,
+};
+
+/// Upper bound on registered reporters, which bounds the median's work.
+pub const MAX_REPORTERS: u32 = 15;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    /// Decimals of every reported price.
+    pub decimals: u32,
+    /// Oldest report, in seconds, that counts towards the median.
+    pub max_age: u64,
+    /// Fresh reports needed before a median is published.
+    pub min_reports: u32,
+}
+
+/// A price as reporters sign it and as `lastprice` returns it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// A signed report, as relayed to `submit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    /// The reporter's ed25519 public key.
+    pub reporter: BytesN<32>,
+    pub asset: Address,
+    pub price: i128,
+    pub timestamp: u64,
+    /// Signature of `payload(asset, price, timestamp)`.
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Reporters,
+    /// (asset, reporter) → that reporter's latest `PriceData`.
+    Report(Address, BytesN<32>),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MedianizerError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    TooManyReporters = 4,
+    DuplicateReporter = 5,
+    UnknownReporter = 6,
+    InvalidPrice = 7,
+    /// A report timestamped after the current ledger.
+    FutureTimestamp = 8,
+}
+
+#[contract]
+pub struct Medianizer;
+
+#[contractimpl]
+impl Medianizer {
+    pub fn initialize(env: Env, config: Config) -> Result<(), MedianizerError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(MedianizerError::AlreadyInitialized);
+        }
+        if config.max_age == 0 || config.min_reports == 0 || config.min_reports > MAX_REPORTERS {
+            return Err(MedianizerError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &Vec::<BytesN<32>>::new(&env));
+        Ok(())
+    }
+
+    // ==================== ADMIN ====================
+
+    pub fn add_reporter(env: Env, reporter: BytesN<32>) -> Result<(), MedianizerError> {
+        Self::config(env.clone())?.admin.require_auth();
+        let mut reporters = Self::reporters(env.clone())?;
+        if reporters.contains(&reporter) {
+            return Err(MedianizerError::DuplicateReporter);
+        }
+        if reporters.len() >= MAX_REPORTERS {
+            return Err(MedianizerError::TooManyReporters);
+        }
+        reporters.push_back(reporter);
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &reporters);
+        Ok(())
+    }
+
+    /// Stop counting `reporter`. Their stored reports are ignored from now
+    /// on, not deleted.
+    pub fn remove_reporter(env: Env, reporter: BytesN<32>) -> Result<(), MedianizerError> {
+        Self::config(env.clone())?.admin.require_auth();
+        let mut reporters = Self::reporters(env.clone())?;
+        let index = reporters
+            .first_index_of(&reporter)
+            .ok_or(MedianizerError::UnknownReporter)?;
+        reporters.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &reporters);
+        Ok(())
+    }
+
+    // ==================== RELAY ====================
+
+    /// Store a batch of signed reports and return how many were new.
+    /// Anyone may call it.
+    ///
+    /// A bad signature traps and fails the whole batch, as do the errors
+    /// below. Reports that are already too old, or no newer than the
+    /// reporter's stored one, are skipped: another relayer may simply have
+    /// been first.
+    ///
+    /// Emits `(report, asset)` with `(reporter, price, timestamp)` for each
+    /// new report.
+    pub fn submit(env: Env, submissions: Vec<Submission>) -> Result<u32, MedianizerError> {
+        let config = Self::config(env.clone())?;
+        let reporters = Self::reporters(env.clone())?;
+        let now = env.ledger().timestamp();
+        let mut accepted = 0;
+        for s in submissions.iter() {
+            if !reporters.contains(&s.reporter) {
+                return Err(MedianizerError::UnknownReporter);
+            }
+            if s.price <= 0 {
+                return Err(MedianizerError::InvalidPrice);
+            }
+            if s.timestamp > now {
+                return Err(MedianizerError::FutureTimestamp);
+            }
+            let message = Self::payload(env.clone(), s.asset.clone(), s.price, s.timestamp);
+            env.crypto()
+                .ed25519_verify(&s.reporter, &message, &s.signature);
+
+            let key = DataKey::Report(s.asset.clone(), s.reporter.clone());
+            let stored: Option<PriceData> = env.storage().persistent().get(&key);
+            let outdated = stored.is_some_and(|stored| s.timestamp <= stored.timestamp);
+            if outdated || now - s.timestamp > config.max_age {
+                continue;
+            }
+            env.storage().persistent().set(
+                &key,
+                &PriceData {
+                    price: s.price,
+                    timestamp: s.timestamp,
+                },
+            );
+            env.events().publish(
+                (symbol_short!("report"), s.asset),
+                (s.reporter, s.price, s.timestamp),
+            );
+            accepted += 1;
+        }
+        Ok(accepted)
+    }
+
+    // ==================== VIEWS ====================
+
+    /// The bytes a reporter signs: this contract's address, `asset`,
+    /// `price` and `timestamp`, XDR-encoded.
+    pub fn payload(env: Env, asset: Address, price: i128, timestamp: u64) -> Bytes {
+        (env.current_contract_address(), asset, price, timestamp).to_xdr(&env)
+    }
+
+    /// Median of the fresh reports for `asset`, or `None` while fewer than
+    /// `min_reports` are fresh.
+    pub fn lastprice(env: Env, asset: Address) -> Option<PriceData> {
+        let config = Self::config(env.clone()).ok()?;
+        let now = env.ledger().timestamp();
+        let mut prices = [0i128; MAX_REPORTERS as usize];
+        let mut count = 0;
+        let mut oldest = now;
+        for reporter in Self::reporters(env.clone()).ok()?.iter() {
+            let report: Option<PriceData> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Report(asset.clone(), reporter));
+            if let Some(report) = report.filter(|r| now - r.timestamp <= config.max_age) {
+                prices[count] = report.price;
+                oldest = oldest.min(report.timestamp);
+                count += 1;
+            }
+        }
+        if (count as u32) < config.min_reports {
+            return None;
+        }
+        Some(PriceData {
+            price: median(&mut prices[..count]),
+            timestamp: oldest,
+        })
+    }
+
+    pub fn decimals(env: Env) -> Result<u32, MedianizerError> {
+        Ok(Self::config(env)?.decimals)
+    }
+
+    pub fn config(env: Env) -> Result<Config, MedianizerError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(MedianizerError::NotInitialized)
+    }
+
+    pub fn reporters(env: Env) -> Result<Vec<BytesN<32>>, MedianizerError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Reporters)
+            .ok_or(MedianizerError::NotInitialized)
+    }
+}
+
+/// Median of a non-empty slice. With an even count, the mean of the two
+/// middle values, rounded down.
+fn median(prices: &mut [i128]) -> i128 {
+    prices.sort_unstable();
+    let mid = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        prices[mid]
+    } else {
+        let (low, high) = (prices[mid - 1], prices[mid]);
+        low + (high - low) / 2
+    }
+}
+
+mod test;
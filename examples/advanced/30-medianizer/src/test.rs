@@ -0,0 +1,248 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec,
+};
+
+const MAX_AGE: u64 = 300;
+
+struct Setup<'a> {
+    env: Env,
+    oracle: MedianizerClient<'a>,
+    asset: Address,
+    /// Three registered reporters.
+    keys: [SigningKey; 3],
+}
+
+/// Three reporters, two fresh reports needed for a median.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 10_000);
+
+    let oracle = MedianizerClient::new(&env, &env.register_contract(None, Medianizer));
+    oracle.initialize(&Config {
+        admin: Address::generate(&env),
+        decimals: 7,
+        max_age: MAX_AGE,
+        min_reports: 2,
+    });
+    let keys = [1, 2, 3].map(|seed| SigningKey::from_bytes(&[seed; 32]));
+    for key in &keys {
+        oracle.add_reporter(&public_key(&env, key));
+    }
+    Setup {
+        asset: Address::generate(&env),
+        env,
+        oracle,
+        keys,
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+/// What a reporter does off-chain: sign the payload for `price` at
+/// `timestamp`.
+fn sign(s: &Setup, key: &SigningKey, price: i128, timestamp: u64) -> Submission {
+    let payload: std::vec::Vec<u8> = s
+        .oracle
+        .payload(&s.asset, &price, &timestamp)
+        .iter()
+        .collect();
+    Submission {
+        reporter: public_key(&s.env, key),
+        asset: s.asset.clone(),
+        price,
+        timestamp,
+        signature: BytesN::from_array(&s.env, &key.sign(&payload).to_bytes()),
+    }
+}
+
+fn now(s: &Setup) -> u64 {
+    s.env.ledger().timestamp()
+}
+
+// ==================== RELAY ====================
+
+/// One relayer submits everyone's reports; no reporter signs a transaction.
+#[test]
+fn test_relayed_reports_set_median() {
+    let s = setup();
+    let t = now(&s);
+    let batch = vec![
+        &s.env,
+        sign(&s, &s.keys[0], 1_000_000, t),
+        sign(&s, &s.keys[1], 1_020_000, t - 10),
+        sign(&s, &s.keys[2], 990_000, t - 20),
+    ];
+    assert_eq!(s.oracle.submit(&batch), 3);
+    assert!(s.env.auths().is_empty());
+
+    assert_eq!(
+        s.oracle.lastprice(&s.asset),
+        Some(PriceData {
+            price: 1_000_000,
+            timestamp: t - 20,
+        })
+    );
+    assert_eq!(s.oracle.decimals(), 7);
+}
+
+#[test]
+fn test_one_liar_cannot_move_median() {
+    let s = setup();
+    let t = now(&s);
+    s.oracle.submit(&vec![
+        &s.env,
+        sign(&s, &s.keys[0], 1_000_000, t),
+        sign(&s, &s.keys[1], 1_010_000, t),
+        sign(&s, &s.keys[2], 900_000_000, t),
+    ]);
+    assert_eq!(s.oracle.lastprice(&s.asset).unwrap().price, 1_010_000);
+}
+
+#[test]
+fn test_even_count_takes_mean_of_middle() {
+    let s = setup();
+    let t = now(&s);
+    s.oracle.submit(&vec![
+        &s.env,
+        sign(&s, &s.keys[0], 1_000_000, t),
+        sign(&s, &s.keys[1], 1_000_005, t),
+    ]);
+    assert_eq!(s.oracle.lastprice(&s.asset).unwrap().price, 1_000_002);
+}
+
+#[test]
+fn test_replayed_and_stale_reports_skipped() {
+    let s = setup();
+    let t = now(&s);
+    let report = sign(&s, &s.keys[0], 1_000_000, t - 5);
+    assert_eq!(s.oracle.submit(&vec![&s.env, report.clone()]), 1);
+
+    // The same payload again, an older one, and one past `max_age`.
+    let batch = vec![
+        &s.env,
+        report,
+        sign(&s, &s.keys[0], 2_000_000, t - 6),
+        sign(&s, &s.keys[1], 2_000_000, t - MAX_AGE - 1),
+    ];
+    assert_eq!(s.oracle.submit(&batch), 0);
+    assert_eq!(s.oracle.lastprice(&s.asset), None);
+
+    // A newer report from the same reporter replaces the old one.
+    s.oracle.submit(&vec![
+        &s.env,
+        sign(&s, &s.keys[0], 1_100_000, t),
+        sign(&s, &s.keys[1], 1_100_000, t),
+    ]);
+    assert_eq!(s.oracle.lastprice(&s.asset).unwrap().price, 1_100_000);
+}
+
+#[test]
+fn test_bad_signature_rejected() {
+    let s = setup();
+    let t = now(&s);
+    // A relayer cannot change the price a reporter signed.
+    let mut tampered = sign(&s, &s.keys[0], 1_000_000, t);
+    tampered.price = 2_000_000;
+    assert!(s.oracle.try_submit(&vec![&s.env, tampered]).is_err());
+
+    // Nor claim another reporter's signature.
+    let mut borrowed = sign(&s, &s.keys[0], 1_000_000, t);
+    borrowed.reporter = public_key(&s.env, &s.keys[1]);
+    assert!(s.oracle.try_submit(&vec![&s.env, borrowed]).is_err());
+}
+
+#[test]
+fn test_submission_validation() {
+    let s = setup();
+    let t = now(&s);
+    let stranger = SigningKey::from_bytes(&[9; 32]);
+    assert_eq!(
+        s.oracle
+            .try_submit(&vec![&s.env, sign(&s, &stranger, 1_000_000, t)]),
+        Err(Ok(MedianizerError::UnknownReporter))
+    );
+    assert_eq!(
+        s.oracle
+            .try_submit(&vec![&s.env, sign(&s, &s.keys[0], 0, t)]),
+        Err(Ok(MedianizerError::InvalidPrice))
+    );
+    assert_eq!(
+        s.oracle
+            .try_submit(&vec![&s.env, sign(&s, &s.keys[0], 1_000_000, t + 1)]),
+        Err(Ok(MedianizerError::FutureTimestamp))
+    );
+}
+
+// ==================== FRESHNESS ====================
+
+#[test]
+fn test_reports_age_out_of_median() {
+    let s = setup();
+    let t = now(&s);
+    s.oracle.submit(&vec![
+        &s.env,
+        sign(&s, &s.keys[0], 1_000_000, t - 100),
+        sign(&s, &s.keys[1], 1_020_000, t),
+        sign(&s, &s.keys[2], 1_040_000, t),
+    ]);
+    assert_eq!(s.oracle.lastprice(&s.asset).unwrap().price, 1_020_000);
+
+    // The first report drops out; the other two still make a median.
+    s.env
+        .ledger()
+        .with_mut(|l| l.timestamp = t - 100 + MAX_AGE + 1);
+    assert_eq!(
+        s.oracle.lastprice(&s.asset),
+        Some(PriceData {
+            price: 1_030_000,
+            timestamp: t,
+        })
+    );
+
+    // Everyone went silent.
+    s.env.ledger().with_mut(|l| l.timestamp = t + MAX_AGE + 1);
+    assert_eq!(s.oracle.lastprice(&s.asset), None);
+}
+
+// ==================== REPORTERS ====================
+
+#[test]
+fn test_removed_reporter_no_longer_counts() {
+    let s = setup();
+    let t = now(&s);
+    s.oracle.submit(&vec![
+        &s.env,
+        sign(&s, &s.keys[0], 1_000_000, t),
+        sign(&s, &s.keys[1], 1_020_000, t),
+    ]);
+    s.oracle.remove_reporter(&public_key(&s.env, &s.keys[1]));
+    assert_eq!(s.oracle.lastprice(&s.asset), None);
+    assert_eq!(
+        s.oracle
+            .try_submit(&vec![&s.env, sign(&s, &s.keys[1], 1_000_000, t)]),
+        Err(Ok(MedianizerError::UnknownReporter))
+    );
+    assert_eq!(
+        s.oracle.try_add_reporter(&public_key(&s.env, &s.keys[0])),
+        Err(Ok(MedianizerError::DuplicateReporter))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_add_reporter_requires_admin() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.oracle
+        .add_reporter(&public_key(&s.env, &SigningKey::from_bytes(&[9; 32])));
+}
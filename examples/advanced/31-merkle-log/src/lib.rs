@@ -0,0 +1,267 @@
+//! # Append-Only Log with Merkle Inclusion Proofs
+//!
+//! Entries are appended to a log and committed to by one 32-byte root. A
+//! party that holds only a root, such as a bridge on another chain or an
+//! auditor who saved yesterday's root, can check that an entry is in the
+//! log from the entry, its index and `TREE_DEPTH` sibling hashes. It never
+//! needs the rest of the log.
+//!
+//! ```text
+//!                 root
+//!              /        \
+//!           n(1,0)     n(1,1)
+//!           /   \       /   \
+//!        leaf0 leaf1 leaf2  zero       proof of leaf2 = [zero, n(1,0)]
+//! ```
+//!
+//! ## An incremental tree
+//!
+//! The tree has a fixed depth, so the root of a log with `n` entries is the
+//! root of a full tree whose leaves past `n` are empty. Recomputing it from
+//! every leaf on each append would cost O(n). Instead:
+//!
+//! - Appending stores the leaf and each node that the leaf completes. A node
+//!   is complete once every leaf under it exists, and then never changes.
+//!   Half of all appends complete no node, a quarter complete one, and so
+//!   on: under two writes per append on average.
+//! - A subtree with no leaves has a fixed "empty" hash per level.
+//! - Only the nodes on the path to the newest leaf are partly filled. There
+//!   is one per level, and each is hashed from its children on demand.
+//!
+//! Because complete nodes never change, the same rules give the root and
+//! proofs for any earlier size of the log, not just the current one.
+//!
+//! ## Domain separation
+//!
+//! Leaves hash as `sha256(0x00 ‖ data)` and inner nodes as
+//! `sha256(0x01 ‖ left ‖ right)`. Without the prefixes, an inner node's two
+//! children could be passed off as the data of a leaf.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
+    Vec,
+};
+
+/// Levels below the root. The log holds at most `2^TREE_DEPTH` entries and
+/// every proof has `TREE_DEPTH` hashes.
+pub const TREE_DEPTH: u32 = 16;
+
+/// Maximum number of entries.
+pub const CAPACITY: u32 = 1 << TREE_DEPTH;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The address allowed to append.
+    Writer,
+    /// Number of entries appended.
+    Size,
+    /// Root of the current log.
+    Root,
+    /// A complete node: `(level, index)`. Level 0 holds the leaf hashes.
+    Node(u32, u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LogError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The log already holds `CAPACITY` entries.
+    LogFull = 3,
+    /// A size larger than the log, or an index outside the requested size.
+    OutOfRange = 4,
+}
+
+#[contract]
+pub struct MerkleLog;
+
+#[contractimpl]
+impl MerkleLog {
+    pub fn initialize(env: Env, writer: Address) -> Result<(), LogError> {
+        if env.storage().instance().has(&DataKey::Writer) {
+            return Err(LogError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Writer, &writer);
+        env.storage().instance().set(&DataKey::Size, &0u32);
+        env.storage()
+            .instance()
+            .set(&DataKey::Root, &Self::empty_hashes(&env).last().unwrap());
+        Ok(())
+    }
+
+    // ==================== WRITE ====================
+
+    /// Append `data` and return its index. Writer only.
+    ///
+    /// Emits `(append, index)` with `(leaf, root)`.
+    pub fn append(env: Env, data: Bytes) -> Result<u32, LogError> {
+        Self::writer(&env)?.require_auth();
+        let index = Self::size(env.clone());
+        if index >= CAPACITY {
+            return Err(LogError::LogFull);
+        }
+
+        let leaf = Self::leaf_hash(env.clone(), data);
+        let mut node = leaf.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Node(0, index), &node);
+
+        // The new leaf completes its parent if it is a right child, the
+        // grandparent if the parent is a right child too, and so on.
+        let size = index + 1;
+        let mut level = 0;
+        while level < TREE_DEPTH && (size >> level) & 1 == 0 {
+            let sibling: BytesN<32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Node(level, (index >> level) ^ 1))
+                .unwrap();
+            node = Self::hash_pair(&env, &sibling, &node);
+            level += 1;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Node(level, index >> level), &node);
+        }
+
+        let root = Self::node(&env, &Self::empty_hashes(&env), TREE_DEPTH, 0, size);
+        env.storage().instance().set(&DataKey::Size, &size);
+        env.storage().instance().set(&DataKey::Root, &root);
+        env.events()
+            .publish((symbol_short!("append"), index), (leaf, root));
+        Ok(index)
+    }
+
+    // ==================== READ ====================
+
+    /// Number of entries appended so far.
+    pub fn size(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Size).unwrap_or(0)
+    }
+
+    /// Root of the current log.
+    pub fn root(env: Env) -> Result<BytesN<32>, LogError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Root)
+            .ok_or(LogError::NotInitialized)
+    }
+
+    /// Root of the log as it was when it held `size` entries.
+    pub fn root_at(env: Env, size: u32) -> Result<BytesN<32>, LogError> {
+        if size > Self::size(env.clone()) {
+            return Err(LogError::OutOfRange);
+        }
+        Ok(Self::node(
+            &env,
+            &Self::empty_hashes(&env),
+            TREE_DEPTH,
+            0,
+            size,
+        ))
+    }
+
+    /// Sibling hashes from leaf `index` up to `root_at(size)`, lowest first.
+    pub fn get_proof(env: Env, index: u32, size: u32) -> Result<Vec<BytesN<32>>, LogError> {
+        if size > Self::size(env.clone()) || index >= size {
+            return Err(LogError::OutOfRange);
+        }
+        let empty = Self::empty_hashes(&env);
+        let mut proof = Vec::new(&env);
+        for level in 0..TREE_DEPTH {
+            proof.push_back(Self::node(&env, &empty, level, (index >> level) ^ 1, size));
+        }
+        Ok(proof)
+    }
+
+    // ==================== VERIFY ====================
+
+    /// Whether `data` is entry `index` of the log whose root is `root`.
+    ///
+    /// Uses no storage: the same check runs anywhere that has the root,
+    /// including in other contracts and off-chain.
+    pub fn verify(
+        env: Env,
+        data: Bytes,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+        root: BytesN<32>,
+    ) -> bool {
+        if proof.len() != TREE_DEPTH || index >= CAPACITY {
+            return false;
+        }
+        let mut node = Self::leaf_hash(env.clone(), data);
+        for (level, sibling) in proof.iter().enumerate() {
+            node = if (index >> level) & 1 == 0 {
+                Self::hash_pair(&env, &node, &sibling)
+            } else {
+                Self::hash_pair(&env, &sibling, &node)
+            };
+        }
+        node == root
+    }
+
+    /// `sha256(0x00 ‖ data)`.
+    pub fn leaf_hash(env: Env, data: Bytes) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(&env, &[0]);
+        preimage.append(&data);
+        env.crypto().sha256(&preimage).to_bytes()
+    }
+
+    // ==================== HELPERS ====================
+
+    fn writer(env: &Env) -> Result<Address, LogError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Writer)
+            .ok_or(LogError::NotInitialized)
+    }
+
+    /// `sha256(0x01 ‖ left ‖ right)`.
+    fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(env, &[1]);
+        preimage.append(&left.clone().into());
+        preimage.append(&right.clone().into());
+        env.crypto().sha256(&preimage).to_bytes()
+    }
+
+    /// Hash of a subtree with no leaves, for each level from 0 to
+    /// `TREE_DEPTH`. Level 0 is 32 zero bytes, which is no leaf's hash.
+    fn empty_hashes(env: &Env) -> Vec<BytesN<32>> {
+        let mut node = BytesN::from_array(env, &[0; 32]);
+        let mut hashes = Vec::from_array(env, [node.clone()]);
+        for _ in 0..TREE_DEPTH {
+            node = Self::hash_pair(env, &node, &node);
+            hashes.push_back(node.clone());
+        }
+        hashes
+    }
+
+    /// Node `(level, index)` of the tree holding the first `size` leaves.
+    fn node(env: &Env, empty: &Vec<BytesN<32>>, level: u32, index: u32, size: u32) -> BytesN<32> {
+        // Leaves under this node: [first, first + 2^level).
+        let first = (index as u64) << level;
+        let end = first + (1u64 << level);
+        if first >= size as u64 {
+            return empty.get(level).unwrap();
+        }
+        if end <= size as u64 {
+            return env
+                .storage()
+                .persistent()
+                .get(&DataKey::Node(level, index))
+                .unwrap();
+        }
+        // Partly filled: at most one child is partly filled in turn, so
+        // this recurses once per level.
+        let left = Self::node(env, empty, level - 1, index * 2, size);
+        let right = Self::node(env, empty, level - 1, index * 2 + 1, size);
+        Self::hash_pair(env, &left, &right)
+    }
+}
+
+mod test;
@@ -0,0 +1,170 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, testutils::Events, vec, IntoVal};
+
+struct Setup<'a> {
+    env: Env,
+    log: MerkleLogClient<'a>,
+    id: Address,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, MerkleLog);
+    let log = MerkleLogClient::new(&env, &id);
+    log.initialize(&Address::generate(&env));
+    Setup { env, log, id }
+}
+
+fn entry(env: &Env, i: u32) -> Bytes {
+    Bytes::from_slice(env, std::format!("entry-{i}").as_bytes())
+}
+
+fn append_entries(s: &Setup, n: u32) {
+    let start = s.log.size();
+    for i in start..start + n {
+        assert_eq!(s.log.append(&entry(&s.env, i)), i);
+    }
+}
+
+fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &[1]);
+    preimage.append(&left.clone().into());
+    preimage.append(&right.clone().into());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+/// The root recomputed from every leaf, padded to `CAPACITY` one level at
+/// a time.
+fn naive_root(env: &Env, leaves: &[BytesN<32>]) -> BytesN<32> {
+    let mut level: std::vec::Vec<BytesN<32>> = leaves.to_vec();
+    let mut empty = BytesN::from_array(env, &[0; 32]);
+    for _ in 0..TREE_DEPTH {
+        if level.len() % 2 == 1 {
+            level.push(empty.clone());
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(env, &pair[0], &pair[1]))
+            .collect();
+        if level.is_empty() {
+            level.push(hash_pair(env, &empty, &empty));
+        }
+        empty = hash_pair(env, &empty, &empty);
+    }
+    level[0].clone()
+}
+
+#[test]
+fn test_empty_log() {
+    let s = setup();
+    assert_eq!(s.log.size(), 0);
+    assert_eq!(s.log.root(), naive_root(&s.env, &[]));
+    assert_eq!(s.log.root_at(&0), s.log.root());
+    assert_eq!(
+        s.log.try_initialize(&Address::generate(&s.env)),
+        Err(Ok(LogError::AlreadyInitialized))
+    );
+}
+
+/// Every size from 1 to 9 crosses each case of the incremental update: a
+/// left leaf, a right leaf, and one to three completed ancestors.
+#[test]
+fn test_root_matches_full_recomputation() {
+    let s = setup();
+    let mut leaves = std::vec::Vec::new();
+    for i in 0..9 {
+        s.log.append(&entry(&s.env, i));
+        leaves.push(s.log.leaf_hash(&entry(&s.env, i)));
+        assert_eq!(s.log.root(), naive_root(&s.env, &leaves), "size {}", i + 1);
+    }
+}
+
+#[test]
+fn test_proof_for_every_entry() {
+    let s = setup();
+    append_entries(&s, 7);
+    let root = s.log.root();
+
+    for i in 0..7 {
+        let proof = s.log.get_proof(&i, &7);
+        assert_eq!(proof.len(), TREE_DEPTH);
+        assert!(s.log.verify(&entry(&s.env, i), &i, &proof, &root));
+        // The proof is bound to both the data and the position.
+        assert!(!s.log.verify(&entry(&s.env, i + 1), &i, &proof, &root));
+        assert!(!s.log.verify(&entry(&s.env, i), &(i ^ 1), &proof, &root));
+    }
+}
+
+/// Complete nodes never change, so an old root and proofs against it stay
+/// available after the log has grown.
+#[test]
+fn test_historical_root_and_proof() {
+    let s = setup();
+    append_entries(&s, 3);
+    let saved = s.log.root();
+
+    append_entries(&s, 10);
+    assert_ne!(s.log.root(), saved);
+    assert_eq!(s.log.root_at(&3), saved);
+
+    let proof = s.log.get_proof(&2, &3);
+    assert!(s.log.verify(&entry(&s.env, 2), &2, &proof, &saved));
+    assert!(!s.log.verify(&entry(&s.env, 2), &2, &proof, &s.log.root()));
+}
+
+#[test]
+fn test_malformed_proof_rejected() {
+    let s = setup();
+    append_entries(&s, 2);
+    let root = s.log.root();
+    let mut proof = s.log.get_proof(&0, &2);
+    proof.pop_back();
+    assert!(!s.log.verify(&entry(&s.env, 0), &0, &proof, &root));
+
+    // The two children of an inner node do not hash to it as leaf data.
+    let left = s.log.leaf_hash(&entry(&s.env, 0));
+    let right = s.log.leaf_hash(&entry(&s.env, 1));
+    let mut children = Bytes::new(&s.env);
+    children.append(&left.clone().into());
+    children.append(&right.clone().into());
+    assert_ne!(s.log.leaf_hash(&children), hash_pair(&s.env, &left, &right));
+}
+
+#[test]
+fn test_out_of_range() {
+    let s = setup();
+    append_entries(&s, 3);
+    assert_eq!(s.log.try_root_at(&4), Err(Ok(LogError::OutOfRange)));
+    assert_eq!(s.log.try_get_proof(&3, &3), Err(Ok(LogError::OutOfRange)));
+    assert_eq!(s.log.try_get_proof(&0, &4), Err(Ok(LogError::OutOfRange)));
+}
+
+#[test]
+fn test_append_emits_leaf_and_root() {
+    let s = setup();
+    s.log.append(&entry(&s.env, 0));
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            (
+                s.id.clone(),
+                (symbol_short!("append"), 0u32).into_val(&s.env),
+                (s.log.leaf_hash(&entry(&s.env, 0)), s.log.root()).into_val(&s.env),
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_append_requires_writer() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.log.append(&entry(&s.env, 0));
+}
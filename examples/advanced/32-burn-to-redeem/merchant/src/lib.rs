@@ -0,0 +1,139 @@
+//! Merchant for the burn-to-redeem example.
+//!
+//! Sells one item at a fixed price and runs a members' area. Both accept
+//! entitlements from the coupon issuer: `checkout` takes a discount off the
+//! price, `enter` grants access for an access pass. The merchant never sees
+//! the burn. It asks the issuer to consume the entitlement and acts on the
+//! perk returned, so a coupon it accepts has been paid for and cannot be
+//! used twice.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Env, TryFromVal, Val,
+};
+
+/// Mirrors the issuer's `Perk`: same variants, same encoding.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Perk {
+    DiscountBps(u32),
+    AccessPass,
+}
+
+/// The part of the issuer's interface the merchant uses.
+#[contractclient(name = "IssuerClient")]
+pub trait IssuerInterface {
+    fn consume(env: Env, consumer: Address, id: u64, holder: Address) -> Perk;
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Issuer,
+    Token,
+    Price,
+    Treasury,
+    /// Persistent: present once an address has entered with a pass.
+    Member(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MerchantError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The issuer refused to consume the entitlement.
+    CouponRejected = 3,
+    /// The entitlement's perk does not apply here.
+    WrongPerk = 4,
+}
+
+#[contract]
+pub struct Merchant;
+
+#[contractimpl]
+impl Merchant {
+    pub fn init(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        price: i128,
+        treasury: Address,
+    ) -> Result<(), MerchantError> {
+        if env.storage().instance().has(&DataKey::Issuer) {
+            return Err(MerchantError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Issuer, &issuer);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Price, &price);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        Ok(())
+    }
+
+    /// Buy the item, optionally with a discount coupon. Returns the amount
+    /// paid.
+    ///
+    /// Emits `(sold, buyer)` with the amount paid.
+    pub fn checkout(env: Env, buyer: Address, coupon: Option<u64>) -> Result<i128, MerchantError> {
+        buyer.require_auth();
+        let price: i128 = Self::get(&env, &DataKey::Price)?;
+        let paid = match coupon {
+            None => price,
+            Some(id) => match Self::consume(&env, id, &buyer)? {
+                Perk::DiscountBps(bps) => price - price * bps as i128 / 10_000,
+                Perk::AccessPass => return Err(MerchantError::WrongPerk),
+            },
+        };
+
+        let token: Address = Self::get(&env, &DataKey::Token)?;
+        let treasury: Address = Self::get(&env, &DataKey::Treasury)?;
+        token::Client::new(&env, &token).transfer(&buyer, &treasury, &paid);
+        env.events().publish((symbol_short!("sold"), buyer), paid);
+        Ok(paid)
+    }
+
+    /// Enter the members' area with an access pass.
+    ///
+    /// Emits `(entered, visitor)` with the pass id.
+    pub fn enter(env: Env, visitor: Address, pass: u64) -> Result<(), MerchantError> {
+        visitor.require_auth();
+        if Self::consume(&env, pass, &visitor)? != Perk::AccessPass {
+            return Err(MerchantError::WrongPerk);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Member(visitor.clone()), &true);
+        env.events()
+            .publish((symbol_short!("entered"), visitor), pass);
+        Ok(())
+    }
+
+    pub fn is_member(env: Env, visitor: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Member(visitor))
+    }
+
+    /// Ask the issuer to consume entitlement `id` for `holder`.
+    fn consume(env: &Env, id: u64, holder: &Address) -> Result<Perk, MerchantError> {
+        let issuer: Address = Self::get(env, &DataKey::Issuer)?;
+        match IssuerClient::new(env, &issuer).try_consume(
+            &env.current_contract_address(),
+            &id,
+            holder,
+        ) {
+            Ok(Ok(perk)) => Ok(perk),
+            _ => Err(MerchantError::CouponRejected),
+        }
+    }
+
+    fn get<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Result<V, MerchantError> {
+        env.storage()
+            .instance()
+            .get(key)
+            .ok_or(MerchantError::NotInitialized)
+    }
+}
+
+mod test;
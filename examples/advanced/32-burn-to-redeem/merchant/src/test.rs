@@ -0,0 +1,96 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+};
+
+const PRICE: i128 = 1_000;
+const DISCOUNT: u64 = 0;
+const PASS: u64 = 1;
+
+/// Issuer stand-in: entitlement 0 is a 25% discount, 1 an access pass.
+/// Each can be consumed once.
+#[contract]
+struct StubIssuer;
+
+#[contractimpl]
+impl StubIssuer {
+    pub fn consume(env: Env, _consumer: Address, id: u64, _holder: Address) -> Perk {
+        assert!(!env.storage().instance().has(&id), "already consumed");
+        env.storage().instance().set(&id, &true);
+        match id {
+            DISCOUNT => Perk::DiscountBps(2_500),
+            PASS => Perk::AccessPass,
+            _ => panic!("unknown entitlement"),
+        }
+    }
+}
+
+struct Setup {
+    merchant: MerchantClient<'static>,
+    token: TokenClient<'static>,
+    treasury: Address,
+    buyer: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let merchant = MerchantClient::new(&env, &env.register_contract(None, Merchant));
+    let treasury = Address::generate(&env);
+    merchant.init(
+        &env.register_contract(None, StubIssuer),
+        &token_id,
+        &PRICE,
+        &treasury,
+    );
+    let buyer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&buyer, &(10 * PRICE));
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        merchant,
+        treasury,
+        buyer,
+    }
+}
+
+#[test]
+fn test_checkout_full_price() {
+    let s = setup();
+    assert_eq!(s.merchant.checkout(&s.buyer, &None), PRICE);
+    assert_eq!(s.token.balance(&s.treasury), PRICE);
+}
+
+#[test]
+fn test_checkout_with_discount_once() {
+    let s = setup();
+    assert_eq!(s.merchant.checkout(&s.buyer, &Some(DISCOUNT)), 750);
+    assert_eq!(
+        s.merchant.try_checkout(&s.buyer, &Some(DISCOUNT)),
+        Err(Ok(MerchantError::CouponRejected))
+    );
+    assert_eq!(s.token.balance(&s.treasury), 750);
+}
+
+#[test]
+fn test_perks_apply_only_where_they_fit() {
+    let s = setup();
+    assert_eq!(
+        s.merchant.try_checkout(&s.buyer, &Some(PASS)),
+        Err(Ok(MerchantError::WrongPerk))
+    );
+    assert_eq!(
+        s.merchant.try_enter(&s.buyer, &DISCOUNT),
+        Err(Ok(MerchantError::WrongPerk))
+    );
+
+    // The failed calls rolled back, so both are still unused.
+    s.merchant.enter(&s.buyer, &PASS);
+    assert!(s.merchant.is_member(&s.buyer));
+}
@@ -0,0 +1,40 @@
+//! A fungible token whose `burn` only removes 99% of the amount, as a
+//! token with a burn fee would. The issuer must refuse to redeem against
+//! it. Only compiled for tests.
+
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum LeakyKey {
+    Balance(Address),
+}
+
+#[contract]
+pub struct LeakyToken;
+
+#[contractimpl]
+impl LeakyToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .set(&LeakyKey::Balance(to), &amount);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&LeakyKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        let balance = Self::balance(env.clone(), from.clone());
+        env.storage()
+            .instance()
+            .set(&LeakyKey::Balance(from), &(balance - amount * 99 / 100));
+    }
+}
@@ -0,0 +1,357 @@
+//! # Burn-to-Redeem Coupons
+//!
+//! A holder burns tokens, or one NFT, and receives an entitlement: an
+//! on-chain record that a perk (a discount, an access pass) is owed to
+//! them. A different contract, the consumer named in the offer, later uses
+//! the entitlement up:
+//!
+//! ```text
+//! add_offer(offer)                  → offer_id         admin
+//! redeem(holder, offer_id, nft)     → entitlement_id   holder signs; burns
+//! consume(consumer, id, holder)     → perk             the offer's consumer
+//! ```
+//!
+//! ## Verifying the burn
+//!
+//! The issuer does not trust the burned asset to have burned anything. For
+//! a fungible token it reads the holder's balance before and after `burn`
+//! and requires the difference to be exactly the offer's amount: a token
+//! with a burn fee, or a broken `burn`, fails with `BurnNotVerified`. For an
+//! NFT it checks `owner_of` before the burn and requires the token to no
+//! longer exist after it.
+//!
+//! ## Entitlement lifecycle
+//!
+//! ```text
+//! redeem ──→ Active ──consume──→ Consumed
+//!              │
+//!              └── expires_at passes ──→ (reads as expired; consume refused)
+//! ```
+//!
+//! Only the consumer named in the offer can consume, and only for the
+//! entitlement's holder, so a perk issued for one shop cannot be spent at
+//! another. A contract's own address is authorized for calls it makes
+//! directly, so the consumer contract needs no signature to consume.
+//! Entitlements are never deleted: a consumed one stays readable as proof
+//! of what was spent.
+//!
+//! The consumer is in `merchant/`. It declares the one call it makes with
+//! `#[contractclient]`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    Address, Env,
+};
+
+/// The part of an NFT collection's interface the issuer uses.
+#[contractclient(name = "NftClient")]
+pub trait NftInterface {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn burn(env: Env, from: Address, token_id: u32);
+}
+
+/// What has to be burned to redeem an offer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BurnAsset {
+    /// `amount` of a SEP-41 token.
+    Token(Address, i128),
+    /// Any one NFT from a collection.
+    Nft(Address),
+}
+
+/// What an entitlement is worth to its consumer.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Perk {
+    /// Price reduction in basis points.
+    DiscountBps(u32),
+    AccessPass,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Offer {
+    pub burn: BurnAsset,
+    pub perk: Perk,
+    /// The only contract that can consume the entitlements.
+    pub consumer: Address,
+    /// Seconds from redemption until the entitlement expires.
+    pub valid_for: u64,
+    pub retired: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntitlementStatus {
+    Active,
+    Consumed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entitlement {
+    pub holder: Address,
+    pub offer_id: u32,
+    pub perk: Perk,
+    pub consumer: Address,
+    pub expires_at: u64,
+    pub status: EntitlementStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    NextOffer,
+    NextEntitlement,
+    Offer(u32),
+    /// Persistent: an `Entitlement`, kept after it is consumed.
+    Entitlement(u64),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RedeemError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// A non-positive burn amount, a discount over 100% or no validity.
+    InvalidOffer = 3,
+    UnknownOffer = 4,
+    OfferRetired = 5,
+    /// An NFT offer was redeemed without a token id.
+    MissingTokenId = 6,
+    /// The holder does not own the NFT.
+    NotOwner = 7,
+    /// The burn did not remove exactly what the offer requires.
+    BurnNotVerified = 8,
+    UnknownEntitlement = 9,
+    /// `consume` called by a contract other than the offer's consumer.
+    WrongConsumer = 10,
+    /// `consume` for an address that does not hold the entitlement.
+    NotHolder = 11,
+    AlreadyConsumed = 12,
+    Expired = 13,
+}
+
+/// 100% in basis points.
+pub const MAX_BPS: u32 = 10_000;
+
+#[contract]
+pub struct CouponIssuer;
+
+#[contractimpl]
+impl CouponIssuer {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), RedeemError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(RedeemError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    // ==================== OFFERS ====================
+
+    /// Publish an offer. Admin only.
+    ///
+    /// Emits `(offer, offer_id)` with the consumer.
+    pub fn add_offer(
+        env: Env,
+        burn: BurnAsset,
+        perk: Perk,
+        consumer: Address,
+        valid_for: u64,
+    ) -> Result<u32, RedeemError> {
+        Self::admin(&env)?.require_auth();
+        let valid_burn = match &burn {
+            BurnAsset::Token(_, amount) => *amount > 0,
+            BurnAsset::Nft(_) => true,
+        };
+        let valid_perk = match perk {
+            Perk::DiscountBps(bps) => bps > 0 && bps <= MAX_BPS,
+            Perk::AccessPass => true,
+        };
+        if !valid_burn || !valid_perk || valid_for == 0 {
+            return Err(RedeemError::InvalidOffer);
+        }
+
+        let offer_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextOffer)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextOffer, &(offer_id + 1));
+        env.storage().persistent().set(
+            &DataKey::Offer(offer_id),
+            &Offer {
+                burn,
+                perk,
+                consumer: consumer.clone(),
+                valid_for,
+                retired: false,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("offer"), offer_id), consumer);
+        Ok(offer_id)
+    }
+
+    /// Stop new redemptions. Entitlements already issued stay valid.
+    /// Admin only.
+    pub fn retire_offer(env: Env, offer_id: u32) -> Result<(), RedeemError> {
+        Self::admin(&env)?.require_auth();
+        let mut offer = Self::load_offer(&env, offer_id)?;
+        offer.retired = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Offer(offer_id), &offer);
+        Ok(())
+    }
+
+    // ==================== REDEEM ====================
+
+    /// Burn what `offer_id` asks for and issue an entitlement to `holder`.
+    /// `nft_id` names the token to burn for an NFT offer and is ignored
+    /// otherwise.
+    ///
+    /// Emits `(redeem, holder)` with `(offer_id, entitlement_id)`.
+    pub fn redeem(
+        env: Env,
+        holder: Address,
+        offer_id: u32,
+        nft_id: Option<u32>,
+    ) -> Result<u64, RedeemError> {
+        holder.require_auth();
+        let offer = Self::load_offer(&env, offer_id)?;
+        if offer.retired {
+            return Err(RedeemError::OfferRetired);
+        }
+
+        match offer.burn {
+            BurnAsset::Token(token, amount) => {
+                let token = token::Client::new(&env, &token);
+                let before = token.balance(&holder);
+                token.burn(&holder, &amount);
+                if before - token.balance(&holder) != amount {
+                    return Err(RedeemError::BurnNotVerified);
+                }
+            }
+            BurnAsset::Nft(collection) => {
+                let nft_id = nft_id.ok_or(RedeemError::MissingTokenId)?;
+                let nft = NftClient::new(&env, &collection);
+                if nft.owner_of(&nft_id) != holder {
+                    return Err(RedeemError::NotOwner);
+                }
+                nft.burn(&holder, &nft_id);
+                // A burned token has no owner at all; a transfer would
+                // still report one.
+                if nft.try_owner_of(&nft_id).is_ok() {
+                    return Err(RedeemError::BurnNotVerified);
+                }
+            }
+        }
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextEntitlement)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextEntitlement, &(id + 1));
+        env.storage().persistent().set(
+            &DataKey::Entitlement(id),
+            &Entitlement {
+                holder: holder.clone(),
+                offer_id,
+                perk: offer.perk,
+                consumer: offer.consumer,
+                expires_at: env.ledger().timestamp() + offer.valid_for,
+                status: EntitlementStatus::Active,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("redeem"), holder), (offer_id, id));
+        Ok(id)
+    }
+
+    // ==================== CONSUME ====================
+
+    /// Use up entitlement `id` on behalf of `holder` and return its perk.
+    /// Only the offer's consumer can call it.
+    ///
+    /// Emits `(consume, id)` with the holder.
+    pub fn consume(
+        env: Env,
+        consumer: Address,
+        id: u64,
+        holder: Address,
+    ) -> Result<Perk, RedeemError> {
+        consumer.require_auth();
+        let mut entitlement = Self::entitlement(env.clone(), id)?;
+        if entitlement.consumer != consumer {
+            return Err(RedeemError::WrongConsumer);
+        }
+        if entitlement.holder != holder {
+            return Err(RedeemError::NotHolder);
+        }
+        if entitlement.status == EntitlementStatus::Consumed {
+            return Err(RedeemError::AlreadyConsumed);
+        }
+        if env.ledger().timestamp() >= entitlement.expires_at {
+            return Err(RedeemError::Expired);
+        }
+
+        entitlement.status = EntitlementStatus::Consumed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Entitlement(id), &entitlement);
+        env.events().publish((symbol_short!("consume"), id), holder);
+        Ok(entitlement.perk)
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn offer(env: Env, offer_id: u32) -> Result<Offer, RedeemError> {
+        Self::load_offer(&env, offer_id)
+    }
+
+    pub fn entitlement(env: Env, id: u64) -> Result<Entitlement, RedeemError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Entitlement(id))
+            .ok_or(RedeemError::UnknownEntitlement)
+    }
+
+    /// Whether entitlement `id` can still be consumed.
+    pub fn is_usable(env: Env, id: u64) -> bool {
+        Self::entitlement(env.clone(), id).is_ok_and(|e| {
+            e.status == EntitlementStatus::Active && env.ledger().timestamp() < e.expires_at
+        })
+    }
+
+    // ==================== HELPERS ====================
+
+    fn admin(env: &Env) -> Result<Address, RedeemError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(RedeemError::NotInitialized)
+    }
+
+    fn load_offer(env: &Env, offer_id: u32) -> Result<Offer, RedeemError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Offer(offer_id))
+            .ok_or(RedeemError::UnknownOffer)
+    }
+}
+
+mod leaky_token;
+mod nft;
+mod test;
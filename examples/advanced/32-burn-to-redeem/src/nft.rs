@@ -0,0 +1,38 @@
+//! Minimal NFT collection burned for access passes in the tests.
+//!
+//! Only compiled for tests: in a real deployment it would be its own crate
+//! and Wasm. It implements just the two calls the issuer makes, plus `mint`.
+
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum NftKey {
+    Owner(u32),
+}
+
+#[contract]
+pub struct TestNft;
+
+#[contractimpl]
+impl TestNft {
+    pub fn mint(env: Env, to: Address, token_id: u32) {
+        env.storage().instance().set(&NftKey::Owner(token_id), &to);
+    }
+
+    /// Panics once the token is burned.
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        env.storage()
+            .instance()
+            .get(&NftKey::Owner(token_id))
+            .unwrap()
+    }
+
+    pub fn burn(env: Env, from: Address, token_id: u32) {
+        from.require_auth();
+        assert!(Self::owner_of(env.clone(), token_id) == from, "not owner");
+        env.storage().instance().remove(&NftKey::Owner(token_id));
+    }
+}
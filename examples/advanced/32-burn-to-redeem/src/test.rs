@@ -0,0 +1,269 @@
+#![cfg(test)]
+
+use super::*;
+use crate::leaky_token::{LeakyToken, LeakyTokenClient};
+use crate::nft::{TestNft, TestNftClient};
+use redeem_merchant::{Merchant, MerchantClient, MerchantError};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, IntoVal,
+};
+
+const PRICE: i128 = 1_000;
+const BURN: i128 = 500;
+const WEEK: u64 = 7 * 24 * 3_600;
+const NFT_ID: u32 = 7;
+
+struct Setup<'a> {
+    env: Env,
+    issuer: CouponIssuerClient<'a>,
+    merchant: MerchantClient<'a>,
+    points: TokenClient<'a>,
+    nft: TestNftClient<'a>,
+    holder: Address,
+    /// 20% off at the merchant for `BURN` points.
+    discount: u32,
+    /// Access pass at the merchant for one NFT.
+    pass: u32,
+}
+
+/// A holder with 2 000 points, NFT `NFT_ID` and enough money for two
+/// purchases, and one offer of each kind redeemable at the merchant.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let issuer = CouponIssuerClient::new(&env, &env.register_contract(None, CouponIssuer));
+    issuer.initialize(&Address::generate(&env));
+    let nft = TestNftClient::new(&env, &env.register_contract(None, TestNft));
+
+    let asset = |holder: &Address, amount: i128| {
+        let id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        StellarAssetClient::new(&env, &id).mint(holder, &amount);
+        id
+    };
+    let holder = Address::generate(&env);
+    let points = asset(&holder, 2_000);
+    let money = asset(&holder, 2 * PRICE);
+    nft.mint(&holder, &NFT_ID);
+
+    let merchant = MerchantClient::new(&env, &env.register_contract(None, Merchant));
+    merchant.init(&issuer.address, &money, &PRICE, &Address::generate(&env));
+
+    let discount = issuer.add_offer(
+        &BurnAsset::Token(points.clone(), BURN),
+        &Perk::DiscountBps(2_000),
+        &merchant.address,
+        &WEEK,
+    );
+    let pass = issuer.add_offer(
+        &BurnAsset::Nft(nft.address.clone()),
+        &Perk::AccessPass,
+        &merchant.address,
+        &WEEK,
+    );
+    Setup {
+        points: TokenClient::new(&env, &points),
+        env,
+        issuer,
+        merchant,
+        nft,
+        holder,
+        discount,
+        pass,
+    }
+}
+
+#[test]
+fn test_token_burn_issues_entitlement() {
+    let s = setup();
+    let id = s.issuer.redeem(&s.holder, &s.discount, &None);
+    assert_eq!(s.points.balance(&s.holder), 2_000 - BURN);
+    let events = s.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &s.env,
+            (
+                s.issuer.address.clone(),
+                (symbol_short!("redeem"), s.holder.clone()).into_val(&s.env),
+                (s.discount, id).into_val(&s.env),
+            )
+        ]
+    );
+    assert_eq!(
+        s.issuer.entitlement(&id),
+        Entitlement {
+            holder: s.holder.clone(),
+            offer_id: s.discount,
+            perk: Perk::DiscountBps(2_000),
+            consumer: s.merchant.address.clone(),
+            expires_at: 1_000 + WEEK,
+            status: EntitlementStatus::Active,
+        }
+    );
+    assert!(s.issuer.is_usable(&id));
+}
+
+#[test]
+fn test_merchant_consumes_discount_once() {
+    let s = setup();
+    let id = s.issuer.redeem(&s.holder, &s.discount, &None);
+
+    assert_eq!(s.merchant.checkout(&s.holder, &Some(id)), 800);
+    assert_eq!(
+        s.issuer.entitlement(&id).status,
+        EntitlementStatus::Consumed
+    );
+    assert!(!s.issuer.is_usable(&id));
+    assert_eq!(
+        s.merchant.try_checkout(&s.holder, &Some(id)),
+        Err(Ok(MerchantError::CouponRejected))
+    );
+}
+
+#[test]
+fn test_nft_burn_grants_access_pass() {
+    let s = setup();
+    let id = s.issuer.redeem(&s.holder, &s.pass, &Some(NFT_ID));
+    assert!(s.nft.try_owner_of(&NFT_ID).is_err());
+
+    s.merchant.enter(&s.holder, &id);
+    assert!(s.merchant.is_member(&s.holder));
+    assert_eq!(
+        s.issuer.entitlement(&id).status,
+        EntitlementStatus::Consumed
+    );
+}
+
+#[test]
+fn test_nft_redeem_requires_owned_token() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    s.nft.mint(&other, &(NFT_ID + 1));
+
+    assert_eq!(
+        s.issuer.try_redeem(&s.holder, &s.pass, &None),
+        Err(Ok(RedeemError::MissingTokenId))
+    );
+    assert_eq!(
+        s.issuer.try_redeem(&s.holder, &s.pass, &Some(NFT_ID + 1)),
+        Err(Ok(RedeemError::NotOwner))
+    );
+    assert_eq!(s.nft.owner_of(&(NFT_ID + 1)), other);
+}
+
+/// A burn that removes less than the offer asks for issues nothing.
+#[test]
+fn test_unverified_burn_is_refused() {
+    let s = setup();
+    let leaky = LeakyTokenClient::new(&s.env, &s.env.register_contract(None, LeakyToken));
+    leaky.mint(&s.holder, &1_000);
+    let offer = s.issuer.add_offer(
+        &BurnAsset::Token(leaky.address.clone(), 100),
+        &Perk::AccessPass,
+        &s.merchant.address,
+        &WEEK,
+    );
+
+    assert_eq!(
+        s.issuer.try_redeem(&s.holder, &offer, &None),
+        Err(Ok(RedeemError::BurnNotVerified))
+    );
+    assert_eq!(leaky.balance(&s.holder), 1_000);
+    assert_eq!(
+        s.issuer.try_entitlement(&0),
+        Err(Ok(RedeemError::UnknownEntitlement))
+    );
+}
+
+#[test]
+fn test_only_the_consumer_can_consume_for_the_holder() {
+    let s = setup();
+    let id = s.issuer.redeem(&s.holder, &s.discount, &None);
+    let other_shop = Address::generate(&s.env);
+
+    assert_eq!(
+        s.issuer.try_consume(&other_shop, &id, &s.holder),
+        Err(Ok(RedeemError::WrongConsumer))
+    );
+    assert_eq!(
+        s.issuer
+            .try_consume(&s.merchant.address, &id, &Address::generate(&s.env)),
+        Err(Ok(RedeemError::NotHolder))
+    );
+    assert!(s.issuer.is_usable(&id));
+}
+
+#[test]
+fn test_entitlement_expires() {
+    let s = setup();
+    let id = s.issuer.redeem(&s.holder, &s.discount, &None);
+
+    s.env.ledger().with_mut(|l| l.timestamp = 1_000 + WEEK);
+    assert!(!s.issuer.is_usable(&id));
+    assert_eq!(
+        s.issuer.try_consume(&s.merchant.address, &id, &s.holder),
+        Err(Ok(RedeemError::Expired))
+    );
+    assert_eq!(
+        s.merchant.try_checkout(&s.holder, &Some(id)),
+        Err(Ok(MerchantError::CouponRejected))
+    );
+}
+
+#[test]
+fn test_retired_offer_keeps_issued_entitlements() {
+    let s = setup();
+    let id = s.issuer.redeem(&s.holder, &s.discount, &None);
+    s.issuer.retire_offer(&s.discount);
+
+    assert_eq!(
+        s.issuer.try_redeem(&s.holder, &s.discount, &None),
+        Err(Ok(RedeemError::OfferRetired))
+    );
+    assert_eq!(s.merchant.checkout(&s.holder, &Some(id)), 800);
+}
+
+#[test]
+fn test_offer_validation() {
+    let s = setup();
+    let invalid = vec![
+        &s.env,
+        (
+            BurnAsset::Token(s.points.address.clone(), 0),
+            Perk::AccessPass,
+            WEEK,
+        ),
+        (
+            BurnAsset::Nft(s.nft.address.clone()),
+            Perk::DiscountBps(0),
+            WEEK,
+        ),
+        (
+            BurnAsset::Nft(s.nft.address.clone()),
+            Perk::DiscountBps(MAX_BPS + 1),
+            WEEK,
+        ),
+        (BurnAsset::Nft(s.nft.address.clone()), Perk::AccessPass, 0),
+    ];
+    for (burn, perk, valid_for) in invalid.iter() {
+        assert_eq!(
+            s.issuer
+                .try_add_offer(&burn, &perk, &s.merchant.address, &valid_for),
+            Err(Ok(RedeemError::InvalidOffer))
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_redeem_requires_holder_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.issuer.redeem(&s.holder, &s.discount, &None);
+}
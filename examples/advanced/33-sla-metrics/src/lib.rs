@@ -0,0 +1,268 @@
+//! # On-Chain SLA Metrics
+//!
+//! Operators usually learn how a contract is doing from an off-chain
+//! indexer. This example keeps the numbers on-chain instead: each
+//! instrumented function bumps a per-function [`FnMetrics`] counter in
+//! instance storage, `get_metrics` returns them all, and a `metrics` event
+//! goes out once per reporting interval, so a plain event subscription is
+//! enough to chart call volume and failure rate.
+//!
+//! ## What counts as a failure
+//!
+//! A call that returns an error is rolled back, counter writes included.
+//! A contract therefore cannot count its own reverted calls. Here the
+//! failures an operator cares about, such as a withdrawal above the
+//! balance, are *reported* instead: `withdraw` succeeds and returns
+//! [`WithdrawOutcome::InsufficientBalance`], so the failure counter sticks.
+//! Only malformed calls, like a zero amount, still revert, and those are
+//! the caller's bug rather than the service's.
+//!
+//! ## Sampling
+//!
+//! Counting every call writes the metrics map on every call. With
+//! `sample_every = n`, a call is counted with probability `1/n`, and
+//! counted as `n` calls. The totals stay unbiased estimates while only about
+//! one call in `n` pays for the write. `n = 1` counts exactly.
+//!
+//! The sampling draw uses `env.prng()`, which a validator could bias. That
+//! is fine for telemetry; never use it for anything that moves value.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Instance: the `MetricsConfig`.
+    Config,
+    /// Instance: `Map<Symbol, FnMetrics>`, function name to counters.
+    Metrics,
+    /// Instance: ledger of the last `metrics` event.
+    LastReport,
+    /// Persistent: a user's balance.
+    Balance(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MetricsConfig {
+    /// Count one call in this many on average. 1 counts every call.
+    pub sample_every: u32,
+    /// Ledgers between `metrics` events. 0 disables the event.
+    pub report_interval: u32,
+}
+
+/// Counters for one function, since the last `reset_metrics`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FnMetrics {
+    pub calls: u64,
+    /// Calls that reported a failure; always at most `calls`.
+    pub failures: u64,
+}
+
+/// Data of the periodic `metrics` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricsReport {
+    /// Ledger of the previous report, or of `initialize`.
+    pub since_ledger: u32,
+    pub metrics: Map<Symbol, FnMetrics>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WithdrawOutcome {
+    /// Paid out; carries the new balance.
+    Paid(i128),
+    /// Nothing paid; carries the available balance.
+    InsufficientBalance(i128),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MetricsError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// `sample_every` of zero.
+    InvalidConfig = 4,
+}
+
+#[contract]
+pub struct MeteredService;
+
+#[contractimpl]
+impl MeteredService {
+    pub fn initialize(env: Env, admin: Address, config: MetricsConfig) -> Result<(), MetricsError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MetricsError::AlreadyInitialized);
+        }
+        check_config(&config)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage()
+            .instance()
+            .set(&DataKey::LastReport, &env.ledger().sequence());
+        Ok(())
+    }
+
+    // ==================== INSTRUMENTED FUNCTIONS ====================
+
+    /// Add `amount` to `user`'s balance and return the new balance.
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<i128, MetricsError> {
+        user.require_auth();
+        check_amount(amount)?;
+        let balance = Self::balance(env.clone(), user.clone()) + amount;
+        set_balance(&env, &user, balance);
+
+        record(&env, symbol_short!("deposit"), false)?;
+        Ok(balance)
+    }
+
+    /// Take `amount` from `user`'s balance. A shortfall is reported in the
+    /// outcome, not as an error, so it is counted as a failure.
+    pub fn withdraw(
+        env: Env,
+        user: Address,
+        amount: i128,
+    ) -> Result<WithdrawOutcome, MetricsError> {
+        user.require_auth();
+        check_amount(amount)?;
+        let balance = Self::balance(env.clone(), user.clone());
+        let outcome = if amount > balance {
+            WithdrawOutcome::InsufficientBalance(balance)
+        } else {
+            set_balance(&env, &user, balance - amount);
+            WithdrawOutcome::Paid(balance - amount)
+        };
+
+        let failed = matches!(outcome, WithdrawOutcome::InsufficientBalance(_));
+        record(&env, symbol_short!("withdraw"), failed)?;
+        Ok(outcome)
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    // ==================== METRICS ====================
+
+    /// Counters for every function called since the last reset.
+    pub fn get_metrics(env: Env) -> Map<Symbol, FnMetrics> {
+        load_metrics(&env)
+    }
+
+    pub fn metrics_config(env: Env) -> Result<MetricsConfig, MetricsError> {
+        load_config(&env)
+    }
+
+    pub fn set_metrics_config(env: Env, config: MetricsConfig) -> Result<(), MetricsError> {
+        admin(&env)?.require_auth();
+        check_config(&config)?;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Clear every counter, for example at the start of an SLA period.
+    pub fn reset_metrics(env: Env) -> Result<(), MetricsError> {
+        admin(&env)?.require_auth();
+        env.storage().instance().remove(&DataKey::Metrics);
+        env.storage()
+            .instance()
+            .set(&DataKey::LastReport, &env.ledger().sequence());
+        Ok(())
+    }
+}
+
+/// Count one call of `function`, subject to sampling, then publish the
+/// `metrics` event if the reporting interval has passed.
+fn record(env: &Env, function: Symbol, failed: bool) -> Result<(), MetricsError> {
+    let config = load_config(env)?;
+    let sampled =
+        config.sample_every == 1 || env.prng().gen_range::<u64>(0..config.sample_every as u64) == 0;
+    if !sampled {
+        return Ok(());
+    }
+
+    let weight = config.sample_every as u64;
+    let mut metrics = load_metrics(env);
+    let mut counters = metrics.get(function.clone()).unwrap_or_default();
+    counters.calls += weight;
+    if failed {
+        counters.failures += weight;
+    }
+    metrics.set(function, counters);
+    env.storage().instance().set(&DataKey::Metrics, &metrics);
+
+    // Checked only on sampled calls, so with sampling the event may come a
+    // few calls late; it never comes early.
+    let last: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastReport)
+        .unwrap_or(0);
+    let now = env.ledger().sequence();
+    if config.report_interval > 0 && now - last >= config.report_interval {
+        env.events().publish(
+            (symbol_short!("metrics"),),
+            MetricsReport {
+                since_ledger: last,
+                metrics,
+            },
+        );
+        env.storage().instance().set(&DataKey::LastReport, &now);
+    }
+    Ok(())
+}
+
+fn admin(env: &Env) -> Result<Address, MetricsError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(MetricsError::NotInitialized)
+}
+
+fn load_config(env: &Env) -> Result<MetricsConfig, MetricsError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(MetricsError::NotInitialized)
+}
+
+fn load_metrics(env: &Env) -> Map<Symbol, FnMetrics> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Metrics)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+fn set_balance(env: &Env, user: &Address, balance: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(user.clone()), &balance);
+}
+
+fn check_config(config: &MetricsConfig) -> Result<(), MetricsError> {
+    if config.sample_every == 0 {
+        return Err(MetricsError::InvalidConfig);
+    }
+    Ok(())
+}
+
+fn check_amount(amount: i128) -> Result<(), MetricsError> {
+    if amount <= 0 {
+        return Err(MetricsError::InvalidAmount);
+    }
+    Ok(())
+}
+
+mod test;
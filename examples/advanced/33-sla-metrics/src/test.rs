@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    map,
+    testutils::{Address as _, Events, Ledger},
+    vec, IntoVal, TryFromVal,
+};
+
+struct Setup {
+    env: Env,
+    client: MeteredServiceClient<'static>,
+    alice: Address,
+}
+
+fn setup(sample_every: u32, report_interval: u32) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = MeteredServiceClient::new(&env, &env.register_contract(None, MeteredService));
+    client.initialize(
+        &Address::generate(&env),
+        &MetricsConfig {
+            sample_every,
+            report_interval,
+        },
+    );
+    Setup {
+        alice: Address::generate(&env),
+        env,
+        client,
+    }
+}
+
+fn counters(calls: u64, failures: u64) -> FnMetrics {
+    FnMetrics { calls, failures }
+}
+
+#[test]
+fn test_counts_calls_per_function() {
+    let t = setup(1, 0);
+    t.client.deposit(&t.alice, &100);
+    t.client.deposit(&t.alice, &50);
+    assert_eq!(t.client.withdraw(&t.alice, &30), WithdrawOutcome::Paid(120));
+
+    assert_eq!(
+        t.client.get_metrics(),
+        map![
+            &t.env,
+            (symbol_short!("deposit"), counters(2, 0)),
+            (symbol_short!("withdraw"), counters(1, 0)),
+        ]
+    );
+}
+
+#[test]
+fn test_reported_failures_are_counted() {
+    let t = setup(1, 0);
+    t.client.deposit(&t.alice, &10);
+    assert_eq!(
+        t.client.withdraw(&t.alice, &11),
+        WithdrawOutcome::InsufficientBalance(10)
+    );
+    t.client.withdraw(&t.alice, &10);
+
+    assert_eq!(t.client.balance(&t.alice), 0);
+    assert_eq!(
+        t.client.get_metrics().get(symbol_short!("withdraw")),
+        Some(counters(2, 1))
+    );
+}
+
+#[test]
+fn test_reverted_calls_leave_no_trace() {
+    let t = setup(1, 0);
+    t.client.deposit(&t.alice, &10);
+    assert_eq!(
+        t.client.try_deposit(&t.alice, &0),
+        Err(Ok(MetricsError::InvalidAmount))
+    );
+    // The error rolled back everything the call did, counters included.
+    assert_eq!(
+        t.client.get_metrics().get(symbol_short!("deposit")),
+        Some(counters(1, 0))
+    );
+}
+
+#[test]
+fn test_sampled_counts_are_weighted_estimates() {
+    let t = setup(4, 0);
+    for _ in 0..400 {
+        t.client.deposit(&t.alice, &1);
+    }
+    let calls = t
+        .client
+        .get_metrics()
+        .get(symbol_short!("deposit"))
+        .unwrap()
+        .calls;
+    // Each sampled call counts as 4, so the estimate moves in steps of 4.
+    assert_eq!(calls % 4, 0);
+    assert!(
+        (300..=500).contains(&calls),
+        "estimate {calls} far from 400"
+    );
+    // The balance itself is exact.
+    assert_eq!(t.client.balance(&t.alice), 400);
+}
+
+#[test]
+fn test_metrics_event_once_per_interval() {
+    let t = setup(1, 100);
+    t.env.ledger().set_sequence_number(50);
+    t.client.deposit(&t.alice, &10);
+    // Only 50 ledgers since initialize: no report yet.
+    assert_eq!(t.env.events().all().len(), 0);
+
+    t.env.ledger().set_sequence_number(100);
+    t.client.withdraw(&t.alice, &20);
+    assert_eq!(
+        t.env.events().all(),
+        vec![
+            &t.env,
+            (
+                t.client.address.clone(),
+                (symbol_short!("metrics"),).into_val(&t.env),
+                MetricsReport {
+                    since_ledger: 0,
+                    metrics: map![
+                        &t.env,
+                        (symbol_short!("deposit"), counters(1, 0)),
+                        (symbol_short!("withdraw"), counters(1, 1)),
+                    ],
+                }
+                .into_val(&t.env),
+            ),
+        ]
+    );
+
+    // The next report covers the 100 ledgers after this one.
+    t.env.ledger().set_sequence_number(199);
+    t.client.deposit(&t.alice, &10);
+    t.env.ledger().set_sequence_number(200);
+    t.client.deposit(&t.alice, &10);
+    let (_, _, data) = t.env.events().all().last().unwrap();
+    let report = MetricsReport::try_from_val(&t.env, &data).unwrap();
+    assert_eq!(report.since_ledger, 100);
+    assert_eq!(
+        report.metrics.get(symbol_short!("deposit")),
+        Some(counters(3, 0))
+    );
+}
+
+#[test]
+fn test_reset_metrics() {
+    let t = setup(1, 0);
+    t.client.deposit(&t.alice, &10);
+    t.client.reset_metrics();
+    assert_eq!(t.client.get_metrics(), Map::new(&t.env));
+
+    t.client.deposit(&t.alice, &10);
+    assert_eq!(
+        t.client.get_metrics().get(symbol_short!("deposit")),
+        Some(counters(1, 0))
+    );
+}
+
+#[test]
+fn test_config_is_validated_and_admin_only() {
+    let t = setup(1, 0);
+    let config = MetricsConfig {
+        sample_every: 0,
+        report_interval: 10,
+    };
+    assert_eq!(
+        t.client.try_set_metrics_config(&config),
+        Err(Ok(MetricsError::InvalidConfig))
+    );
+
+    let config = MetricsConfig {
+        sample_every: 8,
+        report_interval: 10,
+    };
+    t.client.set_metrics_config(&config);
+    assert_eq!(t.client.metrics_config(), config);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_reset_metrics_requires_admin_auth() {
+    let t = setup(1, 0);
+    t.env.set_auths(&[]);
+    t.client.reset_metrics();
+}
@@ -0,0 +1,326 @@
+//! # Allowlisted Relayer Set
+//!
+//! Forwarders and bridges accept operations only from a known set of
+//! relayers. Keeping that set honest takes two mechanisms, both in this
+//! contract:
+//!
+//! - **Heartbeat expiry.** A relayer that goes silent for longer than
+//!   `heartbeat_ledgers` stops being accepted, immediately and without
+//!   anyone having to act. Any accepted call counts as a heartbeat, so a
+//!   busy relayer never needs to send a separate one. See `relayers`.
+//! - **Threshold-gated rotation.** Relayers are added and removed, and the
+//!   threshold changed, only by a `Rotation` that `threshold` active
+//!   relayers approve, each in their own transaction.
+//!
+//! `submit` stands in for the forwarder's or bridge's relayed entrypoint:
+//! it accepts an operation id from an active relayer and emits it.
+//!
+//! ## Rotation races
+//!
+//! Two rotations proposed against the same set can both gather approvals.
+//! Every rotation records the set's `epoch` when proposed, and applying a
+//! rotation bumps the epoch, so the one that lands second is `StaleRotation`
+//! and has to be proposed again against the new set.
+//!
+//! Approvals are stored as addresses and counted when the threshold is
+//! checked, against the relayers active at that moment. An approver that
+//! has expired or been rotated out since approving no longer counts.
+//!
+//! ## Recovery
+//!
+//! If so many relayers go silent that fewer than `threshold` are active, no
+//! rotation can pass. `recover` lets the admin replace the whole set. It
+//! bumps the epoch like a rotation and emits `(recovered,)`, so the use of
+//! this escape hatch is always visible.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map,
+    Vec,
+};
+
+mod relayers;
+
+use relayers::{
+    active_relayers, check_threshold, is_fresh, prune_expired, read_config, read_relayers,
+    require_active, write_relayers,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Instance: the `RelayerConfig`.
+    Config,
+    /// Instance: `Map<Address, u32>`, relayer to last heartbeat ledger.
+    Relayers,
+    /// Instance: bumped by every applied rotation or recovery.
+    Epoch,
+    /// Instance: id of the next rotation.
+    NextRotation,
+    /// Persistent: a pending rotation; removed once applied.
+    Rotation(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RelayerConfig {
+    /// Ledgers a relayer stays active after its last heartbeat.
+    pub heartbeat_ledgers: u32,
+    /// Active relayers needed to approve a rotation.
+    pub threshold: u32,
+}
+
+/// A proposed change to the relayer set.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rotation {
+    /// The set's epoch when proposed; stale once the epoch moves on.
+    pub epoch: u32,
+    pub add: Vec<Address>,
+    pub remove: Vec<Address>,
+    /// Threshold after the rotation.
+    pub threshold: u32,
+    pub approvals: Vec<Address>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RelayerError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// Not in the set, or silent for longer than `heartbeat_ledgers`.
+    NotRelayer = 3,
+    /// Zero, or more than the set would hold.
+    InvalidThreshold = 4,
+    TooManyRelayers = 5,
+    /// A `heartbeat_ledgers` of zero.
+    InvalidConfig = 6,
+    RotationNotFound = 7,
+    /// Another rotation or a recovery changed the set since this proposal.
+    StaleRotation = 8,
+    AlreadyApproved = 9,
+}
+
+#[contract]
+pub struct RelayerSet;
+
+#[contractimpl]
+impl RelayerSet {
+    /// Start with `relayers`, all counted as just heard from.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        relayers: Vec<Address>,
+        config: RelayerConfig,
+    ) -> Result<(), RelayerError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(RelayerError::AlreadyInitialized);
+        }
+        if config.heartbeat_ledgers == 0 {
+            return Err(RelayerError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::Epoch, &0u32);
+        replace_set(&env, relayers, config.threshold)
+    }
+
+    // ==================== RELAYING ====================
+
+    /// Accept operation `op_id` from `relayer`. Counts as a heartbeat.
+    ///
+    /// Emits `(relayed, relayer)` with the operation id.
+    pub fn submit(env: Env, relayer: Address, op_id: BytesN<32>) -> Result<(), RelayerError> {
+        relayer.require_auth();
+        require_active(&env, &relayer)?;
+        env.events()
+            .publish((symbol_short!("relayed"), relayer), op_id);
+        Ok(())
+    }
+
+    /// Keep `relayer` active without submitting anything.
+    pub fn heartbeat(env: Env, relayer: Address) -> Result<(), RelayerError> {
+        relayer.require_auth();
+        require_active(&env, &relayer)
+    }
+
+    /// Remove every expired relayer. Anyone may call this.
+    pub fn prune(env: Env) -> Result<Vec<Address>, RelayerError> {
+        prune_expired(&env)
+    }
+
+    // ==================== ROTATION ====================
+
+    /// Propose a change to the set. The proposer's approval is counted, and
+    /// the rotation applies at once if that meets the threshold.
+    pub fn propose_rotation(
+        env: Env,
+        proposer: Address,
+        add: Vec<Address>,
+        remove: Vec<Address>,
+        threshold: u32,
+    ) -> Result<u32, RelayerError> {
+        proposer.require_auth();
+        require_active(&env, &proposer)?;
+        check_threshold(rotated_set(&env, &add, &remove).len(), threshold)?;
+
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRotation)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRotation, &(id + 1));
+        let rotation = Rotation {
+            epoch: Self::epoch(env.clone()),
+            add,
+            remove,
+            threshold,
+            approvals: Vec::from_array(&env, [proposer]),
+        };
+
+        env.events().publish((symbol_short!("rot_prop"),), id);
+        apply_or_store(&env, id, rotation)?;
+        Ok(id)
+    }
+
+    /// Approve rotation `id`, applying it if this meets the threshold.
+    pub fn approve_rotation(env: Env, relayer: Address, id: u32) -> Result<(), RelayerError> {
+        relayer.require_auth();
+        require_active(&env, &relayer)?;
+        let mut rotation = Self::rotation(env.clone(), id).ok_or(RelayerError::RotationNotFound)?;
+        if rotation.epoch != Self::epoch(env.clone()) {
+            return Err(RelayerError::StaleRotation);
+        }
+        if rotation.approvals.contains(&relayer) {
+            return Err(RelayerError::AlreadyApproved);
+        }
+        rotation.approvals.push_back(relayer);
+        apply_or_store(&env, id, rotation)
+    }
+
+    /// Replace the whole set. Admin only; for when fewer than `threshold`
+    /// relayers are still active and no rotation can pass.
+    ///
+    /// Emits `(recovered,)` with the new epoch.
+    pub fn recover(env: Env, relayers: Vec<Address>, threshold: u32) -> Result<(), RelayerError> {
+        admin(&env)?.require_auth();
+        replace_set(&env, relayers, threshold)?;
+        let epoch = bump_epoch(&env);
+        env.events().publish((symbol_short!("recovered"),), epoch);
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    /// Relayers currently accepted, leaving out expired ones.
+    pub fn relayers(env: Env) -> Result<Vec<Address>, RelayerError> {
+        active_relayers(&env)
+    }
+
+    pub fn is_active(env: Env, relayer: Address) -> bool {
+        let Ok(config) = read_config(&env) else {
+            return false;
+        };
+        read_relayers(&env)
+            .get(relayer)
+            .is_some_and(|last_seen| is_fresh(&env, &config, last_seen))
+    }
+
+    /// Ledger of `relayer`'s last heartbeat, while it is in the set.
+    pub fn last_heartbeat(env: Env, relayer: Address) -> Option<u32> {
+        read_relayers(&env).get(relayer)
+    }
+
+    pub fn config(env: Env) -> Result<RelayerConfig, RelayerError> {
+        read_config(&env)
+    }
+
+    pub fn epoch(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Epoch).unwrap_or(0)
+    }
+
+    pub fn rotation(env: Env, id: u32) -> Option<Rotation> {
+        env.storage().persistent().get(&DataKey::Rotation(id))
+    }
+}
+
+fn admin(env: &Env) -> Result<Address, RelayerError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(RelayerError::NotInitialized)
+}
+
+/// Apply `rotation` if enough of its approvers are active right now;
+/// otherwise store it for more approvals.
+///
+/// Emits `(rotated,)` with the new epoch when applied.
+fn apply_or_store(env: &Env, id: u32, rotation: Rotation) -> Result<(), RelayerError> {
+    let active = active_relayers(env)?;
+    let approved = rotation
+        .approvals
+        .iter()
+        .filter(|approver| active.contains(approver))
+        .count() as u32;
+    if approved < read_config(env)?.threshold {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rotation(id), &rotation);
+        return Ok(());
+    }
+
+    // Expired relayers are dropped, not carried into the new set.
+    prune_expired(env)?;
+    let relayers = rotated_set(env, &rotation.add, &rotation.remove);
+    check_threshold(relayers.len(), rotation.threshold)?;
+    write_relayers(env, &relayers);
+    set_threshold(env, rotation.threshold)?;
+    env.storage().persistent().remove(&DataKey::Rotation(id));
+
+    let epoch = bump_epoch(env);
+    env.events().publish((symbol_short!("rotated"),), epoch);
+    Ok(())
+}
+
+/// The set after removing `remove` and adding `add`. Added relayers count
+/// as just heard from; everyone else keeps their heartbeat.
+fn rotated_set(env: &Env, add: &Vec<Address>, remove: &Vec<Address>) -> Map<Address, u32> {
+    let mut relayers = read_relayers(env);
+    for relayer in remove.iter() {
+        relayers.remove(relayer);
+    }
+    for relayer in add.iter() {
+        relayers.set(relayer, env.ledger().sequence());
+    }
+    relayers
+}
+
+fn replace_set(env: &Env, relayers: Vec<Address>, threshold: u32) -> Result<(), RelayerError> {
+    let mut set = Map::new(env);
+    for relayer in relayers.iter() {
+        set.set(relayer, env.ledger().sequence());
+    }
+    check_threshold(set.len(), threshold)?;
+    write_relayers(env, &set);
+    set_threshold(env, threshold)
+}
+
+fn set_threshold(env: &Env, threshold: u32) -> Result<(), RelayerError> {
+    let mut config = read_config(env)?;
+    config.threshold = threshold;
+    env.storage().instance().set(&DataKey::Config, &config);
+    Ok(())
+}
+
+fn bump_epoch(env: &Env) -> u32 {
+    let epoch = RelayerSet::epoch(env.clone()) + 1;
+    env.storage().instance().set(&DataKey::Epoch, &epoch);
+    epoch
+}
+
+mod test;
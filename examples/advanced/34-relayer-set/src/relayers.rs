@@ -0,0 +1,102 @@
+//! The relayer set and its heartbeats.
+//!
+//! `Relayers` maps each relayer to the ledger of its last heartbeat. A
+//! relayer is active while that ledger is at most `heartbeat_ledgers` old.
+//! Every check goes through `require_active`, so a silent relayer loses its
+//! permissions the moment its window runs out, before anyone prunes it.
+//! `prune_expired` only tidies the map and announces who dropped out.
+//!
+//! Any accepted call from a relayer, `submit` included, counts as a
+//! heartbeat. An expired relayer cannot revive itself; it has to be added
+//! back by a rotation.
+
+use soroban_sdk::{symbol_short, Address, Env, Map, Vec};
+
+use crate::{DataKey, RelayerConfig, RelayerError};
+
+/// Upper bound on relayers, which bounds every loop over the set.
+pub(crate) const MAX_RELAYERS: u32 = 20;
+
+pub(crate) fn read_config(env: &Env) -> Result<RelayerConfig, RelayerError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(RelayerError::NotInitialized)
+}
+
+/// Every relayer in the set, with its last heartbeat ledger. Includes
+/// expired relayers that have not been pruned yet.
+pub(crate) fn read_relayers(env: &Env) -> Map<Address, u32> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Relayers)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+pub(crate) fn write_relayers(env: &Env, relayers: &Map<Address, u32>) {
+    env.storage().instance().set(&DataKey::Relayers, relayers);
+}
+
+/// Whether a heartbeat at `last_seen` still keeps a relayer active.
+pub(crate) fn is_fresh(env: &Env, config: &RelayerConfig, last_seen: u32) -> bool {
+    env.ledger().sequence() <= last_seen.saturating_add(config.heartbeat_ledgers)
+}
+
+/// The relayers whose heartbeat is still fresh.
+pub(crate) fn active_relayers(env: &Env) -> Result<Vec<Address>, RelayerError> {
+    let config = read_config(env)?;
+    let mut active = Vec::new(env);
+    for (relayer, last_seen) in read_relayers(env).iter() {
+        if is_fresh(env, &config, last_seen) {
+            active.push_back(relayer);
+        }
+    }
+    Ok(active)
+}
+
+/// Fail with `NotRelayer` unless `relayer` is in the set and active, then
+/// record a heartbeat for it. The caller still has to `require_auth`.
+pub(crate) fn require_active(env: &Env, relayer: &Address) -> Result<(), RelayerError> {
+    let config = read_config(env)?;
+    let mut relayers = read_relayers(env);
+    match relayers.get(relayer.clone()) {
+        Some(last_seen) if is_fresh(env, &config, last_seen) => {
+            relayers.set(relayer.clone(), env.ledger().sequence());
+            write_relayers(env, &relayers);
+            Ok(())
+        }
+        _ => Err(RelayerError::NotRelayer),
+    }
+}
+
+/// Remove every expired relayer and return them.
+///
+/// Emits `(expired, relayer)` with the last heartbeat ledger for each.
+pub(crate) fn prune_expired(env: &Env) -> Result<Vec<Address>, RelayerError> {
+    let config = read_config(env)?;
+    let mut relayers = read_relayers(env);
+    let mut removed = Vec::new(env);
+    for (relayer, last_seen) in relayers.clone().iter() {
+        if !is_fresh(env, &config, last_seen) {
+            relayers.remove(relayer.clone());
+            env.events()
+                .publish((symbol_short!("expired"), relayer.clone()), last_seen);
+            removed.push_back(relayer);
+        }
+    }
+    if !removed.is_empty() {
+        write_relayers(env, &relayers);
+    }
+    Ok(removed)
+}
+
+/// Check that `threshold` works for a set of `size` relayers.
+pub(crate) fn check_threshold(size: u32, threshold: u32) -> Result<(), RelayerError> {
+    if size > MAX_RELAYERS {
+        return Err(RelayerError::TooManyRelayers);
+    }
+    if threshold == 0 || threshold > size {
+        return Err(RelayerError::InvalidThreshold);
+    }
+    Ok(())
+}
@@ -0,0 +1,297 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, IntoVal,
+};
+
+/// Ledgers a relayer stays active without a heartbeat.
+const WINDOW: u32 = 100;
+
+struct Setup {
+    env: Env,
+    client: RelayerSetClient<'static>,
+    /// The initial relayers, with a threshold of 2.
+    r: [Address; 3],
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = RelayerSetClient::new(&env, &env.register_contract(None, RelayerSet));
+    let r = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.initialize(
+        &Address::generate(&env),
+        &vec![&env, r[0].clone(), r[1].clone(), r[2].clone()],
+        &RelayerConfig {
+            heartbeat_ledgers: WINDOW,
+            threshold: 2,
+        },
+    );
+    Setup { env, client, r }
+}
+
+impl Setup {
+    fn at(&self, ledger: u32) {
+        self.env.ledger().set_sequence_number(ledger);
+    }
+
+    fn op(&self, n: u8) -> BytesN<32> {
+        BytesN::from_array(&self.env, &[n; 32])
+    }
+}
+
+// ==================== RELAYING AND HEARTBEATS ====================
+
+#[test]
+fn test_only_relayers_submit() {
+    let t = setup();
+    t.client.submit(&t.r[0], &t.op(1));
+    assert_eq!(
+        t.env.events().all(),
+        vec![
+            &t.env,
+            (
+                t.client.address.clone(),
+                (symbol_short!("relayed"), t.r[0].clone()).into_val(&t.env),
+                t.op(1).into_val(&t.env),
+            ),
+        ]
+    );
+
+    let outsider = Address::generate(&t.env);
+    assert_eq!(
+        t.client.try_submit(&outsider, &t.op(2)),
+        Err(Ok(RelayerError::NotRelayer))
+    );
+}
+
+#[test]
+fn test_silent_relayer_expires_without_pruning() {
+    let t = setup();
+    t.at(WINDOW);
+    t.client.submit(&t.r[0], &t.op(1));
+
+    // One ledger past the window: rejected, though still in the map.
+    t.at(WINDOW + 1);
+    assert!(!t.client.is_active(&t.r[1]));
+    assert_eq!(t.client.last_heartbeat(&t.r[1]), Some(0));
+    assert_eq!(
+        t.client.try_submit(&t.r[1], &t.op(2)),
+        Err(Ok(RelayerError::NotRelayer))
+    );
+    // An expired relayer cannot revive itself.
+    assert_eq!(
+        t.client.try_heartbeat(&t.r[1]),
+        Err(Ok(RelayerError::NotRelayer))
+    );
+    assert_eq!(t.client.relayers(), vec![&t.env, t.r[0].clone()]);
+}
+
+#[test]
+fn test_heartbeat_and_submit_keep_relayer_active() {
+    let t = setup();
+    t.at(90);
+    t.client.heartbeat(&t.r[1]);
+    t.client.submit(&t.r[2], &t.op(1));
+    t.at(190);
+    assert!(t.client.is_active(&t.r[1]));
+    assert!(t.client.is_active(&t.r[2]));
+    assert!(!t.client.is_active(&t.r[0]));
+    assert_eq!(t.client.last_heartbeat(&t.r[1]), Some(90));
+}
+
+#[test]
+fn test_prune_removes_expired() {
+    let t = setup();
+    t.at(50);
+    t.client.heartbeat(&t.r[0]);
+    t.at(WINDOW + 1);
+
+    let removed = t.client.prune();
+    assert_eq!(removed.len(), 2);
+    assert!(removed.contains(&t.r[1]) && removed.contains(&t.r[2]));
+    assert_eq!(t.client.last_heartbeat(&t.r[1]), None);
+    assert_eq!(t.client.relayers(), vec![&t.env, t.r[0].clone()]);
+    assert_eq!(t.client.prune().len(), 0);
+}
+
+// ==================== ROTATION ====================
+
+#[test]
+fn test_rotation_needs_threshold_approvals() {
+    let t = setup();
+    let new = Address::generate(&t.env);
+    let id = t.client.propose_rotation(
+        &t.r[0],
+        &vec![&t.env, new.clone()],
+        &vec![&t.env, t.r[2].clone()],
+        &2,
+    );
+    // One approval of two: still pending.
+    assert!(t.client.rotation(&id).is_some());
+    assert_eq!(
+        t.client.try_submit(&new, &t.op(1)),
+        Err(Ok(RelayerError::NotRelayer))
+    );
+    assert_eq!(
+        t.client.try_approve_rotation(&t.r[0], &id),
+        Err(Ok(RelayerError::AlreadyApproved))
+    );
+
+    t.client.approve_rotation(&t.r[1], &id);
+    assert_eq!(t.client.rotation(&id), None);
+    assert_eq!(t.client.epoch(), 1);
+    t.client.submit(&new, &t.op(1));
+    assert_eq!(
+        t.client.try_submit(&t.r[2], &t.op(2)),
+        Err(Ok(RelayerError::NotRelayer))
+    );
+}
+
+#[test]
+fn test_rotation_changes_threshold() {
+    let t = setup();
+    let id = t
+        .client
+        .propose_rotation(&t.r[0], &vec![&t.env], &vec![&t.env], &3);
+    t.client.approve_rotation(&t.r[1], &id);
+    assert_eq!(t.client.config().threshold, 3);
+
+    assert_eq!(
+        t.client
+            .try_propose_rotation(&t.r[0], &vec![&t.env], &vec![&t.env], &4),
+        Err(Ok(RelayerError::InvalidThreshold))
+    );
+    assert_eq!(
+        t.client
+            .try_propose_rotation(&t.r[0], &vec![&t.env], &vec![&t.env], &0),
+        Err(Ok(RelayerError::InvalidThreshold))
+    );
+}
+
+#[test]
+fn test_competing_rotations_second_is_stale() {
+    let t = setup();
+    let a = Address::generate(&t.env);
+    let b = Address::generate(&t.env);
+    let first = t
+        .client
+        .propose_rotation(&t.r[0], &vec![&t.env, a.clone()], &vec![&t.env], &2);
+    let second = t
+        .client
+        .propose_rotation(&t.r[1], &vec![&t.env, b.clone()], &vec![&t.env], &2);
+
+    // Both were proposed at epoch 0; the first to reach threshold wins.
+    t.client.approve_rotation(&t.r[2], &first);
+    assert_eq!(
+        t.client.try_approve_rotation(&t.r[2], &second),
+        Err(Ok(RelayerError::StaleRotation))
+    );
+    assert!(t.client.is_active(&a));
+    assert!(!t.client.is_active(&b));
+}
+
+#[test]
+fn test_expired_approval_does_not_count() {
+    let t = setup();
+    let new = Address::generate(&t.env);
+    // r0 approves by proposing at ledger 0, then goes silent.
+    let id = t
+        .client
+        .propose_rotation(&t.r[0], &vec![&t.env, new.clone()], &vec![&t.env], &2);
+
+    t.at(60);
+    t.client.heartbeat(&t.r[1]);
+    t.client.heartbeat(&t.r[2]);
+
+    // At ledger 101 r0 has expired: r1's approval makes only one live.
+    t.at(WINDOW + 1);
+    t.client.approve_rotation(&t.r[1], &id);
+    assert!(t.client.rotation(&id).is_some());
+    assert!(!t.client.is_active(&new));
+
+    // A second live approval applies it, and the expired r0 is dropped.
+    t.client.approve_rotation(&t.r[2], &id);
+    assert!(t.client.is_active(&new));
+    assert_eq!(t.client.last_heartbeat(&t.r[0]), None);
+    assert_eq!(t.client.relayers().len(), 3);
+}
+
+#[test]
+fn test_unknown_rotation() {
+    let t = setup();
+    assert_eq!(
+        t.client.try_approve_rotation(&t.r[0], &7),
+        Err(Ok(RelayerError::RotationNotFound))
+    );
+}
+
+// ==================== RECOVERY ====================
+
+#[test]
+fn test_recover_after_mass_expiry() {
+    let t = setup();
+    t.at(WINDOW + 1);
+    // Nobody is active, so no rotation can even be proposed.
+    assert_eq!(
+        t.client
+            .try_propose_rotation(&t.r[0], &vec![&t.env], &vec![&t.env], &1),
+        Err(Ok(RelayerError::NotRelayer))
+    );
+
+    let fresh = Address::generate(&t.env);
+    t.client.recover(&vec![&t.env, fresh.clone()], &1);
+    assert_eq!(t.client.relayers(), vec![&t.env, fresh.clone()]);
+    assert_eq!(t.client.epoch(), 1);
+    t.client.submit(&fresh, &t.op(1));
+}
+
+#[test]
+fn test_recover_makes_pending_rotations_stale() {
+    let t = setup();
+    let id = t
+        .client
+        .propose_rotation(&t.r[0], &vec![&t.env], &vec![&t.env], &1);
+    t.client
+        .recover(&vec![&t.env, t.r[0].clone(), t.r[1].clone()], &2);
+    assert_eq!(
+        t.client.try_approve_rotation(&t.r[1], &id),
+        Err(Ok(RelayerError::StaleRotation))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_recover_requires_admin_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client.recover(&vec![&t.env, t.r[0].clone()], &1);
+}
+
+#[test]
+fn test_initialize_validates_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = RelayerSetClient::new(&env, &env.register_contract(None, RelayerSet));
+    let admin = Address::generate(&env);
+    let one = vec![&env, Address::generate(&env)];
+    let config = |heartbeat_ledgers, threshold| RelayerConfig {
+        heartbeat_ledgers,
+        threshold,
+    };
+    assert_eq!(
+        client.try_initialize(&admin, &one, &config(WINDOW, 2)),
+        Err(Ok(RelayerError::InvalidThreshold))
+    );
+    assert_eq!(
+        client.try_initialize(&admin, &one, &config(0, 1)),
+        Err(Ok(RelayerError::InvalidConfig))
+    );
+    client.initialize(&admin, &one, &config(WINDOW, 1));
+}
@@ -0,0 +1,282 @@
+//! # Deposit Receipt NFTs
+//!
+//! A share vault whose positions are NFTs. Each deposit mints a new token
+//! recording the shares it bought; whoever owns the token can withdraw it,
+//! which burns the token and pays out the shares' current value:
+//!
+//! ```text
+//! deposit(alice, 100)      → #0  { shares: 100, deposited: 100 }
+//! harvest(50)                      vault earns yield; no shares minted
+//! transfer(alice, bob, #0)         the position changes hands
+//! withdraw(bob, #0)        → 150   #0 burned, its shares redeemed
+//! ```
+//!
+//! ## Two layers
+//!
+//! The vault's accounting stays fungible. `total_assets` and `total_shares`
+//! set the share price, and every share is worth the same no matter which
+//! NFT holds it. The NFT layer only records *who* holds *which* shares:
+//!
+//! - Transfers move ownership and nothing else. They never touch the
+//!   vault's totals or the price.
+//! - A position's value is always computed, never stored:
+//!   `shares * total_assets / total_shares`. Yield reaches every position,
+//!   including ones that changed hands, without a write per position.
+//! - The recorded entry data (`deposited`, `entry_ledger`) is history for
+//!   the holder, e.g. to work out profit. The vault never pays from it.
+//!
+//! One NFT per deposit, rather than adding to an existing position, keeps
+//! each receipt's entry data exact and lets positions be sold separately.
+//!
+//! ## Share math
+//!
+//! `shares = amount * total_shares / total_assets`, rounded down, and
+//! `assets = shares * total_assets / total_shares`, rounded down. Both
+//! round in the vault's favour, so no sequence of deposits and withdrawals
+//! can take out more than was put in. The first deposit mints shares 1:1.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Token,
+    /// Instance: the vault's fungible totals.
+    Totals,
+    /// Instance: id of the next position NFT.
+    NextId,
+    /// Persistent: a live position; removed when burned.
+    Position(u32),
+    /// Persistent: how many positions an address holds.
+    Balance(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Totals {
+    /// Tokens the vault holds for depositors, yield included.
+    pub total_assets: i128,
+    /// Shares held by all live positions.
+    pub total_shares: i128,
+}
+
+/// A position NFT: the shares it holds and how it was opened.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub owner: Address,
+    pub shares: i128,
+    /// Tokens paid in when the position was opened.
+    pub deposited: i128,
+    pub entry_ledger: u32,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VaultError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// The deposit is too small to buy a single share.
+    ZeroShares = 4,
+    /// No live position with this id; never minted or already burned.
+    NotFound = 5,
+    NotOwner = 6,
+}
+
+#[contract]
+pub struct PositionVault;
+
+#[contractimpl]
+impl PositionVault {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), VaultError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    // ==================== VAULT ====================
+
+    /// Deposit `amount` and mint a position NFT for the shares it buys.
+    /// Returns the new token id.
+    ///
+    /// Emits `(mint, owner)` with `(token_id, shares)`.
+    pub fn deposit(env: Env, owner: Address, amount: i128) -> Result<u32, VaultError> {
+        owner.require_auth();
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let mut totals = Self::totals(env.clone());
+        let shares = if totals.total_shares == 0 {
+            amount
+        } else {
+            amount * totals.total_shares / totals.total_assets
+        };
+        if shares == 0 {
+            return Err(VaultError::ZeroShares);
+        }
+
+        token_client(&env)?.transfer(&owner, &env.current_contract_address(), &amount);
+        totals.total_assets += amount;
+        totals.total_shares += shares;
+        env.storage().instance().set(&DataKey::Totals, &totals);
+
+        let token_id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextId, &(token_id + 1));
+        let position = Position {
+            owner: owner.clone(),
+            shares,
+            deposited: amount,
+            entry_ledger: env.ledger().sequence(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Position(token_id), &position);
+        add_balance(&env, &owner, 1);
+
+        env.events()
+            .publish((symbol_short!("mint"), owner), (token_id, shares));
+        Ok(token_id)
+    }
+
+    /// Burn position `token_id` and pay its shares' current value to its
+    /// owner. Returns the amount paid.
+    ///
+    /// Emits `(burn, owner)` with `(token_id, assets)`.
+    pub fn withdraw(env: Env, owner: Address, token_id: u32) -> Result<i128, VaultError> {
+        owner.require_auth();
+        let position = owned_position(&env, &owner, token_id)?;
+        let mut totals = Self::totals(env.clone());
+        let assets = position.shares * totals.total_assets / totals.total_shares;
+
+        totals.total_assets -= assets;
+        totals.total_shares -= position.shares;
+        env.storage().instance().set(&DataKey::Totals, &totals);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Position(token_id));
+        add_balance(&env, &owner, -1);
+        token_client(&env)?.transfer(&env.current_contract_address(), &owner, &assets);
+
+        env.events()
+            .publish((symbol_short!("burn"), owner), (token_id, assets));
+        Ok(assets)
+    }
+
+    /// Add `amount` to the vault's assets without minting shares, raising
+    /// the value of every position. Stands in for a strategy's yield.
+    ///
+    /// Emits `(harvest,)` with the amount.
+    pub fn harvest(env: Env, from: Address, amount: i128) -> Result<(), VaultError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let mut totals = Self::totals(env.clone());
+        if totals.total_shares == 0 {
+            // Nobody to credit; it would go to the next depositor.
+            return Err(VaultError::ZeroShares);
+        }
+        token_client(&env)?.transfer(&from, &env.current_contract_address(), &amount);
+        totals.total_assets += amount;
+        env.storage().instance().set(&DataKey::Totals, &totals);
+
+        env.events().publish((symbol_short!("harvest"),), amount);
+        Ok(())
+    }
+
+    pub fn totals(env: Env) -> Totals {
+        env.storage()
+            .instance()
+            .get(&DataKey::Totals)
+            .unwrap_or_default()
+    }
+
+    /// What withdrawing `token_id` would pay right now.
+    pub fn value_of(env: Env, token_id: u32) -> Result<i128, VaultError> {
+        let position = Self::position(env.clone(), token_id)?;
+        let totals = Self::totals(env);
+        Ok(position.shares * totals.total_assets / totals.total_shares)
+    }
+
+    // ==================== NFT ====================
+
+    /// Move position `token_id` from `from` to `to`. The shares go with it;
+    /// the vault's totals do not change.
+    ///
+    /// Emits `(transfer, from, to)` with the token id.
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) -> Result<(), VaultError> {
+        from.require_auth();
+        let mut position = owned_position(&env, &from, token_id)?;
+        position.owner = to.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Position(token_id), &position);
+        add_balance(&env, &from, -1);
+        add_balance(&env, &to, 1);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), token_id);
+        Ok(())
+    }
+
+    pub fn position(env: Env, token_id: u32) -> Result<Position, VaultError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Position(token_id))
+            .ok_or(VaultError::NotFound)
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, VaultError> {
+        Ok(Self::position(env, token_id)?.owner)
+    }
+
+    /// Live positions held by `owner`.
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(owner))
+            .unwrap_or(0)
+    }
+}
+
+fn token_client(env: &Env) -> Result<token::Client<'_>, VaultError> {
+    let token: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(VaultError::NotInitialized)?;
+    Ok(token::Client::new(env, &token))
+}
+
+fn owned_position(env: &Env, owner: &Address, token_id: u32) -> Result<Position, VaultError> {
+    let position = PositionVault::position(env.clone(), token_id)?;
+    if position.owner != *owner {
+        return Err(VaultError::NotOwner);
+    }
+    Ok(position)
+}
+
+fn add_balance(env: &Env, owner: &Address, delta: i32) {
+    let balance = PositionVault::balance_of(env.clone(), owner.clone());
+    let balance = balance.saturating_add_signed(delta);
+    let key = DataKey::Balance(owner.clone());
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &balance);
+    }
+}
+
+mod test;
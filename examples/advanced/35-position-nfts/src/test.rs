@@ -0,0 +1,225 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, IntoVal,
+};
+
+struct Setup<'a> {
+    env: Env,
+    vault: PositionVaultClient<'a>,
+    token_admin: StellarAssetClient<'a>,
+    token: TokenClient<'a>,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let vault = PositionVaultClient::new(&env, &env.register_contract(None, PositionVault));
+    vault.initialize(&Address::generate(&env), &token_id);
+
+    let token_admin = StellarAssetClient::new(&env, &token_id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &10_000);
+    token_admin.mint(&bob, &10_000);
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        token_admin,
+        env,
+        vault,
+        alice,
+        bob,
+    }
+}
+
+impl Setup<'_> {
+    /// Add `amount` of yield to the vault.
+    fn harvest(&self, amount: i128) {
+        let strategy = Address::generate(&self.env);
+        self.token_admin.mint(&strategy, &amount);
+        self.vault.harvest(&strategy, &amount);
+    }
+}
+
+#[test]
+fn test_deposit_mints_position() {
+    let t = setup();
+    t.env.ledger().set_sequence_number(42);
+    let id = t.vault.deposit(&t.alice, &100);
+
+    assert_eq!(id, 0);
+    assert_eq!(
+        t.vault.position(&id),
+        Position {
+            owner: t.alice.clone(),
+            shares: 100,
+            deposited: 100,
+            entry_ledger: 42,
+        }
+    );
+    assert_eq!(t.vault.balance_of(&t.alice), 1);
+    assert_eq!(
+        t.vault.totals(),
+        Totals {
+            total_assets: 100,
+            total_shares: 100,
+        }
+    );
+    let events = t.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &t.env,
+            (
+                t.vault.address.clone(),
+                (symbol_short!("mint"), t.alice.clone()).into_val(&t.env),
+                (0u32, 100i128).into_val(&t.env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_each_deposit_is_its_own_position() {
+    let t = setup();
+    let first = t.vault.deposit(&t.alice, &100);
+    t.harvest(100);
+    // The price doubled: 100 more buys only 50 shares.
+    let second = t.vault.deposit(&t.alice, &100);
+
+    assert_ne!(first, second);
+    assert_eq!(t.vault.position(&first).shares, 100);
+    assert_eq!(t.vault.position(&second).shares, 50);
+    assert_eq!(t.vault.position(&second).deposited, 100);
+    assert_eq!(t.vault.balance_of(&t.alice), 2);
+    assert_eq!(t.vault.value_of(&first), 200);
+    assert_eq!(t.vault.value_of(&second), 100);
+}
+
+#[test]
+fn test_withdraw_burns_and_pays_current_value() {
+    let t = setup();
+    let id = t.vault.deposit(&t.alice, &100);
+    t.harvest(50);
+
+    assert_eq!(t.vault.withdraw(&t.alice, &id), 150);
+    assert_eq!(t.token.balance(&t.alice), 10_050);
+    assert_eq!(t.vault.try_position(&id), Err(Ok(VaultError::NotFound)));
+    assert_eq!(t.vault.balance_of(&t.alice), 0);
+    assert_eq!(t.vault.totals(), Totals::default());
+    // A burned id cannot be withdrawn twice.
+    assert_eq!(
+        t.vault.try_withdraw(&t.alice, &id),
+        Err(Ok(VaultError::NotFound))
+    );
+}
+
+#[test]
+fn test_transfer_moves_position_not_totals() {
+    let t = setup();
+    let id = t.vault.deposit(&t.alice, &100);
+    t.vault.deposit(&t.bob, &300);
+    let totals = t.vault.totals();
+
+    t.vault.transfer(&t.alice, &t.bob, &id);
+    assert_eq!(t.vault.owner_of(&id), t.bob);
+    assert_eq!(t.vault.balance_of(&t.alice), 0);
+    assert_eq!(t.vault.balance_of(&t.bob), 2);
+    assert_eq!(t.vault.totals(), totals);
+    let events = t.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &t.env,
+            (
+                t.vault.address.clone(),
+                (symbol_short!("transfer"), t.alice.clone(), t.bob.clone()).into_val(&t.env),
+                id.into_val(&t.env),
+            ),
+        ]
+    );
+
+    // Alice no longer owns it; Bob withdraws it.
+    assert_eq!(
+        t.vault.try_withdraw(&t.alice, &id),
+        Err(Ok(VaultError::NotOwner))
+    );
+    assert_eq!(t.vault.withdraw(&t.bob, &id), 100);
+}
+
+#[test]
+fn test_yield_follows_transferred_position() {
+    let t = setup();
+    let id = t.vault.deposit(&t.alice, &100);
+    t.vault.transfer(&t.alice, &t.bob, &id);
+    t.harvest(100);
+
+    // The entry data still records Alice's deposit, but Bob holds the
+    // position, so the yield is his.
+    assert_eq!(t.vault.position(&id).deposited, 100);
+    assert_eq!(t.vault.value_of(&id), 200);
+    assert_eq!(t.vault.withdraw(&t.bob, &id), 200);
+    assert_eq!(t.token.balance(&t.bob), 10_200);
+}
+
+#[test]
+fn test_rounding_favours_the_vault() {
+    let t = setup();
+    let a = t.vault.deposit(&t.alice, &3);
+    t.harvest(1);
+    // 4 assets over 3 shares: 10 tokens buy floor(10 * 3 / 4) = 7 shares.
+    let b = t.vault.deposit(&t.bob, &10);
+    assert_eq!(t.vault.position(&b).shares, 7);
+
+    let paid = t.vault.withdraw(&t.alice, &a) + t.vault.withdraw(&t.bob, &b);
+    // Put in 3 + 1 + 10; paid out at most that.
+    assert!(paid <= 14);
+    assert_eq!(t.token.balance(&t.vault.address), 14 - paid);
+}
+
+#[test]
+fn test_deposit_too_small_for_a_share() {
+    let t = setup();
+    t.vault.deposit(&t.alice, &1);
+    t.harvest(999);
+    // One share is now worth 1000.
+    assert_eq!(
+        t.vault.try_deposit(&t.bob, &999),
+        Err(Ok(VaultError::ZeroShares))
+    );
+    assert_eq!(
+        t.vault.try_deposit(&t.bob, &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_transfer_requires_ownership() {
+    let t = setup();
+    let id = t.vault.deposit(&t.alice, &100);
+    assert_eq!(
+        t.vault.try_transfer(&t.bob, &t.bob, &id),
+        Err(Ok(VaultError::NotOwner))
+    );
+    assert_eq!(
+        t.vault.try_transfer(&t.alice, &t.bob, &7),
+        Err(Ok(VaultError::NotFound))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_transfer_requires_owner_auth() {
+    let t = setup();
+    let id = t.vault.deposit(&t.alice, &100);
+    t.env.set_auths(&[]);
+    t.vault.transfer(&t.alice, &t.bob, &id);
+}
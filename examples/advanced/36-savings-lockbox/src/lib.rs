@@ -0,0 +1,360 @@
+//! # Savings Lockbox
+//!
+//! Savers lock a token until a chosen time. Once it unlocks, a saver can
+//! take out part of the balance and relock the rest for another term. Each
+//! relock raises the box's multiplier, and the multiplier sets a bonus paid
+//! from a bonus pool when the new term ends:
+//!
+//! ```text
+//!          lock            unlock         relock            unlock
+//! balance  1000 ──locked──▶ 1000 ─withdraw 400─▶ 600 ──locked──▶ 600 + 30
+//! mult     1.00x                            1.05x     bonus 5% of 600
+//! ```
+//!
+//! ## Per-box state
+//!
+//! A saver has at most one [`Lockbox`]. Its `balance` can be withdrawn once
+//! `unlock_at` has passed. The bonus for the current term sits in
+//! `pending_bonus`. It is reserved from the pool at relock time, so it can
+//! always be paid, and [`Lockbox::settle`] folds it into `balance` the first
+//! time the box is touched after the term ends.
+//!
+//! The multiplier grows by `relock_bonus_bps` per relock, up to
+//! [`MAX_MULTIPLIER_BPS`]. It belongs to the box: withdrawing everything
+//! closes the box, and a new lock starts again at 1.00x.
+//!
+//! ## Time
+//!
+//! Terms are measured with `env.ledger().timestamp()`, in seconds. A term
+//! must last at least `min_lock` and at most [`MAX_LOCK_DURATION`], which
+//! also keeps `now + duration` far from overflowing. A box is unlocked from
+//! `unlock_at` on, inclusive.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+/// 100% in basis points, the multiplier of a fresh lock.
+pub const BASE_MULTIPLIER_BPS: u32 = 10_000;
+/// The multiplier stops growing at 1.50x.
+pub const MAX_MULTIPLIER_BPS: u32 = 15_000;
+/// Longest term, four years.
+pub const MAX_LOCK_DURATION: u64 = 4 * 365 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub token: Address,
+    /// Shortest term in seconds, for both locks and relocks.
+    pub min_lock: u64,
+    /// Multiplier added per relock, in basis points.
+    pub relock_bonus_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lockbox {
+    /// Principal plus any bonus from finished terms.
+    pub balance: i128,
+    /// Bonus for the current term, paid into `balance` at `unlock_at`.
+    pub pending_bonus: i128,
+    pub unlock_at: u64,
+    pub multiplier_bps: u32,
+    pub relocks: u32,
+}
+
+impl Lockbox {
+    pub fn is_unlocked(&self, now: u64) -> bool {
+        now >= self.unlock_at
+    }
+
+    /// Pay the term's bonus into `balance` once the term has ended.
+    pub fn settle(&mut self, now: u64) {
+        if self.is_unlocked(now) {
+            self.balance += self.pending_bonus;
+            self.pending_bonus = 0;
+        }
+    }
+}
+
+/// Where a saver's box stands at a point in time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockSchedule {
+    pub unlock_at: u64,
+    /// Zero once unlocked.
+    pub seconds_remaining: u64,
+    /// What `withdraw` would pay out in total right now.
+    pub withdrawable: i128,
+    /// Principal and bonus still waiting for `unlock_at`.
+    pub locked: i128,
+    pub multiplier_bps: u32,
+    /// The multiplier and bonus a relock of the whole balance would get
+    /// now. The bonus may exceed what the pool can pay.
+    pub next_multiplier_bps: u32,
+    pub next_relock_bonus: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    /// Tokens available for future bonuses, not yet reserved.
+    BonusPool,
+    Lockbox(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LockboxError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// Outside `min_lock..=MAX_LOCK_DURATION`.
+    InvalidDuration = 4,
+    /// `lock` while the saver still has a box.
+    LockboxExists = 5,
+    NoLockbox = 6,
+    StillLocked = 7,
+    InsufficientBalance = 8,
+    /// The pool cannot cover the relock bonus.
+    InsufficientBonusPool = 9,
+    /// A `min_lock` of zero or above `MAX_LOCK_DURATION`.
+    InvalidConfig = 10,
+}
+
+#[contract]
+pub struct SavingsLockbox;
+
+#[contractimpl]
+impl SavingsLockbox {
+    pub fn initialize(env: Env, config: Config) -> Result<(), LockboxError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(LockboxError::AlreadyInitialized);
+        }
+        if config.min_lock == 0 || config.min_lock > MAX_LOCK_DURATION {
+            return Err(LockboxError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Add `amount` to the bonus pool. Anyone can fund it.
+    pub fn fund(env: Env, from: Address, amount: i128) -> Result<(), LockboxError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(LockboxError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        token::Client::new(&env, &config.token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+        write_pool(&env, Self::bonus_pool(env.clone()) + amount);
+        Ok(())
+    }
+
+    // ==================== LOCKING ====================
+
+    /// Open a box with `amount`, locked for `duration` seconds.
+    ///
+    /// Emits `(lock, saver)` with `(amount, unlock_at)`.
+    pub fn lock(
+        env: Env,
+        saver: Address,
+        amount: i128,
+        duration: u64,
+    ) -> Result<Lockbox, LockboxError> {
+        saver.require_auth();
+        if amount <= 0 {
+            return Err(LockboxError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let key = DataKey::Lockbox(saver.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(LockboxError::LockboxExists);
+        }
+        let unlock_at = unlock_time(&env, &config, duration)?;
+
+        token::Client::new(&env, &config.token).transfer(
+            &saver,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let lockbox = Lockbox {
+            balance: amount,
+            pending_bonus: 0,
+            unlock_at,
+            multiplier_bps: BASE_MULTIPLIER_BPS,
+            relocks: 0,
+        };
+        env.storage().persistent().set(&key, &lockbox);
+
+        env.events()
+            .publish((symbol_short!("lock"), saver), (amount, unlock_at));
+        Ok(lockbox)
+    }
+
+    /// Take `amount` out of an unlocked box. The rest stays, unlocked,
+    /// until it is withdrawn or relocked. Emptying the box closes it.
+    ///
+    /// Emits `(withdraw, saver)` with `(amount, remaining)`.
+    pub fn withdraw(env: Env, saver: Address, amount: i128) -> Result<i128, LockboxError> {
+        saver.require_auth();
+        if amount <= 0 {
+            return Err(LockboxError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let now = env.ledger().timestamp();
+        let mut lockbox = Self::lockbox(env.clone(), saver.clone())?;
+        if !lockbox.is_unlocked(now) {
+            return Err(LockboxError::StillLocked);
+        }
+        lockbox.settle(now);
+        if amount > lockbox.balance {
+            return Err(LockboxError::InsufficientBalance);
+        }
+
+        lockbox.balance -= amount;
+        let key = DataKey::Lockbox(saver.clone());
+        if lockbox.balance == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &lockbox);
+        }
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &saver,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("withdraw"), saver),
+            (amount, lockbox.balance),
+        );
+        Ok(lockbox.balance)
+    }
+
+    /// Lock an unlocked box's whole balance for another `duration` seconds,
+    /// raising its multiplier. The bonus for the new term is reserved from
+    /// the pool now and paid into the box at the end of the term.
+    ///
+    /// Emits `(relock, saver)` with `(unlock_at, multiplier_bps, bonus)`.
+    pub fn relock(env: Env, saver: Address, duration: u64) -> Result<Lockbox, LockboxError> {
+        saver.require_auth();
+        let config = Self::config(env.clone())?;
+        let now = env.ledger().timestamp();
+        let mut lockbox = Self::lockbox(env.clone(), saver.clone())?;
+        if !lockbox.is_unlocked(now) {
+            return Err(LockboxError::StillLocked);
+        }
+        lockbox.settle(now);
+        let unlock_at = unlock_time(&env, &config, duration)?;
+
+        let multiplier_bps = next_multiplier(&config, &lockbox);
+        let bonus = bonus_for(lockbox.balance, multiplier_bps);
+        let pool = Self::bonus_pool(env.clone());
+        if bonus > pool {
+            return Err(LockboxError::InsufficientBonusPool);
+        }
+        write_pool(&env, pool - bonus);
+
+        lockbox.pending_bonus = bonus;
+        lockbox.unlock_at = unlock_at;
+        lockbox.multiplier_bps = multiplier_bps;
+        lockbox.relocks += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Lockbox(saver.clone()), &lockbox);
+
+        env.events().publish(
+            (symbol_short!("relock"), saver),
+            (unlock_at, multiplier_bps, bonus),
+        );
+        Ok(lockbox)
+    }
+
+    // ==================== QUERIES ====================
+
+    /// The saver's box as stored. A bonus whose term has ended shows in
+    /// `pending_bonus` until the next withdraw or relock; see `schedule`.
+    pub fn lockbox(env: Env, saver: Address) -> Result<Lockbox, LockboxError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Lockbox(saver))
+            .ok_or(LockboxError::NoLockbox)
+    }
+
+    /// Where the saver's box stands at the current ledger time.
+    pub fn schedule(env: Env, saver: Address) -> Result<LockSchedule, LockboxError> {
+        let now = env.ledger().timestamp();
+        Self::schedule_at(env, saver, now)
+    }
+
+    /// Where the saver's box would stand at time `at`, for a wallet showing
+    /// what unlocks when. Assumes no withdraw or relock before then.
+    pub fn schedule_at(env: Env, saver: Address, at: u64) -> Result<LockSchedule, LockboxError> {
+        let config = Self::config(env.clone())?;
+        let mut lockbox = Self::lockbox(env, saver)?;
+        lockbox.settle(at);
+        let unlocked = lockbox.is_unlocked(at);
+        let next_multiplier_bps = next_multiplier(&config, &lockbox);
+        Ok(LockSchedule {
+            unlock_at: lockbox.unlock_at,
+            seconds_remaining: lockbox.unlock_at.saturating_sub(at),
+            withdrawable: if unlocked { lockbox.balance } else { 0 },
+            locked: if unlocked {
+                0
+            } else {
+                lockbox.balance + lockbox.pending_bonus
+            },
+            multiplier_bps: lockbox.multiplier_bps,
+            next_multiplier_bps,
+            next_relock_bonus: bonus_for(lockbox.balance, next_multiplier_bps),
+        })
+    }
+
+    pub fn bonus_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::BonusPool)
+            .unwrap_or(0)
+    }
+
+    pub fn config(env: Env) -> Result<Config, LockboxError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(LockboxError::NotInitialized)
+    }
+}
+
+/// End of a term of `duration` seconds starting now.
+fn unlock_time(env: &Env, config: &Config, duration: u64) -> Result<u64, LockboxError> {
+    if duration < config.min_lock || duration > MAX_LOCK_DURATION {
+        return Err(LockboxError::InvalidDuration);
+    }
+    Ok(env.ledger().timestamp() + duration)
+}
+
+fn next_multiplier(config: &Config, lockbox: &Lockbox) -> u32 {
+    lockbox
+        .multiplier_bps
+        .saturating_add(config.relock_bonus_bps)
+        .min(MAX_MULTIPLIER_BPS)
+}
+
+/// Bonus on `balance` at `multiplier_bps`, rounded down.
+fn bonus_for(balance: i128, multiplier_bps: u32) -> i128 {
+    balance * (multiplier_bps - BASE_MULTIPLIER_BPS) as i128 / BASE_MULTIPLIER_BPS as i128
+}
+
+fn write_pool(env: &Env, amount: i128) {
+    env.storage().instance().set(&DataKey::BonusPool, &amount);
+}
+
+mod test;
@@ -0,0 +1,286 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+const START: u64 = 1_000_000;
+
+struct Setup<'a> {
+    env: Env,
+    lockbox: SavingsLockboxClient<'a>,
+    token: token::Client<'a>,
+    saver: Address,
+}
+
+/// A lockbox with a one-week minimum and +5% per relock. The saver holds
+/// 1_000 and the pool holds 500.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = START);
+
+    let issuer = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract_v2(issuer).address();
+    let token_admin = StellarAssetClient::new(&env, &token_id);
+
+    let lockbox = SavingsLockboxClient::new(&env, &env.register_contract(None, SavingsLockbox));
+    lockbox.initialize(&Config {
+        token: token_id.clone(),
+        min_lock: 7 * DAY,
+        relock_bonus_bps: 500,
+    });
+    let sponsor = Address::generate(&env);
+    token_admin.mint(&sponsor, &500);
+    lockbox.fund(&sponsor, &500);
+
+    let saver = Address::generate(&env);
+    token_admin.mint(&saver, &1_000);
+    Setup {
+        token: token::Client::new(&env, &token_id),
+        env,
+        lockbox,
+        saver,
+    }
+}
+
+impl Setup<'_> {
+    fn set_time(&self, timestamp: u64) {
+        self.env.ledger().with_mut(|l| l.timestamp = timestamp);
+    }
+}
+
+// ==================== WITHDRAWALS ====================
+
+#[test]
+fn test_partial_withdrawals_after_unlock() {
+    let s = setup();
+    let lockbox = s.lockbox.lock(&s.saver, &1_000, &(30 * DAY));
+    assert_eq!(lockbox.unlock_at, START + 30 * DAY);
+    assert_eq!(s.token.balance(&s.saver), 0);
+
+    // One second early.
+    s.set_time(START + 30 * DAY - 1);
+    assert_eq!(
+        s.lockbox.try_withdraw(&s.saver, &1),
+        Err(Ok(LockboxError::StillLocked))
+    );
+
+    s.set_time(START + 30 * DAY);
+    assert_eq!(s.lockbox.withdraw(&s.saver, &400), 600);
+    assert_eq!(
+        s.lockbox.try_withdraw(&s.saver, &601),
+        Err(Ok(LockboxError::InsufficientBalance))
+    );
+    // The rest stays unlocked, with no deadline.
+    s.set_time(START + 400 * DAY);
+    assert_eq!(s.lockbox.withdraw(&s.saver, &250), 350);
+    assert_eq!(s.token.balance(&s.saver), 650);
+    assert_eq!(s.lockbox.lockbox(&s.saver).balance, 350);
+}
+
+/// Emptying the box closes it, so the saver can start a new one.
+#[test]
+fn test_full_withdrawal_closes_box() {
+    let s = setup();
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+    assert_eq!(
+        s.lockbox.try_lock(&s.saver, &1, &(7 * DAY)),
+        Err(Ok(LockboxError::LockboxExists))
+    );
+
+    s.set_time(START + 7 * DAY);
+    assert_eq!(s.lockbox.withdraw(&s.saver, &1_000), 0);
+    assert_eq!(
+        s.lockbox.try_lockbox(&s.saver),
+        Err(Ok(LockboxError::NoLockbox))
+    );
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+}
+
+// ==================== RELOCKING ====================
+
+#[test]
+fn test_relock_pays_bonus_at_end_of_term() {
+    let s = setup();
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+    s.set_time(START + 7 * DAY);
+    s.lockbox.withdraw(&s.saver, &400);
+
+    let relocked = s.lockbox.relock(&s.saver, &(14 * DAY));
+    assert_eq!(relocked.multiplier_bps, 10_500);
+    assert_eq!(relocked.pending_bonus, 30);
+    assert_eq!(relocked.unlock_at, START + 21 * DAY);
+    // The bonus is reserved now, so the pool cannot promise it twice.
+    assert_eq!(s.lockbox.bonus_pool(), 470);
+    assert_eq!(
+        s.lockbox.try_withdraw(&s.saver, &1),
+        Err(Ok(LockboxError::StillLocked))
+    );
+
+    s.set_time(START + 21 * DAY);
+    assert_eq!(s.lockbox.withdraw(&s.saver, &630), 0);
+    assert_eq!(s.token.balance(&s.saver), 400 + 630);
+}
+
+/// Bonuses compound: the next relock's bonus is on balance plus the last
+/// bonus, at a higher multiplier.
+#[test]
+fn test_relock_bonus_compounds() {
+    let s = setup();
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+    let mut now = START + 7 * DAY;
+    let mut expected = [(10_500, 50), (11_000, 105), (11_500, 173)].iter();
+    for &(multiplier, bonus) in &mut expected {
+        s.set_time(now);
+        let lockbox = s.lockbox.relock(&s.saver, &(7 * DAY));
+        assert_eq!(
+            (lockbox.multiplier_bps, lockbox.pending_bonus),
+            (multiplier, bonus)
+        );
+        now += 7 * DAY;
+    }
+    assert_eq!(s.lockbox.bonus_pool(), 500 - 50 - 105 - 173);
+
+    // With the pool nearly empty, a relock that needs more fails and
+    // leaves the box unlocked and unchanged.
+    s.set_time(now);
+    assert_eq!(
+        s.lockbox.try_relock(&s.saver, &(7 * DAY)),
+        Err(Ok(LockboxError::InsufficientBonusPool))
+    );
+    let lockbox = s.lockbox.lockbox(&s.saver);
+    assert_eq!(lockbox.relocks, 3);
+    assert_eq!(lockbox.pending_bonus, 173);
+}
+
+#[test]
+fn test_multiplier_capped() {
+    let s = setup();
+    // A small balance, so the pool covers every bonus.
+    s.lockbox.lock(&s.saver, &10, &(7 * DAY));
+    let mut now = START;
+    for _ in 0..11 {
+        now += 7 * DAY;
+        s.set_time(now);
+        s.lockbox.relock(&s.saver, &(7 * DAY));
+    }
+    assert_eq!(
+        s.lockbox.lockbox(&s.saver).multiplier_bps,
+        MAX_MULTIPLIER_BPS
+    );
+}
+
+#[test]
+fn test_relock_requires_unlocked_box() {
+    let s = setup();
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+    s.set_time(START + 7 * DAY - 1);
+    assert_eq!(
+        s.lockbox.try_relock(&s.saver, &(7 * DAY)),
+        Err(Ok(LockboxError::StillLocked))
+    );
+    s.set_time(START + 7 * DAY);
+    assert_eq!(
+        s.lockbox.try_relock(&s.saver, &(7 * DAY - 1)),
+        Err(Ok(LockboxError::InvalidDuration))
+    );
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.lockbox.try_relock(&other, &(7 * DAY)),
+        Err(Ok(LockboxError::NoLockbox))
+    );
+}
+
+// ==================== SCHEDULE ====================
+
+#[test]
+fn test_schedule_over_time() {
+    let s = setup();
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+    assert_eq!(
+        s.lockbox.schedule(&s.saver),
+        LockSchedule {
+            unlock_at: START + 7 * DAY,
+            seconds_remaining: 7 * DAY,
+            withdrawable: 0,
+            locked: 1_000,
+            multiplier_bps: 10_000,
+            next_multiplier_bps: 10_500,
+            next_relock_bonus: 50,
+        }
+    );
+
+    s.set_time(START + 7 * DAY);
+    s.lockbox.relock(&s.saver, &(10 * DAY));
+    let during = s.lockbox.schedule_at(&s.saver, &(START + 10 * DAY));
+    assert_eq!(during.seconds_remaining, 7 * DAY);
+    assert_eq!((during.withdrawable, during.locked), (0, 1_050));
+
+    // After the term the bonus shows as withdrawable before the box is
+    // touched, and the next relock builds on it.
+    let after = s.lockbox.schedule_at(&s.saver, &(START + 17 * DAY));
+    assert_eq!(after.seconds_remaining, 0);
+    assert_eq!((after.withdrawable, after.locked), (1_050, 0));
+    assert_eq!(after.next_multiplier_bps, 11_000);
+    assert_eq!(after.next_relock_bonus, 105);
+    assert_eq!(s.lockbox.lockbox(&s.saver).pending_bonus, 50);
+}
+
+// ==================== VALIDATION ====================
+
+#[test]
+fn test_lock_validation() {
+    let s = setup();
+    assert_eq!(
+        s.lockbox.try_lock(&s.saver, &0, &(7 * DAY)),
+        Err(Ok(LockboxError::InvalidAmount))
+    );
+    assert_eq!(
+        s.lockbox.try_lock(&s.saver, &1_000, &(7 * DAY - 1)),
+        Err(Ok(LockboxError::InvalidDuration))
+    );
+    assert_eq!(
+        s.lockbox
+            .try_lock(&s.saver, &1_000, &(MAX_LOCK_DURATION + 1)),
+        Err(Ok(LockboxError::InvalidDuration))
+    );
+    s.lockbox.lock(&s.saver, &1_000, &MAX_LOCK_DURATION);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_withdraw_requires_saver_auth() {
+    let s = setup();
+    s.lockbox.lock(&s.saver, &1_000, &(7 * DAY));
+    s.set_time(START + 7 * DAY);
+    s.env.set_auths(&[]);
+    s.lockbox.withdraw(&s.saver, &1_000);
+}
+
+#[test]
+fn test_initialize_validation() {
+    let env = Env::default();
+    let lockbox = SavingsLockboxClient::new(&env, &env.register_contract(None, SavingsLockbox));
+    let config = Config {
+        token: Address::generate(&env),
+        min_lock: 0,
+        relock_bonus_bps: 500,
+    };
+    assert_eq!(
+        lockbox.try_initialize(&config),
+        Err(Ok(LockboxError::InvalidConfig))
+    );
+    let config = Config {
+        min_lock: DAY,
+        ..config
+    };
+    lockbox.initialize(&config);
+    assert_eq!(
+        lockbox.try_initialize(&config),
+        Err(Ok(LockboxError::AlreadyInitialized))
+    );
+}
@@ -0,0 +1,242 @@
+//! # View Aggregator
+//!
+//! A frontend showing a dashboard needs data from several contracts: token
+//! balances, the user's NFTs, their savings lockbox, their voting power and
+//! where some proposals stand. One simulated transaction per view means a
+//! dozen RPC round trips, each against a possibly different ledger. This
+//! contract makes all those view calls in one invocation and returns one
+//! struct. The frontend simulates `dashboard` once and never submits it:
+//!
+//! ```text
+//! dashboard(request) ──▶ token.balance(user)           × tokens
+//!                    ──▶ nft.balance_of / tokens_of
+//!                    ──▶ lockbox.schedule(user)
+//!                    ──▶ governance.voting_power / state / has_voted
+//!                    ◀── Dashboard { ledger, balances, nfts, savings, … }
+//! ```
+//!
+//! Every value comes from the same ledger, so the dashboard is consistent:
+//! no balance from one ledger next to a proposal state from the next.
+//!
+//! ## Typed and generic
+//!
+//! `dashboard` knows the cookbook's NFT, savings lockbox and governance
+//! contracts. Like the cross-contract-calls example, it does not link them:
+//! it declares the views it calls with `#[contractclient]` and mirrors the
+//! types they return. Mirrored types must keep the originals' field and
+//! variant names, which is what the values are encoded by.
+//!
+//! `batch` is the generic fallback: any list of `(contract, function,
+//! args)` calls, with the raw results in order.
+//!
+//! ## Read-only
+//!
+//! The aggregator keeps no state and asks for no authorization. It is only
+//! as read-only as the functions it is pointed at, though. `batch` calls
+//! whatever it is given. Since nothing is signed, a call that needs
+//! someone's authorization fails rather than acting for them.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, token, Address, Env,
+    Symbol, Val, Vec,
+};
+
+// ==================== MIRRORED VIEWS ====================
+
+/// The NFT views `dashboard` reads.
+#[contractclient(name = "NftClient")]
+pub trait NftViews {
+    fn balance_of(env: Env, owner: Address) -> u32;
+    fn tokens_of(env: Env, owner: Address) -> Vec<u32>;
+}
+
+/// Mirror of the savings lockbox's `LockSchedule`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockSchedule {
+    pub unlock_at: u64,
+    pub seconds_remaining: u64,
+    pub withdrawable: i128,
+    pub locked: i128,
+    pub multiplier_bps: u32,
+    pub next_multiplier_bps: u32,
+    pub next_relock_bonus: i128,
+}
+
+/// The savings lockbox error this contract tells apart. The number must
+/// match the lockbox's `#[contracterror]` enum.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LockboxError {
+    NoLockbox = 6,
+}
+
+/// The savings lockbox view `dashboard` reads.
+#[contractclient(name = "LockboxClient")]
+pub trait LockboxViews {
+    fn schedule(env: Env, saver: Address) -> Result<LockSchedule, LockboxError>;
+}
+
+/// Mirror of governance's `ProposalState`.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProposalState {
+    Active,
+    QuorumNotReached,
+    Defeated,
+    Succeeded,
+    Executed,
+}
+
+/// The governance views `dashboard` reads.
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceViews {
+    fn voting_power(env: Env, holder: Address) -> i128;
+    fn state(env: Env, id: u32) -> ProposalState;
+    fn has_voted(env: Env, id: u32, voter: Address) -> bool;
+}
+
+// ==================== REQUEST AND RESULT ====================
+
+/// What to read for one user.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DashboardRequest {
+    pub user: Address,
+    /// Tokens to read the user's balance of, in this order.
+    pub tokens: Vec<Address>,
+    pub nft: Address,
+    pub lockbox: Address,
+    pub governance: Address,
+    /// Proposals to report on, in this order.
+    pub proposals: Vec<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Savings {
+    /// The user has no lockbox.
+    NoLockbox,
+    Open(LockSchedule),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalView {
+    pub id: u32,
+    pub state: ProposalState,
+    /// Whether the user has voted on it.
+    pub voted: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dashboard {
+    /// The ledger every value below was read at.
+    pub ledger: u32,
+    pub timestamp: u64,
+    /// One per requested token, in request order.
+    pub balances: Vec<i128>,
+    pub nft_balance: u32,
+    pub nft_tokens: Vec<u32>,
+    pub savings: Savings,
+    pub voting_power: i128,
+    /// One per requested proposal, in request order.
+    pub proposals: Vec<ProposalView>,
+}
+
+/// A view call for `batch`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Query {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AggregatorError {
+    /// More than `MAX_QUERIES` calls in one request.
+    TooManyQueries = 1,
+    /// The lockbox failed with something other than `NoLockbox`.
+    LockboxViewFailed = 2,
+}
+
+/// Most calls `batch` makes, and most tokens or proposals `dashboard`
+/// reads. Keeps one simulation well inside the per-transaction budget.
+pub const MAX_QUERIES: u32 = 20;
+
+#[contract]
+pub struct ViewAggregator;
+
+#[contractimpl]
+impl ViewAggregator {
+    /// Read one user's dashboard from the given contracts.
+    ///
+    /// A failing view aborts the whole call, except a missing lockbox,
+    /// which is reported as `Savings::NoLockbox`.
+    pub fn dashboard(env: Env, request: DashboardRequest) -> Result<Dashboard, AggregatorError> {
+        if request.tokens.len() > MAX_QUERIES || request.proposals.len() > MAX_QUERIES {
+            return Err(AggregatorError::TooManyQueries);
+        }
+        let user = request.user;
+
+        let mut balances = Vec::new(&env);
+        for token in request.tokens.iter() {
+            balances.push_back(token::Client::new(&env, &token).balance(&user));
+        }
+
+        let nft = NftClient::new(&env, &request.nft);
+
+        let savings = match LockboxClient::new(&env, &request.lockbox).try_schedule(&user) {
+            Ok(Ok(schedule)) => Savings::Open(schedule),
+            Err(Ok(LockboxError::NoLockbox)) => Savings::NoLockbox,
+            _ => return Err(AggregatorError::LockboxViewFailed),
+        };
+
+        let governance = GovernanceClient::new(&env, &request.governance);
+        let mut proposals = Vec::new(&env);
+        for id in request.proposals.iter() {
+            proposals.push_back(ProposalView {
+                id,
+                state: governance.state(&id),
+                voted: governance.has_voted(&id, &user),
+            });
+        }
+
+        Ok(Dashboard {
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+            balances,
+            nft_balance: nft.balance_of(&user),
+            nft_tokens: nft.tokens_of(&user),
+            savings,
+            voting_power: governance.voting_power(&user),
+            proposals,
+        })
+    }
+
+    /// Make each call in order and return the raw results. For views the
+    /// typed `dashboard` does not cover.
+    pub fn batch(env: Env, queries: Vec<Query>) -> Result<Vec<Val>, AggregatorError> {
+        if queries.len() > MAX_QUERIES {
+            return Err(AggregatorError::TooManyQueries);
+        }
+        let mut results = Vec::new(&env);
+        for query in queries.iter() {
+            results.push_back(env.invoke_contract::<Val>(
+                &query.contract,
+                &query.function,
+                query.args,
+            ));
+        }
+        Ok(results)
+    }
+}
+
+mod test;
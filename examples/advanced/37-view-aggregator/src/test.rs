@@ -0,0 +1,240 @@
+#![cfg(test)]
+
+use super::*;
+use governance::{Call, Config as GovernanceConfig, Governance};
+use nft::Nft;
+use savings_lockbox::{Config as LockboxConfig, SavingsLockbox};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, FromVal, IntoVal, String,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+struct Setup<'a> {
+    env: Env,
+    aggregator: ViewAggregatorClient<'a>,
+    token: Address,
+    other_token: Address,
+    nft: nft::NftClient<'a>,
+    lockbox: savings_lockbox::SavingsLockboxClient<'a>,
+    governance: governance::GovernanceClient<'a>,
+    alice: Address,
+    bob: Address,
+}
+
+/// The NFT, savings lockbox and governance examples, with Alice active in
+/// all three: two NFTs, 1_000 locked for 30 days, 300 of voting power and a
+/// vote on proposal 0. Bob holds tokens and nothing else.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| {
+        l.sequence_number = 100;
+        l.timestamp = 1_000_000;
+    });
+
+    let issuer = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(issuer.clone())
+        .address();
+    let other_token = env.register_stellar_asset_contract_v2(issuer).address();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&alice, &2_000);
+    StellarAssetClient::new(&env, &token).mint(&bob, &50);
+    StellarAssetClient::new(&env, &other_token).mint(&alice, &7);
+
+    let nft = nft::NftClient::new(&env, &env.register_contract(None, Nft));
+    let name = String::from_str(&env, "Cookbook");
+    nft.initialize(&Address::generate(&env), &name, &name);
+    nft.mint(&alice, &String::from_str(&env, "ipfs://cook/0"));
+    nft.mint(&bob, &String::from_str(&env, "ipfs://cook/1"));
+    nft.mint(&alice, &String::from_str(&env, "ipfs://cook/2"));
+
+    let lockbox = savings_lockbox::SavingsLockboxClient::new(
+        &env,
+        &env.register_contract(None, SavingsLockbox),
+    );
+    lockbox.initialize(&LockboxConfig {
+        token: token.clone(),
+        min_lock: 7 * DAY,
+        relock_bonus_bps: 500,
+    });
+    lockbox.lock(&alice, &1_000, &(30 * DAY));
+
+    let governance =
+        governance::GovernanceClient::new(&env, &env.register_contract(None, Governance));
+    governance.initialize(&GovernanceConfig {
+        token: token.clone(),
+        voting_period: 10,
+        quorum: 100,
+        proposal_threshold: 100,
+    });
+    governance.deposit(&alice, &300);
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    let call = Call {
+        target: nft.address.clone(),
+        function: Symbol::new(&env, "balance_of"),
+        args: vec![&env, alice.into_val(&env)],
+    };
+    governance.propose(&alice, &call);
+    governance.propose(&alice, &call);
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    governance.vote(&alice, &0, &true);
+
+    Setup {
+        aggregator: ViewAggregatorClient::new(&env, &env.register_contract(None, ViewAggregator)),
+        env,
+        token,
+        other_token,
+        nft,
+        lockbox,
+        governance,
+        alice,
+        bob,
+    }
+}
+
+impl Setup<'_> {
+    fn request(&self, user: &Address) -> DashboardRequest {
+        DashboardRequest {
+            user: user.clone(),
+            tokens: vec![&self.env, self.token.clone(), self.other_token.clone()],
+            nft: self.nft.address.clone(),
+            lockbox: self.lockbox.address.clone(),
+            governance: self.governance.address.clone(),
+            proposals: vec![&self.env, 0, 1],
+        }
+    }
+}
+
+// ==================== DASHBOARD ====================
+
+#[test]
+fn test_dashboard_combines_views() {
+    let s = setup();
+    let dashboard = s.aggregator.dashboard(&s.request(&s.alice));
+    assert_eq!(
+        dashboard,
+        Dashboard {
+            ledger: 102,
+            timestamp: 1_000_000,
+            balances: vec![&s.env, 700, 7],
+            nft_balance: 2,
+            nft_tokens: vec![&s.env, 0, 2],
+            savings: Savings::Open(LockSchedule {
+                unlock_at: 1_000_000 + 30 * DAY,
+                seconds_remaining: 30 * DAY,
+                withdrawable: 0,
+                locked: 1_000,
+                multiplier_bps: 10_000,
+                next_multiplier_bps: 10_500,
+                next_relock_bonus: 50,
+            }),
+            voting_power: 300,
+            proposals: vec![
+                &s.env,
+                ProposalView {
+                    id: 0,
+                    state: ProposalState::Active,
+                    voted: true,
+                },
+                ProposalView {
+                    id: 1,
+                    state: ProposalState::Active,
+                    voted: false,
+                },
+            ],
+        }
+    );
+}
+
+/// A user missing from some contracts gets empty values, not an error.
+#[test]
+fn test_dashboard_for_new_user() {
+    let s = setup();
+    let dashboard = s.aggregator.dashboard(&s.request(&s.bob));
+    assert_eq!(dashboard.balances, vec![&s.env, 50, 0]);
+    assert_eq!(dashboard.nft_tokens, vec![&s.env, 1]);
+    assert_eq!(dashboard.savings, Savings::NoLockbox);
+    assert_eq!(dashboard.voting_power, 0);
+    assert!(dashboard.proposals.iter().all(|view| !view.voted));
+}
+
+/// States are decoded through the mirrored enum as they change.
+#[test]
+fn test_dashboard_follows_ledger() {
+    let s = setup();
+    s.env.ledger().with_mut(|l| {
+        l.sequence_number = 200;
+        l.timestamp = 1_000_000 + 30 * DAY;
+    });
+    let dashboard = s.aggregator.dashboard(&s.request(&s.alice));
+    assert_eq!(dashboard.ledger, 200);
+    assert_eq!(
+        dashboard.proposals.get_unchecked(0).state,
+        ProposalState::Succeeded
+    );
+    assert_eq!(
+        dashboard.proposals.get_unchecked(1).state,
+        ProposalState::QuorumNotReached
+    );
+    let Savings::Open(schedule) = dashboard.savings else {
+        panic!("lockbox missing");
+    };
+    assert_eq!((schedule.withdrawable, schedule.locked), (1_000, 0));
+}
+
+/// Any other failing view fails the dashboard as a whole.
+#[test]
+fn test_failing_view_aborts() {
+    let s = setup();
+    let mut request = s.request(&s.alice);
+    request.proposals.push_back(7);
+    assert!(s.aggregator.try_dashboard(&request).is_err());
+}
+
+#[test]
+fn test_too_many_queries() {
+    let s = setup();
+    let mut request = s.request(&s.alice);
+    for _ in 0..MAX_QUERIES {
+        request.tokens.push_back(s.token.clone());
+    }
+    assert_eq!(
+        s.aggregator.try_dashboard(&request),
+        Err(Ok(AggregatorError::TooManyQueries))
+    );
+}
+
+// ==================== BATCH ====================
+
+#[test]
+fn test_batch_returns_raw_results_in_order() {
+    let s = setup();
+    let queries = vec![
+        &s.env,
+        Query {
+            contract: s.nft.address.clone(),
+            function: Symbol::new(&s.env, "total_supply"),
+            args: vec![&s.env],
+        },
+        Query {
+            contract: s.lockbox.address.clone(),
+            function: Symbol::new(&s.env, "bonus_pool"),
+            args: vec![&s.env],
+        },
+        Query {
+            contract: s.governance.address.clone(),
+            function: Symbol::new(&s.env, "voting_power"),
+            args: vec![&s.env, s.alice.into_val(&s.env)],
+        },
+    ];
+    let results = s.aggregator.batch(&queries);
+    assert_eq!(results.len(), 3);
+    assert_eq!(u32::from_val(&s.env, &results.get(0).unwrap()), 3);
+    assert_eq!(i128::from_val(&s.env, &results.get(1).unwrap()), 0);
+    assert_eq!(i128::from_val(&s.env, &results.get(2).unwrap()), 300);
+}
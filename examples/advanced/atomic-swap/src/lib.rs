@@ -0,0 +1,96 @@
+//! # Atomic Swap
+//!
+//! Two parties exchange different tokens in one invocation. Neither has to
+//! trust the other or send first: either both transfers happen or neither
+//! does.
+//!
+//! Each party signs only their own side of the deal, through
+//! `require_auth_for_args`:
+//!
+//! ```text
+//! a signs (token_a, token_b, amount_a, min_b_for_a)
+//!     "I give up to amount_a of A if I get at least min_b_for_a of B"
+//! b signs (token_b, token_a, amount_b, min_a_for_b)
+//!     "I give up to amount_b of B if I get at least min_a_for_b of A"
+//! ```
+//!
+//! Anyone holding both signed payloads (a matching service, one of the two
+//! parties) can submit the swap. Each party then pays exactly the other's
+//! minimum and gets the rest of their offer back.
+
+#![no_std]
+
+use cookbook_guards::check_min_out;
+use soroban_sdk::{contract, contracterror, contractimpl, token, Address, Env, IntoVal};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SwapError {
+    InvalidAmount = 1,
+    /// One side offers less than the other side's minimum.
+    SlippageExceeded = 2,
+}
+
+#[contract]
+pub struct AtomicSwapContract;
+
+#[contractimpl]
+impl AtomicSwapContract {
+    /// Swap `token_a` from `a` for `token_b` from `b`.
+    ///
+    /// `a` receives exactly `min_b_for_a` of `token_b` and pays exactly
+    /// `min_a_for_b` of `token_a`; the same holds the other way round.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        env: Env,
+        a: Address,
+        b: Address,
+        token_a: Address,
+        token_b: Address,
+        amount_a: i128,
+        min_b_for_a: i128,
+        amount_b: i128,
+        min_a_for_b: i128,
+    ) -> Result<(), SwapError> {
+        if min_b_for_a <= 0 || min_a_for_b <= 0 {
+            return Err(SwapError::InvalidAmount);
+        }
+        // Each offer must cover what the other side insists on.
+        check_min_out(amount_b, min_b_for_a).map_err(|_| SwapError::SlippageExceeded)?;
+        check_min_out(amount_a, min_a_for_b).map_err(|_| SwapError::SlippageExceeded)?;
+
+        // Each party authorizes only their own terms, not the whole call.
+        // Neither needs to know the other's exact offer in advance.
+        a.require_auth_for_args(
+            (token_a.clone(), token_b.clone(), amount_a, min_b_for_a).into_val(&env),
+        );
+        b.require_auth_for_args(
+            (token_b.clone(), token_a.clone(), amount_b, min_a_for_b).into_val(&env),
+        );
+
+        move_token(&env, &token_a, &a, &b, amount_a, min_a_for_b);
+        move_token(&env, &token_b, &b, &a, amount_b, min_b_for_a);
+        Ok(())
+    }
+}
+
+/// Take `max_spend` from `from`, pass `transfer_amount` on to `to`, and
+/// refund the difference. Pulling the full offer first means the payer's
+/// signed transfer amount never depends on the counterparty.
+fn move_token(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    max_spend: i128,
+    transfer_amount: i128,
+) {
+    let token = token::Client::new(env, token);
+    let contract = env.current_contract_address();
+    token.transfer(from, &contract, &max_spend);
+    token.transfer(&contract, to, &transfer_amount);
+    token.transfer(&contract, from, &(max_spend - transfer_amount));
+}
+
+mod test;
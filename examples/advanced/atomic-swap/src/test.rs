@@ -0,0 +1,224 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    swap: AtomicSwapContractClient<'static>,
+    token_a: TokenClient<'static>,
+    token_b: TokenClient<'static>,
+    a: Address,
+    b: Address,
+}
+
+/// `a` holds 1_000 of token A, `b` holds 5_000 of token B.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let token_a = create_token(&env, &a, 1_000);
+    let token_b = create_token(&env, &b, 5_000);
+    let swap =
+        AtomicSwapContractClient::new(&env, &env.register_contract(None, AtomicSwapContract));
+
+    Setup {
+        env,
+        swap,
+        token_a,
+        token_b,
+        a,
+        b,
+    }
+}
+
+fn create_token(env: &Env, holder: &Address, amount: i128) -> TokenClient<'static> {
+    let id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    StellarAssetClient::new(env, &id).mint(holder, &amount);
+    TokenClient::new(env, &id)
+}
+
+#[test]
+fn test_swap_exchanges_minimums_and_refunds_rest() {
+    let s = setup();
+    // a offers up to 1_000 A, wants at least 4_500 B.
+    // b offers up to 5_000 B, wants at least 950 A.
+    s.swap.swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &4_500,
+        &5_000,
+        &950,
+    );
+
+    assert_eq!(s.token_a.balance(&s.a), 50);
+    assert_eq!(s.token_a.balance(&s.b), 950);
+    assert_eq!(s.token_b.balance(&s.a), 4_500);
+    assert_eq!(s.token_b.balance(&s.b), 500);
+    assert_eq!(s.token_a.balance(&s.swap.address), 0);
+    assert_eq!(s.token_b.balance(&s.swap.address), 0);
+}
+
+#[test]
+fn test_each_party_signs_only_their_terms() {
+    let s = setup();
+    s.swap.swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &4_500,
+        &5_000,
+        &950,
+    );
+
+    let terms = |party: &Address, give: &Address, get: &Address, amount: i128, min: i128| {
+        (
+            party.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    s.swap.address.clone(),
+                    symbol_short!("swap"),
+                    (give.clone(), get.clone(), amount, min).into_val(&s.env),
+                )),
+                sub_invocations: [AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        give.clone(),
+                        symbol_short!("transfer"),
+                        (party.clone(), s.swap.address.clone(), amount).into_val(&s.env),
+                    )),
+                    sub_invocations: [].into(),
+                }]
+                .into(),
+            },
+        )
+    };
+    assert_eq!(
+        s.env.auths(),
+        [
+            terms(&s.a, &s.token_a.address, &s.token_b.address, 1_000, 4_500),
+            terms(&s.b, &s.token_b.address, &s.token_a.address, 5_000, 950),
+        ]
+    );
+}
+
+#[test]
+fn test_exact_offers() {
+    let s = setup();
+    s.swap.swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &5_000,
+        &5_000,
+        &1_000,
+    );
+    assert_eq!(s.token_b.balance(&s.a), 5_000);
+    assert_eq!(s.token_a.balance(&s.b), 1_000);
+}
+
+#[test]
+fn test_offer_below_counterparty_minimum_rejected() {
+    let s = setup();
+    // b offers 4_000 B but a wants at least 4_500.
+    assert_eq!(
+        s.swap.try_swap(
+            &s.a,
+            &s.b,
+            &s.token_a.address,
+            &s.token_b.address,
+            &1_000,
+            &4_500,
+            &4_000,
+            &950,
+        ),
+        Err(Ok(SwapError::SlippageExceeded))
+    );
+    // a offers 900 A but b wants at least 950.
+    assert_eq!(
+        s.swap.try_swap(
+            &s.a,
+            &s.b,
+            &s.token_a.address,
+            &s.token_b.address,
+            &900,
+            &4_500,
+            &5_000,
+            &950,
+        ),
+        Err(Ok(SwapError::SlippageExceeded))
+    );
+    assert_eq!(s.token_a.balance(&s.a), 1_000);
+    assert_eq!(s.token_b.balance(&s.b), 5_000);
+}
+
+#[test]
+fn test_zero_minimum_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.swap.try_swap(
+            &s.a,
+            &s.b,
+            &s.token_a.address,
+            &s.token_b.address,
+            &1_000,
+            &0,
+            &5_000,
+            &950,
+        ),
+        Err(Ok(SwapError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_insufficient_balance_reverts_both_sides() {
+    let s = setup();
+    // a does not hold 2_000 A: the whole swap fails, nothing moves.
+    assert!(s
+        .swap
+        .try_swap(
+            &s.a,
+            &s.b,
+            &s.token_a.address,
+            &s.token_b.address,
+            &2_000,
+            &4_500,
+            &5_000,
+            &950,
+        )
+        .is_err());
+    assert_eq!(s.token_a.balance(&s.a), 1_000);
+    assert_eq!(s.token_b.balance(&s.b), 5_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_swap_requires_both_signatures() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.swap.swap(
+        &s.a,
+        &s.b,
+        &s.token_a.address,
+        &s.token_b.address,
+        &1_000,
+        &4_500,
+        &5_000,
+        &950,
+    );
+}
@@ -0,0 +1,393 @@
+//! # Auction
+//!
+//! Sellers auction an amount of one token (the lot) for a payment token, in
+//! one of two modes:
+//!
+//! ```text
+//! English   price ▲        ┌── bid          highest bid at end_ledger wins;
+//!                 │    ┌───┘                each outbid bidder is refunded
+//!           reserve ───┘                    in the same call
+//!                 └──────────────────▶ ledger
+//!
+//! Dutch     price ▲
+//!           start ─╲                        first buyer pays the current
+//!                 │   ╲                     price and settles at once
+//!           reserve ────╲────────▶ ledger
+//! ```
+//!
+//! The lot is escrowed in this contract when the auction is created, so
+//! the winner is always paid out. An auction that ends without a winner
+//! returns the lot to the seller when it is settled.
+//!
+//! ## Time
+//!
+//! Auctions run from `start_ledger` until `end_ledger`, measured with
+//! `env.ledger().sequence()`. Bids are accepted while the sequence is below
+//! `end_ledger`; settlement from `end_ledger` on.
+//!
+//! A Dutch price falls linearly from `start_price` at `start_ledger` to
+//! `reserve_price` at `end_ledger`, so it drops by about
+//! `(start_price - reserve_price) / duration` each ledger. It never goes
+//! below the reserve.
+//!
+//! ## Escrow
+//!
+//! An English bid is moved into the contract and the bid it beats is sent
+//! back within the same call. Only the current highest bid is ever held.
+//! A Dutch purchase pays the seller directly, as nothing is left to decide.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+/// A new English bid must beat the current one by at least this much, in
+/// basis points.
+pub const MIN_INCREMENT_BPS: i128 = 500;
+/// Longest auction, about 30 days of ledgers.
+pub const MAX_DURATION: u32 = 518_400;
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AuctionKind {
+    English,
+    Dutch,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub kind: AuctionKind,
+    pub seller: Address,
+    pub lot_token: Address,
+    pub lot_amount: i128,
+    /// Dutch: the opening price. English: equal to `reserve_price`.
+    pub start_price: i128,
+    /// English: the lowest first bid. Dutch: the floor price.
+    pub reserve_price: i128,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    /// The highest bidder, or the Dutch buyer.
+    pub bidder: Option<Address>,
+    pub bid: i128,
+    pub settled: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The payment token.
+    Token,
+    NextId,
+    Auction(u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuctionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// A lot amount or price that is not positive, or a Dutch start price
+    /// below the reserve.
+    InvalidAmount = 3,
+    /// Zero or above `MAX_DURATION`.
+    InvalidDuration = 4,
+    NoAuction = 5,
+    AuctionEnded = 6,
+    AuctionNotEnded = 7,
+    BidTooLow = 8,
+    /// `bid` on a Dutch auction or `buy` on an English one.
+    WrongKind = 9,
+    AlreadySettled = 10,
+    SellerCannotBid = 11,
+}
+
+#[contract]
+pub struct AuctionHouse;
+
+#[contractimpl]
+impl AuctionHouse {
+    /// Set the token all auctions here are paid in.
+    pub fn initialize(env: Env, token: Address) -> Result<(), AuctionError> {
+        if env.storage().instance().has(&DataKey::Token) {
+            return Err(AuctionError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    // ==================== CREATE ====================
+
+    /// Escrow the lot and open an ascending auction for `duration` ledgers.
+    /// The first bid must reach `reserve_price`.
+    ///
+    /// Emits `(auction, created)` with `(id, seller)`.
+    pub fn create_english(
+        env: Env,
+        seller: Address,
+        lot_token: Address,
+        lot_amount: i128,
+        reserve_price: i128,
+        duration: u32,
+    ) -> Result<u32, AuctionError> {
+        create(
+            &env,
+            AuctionKind::English,
+            seller,
+            lot_token,
+            lot_amount,
+            reserve_price,
+            reserve_price,
+            duration,
+        )
+    }
+
+    /// Escrow the lot and open a descending auction for `duration` ledgers,
+    /// its price falling from `start_price` to `reserve_price`.
+    ///
+    /// Emits `(auction, created)` with `(id, seller)`.
+    pub fn create_dutch(
+        env: Env,
+        seller: Address,
+        lot_token: Address,
+        lot_amount: i128,
+        start_price: i128,
+        reserve_price: i128,
+        duration: u32,
+    ) -> Result<u32, AuctionError> {
+        create(
+            &env,
+            AuctionKind::Dutch,
+            seller,
+            lot_token,
+            lot_amount,
+            start_price,
+            reserve_price,
+            duration,
+        )
+    }
+
+    // ==================== BIDDING ====================
+
+    /// Escrow `amount` as the new highest bid on an English auction and
+    /// refund the bid it beats.
+    ///
+    /// Emits `(auction, bid)` with `(id, bidder, amount)`.
+    pub fn bid(env: Env, id: u32, bidder: Address, amount: i128) -> Result<(), AuctionError> {
+        bidder.require_auth();
+        let mut auction = open_auction(&env, id, AuctionKind::English)?;
+        if bidder == auction.seller {
+            return Err(AuctionError::SellerCannotBid);
+        }
+        if amount < min_next_bid(&auction) {
+            return Err(AuctionError::BidTooLow);
+        }
+
+        let token = payment_token(&env)?;
+        let contract = env.current_contract_address();
+        token.transfer(&bidder, &contract, &amount);
+        if let Some(previous) = auction.bidder.take() {
+            token.transfer(&contract, &previous, &auction.bid);
+        }
+        auction.bidder = Some(bidder.clone());
+        auction.bid = amount;
+        save(&env, id, &auction);
+
+        env.events().publish(
+            (symbol_short!("auction"), symbol_short!("bid")),
+            (id, bidder, amount),
+        );
+        Ok(())
+    }
+
+    /// Buy a Dutch auction's lot at the current price. The seller is paid
+    /// and the lot delivered at once. Returns the price paid.
+    ///
+    /// Emits `(auction, bid)` with `(id, buyer, price)`, then
+    /// `(auction, settled)`.
+    pub fn buy(env: Env, id: u32, buyer: Address) -> Result<i128, AuctionError> {
+        buyer.require_auth();
+        let mut auction = open_auction(&env, id, AuctionKind::Dutch)?;
+        if buyer == auction.seller {
+            return Err(AuctionError::SellerCannotBid);
+        }
+        let price = dutch_price(&auction, env.ledger().sequence());
+        payment_token(&env)?.transfer(&buyer, &auction.seller, &price);
+
+        auction.bidder = Some(buyer.clone());
+        auction.bid = price;
+        env.events().publish(
+            (symbol_short!("auction"), symbol_short!("bid")),
+            (id, buyer, price),
+        );
+        finish(&env, id, auction);
+        Ok(price)
+    }
+
+    // ==================== SETTLEMENT ====================
+
+    /// Close an auction from `end_ledger` on. Anyone can settle. The winning
+    /// English bid goes to the seller and the lot to the winner; without a
+    /// winner the lot goes back to the seller. Returns the winner.
+    ///
+    /// Emits `(auction, settled)` with `(id, winner, price)`.
+    pub fn settle(env: Env, id: u32) -> Result<Option<Address>, AuctionError> {
+        let auction = Self::auction(env.clone(), id)?;
+        if auction.settled {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if env.ledger().sequence() < auction.end_ledger {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        let winner = auction.bidder.clone();
+        if winner.is_some() {
+            // Only English bids are still escrowed; a Dutch sale settled in
+            // `buy`.
+            payment_token(&env)?.transfer(
+                &env.current_contract_address(),
+                &auction.seller,
+                &auction.bid,
+            );
+        }
+        finish(&env, id, auction);
+        Ok(winner)
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn auction(env: Env, id: u32) -> Result<Auction, AuctionError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Auction(id))
+            .ok_or(AuctionError::NoAuction)
+    }
+
+    /// What it takes to win right now: the smallest accepted English bid,
+    /// or the Dutch price at the current ledger.
+    pub fn price(env: Env, id: u32) -> Result<i128, AuctionError> {
+        let auction = Self::auction(env.clone(), id)?;
+        Ok(match auction.kind {
+            AuctionKind::English => min_next_bid(&auction),
+            AuctionKind::Dutch => dutch_price(&auction, env.ledger().sequence()),
+        })
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create(
+    env: &Env,
+    kind: AuctionKind,
+    seller: Address,
+    lot_token: Address,
+    lot_amount: i128,
+    start_price: i128,
+    reserve_price: i128,
+    duration: u32,
+) -> Result<u32, AuctionError> {
+    seller.require_auth();
+    payment_token(env)?;
+    if lot_amount <= 0 || reserve_price <= 0 || start_price < reserve_price {
+        return Err(AuctionError::InvalidAmount);
+    }
+    if duration == 0 || duration > MAX_DURATION {
+        return Err(AuctionError::InvalidDuration);
+    }
+    token::Client::new(env, &lot_token).transfer(
+        &seller,
+        &env.current_contract_address(),
+        &lot_amount,
+    );
+
+    let id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+    env.storage().instance().set(&DataKey::NextId, &(id + 1));
+    let start_ledger = env.ledger().sequence();
+    save(
+        env,
+        id,
+        &Auction {
+            kind,
+            seller: seller.clone(),
+            lot_token,
+            lot_amount,
+            start_price,
+            reserve_price,
+            start_ledger,
+            end_ledger: start_ledger + duration,
+            bidder: None,
+            bid: 0,
+            settled: false,
+        },
+    );
+
+    env.events().publish(
+        (symbol_short!("auction"), symbol_short!("created")),
+        (id, seller),
+    );
+    Ok(id)
+}
+
+/// Load an auction of `kind` that still takes bids.
+fn open_auction(env: &Env, id: u32, kind: AuctionKind) -> Result<Auction, AuctionError> {
+    let auction = AuctionHouse::auction(env.clone(), id)?;
+    if auction.kind != kind {
+        return Err(AuctionError::WrongKind);
+    }
+    if auction.settled || env.ledger().sequence() >= auction.end_ledger {
+        return Err(AuctionError::AuctionEnded);
+    }
+    Ok(auction)
+}
+
+fn min_next_bid(auction: &Auction) -> i128 {
+    if auction.bidder.is_none() {
+        return auction.reserve_price;
+    }
+    // Round the increment up so tiny bids still have to grow.
+    let increment = (auction.bid * MIN_INCREMENT_BPS + 9_999) / 10_000;
+    auction.bid + increment
+}
+
+/// The Dutch price at `ledger`, rounded up so it reaches the reserve only
+/// at `end_ledger`.
+fn dutch_price(auction: &Auction, ledger: u32) -> i128 {
+    let duration = (auction.end_ledger - auction.start_ledger) as i128;
+    let remaining = auction.end_ledger.saturating_sub(ledger) as i128;
+    let spread = auction.start_price - auction.reserve_price;
+    auction.reserve_price + (spread * remaining + duration - 1) / duration
+}
+
+/// Deliver the lot to the winner, or back to the seller, and mark the
+/// auction settled.
+fn finish(env: &Env, id: u32, mut auction: Auction) {
+    let recipient = auction.bidder.clone().unwrap_or(auction.seller.clone());
+    token::Client::new(env, &auction.lot_token).transfer(
+        &env.current_contract_address(),
+        &recipient,
+        &auction.lot_amount,
+    );
+    auction.settled = true;
+    save(env, id, &auction);
+    env.events().publish(
+        (symbol_short!("auction"), symbol_short!("settled")),
+        (id, auction.bidder, auction.bid),
+    );
+}
+
+fn payment_token(env: &Env) -> Result<token::Client<'_>, AuctionError> {
+    let token: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Token)
+        .ok_or(AuctionError::NotInitialized)?;
+    Ok(token::Client::new(env, &token))
+}
+
+fn save(env: &Env, id: u32, auction: &Auction) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), auction);
+}
+
+mod test;
@@ -0,0 +1,291 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    vec, IntoVal,
+};
+
+const START: u32 = 1_000;
+const LOT: i128 = 10;
+
+struct Setup<'a> {
+    env: Env,
+    house: AuctionHouseClient<'a>,
+    payment: token::Client<'a>,
+    lot: token::Client<'a>,
+    seller: Address,
+    alice: Address,
+    bob: Address,
+}
+
+/// The seller holds 10 of the lot token; Alice and Bob hold 5_000 each of
+/// the payment token.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.sequence_number = START);
+
+    let issuer = Address::generate(&env);
+    let payment_id = env
+        .register_stellar_asset_contract_v2(issuer.clone())
+        .address();
+    let lot_id = env.register_stellar_asset_contract_v2(issuer).address();
+
+    let house = AuctionHouseClient::new(&env, &env.register_contract(None, AuctionHouse));
+    house.initialize(&payment_id);
+
+    let seller = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    StellarAssetClient::new(&env, &lot_id).mint(&seller, &LOT);
+    for bidder in [&alice, &bob] {
+        StellarAssetClient::new(&env, &payment_id).mint(bidder, &5_000);
+    }
+    Setup {
+        payment: token::Client::new(&env, &payment_id),
+        lot: token::Client::new(&env, &lot_id),
+        env,
+        house,
+        seller,
+        alice,
+        bob,
+    }
+}
+
+impl Setup<'_> {
+    fn set_ledger(&self, sequence: u32) {
+        self.env.ledger().with_mut(|l| l.sequence_number = sequence);
+    }
+
+    /// Reserve 100, open for 50 ledgers.
+    fn english(&self) -> u32 {
+        self.house
+            .create_english(&self.seller, &self.lot.address, &LOT, &100, &50)
+    }
+
+    /// From 1_000 down to 400 over 100 ledgers.
+    fn dutch(&self) -> u32 {
+        self.house
+            .create_dutch(&self.seller, &self.lot.address, &LOT, &1_000, &400, &100)
+    }
+}
+
+// ==================== ENGLISH ====================
+
+#[test]
+fn test_english_outbid_refunded() {
+    let s = setup();
+    let id = s.english();
+    assert_eq!(s.lot.balance(&s.house.address), LOT);
+    assert_eq!(
+        s.house.try_bid(&id, &s.alice, &99),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.house.bid(&id, &s.alice, &100);
+    assert_eq!(s.payment.balance(&s.alice), 4_900);
+
+    // 5% above 100.
+    assert_eq!(s.house.price(&id), 105);
+    assert_eq!(
+        s.house.try_bid(&id, &s.bob, &104),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.house.bid(&id, &s.bob, &105);
+    assert_eq!(s.payment.balance(&s.alice), 5_000);
+    assert_eq!(s.payment.balance(&s.bob), 4_895);
+    // Only the highest bid is held.
+    assert_eq!(s.payment.balance(&s.house.address), 105);
+}
+
+#[test]
+fn test_bid_events() {
+    let s = setup();
+    let id = s.english();
+    let last_event = || {
+        let events = s.env.events().all();
+        events.slice(events.len() - 1..)
+    };
+    let bid_event = |bidder: &Address, amount: i128| {
+        vec![
+            &s.env,
+            (
+                s.house.address.clone(),
+                (symbol_short!("auction"), symbol_short!("bid")).into_val(&s.env),
+                (id, bidder.clone(), amount).into_val(&s.env),
+            ),
+        ]
+    };
+    s.house.bid(&id, &s.alice, &100);
+    assert_eq!(last_event(), bid_event(&s.alice, 100));
+    s.house.bid(&id, &s.bob, &200);
+    assert_eq!(last_event(), bid_event(&s.bob, 200));
+}
+
+#[test]
+fn test_english_deadline_and_settlement() {
+    let s = setup();
+    let id = s.english();
+    s.house.bid(&id, &s.alice, &300);
+    let end = s.house.auction(&id).end_ledger;
+    assert_eq!(end, START + 50);
+
+    // Last ledger for bids...
+    s.set_ledger(end - 1);
+    assert_eq!(
+        s.house.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    s.house.bid(&id, &s.bob, &400);
+
+    // ...and closed from `end_ledger` on.
+    s.set_ledger(end);
+    assert_eq!(
+        s.house.try_bid(&id, &s.alice, &1_000),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+    assert_eq!(s.house.settle(&id), Some(s.bob.clone()));
+    assert_eq!(s.lot.balance(&s.bob), LOT);
+    assert_eq!(s.payment.balance(&s.seller), 400);
+    assert_eq!(s.payment.balance(&s.house.address), 0);
+    assert_eq!(
+        s.house.try_settle(&id),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+/// Nobody reached the reserve: the lot goes back to the seller.
+#[test]
+fn test_english_without_bids_returns_lot() {
+    let s = setup();
+    let id = s.english();
+    s.set_ledger(START + 50);
+    assert_eq!(s.house.settle(&id), None);
+    assert_eq!(s.lot.balance(&s.seller), LOT);
+    assert!(s.house.auction(&id).settled);
+}
+
+// ==================== DUTCH ====================
+
+#[test]
+fn test_dutch_price_decays_per_ledger() {
+    let s = setup();
+    let id = s.dutch();
+    assert_eq!(s.house.price(&id), 1_000);
+    s.set_ledger(START + 1);
+    assert_eq!(s.house.price(&id), 994);
+    s.set_ledger(START + 50);
+    assert_eq!(s.house.price(&id), 700);
+    // Rounded up, so the reserve is reached only at the end.
+    s.set_ledger(START + 99);
+    assert_eq!(s.house.price(&id), 406);
+    s.set_ledger(START + 500);
+    assert_eq!(s.house.price(&id), 400);
+}
+
+#[test]
+fn test_dutch_buy_settles_immediately() {
+    let s = setup();
+    let id = s.dutch();
+    s.set_ledger(START + 50);
+    assert_eq!(s.house.buy(&id, &s.alice), 700);
+    assert_eq!(s.payment.balance(&s.seller), 700);
+    assert_eq!(s.lot.balance(&s.alice), LOT);
+
+    let auction = s.house.auction(&id);
+    assert!(auction.settled);
+    assert_eq!((auction.bidder, auction.bid), (Some(s.alice.clone()), 700));
+    assert_eq!(
+        s.house.try_buy(&id, &s.bob),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+    s.set_ledger(START + 100);
+    assert_eq!(
+        s.house.try_settle(&id),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+#[test]
+fn test_dutch_unsold_returns_lot() {
+    let s = setup();
+    let id = s.dutch();
+    s.set_ledger(START + 100);
+    assert_eq!(
+        s.house.try_buy(&id, &s.alice),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+    assert_eq!(s.house.settle(&id), None);
+    assert_eq!(s.lot.balance(&s.seller), LOT);
+}
+
+// ==================== VALIDATION ====================
+
+#[test]
+fn test_mode_and_seller_checks() {
+    let s = setup();
+    let english = s
+        .house
+        .create_english(&s.seller, &s.lot.address, &5, &100, &50);
+    let dutch = s
+        .house
+        .create_dutch(&s.seller, &s.lot.address, &5, &1_000, &400, &100);
+    assert_eq!(
+        s.house.try_buy(&english, &s.alice),
+        Err(Ok(AuctionError::WrongKind))
+    );
+    assert_eq!(
+        s.house.try_bid(&dutch, &s.alice, &1_000),
+        Err(Ok(AuctionError::WrongKind))
+    );
+    assert_eq!(
+        s.house.try_bid(&english, &s.seller, &100),
+        Err(Ok(AuctionError::SellerCannotBid))
+    );
+    assert_eq!(
+        s.house.try_bid(&(dutch + 1), &s.alice, &100),
+        Err(Ok(AuctionError::NoAuction))
+    );
+}
+
+#[test]
+fn test_create_validation() {
+    let s = setup();
+    let lot = &s.lot.address;
+    assert_eq!(
+        s.house.try_create_english(&s.seller, lot, &0, &100, &50),
+        Err(Ok(AuctionError::InvalidAmount))
+    );
+    assert_eq!(
+        s.house.try_create_english(&s.seller, lot, &LOT, &0, &50),
+        Err(Ok(AuctionError::InvalidAmount))
+    );
+    assert_eq!(
+        s.house
+            .try_create_dutch(&s.seller, lot, &LOT, &399, &400, &100),
+        Err(Ok(AuctionError::InvalidAmount))
+    );
+    assert_eq!(
+        s.house.try_create_english(&s.seller, lot, &LOT, &100, &0),
+        Err(Ok(AuctionError::InvalidDuration))
+    );
+    assert_eq!(
+        s.house
+            .try_create_english(&s.seller, lot, &LOT, &100, &(MAX_DURATION + 1)),
+        Err(Ok(AuctionError::InvalidDuration))
+    );
+    assert_eq!(
+        s.house.try_initialize(lot),
+        Err(Ok(AuctionError::AlreadyInitialized))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_bid_requires_bidder_auth() {
+    let s = setup();
+    let id = s.english();
+    s.env.set_auths(&[]);
+    s.house.bid(&id, &s.alice, &100);
+}
@@ -0,0 +1,28 @@
+//! Callee for the cross-contract calls example: a checked calculator that
+//! reports failures as contract errors instead of panicking.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, Env};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CalcError {
+    Overflow = 1,
+    DivisionByZero = 2,
+}
+
+#[contract]
+pub struct Calculator;
+
+#[contractimpl]
+impl Calculator {
+    pub fn add(_env: Env, a: u32, b: u32) -> Result<u32, CalcError> {
+        a.checked_add(b).ok_or(CalcError::Overflow)
+    }
+
+    pub fn div(_env: Env, a: u32, b: u32) -> Result<u32, CalcError> {
+        a.checked_div(b).ok_or(CalcError::DivisionByZero)
+    }
+}
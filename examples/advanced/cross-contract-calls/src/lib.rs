@@ -0,0 +1,115 @@
+//! # Cross-Contract Calls
+//!
+//! The caller reaches the `Calculator` contract in `callee/` three ways:
+//!
+//! ```text
+//! add_with_client   CalculatorClient::add         typed args, panics on error
+//! add_with_invoke   env.invoke_contract("add")    Vec<Val> args, panics on error
+//! safe_div          CalculatorClient::try_div     callee errors come back as values
+//! ```
+//!
+//! The caller does not depend on the callee crate. Linking it would also
+//! link the callee's exported functions into the caller's Wasm. Instead it
+//! declares the interface it needs with `#[contractclient]`, which generates
+//! `CalculatorClient`, and mirrors the callee's error codes in `CalcError`.
+//!
+//! A callee error that is not caught aborts the whole transaction, including
+//! the caller's own writes. `try_` calls let the caller decide instead.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, symbol_short, vec, Address, Env,
+    IntoVal, Symbol, Val, Vec,
+};
+
+/// Error codes returned by the callee. The numbers must match its
+/// `#[contracterror]` enum; the names are only for this crate.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CalcError {
+    Overflow = 1,
+    DivisionByZero = 2,
+}
+
+/// The part of the callee's interface this contract uses.
+#[contractclient(name = "CalculatorClient")]
+pub trait CalculatorInterface {
+    fn add(env: Env, a: u32, b: u32) -> Result<u32, CalcError>;
+    fn div(env: Env, a: u32, b: u32) -> Result<u32, CalcError>;
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CallerError {
+    /// The callee reported a division by zero.
+    DivisionByZero = 1,
+    /// The callee returned a contract error this contract does not handle.
+    CalleeError = 2,
+    /// The call failed for another reason: the function does not exist, the
+    /// callee panicked, or it ran out of budget.
+    CalleeFailed = 3,
+    /// The callee returned a value of the wrong type.
+    UnexpectedReturn = 4,
+}
+
+#[contract]
+pub struct Caller;
+
+#[contractimpl]
+impl Caller {
+    /// Typed client call. A callee error panics here and propagates to the
+    /// transaction unchanged.
+    pub fn add_with_client(env: Env, callee: Address, a: u32, b: u32) -> u32 {
+        CalculatorClient::new(&env, &callee).add(&a, &b)
+    }
+
+    /// The same call without a client: function name as a `Symbol` and
+    /// arguments converted to `Val` by hand. Nothing checks the argument
+    /// types until the callee receives them.
+    pub fn add_with_invoke(env: Env, callee: Address, a: u32, b: u32) -> u32 {
+        let args: Vec<Val> = vec![&env, a.into_val(&env), b.into_val(&env)];
+        env.invoke_contract(&callee, &symbol_short!("add"), args)
+    }
+
+    /// Catch the callee's errors and translate them into this contract's own.
+    pub fn safe_div(env: Env, callee: Address, a: u32, b: u32) -> Result<u32, CallerError> {
+        match CalculatorClient::new(&env, &callee).try_div(&a, &b) {
+            // Call succeeded and the return value converted to `u32`.
+            Ok(Ok(quotient)) => Ok(quotient),
+            // Call succeeded but returned something else.
+            Ok(Err(_)) => Err(CallerError::UnexpectedReturn),
+            // The callee returned one of its contract errors.
+            Err(Ok(CalcError::DivisionByZero)) => Err(CallerError::DivisionByZero),
+            Err(Ok(_)) => Err(CallerError::CalleeError),
+            // Any other host error.
+            Err(Err(_)) => Err(CallerError::CalleeFailed),
+        }
+    }
+
+    /// Recover from a failed call by falling back to `default`.
+    pub fn div_or(env: Env, callee: Address, a: u32, b: u32, default: u32) -> u32 {
+        Self::safe_div(env, callee, a, b).unwrap_or(default)
+    }
+
+    /// Fully dynamic call: any function, any arguments. Errors are reduced
+    /// to the caller's error codes.
+    pub fn call_raw(
+        env: Env,
+        callee: Address,
+        func: Symbol,
+        args: Vec<Val>,
+    ) -> Result<u32, CallerError> {
+        match env.try_invoke_contract::<u32, CalcError>(&callee, &func, args) {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) => Err(CallerError::UnexpectedReturn),
+            Err(Ok(CalcError::DivisionByZero)) => Err(CallerError::DivisionByZero),
+            Err(Ok(_)) => Err(CallerError::CalleeError),
+            Err(Err(_)) => Err(CallerError::CalleeFailed),
+        }
+    }
+}
+
+mod test;
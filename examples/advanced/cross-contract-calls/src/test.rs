@@ -0,0 +1,113 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{Address, Env, Error, IntoVal};
+
+struct Setup {
+    env: Env,
+    caller: CallerClient<'static>,
+    callee: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    let callee = env.register_contract(None, cross_contract_callee::Calculator);
+    let caller_id = env.register_contract(None, Caller);
+    let caller = CallerClient::new(&env, &caller_id);
+    Setup {
+        env,
+        caller,
+        callee,
+    }
+}
+
+// ── success paths ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_add_with_client() {
+    let s = setup();
+    assert_eq!(s.caller.add_with_client(&s.callee, &2, &3), 5);
+}
+
+#[test]
+fn test_add_with_invoke() {
+    let s = setup();
+    assert_eq!(s.caller.add_with_invoke(&s.callee, &2, &3), 5);
+}
+
+#[test]
+fn test_safe_div() {
+    let s = setup();
+    assert_eq!(s.caller.safe_div(&s.callee, &10, &3), 3);
+    assert_eq!(s.caller.div_or(&s.callee, &10, &2, &0), 5);
+}
+
+#[test]
+fn test_call_raw() {
+    let s = setup();
+    let args = vec![&s.env, 7_u32.into_val(&s.env), 8_u32.into_val(&s.env)];
+    assert_eq!(
+        s.caller.call_raw(&s.callee, &symbol_short!("add"), &args),
+        15
+    );
+}
+
+// ── propagated errors ────────────────────────────────────────────────────────
+
+#[test]
+fn test_uncaught_callee_error_aborts_caller() {
+    let s = setup();
+    // The callee's `Overflow` surfaces unchanged from the outer call.
+    let overflow = Error::from_contract_error(CalcError::Overflow as u32);
+    assert_eq!(
+        s.caller.try_add_with_client(&s.callee, &u32::MAX, &1),
+        Err(Ok(overflow))
+    );
+    assert_eq!(
+        s.caller.try_add_with_invoke(&s.callee, &u32::MAX, &1),
+        Err(Ok(overflow))
+    );
+}
+
+#[test]
+fn test_caught_callee_error_is_translated() {
+    let s = setup();
+    assert_eq!(
+        s.caller.try_safe_div(&s.callee, &1, &0),
+        Err(Ok(CallerError::DivisionByZero))
+    );
+    assert_eq!(s.caller.div_or(&s.callee, &1, &0, &42), 42);
+}
+
+#[test]
+fn test_unhandled_callee_error() {
+    let s = setup();
+    let args = vec![&s.env, u32::MAX.into_val(&s.env), 1_u32.into_val(&s.env)];
+    assert_eq!(
+        s.caller
+            .try_call_raw(&s.callee, &symbol_short!("add"), &args),
+        Err(Ok(CallerError::CalleeError))
+    );
+}
+
+#[test]
+fn test_missing_function_is_a_host_error() {
+    let s = setup();
+    let args = vec![&s.env, 1_u32.into_val(&s.env)];
+    assert_eq!(
+        s.caller
+            .try_call_raw(&s.callee, &symbol_short!("mul"), &args),
+        Err(Ok(CallerError::CalleeFailed))
+    );
+}
+
+#[test]
+fn test_wrong_argument_types_are_a_host_error() {
+    let s = setup();
+    let args = vec![&s.env, symbol_short!("two").into_val(&s.env)];
+    assert_eq!(
+        s.caller
+            .try_call_raw(&s.callee, &symbol_short!("add"), &args),
+        Err(Ok(CallerError::CalleeFailed))
+    );
+}
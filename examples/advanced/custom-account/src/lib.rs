@@ -0,0 +1,250 @@
+//! # Custom Account
+//!
+//! A contract that *is* an account. When anything calls
+//! `account.require_auth()` on this contract's address, the host does not
+//! look for a Stellar keypair signature; it calls the contract's
+//! `__check_auth` with the signature payload and whatever signatures the
+//! submitter attached:
+//!
+//! ```text
+//! vault.withdraw(account, ..)
+//!   └─ account.require_auth()
+//!        └─ host ──▶ CustomAccount::__check_auth(payload, signatures, contexts)
+//!                      ├─ every signer is known and signatures are sorted
+//!                      ├─ ed25519_verify(signer, payload, signature)
+//!                      └─ summed weight ≥ threshold for these contexts
+//! ```
+//!
+//! - **Weighted signers.** Each ed25519 key carries a weight. Signatures
+//!   must be sorted by public key, which rules out counting one key twice.
+//! - **Two thresholds.** Calls into other contracts need the `operation`
+//!   weight. Calls back into this contract (changing signers or thresholds)
+//!   need the usually higher `management` weight.
+//! - **Self-administered.** `set_signer` and `set_thresholds` authorize with
+//!   `current_contract_address().require_auth()`, so they go through
+//!   `__check_auth` like everything else. There is no separate admin key.
+
+#![no_std]
+
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype,
+    crypto::Hash,
+    symbol_short, BytesN, Env, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signer {
+    pub public_key: BytesN<32>,
+    pub weight: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Thresholds {
+    /// Weight needed to authorize calls into other contracts.
+    pub operation: u32,
+    /// Weight needed to authorize calls into this contract.
+    pub management: u32,
+}
+
+/// One entry of the signature list passed to `__check_auth`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Signer(BytesN<32>),
+    TotalWeight,
+    Thresholds,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccountError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    DuplicateSigner = 3,
+    InvalidWeight = 4,
+    /// A threshold is zero or above the total signer weight.
+    InvalidThreshold = 5,
+    UnknownSigner = 6,
+    /// Signatures are not strictly ascending by public key.
+    UnsortedSignatures = 7,
+    InsufficientWeight = 8,
+}
+
+#[contract]
+pub struct CustomAccount;
+
+#[contractimpl]
+impl CustomAccount {
+    pub fn init(
+        env: Env,
+        signers: Vec<Signer>,
+        thresholds: Thresholds,
+    ) -> Result<(), AccountError> {
+        let storage = env.storage().instance();
+        if storage.has(&DataKey::Thresholds) {
+            return Err(AccountError::AlreadyInitialized);
+        }
+        let mut total: u32 = 0;
+        for signer in signers.iter() {
+            if signer.weight == 0 {
+                return Err(AccountError::InvalidWeight);
+            }
+            let key = DataKey::Signer(signer.public_key.clone());
+            if storage.has(&key) {
+                return Err(AccountError::DuplicateSigner);
+            }
+            storage.set(&key, &signer.weight);
+            total = total
+                .checked_add(signer.weight)
+                .ok_or(AccountError::InvalidWeight)?;
+        }
+        Self::check_thresholds(&thresholds, total)?;
+        storage.set(&DataKey::TotalWeight, &total);
+        storage.set(&DataKey::Thresholds, &thresholds);
+        Ok(())
+    }
+
+    /// Add a signer, change its weight, or remove it with `weight == 0`.
+    /// Authorized by the account itself at the management threshold.
+    pub fn set_signer(env: Env, public_key: BytesN<32>, weight: u32) -> Result<(), AccountError> {
+        env.current_contract_address().require_auth();
+        let thresholds = Self::thresholds(env.clone())?;
+        let storage = env.storage().instance();
+        let key = DataKey::Signer(public_key.clone());
+        let old: u32 = storage.get(&key).unwrap_or(0);
+        let total = (Self::total_weight(env.clone()) - old)
+            .checked_add(weight)
+            .ok_or(AccountError::InvalidWeight)?;
+        // Never leave the account unable to reach its own thresholds.
+        Self::check_thresholds(&thresholds, total)?;
+
+        if weight == 0 {
+            storage.remove(&key);
+        } else {
+            storage.set(&key, &weight);
+        }
+        storage.set(&DataKey::TotalWeight, &total);
+        env.events().publish(
+            (symbol_short!("account"), symbol_short!("signer")),
+            (public_key, weight),
+        );
+        Ok(())
+    }
+
+    /// Replace both thresholds. Authorized at the *current* management
+    /// threshold.
+    pub fn set_thresholds(env: Env, thresholds: Thresholds) -> Result<(), AccountError> {
+        env.current_contract_address().require_auth();
+        Self::thresholds(env.clone())?;
+        Self::check_thresholds(&thresholds, Self::total_weight(env.clone()))?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Thresholds, &thresholds);
+        env.events().publish(
+            (symbol_short!("account"), symbol_short!("thresh")),
+            (thresholds.operation, thresholds.management),
+        );
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    /// Weight of `public_key`, or 0 if it is not a signer.
+    pub fn weight(env: Env, public_key: BytesN<32>) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Signer(public_key))
+            .unwrap_or(0)
+    }
+
+    pub fn total_weight(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalWeight)
+            .unwrap_or(0)
+    }
+
+    pub fn thresholds(env: Env) -> Result<Thresholds, AccountError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Thresholds)
+            .ok_or(AccountError::NotInitialized)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn check_thresholds(thresholds: &Thresholds, total: u32) -> Result<(), AccountError> {
+        let reachable = |t: u32| t > 0 && t <= total;
+        if !reachable(thresholds.operation) || !reachable(thresholds.management) {
+            return Err(AccountError::InvalidThreshold);
+        }
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for CustomAccount {
+    type Signature = Vec<Signature>;
+    type Error = AccountError;
+
+    /// Called by the host for every `require_auth` on this contract's
+    /// address. Verifies each signature over `signature_payload` and checks
+    /// the summed weight against the threshold for `auth_contexts`.
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signatures: Vec<Signature>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), AccountError> {
+        let thresholds = Self::thresholds(env.clone())?;
+        let payload = signature_payload.into();
+
+        let mut weight: u32 = 0;
+        let mut previous: Option<BytesN<32>> = None;
+        for sig in signatures.iter() {
+            if let Some(previous) = previous {
+                if sig.public_key <= previous {
+                    return Err(AccountError::UnsortedSignatures);
+                }
+            }
+            let signer_weight: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Signer(sig.public_key.clone()))
+                .ok_or(AccountError::UnknownSigner)?;
+            // Traps on an invalid signature, failing the whole invocation.
+            env.crypto()
+                .ed25519_verify(&sig.public_key, &payload, &sig.signature);
+            weight = weight.saturating_add(signer_weight);
+            previous = Some(sig.public_key);
+        }
+
+        let this = env.current_contract_address();
+        let touches_self = auth_contexts.iter().any(|context| match context {
+            Context::Contract(c) => c.contract == this,
+            _ => false,
+        });
+        let required = if touches_self {
+            thresholds.management
+        } else {
+            thresholds.operation
+        };
+        if weight < required {
+            return Err(AccountError::InsufficientWeight);
+        }
+        Ok(())
+    }
+}
+
+mod test;
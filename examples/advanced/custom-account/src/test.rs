@@ -0,0 +1,381 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::{
+    auth::ContractContext,
+    testutils::BytesN as _,
+    vec,
+    xdr::{
+        HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs, Limits, ScSymbol,
+        ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+        SorobanAuthorizedInvocation, SorobanCredentials, VecM, WriteXdr,
+    },
+    Address, Bytes, IntoVal, Symbol, TryFromVal, Val,
+};
+
+/// Stand-in for any contract that asks the account for authorization.
+#[contract]
+pub struct Vault;
+
+#[contractimpl]
+impl Vault {
+    pub fn withdraw(_env: Env, from: Address, amount: i128) -> i128 {
+        from.require_auth();
+        amount
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    account: Address,
+    client: CustomAccountClient<'a>,
+    vault: Address,
+    // Weights 1, 1 and 2. Operation threshold 2, management threshold 3.
+    keys: [SigningKey; 3],
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    let account = env.register_contract(None, CustomAccount);
+    let client = CustomAccountClient::new(&env, &account);
+    let vault = env.register_contract(None, Vault);
+    let keys = [1u8, 2, 3].map(|n| SigningKey::from_bytes(&[n; 32]));
+    let signers = vec![
+        &env,
+        signer(&env, &keys[0], 1),
+        signer(&env, &keys[1], 1),
+        signer(&env, &keys[2], 2),
+    ];
+    client.init(
+        &signers,
+        &Thresholds {
+            operation: 2,
+            management: 3,
+        },
+    );
+    Setup {
+        env,
+        account,
+        client,
+        vault,
+        keys,
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn signer(env: &Env, key: &SigningKey, weight: u32) -> Signer {
+    Signer {
+        public_key: public_key(env, key),
+        weight,
+    }
+}
+
+/// Sign `payload` with `keys`, sorted by public key as `__check_auth` expects.
+fn sign(env: &Env, payload: &[u8; 32], keys: &[&SigningKey]) -> Vec<Signature> {
+    let mut sorted = std::vec::Vec::from(keys);
+    sorted.sort_by_key(|k| k.verifying_key().to_bytes());
+    let mut signatures = Vec::new(env);
+    for key in sorted {
+        signatures.push_back(Signature {
+            public_key: public_key(env, key),
+            signature: BytesN::from_array(env, &key.sign(payload).to_bytes()),
+        });
+    }
+    signatures
+}
+
+/// Build the authorization entry the host checks when `contract.fn_name(args)`
+/// calls `s.account.require_auth()`, signed by `keys`.
+fn auth_entry(
+    s: &Setup,
+    keys: &[&SigningKey],
+    nonce: i64,
+    contract: &Address,
+    fn_name: &str,
+    args: Vec<Val>,
+) -> SorobanAuthorizationEntry {
+    let env = &s.env;
+    let expiration = env.ledger().sequence() + 100;
+    let args: std::vec::Vec<ScVal> = args
+        .iter()
+        .map(|v| ScVal::try_from_val(env, &v).unwrap())
+        .collect();
+    let invocation = SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: contract.into(),
+            function_name: ScSymbol(fn_name.try_into().unwrap()),
+            args: args.try_into().unwrap(),
+        }),
+        sub_invocations: VecM::default(),
+    };
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: env.ledger().network_id().to_array().into(),
+        nonce,
+        signature_expiration_ledger: expiration,
+        invocation: invocation.clone(),
+    });
+    let payload = env
+        .crypto()
+        .sha256(&Bytes::from_slice(
+            env,
+            &preimage.to_xdr(Limits::none()).unwrap(),
+        ))
+        .to_array();
+    let signatures: Val = sign(env, &payload, keys).into_val(env);
+    SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: (&s.account).into(),
+            nonce,
+            signature_expiration_ledger: expiration,
+            signature: ScVal::try_from_val(env, &signatures).unwrap(),
+        }),
+        root_invocation: invocation,
+    }
+}
+
+/// Call `__check_auth` directly with a context targeting `contract`.
+fn check_auth(
+    s: &Setup,
+    signatures: Vec<Signature>,
+    payload: &BytesN<32>,
+    contract: &Address,
+) -> Result<(), Result<AccountError, soroban_sdk::InvokeError>> {
+    let context = Context::Contract(ContractContext {
+        contract: contract.clone(),
+        fn_name: Symbol::new(&s.env, "withdraw"),
+        args: vec![&s.env],
+    });
+    s.env.try_invoke_contract_check_auth::<AccountError>(
+        &s.account,
+        payload,
+        signatures.into_val(&s.env),
+        &vec![&s.env, context],
+    )
+}
+
+#[test]
+fn test_init() {
+    let s = setup();
+    assert_eq!(s.client.weight(&public_key(&s.env, &s.keys[2])), 2);
+    assert_eq!(s.client.total_weight(), 4);
+    assert_eq!(
+        s.client.thresholds(),
+        Thresholds {
+            operation: 2,
+            management: 3
+        }
+    );
+}
+
+#[test]
+fn test_init_twice_fails() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_init(
+            &vec![&s.env],
+            &Thresholds {
+                operation: 1,
+                management: 1
+            }
+        ),
+        Err(Ok(AccountError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_init_validates_signers_and_thresholds() {
+    let env = Env::default();
+    let client = CustomAccountClient::new(&env, &env.register_contract(None, CustomAccount));
+    let key = SigningKey::from_bytes(&[9; 32]);
+    let ok = Thresholds {
+        operation: 1,
+        management: 1,
+    };
+
+    assert_eq!(
+        client.try_init(&vec![&env, signer(&env, &key, 0)], &ok),
+        Err(Ok(AccountError::InvalidWeight))
+    );
+    assert_eq!(
+        client.try_init(
+            &vec![&env, signer(&env, &key, 1), signer(&env, &key, 1)],
+            &ok
+        ),
+        Err(Ok(AccountError::DuplicateSigner))
+    );
+    assert_eq!(
+        client.try_init(
+            &vec![&env, signer(&env, &key, 1)],
+            &Thresholds {
+                operation: 1,
+                management: 2
+            }
+        ),
+        Err(Ok(AccountError::InvalidThreshold))
+    );
+}
+
+#[test]
+fn test_check_auth_accepts_operation_weight() {
+    let s = setup();
+    let payload = BytesN::random(&s.env);
+    let signatures = sign(&s.env, &payload.to_array(), &[&s.keys[0], &s.keys[1]]);
+    assert_eq!(check_auth(&s, signatures, &payload, &s.vault), Ok(()));
+}
+
+#[test]
+fn test_check_auth_rejects_insufficient_weight() {
+    let s = setup();
+    let payload = BytesN::random(&s.env);
+    let signatures = sign(&s.env, &payload.to_array(), &[&s.keys[0]]);
+    assert_eq!(
+        check_auth(&s, signatures, &payload, &s.vault),
+        Err(Ok(AccountError::InsufficientWeight))
+    );
+}
+
+#[test]
+fn test_check_auth_requires_management_weight_for_self_calls() {
+    let s = setup();
+    let payload = BytesN::random(&s.env);
+    let signatures = sign(&s.env, &payload.to_array(), &[&s.keys[2]]);
+    assert_eq!(
+        check_auth(&s, signatures.clone(), &payload, &s.vault),
+        Ok(())
+    );
+    assert_eq!(
+        check_auth(&s, signatures, &payload, &s.account),
+        Err(Ok(AccountError::InsufficientWeight))
+    );
+}
+
+#[test]
+fn test_check_auth_rejects_duplicate_and_unsorted_signatures() {
+    let s = setup();
+    let payload = BytesN::random(&s.env);
+    let one = sign(&s.env, &payload.to_array(), &[&s.keys[0]]);
+    let mut twice = one.clone();
+    twice.append(&one);
+    assert_eq!(
+        check_auth(&s, twice, &payload, &s.vault),
+        Err(Ok(AccountError::UnsortedSignatures))
+    );
+
+    let sorted = sign(&s.env, &payload.to_array(), &[&s.keys[0], &s.keys[1]]);
+    let reversed = vec![&s.env, sorted.get(1).unwrap(), sorted.get(0).unwrap()];
+    assert_eq!(
+        check_auth(&s, reversed, &payload, &s.vault),
+        Err(Ok(AccountError::UnsortedSignatures))
+    );
+}
+
+#[test]
+fn test_check_auth_rejects_unknown_signer() {
+    let s = setup();
+    let payload = BytesN::random(&s.env);
+    let stranger = SigningKey::from_bytes(&[42; 32]);
+    let signatures = sign(&s.env, &payload.to_array(), &[&stranger]);
+    assert_eq!(
+        check_auth(&s, signatures, &payload, &s.vault),
+        Err(Ok(AccountError::UnknownSigner))
+    );
+}
+
+#[test]
+fn test_check_auth_rejects_signature_over_other_payload() {
+    let s = setup();
+    let payload = BytesN::random(&s.env);
+    let other = BytesN::<32>::random(&s.env);
+    let signatures = sign(&s.env, &other.to_array(), &[&s.keys[2]]);
+    assert!(check_auth(&s, signatures, &payload, &s.vault).is_err());
+}
+
+#[test]
+fn test_signed_call_into_other_contract() {
+    let s = setup();
+    let vault = VaultClient::new(&s.env, &s.vault);
+    let args = (s.account.clone(), 100_i128).into_val(&s.env);
+    let entry = auth_entry(&s, &[&s.keys[2]], 1, &s.vault, "withdraw", args);
+
+    s.env.set_auths(&[entry]);
+    assert_eq!(vault.withdraw(&s.account, &100), 100);
+}
+
+#[test]
+fn test_signed_call_with_too_little_weight_fails() {
+    let s = setup();
+    let vault = VaultClient::new(&s.env, &s.vault);
+    let args = (s.account.clone(), 100_i128).into_val(&s.env);
+    let entry = auth_entry(&s, &[&s.keys[0]], 1, &s.vault, "withdraw", args);
+
+    s.env.set_auths(&[entry]);
+    assert!(vault.try_withdraw(&s.account, &100).is_err());
+}
+
+#[test]
+fn test_set_thresholds_through_check_auth() {
+    let s = setup();
+    let new = Thresholds {
+        operation: 1,
+        management: 4,
+    };
+    let args = (new,).into_val(&s.env);
+    let entry = auth_entry(
+        &s,
+        &[&s.keys[0], &s.keys[2]],
+        1,
+        &s.account,
+        "set_thresholds",
+        args,
+    );
+
+    s.env.set_auths(&[entry]);
+    s.client.set_thresholds(&new);
+    assert_eq!(s.client.thresholds(), new);
+}
+
+#[test]
+fn test_set_signer_needs_management_weight() {
+    let s = setup();
+    let newcomer = SigningKey::from_bytes(&[4; 32]);
+    let pk = public_key(&s.env, &newcomer);
+
+    // Weight 2 is enough for operations but not for managing the account.
+    let args = (pk.clone(), 1_u32).into_val(&s.env);
+    let entry = auth_entry(&s, &[&s.keys[2]], 1, &s.account, "set_signer", args);
+    s.env.set_auths(&[entry]);
+    assert!(s.client.try_set_signer(&pk, &1).is_err());
+
+    let args = (pk.clone(), 1_u32).into_val(&s.env);
+    let entry = auth_entry(
+        &s,
+        &[&s.keys[1], &s.keys[2]],
+        2,
+        &s.account,
+        "set_signer",
+        args,
+    );
+    s.env.set_auths(&[entry]);
+    s.client.set_signer(&pk, &1);
+    assert_eq!(s.client.weight(&pk), 1);
+    assert_eq!(s.client.total_weight(), 5);
+}
+
+#[test]
+fn test_set_signer_cannot_strand_thresholds() {
+    let s = setup();
+    s.env.mock_all_auths();
+    // Removing the weight-2 signer would leave total 2 < management 3.
+    assert_eq!(
+        s.client.try_set_signer(&public_key(&s.env, &s.keys[2]), &0),
+        Err(Ok(AccountError::InvalidThreshold))
+    );
+    s.client.set_signer(&public_key(&s.env, &s.keys[0]), &0);
+    assert_eq!(s.client.total_weight(), 3);
+}
@@ -0,0 +1,38 @@
+//! Child contract deployed by the `deployer` example.
+//!
+//! Soroban 21 has no constructors, so `init` plays that role: the deployer
+//! calls it in the same invocation that creates the contract, which leaves
+//! no window for anyone else to initialize it first.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Owner,
+    Value,
+}
+
+#[contract]
+pub struct ChildContract;
+
+#[contractimpl]
+impl ChildContract {
+    pub fn init(env: Env, owner: Address, value: u32) {
+        if env.storage().instance().has(&DataKey::Owner) {
+            panic!("already initialized");
+        }
+        env.storage().instance().set(&DataKey::Owner, &owner);
+        env.storage().instance().set(&DataKey::Value, &value);
+    }
+
+    pub fn owner(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Owner).unwrap()
+    }
+
+    pub fn value(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Value).unwrap()
+    }
+}
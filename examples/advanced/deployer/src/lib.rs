@@ -0,0 +1,137 @@
+//! # Deployer
+//!
+//! A contract that deploys other contracts. The new address is derived from
+//! the deployer's own address and a salt, so it is known before deployment
+//! and the same inputs can never produce two contracts:
+//!
+//! ```text
+//! address = hash(network id, deployer address, salt)
+//! ```
+//!
+//! `deploy` creates the contract from an uploaded Wasm hash, calls its
+//! initialization function with the given arguments in the same invocation,
+//! records the address, and emits `deployed`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    Symbol, Val, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deployment {
+    pub address: Address,
+    pub wasm_hash: BytesN<32>,
+    pub salt: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Number of contracts deployed so far.
+    Count,
+    /// Persistent: the n-th deployment.
+    Deployment(u32),
+    /// Persistent: index of the deployment made with a salt.
+    Salt(BytesN<32>),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DeployerError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// A contract was already deployed with this salt.
+    SaltUsed = 3,
+}
+
+#[contract]
+pub struct Deployer;
+
+#[contractimpl]
+impl Deployer {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), DeployerError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(DeployerError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Deploy `wasm_hash` under `salt` and call `init_fn(init_args)` on it.
+    /// Admin only.
+    ///
+    /// Soroban 21 has no constructors. Calling the initializer here, in the
+    /// same invocation as the deployment, means nobody can initialize the new
+    /// contract with their own arguments first.
+    ///
+    /// Emits `(deployed, admin)` with `(address, wasm_hash, salt)`.
+    pub fn deploy(
+        env: Env,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+    ) -> Result<Address, DeployerError> {
+        let admin = Self::admin(&env)?;
+        admin.require_auth();
+
+        // Deploying to an occupied address traps; fail with a clear error.
+        if env.storage().persistent().has(&DataKey::Salt(salt.clone())) {
+            return Err(DeployerError::SaltUsed);
+        }
+        let address = env
+            .deployer()
+            .with_current_contract(salt.clone())
+            .deploy(wasm_hash.clone());
+        env.invoke_contract::<Val>(&address, &init_fn, init_args);
+
+        let count = Self::deployment_count(env.clone());
+        env.storage().persistent().set(
+            &DataKey::Deployment(count),
+            &Deployment {
+                address: address.clone(),
+                wasm_hash: wasm_hash.clone(),
+                salt: salt.clone(),
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Salt(salt.clone()), &count);
+        env.storage().instance().set(&DataKey::Count, &(count + 1));
+
+        env.events().publish(
+            (symbol_short!("deployed"), admin),
+            (address.clone(), wasm_hash, salt),
+        );
+        Ok(address)
+    }
+
+    /// Address `deploy` will use for `salt`, whether or not it has run yet.
+    pub fn deployed_address(env: Env, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(salt)
+            .deployed_address()
+    }
+
+    pub fn deployment(env: Env, index: u32) -> Option<Deployment> {
+        env.storage().persistent().get(&DataKey::Deployment(index))
+    }
+
+    pub fn deployment_count(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Count).unwrap_or(0)
+    }
+
+    fn admin(env: &Env) -> Result<Address, DeployerError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(DeployerError::NotInitialized)
+    }
+}
+
+mod test;
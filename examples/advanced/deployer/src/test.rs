@@ -0,0 +1,90 @@
+#![cfg(test)]
+
+//! Native tests cover address derivation and the admin checks. Deploying
+//! from an uploaded Wasm hash runs in `tests/integration`.
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, IntoVal};
+
+struct Setup {
+    env: Env,
+    deployer: DeployerClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, Deployer);
+    let deployer = DeployerClient::new(&env, &id);
+    deployer.initialize(&Address::generate(&env));
+    Setup { env, deployer }
+}
+
+fn salt(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_address_is_deterministic() {
+    let s = setup();
+    let predicted = s.deployer.deployed_address(&salt(&s.env, 1));
+    assert_eq!(s.deployer.deployed_address(&salt(&s.env, 1)), predicted);
+
+    // Anyone can derive it off-chain from the deployer address and salt.
+    let offchain = s
+        .env
+        .deployer()
+        .with_address(s.deployer.address.clone(), salt(&s.env, 1))
+        .deployed_address();
+    assert_eq!(offchain, predicted);
+}
+
+#[test]
+fn test_different_salts_give_different_addresses() {
+    let s = setup();
+    assert_ne!(
+        s.deployer.deployed_address(&salt(&s.env, 1)),
+        s.deployer.deployed_address(&salt(&s.env, 2))
+    );
+}
+
+#[test]
+fn test_same_salt_differs_per_deployer() {
+    let s = setup();
+    let other_id = s.env.register_contract(None, Deployer);
+    let other = DeployerClient::new(&s.env, &other_id);
+    assert_ne!(
+        s.deployer.deployed_address(&salt(&s.env, 1)),
+        other.deployed_address(&salt(&s.env, 1))
+    );
+}
+
+#[test]
+fn test_initialize_only_once() {
+    let s = setup();
+    assert_eq!(
+        s.deployer.try_initialize(&Address::generate(&s.env)),
+        Err(Ok(DeployerError::AlreadyInitialized))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_deploy_requires_admin_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    let args = vec![&s.env, Address::generate(&s.env).into_val(&s.env)];
+    s.deployer.deploy(
+        &BytesN::from_array(&s.env, &[0; 32]),
+        &salt(&s.env, 1),
+        &symbol_short!("init"),
+        &args,
+    );
+}
+
+#[test]
+fn test_nothing_recorded_before_deploy() {
+    let s = setup();
+    assert_eq!(s.deployer.deployment_count(), 0);
+    assert_eq!(s.deployer.deployment(&0), None);
+}
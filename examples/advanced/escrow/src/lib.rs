@@ -0,0 +1,432 @@
+//! # Escrow
+//!
+//! A single buyer/seller escrow with an arbiter, written as an explicit
+//! state machine. Every state change goes through `transition`, which
+//! rejects edges the table below does not contain and emits one event per
+//! transition.
+//!
+//! ```text
+//!            fund (buyer)
+//! Created ─────────────────▶ Funded ──release (buyer)────────────▶ Released
+//!                              │  └───refund (buyer, after deadline)──▶ Refunded
+//!                              │
+//!                   dispute (buyer or seller)
+//!                              ▼
+//!                          Disputed ──resolve (arbiter)──▶ Released | Refunded
+//! ```
+//!
+//! `Released` and `Refunded` are terminal. A dispute freezes the deadline
+//! refund, so a seller who delivered can stop a buyer from reclaiming the
+//! funds and let the arbiter decide.
+//!
+//! ## Fees
+//!
+//! An optional `FeeSchedule`, agreed by buyer and seller before the escrow
+//! is funded and never changed afterwards, takes two fees out of the
+//! payout:
+//!
+//! - the protocol fee, whenever the escrow ends `Released`;
+//! - the arbiter fee, whenever the arbiter resolves a dispute, whichever
+//!   way it rules.
+//!
+//! Each fee is `amount * bps / 10_000`, rounded up and capped, computed by a
+//! `cookbook_fees::FeeStrategy` so the arithmetic is checked and a small
+//! escrow cannot round its fee away. A schedule whose fees cannot be
+//! computed for the escrowed amount is rejected. Fees stay
+//! in the contract, credited to their recipient, until the recipient
+//! withdraws them, so the escrow always holds exactly what it still owes.
+
+#![no_std]
+
+use cookbook_prelude::prelude::*;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, Address,
+    Env, Symbol,
+};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowState {
+    Created,
+    Funded,
+    Released,
+    Refunded,
+    Disputed,
+}
+
+impl EscrowState {
+    /// The transition table. Anything not listed is rejected.
+    pub fn can_transition_to(self, next: EscrowState) -> bool {
+        use EscrowState::*;
+        matches!(
+            (self, next),
+            (Created, Funded)
+                | (Funded, Released)
+                | (Funded, Refunded)
+                | (Funded, Disputed)
+                | (Disputed, Released)
+                | (Disputed, Refunded)
+        )
+    }
+
+    /// Event topic announcing entry into this state.
+    fn topic(self) -> Symbol {
+        match self {
+            EscrowState::Created => symbol_short!("created"),
+            EscrowState::Funded => symbol_short!("funded"),
+            EscrowState::Released => symbol_short!("released"),
+            EscrowState::Refunded => symbol_short!("refunded"),
+            EscrowState::Disputed => symbol_short!("disputed"),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowTerms {
+    pub buyer: Address,
+    pub seller: Address,
+    pub arbiter: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// After this timestamp the buyer may reclaim an undisputed escrow.
+    pub deadline: u64,
+}
+
+/// Fees charged on settlement. Either fee can be turned off with 0 bps.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    /// Receives the protocol fee.
+    pub protocol: Address,
+    /// Share of the amount taken when the seller is paid.
+    pub protocol_bps: u32,
+    /// Most the protocol fee can be, in token units.
+    pub protocol_cap: i128,
+    /// Share of the amount taken when the arbiter resolves a dispute.
+    pub arbiter_bps: u32,
+    /// Most the arbiter fee can be, in token units.
+    pub arbiter_cap: i128,
+}
+
+/// Fees taken but not yet withdrawn.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AccruedFees {
+    pub protocol: i128,
+    pub arbiter: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Terms,
+    State,
+    Fees,
+    Accrued,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EscrowError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// The deadline is not in the future.
+    InvalidDeadline = 4,
+    /// The current state does not allow this transition.
+    InvalidTransition = 5,
+    /// Refunds open only after the deadline.
+    DeadlineNotReached = 6,
+    /// Only the buyer or the seller may do this.
+    NotParty = 7,
+    /// The fees add up to more than the amount, or a cap is negative.
+    InvalidFees = 8,
+    /// No fees are owed to the caller.
+    NothingToWithdraw = 9,
+    /// A fee schedule is already set and cannot be replaced.
+    FeesAlreadySet = 10,
+}
+
+/// 100% in basis points.
+pub const MAX_BPS: u32 = 10_000;
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Record the terms. Nothing moves until the buyer calls `fund`.
+    ///
+    /// Emits `(escrow, created)`.
+    pub fn initialize(env: Env, terms: EscrowTerms) -> Result<(), EscrowError> {
+        if env.storage().instance().has(&DataKey::Terms) {
+            return Err(EscrowError::AlreadyInitialized);
+        }
+        if terms.amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if terms.deadline <= env.ledger().timestamp() {
+            return Err(EscrowError::InvalidDeadline);
+        }
+        env.storage().instance().set(&DataKey::Terms, &terms);
+        env.storage()
+            .instance()
+            .set(&DataKey::State, &EscrowState::Created);
+        env.events().publish(
+            (symbol_short!("escrow"), EscrowState::Created.topic()),
+            terms.amount,
+        );
+        Ok(())
+    }
+
+    /// Set the fees, once, before the escrow is funded, so the buyer funds
+    /// knowing what will be taken. The fees come out of what the buyer and
+    /// seller are owed, so both must authorize the schedule, including who
+    /// collects the protocol fee.
+    ///
+    /// Emits `(escrow, fees)` with `(protocol_bps, arbiter_bps)`.
+    pub fn set_fees(env: Env, fees: FeeSchedule) -> Result<(), EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        terms.buyer.require_auth();
+        terms.seller.require_auth();
+        if Self::state(env.clone())? != EscrowState::Created {
+            return Err(EscrowError::InvalidTransition);
+        }
+        if Self::fees(env.clone()).is_some() {
+            return Err(EscrowError::FeesAlreadySet);
+        }
+        let total_bps = fees
+            .protocol_bps
+            .checked_add(fees.arbiter_bps)
+            .ok_or(EscrowError::InvalidFees)?;
+        if total_bps > MAX_BPS || fees.protocol_cap < 0 || fees.arbiter_cap < 0 {
+            return Err(EscrowError::InvalidFees);
+        }
+        // Both fees must be computable on the escrowed amount and, since
+        // each rounds up and a seller-side ruling takes both, fit in it
+        // together.
+        let protocol_fee = fee(&env, terms.amount, fees.protocol_bps, fees.protocol_cap)?;
+        let arbiter_fee = fee(&env, terms.amount, fees.arbiter_bps, fees.arbiter_cap)?;
+        if protocol_fee + arbiter_fee > terms.amount {
+            return Err(EscrowError::InvalidFees);
+        }
+        env.storage().instance().set(&DataKey::Fees, &fees);
+        env.events().publish(
+            (symbol_short!("escrow"), symbol_short!("fees")),
+            (fees.protocol_bps, fees.arbiter_bps),
+        );
+        Ok(())
+    }
+
+    /// Buyer deposits the agreed amount.
+    pub fn fund(env: Env) -> Result<(), EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        terms.buyer.require_auth();
+        Self::transition(&env, EscrowState::Created, EscrowState::Funded)?;
+        TokenClient::new(&env, &terms.token).transfer(
+            &terms.buyer,
+            &env.current_contract_address(),
+            &terms.amount,
+        );
+        Ok(())
+    }
+
+    /// Buyer confirms delivery: pay the seller.
+    pub fn release(env: Env) -> Result<(), EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        terms.buyer.require_auth();
+        Self::transition(&env, EscrowState::Funded, EscrowState::Released)?;
+        Self::pay(&env, &terms, &terms.seller, false)
+    }
+
+    /// Buyer reclaims the funds once the deadline has passed without a
+    /// release or dispute.
+    pub fn refund(env: Env) -> Result<(), EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        terms.buyer.require_auth();
+        if env.ledger().timestamp() <= terms.deadline {
+            return Err(EscrowError::DeadlineNotReached);
+        }
+        Self::transition(&env, EscrowState::Funded, EscrowState::Refunded)?;
+        Self::pay(&env, &terms, &terms.buyer, false)
+    }
+
+    /// Either party hands the decision to the arbiter.
+    pub fn dispute(env: Env, caller: Address) -> Result<(), EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        if caller != terms.buyer && caller != terms.seller {
+            return Err(EscrowError::NotParty);
+        }
+        caller.require_auth();
+        Self::transition(&env, EscrowState::Funded, EscrowState::Disputed)
+    }
+
+    /// Arbiter's ruling on a dispute: pay the seller or refund the buyer.
+    pub fn resolve(env: Env, release_to_seller: bool) -> Result<(), EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        terms.arbiter.require_auth();
+        if release_to_seller {
+            Self::transition(&env, EscrowState::Disputed, EscrowState::Released)?;
+            Self::pay(&env, &terms, &terms.seller, true)
+        } else {
+            Self::transition(&env, EscrowState::Disputed, EscrowState::Refunded)?;
+            Self::pay(&env, &terms, &terms.buyer, true)
+        }
+    }
+
+    /// Protocol collects its accrued fees. Returns the amount paid.
+    ///
+    /// Emits `(fees, protocol)` with the amount.
+    pub fn withdraw_protocol_fees(env: Env) -> Result<i128, EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        let fees = Self::fees(env.clone()).ok_or(EscrowError::NothingToWithdraw)?;
+        fees.protocol.require_auth();
+        let mut accrued = Self::accrued_fees(env.clone());
+        let amount = core::mem::take(&mut accrued.protocol);
+        Self::withdraw(&env, &terms, &fees.protocol, amount, accrued)?;
+        env.events()
+            .publish((symbol_short!("fees"), symbol_short!("protocol")), amount);
+        Ok(amount)
+    }
+
+    /// Arbiter collects its accrued fees. Returns the amount paid.
+    ///
+    /// Emits `(fees, arbiter)` with the amount.
+    pub fn withdraw_arbiter_fees(env: Env) -> Result<i128, EscrowError> {
+        let terms = Self::terms(env.clone())?;
+        terms.arbiter.require_auth();
+        let mut accrued = Self::accrued_fees(env.clone());
+        let amount = core::mem::take(&mut accrued.arbiter);
+        Self::withdraw(&env, &terms, &terms.arbiter, amount, accrued)?;
+        env.events()
+            .publish((symbol_short!("fees"), symbol_short!("arbiter")), amount);
+        Ok(amount)
+    }
+
+    pub fn state(env: Env) -> Result<EscrowState, EscrowError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::State)
+            .ok_or(EscrowError::NotInitialized)
+    }
+
+    pub fn terms(env: Env) -> Result<EscrowTerms, EscrowError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Terms)
+            .ok_or(EscrowError::NotInitialized)
+    }
+
+    pub fn fees(env: Env) -> Option<FeeSchedule> {
+        env.storage().instance().get(&DataKey::Fees)
+    }
+
+    pub fn accrued_fees(env: Env) -> AccruedFees {
+        env.storage()
+            .instance()
+            .get(&DataKey::Accrued)
+            .unwrap_or_default()
+    }
+
+    // ==================== HELPERS ====================
+
+    /// Move from `from` to `next`. Each caller names the edge it takes, so a
+    /// function cannot reach a state through an edge meant for another role
+    /// (e.g. the buyer releasing a disputed escrow).
+    ///
+    /// Emits `(escrow, <next>)` with the previous state.
+    fn transition(env: &Env, from: EscrowState, next: EscrowState) -> Result<(), EscrowError> {
+        let current = Self::state(env.clone())?;
+        if current != from || !from.can_transition_to(next) {
+            return Err(EscrowError::InvalidTransition);
+        }
+        env.storage().instance().set(&DataKey::State, &next);
+        env.events()
+            .publish((symbol_short!("escrow"), next.topic()), current);
+        Ok(())
+    }
+
+    /// Pay out the escrowed amount to `to`, less the fees that apply:
+    /// the protocol fee if `to` is the seller, the arbiter fee if the
+    /// arbiter `resolved` a dispute. The fees are credited, not paid.
+    fn pay(
+        env: &Env,
+        terms: &EscrowTerms,
+        to: &Address,
+        resolved: bool,
+    ) -> Result<(), EscrowError> {
+        let mut payout = terms.amount;
+        if let Some(fees) = Self::fees(env.clone()) {
+            let mut accrued = Self::accrued_fees(env.clone());
+            if *to == terms.seller {
+                let protocol_fee = fee(env, terms.amount, fees.protocol_bps, fees.protocol_cap)?;
+                accrued.protocol += protocol_fee;
+                payout = payout
+                    .checked_sub(protocol_fee)
+                    .ok_or(EscrowError::InvalidFees)?;
+            }
+            if resolved {
+                let arbiter_fee = fee(env, terms.amount, fees.arbiter_bps, fees.arbiter_cap)?;
+                accrued.arbiter += arbiter_fee;
+                payout = payout
+                    .checked_sub(arbiter_fee)
+                    .ok_or(EscrowError::InvalidFees)?;
+            }
+            if payout < 0 {
+                return Err(EscrowError::InvalidFees);
+            }
+            env.storage().instance().set(&DataKey::Accrued, &accrued);
+        }
+        TokenClient::new(env, &terms.token).transfer(&env.current_contract_address(), to, &payout);
+        Ok(())
+    }
+
+    /// Store `remaining` and pay `amount` to `to`.
+    fn withdraw(
+        env: &Env,
+        terms: &EscrowTerms,
+        to: &Address,
+        amount: i128,
+        remaining: AccruedFees,
+    ) -> Result<(), EscrowError> {
+        if amount == 0 {
+            return Err(EscrowError::NothingToWithdraw);
+        }
+        env.storage().instance().set(&DataKey::Accrued, &remaining);
+        TokenClient::new(env, &terms.token).transfer(&env.current_contract_address(), to, &amount);
+        Ok(())
+    }
+}
+
+/// A share of the amount in basis points, rounded up, never more than `cap`.
+struct CappedBpsFee {
+    bps: u32,
+    cap: i128,
+}
+
+impl FeeStrategy for CappedBpsFee {
+    fn fee(&self, env: &Env, amount: i128) -> Result<i128, FeeError> {
+        Ok(BpsFee { bps: self.bps }.fee(env, amount)?.min(self.cap))
+    }
+
+    fn validate(&self) -> Result<(), FeeError> {
+        BpsFee { bps: self.bps }.validate()?;
+        if self.cap < 0 {
+            return Err(FeeError::InvalidConfig);
+        }
+        Ok(())
+    }
+}
+
+/// `bps` of `amount`, rounded up and capped at `cap`. Any `FeeError` is
+/// reported as `InvalidFees`.
+fn fee(env: &Env, amount: i128, bps: u32, cap: i128) -> Result<i128, EscrowError> {
+    let strategy = CappedBpsFee { bps, cap };
+    strategy.validate().map_err(|_| EscrowError::InvalidFees)?;
+    let (fee, _net) = charge_with(env, &strategy, amount).map_err(|_| EscrowError::InvalidFees)?;
+    Ok(fee)
+}
+
+mod test;
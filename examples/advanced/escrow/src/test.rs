@@ -0,0 +1,645 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token::StellarAssetClient,
+    Address, Env, IntoVal,
+};
+
+const AMOUNT: i128 = 500;
+const DEADLINE: u64 = 1_000;
+
+struct Setup {
+    env: Env,
+    escrow: EscrowContractClient<'static>,
+    token: TokenClient<'static>,
+    buyer: Address,
+    seller: Address,
+}
+
+/// An initialized escrow in `Created`; the buyer holds `AMOUNT`.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let escrow = EscrowContractClient::new(&env, &env.register_contract(None, EscrowContract));
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&buyer, &AMOUNT);
+
+    escrow.initialize(&EscrowTerms {
+        buyer: buyer.clone(),
+        seller: seller.clone(),
+        arbiter: Address::generate(&env),
+        token: token_id.clone(),
+        amount: AMOUNT,
+        deadline: DEADLINE,
+    });
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        escrow,
+        buyer,
+        seller,
+    }
+}
+
+fn funded() -> Setup {
+    let s = setup();
+    s.escrow.fund();
+    s
+}
+
+fn disputed() -> Setup {
+    let s = funded();
+    s.escrow.dispute(&s.seller);
+    s
+}
+
+impl Setup {
+    fn pass_deadline(&self) {
+        self.env.ledger().with_mut(|l| l.timestamp = DEADLINE + 1);
+    }
+
+    fn assert_last_transition(&self, from: EscrowState, to: EscrowState) {
+        assert_eq!(self.escrow.state(), to);
+        // The escrow's own event; token transfers are published by the token.
+        let (_id, topics, data) = self
+            .env
+            .events()
+            .all()
+            .iter()
+            .rev()
+            .find(|(id, _, _)| *id == self.escrow.address)
+            .unwrap();
+        assert_eq!(
+            topics,
+            (symbol_short!("escrow"), to.topic()).into_val(&self.env)
+        );
+        let previous: EscrowState = data.into_val(&self.env);
+        assert_eq!(previous, from);
+    }
+}
+
+// ── transition table ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_transition_table() {
+    use EscrowState::*;
+    let all = [Created, Funded, Released, Refunded, Disputed];
+    let allowed = [
+        (Created, Funded),
+        (Funded, Released),
+        (Funded, Refunded),
+        (Funded, Disputed),
+        (Disputed, Released),
+        (Disputed, Refunded),
+    ];
+    for from in all {
+        for to in all {
+            assert_eq!(
+                from.can_transition_to(to),
+                allowed.contains(&(from, to)),
+                "{from:?} -> {to:?}"
+            );
+        }
+    }
+}
+
+// ── initialization ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_initialize_creates_escrow() {
+    let s = setup();
+    assert_eq!(s.escrow.state(), EscrowState::Created);
+    assert_eq!(s.escrow.terms().amount, AMOUNT);
+    assert_eq!(
+        s.escrow.try_initialize(&s.escrow.terms()),
+        Err(Ok(EscrowError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_initialize_validates_terms() {
+    let s = setup();
+    let fresh = EscrowContractClient::new(&s.env, &s.env.register_contract(None, EscrowContract));
+    let mut terms = s.escrow.terms();
+    terms.amount = 0;
+    assert_eq!(
+        fresh.try_initialize(&terms),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+
+    let mut terms = s.escrow.terms();
+    terms.deadline = s.env.ledger().timestamp();
+    assert_eq!(
+        fresh.try_initialize(&terms),
+        Err(Ok(EscrowError::InvalidDeadline))
+    );
+    assert_eq!(fresh.try_state(), Err(Ok(EscrowError::NotInitialized)));
+}
+
+// ── valid transitions ────────────────────────────────────────────────────────
+
+#[test]
+fn test_created_to_funded() {
+    let s = setup();
+    s.escrow.fund();
+    s.assert_last_transition(EscrowState::Created, EscrowState::Funded);
+    assert_eq!(s.token.balance(&s.escrow.address), AMOUNT);
+    assert_eq!(s.token.balance(&s.buyer), 0);
+}
+
+#[test]
+fn test_funded_to_released() {
+    let s = funded();
+    s.escrow.release();
+    s.assert_last_transition(EscrowState::Funded, EscrowState::Released);
+    assert_eq!(s.token.balance(&s.seller), AMOUNT);
+}
+
+#[test]
+fn test_funded_to_refunded_after_deadline() {
+    let s = funded();
+    s.pass_deadline();
+    s.escrow.refund();
+    s.assert_last_transition(EscrowState::Funded, EscrowState::Refunded);
+    assert_eq!(s.token.balance(&s.buyer), AMOUNT);
+}
+
+#[test]
+fn test_funded_to_disputed() {
+    let s = funded();
+    s.escrow.dispute(&s.buyer);
+    s.assert_last_transition(EscrowState::Funded, EscrowState::Disputed);
+}
+
+#[test]
+fn test_disputed_to_released() {
+    let s = disputed();
+    s.escrow.resolve(&true);
+    s.assert_last_transition(EscrowState::Disputed, EscrowState::Released);
+    assert_eq!(s.token.balance(&s.seller), AMOUNT);
+}
+
+#[test]
+fn test_disputed_to_refunded() {
+    let s = disputed();
+    s.escrow.resolve(&false);
+    s.assert_last_transition(EscrowState::Disputed, EscrowState::Refunded);
+    assert_eq!(s.token.balance(&s.buyer), AMOUNT);
+}
+
+// ── invalid transitions ──────────────────────────────────────────────────────
+
+#[test]
+fn test_invalid_from_created() {
+    let s = setup();
+    let invalid = Err(Ok(EscrowError::InvalidTransition));
+    assert_eq!(s.escrow.try_release(), invalid);
+    assert_eq!(s.escrow.try_dispute(&s.buyer), invalid);
+    assert_eq!(s.escrow.try_resolve(&true), invalid);
+    s.pass_deadline();
+    assert_eq!(s.escrow.try_refund(), invalid);
+}
+
+#[test]
+fn test_invalid_from_funded() {
+    let s = funded();
+    assert_eq!(s.escrow.try_fund(), Err(Ok(EscrowError::InvalidTransition)));
+    assert_eq!(
+        s.escrow.try_resolve(&true),
+        Err(Ok(EscrowError::InvalidTransition))
+    );
+}
+
+#[test]
+fn test_refund_before_deadline_rejected() {
+    let s = funded();
+    s.env.ledger().with_mut(|l| l.timestamp = DEADLINE);
+    assert_eq!(
+        s.escrow.try_refund(),
+        Err(Ok(EscrowError::DeadlineNotReached))
+    );
+}
+
+#[test]
+fn test_invalid_from_disputed() {
+    let s = disputed();
+    let invalid = Err(Ok(EscrowError::InvalidTransition));
+    assert_eq!(s.escrow.try_fund(), invalid);
+    assert_eq!(s.escrow.try_release(), invalid);
+    assert_eq!(s.escrow.try_dispute(&s.buyer), invalid);
+    // A dispute blocks the deadline refund.
+    s.pass_deadline();
+    assert_eq!(s.escrow.try_refund(), invalid);
+}
+
+#[test]
+fn test_terminal_states_are_final() {
+    for terminal in [EscrowState::Released, EscrowState::Refunded] {
+        let s = disputed();
+        s.escrow.resolve(&(terminal == EscrowState::Released));
+        s.pass_deadline();
+
+        let invalid = Err(Ok(EscrowError::InvalidTransition));
+        assert_eq!(s.escrow.try_fund(), invalid);
+        assert_eq!(s.escrow.try_release(), invalid);
+        assert_eq!(s.escrow.try_refund(), invalid);
+        assert_eq!(s.escrow.try_dispute(&s.seller), invalid);
+        assert_eq!(s.escrow.try_resolve(&true), invalid);
+        assert_eq!(s.escrow.state(), terminal);
+    }
+}
+
+// ── roles ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_outsider_cannot_dispute() {
+    let s = funded();
+    assert_eq!(
+        s.escrow.try_dispute(&Address::generate(&s.env)),
+        Err(Ok(EscrowError::NotParty))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_release_requires_buyer_auth() {
+    let s = funded();
+    s.env.set_auths(&[]);
+    s.escrow.release();
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_resolve_requires_arbiter_auth() {
+    let s = disputed();
+    s.env.set_auths(&[]);
+    s.escrow.resolve(&true);
+}
+
+// ── conservation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_every_outcome_conserves_funds() {
+    let outcomes: [fn(&Setup); 4] = [
+        |s| s.escrow.release(),
+        |s| {
+            s.pass_deadline();
+            s.escrow.refund();
+        },
+        |s| {
+            s.escrow.dispute(&s.buyer);
+            s.escrow.resolve(&true);
+        },
+        |s| {
+            s.escrow.dispute(&s.seller);
+            s.escrow.resolve(&false);
+        },
+    ];
+    for settle in outcomes {
+        let s = setup();
+        let arbiter = s.escrow.terms().arbiter;
+        assert_conserved!(
+            s.env,
+            s.token.address,
+            [s.buyer, s.seller, arbiter, s.escrow.address],
+            {
+                s.escrow.fund();
+                settle(&s);
+            }
+        );
+        // Whoever was paid, nothing is left behind in the escrow.
+        assert_eq!(s.token.balance(&s.escrow.address), 0);
+    }
+}
+
+// ── fees ─────────────────────────────────────────────────────────────────────
+
+impl Setup {
+    /// 2% protocol fee capped at 30, 5% arbiter fee capped at 20, paid to a
+    /// fresh protocol address.
+    fn schedule(&self) -> FeeSchedule {
+        FeeSchedule {
+            protocol: Address::generate(&self.env),
+            protocol_bps: 200,
+            protocol_cap: 30,
+            arbiter_bps: 500,
+            arbiter_cap: 20,
+        }
+    }
+
+    fn with_fees(self) -> (Setup, Address) {
+        let fees = self.schedule();
+        self.escrow.set_fees(&fees);
+        (self, fees.protocol)
+    }
+}
+
+#[test]
+fn test_set_fees_validates_schedule() {
+    let s = setup();
+    let mut fees = s.schedule();
+    fees.arbiter_bps = MAX_BPS - fees.protocol_bps + 1;
+    assert_eq!(
+        s.escrow.try_set_fees(&fees),
+        Err(Ok(EscrowError::InvalidFees))
+    );
+
+    // A sum that wraps around u32 is still rejected
+    let mut fees = s.schedule();
+    fees.protocol_bps = u32::MAX;
+    fees.arbiter_bps = 1;
+    assert_eq!(
+        s.escrow.try_set_fees(&fees),
+        Err(Ok(EscrowError::InvalidFees))
+    );
+
+    let mut fees = s.schedule();
+    fees.protocol_cap = -1;
+    assert_eq!(
+        s.escrow.try_set_fees(&fees),
+        Err(Ok(EscrowError::InvalidFees))
+    );
+    assert_eq!(s.escrow.fees(), None);
+
+    // Fixed once the buyer has funded
+    s.escrow.fund();
+    assert_eq!(
+        s.escrow.try_set_fees(&s.schedule()),
+        Err(Ok(EscrowError::InvalidTransition))
+    );
+}
+
+#[test]
+fn test_set_fees_only_once() {
+    let (s, protocol) = setup().with_fees();
+    let mut fees = s.schedule();
+    fees.protocol_bps = 0;
+    assert_eq!(
+        s.escrow.try_set_fees(&fees),
+        Err(Ok(EscrowError::FeesAlreadySet))
+    );
+    assert_eq!(s.escrow.fees().unwrap().protocol, protocol);
+}
+
+#[test]
+fn test_fees_on_large_amount_do_not_overflow() {
+    let s = setup();
+    let fresh = EscrowContractClient::new(&s.env, &s.env.register_contract(None, EscrowContract));
+    let mut terms = s.escrow.terms();
+    terms.amount = i128::MAX;
+    fresh.initialize(&terms);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.buyer, &(i128::MAX - AMOUNT));
+    // i128::MAX * 200 does not fit in an i128, but the fee does
+    fresh.set_fees(&s.schedule());
+    fresh.fund();
+    fresh.release();
+    assert_eq!(fresh.accrued_fees().protocol, 30);
+    assert_eq!(s.token.balance(&s.seller), i128::MAX - 30);
+}
+
+#[test]
+fn test_small_escrow_pays_fee() {
+    let s = setup();
+    let fresh = EscrowContractClient::new(&s.env, &s.env.register_contract(None, EscrowContract));
+    let mut terms = s.escrow.terms();
+    terms.amount = 10;
+    fresh.initialize(&terms);
+    fresh.set_fees(&s.schedule());
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.buyer, &10);
+    fresh.fund();
+    fresh.release();
+    // 2% of 10 is 0.2, rounded up rather than lost
+    assert_eq!(fresh.accrued_fees().protocol, 1);
+    assert_eq!(s.token.balance(&s.seller), 9);
+}
+
+#[test]
+fn test_rounded_fees_must_fit_small_escrow() {
+    let s = setup();
+    let escrow_of = |amount| {
+        let fresh =
+            EscrowContractClient::new(&s.env, &s.env.register_contract(None, EscrowContract));
+        let mut terms = s.escrow.terms();
+        terms.amount = amount;
+        fresh.initialize(&terms);
+        fresh
+    };
+    let mut fees = s.schedule();
+    fees.protocol_bps = 5_000;
+    fees.protocol_cap = i128::MAX;
+    fees.arbiter_bps = 5_000;
+    fees.arbiter_cap = i128::MAX;
+
+    // 50% of 3 rounds up to 2, twice: a seller-side ruling would owe 4.
+    let small = escrow_of(3);
+    assert_eq!(small.try_set_fees(&fees), Err(Ok(EscrowError::InvalidFees)));
+
+    // 2 + 2 fits in 4, so the arbiter can still rule for the seller.
+    let fits = escrow_of(4);
+    fits.set_fees(&fees);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.buyer, &4);
+    fits.fund();
+    fits.dispute(&s.seller);
+    fits.resolve(&true);
+    assert_eq!(
+        fits.accrued_fees(),
+        AccruedFees {
+            protocol: 2,
+            arbiter: 2
+        }
+    );
+    assert_eq!(s.token.balance(&s.seller), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_stranger_cannot_set_fees() {
+    let s = setup();
+    // The stranger names itself as protocol and authorizes the call, but
+    // neither party does.
+    let mut fees = s.schedule();
+    let stranger = fees.protocol.clone();
+    fees.protocol_bps = MAX_BPS;
+    fees.protocol_cap = AMOUNT;
+    fees.arbiter_bps = 0;
+    s.env.mock_auths(&[MockAuth {
+        address: &stranger,
+        invoke: &MockAuthInvoke {
+            contract: &s.escrow.address,
+            fn_name: "set_fees",
+            args: (fees.clone(),).into_val(&s.env),
+            sub_invokes: &[],
+        },
+    }]);
+    s.escrow.set_fees(&fees);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_fees_requires_seller_auth() {
+    let s = setup();
+    let fees = s.schedule();
+    s.env.mock_auths(&[MockAuth {
+        address: &s.buyer,
+        invoke: &MockAuthInvoke {
+            contract: &s.escrow.address,
+            fn_name: "set_fees",
+            args: (fees.clone(),).into_val(&s.env),
+            sub_invokes: &[],
+        },
+    }]);
+    s.escrow.set_fees(&fees);
+}
+
+#[test]
+fn test_release_takes_protocol_fee() {
+    let (s, _protocol) = setup().with_fees();
+    s.escrow.fund();
+    s.escrow.release();
+
+    // 2% of 500; no dispute, so no arbiter fee
+    assert_eq!(s.token.balance(&s.seller), AMOUNT - 10);
+    assert_eq!(
+        s.escrow.accrued_fees(),
+        AccruedFees {
+            protocol: 10,
+            arbiter: 0
+        }
+    );
+    assert_eq!(s.token.balance(&s.escrow.address), 10);
+}
+
+#[test]
+fn test_resolve_takes_arbiter_fee_either_way() {
+    let (s, _protocol) = setup().with_fees();
+    s.escrow.fund();
+    s.escrow.dispute(&s.seller);
+    s.escrow.resolve(&true);
+    // 10 protocol, 5% arbiter fee of 25 capped at 20
+    assert_eq!(s.token.balance(&s.seller), AMOUNT - 30);
+    assert_eq!(
+        s.escrow.accrued_fees(),
+        AccruedFees {
+            protocol: 10,
+            arbiter: 20
+        }
+    );
+
+    // A refund to the buyer pays no protocol fee
+    let (s, _protocol) = setup().with_fees();
+    s.escrow.fund();
+    s.escrow.dispute(&s.buyer);
+    s.escrow.resolve(&false);
+    assert_eq!(s.token.balance(&s.buyer), AMOUNT - 20);
+    assert_eq!(
+        s.escrow.accrued_fees(),
+        AccruedFees {
+            protocol: 0,
+            arbiter: 20
+        }
+    );
+}
+
+#[test]
+fn test_deadline_refund_is_free() {
+    let (s, _protocol) = setup().with_fees();
+    s.escrow.fund();
+    s.pass_deadline();
+    s.escrow.refund();
+    assert_eq!(s.token.balance(&s.buyer), AMOUNT);
+    assert_eq!(s.escrow.accrued_fees(), AccruedFees::default());
+}
+
+#[test]
+fn test_fee_withdrawal() {
+    let (s, protocol) = setup().with_fees();
+    let arbiter = s.escrow.terms().arbiter;
+    s.escrow.fund();
+    s.escrow.dispute(&s.seller);
+    s.escrow.resolve(&true);
+
+    assert_eq!(s.escrow.withdraw_protocol_fees(), 10);
+    assert_eq!(s.token.balance(&protocol), 10);
+    assert_eq!(
+        s.escrow.try_withdraw_protocol_fees(),
+        Err(Ok(EscrowError::NothingToWithdraw))
+    );
+
+    assert_eq!(s.escrow.withdraw_arbiter_fees(), 20);
+    assert_eq!(s.token.balance(&arbiter), 20);
+    assert_eq!(s.escrow.accrued_fees(), AccruedFees::default());
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
+
+#[test]
+fn test_withdraw_without_schedule() {
+    let s = funded();
+    s.escrow.release();
+    assert_eq!(
+        s.escrow.try_withdraw_protocol_fees(),
+        Err(Ok(EscrowError::NothingToWithdraw))
+    );
+    assert_eq!(
+        s.escrow.try_withdraw_arbiter_fees(),
+        Err(Ok(EscrowError::NothingToWithdraw))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_withdraw_arbiter_fees_requires_arbiter_auth() {
+    let (s, _protocol) = setup().with_fees();
+    s.escrow.fund();
+    s.escrow.dispute(&s.seller);
+    s.escrow.resolve(&true);
+    s.env.set_auths(&[]);
+    s.escrow.withdraw_arbiter_fees();
+}
+
+#[test]
+fn test_fee_split_conserves_funds() {
+    let settlements: [fn(&Setup); 3] = [
+        |s| s.escrow.release(),
+        |s| {
+            s.escrow.dispute(&s.buyer);
+            s.escrow.resolve(&true);
+        },
+        |s| {
+            s.escrow.dispute(&s.seller);
+            s.escrow.resolve(&false);
+        },
+    ];
+    for settle in settlements {
+        let (s, protocol) = setup().with_fees();
+        let arbiter = s.escrow.terms().arbiter;
+        assert_conserved!(
+            s.env,
+            s.token.address,
+            [s.buyer, s.seller, arbiter, protocol, s.escrow.address],
+            {
+                s.escrow.fund();
+                settle(&s);
+                // What the escrow still holds is exactly the fees it owes.
+                let accrued = s.escrow.accrued_fees();
+                assert_eq!(
+                    s.token.balance(&s.escrow.address),
+                    accrued.protocol + accrued.arbiter
+                );
+                if accrued.protocol > 0 {
+                    s.escrow.withdraw_protocol_fees();
+                }
+                if accrued.arbiter > 0 {
+                    s.escrow.withdraw_arbiter_fees();
+                }
+            }
+        );
+        assert_eq!(s.token.balance(&s.escrow.address), 0);
+    }
+}
@@ -0,0 +1,337 @@
+//! # DAO Governance
+//!
+//! Token holders lock tokens to get voting power, create proposals, vote
+//! yes or no, and execute proposals that pass. A proposal carries one
+//! contract call, stored as `(target, function, args)`, which the DAO
+//! makes as itself on execution:
+//!
+//! ```text
+//! ledger  L              L+1 … L+voting_period        after
+//!         propose ──────▶ vote(yes/no) ─────────────▶ execute
+//!         snapshot: power going into L                 quorum, yes > no
+//! ```
+//!
+//! ## Weight snapshots
+//!
+//! A vote weighs the voter's locked balance going into the ledger the
+//! proposal was created in. Tokens locked in that ledger or later do not
+//! count. Without that, one holder could vote, move the
+//! tokens to a second address, lock them again and vote a second time, or
+//! borrow tokens for a single ledger to swing a vote. Balances are kept as
+//! checkpoints per holder; see `votes`.
+//!
+//! Withdrawing after a snapshot does not reduce the weight for proposals
+//! that already took it. The tokens have been counted once, at snapshot
+//! time, and cannot be counted again elsewhere.
+//!
+//! ## Outcome
+//!
+//! Voting runs for `voting_period` ledgers after the proposal's ledger,
+//! measured with `env.ledger().sequence()`. Once it ends, a proposal passes
+//! if the votes cast (yes and no together) reach `quorum` and yes
+//! outweighs no. Anyone can then `execute` it, once.
+//!
+//! Execution marks the proposal executed *before* making the call. If the
+//! target fails, the whole invocation reverts and the proposal can be
+//! executed again later.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Val, Vec,
+};
+
+mod votes;
+
+use votes::{current_power, power_before, write_power};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// The token holders lock for voting power.
+    pub token: Address,
+    /// Ledgers a proposal stays open for voting.
+    pub voting_period: u32,
+    /// Smallest total of yes and no votes for a valid outcome.
+    pub quorum: i128,
+    /// Locked balance needed to create a proposal.
+    pub proposal_threshold: i128,
+}
+
+/// A call the DAO makes as itself.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Call {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub call: Call,
+    /// Ledger the proposal was created in. Voting power is read as held
+    /// going into it.
+    pub start_ledger: u32,
+    /// Last ledger in which votes are accepted.
+    pub end_ledger: u32,
+    pub yes: i128,
+    pub no: i128,
+    pub executed: bool,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProposalState {
+    Active,
+    /// Voting ended with fewer than `quorum` votes cast.
+    QuorumNotReached,
+    /// Voting ended with no at least as heavy as yes.
+    Defeated,
+    /// Voting ended and the proposal passed; ready to execute.
+    Succeeded,
+    Executed,
+}
+
+/// A holder's locked balance from `ledger` on.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub power: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    NextProposal,
+    /// Persistent: a proposal by id.
+    Proposal(u32),
+    /// Persistent: set once a holder has voted on a proposal.
+    Voted(u32, Address),
+    /// Persistent: a holder's `Vec<Checkpoint>`.
+    Checkpoints(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum GovernanceError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    /// The proposer holds less than `proposal_threshold`.
+    BelowProposalThreshold = 5,
+    ProposalNotFound = 6,
+    /// Voting has not started or has already ended.
+    VotingClosed = 7,
+    AlreadyVoted = 8,
+    /// The voter held nothing at the snapshot.
+    NoVotingPower = 9,
+    /// `execute` on a proposal that is not `Succeeded`.
+    NotSucceeded = 10,
+    /// A `voting_period` of zero or a negative quorum or threshold.
+    InvalidConfig = 11,
+}
+
+#[contract]
+pub struct Governance;
+
+#[contractimpl]
+impl Governance {
+    pub fn initialize(env: Env, config: Config) -> Result<(), GovernanceError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(GovernanceError::AlreadyInitialized);
+        }
+        if config.voting_period == 0 || config.quorum < 0 || config.proposal_threshold < 0 {
+            return Err(GovernanceError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    // ==================== VOTING POWER ====================
+
+    /// Lock `amount` tokens for voting power.
+    pub fn deposit(env: Env, holder: Address, amount: i128) -> Result<(), GovernanceError> {
+        holder.require_auth();
+        if amount <= 0 {
+            return Err(GovernanceError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        token::Client::new(&env, &config.token).transfer(
+            &holder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        write_power(&env, &holder, current_power(&env, &holder) + amount);
+        Ok(())
+    }
+
+    /// Unlock `amount` tokens. Votes already cast keep their weight.
+    pub fn withdraw(env: Env, holder: Address, amount: i128) -> Result<(), GovernanceError> {
+        holder.require_auth();
+        if amount <= 0 {
+            return Err(GovernanceError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let power = current_power(&env, &holder);
+        if amount > power {
+            return Err(GovernanceError::InsufficientBalance);
+        }
+        write_power(&env, &holder, power - amount);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &holder,
+            &amount,
+        );
+        Ok(())
+    }
+
+    pub fn voting_power(env: Env, holder: Address) -> i128 {
+        current_power(&env, &holder)
+    }
+
+    /// `holder`'s voting power going into `ledger`, the weight a vote on
+    /// a proposal created in that ledger gets.
+    pub fn voting_power_before(env: Env, holder: Address, ledger: u32) -> i128 {
+        power_before(&env, &holder, ledger)
+    }
+
+    // ==================== PROPOSALS ====================
+
+    /// Propose `call` and return the proposal id. Voting opens in the next
+    /// ledger.
+    ///
+    /// Emits `(proposed, proposer)` with the id.
+    pub fn propose(env: Env, proposer: Address, call: Call) -> Result<u32, GovernanceError> {
+        proposer.require_auth();
+        let config = Self::config(env.clone())?;
+        let now = env.ledger().sequence();
+        // The same snapshot rule as votes: power held before this ledger.
+        if power_before(&env, &proposer, now) < config.proposal_threshold {
+            return Err(GovernanceError::BelowProposalThreshold);
+        }
+
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposal)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposal, &(id + 1));
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            call,
+            start_ledger: now,
+            end_ledger: now + config.voting_period,
+            yes: 0,
+            no: 0,
+            executed: false,
+        };
+        save(&env, id, &proposal);
+
+        env.events()
+            .publish((symbol_short!("proposed"), proposer), id);
+        Ok(id)
+    }
+
+    /// Vote on proposal `id` with the voter's power at its snapshot.
+    ///
+    /// Emits `(voted, voter)` with `(id, support, weight)`.
+    pub fn vote(env: Env, voter: Address, id: u32, support: bool) -> Result<i128, GovernanceError> {
+        voter.require_auth();
+        let mut proposal = Self::proposal(env.clone(), id)?;
+        let now = env.ledger().sequence();
+        if now <= proposal.start_ledger || now > proposal.end_ledger {
+            return Err(GovernanceError::VotingClosed);
+        }
+        let voted = DataKey::Voted(id, voter.clone());
+        if env.storage().persistent().has(&voted) {
+            return Err(GovernanceError::AlreadyVoted);
+        }
+        let weight = power_before(&env, &voter, proposal.start_ledger);
+        if weight == 0 {
+            return Err(GovernanceError::NoVotingPower);
+        }
+
+        if support {
+            proposal.yes += weight;
+        } else {
+            proposal.no += weight;
+        }
+        save(&env, id, &proposal);
+        env.storage().persistent().set(&voted, &true);
+
+        env.events()
+            .publish((symbol_short!("voted"), voter), (id, support, weight));
+        Ok(weight)
+    }
+
+    /// Make the call of a `Succeeded` proposal. Anyone may execute.
+    ///
+    /// Emits `(executed,)` with the id.
+    pub fn execute(env: Env, id: u32) -> Result<(), GovernanceError> {
+        if Self::state(env.clone(), id)? != ProposalState::Succeeded {
+            return Err(GovernanceError::NotSucceeded);
+        }
+        let mut proposal = Self::proposal(env.clone(), id)?;
+        proposal.executed = true;
+        save(&env, id, &proposal);
+
+        let call = proposal.call;
+        env.invoke_contract::<Val>(&call.target, &call.function, call.args);
+        env.events().publish((symbol_short!("executed"),), id);
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn state(env: Env, id: u32) -> Result<ProposalState, GovernanceError> {
+        let proposal = Self::proposal(env.clone(), id)?;
+        let config = Self::config(env.clone())?;
+        Ok(if proposal.executed {
+            ProposalState::Executed
+        } else if env.ledger().sequence() <= proposal.end_ledger {
+            ProposalState::Active
+        } else if proposal.yes + proposal.no < config.quorum {
+            ProposalState::QuorumNotReached
+        } else if proposal.yes <= proposal.no {
+            ProposalState::Defeated
+        } else {
+            ProposalState::Succeeded
+        })
+    }
+
+    pub fn proposal(env: Env, id: u32) -> Result<Proposal, GovernanceError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(id))
+            .ok_or(GovernanceError::ProposalNotFound)
+    }
+
+    pub fn has_voted(env: Env, id: u32, voter: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Voted(id, voter))
+    }
+
+    pub fn config(env: Env) -> Result<Config, GovernanceError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(GovernanceError::NotInitialized)
+    }
+}
+
+fn save(env: &Env, id: u32, proposal: &Proposal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proposal(id), proposal);
+}
+
+mod test;
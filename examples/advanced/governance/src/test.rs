@@ -0,0 +1,381 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, IntoVal,
+};
+
+const VOTING_PERIOD: u32 = 10;
+const QUORUM: i128 = 400;
+const THRESHOLD: i128 = 100;
+
+/// A contract that only its admin may change. The DAO is its admin.
+#[contract]
+pub struct Counter;
+
+#[contractimpl]
+impl Counter {
+    pub fn init(env: Env, admin: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+    }
+
+    pub fn bump(env: Env, by: u32) -> u32 {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .unwrap();
+        admin.require_auth();
+        let count = Self::count(env.clone()) + by;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("count"), &count);
+        count
+    }
+
+    pub fn count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("count"))
+            .unwrap_or(0)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    dao: GovernanceClient<'a>,
+    token: token::Client<'a>,
+    token_admin: StellarAssetClient<'a>,
+    counter: CounterClient<'a>,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+/// Alice locks 600, Bob 300 and Carol 100, all at ledger 100. The test then
+/// moves to ledger 101, so those balances count for new proposals.
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.sequence_number = 100);
+
+    let issuer = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract_v2(issuer).address();
+    let token = token::Client::new(&env, &token_id);
+    let token_admin = StellarAssetClient::new(&env, &token_id);
+
+    let dao = GovernanceClient::new(&env, &env.register_contract(None, Governance));
+    dao.initialize(&Config {
+        token: token_id,
+        voting_period: VOTING_PERIOD,
+        quorum: QUORUM,
+        proposal_threshold: THRESHOLD,
+    });
+    let counter = CounterClient::new(&env, &env.register_contract(None, Counter));
+    counter.init(&dao.address);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    for (holder, amount) in [(&alice, 600), (&bob, 300), (&carol, 100)] {
+        token_admin.mint(holder, &amount);
+        dao.deposit(holder, &amount);
+    }
+    let s = Setup {
+        env,
+        dao,
+        token,
+        token_admin,
+        counter,
+        alice,
+        bob,
+        carol,
+    };
+    s.advance(1);
+    s
+}
+
+impl Setup<'_> {
+    fn advance(&self, ledgers: u32) {
+        self.env.ledger().with_mut(|l| l.sequence_number += ledgers);
+    }
+
+    fn bump(&self, by: u32) -> Call {
+        Call {
+            target: self.counter.address.clone(),
+            function: symbol_short!("bump"),
+            args: vec![&self.env, by.into_val(&self.env)],
+        }
+    }
+
+    /// Move past the last voting ledger of `id`.
+    fn end_voting(&self, id: u32) {
+        let end = self.dao.proposal(&id).end_ledger;
+        self.env.ledger().with_mut(|l| l.sequence_number = end + 1);
+    }
+}
+
+// ==================== EXECUTION ====================
+
+#[test]
+fn test_proposal_passes_and_executes() {
+    let s = setup();
+    let id = s.dao.propose(&s.alice, &s.bump(7));
+    assert_eq!(s.dao.state(&id), ProposalState::Active);
+
+    s.advance(1);
+    assert_eq!(s.dao.vote(&s.alice, &id, &true), 600);
+    assert_eq!(s.dao.vote(&s.bob, &id, &false), 300);
+    // Still open, so not executable yet.
+    assert_eq!(
+        s.dao.try_execute(&id),
+        Err(Ok(GovernanceError::NotSucceeded))
+    );
+
+    s.end_voting(id);
+    assert_eq!(s.dao.state(&id), ProposalState::Succeeded);
+    s.dao.execute(&id);
+    assert_eq!(s.counter.count(), 7);
+    assert_eq!(s.dao.state(&id), ProposalState::Executed);
+
+    // A proposal runs once.
+    assert_eq!(
+        s.dao.try_execute(&id),
+        Err(Ok(GovernanceError::NotSucceeded))
+    );
+    assert_eq!(s.counter.count(), 7);
+}
+
+/// The counter only accepts calls authorized by its admin, the DAO, so
+/// nobody can make the call around the vote.
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_target_only_accepts_the_dao() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.counter.bump(&1);
+}
+
+#[test]
+fn test_defeated_proposal_not_executed() {
+    let s = setup();
+    let id = s.dao.propose(&s.bob, &s.bump(1));
+    s.advance(1);
+    s.dao.vote(&s.alice, &id, &false);
+    s.dao.vote(&s.bob, &id, &true);
+    s.end_voting(id);
+    assert_eq!(s.dao.state(&id), ProposalState::Defeated);
+    assert_eq!(
+        s.dao.try_execute(&id),
+        Err(Ok(GovernanceError::NotSucceeded))
+    );
+    assert_eq!(s.counter.count(), 0);
+}
+
+// ==================== QUORUM ====================
+
+/// Bob's 300 votes pass unopposed, but 400 must be cast in total.
+#[test]
+fn test_quorum_failure() {
+    let s = setup();
+    let id = s.dao.propose(&s.bob, &s.bump(1));
+    s.advance(1);
+    s.dao.vote(&s.bob, &id, &true);
+    s.end_voting(id);
+
+    let proposal = s.dao.proposal(&id);
+    assert_eq!((proposal.yes, proposal.no), (300, 0));
+    assert_eq!(s.dao.state(&id), ProposalState::QuorumNotReached);
+    assert_eq!(
+        s.dao.try_execute(&id),
+        Err(Ok(GovernanceError::NotSucceeded))
+    );
+    assert_eq!(s.counter.count(), 0);
+}
+
+/// No votes count towards quorum too: exactly `QUORUM` cast passes.
+#[test]
+fn test_quorum_counts_no_votes() {
+    let s = setup();
+    let id = s.dao.propose(&s.bob, &s.bump(1));
+    s.advance(1);
+    s.dao.vote(&s.bob, &id, &true);
+    s.dao.vote(&s.carol, &id, &false);
+    s.end_voting(id);
+    assert_eq!(s.dao.state(&id), ProposalState::Succeeded);
+    s.dao.execute(&id);
+    assert_eq!(s.counter.count(), 1);
+}
+
+// ==================== VOTING ====================
+
+#[test]
+fn test_double_vote_rejected() {
+    let s = setup();
+    let id = s.dao.propose(&s.alice, &s.bump(1));
+    s.advance(1);
+    s.dao.vote(&s.bob, &id, &true);
+    assert!(s.dao.has_voted(&id, &s.bob));
+    assert_eq!(
+        s.dao.try_vote(&s.bob, &id, &true),
+        Err(Ok(GovernanceError::AlreadyVoted))
+    );
+    // Changing sides is a second vote as well.
+    assert_eq!(
+        s.dao.try_vote(&s.bob, &id, &false),
+        Err(Ok(GovernanceError::AlreadyVoted))
+    );
+    assert_eq!(s.dao.proposal(&id).yes, 300);
+}
+
+#[test]
+fn test_voting_period() {
+    let s = setup();
+    let id = s.dao.propose(&s.alice, &s.bump(1));
+    let proposal = s.dao.proposal(&id);
+    assert_eq!(proposal.end_ledger, proposal.start_ledger + VOTING_PERIOD);
+
+    // Not in the proposal's own ledger...
+    assert_eq!(
+        s.dao.try_vote(&s.alice, &id, &true),
+        Err(Ok(GovernanceError::VotingClosed))
+    );
+    // ...open through the end ledger...
+    s.env
+        .ledger()
+        .with_mut(|l| l.sequence_number = proposal.end_ledger);
+    s.dao.vote(&s.alice, &id, &true);
+    // ...and closed after it.
+    s.advance(1);
+    assert_eq!(
+        s.dao.try_vote(&s.bob, &id, &true),
+        Err(Ok(GovernanceError::VotingClosed))
+    );
+    assert_eq!(
+        s.dao.try_vote(&s.bob, &(id + 1), &true),
+        Err(Ok(GovernanceError::ProposalNotFound))
+    );
+}
+
+/// Tokens moved to a fresh address after the proposal do not vote twice.
+#[test]
+fn test_weight_snapshot() {
+    let s = setup();
+    let id = s.dao.propose(&s.alice, &s.bump(1));
+    s.advance(1);
+    s.dao.vote(&s.carol, &id, &true);
+
+    let mallory = Address::generate(&s.env);
+    s.dao.withdraw(&s.carol, &100);
+    s.token.transfer(&s.carol, &mallory, &100);
+    s.dao.deposit(&mallory, &100);
+    assert_eq!(
+        s.dao.try_vote(&mallory, &id, &true),
+        Err(Ok(GovernanceError::NoVotingPower))
+    );
+    // Carol's vote keeps its weight after she withdrew.
+    assert_eq!(s.dao.proposal(&id).yes, 100);
+
+    // Mallory's tokens do count for proposals created afterwards.
+    s.advance(1);
+    let next = s.dao.propose(&s.alice, &s.bump(1));
+    s.advance(1);
+    assert_eq!(s.dao.vote(&mallory, &next, &true), 100);
+}
+
+/// A deposit in the proposal's own ledger is not part of the snapshot,
+/// so power cannot be borrowed just for one proposal.
+#[test]
+fn test_same_ledger_deposit_not_counted() {
+    let s = setup();
+    let whale = Address::generate(&s.env);
+    s.token_admin.mint(&whale, &10_000);
+    s.dao.deposit(&whale, &10_000);
+    let id = s.dao.propose(&s.alice, &s.bump(1));
+    s.advance(1);
+    assert_eq!(
+        s.dao.try_vote(&whale, &id, &false),
+        Err(Ok(GovernanceError::NoVotingPower))
+    );
+
+    let ledger = s.dao.proposal(&id).start_ledger;
+    assert_eq!(s.dao.voting_power_before(&whale, &ledger), 0);
+    assert_eq!(s.dao.voting_power_before(&whale, &(ledger + 1)), 10_000);
+    assert_eq!(s.dao.voting_power(&whale), 10_000);
+}
+
+// ==================== PROPOSING ====================
+
+#[test]
+fn test_proposal_threshold() {
+    let s = setup();
+    let small = Address::generate(&s.env);
+    s.token_admin.mint(&small, &99);
+    s.dao.deposit(&small, &99);
+    s.advance(1);
+    assert_eq!(
+        s.dao.try_propose(&small, &s.bump(1)),
+        Err(Ok(GovernanceError::BelowProposalThreshold))
+    );
+    // Carol holds exactly the threshold.
+    s.dao.propose(&s.carol, &s.bump(1));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_vote_requires_voter_auth() {
+    let s = setup();
+    let id = s.dao.propose(&s.alice, &s.bump(1));
+    s.advance(1);
+    s.env.set_auths(&[]);
+    s.dao.vote(&s.bob, &id, &true);
+}
+
+// ==================== DEPOSITS ====================
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let s = setup();
+    assert_eq!(s.token.balance(&s.dao.address), 1_000);
+    assert_eq!(
+        s.dao.try_withdraw(&s.bob, &301),
+        Err(Ok(GovernanceError::InsufficientBalance))
+    );
+    assert_eq!(
+        s.dao.try_deposit(&s.bob, &0),
+        Err(Ok(GovernanceError::InvalidAmount))
+    );
+    s.dao.withdraw(&s.bob, &300);
+    assert_eq!(s.dao.voting_power(&s.bob), 0);
+    assert_eq!(s.token.balance(&s.bob), 300);
+}
+
+#[test]
+fn test_initialize_validation() {
+    let env = Env::default();
+    let dao = GovernanceClient::new(&env, &env.register_contract(None, Governance));
+    let config = Config {
+        token: Address::generate(&env),
+        voting_period: 0,
+        quorum: QUORUM,
+        proposal_threshold: THRESHOLD,
+    };
+    assert_eq!(
+        dao.try_initialize(&config),
+        Err(Ok(GovernanceError::InvalidConfig))
+    );
+    let config = Config {
+        voting_period: VOTING_PERIOD,
+        ..config
+    };
+    dao.initialize(&config);
+    assert_eq!(
+        dao.try_initialize(&config),
+        Err(Ok(GovernanceError::AlreadyInitialized))
+    );
+}
@@ -0,0 +1,59 @@
+//! Voting power with history, so a proposal can weigh votes as of the
+//! ledger it was created in.
+//!
+//! Each holder's locked balance is stored as a list of `Checkpoint`s in
+//! ledger order. Changes within one ledger overwrite that ledger's entry,
+//! so the list grows by at most one entry per ledger in which the holder
+//! deposits or withdraws. Lookups binary-search it.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{Checkpoint, DataKey};
+
+pub(crate) fn read_checkpoints(env: &Env, holder: &Address) -> Vec<Checkpoint> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Checkpoints(holder.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// `holder`'s current locked balance.
+pub(crate) fn current_power(env: &Env, holder: &Address) -> i128 {
+    read_checkpoints(env, holder)
+        .last()
+        .map_or(0, |checkpoint| checkpoint.power)
+}
+
+/// `holder`'s locked balance going into ledger `ledger`, ignoring any
+/// change made during it.
+pub(crate) fn power_before(env: &Env, holder: &Address, ledger: u32) -> i128 {
+    let checkpoints = read_checkpoints(env, holder);
+    // Index of the first checkpoint at or after `ledger`.
+    let (mut low, mut high) = (0, checkpoints.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get_unchecked(mid).ledger < ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        checkpoints.get_unchecked(low - 1).power
+    }
+}
+
+/// Record `holder`'s new balance as of the current ledger.
+pub(crate) fn write_power(env: &Env, holder: &Address, power: i128) {
+    let ledger = env.ledger().sequence();
+    let mut checkpoints = read_checkpoints(env, holder);
+    if checkpoints.last().is_some_and(|last| last.ledger == ledger) {
+        checkpoints.pop_back();
+    }
+    checkpoints.push_back(Checkpoint { ledger, power });
+    env.storage()
+        .persistent()
+        .set(&DataKey::Checkpoints(holder.clone()), &checkpoints);
+}
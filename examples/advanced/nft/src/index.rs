@@ -0,0 +1,56 @@
+//! Per-owner token lists for enumeration.
+//!
+//! Each owner has a `Vec<u32>` of their token ids, and each token remembers
+//! its position in that list. Removing a token moves the list's last id
+//! into the gap, so adding and removing both cost two or three writes
+//! whatever the owner's balance. Order within a list is therefore not
+//! stable across removals.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::DataKey;
+
+pub(crate) fn read_tokens(env: &Env, owner: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OwnedTokens(owner.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn write_tokens(env: &Env, owner: &Address, tokens: &Vec<u32>) {
+    let key = DataKey::OwnedTokens(owner.clone());
+    if tokens.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, tokens);
+    }
+}
+
+pub(crate) fn add_token(env: &Env, owner: &Address, token_id: u32) {
+    let mut tokens = read_tokens(env, owner);
+    env.storage()
+        .persistent()
+        .set(&DataKey::OwnedIndex(token_id), &tokens.len());
+    tokens.push_back(token_id);
+    write_tokens(env, owner, &tokens);
+}
+
+pub(crate) fn remove_token(env: &Env, owner: &Address, token_id: u32) {
+    let mut tokens = read_tokens(env, owner);
+    let index: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::OwnedIndex(token_id))
+        .unwrap();
+    let last = tokens.pop_back_unchecked();
+    if last != token_id {
+        tokens.set(index, last);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OwnedIndex(last), &index);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OwnedIndex(token_id));
+    write_tokens(env, owner, &tokens);
+}
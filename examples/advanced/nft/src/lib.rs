@@ -0,0 +1,332 @@
+//! # NFT
+//!
+//! A non-fungible token collection: every token has a sequential `u32` id,
+//! exactly one owner and a metadata URI.
+//!
+//! ```text
+//! mint(alice, "ipfs://…/0")   → 0
+//! approve(alice, bob, 0)          bob may move token 0, once
+//! transfer_from(bob, alice, carol, 0)
+//! tokens_of(carol)            → [0]
+//! burn(carol, 0)                  owner, URI, approval and index entry gone
+//! ```
+//!
+//! ## Approvals
+//!
+//! Besides the owner, two kinds of spender can transfer or burn a token:
+//!
+//! - the token's **approved** address, set with `approve`. There is at most
+//!   one per token, and it is cleared whenever the token moves;
+//! - an owner's **operators**, set with `set_approval_for_all`. An operator
+//!   may move every token the owner holds, now or later, and may also
+//!   `approve` for them. Operators stay until revoked.
+//!
+//! ## Enumeration
+//!
+//! `tokens_of(owner)` lists an owner's tokens without scanning every id. The
+//! contract keeps a `Vec<u32>` per owner and each token's position in it;
+//! see `index`. `balance_of` is that list's length.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
+};
+
+mod index;
+
+use index::{add_token, read_tokens, remove_token};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Name,
+    Symbol,
+    NextId,
+    /// Tokens minted and not burned.
+    Supply,
+    Owner(u32),
+    Uri(u32),
+    /// The single address approved for a token.
+    Approved(u32),
+    /// Set while the second address is an operator for the first.
+    Operator(Address, Address),
+    /// An owner's `Vec<u32>` of token ids.
+    OwnedTokens(Address),
+    /// A token's position in its owner's `OwnedTokens`.
+    OwnedIndex(u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NftError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotFound = 3,
+    /// `from` does not own the token.
+    NotOwner = 4,
+    /// The spender is neither the owner, the approved address nor an
+    /// operator.
+    NotAuthorized = 5,
+    /// `token_of_owner_by_index` past the owner's balance.
+    IndexOutOfBounds = 6,
+    /// Approving the owner, or making an owner their own operator.
+    SelfApproval = 7,
+}
+
+#[contract]
+pub struct Nft;
+
+#[contractimpl]
+impl Nft {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+    ) -> Result<(), NftError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NftError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        Ok(())
+    }
+
+    // ==================== MINT AND BURN ====================
+
+    /// Mint the next id to `to` with metadata at `uri`. Admin only.
+    ///
+    /// Emits `(mint, to)` with the id.
+    pub fn mint(env: Env, to: Address, uri: String) -> Result<u32, NftError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(NftError::NotInitialized)?;
+        admin.require_auth();
+
+        let id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        env.storage()
+            .instance()
+            .set(&DataKey::Supply, &(Self::total_supply(env.clone()) + 1));
+        env.storage().persistent().set(&DataKey::Owner(id), &to);
+        env.storage().persistent().set(&DataKey::Uri(id), &uri);
+        add_token(&env, &to, id);
+
+        env.events().publish((symbol_short!("mint"), to), id);
+        Ok(id)
+    }
+
+    /// Destroy a token. The owner, its approved address or an operator may
+    /// burn it. Its id is never reused.
+    ///
+    /// Emits `(burn, owner)` with the id.
+    pub fn burn(env: Env, spender: Address, token_id: u32) -> Result<(), NftError> {
+        spender.require_auth();
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        check_spender(&env, &spender, &owner, token_id)?;
+
+        env.storage().persistent().remove(&DataKey::Owner(token_id));
+        env.storage().persistent().remove(&DataKey::Uri(token_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approved(token_id));
+        remove_token(&env, &owner, token_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::Supply, &(Self::total_supply(env.clone()) - 1));
+
+        env.events()
+            .publish((symbol_short!("burn"), owner), token_id);
+        Ok(())
+    }
+
+    // ==================== TRANSFERS ====================
+
+    /// Move `from`'s own token to `to`.
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) -> Result<(), NftError> {
+        from.require_auth();
+        if Self::owner_of(env.clone(), token_id)? != from {
+            return Err(NftError::NotOwner);
+        }
+        move_token(&env, &from, &to, token_id);
+        Ok(())
+    }
+
+    /// Move `from`'s token to `to` on `spender`'s authority: the owner, the
+    /// token's approved address or one of `from`'s operators.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<(), NftError> {
+        spender.require_auth();
+        if Self::owner_of(env.clone(), token_id)? != from {
+            return Err(NftError::NotOwner);
+        }
+        check_spender(&env, &spender, &from, token_id)?;
+        move_token(&env, &from, &to, token_id);
+        Ok(())
+    }
+
+    // ==================== APPROVALS ====================
+
+    /// Let `approved` move one token, or clear the approval with `None`.
+    /// The owner or one of its operators may approve.
+    ///
+    /// Emits `(approve, owner)` with `(id, approved)`.
+    pub fn approve(
+        env: Env,
+        caller: Address,
+        approved: Option<Address>,
+        token_id: u32,
+    ) -> Result<(), NftError> {
+        caller.require_auth();
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if caller != owner && !Self::is_approved_for_all(env.clone(), owner.clone(), caller) {
+            return Err(NftError::NotAuthorized);
+        }
+        let key = DataKey::Approved(token_id);
+        match &approved {
+            Some(address) if *address == owner => return Err(NftError::SelfApproval),
+            Some(address) => env.storage().persistent().set(&key, address),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events()
+            .publish((symbol_short!("approve"), owner), (token_id, approved));
+        Ok(())
+    }
+
+    /// Make `operator` able to move all of `owner`'s tokens, or revoke it.
+    ///
+    /// Emits `(appr_all, owner)` with `(operator, approved)`.
+    pub fn set_approval_for_all(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), NftError> {
+        owner.require_auth();
+        if operator == owner {
+            return Err(NftError::SelfApproval);
+        }
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events()
+            .publish((symbol_short!("appr_all"), owner), (operator, approved));
+        Ok(())
+    }
+
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Approved(token_id))
+    }
+
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Operator(owner, operator))
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owner(token_id))
+            .ok_or(NftError::NotFound)
+    }
+
+    pub fn token_uri(env: Env, token_id: u32) -> Result<String, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Uri(token_id))
+            .ok_or(NftError::NotFound)
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        read_tokens(&env, &owner).len()
+    }
+
+    /// All of `owner`'s token ids. Order changes when tokens leave.
+    pub fn tokens_of(env: Env, owner: Address) -> Vec<u32> {
+        read_tokens(&env, &owner)
+    }
+
+    /// The `index`th entry of `tokens_of(owner)`, for paging through large
+    /// holdings one token at a time.
+    pub fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> Result<u32, NftError> {
+        read_tokens(&env, &owner)
+            .get(index)
+            .ok_or(NftError::IndexOutOfBounds)
+    }
+
+    /// Tokens minted and not burned.
+    pub fn total_supply(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Supply).unwrap_or(0)
+    }
+
+    pub fn name(env: Env) -> Result<String, NftError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Name)
+            .ok_or(NftError::NotInitialized)
+    }
+
+    pub fn symbol(env: Env) -> Result<String, NftError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Symbol)
+            .ok_or(NftError::NotInitialized)
+    }
+}
+
+/// `spender` may move `owner`'s token if it is the owner, the token's
+/// approved address or an operator of the owner.
+fn check_spender(
+    env: &Env,
+    spender: &Address,
+    owner: &Address,
+    token_id: u32,
+) -> Result<(), NftError> {
+    if spender == owner
+        || Nft::get_approved(env.clone(), token_id).as_ref() == Some(spender)
+        || Nft::is_approved_for_all(env.clone(), owner.clone(), spender.clone())
+    {
+        Ok(())
+    } else {
+        Err(NftError::NotAuthorized)
+    }
+}
+
+/// Hand the token to `to`, clearing its approval.
+///
+/// Emits `(transfer, from, to)` with the id.
+fn move_token(env: &Env, from: &Address, to: &Address, token_id: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approved(token_id));
+    env.storage()
+        .persistent()
+        .set(&DataKey::Owner(token_id), to);
+    remove_token(env, from, token_id);
+    add_token(env, to, token_id);
+    env.events().publish(
+        (symbol_short!("transfer"), from.clone(), to.clone()),
+        token_id,
+    );
+}
+
+mod test;
@@ -0,0 +1,275 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, IntoVal,
+};
+
+struct Setup<'a> {
+    env: Env,
+    nft: NftClient<'a>,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let nft = NftClient::new(&env, &env.register_contract(None, Nft));
+    nft.initialize(
+        &Address::generate(&env),
+        &String::from_str(&env, "Cookbook Collectibles"),
+        &String::from_str(&env, "COOK"),
+    );
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        carol: Address::generate(&env),
+        env,
+        nft,
+    }
+}
+
+impl Setup<'_> {
+    fn uri(&self, id: u32) -> String {
+        let uris = [
+            "ipfs://cook/0",
+            "ipfs://cook/1",
+            "ipfs://cook/2",
+            "ipfs://cook/3",
+        ];
+        String::from_str(&self.env, uris[id as usize])
+    }
+
+    fn mint(&self, to: &Address, id: u32) -> u32 {
+        self.nft.mint(to, &self.uri(id))
+    }
+}
+
+// ==================== MINT AND BURN ====================
+
+#[test]
+fn test_mint_sequential_ids() {
+    let s = setup();
+    assert_eq!(s.mint(&s.alice, 0), 0);
+    assert_eq!(s.mint(&s.bob, 1), 1);
+    assert_eq!(s.mint(&s.alice, 2), 2);
+
+    assert_eq!(s.nft.owner_of(&1), s.bob);
+    assert_eq!(s.nft.token_uri(&2), s.uri(2));
+    assert_eq!(s.nft.balance_of(&s.alice), 2);
+    assert_eq!(s.nft.total_supply(), 3);
+    assert_eq!(s.nft.try_owner_of(&3), Err(Ok(NftError::NotFound)));
+    assert_eq!(
+        s.nft.name(),
+        String::from_str(&s.env, "Cookbook Collectibles")
+    );
+    assert_eq!(s.nft.symbol(), String::from_str(&s.env, "COOK"));
+
+    let events = s.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &s.env,
+            (
+                s.nft.address.clone(),
+                (symbol_short!("mint"), s.alice.clone()).into_val(&s.env),
+                2u32.into_val(&s.env),
+            ),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_mint_requires_admin_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.mint(&s.alice, 0);
+}
+
+#[test]
+fn test_burn_clears_token() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    s.mint(&s.alice, 1);
+    s.nft.approve(&s.alice, &Some(s.bob.clone()), &0);
+
+    s.nft.burn(&s.alice, &0);
+    assert_eq!(s.nft.try_owner_of(&0), Err(Ok(NftError::NotFound)));
+    assert_eq!(s.nft.try_token_uri(&0), Err(Ok(NftError::NotFound)));
+    assert_eq!(s.nft.get_approved(&0), None);
+    assert_eq!(s.nft.tokens_of(&s.alice), vec![&s.env, 1]);
+    assert_eq!(s.nft.total_supply(), 1);
+
+    // Ids are never reused.
+    assert_eq!(s.mint(&s.alice, 2), 2);
+    assert_eq!(s.nft.try_burn(&s.alice, &0), Err(Ok(NftError::NotFound)));
+}
+
+#[test]
+fn test_burn_by_spenders() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    s.mint(&s.alice, 1);
+    assert_eq!(s.nft.try_burn(&s.bob, &0), Err(Ok(NftError::NotAuthorized)));
+    s.nft.approve(&s.alice, &Some(s.bob.clone()), &0);
+    s.nft.burn(&s.bob, &0);
+
+    s.nft.set_approval_for_all(&s.alice, &s.carol, &true);
+    s.nft.burn(&s.carol, &1);
+    assert_eq!(s.nft.balance_of(&s.alice), 0);
+}
+
+// ==================== TRANSFERS ====================
+
+#[test]
+fn test_transfer() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    assert_eq!(
+        s.nft.try_transfer(&s.bob, &s.carol, &0),
+        Err(Ok(NftError::NotOwner))
+    );
+    s.nft.transfer(&s.alice, &s.bob, &0);
+    assert_eq!(s.nft.owner_of(&0), s.bob);
+    assert_eq!(s.nft.balance_of(&s.alice), 0);
+    assert_eq!(s.nft.tokens_of(&s.bob), vec![&s.env, 0]);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_transfer_requires_owner_auth() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    s.env.set_auths(&[]);
+    s.nft.transfer(&s.alice, &s.bob, &0);
+}
+
+/// A single approval moves the token once: it is cleared on transfer.
+#[test]
+fn test_approved_transfer_from() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    assert_eq!(
+        s.nft.try_transfer_from(&s.bob, &s.alice, &s.bob, &0),
+        Err(Ok(NftError::NotAuthorized))
+    );
+    s.nft.approve(&s.alice, &Some(s.bob.clone()), &0);
+    assert_eq!(s.nft.get_approved(&0), Some(s.bob.clone()));
+
+    s.nft.transfer_from(&s.bob, &s.alice, &s.carol, &0);
+    assert_eq!(s.nft.owner_of(&0), s.carol);
+    assert_eq!(s.nft.get_approved(&0), None);
+    assert_eq!(
+        s.nft.try_transfer_from(&s.bob, &s.carol, &s.bob, &0),
+        Err(Ok(NftError::NotAuthorized))
+    );
+    // `from` must be the current owner.
+    assert_eq!(
+        s.nft.try_transfer_from(&s.carol, &s.alice, &s.bob, &0),
+        Err(Ok(NftError::NotOwner))
+    );
+}
+
+#[test]
+fn test_approval_cleared_and_revoked() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    assert_eq!(
+        s.nft.try_approve(&s.bob, &Some(s.bob.clone()), &0),
+        Err(Ok(NftError::NotAuthorized))
+    );
+    assert_eq!(
+        s.nft.try_approve(&s.alice, &Some(s.alice.clone()), &0),
+        Err(Ok(NftError::SelfApproval))
+    );
+    s.nft.approve(&s.alice, &Some(s.bob.clone()), &0);
+    s.nft.approve(&s.alice, &None, &0);
+    assert_eq!(s.nft.get_approved(&0), None);
+    assert_eq!(
+        s.nft.try_transfer_from(&s.bob, &s.alice, &s.bob, &0),
+        Err(Ok(NftError::NotAuthorized))
+    );
+}
+
+/// An operator moves any of the owner's tokens, including ones minted
+/// after it was set, and can approve others for them.
+#[test]
+fn test_operator() {
+    let s = setup();
+    s.mint(&s.alice, 0);
+    s.nft.set_approval_for_all(&s.alice, &s.bob, &true);
+    assert!(s.nft.is_approved_for_all(&s.alice, &s.bob));
+    s.mint(&s.alice, 1);
+
+    s.nft.transfer_from(&s.bob, &s.alice, &s.bob, &1);
+    s.nft.approve(&s.bob, &Some(s.carol.clone()), &0);
+    s.nft.transfer_from(&s.carol, &s.alice, &s.carol, &0);
+    assert_eq!(s.nft.owner_of(&0), s.carol);
+
+    // Operators act for one owner only, and can be revoked.
+    assert_eq!(
+        s.nft.try_transfer_from(&s.bob, &s.carol, &s.bob, &0),
+        Err(Ok(NftError::NotAuthorized))
+    );
+    s.mint(&s.alice, 2);
+    s.nft.set_approval_for_all(&s.alice, &s.bob, &false);
+    assert_eq!(
+        s.nft.try_transfer_from(&s.bob, &s.alice, &s.bob, &2),
+        Err(Ok(NftError::NotAuthorized))
+    );
+    assert_eq!(
+        s.nft.try_set_approval_for_all(&s.alice, &s.alice, &true),
+        Err(Ok(NftError::SelfApproval))
+    );
+}
+
+// ==================== ENUMERATION ====================
+
+/// Removing a token moves the owner's last token into its slot.
+#[test]
+fn test_enumeration_after_moves() {
+    let s = setup();
+    for id in 0..4 {
+        s.mint(&s.alice, id);
+    }
+    assert_eq!(s.nft.tokens_of(&s.alice), vec![&s.env, 0, 1, 2, 3]);
+
+    s.nft.transfer(&s.alice, &s.bob, &1);
+    assert_eq!(s.nft.tokens_of(&s.alice), vec![&s.env, 0, 3, 2]);
+    s.nft.burn(&s.alice, &0);
+    assert_eq!(s.nft.tokens_of(&s.alice), vec![&s.env, 2, 3]);
+    s.nft.transfer(&s.bob, &s.alice, &1);
+    assert_eq!(s.nft.tokens_of(&s.alice), vec![&s.env, 2, 3, 1]);
+
+    // Every listed token is really owned, and the index pages through them.
+    for (index, id) in s.nft.tokens_of(&s.alice).iter().enumerate() {
+        assert_eq!(s.nft.owner_of(&id), s.alice);
+        assert_eq!(s.nft.token_of_owner_by_index(&s.alice, &(index as u32)), id);
+    }
+    assert_eq!(
+        s.nft.try_token_of_owner_by_index(&s.alice, &3),
+        Err(Ok(NftError::IndexOutOfBounds))
+    );
+    assert_eq!(s.nft.tokens_of(&s.bob), vec![&s.env]);
+    assert_eq!(s.nft.balance_of(&s.alice), 3);
+}
+
+#[test]
+fn test_initialize_once() {
+    let s = setup();
+    let name = String::from_str(&s.env, "Again");
+    assert_eq!(
+        s.nft.try_initialize(&s.alice, &name, &name),
+        Err(Ok(NftError::AlreadyInitialized))
+    );
+    let env = Env::default();
+    let nft = NftClient::new(&env, &env.register_contract(None, Nft));
+    assert_eq!(
+        nft.try_mint(&Address::generate(&env), &String::from_str(&env, "x")),
+        Err(Ok(NftError::NotInitialized))
+    );
+}
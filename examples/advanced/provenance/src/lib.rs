@@ -0,0 +1,252 @@
+//! # Supply-Chain Provenance
+//!
+//! Tracks a physical item through its custodians with an append-only,
+//! hash-linked history:
+//!
+//! ```text
+//! Record 0            Record 1            Record 2
+//! ┌──────────────┐    ┌──────────────┐    ┌──────────────┐
+//! │ Registered   │    │ Transfer     │    │ Checkpoint   │
+//! │ custodian A  │◀───│ prev = H(0)  │◀───│ prev = H(1)  │◀── item.head = H(2)
+//! │ prev = id    │    │ custodian B  │    │ custodian B  │
+//! └──────────────┘    └──────────────┘    └──────────────┘
+//! ```
+//!
+//! - **Custody moves with both parties' consent.** `transfer_custody` needs
+//!   the current custodian *and* the receiver to authorize, so nobody can
+//!   push an item onto an unwilling party or take it without handover.
+//! - **Records are never rewritten.** Each is stored once under
+//!   `Record(item, index)`; the item only keeps a count and the hash of the
+//!   latest record.
+//! - **History is verifiable off-chain.** An indexer can serve the records,
+//!   and anyone can check them against the on-chain head with
+//!   `verify_history`, without trusting the indexer.
+//!
+//! Locations are stored as hashes. The chain proves *that* a custodian
+//! committed to a location at a time; the plaintext stays off-chain with the
+//! parties that need it.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN,
+    Env, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordKind {
+    Registered,
+    Transfer,
+    Checkpoint,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    pub kind: RecordKind,
+    /// Custodian after this record.
+    pub custodian: Address,
+    pub location_hash: BytesN<32>,
+    pub timestamp: u64,
+    /// Hash of the previous record, or the item id for the first one.
+    pub prev: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Item {
+    pub custodian: Address,
+    pub record_count: u32,
+    /// Hash of the latest record.
+    pub head: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Item(BytesN<32>),
+    Record(BytesN<32>, u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProvenanceError {
+    AlreadyRegistered = 1,
+    NotFound = 2,
+    NotCustodian = 3,
+    SelfTransfer = 4,
+}
+
+#[contract]
+pub struct ProvenanceContract;
+
+#[contractimpl]
+impl ProvenanceContract {
+    /// Register a new item with `custodian` holding it at `location_hash`.
+    pub fn register_item(
+        env: Env,
+        item_id: BytesN<32>,
+        custodian: Address,
+        location_hash: BytesN<32>,
+    ) -> Result<(), ProvenanceError> {
+        custodian.require_auth();
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Item(item_id.clone()))
+        {
+            return Err(ProvenanceError::AlreadyRegistered);
+        }
+        let record = Record {
+            kind: RecordKind::Registered,
+            custodian: custodian.clone(),
+            location_hash,
+            timestamp: env.ledger().timestamp(),
+            prev: item_id.clone(),
+        };
+        let item = Item {
+            custodian: custodian.clone(),
+            record_count: 0,
+            head: item_id.clone(),
+        };
+        Self::append(&env, &item_id, item, record);
+        env.events().publish(
+            (symbol_short!("prov"), symbol_short!("register")),
+            (item_id, custodian),
+        );
+        Ok(())
+    }
+
+    /// Hand the item from `from` to `to`. Both must authorize.
+    pub fn transfer_custody(
+        env: Env,
+        item_id: BytesN<32>,
+        from: Address,
+        to: Address,
+        location_hash: BytesN<32>,
+    ) -> Result<(), ProvenanceError> {
+        // Checked first: the same address cannot authorize one frame twice.
+        if from == to {
+            return Err(ProvenanceError::SelfTransfer);
+        }
+        from.require_auth();
+        to.require_auth();
+        let item = Self::item(env.clone(), item_id.clone())?;
+        if item.custodian != from {
+            return Err(ProvenanceError::NotCustodian);
+        }
+        let record = Record {
+            kind: RecordKind::Transfer,
+            custodian: to.clone(),
+            location_hash,
+            timestamp: env.ledger().timestamp(),
+            prev: item.head.clone(),
+        };
+        Self::append(&env, &item_id, item, record);
+        env.events().publish(
+            (symbol_short!("prov"), symbol_short!("transfer")),
+            (item_id, from, to),
+        );
+        Ok(())
+    }
+
+    /// Record where the current custodian has the item now.
+    pub fn checkpoint(
+        env: Env,
+        item_id: BytesN<32>,
+        custodian: Address,
+        location_hash: BytesN<32>,
+    ) -> Result<(), ProvenanceError> {
+        custodian.require_auth();
+        let item = Self::item(env.clone(), item_id.clone())?;
+        if item.custodian != custodian {
+            return Err(ProvenanceError::NotCustodian);
+        }
+        let record = Record {
+            kind: RecordKind::Checkpoint,
+            custodian,
+            location_hash: location_hash.clone(),
+            timestamp: env.ledger().timestamp(),
+            prev: item.head.clone(),
+        };
+        Self::append(&env, &item_id, item, record);
+        env.events().publish(
+            (symbol_short!("prov"), symbol_short!("checkpt")),
+            (item_id, location_hash),
+        );
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn item(env: Env, item_id: BytesN<32>) -> Result<Item, ProvenanceError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Item(item_id))
+            .ok_or(ProvenanceError::NotFound)
+    }
+
+    pub fn record(env: Env, item_id: BytesN<32>, index: u32) -> Result<Record, ProvenanceError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Record(item_id, index))
+            .ok_or(ProvenanceError::NotFound)
+    }
+
+    /// Walk the stored records and check every link and the head.
+    pub fn verify_chain(env: Env, item_id: BytesN<32>) -> Result<bool, ProvenanceError> {
+        let item = Self::item(env.clone(), item_id.clone())?;
+        let mut history = Vec::new(&env);
+        for index in 0..item.record_count {
+            history.push_back(Self::record(env.clone(), item_id.clone(), index)?);
+        }
+        Ok(Self::links_to(&env, &item_id, &item.head, &history))
+    }
+
+    /// Check a history obtained elsewhere (e.g. from an indexer) against the
+    /// on-chain head. True only for the complete, unmodified history.
+    pub fn verify_history(
+        env: Env,
+        item_id: BytesN<32>,
+        history: Vec<Record>,
+    ) -> Result<bool, ProvenanceError> {
+        let item = Self::item(env.clone(), item_id.clone())?;
+        Ok(history.len() == item.record_count
+            && Self::links_to(&env, &item_id, &item.head, &history))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn append(env: &Env, item_id: &BytesN<32>, mut item: Item, record: Record) {
+        let hash = Self::hash(env, &record);
+        env.storage().persistent().set(
+            &DataKey::Record(item_id.clone(), item.record_count),
+            &record,
+        );
+        item.custodian = record.custodian;
+        item.record_count += 1;
+        item.head = hash;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Item(item_id.clone()), &item);
+    }
+
+    fn links_to(env: &Env, item_id: &BytesN<32>, head: &BytesN<32>, history: &Vec<Record>) -> bool {
+        let mut expected = item_id.clone();
+        for record in history.iter() {
+            if record.prev != expected {
+                return false;
+            }
+            expected = Self::hash(env, &record);
+        }
+        expected == *head
+    }
+
+    fn hash(env: &Env, record: &Record) -> BytesN<32> {
+        env.crypto().sha256(&record.clone().to_xdr(env)).to_bytes()
+    }
+}
+
+mod test;
@@ -0,0 +1,210 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ProvenanceContractClient<'a>,
+    item: BytesN<32>,
+    maker: Address,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProvenanceContract);
+    let client = ProvenanceContractClient::new(&env, &contract_id);
+    let item = BytesN::from_array(&env, &[7; 32]);
+    let maker = Address::generate(&env);
+    client.register_item(&item, &maker, &location(&env, 1));
+    Setup {
+        env,
+        client,
+        item,
+        maker,
+    }
+}
+
+fn location(env: &Env, n: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[n; 32])
+}
+
+fn history(s: &Setup) -> Vec<Record> {
+    let count = s.client.item(&s.item).record_count;
+    let mut records = Vec::new(&s.env);
+    for index in 0..count {
+        records.push_back(s.client.record(&s.item, &index));
+    }
+    records
+}
+
+#[test]
+fn test_register_item() {
+    let s = setup();
+    let item = s.client.item(&s.item);
+    assert_eq!(item.custodian, s.maker);
+    assert_eq!(item.record_count, 1);
+
+    let record = s.client.record(&s.item, &0);
+    assert_eq!(record.kind, RecordKind::Registered);
+    assert_eq!(record.prev, s.item);
+    assert!(s.client.verify_chain(&s.item));
+}
+
+#[test]
+fn test_register_twice_fails() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_register_item(&s.item, &other, &location(&s.env, 2)),
+        Err(Ok(ProvenanceError::AlreadyRegistered))
+    );
+}
+
+#[test]
+fn test_transfer_custody_chain() {
+    let s = setup();
+    let shipper = Address::generate(&s.env);
+    let retailer = Address::generate(&s.env);
+
+    s.env.ledger().with_mut(|l| l.timestamp = 1_000);
+    s.client
+        .transfer_custody(&s.item, &s.maker, &shipper, &location(&s.env, 2));
+    s.client.checkpoint(&s.item, &shipper, &location(&s.env, 3));
+    s.env.ledger().with_mut(|l| l.timestamp = 2_000);
+    s.client
+        .transfer_custody(&s.item, &shipper, &retailer, &location(&s.env, 4));
+
+    let item = s.client.item(&s.item);
+    assert_eq!(item.custodian, retailer);
+    assert_eq!(item.record_count, 4);
+
+    let mut kinds = Vec::new(&s.env);
+    for record in history(&s).iter() {
+        kinds.push_back(record.kind);
+    }
+    assert_eq!(
+        kinds,
+        vec![
+            &s.env,
+            RecordKind::Registered,
+            RecordKind::Transfer,
+            RecordKind::Checkpoint,
+            RecordKind::Transfer
+        ]
+    );
+    assert_eq!(s.client.record(&s.item, &3).timestamp, 2_000);
+    assert!(s.client.verify_chain(&s.item));
+}
+
+#[test]
+fn test_transfer_requires_both_parties() {
+    let s = setup();
+    let shipper = Address::generate(&s.env);
+    s.client
+        .transfer_custody(&s.item, &s.maker, &shipper, &location(&s.env, 2));
+
+    let auths = s.env.auths();
+    assert_eq!(auths.len(), 2);
+    assert!(auths.iter().any(|(addr, _)| *addr == s.maker));
+    assert!(auths.iter().any(|(addr, _)| *addr == shipper));
+}
+
+#[test]
+fn test_transfer_from_non_custodian_fails() {
+    let s = setup();
+    let thief = Address::generate(&s.env);
+    let buyer = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_transfer_custody(&s.item, &thief, &buyer, &location(&s.env, 2)),
+        Err(Ok(ProvenanceError::NotCustodian))
+    );
+}
+
+#[test]
+fn test_self_transfer_fails() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_transfer_custody(&s.item, &s.maker, &s.maker, &location(&s.env, 2)),
+        Err(Ok(ProvenanceError::SelfTransfer))
+    );
+}
+
+#[test]
+fn test_checkpoint_by_non_custodian_fails() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_checkpoint(&s.item, &stranger, &location(&s.env, 2)),
+        Err(Ok(ProvenanceError::NotCustodian))
+    );
+}
+
+#[test]
+fn test_unknown_item() {
+    let s = setup();
+    let unknown = BytesN::from_array(&s.env, &[9; 32]);
+    assert_eq!(
+        s.client.try_item(&unknown),
+        Err(Ok(ProvenanceError::NotFound))
+    );
+    assert_eq!(
+        s.client
+            .try_checkpoint(&unknown, &s.maker, &location(&s.env, 2)),
+        Err(Ok(ProvenanceError::NotFound))
+    );
+}
+
+#[test]
+fn test_verify_history_accepts_full_history() {
+    let s = setup();
+    let shipper = Address::generate(&s.env);
+    s.client
+        .transfer_custody(&s.item, &s.maker, &shipper, &location(&s.env, 2));
+    assert!(s.client.verify_history(&s.item, &history(&s)));
+}
+
+#[test]
+fn test_verify_history_rejects_tampering() {
+    let s = setup();
+    let shipper = Address::generate(&s.env);
+    s.client
+        .transfer_custody(&s.item, &s.maker, &shipper, &location(&s.env, 2));
+    s.client.checkpoint(&s.item, &shipper, &location(&s.env, 3));
+    let records = history(&s);
+
+    // Altered location.
+    let mut altered = records.clone();
+    let mut record = altered.get(1).unwrap();
+    record.location_hash = location(&s.env, 9);
+    altered.set(1, record);
+    assert!(!s.client.verify_history(&s.item, &altered));
+
+    // Missing latest record.
+    let mut truncated = records.clone();
+    truncated.pop_back();
+    assert!(!s.client.verify_history(&s.item, &truncated));
+
+    // Reordered records.
+    let mut reordered = records.clone();
+    let first = reordered.get(1).unwrap();
+    reordered.set(1, reordered.get(2).unwrap());
+    reordered.set(2, first);
+    assert!(!s.client.verify_history(&s.item, &reordered));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_checkpoint_requires_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.client.checkpoint(&s.item, &s.maker, &location(&s.env, 2));
+}
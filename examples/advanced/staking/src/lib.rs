@@ -0,0 +1,324 @@
+//! # Staking Rewards
+//!
+//! Users stake one token and earn another, `reward_rate` tokens per second
+//! in total, split in proportion to stake. This is the reward-per-token
+//! accumulator popularised by Synthetix's `StakingRewards`, written out
+//! with checked `i128` fixed-point math.
+//!
+//! ## The accumulator
+//!
+//! `reward_per_token` is the reward one staked unit has earned since
+//! deployment, scaled by `PRECISION` (10^18). Each second adds
+//! `reward_rate * PRECISION / total_staked` to it. A staker remembers the
+//! value they last settled at, `reward_per_token_paid`, so what they earned
+//! since is
+//!
+//! ```text
+//! amount * (reward_per_token - reward_per_token_paid) / PRECISION
+//! ```
+//!
+//! Every change to `total_staked` or `reward_rate` first brings the
+//! accumulator up to the current time (`update_reward`), so each stretch of
+//! time is divided by the stake that was present during it. The cost of
+//! every call is constant no matter how many stakers there are.
+//!
+//! ## Precision
+//!
+//! - **Why scale at all.** With 1000 units staked and a rate of 1 per
+//!   second, one second adds 1/1000 per unit: zero in integer math. Scaled
+//!   by 10^18 it is 10^15, and small stakes still earn their share.
+//! - **Rounding down.** Every division rounds down, so the sum of all
+//!   payouts never exceeds what was emitted. The difference is dust that
+//!   stays in the contract: the accumulator can leave a staker 10^-18 short
+//!   of a whole unit, so each staker loses less than one unit in total.
+//! - **Keeping user dust.** A staker's settled rewards are stored still
+//!   scaled (`UserStake::rewards`). `claim` pays out the whole units and
+//!   keeps the fraction, so claiming every ledger earns exactly as much as
+//!   claiming once at the end.
+//! - **Overflow.** Every multiplication is checked and fails with
+//!   `MathOverflow`. `i128` leaves room for about 10^20 tokens times
+//!   seconds of emission per staked unit; see `math`.
+//!
+//! Rewards emitted while nothing is staked belong to nobody and remain in
+//! the contract. For pausing and an emergency exit, see
+//! `20-streaming-rewards`.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+mod math;
+
+use math::{earned_scaled, reward_per_token, split_scaled};
+
+/// Fixed-point scale of the accumulator and of unclaimed rewards.
+pub const PRECISION: i128 = 1_000_000_000_000_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub stake_token: Address,
+    /// Paid out of this contract's balance; the admin funds it.
+    pub reward_token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    /// Reward tokens emitted per second across all stakers.
+    pub reward_rate: i128,
+    pub total_staked: i128,
+    /// Reward per staked unit up to `last_update`, times `PRECISION`.
+    pub reward_per_token: i128,
+    /// Time the accumulator was last brought up to date.
+    pub last_update: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UserStake {
+    pub amount: i128,
+    /// `Pool::reward_per_token` when this stake was last settled.
+    pub reward_per_token_paid: i128,
+    /// Settled, unclaimed rewards, times `PRECISION`.
+    pub rewards: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Config,
+    Pool,
+    Stake(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StakingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    /// A negative reward rate.
+    InvalidRate = 4,
+    InsufficientStake = 5,
+    /// A fixed-point operation would overflow `i128`.
+    MathOverflow = 6,
+}
+
+#[contract]
+pub struct Staking;
+
+#[contractimpl]
+impl Staking {
+    pub fn initialize(env: Env, config: Config, reward_rate: i128) -> Result<(), StakingError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(StakingError::AlreadyInitialized);
+        }
+        if reward_rate < 0 {
+            return Err(StakingError::InvalidRate);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(
+            &DataKey::Pool,
+            &Pool {
+                reward_rate,
+                total_staked: 0,
+                reward_per_token: 0,
+                last_update: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    // ==================== STAKING ====================
+
+    /// Emits `(stake, staker)` with the amount.
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<(), StakingError> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let (mut pool, mut user) = update_reward(&env, &staker)?;
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &staker,
+            &env.current_contract_address(),
+            &amount,
+        );
+        user.amount += amount;
+        pool.total_staked += amount;
+        save(&env, &pool, &staker, &user);
+
+        env.events()
+            .publish((symbol_short!("stake"), staker), amount);
+        Ok(())
+    }
+
+    /// Emits `(unstake, staker)` with the amount. Rewards stay claimable.
+    pub fn unstake(env: Env, staker: Address, amount: i128) -> Result<(), StakingError> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+        let config = Self::config(env.clone())?;
+        let (mut pool, mut user) = update_reward(&env, &staker)?;
+        if amount > user.amount {
+            return Err(StakingError::InsufficientStake);
+        }
+
+        user.amount -= amount;
+        pool.total_staked -= amount;
+        save(&env, &pool, &staker, &user);
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+
+        env.events()
+            .publish((symbol_short!("unstake"), staker), amount);
+        Ok(())
+    }
+
+    /// Pay out the whole token units `staker` has earned and return the
+    /// amount. The fraction below one unit stays for the next claim.
+    ///
+    /// Emits `(claim, staker)` with the amount, when it is not zero.
+    pub fn claim(env: Env, staker: Address) -> Result<i128, StakingError> {
+        staker.require_auth();
+        let config = Self::config(env.clone())?;
+        let (pool, mut user) = update_reward(&env, &staker)?;
+        let (amount, dust) = split_scaled(user.rewards);
+        user.rewards = dust;
+        save(&env, &pool, &staker, &user);
+
+        if amount > 0 {
+            token::Client::new(&env, &config.reward_token).transfer(
+                &env.current_contract_address(),
+                &staker,
+                &amount,
+            );
+            env.events()
+                .publish((symbol_short!("claim"), staker), amount);
+        }
+        Ok(amount)
+    }
+
+    // ==================== ADMIN ====================
+
+    /// Change the emission rate from now on. Admin only.
+    ///
+    /// Emits `(rate,)` with `(old, new)`.
+    pub fn set_reward_rate(env: Env, reward_rate: i128) -> Result<(), StakingError> {
+        Self::config(env.clone())?.admin.require_auth();
+        if reward_rate < 0 {
+            return Err(StakingError::InvalidRate);
+        }
+        // Everything up to now accrues at the old rate.
+        let mut pool = checkpoint(&env)?;
+        let old = pool.reward_rate;
+        pool.reward_rate = reward_rate;
+        env.storage().instance().set(&DataKey::Pool, &pool);
+
+        env.events()
+            .publish((symbol_short!("rate"),), (old, reward_rate));
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn config(env: Env) -> Result<Config, StakingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(StakingError::NotInitialized)
+    }
+
+    /// The pool as stored, as of its last update.
+    pub fn pool(env: Env) -> Result<Pool, StakingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Pool)
+            .ok_or(StakingError::NotInitialized)
+    }
+
+    /// The accumulator as of now, times `PRECISION`.
+    pub fn reward_per_token(env: Env) -> Result<i128, StakingError> {
+        Ok(accrued(&env, Self::pool(env.clone())?)?.reward_per_token)
+    }
+
+    pub fn user_stake(env: Env, staker: Address) -> UserStake {
+        stake_of(&env, &staker)
+    }
+
+    /// Whole token units `staker` could claim now.
+    pub fn earned(env: Env, staker: Address) -> Result<i128, StakingError> {
+        let pool = accrued(&env, Self::pool(env.clone())?)?;
+        let user = settled(&pool, stake_of(&env, &staker))?;
+        Ok(split_scaled(user.rewards).0)
+    }
+}
+
+/// `pool` brought up to the current time.
+fn accrued(env: &Env, mut pool: Pool) -> Result<Pool, StakingError> {
+    let now = env.ledger().timestamp();
+    pool.reward_per_token = reward_per_token(
+        pool.reward_per_token,
+        pool.reward_rate,
+        now - pool.last_update,
+        pool.total_staked,
+    )?;
+    pool.last_update = now;
+    Ok(pool)
+}
+
+/// Bring the stored accumulator up to date and return it.
+fn checkpoint(env: &Env) -> Result<Pool, StakingError> {
+    let pool = accrued(env, Staking::pool(env.clone())?)?;
+    env.storage().instance().set(&DataKey::Pool, &pool);
+    Ok(pool)
+}
+
+/// Move what `user` earned up to `pool` into their scaled rewards.
+fn settled(pool: &Pool, mut user: UserStake) -> Result<UserStake, StakingError> {
+    let earned = earned_scaled(
+        user.amount,
+        pool.reward_per_token,
+        user.reward_per_token_paid,
+    )?;
+    user.rewards = user
+        .rewards
+        .checked_add(earned)
+        .ok_or(StakingError::MathOverflow)?;
+    user.reward_per_token_paid = pool.reward_per_token;
+    Ok(user)
+}
+
+/// Checkpoint the pool and settle `staker` against it. Callers change the
+/// stake or rewards, then `save` both.
+fn update_reward(env: &Env, staker: &Address) -> Result<(Pool, UserStake), StakingError> {
+    let pool = checkpoint(env)?;
+    let user = settled(&pool, stake_of(env, staker))?;
+    Ok((pool, user))
+}
+
+fn stake_of(env: &Env, staker: &Address) -> UserStake {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Stake(staker.clone()))
+        .unwrap_or_default()
+}
+
+fn save(env: &Env, pool: &Pool, staker: &Address, user: &UserStake) {
+    env.storage().instance().set(&DataKey::Pool, pool);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Stake(staker.clone()), user);
+}
+
+mod test;
@@ -0,0 +1,50 @@
+//! Fixed-point arithmetic for the reward accumulator.
+//!
+//! Values scaled by `PRECISION` carry 18 decimal places below a token's
+//! smallest unit. Every operation is checked, so an overflow surfaces as
+//! `MathOverflow` instead of a trap, and every division rounds down, so
+//! the contract never owes more than it has emitted.
+
+use crate::{StakingError, PRECISION};
+
+/// `a * b / d`, rounded down. `a` and `b` must be non-negative and `d`
+/// positive.
+pub(crate) fn mul_div_floor(a: i128, b: i128, d: i128) -> Result<i128, StakingError> {
+    a.checked_mul(b)
+        .map(|product| product / d)
+        .ok_or(StakingError::MathOverflow)
+}
+
+/// The accumulator after `elapsed` seconds at `rate` tokens per second,
+/// shared by `total_staked` units. Nothing accrues while nothing is staked.
+pub(crate) fn reward_per_token(
+    stored: i128,
+    rate: i128,
+    elapsed: u64,
+    total_staked: i128,
+) -> Result<i128, StakingError> {
+    if total_staked == 0 || elapsed == 0 {
+        return Ok(stored);
+    }
+    let emitted = rate
+        .checked_mul(elapsed as i128)
+        .ok_or(StakingError::MathOverflow)?;
+    let per_token = mul_div_floor(emitted, PRECISION, total_staked)?;
+    stored
+        .checked_add(per_token)
+        .ok_or(StakingError::MathOverflow)
+}
+
+/// What `amount` staked units earned while the accumulator moved from
+/// `paid` to `current`, still scaled by `PRECISION`.
+pub(crate) fn earned_scaled(amount: i128, current: i128, paid: i128) -> Result<i128, StakingError> {
+    amount
+        .checked_mul(current - paid)
+        .ok_or(StakingError::MathOverflow)
+}
+
+/// Split scaled rewards into whole token units, rounded down, and the
+/// scaled remainder.
+pub(crate) fn split_scaled(scaled: i128) -> (i128, i128) {
+    (scaled / PRECISION, scaled % PRECISION)
+}
@@ -0,0 +1,296 @@
+#![cfg(test)]
+
+use super::*;
+use crate::math::{mul_div_floor, reward_per_token as rpt};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+};
+
+/// Reward tokens funded into the pool.
+const FUNDING: i128 = 1_000_000_000_000;
+
+struct Setup<'a> {
+    env: Env,
+    pool: StakingClient<'a>,
+    stake_admin: StellarAssetClient<'a>,
+    stake_token: TokenClient<'a>,
+    reward_token: TokenClient<'a>,
+}
+
+fn setup(reward_rate: i128) -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let stake_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let reward_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let pool = StakingClient::new(&env, &env.register_contract(None, Staking));
+    pool.initialize(
+        &Config {
+            admin: Address::generate(&env),
+            stake_token: stake_id.clone(),
+            reward_token: reward_id.clone(),
+        },
+        &reward_rate,
+    );
+    StellarAssetClient::new(&env, &reward_id).mint(&pool.address, &FUNDING);
+
+    Setup {
+        stake_admin: StellarAssetClient::new(&env, &stake_id),
+        stake_token: TokenClient::new(&env, &stake_id),
+        reward_token: TokenClient::new(&env, &reward_id),
+        env,
+        pool,
+    }
+}
+
+impl Setup<'_> {
+    fn staker(&self, amount: i128) -> Address {
+        let staker = Address::generate(&self.env);
+        self.stake_admin.mint(&staker, &amount);
+        if amount > 0 {
+            self.pool.stake(&staker, &amount);
+        }
+        staker
+    }
+
+    fn at(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+    }
+
+    /// Reward tokens paid out so far.
+    fn paid_out(&self) -> i128 {
+        FUNDING - self.reward_token.balance(&self.pool.address)
+    }
+}
+
+// ==================== FIXED-POINT MATH ====================
+
+#[test]
+fn test_mul_div_rounds_down() {
+    assert_eq!(mul_div_floor(7, 3, 2), Ok(10));
+    assert_eq!(mul_div_floor(1, 1, 3), Ok(0));
+    assert_eq!(mul_div_floor(0, i128::MAX, 1), Ok(0));
+    assert_eq!(
+        mul_div_floor(i128::MAX, 2, 2),
+        Err(StakingError::MathOverflow)
+    );
+}
+
+#[test]
+fn test_scaling_keeps_small_shares() {
+    // 1 token per second over 1000 staked units: 1/1000 per unit.
+    // Unscaled, that is zero; scaled, exactly PRECISION / 1000.
+    assert_eq!(1 / 1000, 0);
+    assert_eq!(rpt(0, 1, 1, 1000), Ok(PRECISION / 1000));
+    // Nothing staked, or no time passed: unchanged.
+    assert_eq!(rpt(5, 1, 100, 0), Ok(5));
+    assert_eq!(rpt(5, 1, 0, 10), Ok(5));
+}
+
+#[test]
+fn test_accumulator_overflow_is_an_error() {
+    // rate * elapsed * PRECISION must fit in i128 (about 1.7e38).
+    assert_eq!(
+        rpt(0, 1_000_000_000_000_000_000, 1_000, 1),
+        Err(StakingError::MathOverflow)
+    );
+    assert!(rpt(0, 1_000_000_000, 1_000_000_000, 1).is_ok());
+}
+
+// ==================== ACCRUAL ====================
+
+#[test]
+fn test_single_staker_earns_full_rate() {
+    let t = setup(10);
+    let alice = t.staker(1_000);
+    t.at(100);
+    assert_eq!(t.pool.earned(&alice), 1_000);
+    assert_eq!(t.pool.claim(&alice), 1_000);
+    assert_eq!(t.reward_token.balance(&alice), 1_000);
+    assert_eq!(t.pool.earned(&alice), 0);
+}
+
+#[test]
+fn test_rewards_split_by_stake_over_time() {
+    let t = setup(100);
+    let alice = t.staker(100);
+    // 0..10: Alice alone earns 1000.
+    t.at(10);
+    let bob = t.staker(300);
+    // 10..20: 1000 split 1:3.
+    t.at(20);
+    t.pool.unstake(&bob, &300);
+    // 20..30: Alice alone again.
+    t.at(30);
+
+    assert_eq!(t.pool.earned(&alice), 1_000 + 250 + 1_000);
+    assert_eq!(t.pool.earned(&bob), 750);
+    // Unstaking kept Bob's rewards claimable.
+    assert_eq!(t.pool.claim(&bob), 750);
+    assert_eq!(t.stake_token.balance(&bob), 300);
+}
+
+#[test]
+fn test_nothing_accrues_while_nothing_is_staked() {
+    let t = setup(10);
+    t.at(1_000);
+    let alice = t.staker(50);
+    t.at(1_010);
+    // The first 1000 seconds went to nobody.
+    assert_eq!(t.pool.earned(&alice), 100);
+}
+
+#[test]
+fn test_rate_change_applies_from_now_on() {
+    let t = setup(10);
+    let alice = t.staker(1);
+    t.at(50);
+    t.pool.set_reward_rate(&1);
+    t.at(150);
+    assert_eq!(t.pool.earned(&alice), 500 + 100);
+
+    t.pool.set_reward_rate(&0);
+    t.at(1_000);
+    assert_eq!(t.pool.earned(&alice), 600);
+    assert_eq!(
+        t.pool.try_set_reward_rate(&-1),
+        Err(Ok(StakingError::InvalidRate))
+    );
+}
+
+// ==================== PRECISION EDGE CASES ====================
+
+#[test]
+fn test_uneven_split_never_overpays() {
+    // 10 per second over stakes of 1, 1 and 1: 3.33... each per second.
+    let t = setup(10);
+    let stakers = [t.staker(1), t.staker(1), t.staker(1)];
+    t.at(1);
+    let mut total = 0;
+    for s in stakers.iter() {
+        total += t.pool.claim(s);
+    }
+    // 10 emitted, 3 paid to each, a third of a unit each kept as dust.
+    assert_eq!(total, 9);
+
+    t.at(3);
+    for s in stakers.iter() {
+        total += t.pool.claim(s);
+    }
+    // 30 emitted by now, but each staker gets 9, not 10. The accumulator
+    // rounded 10/3 down at both checkpoints, so each holds 9.999...,
+    // just short of the tenth unit. The pool never overpays, and the loss
+    // stays below one unit per staker.
+    assert_eq!(total, 27);
+    assert_eq!(t.paid_out(), 27);
+    for s in stakers.iter() {
+        assert_eq!(t.pool.user_stake(s).rewards, PRECISION - 1);
+    }
+}
+
+#[test]
+fn test_minnow_beside_whale_still_earns() {
+    let t = setup(1);
+    let whale = t.staker(1_000_000_000_000);
+    let minnow = t.staker(1);
+
+    // Each second pays the minnow 1e-12 of a token.
+    t.at(1_000_000);
+    assert_eq!(t.pool.earned(&minnow), 0);
+    let unit = t.pool.user_stake(&minnow);
+    assert_eq!(unit.rewards, 0, "settled lazily");
+
+    // Their share is tracked in the accumulator and surfaces in time.
+    t.at(1_000_000_000_001);
+    assert_eq!(t.pool.earned(&minnow), 1);
+    assert!(t.pool.earned(&whale) + t.pool.earned(&minnow) <= 1_000_000_000_001);
+}
+
+#[test]
+fn test_frequent_claims_lose_no_dust() {
+    // Alice and Bob each earn 0.5 per second. Alice claims every second,
+    // Bob once at the end; they end up with the same amount.
+    let t = setup(1);
+    let alice = t.staker(10);
+    let bob = t.staker(10);
+    for second in 1..=9u64 {
+        t.at(second);
+        t.pool.claim(&alice);
+    }
+    t.at(10);
+    let alice_total = t.reward_token.balance(&alice) + t.pool.claim(&alice);
+    let bob_total = t.pool.claim(&bob);
+    assert_eq!(alice_total, 5);
+    assert_eq!(bob_total, 5);
+
+    // The half-unit Alice could not claim each odd second was kept, scaled.
+    t.at(11);
+    t.pool.claim(&alice);
+    assert_eq!(t.pool.user_stake(&alice).rewards, PRECISION / 2);
+}
+
+#[test]
+fn test_payouts_bounded_by_emission() {
+    // Awkward numbers: stakes of 3, 7 and 11, rate 13, joins and exits at
+    // odd times. Whatever rounding happens, the pool never pays more than
+    // it emitted, and loses less than one unit per staker.
+    let t = setup(13);
+    let a = t.staker(3);
+    t.at(7);
+    let b = t.staker(7);
+    t.at(19);
+    let c = t.staker(11);
+    t.at(23);
+    t.pool.unstake(&b, &7);
+    t.at(41);
+
+    let mut paid = 0;
+    for s in [&a, &b, &c] {
+        paid += t.pool.claim(s);
+    }
+    let emitted = 13 * 41;
+    assert!(paid <= emitted);
+    assert!(emitted - paid < 3, "lost {} to rounding", emitted - paid);
+    assert_eq!(t.paid_out(), paid);
+}
+
+#[test]
+fn test_settlement_overflow_is_an_error() {
+    // A huge stake times a large accumulator delta overflows when settled.
+    let t = setup(1_000_000_000_000_000);
+    let big = t.staker(1);
+    t.at(100_000_000);
+    assert_eq!(t.pool.try_claim(&big), Err(Ok(StakingError::MathOverflow)));
+}
+
+// ==================== VALIDATION AND AUTH ====================
+
+#[test]
+fn test_invalid_stake_and_unstake() {
+    let t = setup(1);
+    let alice = t.staker(10);
+    assert_eq!(
+        t.pool.try_stake(&alice, &0),
+        Err(Ok(StakingError::InvalidAmount))
+    );
+    assert_eq!(
+        t.pool.try_unstake(&alice, &11),
+        Err(Ok(StakingError::InsufficientStake))
+    );
+    t.pool.unstake(&alice, &10);
+    assert_eq!(t.stake_token.balance(&alice), 10);
+    assert_eq!(t.pool.pool().total_staked, 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_reward_rate_requires_admin_auth() {
+    let t = setup(1);
+    t.env.set_auths(&[]);
+    t.pool.set_reward_rate(&2);
+}
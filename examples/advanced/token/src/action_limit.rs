@@ -0,0 +1,55 @@
+//! Optional anti-bot mode: at most `limit` transfers per address per ledger.
+//!
+//! Each action bumps a counter in temporary storage keyed by
+//! `(address, ledger sequence)`. A new ledger means a new key, so counters
+//! never need resetting, and the network evicts old ones on its own.
+
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::storage::{ActionKey, DataKey};
+use crate::TokenError;
+
+/// Current per-ledger limit; 0 means the mode is off.
+pub(crate) fn read_action_limit(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ActionLimit)
+        .unwrap_or(0)
+}
+
+pub(crate) fn write_action_limit(env: &Env, limit: u32) {
+    env.storage().instance().set(&DataKey::ActionLimit, &limit);
+}
+
+/// Actions `id` has taken in the current ledger.
+pub(crate) fn read_actions(env: &Env, id: &Address) -> u32 {
+    env.storage()
+        .temporary()
+        .get(&action_key(env, id))
+        .unwrap_or(0)
+}
+
+/// Count one action for `id`, failing once the limit for this ledger is
+/// reached. Does nothing while the mode is off.
+pub(crate) fn record_action(env: &Env, id: &Address) {
+    let limit = read_action_limit(env);
+    if limit == 0 {
+        return;
+    }
+    let count = read_actions(env, id);
+    if count >= limit {
+        panic_with_error!(env, TokenError::ActionLimitExceeded);
+    }
+    // The default minimum TTL of a temporary entry outlives the ledger it
+    // counts, so no extension is needed.
+    env.storage()
+        .temporary()
+        .set(&action_key(env, id), &(count + 1));
+}
+
+fn action_key(env: &Env, id: &Address) -> DataKey {
+    DataKey::Actions(ActionKey {
+        address: id.clone(),
+        sequence: env.ledger().sequence(),
+    })
+}
@@ -0,0 +1,413 @@
+//! # SEP-41 Token
+//!
+//! A complete fungible token implementing the [SEP-41] token interface
+//! (`soroban_sdk::token::TokenInterface`), plus the admin functions a token
+//! issuer needs (`initialize`, `mint`, `set_admin`) and a minting
+//! controller (minters, a supply cap and an emission schedule).
+//!
+//! Because the contract implements the standard interface, any SEP-41 client
+//! can talk to it — including `soroban_sdk::token::TokenClient`, which is
+//! what the tests use. Wallets, DEXes and other contracts need no knowledge
+//! of this particular implementation.
+//!
+//! ## Storage layout
+//!
+//! | Data                         | Storage    | Why                                        |
+//! |------------------------------|------------|--------------------------------------------|
+//! | admin, decimals, name, symbol| instance   | small, read on most calls                  |
+//! | `Balance(id)`                | persistent | must never silently disappear              |
+//! | `Allowance(from, spender)`   | temporary  | TTL set to the expiration ledger           |
+//! | `Actions(id, sequence)`      | temporary  | only meaningful for one ledger             |
+//! | `Spenders(owner)`            | persistent | index of approvals; see `spenders`         |
+//! | supply, cap, schedule        | instance   | checked on every mint; see `minting`       |
+//! | `Minter(id)`                 | persistent | a minter's remaining allowance             |
+//!
+//! Allowances carry an `expiration_ledger`. The entry's TTL is set to match,
+//! so the network deletes it once it can no longer be spent; reads also treat
+//! anything past its expiration as zero.
+//!
+//! ## Events
+//!
+//! | Function           | Topics                          | Data                          |
+//! |--------------------|---------------------------------|-------------------------------|
+//! | `transfer`         | `("transfer", from, to)`        | `amount`                      |
+//! | `approve`          | `("approve", from, spender)`    | `(amount, expiration_ledger)` |
+//! | `revoke_all`       | `("approve", from, spender)`    | `(0, current ledger)`, each   |
+//! | `mint`             | `("mint", admin, to)`           | `amount`                      |
+//! | `mint_from`        | `("mint", minter, to)`          | `amount`                      |
+//! | `set_minter`       | `("set_minter", admin, minter)` | `allowance`                   |
+//! | `set_max_supply`   | `("max_supply", admin)`         | `max_supply`                  |
+//! | `set_emission`     | `("emission", admin)`           | `(epoch_ledgers, per_epoch)`  |
+//! | `burn`             | `("burn", from)`                | `amount`                      |
+//! | `set_admin`        | `("set_admin", admin)`          | `new_admin`                   |
+//! | `set_action_limit` | `("act_limit", admin)`          | `limit`                       |
+//!
+//! ## Anti-bot mode
+//!
+//! `set_action_limit(n)` caps every address at `n` transfers per ledger
+//! (`transfer` counts the sender, `transfer_from` the spender). Counters live
+//! in temporary storage keyed by `(address, sequence)`; see `action_limit`.
+//! A limit of 0, the default, turns the mode off.
+//!
+//! ## Listing and revoking approvals
+//!
+//! Owners forget what they approved. `approvals(owner)` lists every live
+//! allowance and `revoke_all(owner)` zeroes them in one call. Both read a
+//! per-owner index of spenders that `approve` maintains, capped at
+//! `MAX_SPENDERS` (20) entries; see `spenders`.
+//!
+//! ## Minting controls
+//!
+//! The admin can hand out bounded minting rights instead of minting
+//! everything itself. `set_minter(minter, allowance)` lets `minter` create
+//! up to `allowance` tokens through `mint_from`. Every mint, the admin's
+//! included, also has to fit under the hard cap from `set_max_supply` and
+//! the current epoch's budget from `set_emission`. `remaining_mintable`
+//! reports how much a given address could mint right now.
+//!
+//! [SEP-41]: https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0041.md
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, panic_with_error, symbol_short, token::TokenInterface,
+    Address, Env, Map, String, Symbol, Vec,
+};
+
+mod action_limit;
+mod minting;
+mod spenders;
+mod storage;
+
+use action_limit::{read_action_limit, read_actions, record_action, write_action_limit};
+pub use minting::EmissionSchedule;
+use minting::{
+    read_emission_schedule, read_max_supply, read_minter_allowance, read_total_supply, record_burn,
+    record_mint, remaining_supply, spend_minter_allowance, write_emission_schedule,
+    write_max_supply, write_minter_allowance,
+};
+use spenders::{live_spenders, read_spenders, track_approval, write_spenders};
+use storage::{
+    extend_instance, read_allowance, read_balance, receive_balance, spend_allowance, spend_balance,
+    write_allowance,
+};
+pub use storage::{AllowanceValue, DataKey};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NegativeAmount = 3,
+    InsufficientBalance = 4,
+    InsufficientAllowance = 5,
+    /// A non-zero allowance was given an expiration ledger in the past.
+    InvalidExpiration = 6,
+    /// More than 18 decimals were requested.
+    DecimalsTooLarge = 7,
+    Overflow = 8,
+    /// The address already used up its actions for this ledger.
+    ActionLimitExceeded = 9,
+    /// The owner already has `MAX_SPENDERS` live approvals.
+    TooManySpenders = 10,
+    /// The mint would take the total supply over `max_supply`.
+    SupplyCapExceeded = 11,
+    /// The mint would exceed what the current epoch allows.
+    EpochLimitExceeded = 12,
+    /// The minter has no allowance left for this amount.
+    MinterAllowanceExceeded = 13,
+    /// A supply cap above the current cap or below the current supply, or
+    /// a negative emission or minter allowance.
+    InvalidMintConfig = 14,
+}
+
+#[contract]
+pub struct TokenContract;
+
+#[contractimpl]
+impl TokenContract {
+    /// Set the admin (the only address that can mint) and the metadata.
+    pub fn initialize(env: Env, admin: Address, decimals: u32, name: String, symbol: String) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, TokenError::AlreadyInitialized);
+        }
+        if decimals > 18 {
+            panic_with_error!(&env, TokenError::DecimalsTooLarge);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Decimals, &decimals);
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        extend_instance(&env);
+    }
+
+    /// Create `amount` new tokens for `to`. Admin only; subject to the
+    /// supply cap and emission schedule.
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        check_nonnegative_amount(&env, amount);
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        extend_instance(&env);
+
+        record_mint(&env, amount);
+        receive_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("mint"), admin, to), amount);
+    }
+
+    /// Create `amount` new tokens for `to` out of `minter`'s allowance.
+    /// Subject to the supply cap and emission schedule, like `mint`.
+    pub fn mint_from(env: Env, minter: Address, to: Address, amount: i128) {
+        minter.require_auth();
+        check_nonnegative_amount(&env, amount);
+        extend_instance(&env);
+
+        spend_minter_allowance(&env, &minter, amount);
+        record_mint(&env, amount);
+        receive_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("mint"), minter, to), amount);
+    }
+
+    /// Let `minter` mint up to `allowance` more tokens, replacing what it
+    /// had left; 0 removes the minter. Admin only.
+    pub fn set_minter(env: Env, minter: Address, allowance: i128) {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        if allowance < 0 {
+            panic_with_error!(&env, TokenError::InvalidMintConfig);
+        }
+        extend_instance(&env);
+
+        write_minter_allowance(&env, &minter, allowance);
+        env.events()
+            .publish((Symbol::new(&env, "set_minter"), admin, minter), allowance);
+    }
+
+    /// Cap the total supply. Once set, the cap can only be lowered, and
+    /// never below the current supply. Admin only.
+    pub fn set_max_supply(env: Env, max_supply: i128) {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        extend_instance(&env);
+
+        write_max_supply(&env, max_supply);
+        env.events()
+            .publish((Symbol::new(&env, "max_supply"), admin), max_supply);
+    }
+
+    /// Allow at most `per_epoch` tokens to be minted every `epoch_ledgers`
+    /// ledgers; an `epoch_ledgers` of 0 removes the schedule. Admin only.
+    pub fn set_emission(env: Env, epoch_ledgers: u32, per_epoch: i128) {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        extend_instance(&env);
+
+        write_emission_schedule(&env, epoch_ledgers, per_epoch);
+        env.events().publish(
+            (symbol_short!("emission"), admin),
+            (epoch_ledgers, per_epoch),
+        );
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        read_total_supply(&env)
+    }
+
+    pub fn max_supply(env: Env) -> Option<i128> {
+        read_max_supply(&env)
+    }
+
+    pub fn emission(env: Env) -> Option<EmissionSchedule> {
+        read_emission_schedule(&env)
+    }
+
+    pub fn minter_allowance(env: Env, minter: Address) -> i128 {
+        read_minter_allowance(&env, &minter)
+    }
+
+    /// How much `minter` could mint in this ledger: the smallest of its
+    /// allowance (unlimited for the admin), the room under the cap and the
+    /// room left in the current epoch. `i128::MAX` means no limit.
+    pub fn remaining_mintable(env: Env, minter: Address) -> i128 {
+        let remaining = remaining_supply(&env);
+        if minter == Self::admin(env.clone()) {
+            return remaining;
+        }
+        remaining.min(read_minter_allowance(&env, &minter))
+    }
+
+    /// Hand minting rights to `new_admin`. Admin only.
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        extend_instance(&env);
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.events()
+            .publish((symbol_short!("set_admin"), admin), new_admin);
+    }
+
+    /// Cap transfers per address per ledger; 0 turns the limit off. Admin only.
+    pub fn set_action_limit(env: Env, limit: u32) {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        extend_instance(&env);
+
+        write_action_limit(&env, limit);
+        env.events()
+            .publish((symbol_short!("act_limit"), admin), limit);
+    }
+
+    pub fn action_limit(env: Env) -> u32 {
+        read_action_limit(&env)
+    }
+
+    /// Transfers `id` has made in the current ledger while the limit is on.
+    pub fn actions_this_ledger(env: Env, id: Address) -> u32 {
+        read_actions(&env, &id)
+    }
+
+    /// Every live approval `owner` has given, by spender.
+    pub fn approvals(env: Env, owner: Address) -> Map<Address, AllowanceValue> {
+        extend_instance(&env);
+        let mut approvals = Map::new(&env);
+        for spender in read_spenders(&env, &owner).iter() {
+            let allowance = read_allowance(&env, &owner, &spender);
+            if allowance.amount > 0 {
+                approvals.set(spender, allowance);
+            }
+        }
+        approvals
+    }
+
+    /// Zero every allowance `owner` has given and clear the index. Returns
+    /// how many live allowances were revoked.
+    ///
+    /// Emits a standard `approve` event with amount 0 for each of them.
+    pub fn revoke_all(env: Env, owner: Address) -> u32 {
+        owner.require_auth();
+        extend_instance(&env);
+
+        let sequence = env.ledger().sequence();
+        let live = live_spenders(&env, &owner, &read_spenders(&env, &owner));
+        for spender in live.iter() {
+            write_allowance(&env, &owner, &spender, 0, sequence);
+            env.events().publish(
+                (symbol_short!("approve"), owner.clone(), spender),
+                (0i128, sequence),
+            );
+        }
+        write_spenders(&env, &owner, &Vec::new(&env));
+        live.len()
+    }
+
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, TokenError::NotInitialized))
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for TokenContract {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        extend_instance(&env);
+        read_allowance(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        check_nonnegative_amount(&env, amount);
+        extend_instance(&env);
+
+        write_allowance(&env, &from, &spender, amount, expiration_ledger);
+        track_approval(&env, &from, &spender, amount);
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        extend_instance(&env);
+        read_balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(&env, amount);
+        extend_instance(&env);
+        record_action(&env, &from);
+
+        spend_balance(&env, &from, amount);
+        receive_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(&env, amount);
+        extend_instance(&env);
+        record_action(&env, &spender);
+
+        spend_allowance(&env, &from, &spender, amount);
+        spend_balance(&env, &from, amount);
+        receive_balance(&env, &to, amount);
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(&env, amount);
+        extend_instance(&env);
+
+        spend_balance(&env, &from, amount);
+        record_burn(&env, amount);
+        env.events().publish((symbol_short!("burn"), from), amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(&env, amount);
+        extend_instance(&env);
+
+        spend_allowance(&env, &from, &spender, amount);
+        spend_balance(&env, &from, amount);
+        record_burn(&env, amount);
+        env.events().publish((symbol_short!("burn"), from), amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or_else(|| panic_with_error!(&env, TokenError::NotInitialized))
+    }
+
+    fn name(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Name)
+            .unwrap_or_else(|| panic_with_error!(&env, TokenError::NotInitialized))
+    }
+
+    fn symbol(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Symbol)
+            .unwrap_or_else(|| panic_with_error!(&env, TokenError::NotInitialized))
+    }
+}
+
+fn check_nonnegative_amount(env: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, TokenError::NegativeAmount);
+    }
+}
+
+mod test;
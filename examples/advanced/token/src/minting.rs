@@ -0,0 +1,171 @@
+//! Minting controls: a supply cap, per-minter allowances and an emission
+//! schedule.
+//!
+//! Every mint, by the admin or by a minter, goes through `record_mint`,
+//! which checks the cap and the current epoch's budget before adding to the
+//! total supply. Minters additionally spend their own allowance, which only
+//! the admin can refill.
+//!
+//! The schedule splits ledgers into epochs of `epoch_ledgers`. The amount
+//! minted in the current epoch is stored together with the epoch number, so
+//! the first mint of a new epoch starts from zero without a reset.
+
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+
+use crate::storage::DataKey;
+use crate::TokenError;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionSchedule {
+    /// Length of an epoch in ledgers.
+    pub epoch_ledgers: u32,
+    /// Most that can be minted in one epoch.
+    pub per_epoch: i128,
+}
+
+/// Amount minted so far in `epoch`.
+#[contracttype]
+#[derive(Clone)]
+pub struct EpochMinted {
+    pub epoch: u32,
+    pub minted: i128,
+}
+
+// ── supply ───────────────────────────────────────────────────────────────────
+
+pub(crate) fn read_total_supply(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0)
+}
+
+fn write_total_supply(env: &Env, supply: i128) {
+    env.storage().instance().set(&DataKey::TotalSupply, &supply);
+}
+
+/// The hard cap on total supply, if one has been set.
+pub(crate) fn read_max_supply(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::MaxSupply)
+}
+
+/// Set the cap. It can only be lowered once set, and never below the
+/// current supply.
+pub(crate) fn write_max_supply(env: &Env, max_supply: i128) {
+    let raises = read_max_supply(env).is_some_and(|cap| max_supply > cap);
+    if raises || max_supply < read_total_supply(env) {
+        panic_with_error!(env, TokenError::InvalidMintConfig);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::MaxSupply, &max_supply);
+}
+
+pub(crate) fn record_burn(env: &Env, amount: i128) {
+    write_total_supply(env, read_total_supply(env) - amount);
+}
+
+/// Count `amount` against the cap and the current epoch, failing if either
+/// would be exceeded.
+pub(crate) fn record_mint(env: &Env, amount: i128) {
+    let supply = read_total_supply(env)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    if read_max_supply(env).is_some_and(|cap| supply > cap) {
+        panic_with_error!(env, TokenError::SupplyCapExceeded);
+    }
+
+    if let Some(schedule) = read_emission_schedule(env) {
+        let epoch = current_epoch(env, &schedule);
+        let minted = read_epoch_minted(env, epoch) + amount;
+        if minted > schedule.per_epoch {
+            panic_with_error!(env, TokenError::EpochLimitExceeded);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EpochMinted, &EpochMinted { epoch, minted });
+    }
+    write_total_supply(env, supply);
+}
+
+/// What can still be minted in total: the room under the cap and in the
+/// current epoch, whichever is smaller. `i128::MAX` when neither is set.
+pub(crate) fn remaining_supply(env: &Env) -> i128 {
+    let mut remaining = match read_max_supply(env) {
+        Some(cap) => cap - read_total_supply(env),
+        None => i128::MAX,
+    };
+    if let Some(schedule) = read_emission_schedule(env) {
+        let epoch = current_epoch(env, &schedule);
+        remaining = remaining.min(schedule.per_epoch - read_epoch_minted(env, epoch));
+    }
+    remaining
+}
+
+// ── emission schedule ────────────────────────────────────────────────────────
+
+pub(crate) fn read_emission_schedule(env: &Env) -> Option<EmissionSchedule> {
+    env.storage().instance().get(&DataKey::Emission)
+}
+
+/// Set the schedule; an `epoch_ledgers` of 0 removes it.
+pub(crate) fn write_emission_schedule(env: &Env, epoch_ledgers: u32, per_epoch: i128) {
+    if epoch_ledgers == 0 {
+        env.storage().instance().remove(&DataKey::Emission);
+        return;
+    }
+    if per_epoch < 0 {
+        panic_with_error!(env, TokenError::InvalidMintConfig);
+    }
+    env.storage().instance().set(
+        &DataKey::Emission,
+        &EmissionSchedule {
+            epoch_ledgers,
+            per_epoch,
+        },
+    );
+}
+
+pub(crate) fn current_epoch(env: &Env, schedule: &EmissionSchedule) -> u32 {
+    env.ledger().sequence() / schedule.epoch_ledgers
+}
+
+/// Amount minted in `epoch`; 0 once a later epoch has started.
+fn read_epoch_minted(env: &Env, epoch: u32) -> i128 {
+    match env
+        .storage()
+        .instance()
+        .get::<_, EpochMinted>(&DataKey::EpochMinted)
+    {
+        Some(record) if record.epoch == epoch => record.minted,
+        _ => 0,
+    }
+}
+
+// ── minters ──────────────────────────────────────────────────────────────────
+
+pub(crate) fn read_minter_allowance(env: &Env, minter: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Minter(minter.clone()))
+        .unwrap_or(0)
+}
+
+/// Set what `minter` may still mint; 0 removes the minter.
+pub(crate) fn write_minter_allowance(env: &Env, minter: &Address, allowance: i128) {
+    let key = DataKey::Minter(minter.clone());
+    if allowance == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &allowance);
+    }
+}
+
+pub(crate) fn spend_minter_allowance(env: &Env, minter: &Address, amount: i128) {
+    let allowance = read_minter_allowance(env, minter);
+    if allowance < amount {
+        panic_with_error!(env, TokenError::MinterAllowanceExceeded);
+    }
+    write_minter_allowance(env, minter, allowance - amount);
+}
@@ -0,0 +1,71 @@
+//! Per-owner index of approved spenders, so an owner can list and revoke
+//! every approval they have given.
+//!
+//! Allowances live under one temporary key per `(from, spender)` pair, and
+//! storage cannot enumerate keys. `Spenders(from)` keeps the list of
+//! spenders next to them. `approve` maintains it: a non-zero approval adds
+//! the spender, approving 0 removes it. Allowances that expire or are spent
+//! down stay listed until the index is full. The next new approval then
+//! prunes them, so `transfer_from` never pays for index upkeep.
+
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::storage::{read_allowance, DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+use crate::TokenError;
+
+/// Most spenders one owner can have listed at once. Bounds the cost of
+/// `approvals` and `revoke_all`.
+pub(crate) const MAX_SPENDERS: u32 = 20;
+
+/// Spenders `owner` has approved, oldest first. Some may have expired or
+/// been spent down since.
+pub(crate) fn read_spenders(env: &Env, owner: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Spenders(owner.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub(crate) fn write_spenders(env: &Env, owner: &Address, spenders: &Vec<Address>) {
+    let key = DataKey::Spenders(owner.clone());
+    if spenders.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, spenders);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Update the index after `owner` approved `amount` for `spender`.
+pub(crate) fn track_approval(env: &Env, owner: &Address, spender: &Address, amount: i128) {
+    let mut spenders = read_spenders(env, owner);
+    match (amount > 0, spenders.first_index_of(spender)) {
+        (true, Some(_)) | (false, None) => return,
+        (false, Some(i)) => {
+            spenders.remove(i);
+        }
+        (true, None) => {
+            if spenders.len() >= MAX_SPENDERS {
+                spenders = live_spenders(env, owner, &spenders);
+            }
+            if spenders.len() >= MAX_SPENDERS {
+                panic_with_error!(env, TokenError::TooManySpenders);
+            }
+            spenders.push_back(spender.clone());
+        }
+    }
+    write_spenders(env, owner, &spenders);
+}
+
+/// The listed spenders whose allowance is still non-zero and unexpired.
+pub(crate) fn live_spenders(env: &Env, owner: &Address, spenders: &Vec<Address>) -> Vec<Address> {
+    let mut live = Vec::new(env);
+    for spender in spenders.iter() {
+        if read_allowance(env, owner, &spender).amount > 0 {
+            live.push_back(spender);
+        }
+    }
+    live
+}
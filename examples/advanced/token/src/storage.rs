@@ -0,0 +1,181 @@
+//! Storage keys and the balance / allowance primitives the contract is built on.
+
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+
+use crate::TokenError;
+
+pub(crate) const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Instance TTL: refreshed on every call once it drops below the threshold.
+pub(crate) const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Balance TTL: refreshed whenever a balance is read or written.
+pub(crate) const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub(crate) const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AllowanceKey {
+    pub from: Address,
+    pub spender: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ActionKey {
+    pub address: Address,
+    pub sequence: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    /// Last ledger at which the allowance can be spent.
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Decimals,
+    Name,
+    Symbol,
+    /// Persistent: an account's balance.
+    Balance(Address),
+    /// Temporary: lives exactly until its `expiration_ledger`.
+    Allowance(AllowanceKey),
+    /// Per-ledger action limit; absent or 0 means unlimited.
+    ActionLimit,
+    /// Temporary: actions an address took in one ledger.
+    Actions(ActionKey),
+    /// Persistent: spenders an owner has approved, for listing and revoking.
+    Spenders(Address),
+    /// Sum of all balances: minted minus burned.
+    TotalSupply,
+    /// Hard cap on `TotalSupply`; absent means uncapped.
+    MaxSupply,
+    /// `EmissionSchedule`; absent means no per-epoch limit.
+    Emission,
+    /// `EpochMinted` for the latest epoch anything was minted in.
+    EpochMinted,
+    /// Persistent: what a minter may still mint.
+    Minter(Address),
+}
+
+pub(crate) fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// ── balances ─────────────────────────────────────────────────────────────────
+
+pub(crate) fn read_balance(env: &Env, id: &Address) -> i128 {
+    let key = DataKey::Balance(id.clone());
+    match env.storage().persistent().get::<_, i128>(&key) {
+        Some(balance) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                BALANCE_LIFETIME_THRESHOLD,
+                BALANCE_BUMP_AMOUNT,
+            );
+            balance
+        }
+        None => 0,
+    }
+}
+
+fn write_balance(env: &Env, id: &Address, amount: i128) {
+    let key = DataKey::Balance(id.clone());
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub(crate) fn receive_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = read_balance(env, id);
+    let new_balance = balance
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    write_balance(env, id, new_balance);
+}
+
+pub(crate) fn spend_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = read_balance(env, id);
+    if balance < amount {
+        panic_with_error!(env, TokenError::InsufficientBalance);
+    }
+    write_balance(env, id, balance - amount);
+}
+
+// ── allowances ───────────────────────────────────────────────────────────────
+
+/// The allowance as it stands now; expired allowances read as zero.
+pub(crate) fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let key = DataKey::Allowance(AllowanceKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    match env.storage().temporary().get::<_, AllowanceValue>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        Some(allowance) => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+/// Store an allowance and set the entry's TTL to its expiration, so the
+/// network deletes it once it can no longer be spent.
+pub(crate) fn write_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    if amount > 0 && expiration_ledger < env.ledger().sequence() {
+        panic_with_error!(env, TokenError::InvalidExpiration);
+    }
+
+    let key = DataKey::Allowance(AllowanceKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    let allowance = AllowanceValue {
+        amount,
+        expiration_ledger,
+    };
+    env.storage().temporary().set(&key, &allowance);
+
+    if amount > 0 {
+        let live_for = expiration_ledger - env.ledger().sequence();
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, live_for, live_for);
+    }
+}
+
+pub(crate) fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(env, TokenError::InsufficientAllowance);
+    }
+    if amount > 0 {
+        write_allowance(
+            env,
+            from,
+            spender,
+            allowance.amount - amount,
+            allowance.expiration_ledger,
+        );
+    }
+}
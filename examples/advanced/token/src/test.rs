@@ -0,0 +1,673 @@
+#![cfg(test)]
+
+use super::*;
+use cookbook_testing::{assert_conserved, Balances};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
+    token::TokenClient,
+    Address, Env, Error, IntoVal, String, Symbol,
+};
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    /// Admin-side client generated for this contract.
+    contract: TokenContractClient<'static>,
+    /// The standard SEP-41 client any wallet or contract would use.
+    token: TokenClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, TokenContract);
+    let contract = TokenContractClient::new(&env, &id);
+    let admin = Address::generate(&env);
+    contract.initialize(
+        &admin,
+        &7,
+        &String::from_str(&env, "Cookbook Dollar"),
+        &String::from_str(&env, "CBD"),
+    );
+    Setup {
+        token: TokenClient::new(&env, &id),
+        env,
+        admin,
+        contract,
+    }
+}
+
+fn contract_error(err: TokenError) -> Error {
+    Error::from_contract_error(err as u32)
+}
+
+// ── metadata & admin ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_metadata() {
+    let s = setup();
+    assert_eq!(s.token.decimals(), 7);
+    assert_eq!(s.token.name(), String::from_str(&s.env, "Cookbook Dollar"));
+    assert_eq!(s.token.symbol(), String::from_str(&s.env, "CBD"));
+    assert_eq!(s.contract.admin(), s.admin);
+}
+
+#[test]
+fn test_initialize_only_once() {
+    let s = setup();
+    let result = s.contract.try_initialize(
+        &s.admin,
+        &7,
+        &String::from_str(&s.env, "Again"),
+        &String::from_str(&s.env, "AGN"),
+    );
+    assert_eq!(
+        result,
+        Err(Ok(contract_error(TokenError::AlreadyInitialized)))
+    );
+}
+
+#[test]
+fn test_mint_requires_admin_auth() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    s.contract.mint(&user, &1_000);
+
+    assert_eq!(
+        s.env.auths(),
+        [(
+            s.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    s.contract.address.clone(),
+                    symbol_short!("mint"),
+                    (&user, 1_000_i128).into_val(&s.env),
+                )),
+                sub_invocations: [].into(),
+            }
+        )]
+    );
+    assert_eq!(s.token.balance(&user), 1_000);
+}
+
+#[test]
+fn test_set_admin_moves_minting_rights() {
+    let s = setup();
+    let new_admin = Address::generate(&s.env);
+    s.contract.set_admin(&new_admin);
+    assert_eq!(s.contract.admin(), new_admin);
+
+    let user = Address::generate(&s.env);
+    s.contract.mint(&user, &5);
+    assert_eq!(s.env.auths()[0].0, new_admin);
+}
+
+// ── transfers ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_transfer() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &1_000);
+
+    s.token.transfer(&alice, &bob, &300);
+    assert_eq!(s.env.auths()[0].0, alice, "sender authorizes");
+    assert_eq!(s.token.balance(&alice), 700);
+    assert_eq!(s.token.balance(&bob), 300);
+
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("transfer"), alice, bob).into_val(&s.env)
+    );
+    let amount: i128 = data.into_val(&s.env);
+    assert_eq!(amount, 300);
+}
+
+#[test]
+fn test_transfer_insufficient_balance() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    assert_eq!(
+        s.token
+            .try_transfer(&alice, &Address::generate(&s.env), &101),
+        Err(Ok(contract_error(TokenError::InsufficientBalance)))
+    );
+}
+
+#[test]
+fn test_negative_amounts_rejected() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let negative = Err(Ok(contract_error(TokenError::NegativeAmount)));
+    assert_eq!(s.token.try_transfer(&alice, &bob, &-1), negative);
+    assert_eq!(s.token.try_approve(&alice, &bob, &-1, &100), negative);
+    assert_eq!(s.contract.try_mint(&alice, &-1), negative);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_transfer_requires_sender_auth() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    s.env.set_auths(&[]);
+    s.token.transfer(&alice, &Address::generate(&s.env), &1);
+}
+
+// ── allowances ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &1_000);
+
+    let expiration = s.env.ledger().sequence() + 200;
+    s.token.approve(&alice, &spender, &500, &expiration);
+    assert_eq!(s.token.allowance(&alice, &spender), 500);
+
+    s.token.transfer_from(&spender, &alice, &bob, &200);
+    assert_eq!(s.env.auths()[0].0, spender, "spender authorizes");
+    assert_eq!(s.token.allowance(&alice, &spender), 300);
+    assert_eq!(s.token.balance(&alice), 800);
+    assert_eq!(s.token.balance(&bob), 200);
+
+    assert_eq!(
+        s.token.try_transfer_from(&spender, &alice, &bob, &301),
+        Err(Ok(contract_error(TokenError::InsufficientAllowance)))
+    );
+}
+
+#[test]
+fn test_allowance_expires() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+    s.contract.mint(&alice, &1_000);
+
+    let expiration = s.env.ledger().sequence() + 10;
+    s.token.approve(&alice, &spender, &500, &expiration);
+
+    // Spendable through its expiration ledger...
+    s.env.ledger().with_mut(|l| l.sequence_number = expiration);
+    assert_eq!(s.token.allowance(&alice, &spender), 500);
+
+    // ...and worth nothing after it.
+    s.env
+        .ledger()
+        .with_mut(|l| l.sequence_number = expiration + 1);
+    assert_eq!(s.token.allowance(&alice, &spender), 0);
+    assert_eq!(
+        s.token.try_transfer_from(&spender, &alice, &spender, &1),
+        Err(Ok(contract_error(TokenError::InsufficientAllowance)))
+    );
+}
+
+#[test]
+fn test_approve_with_past_expiration_rejected() {
+    let s = setup();
+    s.env.ledger().with_mut(|l| l.sequence_number = 100);
+    let alice = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+
+    assert_eq!(
+        s.token.try_approve(&alice, &spender, &10, &99),
+        Err(Ok(contract_error(TokenError::InvalidExpiration)))
+    );
+    // Clearing an allowance never needs a future expiration.
+    s.token.approve(&alice, &spender, &0, &0);
+    assert_eq!(s.token.allowance(&alice, &spender), 0);
+}
+
+#[test]
+fn test_approve_event() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+    s.token.approve(&alice, &spender, &42, &1_000);
+
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("approve"), alice, spender).into_val(&s.env)
+    );
+    let value: (i128, u32) = data.into_val(&s.env);
+    assert_eq!(value, (42, 1_000));
+}
+
+// ── burning ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_burn_and_burn_from() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+    s.contract.mint(&alice, &1_000);
+
+    s.token.burn(&alice, &100);
+    assert_eq!(s.token.balance(&alice), 900);
+
+    s.token.approve(&alice, &spender, &50, &1_000);
+    s.token.burn_from(&spender, &alice, &50);
+    assert_eq!(s.token.balance(&alice), 850);
+    assert_eq!(s.token.allowance(&alice, &spender), 0);
+
+    assert_eq!(
+        s.token.try_burn(&alice, &851),
+        Err(Ok(contract_error(TokenError::InsufficientBalance)))
+    );
+}
+
+// ── conservation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_moving_tokens_conserves_supply() {
+    let s = setup();
+    let [alice, bob, spender] = [0; 3].map(|_| Address::generate(&s.env));
+    s.contract.mint(&alice, &1_000);
+    s.token.approve(&alice, &spender, &500, &1_000);
+
+    assert_conserved!(s.env, s.token.address, [alice, bob, spender], {
+        s.token.transfer(&alice, &bob, &300);
+        s.token.transfer(&bob, &bob, &100);
+        s.token.transfer_from(&spender, &alice, &spender, &250);
+        // A failed transfer moves nothing.
+        let _ = s.token.try_transfer(&bob, &alice, &301);
+    });
+
+    // Burning is the one operation that shrinks the total, by exactly the
+    // amount burned.
+    let accounts = [&alice, &bob, &spender];
+    let before = Balances::of_token(&s.env, &s.token.address, &accounts);
+    s.token.burn(&bob, &100);
+    let after = Balances::of_token(&s.env, &s.token.address, &accounts);
+    assert_eq!(before.total() - after.total(), 100);
+}
+
+// ── anti-bot mode ────────────────────────────────────────────────────────────
+
+fn next_ledger(env: &Env) {
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+}
+
+#[test]
+fn test_action_limit_off_by_default() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    for _ in 0..10 {
+        s.token.transfer(&alice, &Address::generate(&s.env), &1);
+    }
+    assert_eq!(s.contract.action_limit(), 0);
+    assert_eq!(s.contract.actions_this_ledger(&alice), 0);
+}
+
+#[test]
+fn test_action_limit_caps_transfers_per_ledger() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    s.contract.set_action_limit(&2);
+
+    s.token.transfer(&alice, &bob, &1);
+    s.token.transfer(&alice, &bob, &1);
+    assert_eq!(s.contract.actions_this_ledger(&alice), 2);
+    assert_eq!(
+        s.token.try_transfer(&alice, &bob, &1),
+        Err(Ok(contract_error(TokenError::ActionLimitExceeded)))
+    );
+
+    // Other addresses have their own counters.
+    s.token.transfer(&bob, &alice, &1);
+}
+
+#[test]
+fn test_action_limit_resets_each_ledger() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    s.contract.set_action_limit(&1);
+
+    s.token.transfer(&alice, &bob, &1);
+    assert!(s.token.try_transfer(&alice, &bob, &1).is_err());
+
+    next_ledger(&s.env);
+    assert_eq!(s.contract.actions_this_ledger(&alice), 0);
+    s.token.transfer(&alice, &bob, &1);
+    assert!(s.token.try_transfer(&alice, &bob, &1).is_err());
+
+    next_ledger(&s.env);
+    s.token.transfer(&alice, &bob, &1);
+    assert_eq!(s.token.balance(&bob), 3);
+}
+
+#[test]
+fn test_action_limit_counts_spender_on_transfer_from() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let spender = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    s.token.approve(&alice, &spender, &100, &1_000);
+    s.contract.set_action_limit(&1);
+
+    s.token.transfer_from(&spender, &alice, &bob, &1);
+    assert_eq!(s.contract.actions_this_ledger(&spender), 1);
+    assert_eq!(s.contract.actions_this_ledger(&alice), 0);
+    assert_eq!(
+        s.token.try_transfer_from(&spender, &alice, &bob, &1),
+        Err(Ok(contract_error(TokenError::ActionLimitExceeded)))
+    );
+
+    // The owner's own transfers are still allowed.
+    s.token.transfer(&alice, &bob, &1);
+}
+
+#[test]
+fn test_action_limit_can_be_turned_off() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &100);
+    s.contract.set_action_limit(&1);
+    s.token.transfer(&alice, &bob, &1);
+
+    s.contract.set_action_limit(&0);
+    s.token.transfer(&alice, &bob, &1);
+    s.token.transfer(&alice, &bob, &1);
+    assert_eq!(s.token.balance(&bob), 3);
+}
+
+#[test]
+fn test_set_action_limit_requires_admin_auth() {
+    let s = setup();
+    s.contract.set_action_limit(&3);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("act_limit"), s.admin.clone()).into_val(&s.env)
+    );
+    let limit: u32 = data.into_val(&s.env);
+    assert_eq!(limit, 3);
+}
+
+// ── approval registry ────────────────────────────────────────────────────────
+
+#[test]
+fn test_approvals_lists_live_allowances() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let dex = Address::generate(&s.env);
+    let game = Address::generate(&s.env);
+    let old = Address::generate(&s.env);
+    let sequence = s.env.ledger().sequence();
+
+    s.token.approve(&alice, &dex, &500, &(sequence + 200));
+    s.token.approve(&alice, &game, &20, &(sequence + 200));
+    s.token.approve(&alice, &old, &5, &(sequence + 10));
+    // Re-approving updates the amount without listing the spender twice.
+    s.token.approve(&alice, &dex, &300, &(sequence + 200));
+    assert_eq!(s.contract.approvals(&alice).len(), 3);
+    assert_eq!(
+        s.contract.approvals(&alice).get(dex.clone()),
+        Some(AllowanceValue {
+            amount: 300,
+            expiration_ledger: sequence + 200,
+        })
+    );
+
+    // Approving 0 removes the spender; expired allowances are not shown.
+    s.token.approve(&alice, &game, &0, &0);
+    s.env
+        .ledger()
+        .with_mut(|l| l.sequence_number = sequence + 11);
+    let approvals = s.contract.approvals(&alice);
+    assert_eq!(approvals.keys(), soroban_sdk::vec![&s.env, dex]);
+}
+
+#[test]
+fn test_revoke_all_zeroes_every_allowance() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let dex = Address::generate(&s.env);
+    let game = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.contract.mint(&alice, &1_000);
+    let expiration = s.env.ledger().sequence() + 200;
+    s.token.approve(&alice, &dex, &500, &expiration);
+    s.token.approve(&alice, &game, &20, &expiration);
+    s.token.transfer_from(&game, &alice, &bob, &20);
+
+    // The spent-down allowance is not counted as revoked.
+    assert_eq!(s.contract.revoke_all(&alice), 1);
+    assert_eq!(s.env.auths()[0].0, alice, "owner authorizes");
+    assert!(s.contract.approvals(&alice).is_empty());
+    assert_eq!(s.token.allowance(&alice, &dex), 0);
+    assert_eq!(
+        s.token.try_transfer_from(&dex, &alice, &bob, &1),
+        Err(Ok(contract_error(TokenError::InsufficientAllowance)))
+    );
+
+    // Indexers see the usual approve event, with amount 0.
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("approve"), alice.clone(), dex).into_val(&s.env)
+    );
+    let value: (i128, u32) = data.into_val(&s.env);
+    assert_eq!(value, (0, s.env.ledger().sequence()));
+
+    assert_eq!(s.contract.revoke_all(&alice), 0);
+}
+
+#[test]
+fn test_spender_index_is_capped() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let expiration = s.env.ledger().sequence() + 200;
+    let mut first = None;
+    for _ in 0..spenders::MAX_SPENDERS {
+        let spender = Address::generate(&s.env);
+        s.token.approve(&alice, &spender, &1, &expiration);
+        first.get_or_insert(spender);
+    }
+
+    let extra = Address::generate(&s.env);
+    assert_eq!(
+        s.token.try_approve(&alice, &extra, &1, &expiration),
+        Err(Ok(contract_error(TokenError::TooManySpenders)))
+    );
+
+    // Once an allowance is spent down, a full index makes room by pruning.
+    let first = first.unwrap();
+    s.contract.mint(&alice, &1);
+    s.token.transfer_from(&first, &alice, &first, &1);
+    s.token.approve(&alice, &extra, &1, &expiration);
+    assert_eq!(s.contract.approvals(&alice).len(), spenders::MAX_SPENDERS);
+    assert!(s.contract.approvals(&alice).contains_key(extra));
+}
+
+// ── minting controls ─────────────────────────────────────────────────────────
+
+fn set_sequence(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|l| l.sequence_number = sequence);
+}
+
+#[test]
+fn test_minter_spends_its_allowance() {
+    let s = setup();
+    let minter = Address::generate(&s.env);
+    let user = Address::generate(&s.env);
+    s.contract.set_minter(&minter, &500);
+
+    s.contract.mint_from(&minter, &user, &300);
+    assert_eq!(s.token.balance(&user), 300);
+    assert_eq!(s.contract.minter_allowance(&minter), 200);
+    assert_eq!(s.contract.remaining_mintable(&minter), 200);
+    assert_eq!(
+        s.contract.try_mint_from(&minter, &user, &201),
+        Err(Ok(contract_error(TokenError::MinterAllowanceExceeded)))
+    );
+
+    // Removing the minter leaves it nothing.
+    s.contract.set_minter(&minter, &0);
+    assert_eq!(
+        s.contract.try_mint_from(&minter, &user, &1),
+        Err(Ok(contract_error(TokenError::MinterAllowanceExceeded)))
+    );
+    let stranger = Address::generate(&s.env);
+    assert_eq!(s.contract.remaining_mintable(&stranger), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_mint_from_requires_minter_auth() {
+    let s = setup();
+    let minter = Address::generate(&s.env);
+    s.contract.set_minter(&minter, &500);
+    s.env.set_auths(&[]);
+    s.contract
+        .mint_from(&minter, &Address::generate(&s.env), &1);
+}
+
+#[test]
+fn test_supply_cap_applies_to_every_mint() {
+    let s = setup();
+    let minter = Address::generate(&s.env);
+    let user = Address::generate(&s.env);
+    s.contract.set_minter(&minter, &10_000);
+    s.contract.set_max_supply(&1_000);
+
+    s.contract.mint(&user, &600);
+    s.contract.mint_from(&minter, &user, &300);
+    assert_eq!(s.contract.total_supply(), 900);
+    assert_eq!(s.contract.remaining_mintable(&s.admin), 100);
+    assert_eq!(s.contract.remaining_mintable(&minter), 100);
+    assert_eq!(
+        s.contract.try_mint(&user, &101),
+        Err(Ok(contract_error(TokenError::SupplyCapExceeded)))
+    );
+    assert_eq!(
+        s.contract.try_mint_from(&minter, &user, &101),
+        Err(Ok(contract_error(TokenError::SupplyCapExceeded)))
+    );
+    // The failed mint_from did not spend the allowance.
+    assert_eq!(s.contract.minter_allowance(&minter), 9_700);
+
+    // Burning makes room again.
+    s.token.burn(&user, &400);
+    assert_eq!(s.contract.total_supply(), 500);
+    s.contract.mint(&user, &500);
+}
+
+#[test]
+fn test_supply_cap_can_only_be_lowered() {
+    let s = setup();
+    s.contract.mint(&Address::generate(&s.env), &400);
+    assert_eq!(s.contract.max_supply(), None);
+
+    s.contract.set_max_supply(&1_000);
+    s.contract.set_max_supply(&800);
+    assert_eq!(s.contract.max_supply(), Some(800));
+    assert_eq!(
+        s.contract.try_set_max_supply(&900),
+        Err(Ok(contract_error(TokenError::InvalidMintConfig)))
+    );
+    assert_eq!(
+        s.contract.try_set_max_supply(&399),
+        Err(Ok(contract_error(TokenError::InvalidMintConfig)))
+    );
+}
+
+#[test]
+fn test_emission_limits_each_epoch() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    set_sequence(&s.env, 1_000);
+    s.contract.set_emission(&100, &500);
+    assert_eq!(
+        s.contract.emission(),
+        Some(EmissionSchedule {
+            epoch_ledgers: 100,
+            per_epoch: 500,
+        })
+    );
+
+    // Epoch 10 covers ledgers 1_000..1_100.
+    s.contract.mint(&user, &300);
+    set_sequence(&s.env, 1_099);
+    s.contract.mint(&user, &200);
+    assert_eq!(s.contract.remaining_mintable(&s.admin), 0);
+    assert_eq!(
+        s.contract.try_mint(&user, &1),
+        Err(Ok(contract_error(TokenError::EpochLimitExceeded)))
+    );
+
+    // Ledger 1_100 starts epoch 11 with a fresh budget.
+    set_sequence(&s.env, 1_100);
+    assert_eq!(s.contract.remaining_mintable(&s.admin), 500);
+    s.contract.mint(&user, &500);
+
+    // Skipping epochs does not bank their budgets.
+    set_sequence(&s.env, 1_450);
+    assert_eq!(s.contract.remaining_mintable(&s.admin), 500);
+    assert_eq!(
+        s.contract.try_mint(&user, &501),
+        Err(Ok(contract_error(TokenError::EpochLimitExceeded)))
+    );
+    assert_eq!(s.token.balance(&user), 1_000);
+}
+
+#[test]
+fn test_remaining_mintable_takes_the_tightest_limit() {
+    let s = setup();
+    let minter = Address::generate(&s.env);
+    let user = Address::generate(&s.env);
+    assert_eq!(s.contract.remaining_mintable(&s.admin), i128::MAX);
+
+    s.contract.set_minter(&minter, &400);
+    s.contract.set_max_supply(&1_000);
+    s.contract.set_emission(&100, &300);
+    assert_eq!(s.contract.remaining_mintable(&minter), 300);
+
+    s.contract.mint(&user, &250);
+    assert_eq!(s.contract.remaining_mintable(&minter), 50);
+
+    set_sequence(&s.env, s.env.ledger().sequence() + 100);
+    assert_eq!(s.contract.remaining_mintable(&minter), 300);
+    s.contract.mint_from(&minter, &user, &300);
+    assert_eq!(s.contract.remaining_mintable(&minter), 0);
+
+    // Without a schedule the minter's allowance of 100 is the limit.
+    s.contract.set_emission(&0, &0);
+    assert_eq!(s.contract.emission(), None);
+    assert_eq!(s.contract.remaining_mintable(&minter), 100);
+    assert_eq!(s.contract.remaining_mintable(&s.admin), 450);
+}
+
+#[test]
+fn test_set_minter_requires_admin_auth() {
+    let s = setup();
+    let minter = Address::generate(&s.env);
+    s.contract.set_minter(&minter, &250);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+
+    let (_id, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&s.env, "set_minter"), s.admin.clone(), minter).into_val(&s.env)
+    );
+    let allowance: i128 = data.into_val(&s.env);
+    assert_eq!(allowance, 250);
+}
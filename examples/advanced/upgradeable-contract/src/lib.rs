@@ -0,0 +1,121 @@
+//! # Upgradeable Contract — v1
+//!
+//! `env.deployer().update_current_contract_wasm(hash)` swaps the code behind
+//! a contract address in place. The address, its token balances and **all of
+//! its storage** (instance, persistent and temporary) stay as they are; only
+//! the Wasm changes.
+//!
+//! That makes two things the upgrade author's job:
+//!
+//! 1. **Gate `upgrade()`.** Whoever can call it controls every future line of
+//!    code at this address. Here it requires the admin's authorization.
+//! 2. **Stay compatible with existing data.** The new Wasm reads entries the
+//!    old one wrote. Keys and types it keeps must decode the same way; layouts
+//!    it changes need an explicit migration step (see `v2/`).
+//!
+//! This v1 stores:
+//!
+//! | Key              | Storage    | Type  | v2 change                      |
+//! |------------------|------------|-------|--------------------------------|
+//! | `Admin`          | instance   | Address | unchanged                    |
+//! | `WasmHash`       | instance   | BytesN<32> | unchanged                 |
+//! | `Counter`        | instance   | `u32` | migrated to `u64` `Calls`      |
+//! | `Balance(user)`  | persistent | `i128`| unchanged                      |
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+
+/// Version reported by this Wasm.
+pub const VERSION: u32 = 1;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Hash of the Wasm most recently installed through `upgrade`.
+    WasmHash,
+    /// Number of deposits handled.
+    Counter,
+    Balance(Address),
+}
+
+#[contract]
+pub struct UpgradeableContract;
+
+#[contractimpl]
+impl UpgradeableContract {
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    pub fn deposit(env: Env, user: Address, amount: i128) -> i128 {
+        user.require_auth();
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let balance = Self::balance(env.clone(), user.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user), &balance);
+
+        let count: u32 = env.storage().instance().get(&DataKey::Counter).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::Counter, &(count + 1));
+        balance
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    pub fn counter(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Counter).unwrap_or(0)
+    }
+
+    // ==================== UPGRADE ====================
+
+    /// Replace this contract's Wasm with `new_wasm_hash`. Admin only.
+    ///
+    /// The Wasm must already be uploaded to the network. The new code takes
+    /// effect once this invocation returns.
+    ///
+    /// Emits `(upgraded,)` with the new hash.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::WasmHash, &new_wasm_hash);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+    }
+
+    /// Hash of the Wasm installed by the last `upgrade`, if any.
+    pub fn wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::WasmHash)
+    }
+
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Not initialized")
+    }
+
+    pub fn version() -> u32 {
+        VERSION
+    }
+}
+
+mod test;
@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+fn setup() -> (Env, Address, UpgradeableContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, UpgradeableContract);
+    let client = UpgradeableContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, admin, client)
+}
+
+#[test]
+fn test_initialize() {
+    let (_env, admin, client) = setup();
+    assert_eq!(client.admin(), admin);
+    assert_eq!(client.version(), 1);
+    assert_eq!(client.wasm_hash(), None);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice() {
+    let (env, _admin, client) = setup();
+    client.initialize(&Address::generate(&env));
+}
+
+#[test]
+fn test_deposit_updates_both_storage_types() {
+    let (env, _admin, client) = setup();
+    let user = Address::generate(&env);
+    assert_eq!(client.deposit(&user, &100), 100);
+    assert_eq!(client.deposit(&user, &50), 150);
+    assert_eq!(client.balance(&user), 150);
+    assert_eq!(client.counter(), 2);
+}
+
+#[test]
+fn test_upgrade_to_unknown_wasm_fails_after_auth() {
+    let (env, _admin, client) = setup();
+    // The admin check passes; the host then rejects the hash because no such
+    // Wasm was uploaded. The full upgrade runs in `tests/integration`.
+    let result = client.try_upgrade(&BytesN::from_array(&env, &[9; 32]));
+    assert!(result.is_err());
+    assert_eq!(client.wasm_hash(), None, "failed upgrade leaves no trace");
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_upgrade_requires_admin_auth() {
+    let (env, _admin, client) = setup();
+    env.set_auths(&[]);
+    client.upgrade(&BytesN::from_array(&env, &[0; 32]));
+}
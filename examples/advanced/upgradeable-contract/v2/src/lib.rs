@@ -0,0 +1,135 @@
+//! # Upgradeable Contract — v2
+//!
+//! The Wasm that `UpgradeableContract` v1 is upgraded to. Everything v1
+//! wrote is still in storage after the upgrade, so v2:
+//!
+//! - keeps `Admin`, `WasmHash` and `Balance(user)` with the same keys and
+//!   types — balances need no migration at all;
+//! - changes the deposit counter from `Counter: u32` to `Calls: u64`, and
+//!   ships a one-time, admin-only `migrate()` that converts it;
+//! - records the storage layout in `SchemaVersion` so `migrate()` cannot run
+//!   twice and new code can refuse to run on unmigrated state.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+
+/// Version reported by this Wasm.
+pub const VERSION: u32 = 2;
+
+/// Storage layout this Wasm expects.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Keys shared with v1 keep their position and types so existing entries
+/// decode unchanged. New keys are appended.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    WasmHash,
+    /// v1 layout, removed by `migrate`.
+    Counter,
+    Balance(Address),
+    /// v2 replacement for `Counter`.
+    Calls,
+    SchemaVersion,
+}
+
+#[contract]
+pub struct UpgradeableContract;
+
+#[contractimpl]
+impl UpgradeableContract {
+    /// Convert v1 state to the v2 layout. Admin only, and only once.
+    ///
+    /// Emits `(migrated,)` with the number of calls carried over.
+    pub fn migrate(env: Env) -> u64 {
+        Self::admin(env.clone()).require_auth();
+        if Self::schema_version(env.clone()) >= SCHEMA_VERSION {
+            panic!("Already migrated");
+        }
+
+        let old: u32 = env.storage().instance().get(&DataKey::Counter).unwrap_or(0);
+        let calls = old as u64;
+        env.storage().instance().remove(&DataKey::Counter);
+        env.storage().instance().set(&DataKey::Calls, &calls);
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish((symbol_short!("migrated"),), calls);
+        calls
+    }
+
+    pub fn deposit(env: Env, user: Address, amount: i128) -> i128 {
+        Self::require_migrated(&env);
+        user.require_auth();
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let balance = Self::balance(env.clone(), user.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user), &balance);
+
+        let calls = Self::calls(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::Calls, &calls);
+        balance
+    }
+
+    pub fn balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    pub fn calls(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Calls).unwrap_or(0)
+    }
+
+    /// Storage layout version; `1` until `migrate` has run.
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(1)
+    }
+
+    /// Replace this contract's Wasm with `new_wasm_hash`. Admin only.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::WasmHash, &new_wasm_hash);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+    }
+
+    pub fn wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::WasmHash)
+    }
+
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Not initialized")
+    }
+
+    pub fn version() -> u32 {
+        VERSION
+    }
+
+    fn require_migrated(env: &Env) {
+        if Self::schema_version(env.clone()) < SCHEMA_VERSION {
+            panic!("Migration pending");
+        }
+    }
+}
+
+mod test;
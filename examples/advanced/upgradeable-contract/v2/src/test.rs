@@ -0,0 +1,67 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+/// Register v2 over storage that looks like v1 left it.
+fn setup_with_v1_state(counter: u32) -> (Env, Address, UpgradeableContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, UpgradeableContract);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Counter, &counter);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone()), &150i128);
+    });
+    (
+        env.clone(),
+        user,
+        UpgradeableContractClient::new(&env, &contract_id),
+    )
+}
+
+#[test]
+fn test_migrate_converts_counter() {
+    let (env, user, client) = setup_with_v1_state(7);
+    assert_eq!(client.schema_version(), 1);
+
+    assert_eq!(client.migrate(), 7);
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    assert_eq!(client.calls(), 7);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&DataKey::Counter));
+    });
+
+    // Unchanged keys need no migration.
+    assert_eq!(client.balance(&user), 150);
+    client.deposit(&user, &50);
+    assert_eq!(client.balance(&user), 200);
+    assert_eq!(client.calls(), 8);
+}
+
+#[test]
+#[should_panic(expected = "Migration pending")]
+fn test_deposit_blocked_until_migrated() {
+    let (_env, user, client) = setup_with_v1_state(1);
+    client.deposit(&user, &1);
+}
+
+#[test]
+#[should_panic(expected = "Already migrated")]
+fn test_migrate_only_once() {
+    let (_env, _user, client) = setup_with_v1_state(1);
+    client.migrate();
+    client.migrate();
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_migrate_requires_admin_auth() {
+    let (env, _user, client) = setup_with_v1_state(1);
+    env.set_auths(&[]);
+    client.migrate();
+}
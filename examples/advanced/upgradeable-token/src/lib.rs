@@ -0,0 +1,244 @@
+//! # Upgradeable Token — v1
+//!
+//! A small token whose code can be replaced in place with
+//! `update_current_contract_wasm`, combining the `token` and
+//! `upgradeable-contract` recipes. Holders keep their balances across an
+//! upgrade because storage is never touched by the code swap: the new Wasm
+//! simply reads the same entries.
+//!
+//! Two rules keep that true:
+//!
+//! 1. **A stable layout.** v2 (`v2/`) keeps every v1 `DataKey` variant in
+//!    the same position with the same payload, and every stored type
+//!    unchanged. New keys are appended.
+//! 2. **Wasm-hash gating.** `upgrade(hash)` only installs a hash the admin
+//!    approved earlier with `approve_wasm`. The approval is public (it
+//!    emits an event), so holders see what is coming before it lands, and
+//!    each approval is used up by the upgrade it allows.
+//!
+//! This v1 stores:
+//!
+//! | Key                 | Storage    | Type         | v2 change |
+//! |---------------------|------------|--------------|-----------|
+//! | `Admin`             | instance   | `Address`    | unchanged |
+//! | `Metadata`          | instance   | `Metadata`   | unchanged |
+//! | `TotalSupply`       | instance   | `i128`       | unchanged; `burn` lowers it |
+//! | `WasmHash`          | instance   | `BytesN<32>` | unchanged |
+//! | `ApprovedWasm(hash)`| persistent | `bool`       | unchanged |
+//! | `Balance(id)`       | persistent | `i128`       | unchanged |
+//!
+//! v1 has no `burn` and charges no fees; v2 adds both.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String,
+};
+
+/// Version reported by this Wasm.
+pub const VERSION: u32 = 1;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Metadata,
+    TotalSupply,
+    /// Hash of the Wasm most recently installed through `upgrade`.
+    WasmHash,
+    /// A hash the admin has approved for the next `upgrade`.
+    ApprovedWasm(BytesN<32>),
+    Balance(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    /// `upgrade` was called with a hash that `approve_wasm` did not approve.
+    WasmNotApproved = 5,
+}
+
+#[contract]
+pub struct UpgradeableToken;
+
+#[contractimpl]
+impl UpgradeableToken {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        decimals: u32,
+        name: String,
+        symbol: String,
+    ) -> Result<(), TokenError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(TokenError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(
+            &DataKey::Metadata,
+            &Metadata {
+                decimals,
+                name,
+                symbol,
+            },
+        );
+        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+        Ok(())
+    }
+
+    // ==================== TOKEN ====================
+
+    /// Create `amount` new tokens for `to`. Admin only.
+    ///
+    /// Emits `(mint, admin, to)` with the amount.
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+        check_amount(amount)?;
+
+        set_balance(&env, &to, Self::balance(env.clone(), to.clone()) + amount);
+        let supply = Self::total_supply(env.clone()) + amount;
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+
+        env.events()
+            .publish((symbol_short!("mint"), admin, to), amount);
+        Ok(())
+    }
+
+    /// Emits `(transfer, from, to)` with the amount.
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        check_amount(amount)?;
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+
+        set_balance(&env, &from, from_balance - amount);
+        set_balance(&env, &to, Self::balance(env.clone(), to.clone()) + amount);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+        Ok(())
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    pub fn metadata(env: Env) -> Result<Metadata, TokenError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Metadata)
+            .ok_or(TokenError::NotInitialized)
+    }
+
+    // ==================== UPGRADE ====================
+
+    /// Approve `hash` for a later `upgrade`. Admin only.
+    ///
+    /// Emits `(wasm_appr,)` with the hash, so the pending code is public
+    /// before it is installed.
+    pub fn approve_wasm(env: Env, hash: BytesN<32>) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ApprovedWasm(hash.clone()), &true);
+        env.events().publish((symbol_short!("wasm_appr"),), hash);
+        Ok(())
+    }
+
+    /// Withdraw an approval that has not been used yet. Admin only.
+    pub fn revoke_wasm(env: Env, hash: BytesN<32>) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ApprovedWasm(hash));
+        Ok(())
+    }
+
+    pub fn is_wasm_approved(env: Env, hash: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::ApprovedWasm(hash))
+    }
+
+    /// Replace this contract's Wasm with `new_wasm_hash`. Admin only, and
+    /// only for a hash approved with `approve_wasm`; the approval is used
+    /// up.
+    ///
+    /// The Wasm must already be uploaded to the network. The new code takes
+    /// effect once this invocation returns.
+    ///
+    /// Emits `(upgraded,)` with the new hash.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        let approval = DataKey::ApprovedWasm(new_wasm_hash.clone());
+        if !env.storage().persistent().has(&approval) {
+            return Err(TokenError::WasmNotApproved);
+        }
+
+        env.storage().persistent().remove(&approval);
+        env.storage()
+            .instance()
+            .set(&DataKey::WasmHash, &new_wasm_hash);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+        Ok(())
+    }
+
+    /// Hash of the Wasm installed by the last `upgrade`, if any.
+    pub fn wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::WasmHash)
+    }
+
+    pub fn admin(env: Env) -> Result<Address, TokenError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)
+    }
+
+    pub fn version() -> u32 {
+        VERSION
+    }
+}
+
+fn set_balance(env: &Env, id: &Address, balance: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &balance);
+}
+
+fn check_amount(amount: i128) -> Result<(), TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    Ok(())
+}
+
+mod test;
@@ -0,0 +1,115 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+struct Setup {
+    env: Env,
+    client: UpgradeableTokenClient<'static>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = UpgradeableTokenClient::new(&env, &env.register_contract(None, UpgradeableToken));
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &7,
+        &String::from_str(&env, "Cookbook"),
+        &String::from_str(&env, "COOK"),
+    );
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        env,
+        client,
+        admin,
+    }
+}
+
+#[test]
+fn test_initialize() {
+    let t = setup();
+    assert_eq!(t.client.admin(), t.admin);
+    assert_eq!(t.client.version(), 1);
+    assert_eq!(t.client.metadata().decimals, 7);
+    assert_eq!(t.client.wasm_hash(), None);
+    assert_eq!(
+        t.client.try_initialize(
+            &t.admin,
+            &7,
+            &String::from_str(&t.env, "Cookbook"),
+            &String::from_str(&t.env, "COOK"),
+        ),
+        Err(Ok(TokenError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_mint_and_transfer() {
+    let t = setup();
+    t.client.mint(&t.alice, &1_000);
+    t.client.transfer(&t.alice, &t.bob, &400);
+
+    assert_eq!(t.client.balance(&t.alice), 600);
+    assert_eq!(t.client.balance(&t.bob), 400);
+    assert_eq!(t.client.total_supply(), 1_000);
+    assert_eq!(
+        t.client.try_transfer(&t.alice, &t.bob, &601),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+    assert_eq!(
+        t.client.try_transfer(&t.alice, &t.bob, &0),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_upgrade_requires_approved_hash() {
+    let t = setup();
+    let hash = BytesN::from_array(&t.env, &[9; 32]);
+    assert_eq!(
+        t.client.try_upgrade(&hash),
+        Err(Ok(TokenError::WasmNotApproved))
+    );
+
+    t.client.approve_wasm(&hash);
+    assert!(t.client.is_wasm_approved(&hash));
+    t.client.revoke_wasm(&hash);
+    assert_eq!(
+        t.client.try_upgrade(&hash),
+        Err(Ok(TokenError::WasmNotApproved))
+    );
+}
+
+#[test]
+fn test_approved_upgrade_to_unknown_wasm_fails_after_checks() {
+    let t = setup();
+    let hash = BytesN::from_array(&t.env, &[9; 32]);
+    t.client.approve_wasm(&hash);
+    // Auth and approval pass; the host then rejects the hash because no such
+    // Wasm was uploaded. The full upgrade runs in `tests/integration`.
+    assert!(t.client.try_upgrade(&hash).is_err());
+    assert_eq!(t.client.wasm_hash(), None, "failed upgrade leaves no trace");
+    assert!(t.client.is_wasm_approved(&hash), "approval not used up");
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_approve_wasm_requires_admin_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client.approve_wasm(&BytesN::from_array(&t.env, &[0; 32]));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_mint_requires_admin_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client.mint(&t.alice, &1);
+}
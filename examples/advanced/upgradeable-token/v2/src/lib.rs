@@ -0,0 +1,317 @@
+//! # Upgradeable Token — v2
+//!
+//! The Wasm that `UpgradeableToken` v1 is upgraded to. It adds `burn` and a
+//! transfer fee, without touching anything v1 wrote:
+//!
+//! - every v1 `DataKey` variant keeps its position and payload, so
+//!   `Balance(id)`, `TotalSupply` and the rest decode unchanged and holders
+//!   need no migration at all;
+//! - the fee config is new state, appended as `DataKey::Fees`. The
+//!   admin-only `migrate(collector, fee_bps)` writes it once and records
+//!   `SchemaVersion = 2`;
+//! - `transfer` and `burn` refuse to run until `migrate` has, so v2 never
+//!   runs without a fee config. Reads work throughout.
+//!
+//! The fee is charged through `cookbook_fees`, rounded up, so even the
+//! smallest transfer pays it under a non-zero rate.
+
+#![no_std]
+
+use cookbook_fees::{charge_with, BpsFee, FeeStrategy};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String,
+};
+
+/// Version reported by this Wasm.
+pub const VERSION: u32 = 2;
+
+/// Storage layout this Wasm expects.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// 100% in basis points.
+pub const MAX_FEE_BPS: u32 = cookbook_fees::MAX_BPS;
+
+/// Keys shared with v1 keep their position and types so existing entries
+/// decode unchanged. New keys are appended.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Metadata,
+    TotalSupply,
+    WasmHash,
+    ApprovedWasm(BytesN<32>),
+    Balance(Address),
+    /// v2: the `FeeConfig`, written by `migrate`.
+    Fees,
+    /// v2: storage layout version; absent means 1.
+    SchemaVersion,
+}
+
+/// Unchanged from v1.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Receives every transfer fee.
+    pub collector: Address,
+    /// Share of each transfer taken as a fee, rounded up.
+    pub fee_bps: u32,
+}
+
+/// v1's codes keep their values; new ones are appended.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    WasmNotApproved = 5,
+    /// `transfer` or `burn` before `migrate`.
+    MigrationPending = 6,
+    AlreadyMigrated = 7,
+    /// A fee above `MAX_FEE_BPS`.
+    InvalidFee = 8,
+}
+
+#[contract]
+pub struct UpgradeableToken;
+
+#[contractimpl]
+impl UpgradeableToken {
+    // ==================== MIGRATION ====================
+
+    /// Write the v2 fee config. Admin only, and only once.
+    ///
+    /// Emits `(migrated,)` with the fee in basis points.
+    pub fn migrate(env: Env, collector: Address, fee_bps: u32) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        if Self::schema_version(env.clone()) >= SCHEMA_VERSION {
+            return Err(TokenError::AlreadyMigrated);
+        }
+        set_fees(&env, collector, fee_bps)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish((symbol_short!("migrated"),), fee_bps);
+        Ok(())
+    }
+
+    /// Storage layout version; `1` until `migrate` has run.
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(1)
+    }
+
+    // ==================== TOKEN ====================
+
+    /// Unchanged from v1.
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+        check_amount(amount)?;
+
+        set_balance(&env, &to, Self::balance(env.clone(), to.clone()) + amount);
+        let supply = Self::total_supply(env.clone()) + amount;
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+
+        env.events()
+            .publish((symbol_short!("mint"), admin, to), amount);
+        Ok(())
+    }
+
+    /// Move `amount` from `from`, of which `to` receives all but the fee.
+    ///
+    /// Emits `(transfer, from, to)` with the amount received, and
+    /// `(fee, from, collector)` with the fee when it is not zero.
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        require_migrated(&env)?;
+        from.require_auth();
+        check_amount(amount)?;
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+
+        let fees = Self::fees(env.clone())?;
+        let (fee, received) = charge_with(&env, &BpsFee { bps: fees.fee_bps }, amount)
+            .map_err(|_| TokenError::InvalidFee)?;
+        set_balance(&env, &from, from_balance - amount);
+        set_balance(&env, &to, Self::balance(env.clone(), to.clone()) + received);
+        if fee > 0 {
+            let collector = fees.collector;
+            set_balance(
+                &env,
+                &collector,
+                Self::balance(env.clone(), collector.clone()) + fee,
+            );
+            env.events()
+                .publish((symbol_short!("fee"), from.clone(), collector), fee);
+        }
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), received);
+        Ok(())
+    }
+
+    /// Destroy `amount` of `from`'s tokens. New in v2.
+    ///
+    /// Emits `(burn, from)` with the amount.
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        require_migrated(&env)?;
+        from.require_auth();
+        check_amount(amount)?;
+        let balance = Self::balance(env.clone(), from.clone());
+        if balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+
+        set_balance(&env, &from, balance - amount);
+        let supply = Self::total_supply(env.clone()) - amount;
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+
+        env.events().publish((symbol_short!("burn"), from), amount);
+        Ok(())
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    pub fn metadata(env: Env) -> Result<Metadata, TokenError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Metadata)
+            .ok_or(TokenError::NotInitialized)
+    }
+
+    // ==================== FEES ====================
+
+    /// Change the fee after migration. Admin only.
+    pub fn set_fee_config(env: Env, collector: Address, fee_bps: u32) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        require_migrated(&env)?;
+        set_fees(&env, collector, fee_bps)
+    }
+
+    pub fn fees(env: Env) -> Result<FeeConfig, TokenError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Fees)
+            .ok_or(TokenError::MigrationPending)
+    }
+
+    // ==================== UPGRADE ====================
+
+    /// Approve `hash` for a later `upgrade`. Admin only.
+    pub fn approve_wasm(env: Env, hash: BytesN<32>) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ApprovedWasm(hash.clone()), &true);
+        env.events().publish((symbol_short!("wasm_appr"),), hash);
+        Ok(())
+    }
+
+    pub fn revoke_wasm(env: Env, hash: BytesN<32>) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ApprovedWasm(hash));
+        Ok(())
+    }
+
+    pub fn is_wasm_approved(env: Env, hash: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::ApprovedWasm(hash))
+    }
+
+    /// Replace this contract's Wasm with an approved `new_wasm_hash`. Admin
+    /// only. Kept in v2 so the token can be upgraded again.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+        Self::admin(env.clone())?.require_auth();
+        let approval = DataKey::ApprovedWasm(new_wasm_hash.clone());
+        if !env.storage().persistent().has(&approval) {
+            return Err(TokenError::WasmNotApproved);
+        }
+
+        env.storage().persistent().remove(&approval);
+        env.storage()
+            .instance()
+            .set(&DataKey::WasmHash, &new_wasm_hash);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+        Ok(())
+    }
+
+    pub fn wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::WasmHash)
+    }
+
+    pub fn admin(env: Env) -> Result<Address, TokenError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)
+    }
+
+    pub fn version() -> u32 {
+        VERSION
+    }
+}
+
+fn require_migrated(env: &Env) -> Result<(), TokenError> {
+    if UpgradeableToken::schema_version(env.clone()) < SCHEMA_VERSION {
+        return Err(TokenError::MigrationPending);
+    }
+    Ok(())
+}
+
+fn set_fees(env: &Env, collector: Address, fee_bps: u32) -> Result<(), TokenError> {
+    BpsFee { bps: fee_bps }
+        .validate()
+        .map_err(|_| TokenError::InvalidFee)?;
+    env.storage()
+        .instance()
+        .set(&DataKey::Fees, &FeeConfig { collector, fee_bps });
+    Ok(())
+}
+
+fn set_balance(env: &Env, id: &Address, balance: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &balance);
+}
+
+fn check_amount(amount: i128) -> Result<(), TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    Ok(())
+}
+
+mod test;
@@ -0,0 +1,165 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+struct Setup {
+    env: Env,
+    client: UpgradeableTokenClient<'static>,
+    alice: Address,
+    bob: Address,
+    collector: Address,
+}
+
+/// Register v2 over storage that looks like v1 left it: Alice holds 1000
+/// of a 1000 supply.
+fn setup_with_v1_state() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, UpgradeableToken);
+    let alice = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let instance = env.storage().instance();
+        instance.set(&DataKey::Admin, &Address::generate(&env));
+        instance.set(
+            &DataKey::Metadata,
+            &Metadata {
+                decimals: 7,
+                name: String::from_str(&env, "Cookbook"),
+                symbol: String::from_str(&env, "COOK"),
+            },
+        );
+        instance.set(&DataKey::TotalSupply, &1_000i128);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(alice.clone()), &1_000i128);
+    });
+    Setup {
+        client: UpgradeableTokenClient::new(&env, &contract_id),
+        alice,
+        bob: Address::generate(&env),
+        collector: Address::generate(&env),
+        env,
+    }
+}
+
+#[test]
+fn test_v1_state_reads_unchanged() {
+    let t = setup_with_v1_state();
+    assert_eq!(t.client.schema_version(), 1);
+    assert_eq!(t.client.version(), 2);
+    assert_eq!(t.client.balance(&t.alice), 1_000);
+    assert_eq!(t.client.total_supply(), 1_000);
+    assert_eq!(t.client.metadata().symbol, String::from_str(&t.env, "COOK"));
+}
+
+#[test]
+fn test_transfer_and_burn_blocked_until_migrated() {
+    let t = setup_with_v1_state();
+    assert_eq!(
+        t.client.try_transfer(&t.alice, &t.bob, &1),
+        Err(Ok(TokenError::MigrationPending))
+    );
+    assert_eq!(
+        t.client.try_burn(&t.alice, &1),
+        Err(Ok(TokenError::MigrationPending))
+    );
+    assert_eq!(t.client.try_fees(), Err(Ok(TokenError::MigrationPending)));
+}
+
+#[test]
+fn test_migrate_writes_fee_config_once() {
+    let t = setup_with_v1_state();
+    t.client.migrate(&t.collector, &250);
+    assert_eq!(t.client.schema_version(), SCHEMA_VERSION);
+    assert_eq!(
+        t.client.fees(),
+        FeeConfig {
+            collector: t.collector.clone(),
+            fee_bps: 250,
+        }
+    );
+    assert_eq!(
+        t.client.try_migrate(&t.collector, &0),
+        Err(Ok(TokenError::AlreadyMigrated))
+    );
+    // Balances needed no migration.
+    assert_eq!(t.client.balance(&t.alice), 1_000);
+}
+
+#[test]
+fn test_migrate_rejects_fee_over_100_percent() {
+    let t = setup_with_v1_state();
+    assert_eq!(
+        t.client.try_migrate(&t.collector, &(MAX_FEE_BPS + 1)),
+        Err(Ok(TokenError::InvalidFee))
+    );
+    assert_eq!(t.client.schema_version(), 1);
+}
+
+#[test]
+fn test_transfer_charges_fee() {
+    let t = setup_with_v1_state();
+    t.client.migrate(&t.collector, &250);
+
+    t.client.transfer(&t.alice, &t.bob, &400);
+    assert_eq!(t.client.balance(&t.alice), 600);
+    assert_eq!(t.client.balance(&t.bob), 390);
+    assert_eq!(t.client.balance(&t.collector), 10);
+    // Fees move tokens; they do not destroy them.
+    assert_eq!(t.client.total_supply(), 1_000);
+
+    // Rounded up: 2.5% of 39 is 0.975.
+    t.client.transfer(&t.bob, &t.alice, &39);
+    assert_eq!(t.client.balance(&t.alice), 638);
+    assert_eq!(t.client.balance(&t.collector), 11);
+}
+
+#[test]
+fn test_dust_transfer_pays_fee() {
+    let t = setup_with_v1_state();
+    t.client.migrate(&t.collector, &250);
+
+    // 2.5% of 1 would round down to nothing; split into single-unit
+    // transfers, a payment would escape the fee entirely.
+    t.client.transfer(&t.alice, &t.bob, &1);
+    assert_eq!(t.client.balance(&t.bob), 0);
+    assert_eq!(t.client.balance(&t.collector), 1);
+    assert_eq!(t.client.total_supply(), 1_000);
+}
+
+#[test]
+fn test_burn_lowers_supply() {
+    let t = setup_with_v1_state();
+    t.client.migrate(&t.collector, &0);
+
+    t.client.burn(&t.alice, &300);
+    assert_eq!(t.client.balance(&t.alice), 700);
+    assert_eq!(t.client.total_supply(), 700);
+    assert_eq!(
+        t.client.try_burn(&t.alice, &701),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_set_fee_config_after_migration() {
+    let t = setup_with_v1_state();
+    assert_eq!(
+        t.client.try_set_fee_config(&t.collector, &100),
+        Err(Ok(TokenError::MigrationPending))
+    );
+    t.client.migrate(&t.collector, &250);
+    t.client.set_fee_config(&t.bob, &0);
+
+    t.client.transfer(&t.alice, &t.bob, &100);
+    assert_eq!(t.client.balance(&t.bob), 100);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_migrate_requires_admin_auth() {
+    let t = setup_with_v1_state();
+    t.env.set_auths(&[]);
+    t.client.migrate(&t.collector, &250);
+}
@@ -0,0 +1,228 @@
+//! # Vesting
+//!
+//! Tokens locked for a beneficiary and released on a cliff + linear
+//! schedule:
+//!
+//! ```text
+//! vested
+//!   total ┤                    ┌──────────
+//!         │                 ╱
+//!         │              ╱
+//!         │           ╱
+//!         │        ┌╱          linear from `start` to `start + duration`,
+//!       0 ┼────────┘           nothing before `start + cliff`
+//!         └────────┬───────────┬──────────▶ time
+//!           start  cliff       start + duration
+//! ```
+//!
+//! Nothing is pushed to the beneficiary. `claim` computes the vested amount
+//! from `env.ledger().timestamp()` and pays out whatever has not been
+//! claimed yet. A revocable grant can be revoked by its grantor: vesting
+//! stops, the unvested remainder goes back, and what had already vested
+//! stays claimable.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Schedule {
+    pub grantor: Address,
+    pub beneficiary: Address,
+    pub token: Address,
+    pub total: i128,
+    pub claimed: i128,
+    pub start: u64,
+    /// Seconds after `start` before anything vests.
+    pub cliff: u64,
+    /// Seconds after `start` until everything has vested.
+    pub duration: u64,
+    pub revocable: bool,
+    /// When the grantor revoked the grant; vesting stops here.
+    pub revoked_at: Option<u64>,
+}
+
+impl Schedule {
+    /// Amount vested at `now`, ignoring what has been claimed.
+    pub fn vested_at(&self, now: u64) -> i128 {
+        let now = match self.revoked_at {
+            Some(revoked_at) => now.min(revoked_at),
+            None => now,
+        };
+        if now < self.start + self.cliff {
+            0
+        } else if now >= self.start + self.duration {
+            self.total
+        } else {
+            self.total * (now - self.start) as i128 / self.duration as i128
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextId,
+    Schedule(u32),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VestingError {
+    NotFound = 1,
+    InvalidAmount = 2,
+    /// `duration` is zero or shorter than `cliff`.
+    InvalidSchedule = 3,
+    NothingToClaim = 4,
+    NotRevocable = 5,
+    AlreadyRevoked = 6,
+}
+
+#[contract]
+pub struct VestingContract;
+
+#[contractimpl]
+impl VestingContract {
+    /// Lock `total` from the grantor for `beneficiary`.
+    ///
+    /// Emits `(vesting, created)` with `(id, beneficiary, total)`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_schedule(
+        env: Env,
+        grantor: Address,
+        beneficiary: Address,
+        token: Address,
+        total: i128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+        revocable: bool,
+    ) -> Result<u32, VestingError> {
+        grantor.require_auth();
+        if total <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        if duration == 0 || cliff > duration {
+            return Err(VestingError::InvalidSchedule);
+        }
+        token::Client::new(&env, &token).transfer(
+            &grantor,
+            &env.current_contract_address(),
+            &total,
+        );
+
+        let id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(0);
+        env.storage().instance().set(&DataKey::NextId, &(id + 1));
+        Self::save(
+            &env,
+            id,
+            &Schedule {
+                grantor,
+                beneficiary: beneficiary.clone(),
+                token,
+                total,
+                claimed: 0,
+                start,
+                cliff,
+                duration,
+                revocable,
+                revoked_at: None,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("vesting"), symbol_short!("created")),
+            (id, beneficiary, total),
+        );
+        Ok(id)
+    }
+
+    /// Pay the beneficiary everything vested and not yet claimed.
+    ///
+    /// Emits `(vesting, claimed)` with `(id, amount)`.
+    pub fn claim(env: Env, id: u32) -> Result<i128, VestingError> {
+        let mut schedule = Self::load(&env, id)?;
+        schedule.beneficiary.require_auth();
+
+        let amount = schedule.vested_at(env.ledger().timestamp()) - schedule.claimed;
+        if amount == 0 {
+            return Err(VestingError::NothingToClaim);
+        }
+        schedule.claimed += amount;
+        Self::save(&env, id, &schedule);
+        token::Client::new(&env, &schedule.token).transfer(
+            &env.current_contract_address(),
+            &schedule.beneficiary,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("vesting"), symbol_short!("claimed")),
+            (id, amount),
+        );
+        Ok(amount)
+    }
+
+    /// Stop vesting and return the unvested remainder to the grantor. The
+    /// beneficiary can still claim what vested before now.
+    ///
+    /// Emits `(vesting, revoked)` with `(id, returned)`.
+    pub fn revoke(env: Env, id: u32) -> Result<i128, VestingError> {
+        let mut schedule = Self::load(&env, id)?;
+        schedule.grantor.require_auth();
+        if !schedule.revocable {
+            return Err(VestingError::NotRevocable);
+        }
+        if schedule.revoked_at.is_some() {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        let now = env.ledger().timestamp();
+        let returned = schedule.total - schedule.vested_at(now);
+        schedule.revoked_at = Some(now);
+        Self::save(&env, id, &schedule);
+        if returned > 0 {
+            token::Client::new(&env, &schedule.token).transfer(
+                &env.current_contract_address(),
+                &schedule.grantor,
+                &returned,
+            );
+        }
+
+        env.events().publish(
+            (symbol_short!("vesting"), symbol_short!("revoked")),
+            (id, returned),
+        );
+        Ok(returned)
+    }
+
+    pub fn schedule(env: Env, id: u32) -> Option<Schedule> {
+        env.storage().persistent().get(&DataKey::Schedule(id))
+    }
+
+    /// Amount vested so far, claimed or not.
+    pub fn vested(env: Env, id: u32) -> Result<i128, VestingError> {
+        Ok(Self::load(&env, id)?.vested_at(env.ledger().timestamp()))
+    }
+
+    /// Amount `claim` would pay right now.
+    pub fn claimable(env: Env, id: u32) -> Result<i128, VestingError> {
+        let schedule = Self::load(&env, id)?;
+        Ok(schedule.vested_at(env.ledger().timestamp()) - schedule.claimed)
+    }
+
+    fn load(env: &Env, id: u32) -> Result<Schedule, VestingError> {
+        Self::schedule(env.clone(), id).ok_or(VestingError::NotFound)
+    }
+
+    fn save(env: &Env, id: u32, schedule: &Schedule) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(id), schedule);
+    }
+}
+
+mod test;
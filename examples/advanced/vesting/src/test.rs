@@ -0,0 +1,233 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const TOTAL: i128 = 1_200;
+const START: u64 = 10_000;
+const CLIFF: u64 = 300;
+const DURATION: u64 = 1_200;
+
+struct Setup {
+    env: Env,
+    vesting: VestingContractClient<'static>,
+    token: TokenClient<'static>,
+    grantor: Address,
+    beneficiary: Address,
+    id: u32,
+}
+
+/// One revocable grant of `TOTAL`: 300s cliff, fully vested after 1_200s.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = START);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let vesting = VestingContractClient::new(&env, &env.register_contract(None, VestingContract));
+    let grantor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&grantor, &(2 * TOTAL));
+
+    let id = vesting.create_schedule(
+        &grantor,
+        &beneficiary,
+        &token_id,
+        &TOTAL,
+        &START,
+        &CLIFF,
+        &DURATION,
+        &true,
+    );
+    Setup {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        vesting,
+        grantor,
+        beneficiary,
+        id,
+    }
+}
+
+impl Setup {
+    fn at(&self, offset: u64) {
+        self.env.ledger().with_mut(|l| l.timestamp = START + offset);
+    }
+}
+
+// ── vesting math ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_nothing_vests_before_cliff() {
+    let s = setup();
+    s.at(CLIFF - 1);
+    assert_eq!(s.vesting.vested(&s.id), 0);
+    assert_eq!(
+        s.vesting.try_claim(&s.id),
+        Err(Ok(VestingError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_cliff_releases_linear_share() {
+    let s = setup();
+    s.at(CLIFF);
+    // 300 / 1_200 of the total, not zero: the cliff delays, it does not reset.
+    assert_eq!(s.vesting.vested(&s.id), 300);
+}
+
+#[test]
+fn test_linear_between_cliff_and_end() {
+    let s = setup();
+    for (offset, expected) in [(600, 600), (900, 900), (1_199, 1_199)] {
+        s.at(offset);
+        assert_eq!(s.vesting.vested(&s.id), expected);
+    }
+    s.at(DURATION);
+    assert_eq!(s.vesting.vested(&s.id), TOTAL);
+    s.at(DURATION * 10);
+    assert_eq!(s.vesting.vested(&s.id), TOTAL);
+}
+
+#[test]
+fn test_schedule_starting_in_future() {
+    let s = setup();
+    let later = s.vesting.create_schedule(
+        &s.grantor,
+        &s.beneficiary,
+        &s.token.address,
+        &TOTAL,
+        &(START + 5_000),
+        &0,
+        &DURATION,
+        &false,
+    );
+    s.at(4_999);
+    assert_eq!(s.vesting.vested(&later), 0);
+    s.at(5_000 + 600);
+    assert_eq!(s.vesting.vested(&later), 600);
+}
+
+// ── claiming ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_claims_pay_only_the_difference() {
+    let s = setup();
+    s.at(400);
+    assert_eq!(s.vesting.claim(&s.id), 400);
+    s.at(1_000);
+    assert_eq!(s.vesting.claimable(&s.id), 600);
+    assert_eq!(s.vesting.claim(&s.id), 600);
+    assert_eq!(
+        s.vesting.try_claim(&s.id),
+        Err(Ok(VestingError::NothingToClaim))
+    );
+
+    s.at(DURATION);
+    assert_eq!(s.vesting.claim(&s.id), 200);
+    assert_eq!(s.token.balance(&s.beneficiary), TOTAL);
+    assert_eq!(s.token.balance(&s.vesting.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_claim_requires_beneficiary_auth() {
+    let s = setup();
+    s.at(DURATION);
+    s.env.set_auths(&[]);
+    s.vesting.claim(&s.id);
+}
+
+// ── revocation ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_revoke_returns_unvested_and_freezes_vesting() {
+    let s = setup();
+    s.at(600);
+    assert_eq!(s.vesting.revoke(&s.id), 600);
+    assert_eq!(s.token.balance(&s.grantor), TOTAL + 600);
+
+    s.at(DURATION * 2);
+    assert_eq!(s.vesting.vested(&s.id), 600);
+    assert_eq!(s.vesting.claim(&s.id), 600);
+    assert_eq!(s.token.balance(&s.vesting.address), 0);
+}
+
+#[test]
+fn test_revoke_keeps_unclaimed_vested_tokens() {
+    let s = setup();
+    s.at(300);
+    s.vesting.claim(&s.id);
+    s.at(900);
+    s.vesting.revoke(&s.id);
+    // 600 vested between the claim and the revocation stay claimable.
+    assert_eq!(s.vesting.claim(&s.id), 600);
+}
+
+#[test]
+fn test_revoke_before_cliff_returns_everything() {
+    let s = setup();
+    s.at(CLIFF - 1);
+    assert_eq!(s.vesting.revoke(&s.id), TOTAL);
+    assert_eq!(
+        s.vesting.try_claim(&s.id),
+        Err(Ok(VestingError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_revoke_rules() {
+    let s = setup();
+    s.vesting.revoke(&s.id);
+    assert_eq!(
+        s.vesting.try_revoke(&s.id),
+        Err(Ok(VestingError::AlreadyRevoked))
+    );
+
+    let fixed = s.vesting.create_schedule(
+        &s.grantor,
+        &s.beneficiary,
+        &s.token.address,
+        &100,
+        &START,
+        &0,
+        &DURATION,
+        &false,
+    );
+    assert_eq!(
+        s.vesting.try_revoke(&fixed),
+        Err(Ok(VestingError::NotRevocable))
+    );
+}
+
+// ── validation ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_invalid_schedules_rejected() {
+    let s = setup();
+    let create = |total: i128, cliff: u64, duration: u64| {
+        s.vesting.try_create_schedule(
+            &s.grantor,
+            &s.beneficiary,
+            &s.token.address,
+            &total,
+            &START,
+            &cliff,
+            &duration,
+            &true,
+        )
+    };
+    assert_eq!(create(0, 0, 100), Err(Ok(VestingError::InvalidAmount)));
+    assert_eq!(create(100, 0, 0), Err(Ok(VestingError::InvalidSchedule)));
+    assert_eq!(
+        create(100, 200, 100),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(s.vesting.try_vested(&99), Err(Ok(VestingError::NotFound)));
+}
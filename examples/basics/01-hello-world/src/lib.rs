@@ -8,13 +8,62 @@
 //! - How to use the `Env` parameter to access the blockchain environment
 //! - How to work with Soroban SDK types (`Symbol`, `String`)
 //! - How to perform `no_std`-safe string manipulation on-chain
+//! - How to keep a `Map` in instance storage, with a fallback for missing keys
+//!
+//! ## Localized greetings
+//!
+//! `hello` always answers in English. `hello_localized` looks the locale up
+//! in a `Map<Symbol, String>` of templates set by the admin, such as
+//! `es => "¡Hola, {}!"`, and substitutes the name for `{}`. A missing
+//! locale falls back step by step:
+//!
+//! ```text
+//! es_MX  →  es  →  DEFAULT_TEMPLATE ("Hello, {}!")
+//! ```
 
 // Soroban contracts must be `no_std` – they run inside the Wasm sandbox and
 // have no access to the Rust standard library.
 #![no_std]
 
 // Import core types and macros from the Soroban SDK
-use soroban_sdk::{contract, contractimpl, vec, Env, String, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, Env, Map, String, Symbol,
+    SymbolStr, TryFromVal, Vec,
+};
+
+/// Template used when no greeting is set for a locale or its language.
+pub const DEFAULT_TEMPLATE: &str = "Hello, {}!";
+
+/// Where the name goes in a template.
+const PLACEHOLDER: &[u8] = b"{}";
+
+/// Longest template, in bytes.
+pub const MAX_TEMPLATE_LEN: u32 = 64;
+
+/// Longest `Symbol`, and so the longest name.
+const MAX_NAME_LEN: usize = 32;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// `Map<Symbol, String>`: locale to template.
+    Greetings,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum GreetingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The template is longer than `MAX_TEMPLATE_LEN` bytes.
+    TemplateTooLong = 3,
+    /// The template does not contain `{}` exactly once.
+    InvalidPlaceholder = 4,
+    /// No greeting is set for the locale.
+    UnknownLocale = 5,
+}
 
 /// The contract type.
 ///
@@ -89,6 +138,135 @@ impl HelloContract {
         // `soroban_sdk::String` that callers can inspect.
         String::from_bytes(&env, &buf[..total])
     }
+
+    // ==================== LOCALIZED GREETINGS ====================
+
+    /// Set the admin who manages the greetings.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), GreetingError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(GreetingError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Set the greeting template for `locale`, replacing any earlier one.
+    /// The template must contain `{}` exactly once, where the name goes.
+    pub fn set_greeting(env: Env, locale: Symbol, template: String) -> Result<(), GreetingError> {
+        Self::admin(&env)?.require_auth();
+        if template.len() > MAX_TEMPLATE_LEN {
+            return Err(GreetingError::TemplateTooLong);
+        }
+        let mut buf = [0u8; MAX_TEMPLATE_LEN as usize];
+        let bytes = &mut buf[..template.len() as usize];
+        template.copy_into_slice(bytes);
+        let placeholders = bytes
+            .windows(PLACEHOLDER.len())
+            .filter(|w| *w == PLACEHOLDER)
+            .count();
+        if placeholders != 1 {
+            return Err(GreetingError::InvalidPlaceholder);
+        }
+
+        let mut greetings = Self::greetings(&env);
+        greetings.set(locale, template);
+        env.storage()
+            .instance()
+            .set(&DataKey::Greetings, &greetings);
+        Ok(())
+    }
+
+    pub fn remove_greeting(env: Env, locale: Symbol) -> Result<(), GreetingError> {
+        Self::admin(&env)?.require_auth();
+        let mut greetings = Self::greetings(&env);
+        if greetings.remove(locale).is_none() {
+            return Err(GreetingError::UnknownLocale);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Greetings, &greetings);
+        Ok(())
+    }
+
+    /// The template set for exactly `locale`, without fallback.
+    pub fn greeting(env: Env, locale: Symbol) -> Option<String> {
+        Self::greetings(&env).get(locale)
+    }
+
+    /// Every locale with a greeting, in `Symbol` order.
+    pub fn locales(env: Env) -> Vec<Symbol> {
+        Self::greetings(&env).keys()
+    }
+
+    /// Greet `name` in `locale`.
+    ///
+    /// Tries `locale` itself, then its language (the part before the first
+    /// `_`, so `es_MX` falls back to `es`), then `DEFAULT_TEMPLATE`.
+    ///
+    /// ```text
+    /// hello_localized(es_MX, Ana) -> "¡Hola, Ana!"   (with only `es` set)
+    /// hello_localized(fr, Ana)    -> "Hello, Ana!"   (nothing set)
+    /// ```
+    pub fn hello_localized(env: Env, locale: Symbol, name: Symbol) -> String {
+        let greetings = Self::greetings(&env);
+        let template = greetings
+            .get(locale.clone())
+            .or_else(|| greetings.get(Self::language(&env, &locale)?))
+            .unwrap_or_else(|| String::from_str(&env, DEFAULT_TEMPLATE));
+        Self::render(&env, &template, &name)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn admin(env: &Env) -> Result<Address, GreetingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(GreetingError::NotInitialized)
+    }
+
+    fn greetings(env: &Env) -> Map<Symbol, String> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Greetings)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// The language part of `locale`, if it has a region: `es_MX` → `es`.
+    fn language(env: &Env, locale: &Symbol) -> Option<Symbol> {
+        let locale = SymbolStr::try_from_val(env, &locale.to_symbol_val()).ok()?;
+        let locale: &str = locale.as_ref();
+        let (language, _region) = locale.split_once('_')?;
+        Some(Symbol::new(env, language))
+    }
+
+    /// Substitute `name` for the `{}` in `template`, in one stack buffer as
+    /// `hello` does. `set_greeting` has checked the template's length and
+    /// placeholder; the default template is known to be valid.
+    fn render(env: &Env, template: &String, name: &Symbol) -> String {
+        let name = SymbolStr::try_from_val(env, &name.to_symbol_val())
+            .unwrap_or_else(|_| panic!("symbol conversion failed"));
+        let name: &str = name.as_ref();
+        let name = name.as_bytes();
+
+        let mut template_buf = [0u8; MAX_TEMPLATE_LEN as usize];
+        let template_bytes = &mut template_buf[..template.len() as usize];
+        template.copy_into_slice(template_bytes);
+        let at = template_bytes
+            .windows(PLACEHOLDER.len())
+            .position(|w| w == PLACEHOLDER)
+            .unwrap_or(template_bytes.len());
+        let (before, after) = template_bytes.split_at(at);
+        let after = after.get(PLACEHOLDER.len()..).unwrap_or(&[]);
+
+        let mut buf = [0u8; MAX_TEMPLATE_LEN as usize + MAX_NAME_LEN];
+        let mut len = 0;
+        for part in [before, name, after] {
+            buf[len..len + part.len()].copy_from_slice(part);
+            len += part.len();
+        }
+        String::from_bytes(env, &buf[..len])
+    }
 }
 
 // Pull in the dedicated test module.
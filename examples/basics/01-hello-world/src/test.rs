@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{symbol_short, Env, String};
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env, String};
 
 /// Tests the basic functionality of the Hello World contract.
 ///
@@ -50,49 +50,45 @@ fn test_hello_with_different_names() {
     }
 }
 
-/// Tests edge cases including empty strings and long strings.
+/// Tests edge cases including empty and maximum-length symbols.
 ///
 /// Validates that:
-/// - The contract handles an empty String correctly.
-/// - The contract handles long strings gracefully.
+/// - The contract handles an empty Symbol correctly.
+/// - The contract handles the longest possible Symbol (32 characters).
 #[test]
 fn test_edge_cases() {
     let env = Env::default();
     let contract_id = env.register_contract(None, HelloContract);
     let client = HelloContractClient::new(&env, &contract_id);
 
-    // 1. Empty string
-    let empty_name = String::from_str(&env, "");
-    let result_empty = client.hello(&empty_name);
-    assert_eq!(result_empty.get(1).unwrap(), empty_name);
+    // 1. Empty symbol
+    let result_empty = client.hello(&symbol_short!(""));
+    assert_eq!(result_empty, String::from_str(&env, "Hello, !"));
 
-    // 2. Medium string
-    let mid_string = String::from_str(&env, "123456789");
-    let result_mid = client.hello(&mid_string);
-    assert_eq!(result_mid.get(1).unwrap(), mid_string);
+    // 2. The longest short symbol
+    let result_mid = client.hello(&symbol_short!("123456789"));
+    assert_eq!(result_mid, String::from_str(&env, "Hello, 123456789!"));
 
-    // 3. Long string
-    let long_name = String::from_str(
-        &env,
-        "ThisIsALongerStringThatGoesBeyondThirtyTwoCharactersIfNeeded",
+    // 3. The longest symbol
+    let long_name = "ThisIsALongerSymbolOf32Character";
+    let result_long = client.hello(&Symbol::new(&env, long_name));
+    assert_eq!(
+        result_long,
+        String::from_str(&env, "Hello, ThisIsALongerSymbolOf32Character!")
     );
-    let result_long = client.hello(&long_name);
-    assert_eq!(result_long.get(1).unwrap(), long_name);
 }
 
-/// Tests handling of strings with special characters.
+/// Tests the shape of the greeting.
 ///
-/// Validates that strings containing spaces or punctuation are processed correctly.
+/// Validates that the response bytes begin with "Hello, ".
 #[test]
 fn test_hello_starts_with_hello() {
     let env = Env::default();
     let contract_id = env.register_contract(None, HelloContract);
     let client = HelloContractClient::new(&env, &contract_id);
 
-    // String with space
-    let name_with_space = String::from_str(&env, "Hello World");
-    let result = client.hello(&name_with_space);
-    assert_eq!(result.get(1).unwrap(), name_with_space);
+    let result = client.hello(&symbol_short!("Hello_W"));
+    assert_eq!(result, String::from_str(&env, "Hello, Hello_W!"));
 
     // Copy the response bytes into a local buffer so we can inspect them.
     let mut buf = [0u8; 40];
@@ -105,3 +101,174 @@ fn test_hello_starts_with_hello() {
         "Expected greeting to begin with 'Hello, ', got: {result_str}"
     );
 }
+
+// ==================== LOCALIZED GREETINGS ====================
+
+struct Setup {
+    env: Env,
+    client: HelloContractClient<'static>,
+    admin: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HelloContractClient::new(&env, &env.register_contract(None, HelloContract));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    Setup { env, client, admin }
+}
+
+fn s(env: &Env, text: &str) -> String {
+    String::from_str(env, text)
+}
+
+#[test]
+fn test_hello_localized_uses_locale_template() {
+    let t = setup();
+    t.client
+        .set_greeting(&symbol_short!("es"), &s(&t.env, "¡Hola, {}!"));
+    t.client
+        .set_greeting(&symbol_short!("ja"), &s(&t.env, "{}さん、こんにちは"));
+
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("es"), &symbol_short!("Ana")),
+        s(&t.env, "¡Hola, Ana!")
+    );
+    // The placeholder can be anywhere in the template.
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("ja"), &symbol_short!("Ken")),
+        s(&t.env, "Kenさん、こんにちは")
+    );
+    assert_eq!(
+        t.client.locales(),
+        vec![&t.env, symbol_short!("es"), symbol_short!("ja")]
+    );
+}
+
+#[test]
+fn test_hello_localized_falls_back_to_default() {
+    let t = setup();
+    // Nothing set: the default template, same as `hello`.
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("fr"), &symbol_short!("Ana")),
+        t.client.hello(&symbol_short!("Ana"))
+    );
+
+    t.client
+        .set_greeting(&symbol_short!("es"), &s(&t.env, "¡Hola, {}!"));
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("fr"), &symbol_short!("Ana")),
+        s(&t.env, "Hello, Ana!")
+    );
+    assert_eq!(t.client.greeting(&symbol_short!("fr")), None);
+}
+
+#[test]
+fn test_hello_localized_falls_back_to_language() {
+    let t = setup();
+    t.client
+        .set_greeting(&symbol_short!("es"), &s(&t.env, "¡Hola, {}!"));
+
+    // No `es_MX` template: use `es`.
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("es_MX"), &symbol_short!("Ana")),
+        s(&t.env, "¡Hola, Ana!")
+    );
+
+    // A region template wins over its language.
+    t.client
+        .set_greeting(&symbol_short!("es_MX"), &s(&t.env, "¡Qué onda, {}!"));
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("es_MX"), &symbol_short!("Ana")),
+        s(&t.env, "¡Qué onda, Ana!")
+    );
+
+    // An unknown language with a region still reaches the default.
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("pt_BR"), &symbol_short!("Ana")),
+        s(&t.env, "Hello, Ana!")
+    );
+}
+
+#[test]
+fn test_remove_greeting_restores_fallback() {
+    let t = setup();
+    t.client
+        .set_greeting(&symbol_short!("es"), &s(&t.env, "¡Hola, {}!"));
+    t.client.remove_greeting(&symbol_short!("es"));
+
+    assert_eq!(
+        t.client
+            .hello_localized(&symbol_short!("es"), &symbol_short!("Ana")),
+        s(&t.env, "Hello, Ana!")
+    );
+    assert_eq!(
+        t.client.try_remove_greeting(&symbol_short!("es")),
+        Err(Ok(GreetingError::UnknownLocale))
+    );
+}
+
+#[test]
+fn test_set_greeting_validates_template() {
+    let t = setup();
+    let es = symbol_short!("es");
+    assert_eq!(
+        t.client.try_set_greeting(&es, &s(&t.env, "¡Hola!")),
+        Err(Ok(GreetingError::InvalidPlaceholder))
+    );
+    assert_eq!(
+        t.client.try_set_greeting(&es, &s(&t.env, "{} y {}")),
+        Err(Ok(GreetingError::InvalidPlaceholder))
+    );
+
+    extern crate std;
+    let max = std::format!("{}{{}}", "x".repeat(MAX_TEMPLATE_LEN as usize - 2));
+    t.client.set_greeting(&es, &s(&t.env, &max));
+    let too_long = std::format!("x{max}");
+    assert_eq!(
+        t.client.try_set_greeting(&es, &s(&t.env, &too_long)),
+        Err(Ok(GreetingError::TemplateTooLong))
+    );
+
+    // The longest template and the longest name still fit.
+    let name = "ThisIsALongerSymbolOf32Character";
+    let greeting = t.client.hello_localized(&es, &Symbol::new(&t.env, name));
+    assert_eq!(greeting.len(), MAX_TEMPLATE_LEN - 2 + name.len() as u32);
+}
+
+#[test]
+fn test_initialize_only_once() {
+    let t = setup();
+    assert_eq!(
+        t.client.try_initialize(&t.admin),
+        Err(Ok(GreetingError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_set_greeting_requires_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HelloContractClient::new(&env, &env.register_contract(None, HelloContract));
+    assert_eq!(
+        client.try_set_greeting(&symbol_short!("es"), &s(&env, "¡Hola, {}!")),
+        Err(Ok(GreetingError::NotInitialized))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_greeting_requires_admin_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client
+        .set_greeting(&symbol_short!("es"), &s(&t.env, "¡Hola, {}!"));
+}
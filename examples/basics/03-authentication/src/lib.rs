@@ -1,6 +1,10 @@
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Env, Symbol, Vec};
+use cookbook_state::{state_machine, transition_to, StateError};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address,
+    BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -8,6 +12,9 @@ use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_sh
 
 /// Roles that can be assigned to accounts. The numeric discriminants are used
 /// when returning roles as `u32` to callers that cannot decode the enum.
+///
+/// Roles are ordered: Admin > Moderator > User. A lower discriminant is a
+/// higher role.
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Role {
@@ -16,6 +23,24 @@ pub enum Role {
     User = 2,
 }
 
+impl Role {
+    /// Whether this role is `min` or above it in the hierarchy.
+    pub fn satisfies(self, min: Role) -> bool {
+        self as u32 <= min as u32
+    }
+}
+
+/// An entry of `list_role_holders`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleHolder {
+    pub account: Address,
+    pub role: Role,
+    /// Ledger timestamp from which the role no longer counts; `None` for a
+    /// permanent role.
+    pub expires_at: Option<u64>,
+}
+
 /// Contract-wide operational state. Transitions are admin-only.
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -25,6 +50,31 @@ pub enum ContractState {
     Frozen = 2,
 }
 
+// A frozen contract is thawed to `Paused` first, never straight to `Active`.
+state_machine! {
+    ContractState, key = DataKey::State, initial = Active;
+    Active => [Paused, Frozen],
+    Paused => [Active, Frozen],
+    Frozen => [Paused],
+}
+
+/// One entry of the on-chain admin audit log.
+///
+/// Only a hash of the arguments is stored: it is enough to prove what was
+/// called (re-hash the arguments you expect and compare) while keeping every
+/// entry the same small size.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 0 and never reused.
+    pub seq: u64,
+    pub actor: Address,
+    pub action: Symbol,
+    /// SHA-256 of the XDR-encoded argument vector.
+    pub args_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
 /// Storage keys. Instance storage holds contract-wide config; persistent
 /// storage holds per-account data that must survive across ledgers.
 #[contracttype]
@@ -36,16 +86,129 @@ pub enum DataKey {
     TimeLock,
     CooldownPeriod,
     LastAction(Address),
+    Balance(Address),
+    Allowance(Address, Address),
+    /// Total number of audit entries ever written.
+    AuditCount,
+    /// Ring-buffer slot (`seq % AUDIT_CAPACITY`) holding an `AuditEntry`.
+    AuditSlot(u32),
+    /// Scope bitmask of a delegated admin (`SCOPE_*` flags).
+    AdminScopes(Address),
+    /// Expiry timestamp of a temporary role.
+    RoleExpiry(Address),
+    /// Index of every account with a stored role, for `list_role_holders`.
+    RoleHolders,
+    /// Address proposed as the next admin, until it accepts.
+    PendingAdmin,
+    /// Mask of paused functions (`PAUSE_*` flags).
+    PausedFns,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuthError {
+    Unauthorized = 1,
+    NotAdmin = 2,
+    AlreadyInitialized = 3,
+    /// The caller is a delegated admin but lacks the scope for this function.
+    MissingScope = 4,
+    /// A batch call was given no items.
+    EmptyBatch = 5,
+    /// A batch call exceeded `MAX_BATCH_SIZE` items.
+    BatchTooLarge = 6,
+    /// The same account or config key appears twice in one batch.
+    DuplicateBatchEntry = 7,
+    /// The super-admin's role cannot be changed through a batch, nor its
+    /// scopes through `set_admin_scopes`.
+    ProtectedAccount = 8,
+    UnknownConfigKey = 9,
+    InvalidConfigValue = 10,
+    /// The caller has no role, or one below the required level.
+    InsufficientRole = 11,
+    /// The caller's role has passed its expiry timestamp.
+    RoleExpired = 12,
+    /// A role expiry that is not in the future.
+    InvalidExpiry = 13,
+    /// Granting would exceed `MAX_ROLE_HOLDERS` live role holders.
+    TooManyRoleHolders = 14,
+    /// `accept_admin` or `cancel_admin_transfer` with no proposal open.
+    NoPendingAdmin = 15,
+    /// `accept_admin` called by an address other than the proposed one.
+    NotPendingAdmin = 16,
+    /// The contract state has no declared transition to the requested one.
+    InvalidStateTransition = 17,
+    /// `pause_fn` or `unpause_fn` named a function that cannot be paused.
+    UnknownFunction = 18,
+    /// The function has been paused with `pause_fn`.
+    FunctionPaused = 19,
+    /// `time_locked_action` before the time set with `set_time_lock`.
+    TimeLocked = 20,
+    /// `cooldown_action` again before the cooldown period has passed.
+    CooldownActive = 21,
+    /// An action that needs the `Active` state.
+    ContractNotActive = 22,
+    /// A scope mask with bits outside `ALL_SCOPES`.
+    InvalidScopes = 23,
+    /// A transfer amount that is not positive, or a balance that would
+    /// overflow.
+    InvalidAmount = 24,
+    /// A transfer larger than the sender's balance.
+    InsufficientBalance = 25,
+}
+
+impl From<StateError> for AuthError {
+    fn from(_: StateError) -> Self {
+        AuthError::InvalidStateTransition
+    }
 }
 
+/// Admin scope: contract configuration (time-lock, cooldown, state).
+pub const SCOPE_CONFIG: u32 = 1 << 0;
+/// Admin scope: moving or assigning funds.
+pub const SCOPE_FUNDS: u32 = 1 << 1;
+/// Admin scope: replacing the contract Wasm.
+pub const SCOPE_UPGRADE: u32 = 1 << 2;
+/// Every scope. The super-admin implicitly holds all of them.
+pub const ALL_SCOPES: u32 = SCOPE_CONFIG | SCOPE_FUNDS | SCOPE_UPGRADE;
+
+/// Pause flag: `transfer`.
+pub const PAUSE_TRANSFER: u32 = 1 << 0;
+/// Pause flag: `transfer_from`.
+pub const PAUSE_TRANSFER_FROM: u32 = 1 << 1;
+/// Pause flag: `approve`.
+pub const PAUSE_APPROVE: u32 = 1 << 2;
+
+/// Maximum number of items accepted by `grant_roles` and `set_configs`.
+pub const MAX_BATCH_SIZE: u32 = 20;
+
+/// Config key for the time-lock unlock timestamp.
+pub const CONFIG_TIME_LOCK: Symbol = symbol_short!("time_lock");
+/// Config key for the cooldown period in seconds.
+pub const CONFIG_COOLDOWN: Symbol = symbol_short!("cooldown");
+/// Config key for the contract state (`ContractState` discriminant).
+pub const CONFIG_STATE: Symbol = symbol_short!("state");
+/// Upper bound for `CONFIG_COOLDOWN` (30 days).
+pub const MAX_COOLDOWN: u64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of accounts in the role-holder index. Expired roles are
+/// pruned to make room before a grant is rejected.
+pub const MAX_ROLE_HOLDERS: u32 = 50;
+
+/// Number of audit entries kept on-chain. Older entries are overwritten.
+pub const AUDIT_CAPACITY: u32 = 64;
+
+/// Maximum number of entries returned by a single `get_audit_log` call.
+pub const AUDIT_PAGE_LIMIT: u32 = 20;
+
 // ---------------------------------------------------------------------------
 // Contract
 // ---------------------------------------------------------------------------
 
 /// Authentication Patterns Contract
-/// 
+///
 /// This contract demonstrates various address authentication patterns using Soroban's require_auth() function.
-/// 
+///
 /// # Context
 /// Address authentication is the foundation of authorization in Soroban. The require_auth() function:
 /// - Verifies that the caller has authorized the transaction
@@ -55,23 +218,6 @@ pub enum DataKey {
 #[contract]
 pub struct AuthContract;
 
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-#[repr(u32)]
-pub enum AuthError {
-    Unauthorized = 1,
-    NotAdmin = 2,
-    AlreadyInitialized = 3,
-}
-
-#[contracttype]
-#[derive(Clone)]
-pub enum DataKey {
-    Admin,
-    Balance(Address),
-    Allowance(Address, Address),
-}
-
 #[contractimpl]
 impl AuthContract {
     /// Basic authentication check
@@ -80,92 +226,245 @@ impl AuthContract {
         true
     }
 
-    /// Initialize contract with admin
+    // ==================== INITIALIZATION ====================
+
+    /// Initializes the contract with the given admin address.
+    ///
+    /// Must be called exactly once. Fails on repeated calls to prevent
+    /// admin hijacking after deployment. The admin is also granted
+    /// `Role::Admin`.
     pub fn initialize(env: Env, admin: Address) -> Result<(), AuthError> {
-    /// Single-address authorization pattern
-    /// 
-    /// Demonstrates how to require authentication from a specific address for operations
-    /// like transferring assets or modifying user-specific data.
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `from` - The address initiating the transfer
-    /// * `to` - The destination address
-    /// * `amount` - The amount to transfer
-    /// 
-    /// # How authorization is verified:
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AuthError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(admin.clone()), &Role::Admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoleHolders, &vec![&env, admin.clone()]);
+
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("init"),
+            vec![&env, admin.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    // ==================== ADMIN TRANSFER ====================
+
+    /// Step 1 of an admin handover: propose `new_admin`. Admin only.
+    ///
+    /// Nothing changes until `new_admin` calls `accept_admin`, so a typo in
+    /// the address cannot lock the contract out of its admin. A new proposal
+    /// replaces an open one.
+    ///
+    /// Emits `(admin, proposed)` with `(admin, new_admin)`.
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), AuthError> {
+        Self::check_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &new_admin);
+
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("proposed")),
+            (admin.clone(), new_admin.clone()),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("prop_adm"),
+            vec![&env, new_admin.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Step 2: the proposed address takes over as admin, proving with its
+    /// own authorization that it exists and is controlled.
+    ///
+    /// The `Admin` role moves with it, and any delegated scopes it held are
+    /// dropped since the admin holds every scope.
+    ///
+    /// Emits `(admin, accepted)` with `(old_admin, new_admin)`.
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AuthError> {
+        new_admin.require_auth();
+        let pending = Self::get_pending_admin(env.clone()).ok_or(AuthError::NoPendingAdmin)?;
+        if pending != new_admin {
+            return Err(AuthError::NotPendingAdmin);
+        }
+        let old_admin = Self::get_admin(env.clone()).ok_or(AuthError::NotAdmin)?;
+
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AdminScopes(new_admin.clone()));
+        Self::remove_role(&env, &old_admin);
+        Self::store_role(&env, &new_admin, Role::Admin, None)?;
+
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("accepted")),
+            (old_admin, new_admin.clone()),
+        );
+        Self::record_audit(
+            &env,
+            &new_admin,
+            symbol_short!("acc_adm"),
+            vec![&env, new_admin.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Withdraw an open proposal. Admin only.
+    ///
+    /// Emits `(admin, cancelled)` with the address that was proposed.
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), AuthError> {
+        Self::check_admin(&env, &admin)?;
+        let pending = Self::get_pending_admin(env.clone()).ok_or(AuthError::NoPendingAdmin)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("cancelled")),
+            pending.clone(),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("cncl_adm"),
+            vec![&env, pending.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// The address proposed as next admin, if a handover is open.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    // ==================== SINGLE-ADDRESS PATTERNS ====================
+
+    /// Admin-only function
+    pub fn admin_action(env: Env, admin: Address, value: u32) -> Result<u32, AuthError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuthError::NotAdmin)?;
+
+        if admin != stored_admin {
+            return Err(AuthError::NotAdmin);
+        }
+
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("admin_act"),
+            vec![&env, value.into_val(&env)],
+        );
+        Ok(value * 2)
+    }
+
+    /// Transfer with authentication
+    ///
     /// The `from.require_auth()` call ensures that the `from` address has authorized this transaction.
     /// This prevents someone else from initiating a transfer from another person's account.
-    pub fn transfer(_env: Env, from: Address, _to: Address, amount: i128) -> bool {
-        // Require authorization from the 'from' address
-        // This prevents unauthorized transfers from someone else's account
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), AuthError> {
+        Self::when_not_paused(&env, PAUSE_TRANSFER)?;
         from.require_auth();
-    
-        // Validate inputs
-        if amount <= 0 {
-            panic!("Amount must be positive");
-        }
-    
-        // Perform the transfer logic here (in a real contract, this would update balances)
-        // For demonstration purposes, we just return true
-        true
+        Self::move_balance(&env, &from, &to, amount)
     }
 
-    /// Admin-only function pattern
-    /// 
-    /// Demonstrates how to restrict function access to a specific admin address.
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `admin` - The address claiming to be admin
-    /// * `new_admin` - The address to set as new admin
-    /// 
-    /// # Security considerations:
-    /// - Store the admin address in persistent storage
-    /// - Only allow the current admin to change the admin
-    /// - Always verify admin permissions before critical operations
-    pub fn set_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), AuthError> {
-        // First, check if there's already an admin stored
-        if let Some(stored_admin) = env.storage().instance().get::<Symbol, Address>(&ADMIN_KEY) {
-            // If there's a stored admin, verify that the caller is that admin
-            if admin != stored_admin {
-                return Err(AuthError::AdminOnly);
-            }
-            // Require authorization from the current admin
-            admin.require_auth();
-        } else {
-            // If no admin is set yet, anyone can become the initial admin
-            // In a real deployment, this would typically be the contract deployer
-            admin.require_auth();
-        }
+    /// Set balance (requires `SCOPE_FUNDS`)
+    pub fn set_balance(
+        env: Env,
+        admin: Address,
+        user: Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_FUNDS)?;
 
-        // Set the new admin
-        env.storage().instance().set(&ADMIN_KEY, &new_admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone()), &amount);
 
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("set_bal"),
+            vec![&env, user.into_val(&env), amount.into_val(&env)],
+        );
         Ok(())
     }
 
-    /// Get the current admin address
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// 
-    /// # Returns
-    /// The current admin address, if set
-    pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().instance().get::<Symbol, Address>(&ADMIN_KEY)
+    /// Get balance
+    pub fn get_balance(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(user))
+            .unwrap_or(0)
+    }
+
+    /// Approve allowance
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        Self::when_not_paused(&env, PAUSE_APPROVE)?;
+        from.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowance(from, spender), &amount);
+        Ok(())
+    }
+
+    /// Transfer from allowance
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        Self::when_not_paused(&env, PAUSE_TRANSFER_FROM)?;
+        spender.require_auth();
+
+        let allowance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allowance(from.clone(), spender.clone()))
+            .unwrap_or(0);
+
+        // Checked before the allowance: a negative amount would pass
+        // `allowance < amount` and raise the allowance below.
+        if amount <= 0 {
+            return Err(AuthError::InvalidAmount);
+        }
+        if allowance < amount {
+            return Err(AuthError::Unauthorized);
+        }
+
+        Self::move_balance(&env, &from, &to, amount)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowance(from, spender), &(allowance - amount));
+
+        Ok(())
     }
 
     /// User-specific operations pattern
-    /// 
-    /// Demonstrates how to perform operations that affect only the authenticated user.
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `user` - The user whose data will be modified
-    /// * `data` - The data to store for the user
-    /// 
-    /// # Pattern:
+    ///
     /// 1. Require auth from the user who owns the data
     /// 2. Use the authenticated address as a key for user-specific storage
     pub fn update_user_data(env: Env, user: Address, data: Symbol) -> bool {
@@ -181,27 +480,12 @@ impl AuthContract {
     }
 
     /// Retrieve user-specific data
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `user` - The user whose data to retrieve
-    /// 
-    /// # Returns
-    /// The data stored for the user, if any
     pub fn get_user_data(env: Env, user: Address) -> Option<Symbol> {
         env.storage().persistent().get(&user)
     }
 
     /// Function demonstrating proper error handling for auth failures
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `user` - The address that should authorize the transaction
-    /// * `operation` - The operation identifier
-    /// 
-    /// # Returns
-    /// Result indicating success or specific error type
-    /// 
+    ///
     /// # Proper error handling:
     /// - Clear error messages when auth fails
     /// - Meaningful error codes for different failure types
@@ -220,155 +504,750 @@ impl AuthContract {
             return Err(AuthError::Unauthorized);
         }
 
-        // Perform the secure operation
-        let result = vec![&env, symbol_short!("success"), operation];
-
-        Ok(result)
+        Ok(vec![&env, symbol_short!("success"), operation])
     }
 
     /// Demonstration of self-authorization pattern
-    /// 
-    /// Shows how a contract can authenticate itself when calling other contracts
-    /// 
-    /// # Parameters
-    /// * `env` - The Soroban environment
-    /// * `self_address` - The address of this contract
-    /// 
-    /// # Self-authorization use case:
+    ///
     /// When a contract needs to authenticate itself to call other contracts
     /// or when implementing contract-to-contract authorization
     pub fn self_authenticate(_env: Env, self_address: Address) -> bool {
-        // The contract authenticates itself
-        // This is useful when the contract needs to prove its identity to other contracts
         self_address.require_auth();
-    
-        // In a real scenario, this would be used to call other contracts
-        // or to prove the contract's identity for cross-contract operations
         true
     }
 
-    /// Demonstrates basic address-based authentication.
-    /// Only the 'user' can successfully call this function.
-    pub fn secure_action(_env: Env, user: Address) {
-        // 1. The magic line: checks signature and protects against replays.
+    // ==================== MULTI-PARTY PATTERNS ====================
+
+    /// Multi-signature operation
+    pub fn multi_sig_action(_env: Env, signers: Vec<Address>, value: u32) -> u32 {
+        for signer in signers.iter() {
+            signer.require_auth();
+        }
+        value + signers.len()
+    }
+
+    /// Emit event with authentication
+    pub fn emit_event(env: Env, user: Address, message: Symbol) {
         user.require_auth();
+        env.events()
+            .publish((symbol_short!("event"), user), message);
     }
 
-    // ==================== INITIALIZATION ====================
+    // ==================== ROLE-BASED ACCESS CONTROL ====================
 
-    /// Initializes the contract with the given admin address.
+    /// Assign `role` to `account`, replacing any previous role and expiry.
+    /// Admin only.
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        account: Address,
+        role: Role,
+    ) -> Result<(), AuthError> {
+        Self::check_admin(&env, &admin)?;
+        Self::store_role(&env, &account, role, None)?;
+
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("granted")),
+            (account.clone(), role),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("grant"),
+            vec![&env, account.into_val(&env), role.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Like `grant_role`, but the role stops counting at `expires_at`
+    /// (a ledger timestamp), without anyone having to revoke it.
     ///
-    /// Must be called exactly once. Panics on repeated calls to prevent
-    /// admin hijacking after deployment.
-    pub fn initialize(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            return Err(AuthError::AlreadyInitialized);
+    /// Emits `(role, granted)` with `(account, role, expires_at)`.
+    pub fn grant_role_until(
+        env: Env,
+        admin: Address,
+        account: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), AuthError> {
+        Self::check_admin(&env, &admin)?;
+        if expires_at <= env.ledger().timestamp() {
+            return Err(AuthError::InvalidExpiry);
         }
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        Self::store_role(&env, &account, role, Some(expires_at))?;
+
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("granted")),
+            (account.clone(), role, expires_at),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("grant_til"),
+            vec![
+                &env,
+                account.into_val(&env),
+                role.into_val(&env),
+                expires_at.into_val(&env),
+            ],
+        );
         Ok(())
     }
 
-    /// Get admin address
-    pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().instance().get(&DataKey::Admin)
+    /// Remove any role from `account`. Admin only; a no-op for unassigned accounts.
+    pub fn revoke_role(env: Env, admin: Address, account: Address) -> Result<(), AuthError> {
+        Self::check_admin(&env, &admin)?;
+        Self::remove_role(&env, &account);
+
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("revoked")),
+            account.clone(),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("revoke"),
+            vec![&env, account.into_val(&env)],
+        );
+        Ok(())
     }
 
-    /// Admin-only function
-    pub fn admin_action(env: Env, admin: Address, value: u32) -> Result<u32, AuthError> {
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Return the role of `account` as its numeric discriminant.
+    /// `InsufficientRole` if none is assigned, `RoleExpired` if it expired.
+    pub fn get_role(env: Env, account: Address) -> Result<u32, AuthError> {
+        Ok(Self::active_role(&env, &account)? as u32)
+    }
+
+    /// Whether `account` holds exactly `role`, unexpired.
+    pub fn has_role(env: Env, account: Address, role: Role) -> bool {
+        Self::active_role(&env, &account) == Ok(role)
+    }
+
+    /// Authenticate `caller` and check that it holds `min_role` or a role
+    /// above it. Returns the role actually held.
+    pub fn require_min_role(env: Env, caller: Address, min_role: Role) -> Result<Role, AuthError> {
+        caller.require_auth();
+        let role = Self::active_role(&env, &caller)?;
+        if !role.satisfies(min_role) {
+            return Err(AuthError::InsufficientRole);
+        }
+        Ok(role)
+    }
+
+    /// Every account holding an unexpired role, in the order first granted.
+    pub fn list_role_holders(env: Env) -> Vec<RoleHolder> {
+        let mut holders = Vec::new(&env);
+        for account in Self::role_holders(&env).iter() {
+            if let Ok(role) = Self::active_role(&env, &account) {
+                let expires_at = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::RoleExpiry(account.clone()));
+                holders.push_back(RoleHolder {
+                    account,
+                    role,
+                    expires_at,
+                });
+            }
+        }
+        holders
+    }
+
+    /// Moderator-level action. Admins implicitly satisfy moderator checks.
+    pub fn moderator_action(env: Env, caller: Address, value: u64) -> Result<u64, AuthError> {
+        Self::require_min_role(env.clone(), caller, Role::Moderator)?;
+        let result = value + 100;
+        env.events().publish((symbol_short!("moderator"),), result);
+        Ok(result)
+    }
+
+    // ==================== TIME-BASED RESTRICTIONS ====================
+
+    /// Set the timestamp before which `time_locked_action` is rejected.
+    /// Requires `SCOPE_CONFIG`.
+    pub fn set_time_lock(env: Env, admin: Address, unlock_time: u64) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_CONFIG)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::TimeLock, &unlock_time);
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("time_lock"),
+            vec![&env, unlock_time.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    pub fn time_locked_action(env: Env, caller: Address) -> Result<u64, AuthError> {
+        caller.require_auth();
+
+        let unlock_time: u64 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthError::NotAdmin)?;
-        
-        if admin != stored_admin {
-            return Err(AuthError::NotAdmin);
+            .get(&DataKey::TimeLock)
+            .unwrap_or(0);
+        let now = env.ledger().timestamp();
+        if now < unlock_time {
+            return Err(AuthError::TimeLocked);
         }
-        
-        Ok(value * 2)
+        Ok(now)
     }
 
-    /// Transfer with authentication
-    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), AuthError> {
-        from.require_auth();
-        
-        let from_balance: i128 = env.storage().persistent().get(&DataKey::Balance(from.clone())).unwrap_or(0);
-        let to_balance: i128 = env.storage().persistent().get(&DataKey::Balance(to.clone())).unwrap_or(0);
-        
-        env.storage().persistent().set(&DataKey::Balance(from), &(from_balance - amount));
-        env.storage().persistent().set(&DataKey::Balance(to), &(to_balance + amount));
-        
+    /// Set the minimum interval between two `cooldown_action` calls per address.
+    /// Requires `SCOPE_CONFIG`. Fails with `InvalidConfigValue` above
+    /// `MAX_COOLDOWN`, as `set_configs` does.
+    pub fn set_cooldown(env: Env, admin: Address, period: u64) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_CONFIG)?;
+        if period > MAX_COOLDOWN {
+            return Err(AuthError::InvalidConfigValue);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CooldownPeriod, &period);
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("cooldown"),
+            vec![&env, period.into_val(&env)],
+        );
         Ok(())
     }
 
-    /// Set balance (admin only)
-    pub fn set_balance(env: Env, admin: Address, user: Address, amount: i128) -> Result<(), AuthError> {
-        admin.require_auth();
-        let stored_admin: Address = env
+    pub fn cooldown_action(env: Env, caller: Address) -> Result<u64, AuthError> {
+        caller.require_auth();
+
+        let period: u64 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(AuthError::NotAdmin)?;
-        
-        if admin != stored_admin {
+            .get(&DataKey::CooldownPeriod)
+            .unwrap_or(0);
+        let now = env.ledger().timestamp();
+        let last: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastAction(caller.clone()));
+        if let Some(last_ts) = last {
+            if now < last_ts + period {
+                return Err(AuthError::CooldownActive);
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastAction(caller), &now);
+        Ok(now)
+    }
+
+    // ==================== STATE-BASED AUTHORIZATION ====================
+
+    /// Requires `SCOPE_CONFIG`. `InvalidStateTransition` on a transition
+    /// `ContractState` does not declare, such as `Frozen` to `Active`.
+    ///
+    /// Emits `(state,)` with `(from, to)` when the state changes.
+    pub fn set_state(env: Env, admin: Address, state: ContractState) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_CONFIG)?;
+        transition_to(&env, state)?;
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("set_state"),
+            vec![&env, state.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Return the contract state as its numeric discriminant.
+    pub fn get_state(env: Env) -> u32 {
+        ContractState::current(&env) as u32
+    }
+
+    pub fn active_only_action(env: Env, caller: Address) -> Result<u64, AuthError> {
+        caller.require_auth();
+        if ContractState::current(&env) != ContractState::Active {
+            return Err(AuthError::ContractNotActive);
+        }
+        Ok(env.ledger().timestamp())
+    }
+
+    // ==================== PER-FUNCTION PAUSE ====================
+
+    /// Pause one function by name (`transfer`, `transfer_from` or
+    /// `approve`). Requires `SCOPE_CONFIG`.
+    ///
+    /// Unlike `set_state`, the rest of the contract keeps working: pausing
+    /// `transfer` leaves balances readable and allowances settable.
+    ///
+    /// Emits `(paused, fn_name)` with the admin.
+    pub fn pause_fn(env: Env, admin: Address, fn_name: Symbol) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_CONFIG)?;
+        let flag = Self::pause_flag(&env, &fn_name)?;
+        let paused = Self::get_paused_fns(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::PausedFns, &(paused | flag));
+
+        env.events()
+            .publish((symbol_short!("paused"), fn_name.clone()), admin.clone());
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("pause_fn"),
+            vec![&env, fn_name.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Undo `pause_fn`. Requires `SCOPE_CONFIG`.
+    ///
+    /// Emits `(unpaused, fn_name)` with the admin.
+    pub fn unpause_fn(env: Env, admin: Address, fn_name: Symbol) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_CONFIG)?;
+        let flag = Self::pause_flag(&env, &fn_name)?;
+        let paused = Self::get_paused_fns(env.clone()) & !flag;
+        if paused == 0 {
+            env.storage().instance().remove(&DataKey::PausedFns);
+        } else {
+            env.storage().instance().set(&DataKey::PausedFns, &paused);
+        }
+
+        env.events()
+            .publish((symbol_short!("unpaused"), fn_name.clone()), admin.clone());
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("unpaus_fn"),
+            vec![&env, fn_name.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Mask of paused functions (`PAUSE_*` flags).
+    pub fn get_paused_fns(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PausedFns)
+            .unwrap_or(0)
+    }
+
+    /// Whether `fn_name` is paused. Unknown names are never paused.
+    pub fn is_fn_paused(env: Env, fn_name: Symbol) -> bool {
+        match Self::pause_flag(&env, &fn_name) {
+            Ok(flag) => Self::get_paused_fns(env) & flag != 0,
+            Err(_) => false,
+        }
+    }
+
+    // ==================== BATCH OPERATIONS ====================
+
+    /// Assign several roles in one call. Super-admin only.
+    ///
+    /// All-or-nothing: every item is validated before anything is written, so
+    /// a single bad item rejects the whole batch and no role changes.
+    ///
+    /// Emits one `(role, batch)` event carrying all grants.
+    pub fn grant_roles(
+        env: Env,
+        admin: Address,
+        grants: Vec<(Address, Role)>,
+    ) -> Result<u32, AuthError> {
+        Self::check_admin(&env, &admin)?;
+        Self::check_batch_len(grants.len())?;
+
+        let mut seen: Vec<Address> = Vec::new(&env);
+        for (account, _role) in grants.iter() {
+            if account == admin {
+                return Err(AuthError::ProtectedAccount);
+            }
+            if seen.contains(&account) {
+                return Err(AuthError::DuplicateBatchEntry);
+            }
+            seen.push_back(account);
+        }
+
+        for (account, role) in grants.iter() {
+            Self::store_role(&env, &account, role, None)?;
+        }
+
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("batch")),
+            grants.clone(),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("grants"),
+            vec![&env, grants.into_val(&env)],
+        );
+        Ok(seen.len())
+    }
+
+    /// Update several config values in one call. Requires `SCOPE_CONFIG`.
+    ///
+    /// Keys are `CONFIG_TIME_LOCK`, `CONFIG_COOLDOWN` and `CONFIG_STATE`; a
+    /// new state must be reachable from the current one.
+    /// All-or-nothing, like `grant_roles`.
+    ///
+    /// Emits one `(config, batch)` event carrying all updates.
+    pub fn set_configs(
+        env: Env,
+        admin: Address,
+        updates: Vec<(Symbol, u64)>,
+    ) -> Result<u32, AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_CONFIG)?;
+        Self::check_batch_len(updates.len())?;
+
+        let mut seen: Vec<Symbol> = Vec::new(&env);
+        for (key, value) in updates.iter() {
+            if key == CONFIG_COOLDOWN {
+                if value > MAX_COOLDOWN {
+                    return Err(AuthError::InvalidConfigValue);
+                }
+            } else if key == CONFIG_STATE {
+                let state = Self::state_from_u64(value)?;
+                let current = ContractState::current(&env);
+                if state != current && !ContractState::can_transition(current, state) {
+                    return Err(AuthError::InvalidStateTransition);
+                }
+            } else if key != CONFIG_TIME_LOCK {
+                return Err(AuthError::UnknownConfigKey);
+            }
+            if seen.contains(&key) {
+                return Err(AuthError::DuplicateBatchEntry);
+            }
+            seen.push_back(key);
+        }
+
+        for (key, value) in updates.iter() {
+            if key == CONFIG_TIME_LOCK {
+                env.storage().instance().set(&DataKey::TimeLock, &value);
+            } else if key == CONFIG_COOLDOWN {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::CooldownPeriod, &value);
+            } else {
+                transition_to(&env, Self::state_from_u64(value)?)?;
+            }
+        }
+
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("batch")),
+            updates.clone(),
+        );
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("configs"),
+            vec![&env, updates.into_val(&env)],
+        );
+        Ok(seen.len())
+    }
+
+    // ==================== SCOPED ADMINS ====================
+
+    /// Grant `admin` exactly the scopes in `scopes` (a mask of `SCOPE_*`
+    /// flags), replacing any previous mask. Passing `0` removes the admin.
+    ///
+    /// Only the super-admin (the address passed to `initialize`) can manage
+    /// scopes; delegated admins cannot widen their own or others' access.
+    pub fn set_admin_scopes(
+        env: Env,
+        super_admin: Address,
+        admin: Address,
+        scopes: u32,
+    ) -> Result<(), AuthError> {
+        Self::check_admin(&env, &super_admin)?;
+        if scopes & !ALL_SCOPES != 0 {
+            return Err(AuthError::InvalidScopes);
+        }
+        if admin == super_admin {
+            return Err(AuthError::ProtectedAccount);
+        }
+
+        let key = DataKey::AdminScopes(admin.clone());
+        if scopes == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &scopes);
+        }
+
+        env.events()
+            .publish((symbol_short!("scopes"), admin.clone()), scopes);
+        Self::record_audit(
+            &env,
+            &super_admin,
+            symbol_short!("scopes"),
+            vec![&env, admin.into_val(&env), scopes.into_val(&env)],
+        );
+        Ok(())
+    }
+
+    /// Scope mask held by `admin`. The super-admin reports `ALL_SCOPES`.
+    pub fn get_admin_scopes(env: Env, admin: Address) -> u32 {
+        if Self::get_admin(env.clone()) == Some(admin.clone()) {
+            return ALL_SCOPES;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::AdminScopes(admin))
+            .unwrap_or(0)
+    }
+
+    /// Replace the contract Wasm. Requires `SCOPE_UPGRADE`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), AuthError> {
+        Self::check_scope(&env, &admin, SCOPE_UPGRADE)?;
+        Self::record_audit(
+            &env,
+            &admin,
+            symbol_short!("upgrade"),
+            vec![&env, new_wasm_hash.into_val(&env)],
+        );
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    // ==================== AUDIT LOG ====================
+
+    /// Total number of admin actions recorded since deployment.
+    ///
+    /// Only the newest `AUDIT_CAPACITY` entries are retained, so the oldest
+    /// readable sequence number is `audit_len() - AUDIT_CAPACITY` once the
+    /// buffer has wrapped.
+    pub fn audit_len(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AuditCount)
+            .unwrap_or(0)
+    }
+
+    /// Return up to `limit` audit entries starting at sequence number `start`,
+    /// oldest first.
+    ///
+    /// Entries that were already overwritten are skipped, so a page may begin
+    /// after `start`. `limit` is capped at `AUDIT_PAGE_LIMIT`; page forward by
+    /// passing the last returned `seq + 1` as the next `start`.
+    pub fn get_audit_log(env: Env, start: u64, limit: u32) -> Vec<AuditEntry> {
+        let count = Self::audit_len(env.clone());
+        let oldest = count.saturating_sub(AUDIT_CAPACITY as u64);
+        let from = start.max(oldest);
+        let to = count.min(from.saturating_add(limit.min(AUDIT_PAGE_LIMIT) as u64));
+
+        let mut page = Vec::new(&env);
+        for seq in from..to {
+            let entry: AuditEntry = env
+                .storage()
+                .persistent()
+                .get(&Self::audit_slot(seq))
+                .unwrap();
+            page.push_back(entry);
+        }
+        page
+    }
+
+    // ==================== HELPERS ====================
+
+    /// Move `amount` from `from` to `to` after checking that it is positive
+    /// and covered by `from`'s balance.
+    fn move_balance(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), AuthError> {
+        if amount <= 0 {
+            return Err(AuthError::InvalidAmount);
+        }
+        let from_balance = Self::get_balance(env.clone(), from.clone());
+        let from_balance = from_balance
+            .checked_sub(amount)
+            .filter(|balance| *balance >= 0)
+            .ok_or(AuthError::InsufficientBalance)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(from.clone()), &from_balance);
+
+        // Read after the debit, so a transfer to oneself nets out.
+        let to_balance = Self::get_balance(env.clone(), to.clone())
+            .checked_add(amount)
+            .ok_or(AuthError::InvalidAmount)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to.clone()), &to_balance);
+        Ok(())
+    }
+
+    /// Authenticate `caller` and check that it is the stored admin.
+    fn check_admin(env: &Env, caller: &Address) -> Result<(), AuthError> {
+        caller.require_auth();
+        if Self::get_admin(env.clone()) != Some(caller.clone()) {
             return Err(AuthError::NotAdmin);
         }
-        
-        env.storage().persistent().set(&DataKey::Balance(user), &amount);
         Ok(())
     }
 
-    /// Get balance
-    pub fn get_balance(env: Env, user: Address) -> i128 {
-        env.storage().persistent().get(&DataKey::Balance(user)).unwrap_or(0)
+    /// `FunctionPaused` if the function behind `flag` is paused.
+    fn when_not_paused(env: &Env, flag: u32) -> Result<(), AuthError> {
+        if Self::get_paused_fns(env.clone()) & flag != 0 {
+            return Err(AuthError::FunctionPaused);
+        }
+        Ok(())
     }
 
-    /// Approve allowance
-    pub fn approve(env: Env, from: Address, spender: Address, amount: i128) -> Result<(), AuthError> {
-        from.require_auth();
-        env.storage().persistent().set(&DataKey::Allowance(from, spender), &amount);
+    /// The `PAUSE_*` flag of a pausable function.
+    fn pause_flag(env: &Env, fn_name: &Symbol) -> Result<u32, AuthError> {
+        let pausable = [
+            (symbol_short!("transfer"), PAUSE_TRANSFER),
+            (Symbol::new(env, "transfer_from"), PAUSE_TRANSFER_FROM),
+            (symbol_short!("approve"), PAUSE_APPROVE),
+        ];
+        pausable
+            .into_iter()
+            .find(|(name, _)| name == fn_name)
+            .map(|(_, flag)| flag)
+            .ok_or(AuthError::UnknownFunction)
+    }
+
+    fn check_batch_len(len: u32) -> Result<(), AuthError> {
+        if len == 0 {
+            return Err(AuthError::EmptyBatch);
+        }
+        if len > MAX_BATCH_SIZE {
+            return Err(AuthError::BatchTooLarge);
+        }
         Ok(())
     }
 
-    /// Transfer from allowance
-    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) -> Result<(), AuthError> {
-        spender.require_auth();
-        
-        let allowance: i128 = env
+    fn state_from_u64(value: u64) -> Result<ContractState, AuthError> {
+        match value {
+            0 => Ok(ContractState::Active),
+            1 => Ok(ContractState::Paused),
+            2 => Ok(ContractState::Frozen),
+            _ => Err(AuthError::InvalidConfigValue),
+        }
+    }
+
+    /// Authenticate `caller` and check that it holds `scope`.
+    ///
+    /// Callers without any scope get `NotAdmin`; delegated admins that lack
+    /// this particular scope get `MissingScope`.
+    fn check_scope(env: &Env, caller: &Address, scope: u32) -> Result<(), AuthError> {
+        caller.require_auth();
+        let scopes = Self::get_admin_scopes(env.clone(), caller.clone());
+        if scopes == 0 {
+            return Err(AuthError::NotAdmin);
+        }
+        if scopes & scope == 0 {
+            return Err(AuthError::MissingScope);
+        }
+        Ok(())
+    }
+
+    /// The role of `account`, unless none is stored or it has expired.
+    fn active_role(env: &Env, account: &Address) -> Result<Role, AuthError> {
+        let role: Role = env
             .storage()
             .persistent()
-            .get(&DataKey::Allowance(from.clone(), spender.clone()))
-            .unwrap_or(0);
-        
-        if allowance < amount {
-            return Err(AuthError::Unauthorized);
+            .get(&DataKey::Role(account.clone()))
+            .ok_or(AuthError::InsufficientRole)?;
+        let expiry: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoleExpiry(account.clone()));
+        match expiry {
+            Some(expires_at) if env.ledger().timestamp() >= expires_at => {
+                Err(AuthError::RoleExpired)
+            }
+            _ => Ok(role),
+        }
+    }
+
+    fn role_holders(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoleHolders)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Write `role` and its expiry for `account` and add it to the index.
+    ///
+    /// A full index is first pruned of expired roles; `TooManyRoleHolders`
+    /// only if none have expired.
+    fn store_role(
+        env: &Env,
+        account: &Address,
+        role: Role,
+        expires_at: Option<u64>,
+    ) -> Result<(), AuthError> {
+        let mut holders = Self::role_holders(env);
+        if !holders.contains(account) {
+            if holders.len() >= MAX_ROLE_HOLDERS {
+                for holder in holders.clone().iter() {
+                    if Self::active_role(env, &holder) == Err(AuthError::RoleExpired) {
+                        Self::remove_role(env, &holder);
+                    }
+                }
+                holders = Self::role_holders(env);
+                if holders.len() >= MAX_ROLE_HOLDERS {
+                    return Err(AuthError::TooManyRoleHolders);
+                }
+            }
+            holders.push_back(account.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::RoleHolders, &holders);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(account.clone()), &role);
+        let expiry_key = DataKey::RoleExpiry(account.clone());
+        match expires_at {
+            Some(expires_at) => env.storage().persistent().set(&expiry_key, &expires_at),
+            None => env.storage().persistent().remove(&expiry_key),
         }
-        
-        let from_balance: i128 = env.storage().persistent().get(&DataKey::Balance(from.clone())).unwrap_or(0);
-        let to_balance: i128 = env.storage().persistent().get(&DataKey::Balance(to.clone())).unwrap_or(0);
-        
-        env.storage().persistent().set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage().persistent().set(&DataKey::Balance(to), &(to_balance + amount));
-        env.storage().persistent().set(&DataKey::Allowance(from, spender), &(allowance - amount));
-        
         Ok(())
     }
 
-    /// Multi-signature operation
-    pub fn multi_sig_action(env: Env, signers: Vec<Address>, value: u32) -> u32 {
-        for signer in signers.iter() {
-            signer.require_auth();
+    /// Delete the role and expiry of `account` and drop it from the index.
+    fn remove_role(env: &Env, account: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Role(account.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RoleExpiry(account.clone()));
+        let mut holders = Self::role_holders(env);
+        if let Some(index) = holders.first_index_of(account) {
+            holders.remove(index);
+            env.storage()
+                .persistent()
+                .set(&DataKey::RoleHolders, &holders);
         }
-        value + signers.len()
     }
 
-    /// Emit event with authentication
-    pub fn emit_event(env: Env, user: Address, message: Symbol) {
-        user.require_auth();
-        env.events().publish((symbol_short!("event"), user), message);
+    fn audit_slot(seq: u64) -> DataKey {
+        DataKey::AuditSlot((seq % AUDIT_CAPACITY as u64) as u32)
+    }
+
+    /// Append an entry to the ring buffer, overwriting the oldest slot once
+    /// `AUDIT_CAPACITY` entries exist.
+    fn record_audit(env: &Env, actor: &Address, action: Symbol, args: Vec<Val>) {
+        let seq = Self::audit_len(env.clone());
+        let entry = AuditEntry {
+            seq,
+            actor: actor.clone(),
+            action,
+            args_hash: env.crypto().sha256(&args.to_xdr(env)).into(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&Self::audit_slot(seq), &entry);
+        env.storage()
+            .instance()
+            .set(&DataKey::AuditCount, &(seq + 1));
     }
 }
 
 mod test;
+mod test_auth;
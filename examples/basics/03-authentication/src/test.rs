@@ -1,16 +1,30 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, vec, Env};
+use crate::test_auth::{invocation, TestSigner};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, Env,
+};
+
+fn setup_initialized_contract() -> (Env, Address, Address, AuthContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AuthContract);
+    let client = AuthContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    (env, contract_id, admin, client)
+}
 
 #[test]
 fn test_check_auth() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let user = Address::generate(&env);
     env.mock_all_auths();
-    
+
     assert!(client.check_auth(&user));
 }
 
@@ -19,10 +33,10 @@ fn test_initialize() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     assert_eq!(client.get_admin(), Some(admin.clone()));
 }
@@ -33,10 +47,10 @@ fn test_initialize_twice_fails() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.initialize(&admin);
 }
@@ -46,10 +60,10 @@ fn test_admin_action() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     let result = client.admin_action(&admin, &10);
     assert_eq!(result, 20);
@@ -61,11 +75,11 @@ fn test_admin_action_unauthorized() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     let non_admin = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.admin_action(&non_admin, &10);
 }
@@ -75,17 +89,17 @@ fn test_transfer() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.set_balance(&admin, &user1, &1000);
-    
+
     client.transfer(&user1, &user2, &300);
-    
+
     assert_eq!(client.get_balance(&user1), 700);
     assert_eq!(client.get_balance(&user2), 300);
 }
@@ -95,19 +109,19 @@ fn test_approve_and_transfer_from() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     let owner = Address::generate(&env);
     let spender = Address::generate(&env);
     let recipient = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.set_balance(&admin, &owner, &1000);
     client.approve(&owner, &spender, &500);
-    
+
     client.transfer_from(&spender, &owner, &recipient, &200);
-    
+
     assert_eq!(client.get_balance(&owner), 800);
     assert_eq!(client.get_balance(&recipient), 200);
 }
@@ -118,31 +132,107 @@ fn test_transfer_from_insufficient_allowance() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     let owner = Address::generate(&env);
     let spender = Address::generate(&env);
     let recipient = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.set_balance(&admin, &owner, &1000);
     client.approve(&owner, &spender, &100);
-    
+
     client.transfer_from(&spender, &owner, &recipient, &200);
 }
 
+#[test]
+fn test_transfer_rejects_non_positive_amount() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.set_balance(&admin, &bob, &100);
+
+    // A negative amount would otherwise move funds from `to` to `from`.
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &-50),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &0),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(client.get_balance(&alice), 0);
+    assert_eq!(client.get_balance(&bob), 100);
+}
+
+#[test]
+fn test_transfer_rejects_overdraw() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.set_balance(&admin, &alice, &100);
+
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &101),
+        Err(Ok(AuthError::InsufficientBalance))
+    );
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.get_balance(&alice), 0);
+    assert_eq!(client.get_balance(&bob), 100);
+}
+
+#[test]
+fn test_transfer_from_rejects_non_positive_amount() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let victim = Address::generate(&env);
+    client.set_balance(&admin, &victim, &100);
+    client.approve(&owner, &spender, &10);
+
+    // A negative amount would pass `allowance < amount`, raise the
+    // allowance and take funds from `to`.
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &victim, &-50),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &victim, &0),
+        Err(Ok(AuthError::InvalidAmount))
+    );
+    assert_eq!(client.get_balance(&victim), 100);
+}
+
+#[test]
+fn test_transfer_from_rejects_overdraw() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.set_balance(&admin, &owner, &50);
+    client.approve(&owner, &spender, &500);
+
+    // The allowance covers it; the balance does not.
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &recipient, &51),
+        Err(Ok(AuthError::InsufficientBalance))
+    );
+    assert_eq!(client.get_balance(&owner), 50);
+    assert_eq!(client.get_balance(&recipient), 0);
+}
+
 #[test]
 fn test_multi_sig_action() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let signer1 = Address::generate(&env);
     let signer2 = Address::generate(&env);
     let signer3 = Address::generate(&env);
     env.mock_all_auths();
-    
+
     let signers = vec![&env, signer1, signer2, signer3];
     let result = client.multi_sig_action(&signers, &10);
     assert_eq!(result, 13);
@@ -153,10 +243,10 @@ fn test_emit_event() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let user = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.emit_event(&user, &symbol_short!("hello"));
 }
 
@@ -165,14 +255,14 @@ fn test_set_balance_admin_only() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.set_balance(&admin, &user, &5000);
-    
+
     assert_eq!(client.get_balance(&user), 5000);
 }
 
@@ -182,12 +272,12 @@ fn test_set_balance_non_admin_fails() {
     let env = Env::default();
     let contract_id = env.register_contract(None, AuthContract);
     let client = AuthContractClient::new(&env, &contract_id);
-    
+
     let admin = Address::generate(&env);
     let non_admin = Address::generate(&env);
     let user = Address::generate(&env);
     env.mock_all_auths();
-    
+
     client.initialize(&admin);
     client.set_balance(&non_admin, &user, &5000);
 }
@@ -233,14 +323,16 @@ fn test_multi_party_cooldown_isolation() {
 }
 
 #[test]
-#[should_panic(expected = "Not admin")]
 fn test_non_admin_cannot_grant_roles() {
     let (env, _contract_id, admin, client) = setup_initialized_contract();
     let attacker = Address::generate(&env);
     let victim = Address::generate(&env);
 
     client.grant_role(&admin, &attacker, &Role::User);
-    client.grant_role(&attacker, &victim, &Role::Admin);
+    assert_eq!(
+        client.try_grant_role(&attacker, &victim, &Role::Admin),
+        Err(Ok(AuthError::NotAdmin))
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -261,11 +353,13 @@ fn test_role_overwrite() {
 }
 
 #[test]
-#[should_panic(expected = "No role assigned")]
-fn test_get_role_unassigned_panics() {
+fn test_get_role_unassigned() {
     let (env, _contract_id, _admin, client) = setup_initialized_contract();
     let unassigned = Address::generate(&env);
-    client.get_role(&unassigned);
+    assert_eq!(
+        client.try_get_role(&unassigned),
+        Err(Ok(AuthError::InsufficientRole))
+    );
 }
 
 #[test]
@@ -282,6 +376,23 @@ fn test_cooldown_zero_period() {
     assert_eq!(result, 100);
 }
 
+#[test]
+fn test_cooldown_rejects_period_over_max() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+
+    client.set_cooldown(&admin, &MAX_COOLDOWN);
+    let recorded = client.audit_len();
+    assert_eq!(
+        client.try_set_cooldown(&admin, &u64::MAX),
+        Err(Ok(AuthError::InvalidConfigValue))
+    );
+    assert_eq!(
+        client.try_set_cooldown(&admin, &(MAX_COOLDOWN + 1)),
+        Err(Ok(AuthError::InvalidConfigValue))
+    );
+    assert_eq!(client.audit_len(), recorded);
+}
+
 #[test]
 fn test_time_lock_zero_allows_immediate() {
     let (env, _contract_id, admin, client) = setup_initialized_contract();
@@ -307,28 +418,990 @@ fn test_revoke_nonexistent_role() {
 }
 
 #[test]
-#[should_panic(expected = "Not admin")]
 fn test_non_admin_cannot_set_state() {
     let (env, _contract_id, admin, client) = setup_initialized_contract();
     let user = Address::generate(&env);
     client.grant_role(&admin, &user, &Role::User);
-    client.set_state(&user, &ContractState::Paused);
+    assert_eq!(
+        client.try_set_state(&user, &ContractState::Paused),
+        Err(Ok(AuthError::NotAdmin))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Not admin")]
 fn test_non_admin_cannot_set_time_lock() {
     let (env, _contract_id, admin, client) = setup_initialized_contract();
     let user = Address::generate(&env);
     client.grant_role(&admin, &user, &Role::User);
-    client.set_time_lock(&user, &1000);
+    assert_eq!(
+        client.try_set_time_lock(&user, &1000),
+        Err(Ok(AuthError::NotAdmin))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Not admin")]
 fn test_non_admin_cannot_set_cooldown() {
     let (env, _contract_id, admin, client) = setup_initialized_contract();
     let user = Address::generate(&env);
     client.grant_role(&admin, &user, &Role::User);
-    client.set_cooldown(&user, &100);
+    assert_eq!(
+        client.try_set_cooldown(&user, &100),
+        Err(Ok(AuthError::NotAdmin))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// 10. Audit log tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_audit_log_records_admin_actions() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.grant_role(&admin, &user, &Role::Moderator);
+
+    assert_eq!(client.audit_len(), 2);
+    let log = client.get_audit_log(&0, &10);
+    assert_eq!(log.len(), 2);
+
+    let init = log.get(0).unwrap();
+    assert_eq!(init.seq, 0);
+    assert_eq!(init.action, symbol_short!("init"));
+
+    let grant = log.get(1).unwrap();
+    assert_eq!(grant.seq, 1);
+    assert_eq!(grant.actor, admin);
+    assert_eq!(grant.action, symbol_short!("grant"));
+    assert_eq!(grant.timestamp, 500);
+
+    // The stored hash matches the arguments the admin actually passed.
+    let args: Vec<Val> = vec![&env, user.into_val(&env), Role::Moderator.into_val(&env)];
+    let expected: BytesN<32> = env.crypto().sha256(&args.to_xdr(&env)).into();
+    assert_eq!(grant.args_hash, expected);
+}
+
+#[test]
+fn test_audit_log_ignores_non_admin_calls() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+
+    client.check_auth(&user);
+    client.update_user_data(&user, &symbol_short!("data"));
+    assert_eq!(client.audit_len(), 1);
+}
+
+#[test]
+fn test_audit_log_paging() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+    for period in 0..9u64 {
+        client.set_cooldown(&admin, &period);
+    }
+    assert_eq!(client.audit_len(), 10);
+
+    let first = client.get_audit_log(&0, &4);
+    assert_eq!(first.len(), 4);
+    let next_start = first.last().unwrap().seq + 1;
+
+    let second = client.get_audit_log(&next_start, &4);
+    assert_eq!(second.get(0).unwrap().seq, 4);
+
+    let tail = client.get_audit_log(&8, &4);
+    assert_eq!(tail.len(), 2);
+    assert!(client.get_audit_log(&10, &4).is_empty());
+}
+
+#[test]
+fn test_audit_log_page_limit() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+    for period in 0..25u64 {
+        client.set_cooldown(&admin, &period);
+    }
+    assert_eq!(client.audit_len(), 26);
+
+    // Requests larger than the page limit are capped.
+    let page = client.get_audit_log(&0, &1000);
+    assert_eq!(page.len(), AUDIT_PAGE_LIMIT);
+    assert_eq!(page.last().unwrap().seq, AUDIT_PAGE_LIMIT as u64 - 1);
+
+    // A start near `u64::MAX` returns nothing instead of overflowing.
+    assert!(client.get_audit_log(&u64::MAX, &u32::MAX).is_empty());
+}
+
+#[test]
+fn test_audit_log_ring_buffer_wraps() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+    let total = AUDIT_CAPACITY as u64 + 5;
+    for value in 1..total as u32 {
+        client.admin_action(&admin, &value);
+    }
+    assert_eq!(client.audit_len(), total);
+
+    // The first five entries were overwritten; paging from 0 starts at the
+    // oldest retained entry.
+    let page = client.get_audit_log(&0, &AUDIT_PAGE_LIMIT);
+    assert_eq!(page.len(), AUDIT_PAGE_LIMIT);
+    assert_eq!(page.get(0).unwrap().seq, 5);
+
+    let newest = client.get_audit_log(&(total - 1), &1).get(0).unwrap();
+    assert_eq!(newest.seq, total - 1);
+    assert_eq!(newest.action, symbol_short!("admin_act"));
+}
+
+// ---------------------------------------------------------------------------
+// 11. Scoped admin tests
+// ---------------------------------------------------------------------------
+
+/// Every scope-gated function paired with the scope it requires. Each closure
+/// calls the function as `caller` and reports whether the scope check passed.
+#[allow(clippy::type_complexity)]
+fn scoped_functions() -> [(&'static str, u32, fn(&AuthContractClient, &Address) -> bool); 7] {
+    [
+        ("set_time_lock", SCOPE_CONFIG, |c, a| {
+            c.try_set_time_lock(a, &0).is_ok()
+        }),
+        ("set_cooldown", SCOPE_CONFIG, |c, a| {
+            c.try_set_cooldown(a, &0).is_ok()
+        }),
+        ("set_state", SCOPE_CONFIG, |c, a| {
+            c.try_set_state(a, &ContractState::Active).is_ok()
+        }),
+        ("pause_fn", SCOPE_CONFIG, |c, a| {
+            c.try_pause_fn(a, &symbol_short!("approve")).is_ok()
+        }),
+        ("unpause_fn", SCOPE_CONFIG, |c, a| {
+            c.try_unpause_fn(a, &symbol_short!("approve")).is_ok()
+        }),
+        ("set_balance", SCOPE_FUNDS, |c, a| {
+            c.try_set_balance(a, a, &1).is_ok()
+        }),
+        ("upgrade", SCOPE_UPGRADE, |c, a| {
+            // No Wasm is installed under this hash, so an authorized call
+            // still fails — but with a host error, not an `AuthError`.
+            let hash = BytesN::from_array(&c.env, &[0; 32]);
+            !matches!(c.try_upgrade(a, &hash), Err(Ok(_)))
+        }),
+    ]
+}
+
+#[test]
+fn test_scope_function_matrix() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+
+    for mask in 0..=ALL_SCOPES {
+        let delegate = Address::generate(&env);
+        if mask != 0 {
+            client.set_admin_scopes(&admin, &delegate, &mask);
+        }
+        assert_eq!(client.get_admin_scopes(&delegate), mask);
+
+        for (name, scope, call) in scoped_functions() {
+            let expected = mask & scope != 0;
+            assert_eq!(
+                call(&client, &delegate),
+                expected,
+                "{name} with scopes {mask:#05b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_super_admin_holds_every_scope() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+    assert_eq!(client.get_admin_scopes(&admin), ALL_SCOPES);
+    for (name, _scope, call) in scoped_functions() {
+        assert!(call(&client, &admin), "{name}");
+    }
+}
+
+#[test]
+fn test_scope_errors_distinguish_non_admin_from_missing_scope() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let outsider = Address::generate(&env);
+    let config_admin = Address::generate(&env);
+    client.set_admin_scopes(&admin, &config_admin, &SCOPE_CONFIG);
+
+    assert_eq!(
+        client.try_set_balance(&outsider, &outsider, &1),
+        Err(Ok(AuthError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_set_balance(&config_admin, &outsider, &1),
+        Err(Ok(AuthError::MissingScope))
+    );
+}
+
+#[test]
+fn test_funds_admin_cannot_change_config() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let funds_admin = Address::generate(&env);
+    client.set_admin_scopes(&admin, &funds_admin, &SCOPE_FUNDS);
+    assert_eq!(
+        client.try_set_state(&funds_admin, &ContractState::Frozen),
+        Err(Ok(AuthError::MissingScope))
+    );
+}
+
+#[test]
+fn test_removing_scopes_revokes_access() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let delegate = Address::generate(&env);
+
+    client.set_admin_scopes(&admin, &delegate, &ALL_SCOPES);
+    client.set_cooldown(&delegate, &10);
+
+    client.set_admin_scopes(&admin, &delegate, &0);
+    assert_eq!(client.get_admin_scopes(&delegate), 0);
+    assert_eq!(
+        client.try_set_cooldown(&delegate, &10),
+        Err(Ok(AuthError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_delegated_admin_cannot_manage_scopes() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let delegate = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.set_admin_scopes(&admin, &delegate, &ALL_SCOPES);
+    assert_eq!(
+        client.try_set_admin_scopes(&delegate, &other, &SCOPE_FUNDS),
+        Err(Ok(AuthError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_unknown_scope_bits_rejected() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    assert_eq!(
+        client.try_set_admin_scopes(&admin, &Address::generate(&env), &(1 << 5)),
+        Err(Ok(AuthError::InvalidScopes))
+    );
+    assert_eq!(
+        client.try_set_admin_scopes(&admin, &admin, &SCOPE_FUNDS),
+        Err(Ok(AuthError::ProtectedAccount))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// 12. Batch operation tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_grant_roles_applies_all_items() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let moderator = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let grants = vec![
+        &env,
+        (moderator.clone(), Role::Moderator),
+        (user.clone(), Role::User),
+    ];
+    assert_eq!(client.grant_roles(&admin, &grants), 2);
+    assert!(client.has_role(&moderator, &Role::Moderator));
+    assert!(client.has_role(&user, &Role::User));
+
+    // One aggregated event and one audit entry for the whole batch.
+    let (_id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        vec![
+            &env,
+            symbol_short!("role").into_val(&env),
+            symbol_short!("batch").into_val(&env)
+        ]
+    );
+    let emitted: Vec<(Address, Role)> = data.into_val(&env);
+    assert_eq!(emitted, grants);
+    assert_eq!(client.audit_len(), 2);
+    let entry = client.get_audit_log(&1, &1).get(0).unwrap();
+    assert_eq!(entry.action, symbol_short!("grants"));
+}
+
+#[test]
+fn test_grant_roles_is_all_or_nothing() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    // The last item is invalid, so the valid ones before it are not applied.
+    let grants = vec![
+        &env,
+        (first.clone(), Role::Moderator),
+        (second.clone(), Role::User),
+        (first.clone(), Role::User),
+    ];
+    assert_eq!(
+        client.try_grant_roles(&admin, &grants),
+        Err(Ok(AuthError::DuplicateBatchEntry))
+    );
+    assert!(!client.has_role(&first, &Role::Moderator));
+    assert!(!client.has_role(&second, &Role::User));
+    assert_eq!(client.audit_len(), 1);
+}
+
+#[test]
+fn test_grant_roles_validation_errors() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let outsider = Address::generate(&env);
+    let one = vec![&env, (Address::generate(&env), Role::User)];
+
+    assert_eq!(
+        client.try_grant_roles(&outsider, &one),
+        Err(Ok(AuthError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_grant_roles(&admin, &Vec::new(&env)),
+        Err(Ok(AuthError::EmptyBatch))
+    );
+    assert_eq!(
+        client.try_grant_roles(&admin, &vec![&env, (admin.clone(), Role::User)]),
+        Err(Ok(AuthError::ProtectedAccount))
+    );
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_BATCH_SIZE {
+        too_many.push_back((Address::generate(&env), Role::User));
+    }
+    assert_eq!(
+        client.try_grant_roles(&admin, &too_many),
+        Err(Ok(AuthError::BatchTooLarge))
+    );
+}
+
+#[test]
+fn test_set_configs_applies_all_items() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let caller = Address::generate(&env);
+
+    let updates = vec![
+        &env,
+        (CONFIG_TIME_LOCK, 1_000u64),
+        (CONFIG_COOLDOWN, 60u64),
+        (CONFIG_STATE, ContractState::Paused as u64),
+    ];
+    assert_eq!(client.set_configs(&admin, &updates), 3);
+    assert_eq!(client.get_state(), ContractState::Paused as u32);
+
+    env.ledger().with_mut(|li| li.timestamp = 999);
+    assert_eq!(
+        client.try_time_locked_action(&caller),
+        Err(Ok(AuthError::TimeLocked))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.time_locked_action(&caller);
+
+    let (_id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        vec![
+            &env,
+            symbol_short!("config").into_val(&env),
+            symbol_short!("batch").into_val(&env)
+        ]
+    );
+    let emitted: Vec<(Symbol, u64)> = data.into_val(&env);
+    assert_eq!(emitted, updates);
+}
+
+#[test]
+fn test_set_configs_is_all_or_nothing() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+
+    let cases = [
+        (
+            vec![
+                &env,
+                (CONFIG_STATE, ContractState::Frozen as u64),
+                (symbol_short!("fee"), 1u64),
+            ],
+            AuthError::UnknownConfigKey,
+        ),
+        (
+            vec![
+                &env,
+                (CONFIG_STATE, ContractState::Frozen as u64),
+                (CONFIG_COOLDOWN, MAX_COOLDOWN + 1),
+            ],
+            AuthError::InvalidConfigValue,
+        ),
+        (
+            vec![
+                &env,
+                (CONFIG_STATE, ContractState::Frozen as u64),
+                (CONFIG_STATE, 3u64),
+            ],
+            AuthError::InvalidConfigValue,
+        ),
+        (
+            vec![
+                &env,
+                (CONFIG_STATE, ContractState::Frozen as u64),
+                (CONFIG_STATE, ContractState::Paused as u64),
+            ],
+            AuthError::DuplicateBatchEntry,
+        ),
+    ];
+    for (updates, error) in cases {
+        assert_eq!(client.try_set_configs(&admin, &updates), Err(Ok(error)));
+        // The valid first item was not applied either.
+        assert_eq!(client.get_state(), ContractState::Active as u32);
+    }
+    assert_eq!(client.audit_len(), 1);
+}
+
+#[test]
+fn test_set_configs_requires_config_scope() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let funds_admin = Address::generate(&env);
+    client.set_admin_scopes(&admin, &funds_admin, &SCOPE_FUNDS);
+    let config_admin = Address::generate(&env);
+    client.set_admin_scopes(&admin, &config_admin, &SCOPE_CONFIG);
+
+    let updates = vec![&env, (CONFIG_COOLDOWN, 5u64)];
+    assert_eq!(
+        client.try_set_configs(&funds_admin, &updates),
+        Err(Ok(AuthError::MissingScope))
+    );
+    assert_eq!(client.set_configs(&config_admin, &updates), 1);
+}
+
+// ---------------------------------------------------------------------------
+// 13. Signed authorization tests (no mock_all_auths)
+// ---------------------------------------------------------------------------
+
+/// Initialized contract where `from` holds 100, funded while auths are
+/// still mocked. Callers switch to real signatures with `env.set_auths`.
+fn setup_signed_transfer() -> (Env, AuthContractClient<'static>, TestSigner, Address) {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let from = TestSigner::new(&env, 1);
+    let to = Address::generate(&env);
+    client.set_balance(&admin, &from.address, &100);
+    (env, client, from, to)
+}
+
+#[test]
+fn test_signed_transfer_succeeds() {
+    let (env, client, from, to) = setup_signed_transfer();
+    let args = (from.address.clone(), to.clone(), 30_i128).into_val(&env);
+    let signed = invocation(&env, &client.address, "transfer", args, &[]);
+
+    env.set_auths(&[from.authorize(&env, 1, signed)]);
+    client.transfer(&from.address, &to, &30);
+
+    assert_eq!(client.get_balance(&from.address), 70);
+    assert_eq!(client.get_balance(&to), 30);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_transfer_without_entry_fails() {
+    let (env, client, from, to) = setup_signed_transfer();
+    env.set_auths(&[]);
+    client.transfer(&from.address, &to, &30);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_entry_for_other_arguments_fails() {
+    let (env, client, from, to) = setup_signed_transfer();
+    // Signed for 30; the call asks for 90.
+    let args = (from.address.clone(), to.clone(), 30_i128).into_val(&env);
+    let signed = invocation(&env, &client.address, "transfer", args, &[]);
+
+    env.set_auths(&[from.authorize(&env, 1, signed)]);
+    client.transfer(&from.address, &to, &90);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_entry_from_wrong_signer_fails() {
+    let (env, client, from, to) = setup_signed_transfer();
+    let stranger = TestSigner::new(&env, 2);
+    let args = (from.address.clone(), to.clone(), 30_i128).into_val(&env);
+    let signed = invocation(&env, &client.address, "transfer", args, &[]);
+
+    env.set_auths(&[stranger.authorize(&env, 1, signed)]);
+    client.transfer(&from.address, &to, &30);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, ExistingValue)")]
+fn test_replayed_entry_fails() {
+    let (env, client, from, to) = setup_signed_transfer();
+    let args = (from.address.clone(), to.clone(), 30_i128).into_val(&env);
+    let signed = invocation(&env, &client.address, "transfer", args, &[]);
+    let entries = [from.authorize(&env, 1, signed)];
+
+    env.set_auths(&entries);
+    client.transfer(&from.address, &to, &30);
+    // The host recorded nonce 1 for `from`; the same entry is rejected.
+    env.set_auths(&entries);
+    client.transfer(&from.address, &to, &30);
+}
+
+#[test]
+fn test_multi_sig_needs_one_entry_per_signer() {
+    let (env, _contract_id, _admin, client) = setup_initialized_contract();
+    let alice = TestSigner::new(&env, 1);
+    let bob = TestSigner::new(&env, 2);
+    let signers = vec![&env, alice.address.clone(), bob.address.clone()];
+    let signed = || {
+        let args = (signers.clone(), 7_u32).into_val(&env);
+        invocation(&env, &client.address, "multi_sig_action", args, &[])
+    };
+
+    // Alice alone is not enough.
+    env.set_auths(&[alice.authorize(&env, 1, signed())]);
+    assert!(client.try_multi_sig_action(&signers, &7).is_err());
+
+    env.set_auths(&[
+        alice.authorize(&env, 2, signed()),
+        bob.authorize(&env, 1, signed()),
+    ]);
+    assert_eq!(client.multi_sig_action(&signers, &7), 9);
+}
+
+// ---------------------------------------------------------------------------
+// 14. Role hierarchy and expiry tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_min_role_follows_hierarchy() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let moderator = Address::generate(&env);
+    let user = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    client.grant_role(&admin, &moderator, &Role::Moderator);
+    client.grant_role(&admin, &user, &Role::User);
+
+    for (caller, held) in [
+        (&admin, Role::Admin),
+        (&moderator, Role::Moderator),
+        (&user, Role::User),
+    ] {
+        for min in [Role::Admin, Role::Moderator, Role::User] {
+            let expected = if held.satisfies(min) {
+                Ok(Ok(held))
+            } else {
+                Err(Ok(AuthError::InsufficientRole))
+            };
+            assert_eq!(client.try_require_min_role(caller, &min), expected);
+        }
+    }
+    assert_eq!(
+        client.try_require_min_role(&outsider, &Role::User),
+        Err(Ok(AuthError::InsufficientRole))
+    );
+}
+
+#[test]
+fn test_user_cannot_call_moderator_action() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    client.grant_role(&admin, &user, &Role::User);
+    assert_eq!(
+        client.try_moderator_action(&user, &10),
+        Err(Ok(AuthError::InsufficientRole))
+    );
+}
+
+#[test]
+fn test_role_expires() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let temp = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    assert_eq!(
+        client.try_grant_role_until(&admin, &temp, &Role::Moderator, &1_000),
+        Err(Ok(AuthError::InvalidExpiry))
+    );
+    client.grant_role_until(&admin, &temp, &Role::Moderator, &2_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_999);
+    assert_eq!(client.moderator_action(&temp, &1), 101);
+    assert!(client.has_role(&temp, &Role::Moderator));
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(
+        client.try_require_min_role(&temp, &Role::User),
+        Err(Ok(AuthError::RoleExpired))
+    );
+    assert!(!client.has_role(&temp, &Role::Moderator));
+    assert_eq!(client.list_role_holders().len(), 1);
+
+    // A permanent grant clears the expiry
+    client.grant_role(&admin, &temp, &Role::User);
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    assert_eq!(client.require_min_role(&temp, &Role::User), Role::User);
+}
+
+#[test]
+fn test_expired_moderator_cannot_act() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let temp = Address::generate(&env);
+    client.grant_role_until(&admin, &temp, &Role::Moderator, &100);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.try_moderator_action(&temp, &1),
+        Err(Ok(AuthError::RoleExpired))
+    );
+}
+
+#[test]
+fn test_list_role_holders_tracks_grants_and_revokes() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let moderator = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.grant_role(&admin, &moderator, &Role::Moderator);
+    client.grant_role_until(&admin, &user, &Role::User, &500);
+    // Re-granting does not add a second entry
+    client.grant_role(&admin, &moderator, &Role::User);
+
+    assert_eq!(
+        client.list_role_holders(),
+        vec![
+            &env,
+            RoleHolder {
+                account: admin.clone(),
+                role: Role::Admin,
+                expires_at: None,
+            },
+            RoleHolder {
+                account: moderator.clone(),
+                role: Role::User,
+                expires_at: None,
+            },
+            RoleHolder {
+                account: user.clone(),
+                role: Role::User,
+                expires_at: Some(500),
+            },
+        ]
+    );
+
+    client.revoke_role(&admin, &moderator);
+    let holders = client.list_role_holders();
+    assert_eq!(holders.len(), 2);
+    assert_eq!(holders.get(1).unwrap().account, user);
+}
+
+#[test]
+fn test_full_role_index_prunes_expired_roles() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let temp = Address::generate(&env);
+    client.grant_role_until(&admin, &temp, &Role::User, &100);
+    for _ in 2..MAX_ROLE_HOLDERS {
+        client.grant_role(&admin, &Address::generate(&env), &Role::User);
+    }
+    let newcomer = vec![&env, (Address::generate(&env), Role::User)];
+    assert_eq!(
+        client.try_grant_roles(&admin, &newcomer),
+        Err(Ok(AuthError::TooManyRoleHolders))
+    );
+
+    // Once `temp` expires, its slot is reclaimed
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.grant_roles(&admin, &newcomer), 1);
+    let holders = client.list_role_holders();
+    assert_eq!(holders.len(), MAX_ROLE_HOLDERS);
+    assert!(!holders.iter().any(|h| h.account == temp));
+}
+
+// ---------------------------------------------------------------------------
+// 15. Two-step admin transfer tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_transfer_propose_and_accept() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let new_admin = Address::generate(&env);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("admin"), symbol_short!("proposed")).into_val(&env),
+                (admin.clone(), new_admin.clone()).into_val(&env),
+            )
+        ]
+    );
+
+    client.accept_admin(&new_admin);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id,
+                (symbol_short!("admin"), symbol_short!("accepted")).into_val(&env),
+                (admin.clone(), new_admin.clone()).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_pending_admin(), None);
+    assert!(client.has_role(&new_admin, &Role::Admin));
+    assert!(!client.has_role(&admin, &Role::Admin));
+    assert_eq!(client.get_admin_scopes(&new_admin), ALL_SCOPES);
+
+    assert_eq!(client.admin_action(&new_admin, &4), 8);
+    assert_eq!(
+        client.try_admin_action(&admin, &4),
+        Err(Ok(AuthError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_unaccepted_admin_proposal_changes_nothing() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let new_admin = Address::generate(&env);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+    assert_eq!(
+        client.try_admin_action(&new_admin, &1),
+        Err(Ok(AuthError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_propose_admin(&new_admin, &new_admin),
+        Err(Ok(AuthError::NotAdmin))
+    );
+
+    client.cancel_admin_transfer(&admin);
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(AuthError::NoPendingAdmin))
+    );
+    assert_eq!(
+        client.try_cancel_admin_transfer(&admin),
+        Err(Ok(AuthError::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn test_admin_transfer_wrong_acceptor() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let new_admin = Address::generate(&env);
+    let intruder = Address::generate(&env);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(
+        client.try_accept_admin(&intruder),
+        Err(Ok(AuthError::NotPendingAdmin))
+    );
+
+    // A newer proposal replaces the old one
+    client.propose_admin(&admin, &intruder);
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(AuthError::NotPendingAdmin))
+    );
+    client.accept_admin(&intruder);
+    assert_eq!(client.get_admin(), Some(intruder));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_accept_admin_requires_new_admin_auth() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&admin, &new_admin);
+
+    env.set_auths(&[]);
+    client.accept_admin(&new_admin);
+}
+
+// ---------------------------------------------------------------------------
+// 16. Contract state transition tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_state_emits_transition() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+
+    client.set_state(&admin, &ContractState::Frozen);
+    assert_eq!(client.get_state(), ContractState::Frozen as u32);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id,
+                (symbol_short!("state"),).into_val(&env),
+                (ContractState::Active, ContractState::Frozen).into_val(&env),
+            )
+        ]
+    );
+
+    // Thawing goes through Paused.
+    client.set_state(&admin, &ContractState::Paused);
+    client.set_state(&admin, &ContractState::Active);
+    assert_eq!(client.get_state(), ContractState::Active as u32);
+}
+
+#[test]
+fn test_set_state_rejects_frozen_to_active() {
+    let (_env, _contract_id, admin, client) = setup_initialized_contract();
+    client.set_state(&admin, &ContractState::Frozen);
+    assert_eq!(
+        client.try_set_state(&admin, &ContractState::Active),
+        Err(Ok(AuthError::InvalidStateTransition))
+    );
+}
+
+#[test]
+fn test_set_configs_rejects_undeclared_transition() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    client.set_state(&admin, &ContractState::Frozen);
+
+    let updates = vec![
+        &env,
+        (CONFIG_COOLDOWN, 60u64),
+        (CONFIG_STATE, ContractState::Active as u64),
+    ];
+    assert_eq!(
+        client.try_set_configs(&admin, &updates),
+        Err(Ok(AuthError::InvalidStateTransition))
+    );
+    assert_eq!(client.get_state(), ContractState::Frozen as u32);
+}
+
+// ---------------------------------------------------------------------------
+// 17. Per-function pause tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_pause_fn_blocks_only_that_function() {
+    let (env, contract_id, admin, client) = setup_initialized_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.set_balance(&admin, &alice, &100);
+
+    let transfer = symbol_short!("transfer");
+    client.pause_fn(&admin, &transfer);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id,
+                (symbol_short!("paused"), transfer.clone()).into_val(&env),
+                admin.into_val(&env),
+            )
+        ]
+    );
+    assert!(client.is_fn_paused(&transfer));
+    assert_eq!(client.get_paused_fns(), PAUSE_TRANSFER);
+
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10),
+        Err(Ok(AuthError::FunctionPaused))
+    );
+    // Reads and other writes are unaffected.
+    assert_eq!(client.get_balance(&alice), 100);
+    client.approve(&alice, &bob, &50);
+    client.transfer_from(&bob, &alice, &bob, &20);
+    assert_eq!(client.get_balance(&bob), 20);
+
+    client.unpause_fn(&admin, &transfer);
+    assert!(!client.is_fn_paused(&transfer));
+    client.transfer(&alice, &bob, &10);
+    assert_eq!(client.get_balance(&bob), 30);
+}
+
+#[test]
+fn test_pause_flags_combine() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.pause_fn(&admin, &Symbol::new(&env, "transfer_from"));
+    client.pause_fn(&admin, &symbol_short!("approve"));
+    assert_eq!(client.get_paused_fns(), PAUSE_TRANSFER_FROM | PAUSE_APPROVE);
+    assert_eq!(
+        client.try_approve(&alice, &bob, &1),
+        Err(Ok(AuthError::FunctionPaused))
+    );
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &1),
+        Err(Ok(AuthError::FunctionPaused))
+    );
+
+    client.unpause_fn(&admin, &symbol_short!("approve"));
+    assert_eq!(client.get_paused_fns(), PAUSE_TRANSFER_FROM);
+    client.approve(&alice, &bob, &1);
+}
+
+#[test]
+fn test_pause_fn_rejects_unknown_function() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        client.try_pause_fn(&admin, &symbol_short!("get_bal")),
+        Err(Ok(AuthError::UnknownFunction))
+    );
+    assert!(!client.is_fn_paused(&symbol_short!("get_bal")));
+    assert_eq!(
+        client.try_pause_fn(&outsider, &symbol_short!("transfer")),
+        Err(Ok(AuthError::NotAdmin))
+    );
+    assert_eq!(client.get_paused_fns(), 0);
+}
+
+// ---------------------------------------------------------------------------
+// 18. Typed error tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_time_lock_returns_typed_error() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    client.set_time_lock(&admin, &500);
+
+    env.ledger().with_mut(|li| li.timestamp = 499);
+    assert_eq!(
+        client.try_time_locked_action(&admin),
+        Err(Ok(AuthError::TimeLocked))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.time_locked_action(&admin), 500);
+}
+
+#[test]
+fn test_cooldown_returns_typed_error() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+    client.set_cooldown(&admin, &60);
+
+    client.cooldown_action(&user);
+    assert_eq!(
+        client.try_cooldown_action(&user),
+        Err(Ok(AuthError::CooldownActive))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 60);
+    client.cooldown_action(&user);
+}
+
+#[test]
+fn test_inactive_contract_returns_typed_error() {
+    let (env, _contract_id, admin, client) = setup_initialized_contract();
+    let user = Address::generate(&env);
+
+    client.set_state(&admin, &ContractState::Paused);
+    assert_eq!(
+        client.try_active_only_action(&user),
+        Err(Ok(AuthError::ContractNotActive))
+    );
+    client.set_state(&admin, &ContractState::Active);
+    client.active_only_action(&user);
 }
@@ -0,0 +1,150 @@
+//! Helpers for testing with real signatures instead of `mock_all_auths`.
+//!
+//! `mock_all_auths` approves every `require_auth` call, so a test passes even
+//! if the contract asks the wrong address or for the wrong arguments. These
+//! helpers build the `SorobanAuthorizationEntry` a wallet would attach to the
+//! transaction, sign it, and hand it to `env.set_auths`. The host then checks
+//! it exactly as on-chain: the signer, the invocation tree, the arguments,
+//! the nonce and the expiration ledger all have to match.
+//!
+//! Signers are small account contracts holding one ed25519 key. Their
+//! `__check_auth` verifies the signature, which keeps the tests independent
+//! of classic Stellar account entries.
+
+#![cfg(test)]
+
+extern crate std;
+
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contractimpl, contracttype,
+    crypto::Hash,
+    xdr::{
+        HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs, Limits, ScSymbol,
+        ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+        SorobanAuthorizedInvocation, SorobanCredentials, WriteXdr,
+    },
+    Address, Bytes, BytesN, Env, Error, TryFromVal, Val, Vec,
+};
+
+/// Ledgers a signed entry stays valid for.
+const SIGNATURE_TTL_LEDGERS: u32 = 100;
+
+#[contracttype]
+#[derive(Clone)]
+enum SignerKey {
+    PublicKey,
+}
+
+/// Account contract that accepts an ed25519 signature from one key.
+#[contract]
+pub struct SignerAccount;
+
+#[contractimpl]
+impl SignerAccount {
+    pub fn init(env: Env, public_key: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&SignerKey::PublicKey, &public_key);
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for SignerAccount {
+    type Signature = BytesN<64>;
+    type Error = Error;
+
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: BytesN<64>,
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), Error> {
+        let public_key: BytesN<32> = env.storage().instance().get(&SignerKey::PublicKey).unwrap();
+        env.crypto()
+            .ed25519_verify(&public_key, &signature_payload.into(), &signature);
+        Ok(())
+    }
+}
+
+/// An address backed by an ed25519 key the test controls.
+pub struct TestSigner {
+    pub address: Address,
+    key: SigningKey,
+}
+
+impl TestSigner {
+    pub fn new(env: &Env, seed: u8) -> Self {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let address = env.register_contract(None, SignerAccount);
+        SignerAccountClient::new(env, &address)
+            .init(&BytesN::from_array(env, &key.verifying_key().to_bytes()));
+        Self { address, key }
+    }
+
+    /// Sign `invocation` for this address. Each entry needs a `nonce` that
+    /// this signer has not used before.
+    pub fn authorize(
+        &self,
+        env: &Env,
+        nonce: i64,
+        invocation: SorobanAuthorizedInvocation,
+    ) -> SorobanAuthorizationEntry {
+        let expiration = env.ledger().sequence() + SIGNATURE_TTL_LEDGERS;
+        let payload = signature_payload(env, nonce, expiration, &invocation);
+        let signature = BytesN::from_array(env, &self.key.sign(&payload).to_bytes());
+        SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: (&self.address).into(),
+                nonce,
+                signature_expiration_ledger: expiration,
+                signature: ScVal::try_from_val(env, &Val::from(signature)).unwrap(),
+            }),
+            root_invocation: invocation,
+        }
+    }
+}
+
+/// The call `contract.fn_name(args)` as it appears in an authorization tree,
+/// with the calls it makes that need the same signer's auth as children.
+pub fn invocation(
+    env: &Env,
+    contract: &Address,
+    fn_name: &str,
+    args: Vec<Val>,
+    sub_invocations: &[SorobanAuthorizedInvocation],
+) -> SorobanAuthorizedInvocation {
+    let args: std::vec::Vec<ScVal> = args
+        .iter()
+        .map(|arg| ScVal::try_from_val(env, &arg).unwrap())
+        .collect();
+    SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: contract.into(),
+            function_name: ScSymbol(fn_name.try_into().unwrap()),
+            args: args.try_into().unwrap(),
+        }),
+        sub_invocations: sub_invocations.to_vec().try_into().unwrap(),
+    }
+}
+
+/// What a signer actually signs: the SHA-256 of the network id, nonce,
+/// expiration ledger and invocation tree.
+pub fn signature_payload(
+    env: &Env,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    invocation: &SorobanAuthorizedInvocation,
+) -> [u8; 32] {
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: env.ledger().network_id().to_array().into(),
+        nonce,
+        signature_expiration_ledger,
+        invocation: invocation.clone(),
+    });
+    let xdr = preimage.to_xdr(Limits::none()).unwrap();
+    env.crypto()
+        .sha256(&Bytes::from_slice(env, &xdr))
+        .to_array()
+}
@@ -0,0 +1,117 @@
+//! # On-chain Event Log
+//!
+//! Events are not contract state: an indexer that was offline when an event
+//! was emitted has no way to ask the contract for it later. `EventLog`
+//! publishes an event as usual *and* keeps a copy of the last
+//! [`LOG_CAPACITY`] events in persistent storage, so consumers that miss
+//! events can backfill from the contract itself.
+//!
+//! ## Layout
+//!
+//! ```text
+//! instance   LogKey::Count          total events ever logged (next seq)
+//! persistent LogKey::Slot(seq % N)  LogEntry for seq
+//! ```
+//!
+//! The slots form a ring buffer: once `N` events exist, each new event
+//! overwrites the oldest one, so storage stays bounded no matter how long
+//! the contract runs.
+//!
+//! ## Paging
+//!
+//! Pages are numbered by sequence, not by position in the buffer: page `p`
+//! of size `s` always covers sequence numbers `p * s .. (p + 1) * s`. A page
+//! therefore never shifts when new events arrive; it only loses entries
+//! that were overwritten. Every entry carries its `seq`, so a consumer can
+//! tell exactly which events it can no longer recover.
+//!
+//! The data payload is stored as its XDR encoding, the same form an event
+//! stream delivers it in, so it decodes with whatever the consumer already
+//! uses for live events (`T::from_xdr` in Rust).
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Bytes, Env, IntoVal, Val, Vec};
+
+/// Number of events kept on-chain. Older entries are overwritten.
+pub const LOG_CAPACITY: u32 = 32;
+
+/// Maximum `page_size` honoured by `get_log`; larger values are capped.
+pub const LOG_PAGE_LIMIT: u32 = 10;
+
+/// A stored copy of one published event.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    /// Position in the log, starting at 0 and never reused.
+    pub seq: u64,
+    /// The event's topics, exactly as published.
+    pub topics: Vec<Val>,
+    /// XDR encoding of the event's data payload.
+    pub data: Bytes,
+    /// Ledger sequence the event was emitted in.
+    pub ledger: u32,
+}
+
+/// Storage keys used by the event log.
+#[contracttype]
+#[derive(Clone)]
+pub enum LogKey {
+    /// Total number of events ever logged.
+    Count,
+    /// Ring-buffer slot (`seq % LOG_CAPACITY`) holding a `LogEntry`.
+    Slot(u32),
+}
+
+/// Publishes events and records them in the on-chain ring buffer.
+pub struct EventLog;
+
+impl EventLog {
+    /// Publish an event and append it to the log.
+    pub fn publish<T, D>(env: &Env, topics: T, data: D)
+    where
+        T: IntoVal<Env, Vec<Val>>,
+        D: IntoVal<Env, Val>,
+    {
+        let topics: Vec<Val> = topics.into_val(env);
+        let data: Val = data.into_val(env);
+        env.events().publish(topics.clone(), data);
+
+        let seq = Self::len(env);
+        let entry = LogEntry {
+            seq,
+            topics,
+            data: data.to_xdr(env),
+            ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&Self::slot(seq), &entry);
+        env.storage().instance().set(&LogKey::Count, &(seq + 1));
+    }
+
+    /// Total number of events logged since deployment.
+    pub fn len(env: &Env) -> u64 {
+        env.storage().instance().get(&LogKey::Count).unwrap_or(0)
+    }
+
+    /// Entries with sequence numbers in `page * page_size .. (page + 1) *
+    /// page_size`, oldest first, skipping any that were overwritten.
+    ///
+    /// `page_size` is capped at `LOG_PAGE_LIMIT`. A page past the end, or a
+    /// `page_size` of zero, yields an empty vector.
+    pub fn page(env: &Env, page: u32, page_size: u32) -> Vec<LogEntry> {
+        let page_size = page_size.min(LOG_PAGE_LIMIT) as u64;
+        let count = Self::len(env);
+        let oldest = count.saturating_sub(LOG_CAPACITY as u64);
+        let from = (page as u64 * page_size).max(oldest);
+        let to = count.min((page as u64 + 1) * page_size);
+
+        let mut entries = Vec::new(env);
+        for seq in from..to {
+            let entry: LogEntry = env.storage().persistent().get(&Self::slot(seq)).unwrap();
+            entries.push_back(entry);
+        }
+        entries
+    }
+
+    fn slot(seq: u64) -> LogKey {
+        LogKey::Slot((seq % LOG_CAPACITY as u64) as u32)
+    }
+}
@@ -66,10 +66,20 @@
 //!
 //! Events are published via `env.events().publish()` and can be
 //! queried off-chain for indexing and monitoring.
+//!
+//! ## Backfilling Missed Events
+//!
+//! The structured events (examples 1–4) go through [`EventLog`], which also
+//! keeps the last [`LOG_CAPACITY`] of them in persistent storage. Consumers
+//! that were offline read them back with `get_log(page, page_size)`.
 
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+mod event_log;
+
+pub use event_log::{EventLog, LogEntry, LogKey, LOG_CAPACITY, LOG_PAGE_LIMIT};
+
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 /// Event-emitting contract demonstrating both basic emission and
 /// query-friendly topic design.
@@ -189,7 +199,8 @@ impl EventsContract {
     /// Placing both addresses in topics means an off-chain indexer can
     /// efficiently retrieve all transfers _to_ or _from_ a given address.
     pub fn transfer(env: Env, sender: Address, recipient: Address, amount: i128, memo: u64) {
-        env.events().publish(
+        EventLog::publish(
+            &env,
             (CONTRACT_NS, symbol_short!("transfer"), sender, recipient),
             TransferEventData { amount, memo },
         );
@@ -214,7 +225,8 @@ impl EventsContract {
     /// The config `key` is in the topics so consumers can subscribe to changes
     /// for a specific parameter (e.g. only `"max_supply"` updates).
     pub fn update_config(env: Env, key: Symbol, old_value: u64, new_value: u64) {
-        env.events().publish(
+        EventLog::publish(
+            &env,
             (CONTRACT_NS, symbol_short!("cfg_upd"), key),
             ConfigUpdateEventData {
                 old_value,
@@ -239,7 +251,8 @@ impl EventsContract {
     /// **Data:** `AdminActionEventData { action, timestamp }`
     pub fn admin_action(env: Env, admin: Address, action: Symbol) {
         let timestamp = env.ledger().timestamp();
-        env.events().publish(
+        EventLog::publish(
+            &env,
             (CONTRACT_NS, symbol_short!("admin"), admin),
             AdminActionEventData { action, timestamp },
         );
@@ -267,7 +280,8 @@ impl EventsContract {
     pub fn audit_trail(env: Env, actor: Address, action: Symbol, details: Symbol) {
         let timestamp = env.ledger().timestamp();
         let sequence = env.ledger().sequence();
-        env.events().publish(
+        EventLog::publish(
+            &env,
             (CONTRACT_NS, symbol_short!("audit"), actor, action),
             AuditTrailEventData {
                 details,
@@ -277,6 +291,28 @@ impl EventsContract {
         );
     }
 
+    // -----------------------------------------------------------------------
+    // Event log (backfill for consumers that missed events)
+    // -----------------------------------------------------------------------
+
+    /// Total number of structured events logged since deployment.
+    ///
+    /// Only the newest `LOG_CAPACITY` are retained, so once the log has
+    /// wrapped the oldest readable sequence number is
+    /// `log_len() - LOG_CAPACITY`.
+    pub fn log_len(env: Env) -> u64 {
+        EventLog::len(&env)
+    }
+
+    /// Return page `page` of the event log, oldest first.
+    ///
+    /// Page `p` covers sequence numbers `p * page_size .. (p + 1) * page_size`;
+    /// entries already overwritten are omitted. `page_size` is capped at
+    /// `LOG_PAGE_LIMIT`.
+    pub fn get_log(env: Env, page: u32, page_size: u32) -> Vec<LogEntry> {
+        EventLog::page(&env, page, page_size)
+    }
+
     // -----------------------------------------------------------------------
     // Simple helpers (kept for backward-compatibility)
     // -----------------------------------------------------------------------
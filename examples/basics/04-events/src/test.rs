@@ -11,6 +11,7 @@ use super::*;
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events as _},
+    xdr::FromXdr,
     Address, Env, Symbol, TryFromVal,
 };
 
@@ -521,3 +522,129 @@ fn test_audit_trail_structured_data_payload() {
     assert_eq!(payload.timestamp, env.ledger().timestamp());
     assert_eq!(payload.sequence, env.ledger().sequence());
 }
+
+// ---------------------------------------------------------------------------
+// Event log: on-chain backfill
+// ---------------------------------------------------------------------------
+
+/// Emit `count` config updates; entry `seq` carries `new_value == seq`.
+fn log_updates(client: &EventsContractClient, count: u64) {
+    for i in 0..count {
+        client.update_config(&symbol_short!("fee"), &0, &i);
+    }
+}
+
+fn logged_value(env: &Env, entry: &LogEntry) -> u64 {
+    ConfigUpdateEventData::from_xdr(env, &entry.data)
+        .unwrap()
+        .new_value
+}
+
+#[test]
+fn test_log_records_published_event() {
+    let (env, _, client) = make_env_and_client();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.transfer(&sender, &recipient, &250, &9);
+
+    assert_eq!(client.log_len(), 1);
+    let entry = client.get_log(&0, &10).get(0).unwrap();
+    assert_eq!(entry.seq, 0);
+    assert_eq!(entry.ledger, env.ledger().sequence());
+
+    // The stored copy matches what was published.
+    let (_id, topics, data) = env.events().all().get(0).unwrap();
+    assert_eq!(entry.topics.len(), 4);
+    assert_eq!(entry.topics.to_vals(), topics.to_vals());
+    let payload = TransferEventData::from_xdr(&env, &entry.data).unwrap();
+    let published = TransferEventData::try_from_val(&env, &data).unwrap();
+    assert_eq!(payload.amount, published.amount);
+    assert_eq!(payload.memo, 9);
+}
+
+#[test]
+fn test_log_skips_unlogged_helpers() {
+    let (_env, _, client) = make_env_and_client();
+
+    client.emit_simple(&1);
+    client.emit_multiple(&3);
+    assert_eq!(client.log_len(), 0);
+    assert!(client.get_log(&0, &10).is_empty());
+}
+
+#[test]
+fn test_log_pagination_boundaries() {
+    let (env, _, client) = make_env_and_client();
+    log_updates(&client, 7);
+
+    let first = client.get_log(&0, &3);
+    assert_eq!(first.len(), 3);
+    assert_eq!(first.get(0).unwrap().seq, 0);
+    assert_eq!(logged_value(&env, &first.get(2).unwrap()), 2);
+
+    // The last page is partial; pages past the end are empty.
+    let last = client.get_log(&2, &3);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0).unwrap().seq, 6);
+    assert!(client.get_log(&3, &3).is_empty());
+
+    // A zero page size returns nothing; an oversized one is capped.
+    assert!(client.get_log(&0, &0).is_empty());
+    let capped = client.get_log(&0, &1000);
+    assert_eq!(capped.len(), 7);
+    log_updates(&client, LOG_PAGE_LIMIT as u64);
+    assert_eq!(client.get_log(&0, &1000).len(), LOG_PAGE_LIMIT);
+    assert_eq!(
+        client.get_log(&1, &1000).get(0).unwrap().seq,
+        LOG_PAGE_LIMIT as u64
+    );
+}
+
+#[test]
+fn test_log_ring_buffer_wraps() {
+    let (env, _, client) = make_env_and_client();
+    let total = LOG_CAPACITY as u64 + 5;
+    log_updates(&client, total);
+    assert_eq!(client.log_len(), total);
+
+    // Sequences 0..5 were overwritten, so page 0 starts at the oldest survivor.
+    let page = client.get_log(&0, &LOG_PAGE_LIMIT);
+    assert_eq!(page.len(), LOG_PAGE_LIMIT - 5);
+    assert_eq!(page.get(0).unwrap().seq, 5);
+    assert_eq!(logged_value(&env, &page.get(0).unwrap()), 5);
+
+    // Reading every page returns exactly the retained window, in order.
+    let mut seen = 0;
+    let mut expected = total - LOG_CAPACITY as u64;
+    let mut p = 0;
+    loop {
+        let page = client.get_log(&p, &LOG_PAGE_LIMIT);
+        if page.is_empty() && expected == total {
+            break;
+        }
+        for entry in page.iter() {
+            assert_eq!(entry.seq, expected);
+            assert_eq!(logged_value(&env, &entry), expected);
+            expected += 1;
+            seen += 1;
+        }
+        p += 1;
+    }
+    assert_eq!(seen, LOG_CAPACITY);
+}
+
+#[test]
+fn test_log_pages_are_stable_across_appends() {
+    let (env, _, client) = make_env_and_client();
+    log_updates(&client, 6);
+    let before = client.get_log(&1, &3);
+
+    log_updates(&client, 4);
+    let after = client.get_log(&1, &3);
+    assert_eq!(before.len(), after.len());
+    for (a, b) in before.iter().zip(after.iter()) {
+        assert_eq!(a.seq, b.seq);
+        assert_eq!(logged_value(&env, &a), logged_value(&env, &b));
+    }
+}
@@ -8,6 +8,7 @@
 //! ### 1. Parameter Validation
 //! - Type checking and range validation
 //! - Format validation for strings and addresses
+//! - Charset sanitization and shallow email checks on a stack buffer
 //! - Business rule validation
 //! - Clear error messages for invalid inputs
 //!
@@ -15,19 +16,30 @@
 //! - Contract state consistency checks
 //! - Invariant validation
 //! - Temporal validation (time-based constraints)
+//! - Rate limiting with a per-address token bucket
 //! - Resource availability validation
 //!
 //! ### 3. Authorization Validation
 //! - Role-based access control
 //! - Ownership verification
 //! - Multi-signature requirements
+//! - Off-chain approvals: ed25519-signed requests with replay protection
 //! - Permission checks for specific operations
+//! - Blacklist and whitelist (allowlist) checks
+//!
+//! ### 4. Chaining Checks
+//! - [`Validator`] runs checks in order and returns the first failure
 
 #![no_std]
+use cookbook_state::{state_machine, transition, StateError};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, String, Vec,
 };
 
+pub mod validator;
+pub use validator::Validator;
+
 // ---------------------------------------------------------------------------
 // Error Types
 // ---------------------------------------------------------------------------
@@ -51,6 +63,8 @@ pub enum ValidationError {
     InvalidTimestamp = 111,
     TimestampInPast = 112,
     TimestampInDistantFuture = 113,
+    InvalidExpirationLedger = 114,
+    InvalidEmail = 115,
 
     // State validation errors (200-299)
     ContractNotInitialized = 200,
@@ -76,6 +90,14 @@ pub enum ValidationError {
     ExpiredSignature = 307,
     WrongContract = 308,
     Blacklisted = 309,
+    NotWhitelisted = 310,
+    SignatureReplayed = 311,
+}
+
+impl From<StateError> for ValidationError {
+    fn from(_: StateError) -> Self {
+        ValidationError::InvalidStateTransition
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -101,6 +123,55 @@ pub enum ContractState {
     Frozen = 3,
 }
 
+// Frozen is terminal: nothing moves a contract out of it.
+state_machine! {
+    ContractState, key = DataKey::State, initial = Uninitialized;
+    Uninitialized => [Active],
+    Active => [Paused, Frozen],
+    Paused => [Active, Frozen],
+    Frozen => [],
+}
+
+/// Characters a sanitized string may contain. Both are ASCII-only, so a
+/// multi-byte UTF-8 character is always rejected.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Charset {
+    /// `a-z`, `A-Z`, `0-9` and `_`: handles, tickers, keys
+    Identifier,
+    /// Printable ASCII, space included: no control characters or DEL
+    PrintableAscii,
+}
+
+/// Longest string the charset validators copy onto the stack, in bytes
+pub const MAX_SANITIZED_LEN: u32 = 256;
+
+/// An allowance as stored: how much `spender` may still move for `owner`,
+/// and the last ledger at which it can be spent.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Token-bucket parameters: each address holds up to `max_ops` tokens and
+/// regains `refill_per_ledger` of them every ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitConfig {
+    pub max_ops: u32,
+    pub refill_per_ledger: u32,
+}
+
+/// An address's bucket as stored: its tokens when it was last updated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bucket {
+    pub tokens: u32,
+    pub updated_ledger: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
@@ -109,10 +180,25 @@ pub enum DataKey {
     State,
     UserRole(Address),
     Balance(Address),
+    /// Temporary: an `AllowanceValue` living until its expiration ledger.
     Allowance(Address, Address),
     LastAction(Address),
     Cooldown(Address),
+    /// Persistent: present while the address is blacklisted.
     Blacklist(Address),
+    /// Persistent: present while the address is whitelisted.
+    Whitelist(Address),
+    /// When true, only whitelisted addresses pass `validate_access_list`.
+    WhitelistMode,
+    /// The `RateLimitConfig`; absent while rate limiting is off.
+    RateLimit,
+    /// Temporary: a `Bucket`, living until it would be full again.
+    Bucket(Address),
+    /// Persistent: present while the ed25519 key may approve requests.
+    Signer(BytesN<32>),
+    /// Temporary: present once a signer's nonce has been used, living
+    /// until the request it approved expires.
+    UsedNonce(BytesN<32>, u64),
     Counter,
 }
 
@@ -154,7 +240,7 @@ impl ValidationContract {
         // Set initial state
         env.storage().instance().set(&DataKey::Owner, &owner);
         env.storage().instance().set(&DataKey::Admin, &owner);
-        env.storage().instance().set(&DataKey::State, &ContractState::Active);
+        transition!(&env, ContractState::Uninitialized => ContractState::Active)?;
 
         Ok(())
     }
@@ -229,6 +315,78 @@ impl ValidationContract {
         Ok(())
     }
 
+    /// Example of charset validation on a string's bytes
+    /// 
+    /// `String` offers no character access in a contract, so the bytes are
+    /// copied into a stack buffer and checked one by one. Because every
+    /// allowed character is ASCII, checking bytes is the same as checking
+    /// characters: each byte of a multi-byte UTF-8 character is above 0x7F
+    /// and fails.
+    /// 
+    /// # Arguments
+    /// * `text` - The string to validate
+    /// * `charset` - The characters allowed
+    /// 
+    /// # Errors
+    /// * `ValidationError::StringTooLong` - If string exceeds `MAX_SANITIZED_LEN` bytes
+    /// * `ValidationError::InvalidString` - If string contains a character outside `charset`
+    pub fn validate_string_charset(text: String, charset: Charset) -> Result<(), ValidationError> {
+        let mut buf = [0u8; MAX_SANITIZED_LEN as usize];
+        let bytes = Self::copy_to_buffer(&text, &mut buf)?;
+
+        let allowed = |c: &u8| match charset {
+            Charset::Identifier => c.is_ascii_alphanumeric() || *c == b'_',
+            Charset::PrintableAscii => (b' '..=b'~').contains(c),
+        };
+        if !bytes.iter().all(allowed) {
+            return Err(ValidationError::InvalidString);
+        }
+
+        Ok(())
+    }
+
+    /// Example of a shallow email format check
+    /// 
+    /// Checks the shape only: one `@`, a non-empty local part, and a domain
+    /// of dot-separated labels with at least one dot. All characters must
+    /// be printable ASCII without spaces. Whether the address exists can
+    /// only be checked off-chain.
+    /// 
+    /// # Arguments
+    /// * `text` - The string to validate
+    /// 
+    /// # Errors
+    /// * `ValidationError::StringTooLong` - If string exceeds `MAX_SANITIZED_LEN` bytes
+    /// * `ValidationError::InvalidEmail` - If string is not shaped like an email address
+    pub fn validate_email(text: String) -> Result<(), ValidationError> {
+        let mut buf = [0u8; MAX_SANITIZED_LEN as usize];
+        let bytes = Self::copy_to_buffer(&text, &mut buf)?;
+
+        if !bytes.iter().all(|c| (b'!'..=b'~').contains(c)) {
+            return Err(ValidationError::InvalidEmail);
+        }
+        let mut parts = bytes.split(|c| *c == b'@');
+        let (Some(local), Some(domain), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(ValidationError::InvalidEmail);
+        };
+        let labels_ok = domain.split(|c| *c == b'.').all(|label| !label.is_empty());
+        if local.is_empty() || !domain.contains(&b'.') || !labels_ok {
+            return Err(ValidationError::InvalidEmail);
+        }
+
+        Ok(())
+    }
+
+    /// Copy `text` into `buf` and return the filled part
+    fn copy_to_buffer<'a>(text: &String, buf: &'a mut [u8]) -> Result<&'a [u8], ValidationError> {
+        let len = text.len() as usize;
+        if len > buf.len() {
+            return Err(ValidationError::StringTooLong);
+        }
+        text.copy_into_slice(&mut buf[..len]);
+        Ok(&buf[..len])
+    }
+
     /// Example of address parameter validation
     /// 
     /// # Arguments
@@ -319,16 +477,7 @@ impl ValidationContract {
         env: &Env,
         required_state: ContractState,
     ) -> Result<(), ValidationError> {
-        // Check if contract is initialized
-        if !env.storage().instance().has(&DataKey::State) {
-            return Err(ValidationError::ContractNotInitialized);
-        }
-
-        let current_state: ContractState = env
-            .storage()
-            .instance()
-            .get(&DataKey::State)
-            .unwrap();
+        let current_state = ContractState::current(env);
 
         match current_state {
             ContractState::Uninitialized => {
@@ -387,20 +536,16 @@ impl ValidationContract {
     /// * `required_amount` - The required minimum allowance
     /// 
     /// # Errors
-    /// * `ValidationError::InsufficientAllowance` - If allowance is insufficient
+    /// * `ValidationError::InsufficientAllowance` - If allowance is insufficient or expired
     pub fn validate_allowance(
         env: &Env,
         owner: Address,
         spender: Address,
         required_amount: i128,
     ) -> Result<(), ValidationError> {
-        let allowance: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Allowance(owner.clone(), spender.clone()))
-            .unwrap_or(0);
+        let allowance = Self::read_allowance(env, &owner, &spender);
 
-        if allowance < required_amount {
+        if allowance.amount < required_amount {
             return Err(ValidationError::InsufficientAllowance);
         }
 
@@ -446,15 +591,14 @@ impl ValidationContract {
     /// * `ValidationError::NotOwner` - If address is not owner
     /// * `ValidationError::InsufficientRole` - If role is insufficient
     /// * `ValidationError::Blacklisted` - If address is blacklisted
+    /// * `ValidationError::NotWhitelisted` - If whitelist mode is on and address is not whitelisted
     pub fn validate_role(
         env: &Env,
         address: Address,
         required_role: UserRole,
     ) -> Result<(), ValidationError> {
-        // Check if address is blacklisted
-        if env.storage().instance().has(&DataKey::Blacklist(address.clone())) {
-            return Err(ValidationError::Blacklisted);
-        }
+        // Check the blacklist and whitelist first
+        Self::validate_access_list(env, address.clone())?;
 
         // Get user role
         let user_role: UserRole = env
@@ -475,10 +619,10 @@ impl ValidationContract {
                     return Err(ValidationError::NotOwner);
                 }
             }
-            UserRole::Admin => {
-                if user_role != UserRole::Admin && user_role != UserRole::Owner {
-                    return Err(ValidationError::NotAdmin);
-                }
+            UserRole::Admin
+                if user_role != UserRole::Admin && user_role != UserRole::Owner =>
+            {
+                return Err(ValidationError::NotAdmin);
             }
             _ => {}
         }
@@ -530,10 +674,42 @@ impl ValidationContract {
         Ok(())
     }
 
+    /// Example of blacklist / whitelist validation
+    /// 
+    /// The blacklist always applies. The whitelist only applies while
+    /// whitelist mode is on, and then every address not on it is rejected.
+    /// 
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `address` - The address to check
+    /// 
+    /// # Errors
+    /// * `ValidationError::Blacklisted` - If address is blacklisted
+    /// * `ValidationError::NotWhitelisted` - If whitelist mode is on and address is not whitelisted
+    pub fn validate_access_list(env: &Env, address: Address) -> Result<(), ValidationError> {
+        if env.storage().persistent().has(&DataKey::Blacklist(address.clone())) {
+            return Err(ValidationError::Blacklisted);
+        }
+
+        let whitelist_mode: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::WhitelistMode)
+            .unwrap_or(false);
+        if whitelist_mode && !env.storage().persistent().has(&DataKey::Whitelist(address)) {
+            return Err(ValidationError::NotWhitelisted);
+        }
+
+        Ok(())
+    }
+
     // ==================== COMBINED VALIDATION EXAMPLES ====================
 
     /// Example of a function that combines all validation types
     /// 
+    /// The checks are chained on a [`Validator`], which stops at the first
+    /// one that fails.
+    /// 
     /// # Arguments
     /// * `env` - The contract environment
     /// * `from` - The sender address
@@ -542,7 +718,7 @@ impl ValidationContract {
     /// * `message` - Optional transfer message
     /// 
     /// # Errors
-    /// Various validation errors depending on the validation that fails
+    /// The error of the first check that fails, in the order of the chain
     pub fn validated_transfer(
         env: Env,
         from: Address,
@@ -550,25 +726,28 @@ impl ValidationContract {
         amount: i128,
         message: Option<String>,
     ) -> Result<(), ValidationError> {
-        // 1. Parameter validation
-        Self::validate_address(from.clone())?;
-        Self::validate_address(to.clone())?;
-        Self::validate_amount_parameters(amount, 1, 1000000)?;
-        
-        if let Some(msg) = &message {
-            Self::validate_string_parameters(msg.clone(), 0, 100)?;
-        }
-
-        // 2. State validation
-        Self::validate_contract_state(&env, ContractState::Active)?;
-        Self::validate_balance(&env, from.clone(), amount)?;
-
-        // 3. Authorization validation
-        Self::validate_role(&env, from.clone(), UserRole::User)?;
+        Validator::new(&env)
+            // 1. Parameter validation
+            .address(&from)
+            .address(&to)
+            .amount(amount, 1, 1000000)
+            .check(|| match &message {
+                Some(msg) => Self::validate_string_parameters(msg.clone(), 0, 100),
+                None => Ok(()),
+            })
+            // 2. State validation
+            .state(ContractState::Active)
+            .balance(&from, amount)
+            // 3. Authorization validation
+            .role(&from, UserRole::User)
+            .access_list(&to)
+            // 4. Business logic validation
+            .cooldown(&from, 60) // 1 minute cooldown
+            .finish()?;
         from.require_auth();
 
-        // 4. Business logic validation (cooldown, rate limiting, etc.)
-        Self::validate_cooldown(&env, from.clone(), 60)?; // 1 minute cooldown
+        // Spends a token, so it runs only once every check has passed
+        Self::take_tokens(&env, &from, 1)?;
 
         // Execute the transfer
         let from_balance: i128 = env
@@ -592,6 +771,473 @@ impl ValidationContract {
         Ok(())
     }
 
+    // ==================== ALLOWANCE / DELEGATION ====================
+
+    /// Let `spender` move up to `amount` of `owner`'s balance until
+    /// `expiration_ledger`, replacing any previous allowance
+    /// 
+    /// The allowance lives in temporary storage with its TTL set to the
+    /// expiration ledger, so the network deletes it once it can no longer
+    /// be spent. Approving 0 revokes.
+    /// 
+    /// # Arguments
+    /// * `owner` - The address whose balance is delegated
+    /// * `spender` - The address allowed to spend it
+    /// * `amount` - The allowance, replacing the current one
+    /// * `expiration_ledger` - Last ledger at which the allowance can be spent
+    /// 
+    /// # Errors
+    /// * `ValidationError::InvalidAmount` - If amount is negative
+    /// * `ValidationError::InvalidAddress` - If owner and spender are the same
+    /// * `ValidationError::InvalidExpirationLedger` - If a non-zero allowance expires in the past
+    /// * `ValidationError::ContractPaused` - If contract is paused
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), ValidationError> {
+        // 1. Parameter validation
+        if amount < 0 {
+            return Err(ValidationError::InvalidAmount);
+        }
+        if owner == spender {
+            return Err(ValidationError::InvalidAddress);
+        }
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(ValidationError::InvalidExpirationLedger);
+        }
+
+        // 2. State validation
+        Self::validate_contract_state(&env, ContractState::Active)?;
+
+        // 3. Authorization validation
+        owner.require_auth();
+
+        Self::write_allowance(&env, &owner, &spender, amount, expiration_ledger);
+
+        Ok(())
+    }
+
+    /// Move `amount` from `from` to `to` on behalf of `from`, spending
+    /// `spender`'s allowance
+    /// 
+    /// # Arguments
+    /// * `spender` - The delegate, who authorizes the call
+    /// * `from` - The owner of the balance
+    /// * `to` - The recipient
+    /// * `amount` - The amount to transfer
+    /// 
+    /// # Errors
+    /// * `ValidationError::InsufficientAllowance` - If the allowance is too small or expired
+    /// * `ValidationError::InsufficientBalance` - If `from` cannot cover the amount
+    /// * `ValidationError::ContractPaused` - If contract is paused
+    /// * `ValidationError::Blacklisted` - If `spender`, `from` or `to` is blacklisted
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ValidationError> {
+        // 1. Parameter validation
+        Self::validate_amount_parameters(amount, 1, 1000000)?;
+
+        // 2. State validation
+        Self::validate_contract_state(&env, ContractState::Active)?;
+        Self::validate_balance(&env, from.clone(), amount)?;
+
+        // 3. Authorization validation
+        Self::validate_access_list(&env, spender.clone())?;
+        Self::validate_access_list(&env, from.clone())?;
+        Self::validate_access_list(&env, to.clone())?;
+        spender.require_auth();
+
+        // Decrement on use; an allowance never goes below zero
+        let allowance = Self::read_allowance(&env, &from, &spender);
+        let remaining = allowance
+            .amount
+            .checked_sub(amount)
+            .filter(|remaining| *remaining >= 0)
+            .ok_or(ValidationError::InsufficientAllowance)?;
+        Self::write_allowance(&env, &from, &spender, remaining, allowance.expiration_ledger);
+
+        let from_balance = Self::get_balance(env.clone(), from.clone());
+        let to_balance = Self::get_balance(env.clone(), to.clone());
+        env.storage().persistent().set(&DataKey::Balance(from), &(from_balance - amount));
+        env.storage().persistent().set(&DataKey::Balance(to), &(to_balance + amount));
+
+        Ok(())
+    }
+
+    /// Get the allowance `spender` can still spend; expired allowances are 0
+    pub fn get_allowance(env: Env, owner: Address, spender: Address) -> i128 {
+        Self::read_allowance(&env, &owner, &spender).amount
+    }
+
+    fn read_allowance(env: &Env, owner: &Address, spender: &Address) -> AllowanceValue {
+        let key = DataKey::Allowance(owner.clone(), spender.clone());
+        match env.storage().temporary().get::<_, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            Some(allowance) => AllowanceValue {
+                amount: 0,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+            None => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    fn write_allowance(
+        env: &Env,
+        owner: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        let key = DataKey::Allowance(owner.clone(), spender.clone());
+        if amount == 0 {
+            env.storage().temporary().remove(&key);
+            return;
+        }
+
+        env.storage().temporary().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        let live_for = expiration_ledger - env.ledger().sequence();
+        env.storage().temporary().extend_ttl(&key, live_for, live_for);
+    }
+
+    // ==================== ACCESS LISTS ====================
+
+    /// Blacklist an address (admin only)
+    /// 
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `address` - The address to blacklist
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::ResourceAlreadyExists` - If address is already blacklisted
+    pub fn add_to_blacklist(env: Env, admin: Address, address: Address) -> Result<(), ValidationError> {
+        Self::set_listed(&env, admin, DataKey::Blacklist(address.clone()), true)?;
+        env.events()
+            .publish((symbol_short!("blacklist"), symbol_short!("added")), address);
+        Ok(())
+    }
+
+    /// Remove an address from the blacklist (admin only)
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::ResourceNotFound` - If address is not blacklisted
+    pub fn remove_from_blacklist(env: Env, admin: Address, address: Address) -> Result<(), ValidationError> {
+        Self::set_listed(&env, admin, DataKey::Blacklist(address.clone()), false)?;
+        env.events()
+            .publish((symbol_short!("blacklist"), symbol_short!("removed")), address);
+        Ok(())
+    }
+
+    /// Check whether an address is blacklisted
+    pub fn is_blacklisted(env: Env, address: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Blacklist(address))
+    }
+
+    /// Whitelist an address (admin only)
+    /// 
+    /// Whitelisting has no effect until whitelist mode is turned on.
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::ResourceAlreadyExists` - If address is already whitelisted
+    pub fn add_to_whitelist(env: Env, admin: Address, address: Address) -> Result<(), ValidationError> {
+        Self::set_listed(&env, admin, DataKey::Whitelist(address.clone()), true)?;
+        env.events()
+            .publish((symbol_short!("whitelist"), symbol_short!("added")), address);
+        Ok(())
+    }
+
+    /// Remove an address from the whitelist (admin only)
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::ResourceNotFound` - If address is not whitelisted
+    pub fn remove_from_whitelist(env: Env, admin: Address, address: Address) -> Result<(), ValidationError> {
+        Self::set_listed(&env, admin, DataKey::Whitelist(address.clone()), false)?;
+        env.events()
+            .publish((symbol_short!("whitelist"), symbol_short!("removed")), address);
+        Ok(())
+    }
+
+    /// Check whether an address is whitelisted
+    pub fn is_whitelisted(env: Env, address: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Whitelist(address))
+    }
+
+    /// Turn whitelist mode on or off (admin only)
+    /// 
+    /// While it is on, only whitelisted addresses can transact.
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    pub fn set_whitelist_mode(env: Env, admin: Address, enabled: bool) -> Result<(), ValidationError> {
+        Self::validate_admin(&env, admin.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::WhitelistMode, &enabled);
+        env.events().publish((symbol_short!("wl_mode"),), enabled);
+        Ok(())
+    }
+
+    /// Check whether whitelist mode is on
+    pub fn is_whitelist_mode(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::WhitelistMode)
+            .unwrap_or(false)
+    }
+
+    /// Add (`listed`) or remove a blacklist / whitelist / signer entry
+    /// after checking the admin
+    fn set_listed(env: &Env, admin: Address, key: DataKey, listed: bool) -> Result<(), ValidationError> {
+        Self::validate_admin(env, admin.clone())?;
+        admin.require_auth();
+
+        let present = env.storage().persistent().has(&key);
+        match (listed, present) {
+            (true, true) => return Err(ValidationError::ResourceAlreadyExists),
+            (false, false) => return Err(ValidationError::ResourceNotFound),
+            (true, false) => env.storage().persistent().set(&key, &true),
+            (false, true) => env.storage().persistent().remove(&key),
+        }
+        Ok(())
+    }
+
+    // ==================== RATE LIMITING ====================
+
+    /// Configure the token-bucket rate limiter (admin only)
+    /// 
+    /// Every address starts with a full bucket of `max_ops` tokens and
+    /// regains `refill_per_ledger` tokens per ledger, up to `max_ops`.
+    /// Unlike the fixed cooldown, this allows short bursts while capping the
+    /// sustained rate. A `max_ops` of 0 turns rate limiting off.
+    /// 
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `max_ops` - Bucket capacity
+    /// * `refill_per_ledger` - Tokens regained per ledger
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::InvalidAmount` - If a non-zero capacity never refills
+    pub fn configure_rate_limit(
+        env: Env,
+        admin: Address,
+        max_ops: u32,
+        refill_per_ledger: u32,
+    ) -> Result<(), ValidationError> {
+        Self::validate_admin(&env, admin.clone())?;
+        admin.require_auth();
+
+        if max_ops == 0 {
+            env.storage().instance().remove(&DataKey::RateLimit);
+        } else if refill_per_ledger == 0 {
+            return Err(ValidationError::InvalidAmount);
+        } else {
+            env.storage().instance().set(
+                &DataKey::RateLimit,
+                &RateLimitConfig {
+                    max_ops,
+                    refill_per_ledger,
+                },
+            );
+        }
+        env.events()
+            .publish((symbol_short!("rate_lim"),), (max_ops, refill_per_ledger));
+        Ok(())
+    }
+
+    /// Spend `cost` tokens from `address`'s bucket
+    /// 
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `address` - The address whose bucket is spent
+    /// * `cost` - Tokens the operation costs
+    /// 
+    /// # Returns
+    /// The tokens left in the bucket
+    /// 
+    /// # Errors
+    /// * `ValidationError::InvalidAmount` - If cost is 0
+    /// * `ValidationError::RateLimitExceeded` - If the bucket holds fewer than `cost` tokens
+    pub fn consume(env: Env, address: Address, cost: u32) -> Result<u32, ValidationError> {
+        if cost == 0 {
+            return Err(ValidationError::InvalidAmount);
+        }
+        address.require_auth();
+
+        Self::take_tokens(&env, &address, cost)
+    }
+
+    /// Get the rate limiter configuration, if rate limiting is on
+    pub fn get_rate_limit(env: Env) -> Option<RateLimitConfig> {
+        env.storage().instance().get(&DataKey::RateLimit)
+    }
+
+    /// Get the tokens `address` could spend right now
+    pub fn get_available_tokens(env: Env, address: Address) -> u32 {
+        match Self::get_rate_limit(env.clone()) {
+            Some(config) => Self::refilled_tokens(&env, &config, &address),
+            None => u32::MAX,
+        }
+    }
+
+    /// Refill `address`'s bucket and take `cost` tokens from it; does
+    /// nothing while rate limiting is off
+    fn take_tokens(env: &Env, address: &Address, cost: u32) -> Result<u32, ValidationError> {
+        let Some(config) = Self::get_rate_limit(env.clone()) else {
+            return Ok(u32::MAX);
+        };
+        let tokens = Self::refilled_tokens(env, &config, address)
+            .checked_sub(cost)
+            .ok_or(ValidationError::RateLimitExceeded)?;
+
+        // A missing bucket reads as full, so the entry only has to live
+        // until it would have refilled.
+        let key = DataKey::Bucket(address.clone());
+        let live_for = (config.max_ops - tokens).div_ceil(config.refill_per_ledger);
+        env.storage().temporary().set(
+            &key,
+            &Bucket {
+                tokens,
+                updated_ledger: env.ledger().sequence(),
+            },
+        );
+        env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        Ok(tokens)
+    }
+
+    /// Tokens in `address`'s bucket after refilling it for the ledgers
+    /// since its last update
+    fn refilled_tokens(env: &Env, config: &RateLimitConfig, address: &Address) -> u32 {
+        let Some(bucket) = env
+            .storage()
+            .temporary()
+            .get::<_, Bucket>(&DataKey::Bucket(address.clone()))
+        else {
+            return config.max_ops;
+        };
+        let elapsed = (env.ledger().sequence() - bucket.updated_ledger) as u64;
+        let refilled = bucket.tokens as u64 + elapsed * config.refill_per_ledger as u64;
+        refilled.min(config.max_ops as u64) as u32
+    }
+
+    // ==================== SIGNED REQUESTS ====================
+
+    /// Trust an ed25519 key to approve requests off-chain (admin only)
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::ResourceAlreadyExists` - If the key is already trusted
+    pub fn add_signer(env: Env, admin: Address, public_key: BytesN<32>) -> Result<(), ValidationError> {
+        Self::set_listed(&env, admin, DataKey::Signer(public_key), true)
+    }
+
+    /// Stop trusting an ed25519 key (admin only)
+    /// 
+    /// # Errors
+    /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::ResourceNotFound` - If the key is not trusted
+    pub fn remove_signer(env: Env, admin: Address, public_key: BytesN<32>) -> Result<(), ValidationError> {
+        Self::set_listed(&env, admin, DataKey::Signer(public_key), false)
+    }
+
+    /// Check whether an ed25519 key may approve requests
+    pub fn is_signer(env: Env, public_key: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::Signer(public_key))
+    }
+
+    /// The bytes a signer signs to approve `payload`
+    /// 
+    /// The contract's own address, the nonce and the expiration ledger are
+    /// signed along with the payload, so an approval cannot be replayed
+    /// against another contract, reused, or kept back and used later.
+    pub fn signed_request_message(env: Env, payload: Bytes, nonce: u64, expiration_ledger: u32) -> Bytes {
+        let mut message = env.current_contract_address().to_xdr(&env);
+        message.extend_from_array(&nonce.to_be_bytes());
+        message.extend_from_array(&expiration_ledger.to_be_bytes());
+        message.append(&payload);
+        message
+    }
+
+    /// Accept `payload` if a trusted key signed it off-chain
+    /// 
+    /// Each nonce is accepted once per key. Used nonces are kept in
+    /// temporary storage until the expiration ledger: after that the
+    /// signature is refused as expired anyway, so the record can go.
+    /// 
+    /// `ed25519_verify` does not return an error: a signature that does not
+    /// match the message traps and aborts the invocation.
+    /// 
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `payload` - The approved request
+    /// * `nonce` - Any value the signer has not used before
+    /// * `expiration_ledger` - Last ledger at which the approval is valid
+    /// * `signature` - ed25519 signature of `signed_request_message`
+    /// * `public_key` - The signer's key
+    /// 
+    /// # Errors
+    /// * `ValidationError::ExpiredSignature` - If the expiration ledger has passed
+    /// * `ValidationError::InvalidExpirationLedger` - If it is beyond the longest storage TTL
+    /// * `ValidationError::InvalidSignature` - If the key is not a trusted signer
+    /// * `ValidationError::SignatureReplayed` - If the nonce has been used
+    pub fn verify_signed_request(
+        env: Env,
+        payload: Bytes,
+        nonce: u64,
+        expiration_ledger: u32,
+        signature: BytesN<64>,
+        public_key: BytesN<32>,
+    ) -> Result<(), ValidationError> {
+        // 1. Parameter validation
+        let current = env.ledger().sequence();
+        if expiration_ledger < current {
+            return Err(ValidationError::ExpiredSignature);
+        }
+        let live_for = expiration_ledger - current;
+        if live_for > env.storage().max_ttl() {
+            return Err(ValidationError::InvalidExpirationLedger);
+        }
+
+        // 2. Authorization validation
+        if !Self::is_signer(env.clone(), public_key.clone()) {
+            return Err(ValidationError::InvalidSignature);
+        }
+        let key = DataKey::UsedNonce(public_key.clone(), nonce);
+        if env.storage().temporary().has(&key) {
+            return Err(ValidationError::SignatureReplayed);
+        }
+        let message = Self::signed_request_message(env.clone(), payload, nonce, expiration_ledger);
+        env.crypto().ed25519_verify(&public_key, &message, &signature);
+
+        // 3. Burn the nonce
+        env.storage().temporary().set(&key, &true);
+        env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        env.events().publish((symbol_short!("signed"), public_key), nonce);
+
+        Ok(())
+    }
+
     // ==================== UTILITY FUNCTIONS ====================
 
     /// Set user role (admin only)
@@ -632,11 +1278,12 @@ impl ValidationContract {
     /// 
     /// # Errors
     /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::InvalidStateTransition` - If contract is not active
     pub fn pause_contract(env: Env, admin: Address) -> Result<(), ValidationError> {
         Self::validate_admin(&env, admin.clone())?;
         admin.require_auth();
 
-        env.storage().instance().set(&DataKey::State, &ContractState::Paused);
+        transition!(&env, ContractState::Active => ContractState::Paused)?;
 
         Ok(())
     }
@@ -649,21 +1296,19 @@ impl ValidationContract {
     /// 
     /// # Errors
     /// * `ValidationError::NotAdmin` - If caller is not admin
+    /// * `ValidationError::InvalidStateTransition` - If contract is not paused
     pub fn resume_contract(env: Env, admin: Address) -> Result<(), ValidationError> {
         Self::validate_admin(&env, admin.clone())?;
         admin.require_auth();
 
-        env.storage().instance().set(&DataKey::State, &ContractState::Active);
+        transition!(&env, ContractState::Paused => ContractState::Active)?;
 
         Ok(())
     }
 
     /// Get contract state
     pub fn get_contract_state(env: Env) -> ContractState {
-        env.storage()
-            .instance()
-            .get(&DataKey::State)
-            .unwrap_or(ContractState::Uninitialized)
+        ContractState::current(&env)
     }
 
     /// Get user role
@@ -1,7 +1,7 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{Env, String, Vec};
-use soroban_sdk::testutils::{Address as AddressTest, Ledger as LedgerTest};
+use soroban_sdk::{symbol_short, Bytes, BytesN, Env, IntoVal, String, Vec};
+use soroban_sdk::testutils::{Address as AddressTest, Events, Ledger as LedgerTest};
 
 #[test]
 fn test_parameter_validation() {
@@ -109,96 +109,89 @@ fn test_parameter_validation() {
 #[test]
 fn test_state_validation() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, ValidationContract);
+    let client = ValidationContractClient::new(&env, &contract_id);
 
+    // Test uninitialized contract
     env.as_contract(&contract_id, || {
-        // Test uninitialized contract
         assert_eq!(
             ValidationContract::validate_contract_state(&env, ContractState::Active),
             Err(ValidationError::ContractNotInitialized)
         );
-
-        // Initialize contract
-        let owner = <soroban_sdk::Address as AddressTest>::generate(&env);
-        env.storage().instance().set(&DataKey::Owner, &owner);
-        env.storage().instance().set(&DataKey::State, &ContractState::Active);
     });
 
-    // Test active contract state
-    assert_eq!(
-        ValidationContract::validate_contract_state(&env, ContractState::Active),
-        Ok(())
-    );
+    // Initialize contract
+    let owner = <soroban_sdk::Address as AddressTest>::generate(&env);
+    client.initialize(&owner);
 
-    // Test balance validation
     let user = <soroban_sdk::Address as AddressTest>::generate(&env);
-    
-    // Insufficient balance
-    assert_eq!(
-        ValidationContract::validate_balance(&env, user.clone(), 100),
-        Err(ValidationError::InsufficientBalance)
-    );
-
-    // Set balance and test again
+    let spender = <soroban_sdk::Address as AddressTest>::generate(&env);
     env.as_contract(&contract_id, || {
-        env.storage().persistent().set(&DataKey::Balance(user.clone()), &200);
-    });
-    assert_eq!(
-        ValidationContract::validate_balance(&env, user.clone(), 100),
-        Ok(())
-    );
+        // Test active contract state
+        assert_eq!(
+            ValidationContract::validate_contract_state(&env, ContractState::Active),
+            Ok(())
+        );
 
-    // Test allowance validation
-    let spender = <soroban_sdk::Address as AddressTest>::generate(&env);
-    
-    // Insufficient allowance
-    assert_eq!(
-        ValidationContract::validate_allowance(&env, user.clone(), spender.clone(), 100),
-        Err(ValidationError::InsufficientAllowance)
-    );
+        // Insufficient balance
+        assert_eq!(
+            ValidationContract::validate_balance(&env, user.clone(), 100),
+            Err(ValidationError::InsufficientBalance)
+        );
 
-    // Set allowance and test again
-    env.as_contract(&contract_id, || {
-        env.storage().persistent().set(&DataKey::Allowance(user.clone(), spender.clone()), &200);
-    });
-    assert_eq!(
-        ValidationContract::validate_allowance(&env, user.clone(), spender.clone(), 100),
-        Ok(())
-    );
+        // Set balance and test again
+        env.storage().persistent().set(&DataKey::Balance(user.clone()), &200i128);
+        assert_eq!(
+            ValidationContract::validate_balance(&env, user.clone(), 100),
+            Ok(())
+        );
 
-    // Test cooldown validation
-    // No previous action should pass
-    assert_eq!(
-        ValidationContract::validate_cooldown(&env, user.clone(), 60),
-        Ok(())
-    );
+        // Insufficient allowance
+        assert_eq!(
+            ValidationContract::validate_allowance(&env, user.clone(), spender.clone(), 100),
+            Err(ValidationError::InsufficientAllowance)
+        );
 
-    // Set last action and test cooldown
-    env.as_contract(&contract_id, || {
+        // Set allowance and test again
+        let allowance = AllowanceValue { amount: 200, expiration_ledger: env.ledger().sequence() };
+        env.storage().temporary().set(&DataKey::Allowance(user.clone(), spender.clone()), &allowance);
+        assert_eq!(
+            ValidationContract::validate_allowance(&env, user.clone(), spender.clone(), 100),
+            Ok(())
+        );
+
+        // No previous action should pass the cooldown
+        assert_eq!(
+            ValidationContract::validate_cooldown(&env, user.clone(), 60),
+            Ok(())
+        );
+
+        // Set last action and test cooldown
         env.storage().persistent().set(&DataKey::LastAction(user.clone()), &env.ledger().timestamp());
+        assert_eq!(
+            ValidationContract::validate_cooldown(&env, user.clone(), 60),
+            Err(ValidationError::CooldownActive)
+        );
     });
-    assert_eq!(
-        ValidationContract::validate_cooldown(&env, user.clone(), 60),
-        Err(ValidationError::CooldownActive)
-    );
 
     // Test paused contract state
+    client.pause_contract(&owner);
     env.as_contract(&contract_id, || {
-        env.storage().instance().set(&DataKey::State, &ContractState::Paused);
+        assert_eq!(
+            ValidationContract::validate_contract_state(&env, ContractState::Active),
+            Err(ValidationError::ContractPaused)
+        );
     });
-    assert_eq!(
-        ValidationContract::validate_contract_state(&env, ContractState::Active),
-        Err(ValidationError::ContractPaused)
-    );
 
     // Resume and test again
+    client.resume_contract(&owner);
     env.as_contract(&contract_id, || {
-        env.storage().instance().set(&DataKey::State, &ContractState::Active);
+        assert_eq!(
+            ValidationContract::validate_contract_state(&env, ContractState::Active),
+            Ok(())
+        );
     });
-    assert_eq!(
-        ValidationContract::validate_contract_state(&env, ContractState::Active),
-        Ok(())
-    );
 }
 
 #[test]
@@ -211,6 +204,7 @@ fn test_authorization_validation() {
         let admin = <soroban_sdk::Address as AddressTest>::generate(&env);
         let user = <soroban_sdk::Address as AddressTest>::generate(&env);
         let moderator = <soroban_sdk::Address as AddressTest>::generate(&env);
+        env.storage().instance().set(&DataKey::Owner, &owner);
 
         // Test role validation with no role assigned
         assert_eq!(
@@ -219,9 +213,7 @@ fn test_authorization_validation() {
         );
 
         // Set user role
-        env.as_contract(&contract_id, || {
-            env.storage().instance().set(&DataKey::UserRole(user.clone()), &UserRole::User);
-        });
+        env.storage().instance().set(&DataKey::UserRole(user.clone()), &UserRole::User);
         assert_eq!(
             ValidationContract::validate_role(&env, user.clone(), UserRole::User),
             Ok(())
@@ -234,19 +226,15 @@ fn test_authorization_validation() {
         );
 
         // Set moderator role
-        env.as_contract(&contract_id, || {
-            env.storage().instance().set(&DataKey::UserRole(moderator.clone()), &UserRole::Moderator);
-        });
+        env.storage().instance().set(&DataKey::UserRole(moderator.clone()), &UserRole::Moderator);
         assert_eq!(
             ValidationContract::validate_role(&env, moderator.clone(), UserRole::User),
             Ok(())
         );
 
         // Set admin role
-        env.as_contract(&contract_id, || {
-            env.storage().instance().set(&DataKey::UserRole(admin.clone()), &UserRole::Admin);
-            env.storage().instance().set(&DataKey::Admin, &admin);
-        });
+        env.storage().instance().set(&DataKey::UserRole(admin.clone()), &UserRole::Admin);
+        env.storage().instance().set(&DataKey::Admin, &admin);
         assert_eq!(
             ValidationContract::validate_role(&env, admin.clone(), UserRole::Moderator),
             Ok(())
@@ -263,144 +251,104 @@ fn test_authorization_validation() {
 #[test]
 fn test_validated_transfer() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, ValidationContract);
+    let client = ValidationContractClient::new(&env, &contract_id);
 
-    env.as_contract(&contract_id, || {
-        // Initialize contract
-        let owner = <soroban_sdk::Address as AddressTest>::generate(&env);
-        let user = <soroban_sdk::Address as AddressTest>::generate(&env);
-        let recipient = <soroban_sdk::Address as AddressTest>::generate(&env);
-        env.storage().instance().set(&DataKey::Admin, &owner);
-        env.storage().instance().set(&DataKey::State, &ContractState::Active);
-
-        // Set user roles
-        env.as_contract(&contract_id, || {
-            env.storage().instance().set(&DataKey::UserRole(user.clone()), &UserRole::User);
-            env.storage().instance().set(&DataKey::UserRole(recipient.clone()), &UserRole::User);
-        });
-
-        // Set initial balance
-        env.as_contract(&contract_id, || {
-            env.storage().persistent().set(&DataKey::Balance(user.clone()), &1000);
-        });
-
-        // Test successful transfer
-        assert_eq!(
-            ValidationContract::validated_transfer(
-                env.clone(),
-                user.clone(),
-                recipient.clone(),
-                100,
-                Some(String::from_str(&env, "Test transfer"))
-            ),
-            Ok(())
-        );
+    // Initialize contract
+    let owner = <soroban_sdk::Address as AddressTest>::generate(&env);
+    let user = <soroban_sdk::Address as AddressTest>::generate(&env);
+    let recipient = <soroban_sdk::Address as AddressTest>::generate(&env);
+    client.initialize(&owner);
 
-        // Verify balances updated
-        env.as_contract(&contract_id, || {
-            let balance1: i128 = env.storage().persistent().get(&DataKey::Balance(user.clone())).unwrap_or(0);
-            let balance2: i128 = env.storage().persistent().get(&DataKey::Balance(recipient.clone())).unwrap_or(0);
-            assert_eq!(balance1, 900);
-            assert_eq!(balance2, 100);
-        });
-
-        // Test insufficient balance
-        env.as_contract(&contract_id, || {
-            ValidationContract::initialize(env.clone(), owner.clone()).unwrap();
-            
-            // Set user role
-            ValidationContract::set_user_role(env.clone(), owner.clone(), user.clone(), UserRole::User).unwrap();
-            
-            // Test validated transfer
-            assert_eq!(
-                ValidationContract::validated_transfer(env.clone(), user.clone(), recipient.clone(), 1000, None),
-                Err(ValidationError::InsufficientBalance)
-            );
-            
-            // Test transfer with insufficient balance
-            assert_eq!(
-                ValidationContract::validated_transfer(env.clone(), user.clone(), recipient.clone(), 1000000, None),
-                Err(ValidationError::InsufficientBalance)
-            );
-        });
-
-        // Test with paused contract
-        env.as_contract(&contract_id, || {
-            env.storage().instance().set(&DataKey::State, &ContractState::Paused);
-        });
-        assert_eq!(
-            ValidationContract::validated_transfer(env.clone(), user.clone(), recipient.clone(), 50, None),
-            Err(ValidationError::ContractPaused)
-        );
+    // Set user roles
+    client.set_user_role(&owner, &user, &UserRole::User);
+    client.set_user_role(&owner, &recipient, &UserRole::User);
 
-        // Resume and test again
-        env.as_contract(&contract_id, || {
-            env.storage().instance().set(&DataKey::State, &ContractState::Active);
-        });
-        
-        // Wait for cooldown to pass
-        env.ledger().set_timestamp(env.ledger().timestamp() + 61);
-        
-        assert_eq!(
-            ValidationContract::validated_transfer(env.clone(), user.clone(), recipient.clone(), 50, None),
-            Ok(())
-        );
+    // Set initial balance
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&DataKey::Balance(user.clone()), &1000i128);
     });
+
+    // Test successful transfer
+    client.validated_transfer(&user, &recipient, &100, &Some(String::from_str(&env, "Test transfer")));
+
+    // Verify balances updated
+    assert_eq!(client.get_balance(&user), 900);
+    assert_eq!(client.get_balance(&recipient), 100);
+
+    // Test transfer with insufficient balance
+    assert_eq!(
+        client.try_validated_transfer(&user, &recipient, &1000, &None),
+        Err(Ok(ValidationError::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_validated_transfer(&user, &recipient, &1000000, &None),
+        Err(Ok(ValidationError::InsufficientBalance))
+    );
+
+    // Test with paused contract
+    client.pause_contract(&owner);
+    assert_eq!(
+        client.try_validated_transfer(&user, &recipient, &50, &None),
+        Err(Ok(ValidationError::ContractPaused))
+    );
+
+    // Resume and wait for cooldown to pass
+    client.resume_contract(&owner);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 61);
+
+    client.validated_transfer(&user, &recipient, &50, &None);
+    assert_eq!(client.get_balance(&recipient), 150);
 }
 
 #[test]
 fn test_admin_functions() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, ValidationContract);
+    let client = ValidationContractClient::new(&env, &contract_id);
 
     let admin = <soroban_sdk::Address as AddressTest>::generate(&env);
     let user = <soroban_sdk::Address as AddressTest>::generate(&env);
 
-    env.as_contract(&contract_id, || {
-        // Initialize contract
-        let owner = <soroban_sdk::Address as AddressTest>::generate(&env);
-        env.storage().instance().set(&DataKey::Owner, &owner);
-        env.storage().instance().set(&DataKey::Admin, &owner);
-        env.storage().instance().set(&DataKey::State, &ContractState::Active);
+    assert_eq!(client.get_contract_state(), ContractState::Uninitialized);
+    client.initialize(&admin);
+    assert_eq!(client.get_contract_state(), ContractState::Active);
 
-        // Set admin role
-        env.storage().instance().set(&DataKey::UserRole(admin.clone()), &UserRole::Admin);
-        env.storage().instance().set(&DataKey::Admin, &admin);
+    // Already initialized
+    assert_eq!(
+        client.try_initialize(&user),
+        Err(Ok(ValidationError::ContractNotInitialized))
+    );
 
-        // Test admin setting user role
-        assert_eq!(
-            ValidationContract::set_user_role(env.clone(), admin.clone(), user.clone(), UserRole::Moderator),
-            Ok(())
-        );
+    // Test admin setting user role
+    client.set_user_role(&admin, &user, &UserRole::Moderator);
+    assert_eq!(client.get_user_role(&user), UserRole::Moderator);
 
-        // Check role was set
-        let role: UserRole = env.storage().instance().get(&DataKey::UserRole(user.clone())).unwrap_or(UserRole::None);
-        assert_eq!(role, UserRole::Moderator);
+    // Test admin pausing contract
+    client.pause_contract(&admin);
+    assert_eq!(client.get_contract_state(), ContractState::Paused);
 
-        // Test admin pausing contract
-        assert_eq!(
-            ValidationContract::pause_contract(env.clone(), admin.clone()),
-            Ok(())
-        );
-        let state: ContractState = env.storage().instance().get(&DataKey::State).unwrap_or(ContractState::Uninitialized);
-        assert_eq!(state, ContractState::Paused);
+    // Pausing twice is not a transition
+    assert_eq!(
+        client.try_pause_contract(&admin),
+        Err(Ok(ValidationError::InvalidStateTransition))
+    );
 
-        // Test admin resuming contract
-        assert_eq!(
-            ValidationContract::resume_contract(env.clone(), admin.clone()),
-            Ok(())
-        );
-        let state: ContractState = env.storage().instance().get(&DataKey::State).unwrap_or(ContractState::Uninitialized);
-        assert_eq!(state, ContractState::Active);
-    });
+    // Test admin resuming contract
+    client.resume_contract(&admin);
+    assert_eq!(client.get_contract_state(), ContractState::Active);
+    assert_eq!(
+        client.try_resume_contract(&admin),
+        Err(Ok(ValidationError::InvalidStateTransition))
+    );
 
     // Test non-admin trying to pause
-    env.as_contract(&contract_id, || {
-        assert_eq!(
-            ValidationContract::pause_contract(env.clone(), user.clone()),
-            Err(ValidationError::NotAdmin)
-        );
-    });
+    assert_eq!(
+        client.try_pause_contract(&user),
+        Err(Ok(ValidationError::NotAdmin))
+    );
 }
 
 #[test]
@@ -422,6 +370,8 @@ fn test_error_codes() {
         ValidationError::InvalidTimestamp,
         ValidationError::TimestampInPast,
         ValidationError::TimestampInDistantFuture,
+        ValidationError::InvalidExpirationLedger,
+        ValidationError::InvalidEmail,
         ValidationError::ContractNotInitialized,
         ValidationError::ContractPaused,
         ValidationError::ContractFrozen,
@@ -443,13 +393,15 @@ fn test_error_codes() {
         ValidationError::ExpiredSignature,
         ValidationError::WrongContract,
         ValidationError::Blacklisted,
+        ValidationError::NotWhitelisted,
+        ValidationError::SignatureReplayed,
     ];
 
     // Verify all errors have unique codes
     let mut codes = Vec::new(&env);
     for error in errors.iter() {
         let code = *error as u32;
-        assert!(!codes.contains(&code), "Duplicate error code: {}", code);
+        assert!(!codes.contains(code), "Duplicate error code: {}", code);
         codes.push_back(code);
     }
 
@@ -463,8 +415,9 @@ fn test_error_codes() {
             ValidationError::StringTooLong | ValidationError::InvalidEnum |
             ValidationError::InvalidArray | ValidationError::ArrayTooSmall |
             ValidationError::ArrayTooLarge | ValidationError::InvalidTimestamp |
-            ValidationError::TimestampInPast | ValidationError::TimestampInDistantFuture => {
-                assert!(code >= 100 && code < 200, "Parameter validation error should be in range 100-199");
+            ValidationError::TimestampInPast | ValidationError::TimestampInDistantFuture |
+            ValidationError::InvalidExpirationLedger | ValidationError::InvalidEmail => {
+                assert!((100..200).contains(&code), "Parameter validation error should be in range 100-199");
             }
             ValidationError::ContractNotInitialized | ValidationError::ContractPaused |
             ValidationError::ContractFrozen | ValidationError::InsufficientBalance |
@@ -472,14 +425,15 @@ fn test_error_codes() {
             ValidationError::ResourceAlreadyExists | ValidationError::InvalidStateTransition |
             ValidationError::InvariantViolation | ValidationError::RateLimitExceeded |
             ValidationError::CooldownActive => {
-                assert!(code >= 200 && code < 300, "State validation error should be in range 200-299");
+                assert!((200..300).contains(&code), "State validation error should be in range 200-299");
             }
             ValidationError::Unauthorized | ValidationError::NotAdmin |
             ValidationError::NotOwner | ValidationError::InsufficientRole |
             ValidationError::SignatureRequired | ValidationError::MultiSigRequired |
             ValidationError::InvalidSignature | ValidationError::ExpiredSignature |
-            ValidationError::WrongContract | ValidationError::Blacklisted => {
-                assert!(code >= 300 && code < 400, "Authorization validation error should be in range 300-399");
+            ValidationError::WrongContract | ValidationError::Blacklisted |
+            ValidationError::NotWhitelisted | ValidationError::SignatureReplayed => {
+                assert!((300..400).contains(&code), "Authorization validation error should be in range 300-399");
             }
         }
     }
@@ -549,3 +503,580 @@ fn test_edge_cases() {
         Err(ValidationError::TimestampInDistantFuture)
     ); // One second over limit
 }
+
+fn setup_with_balance(amount: i128) -> (Env, ValidationContractClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+    let contract_id = env.register_contract(None, ValidationContract);
+    let client = ValidationContractClient::new(&env, &contract_id);
+
+    let owner = <soroban_sdk::Address as AddressTest>::generate(&env);
+    let spender = <soroban_sdk::Address as AddressTest>::generate(&env);
+    let recipient = <soroban_sdk::Address as AddressTest>::generate(&env);
+    client.initialize(&owner);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&DataKey::Balance(owner.clone()), &amount);
+    });
+    (env, client, owner, spender, recipient)
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let (_env, client, owner, spender, recipient) = setup_with_balance(1000);
+
+    client.approve(&owner, &spender, &300, &200);
+    assert_eq!(client.get_allowance(&owner, &spender), 300);
+
+    // Each transfer decrements the allowance
+    client.transfer_from(&spender, &owner, &recipient, &100);
+    assert_eq!(client.get_allowance(&owner, &spender), 200);
+    assert_eq!(client.get_balance(&owner), 900);
+    assert_eq!(client.get_balance(&recipient), 100);
+
+    client.transfer_from(&spender, &owner, &recipient, &200);
+    assert_eq!(client.get_allowance(&owner, &spender), 0);
+
+    // Spent down to zero: nothing more can move
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &recipient, &1),
+        Err(Ok(ValidationError::InsufficientAllowance))
+    );
+    assert_eq!(client.get_balance(&owner), 700);
+}
+
+#[test]
+fn test_transfer_from_over_allowance_fails() {
+    let (_env, client, owner, spender, recipient) = setup_with_balance(1000);
+    client.approve(&owner, &spender, &50, &200);
+
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &recipient, &51),
+        Err(Ok(ValidationError::InsufficientAllowance))
+    );
+    // Nothing was spent
+    assert_eq!(client.get_allowance(&owner, &spender), 50);
+    assert_eq!(client.get_balance(&owner), 1000);
+
+    // The allowance does not cover a missing balance either
+    client.approve(&owner, &spender, &5000, &200);
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &recipient, &2000),
+        Err(Ok(ValidationError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_allowance_expires() {
+    let (env, client, owner, spender, recipient) = setup_with_balance(1000);
+    client.approve(&owner, &spender, &300, &110);
+
+    env.ledger().set_sequence_number(110);
+    client.transfer_from(&spender, &owner, &recipient, &100);
+
+    env.ledger().set_sequence_number(111);
+    assert_eq!(client.get_allowance(&owner, &spender), 0);
+    assert_eq!(
+        client.try_transfer_from(&spender, &owner, &recipient, &100),
+        Err(Ok(ValidationError::InsufficientAllowance))
+    );
+}
+
+#[test]
+fn test_approve_validation() {
+    let (_env, client, owner, spender, _recipient) = setup_with_balance(1000);
+
+    assert_eq!(
+        client.try_approve(&owner, &spender, &-1, &200),
+        Err(Ok(ValidationError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_approve(&owner, &owner, &10, &200),
+        Err(Ok(ValidationError::InvalidAddress))
+    );
+    assert_eq!(
+        client.try_approve(&owner, &spender, &10, &99),
+        Err(Ok(ValidationError::InvalidExpirationLedger))
+    );
+
+    // Approving zero revokes, whatever the expiration
+    client.approve(&owner, &spender, &10, &200);
+    client.approve(&owner, &spender, &0, &0);
+    assert_eq!(client.get_allowance(&owner, &spender), 0);
+
+    client.pause_contract(&owner);
+    assert_eq!(
+        client.try_approve(&owner, &spender, &10, &200),
+        Err(Ok(ValidationError::ContractPaused))
+    );
+}
+
+#[test]
+fn test_blacklisted_address_cannot_transfer() {
+    let (env, client, owner, user, recipient) = setup_with_balance(1000);
+    client.set_user_role(&owner, &owner, &UserRole::User);
+
+    client.add_to_blacklist(&owner, &user);
+    assert!(client.is_blacklisted(&user));
+    assert_eq!(
+        client.try_add_to_blacklist(&owner, &user),
+        Err(Ok(ValidationError::ResourceAlreadyExists))
+    );
+
+    // Blocked as sender and as recipient
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&DataKey::Balance(user.clone()), &500i128);
+    });
+    client.set_user_role(&owner, &user, &UserRole::User);
+    assert_eq!(
+        client.try_validated_transfer(&user, &recipient, &100, &None),
+        Err(Ok(ValidationError::Blacklisted))
+    );
+    assert_eq!(
+        client.try_validated_transfer(&owner, &user, &100, &None),
+        Err(Ok(ValidationError::Blacklisted))
+    );
+
+    // Also blocked as a spender
+    client.approve(&owner, &user, &100, &200);
+    assert_eq!(
+        client.try_transfer_from(&user, &owner, &recipient, &50),
+        Err(Ok(ValidationError::Blacklisted))
+    );
+
+    client.remove_from_blacklist(&owner, &user);
+    assert!(!client.is_blacklisted(&user));
+    assert_eq!(
+        client.try_remove_from_blacklist(&owner, &user),
+        Err(Ok(ValidationError::ResourceNotFound))
+    );
+    client.validated_transfer(&user, &recipient, &100, &None);
+    assert_eq!(client.get_balance(&recipient), 100);
+}
+
+#[test]
+fn test_whitelist_mode() {
+    let (env, client, owner, user, recipient) = setup_with_balance(1000);
+    client.set_user_role(&owner, &owner, &UserRole::User);
+    client.add_to_whitelist(&owner, &owner);
+    assert!(client.is_whitelisted(&owner));
+
+    // The whitelist is ignored until the mode is on
+    assert!(!client.is_whitelist_mode());
+    client.validated_transfer(&owner, &recipient, &100, &None);
+
+    client.set_whitelist_mode(&owner, &true);
+    assert!(client.is_whitelist_mode());
+    env.ledger().set_timestamp(env.ledger().timestamp() + 61);
+    assert_eq!(
+        client.try_validated_transfer(&owner, &user, &100, &None),
+        Err(Ok(ValidationError::NotWhitelisted))
+    );
+
+    client.add_to_whitelist(&owner, &user);
+    client.validated_transfer(&owner, &user, &100, &None);
+    assert_eq!(client.get_balance(&user), 100);
+
+    // The blacklist wins over the whitelist
+    client.add_to_blacklist(&owner, &user);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 61);
+    assert_eq!(
+        client.try_validated_transfer(&owner, &user, &100, &None),
+        Err(Ok(ValidationError::Blacklisted))
+    );
+}
+
+#[test]
+fn test_access_lists_require_admin() {
+    let (env, client, owner, user, _recipient) = setup_with_balance(1000);
+    let outsider = <soroban_sdk::Address as AddressTest>::generate(&env);
+
+    assert_eq!(
+        client.try_add_to_blacklist(&outsider, &user),
+        Err(Ok(ValidationError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_add_to_whitelist(&outsider, &user),
+        Err(Ok(ValidationError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_set_whitelist_mode(&outsider, &true),
+        Err(Ok(ValidationError::NotAdmin))
+    );
+
+    // Each change emits an event
+    client.add_to_blacklist(&owner, &user);
+    let (_id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        soroban_sdk::vec![&env, symbol_short!("blacklist").into_val(&env), symbol_short!("added").into_val(&env)]
+    );
+    let listed: Address = data.into_val(&env);
+    assert_eq!(listed, user);
+}
+
+#[test]
+fn test_rate_limit_refills_over_ledgers() {
+    let (env, client, owner, user, _recipient) = setup_with_balance(1000);
+
+    // Off by default: nothing is limited
+    assert_eq!(client.get_rate_limit(), None);
+    assert_eq!(client.consume(&user, &100), u32::MAX);
+
+    client.configure_rate_limit(&owner, &5, &2);
+    assert_eq!(
+        client.get_rate_limit(),
+        Some(RateLimitConfig { max_ops: 5, refill_per_ledger: 2 })
+    );
+
+    // A fresh bucket is full and allows a burst up to its capacity
+    assert_eq!(client.get_available_tokens(&user), 5);
+    assert_eq!(client.consume(&user, &3), 2);
+    assert_eq!(client.consume(&user, &2), 0);
+    assert_eq!(
+        client.try_consume(&user, &1),
+        Err(Ok(ValidationError::RateLimitExceeded))
+    );
+
+    // Two tokens come back per ledger
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    assert_eq!(client.get_available_tokens(&user), 2);
+    assert_eq!(
+        client.try_consume(&user, &3),
+        Err(Ok(ValidationError::RateLimitExceeded))
+    );
+    assert_eq!(client.consume(&user, &1), 1);
+
+    // Refill stops at the capacity
+    env.ledger().set_sequence_number(env.ledger().sequence() + 10);
+    assert_eq!(client.get_available_tokens(&user), 5);
+
+    // Buckets are per address
+    let other = <soroban_sdk::Address as AddressTest>::generate(&env);
+    client.consume(&user, &5);
+    assert_eq!(client.get_available_tokens(&other), 5);
+}
+
+#[test]
+fn test_rate_limit_applies_to_validated_transfer() {
+    let (env, client, owner, _user, recipient) = setup_with_balance(1000);
+    client.set_user_role(&owner, &owner, &UserRole::User);
+    client.configure_rate_limit(&owner, &1, &1);
+
+    client.validated_transfer(&owner, &recipient, &100, &None);
+    assert_eq!(client.get_available_tokens(&owner), 0);
+
+    // Past the cooldown but in the same ledger, the bucket is still empty
+    env.ledger().set_timestamp(env.ledger().timestamp() + 61);
+    assert_eq!(
+        client.try_validated_transfer(&owner, &recipient, &100, &None),
+        Err(Ok(ValidationError::RateLimitExceeded))
+    );
+
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    client.validated_transfer(&owner, &recipient, &100, &None);
+    assert_eq!(client.get_balance(&recipient), 200);
+
+    // A capacity of 0 turns the limiter off
+    client.configure_rate_limit(&owner, &0, &0);
+    assert_eq!(client.get_rate_limit(), None);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 61);
+    client.validated_transfer(&owner, &recipient, &100, &None);
+}
+
+#[test]
+fn test_rate_limit_configuration_validation() {
+    let (env, client, owner, user, _recipient) = setup_with_balance(1000);
+    let outsider = <soroban_sdk::Address as AddressTest>::generate(&env);
+
+    assert_eq!(
+        client.try_configure_rate_limit(&outsider, &5, &1),
+        Err(Ok(ValidationError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_configure_rate_limit(&owner, &5, &0),
+        Err(Ok(ValidationError::InvalidAmount))
+    );
+
+    client.configure_rate_limit(&owner, &5, &1);
+    assert_eq!(
+        client.try_consume(&user, &0),
+        Err(Ok(ValidationError::InvalidAmount))
+    );
+}
+
+fn sign_request(
+    client: &ValidationContractClient,
+    key: &ed25519_dalek::SigningKey,
+    payload: &Bytes,
+    nonce: u64,
+    expiration_ledger: u32,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer as _;
+    let message = client.signed_request_message(payload, &nonce, &expiration_ledger);
+    let mut buf = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buf[..len]);
+    BytesN::from_array(&client.env, &key.sign(&buf[..len]).to_bytes())
+}
+
+#[test]
+fn test_signed_request_accepted_once() {
+    let (env, client, owner, _user, _recipient) = setup_with_balance(1000);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    client.add_signer(&owner, &public_key);
+    assert!(client.is_signer(&public_key));
+
+    let payload = Bytes::from_slice(&env, b"withdraw 100");
+    let expiry = env.ledger().sequence() + 100;
+    let signature = sign_request(&client, &key, &payload, 1, expiry);
+    client.verify_signed_request(&payload, &1, &expiry, &signature, &public_key);
+
+    // The same approval cannot be used twice
+    assert_eq!(
+        client.try_verify_signed_request(&payload, &1, &expiry, &signature, &public_key),
+        Err(Ok(ValidationError::SignatureReplayed))
+    );
+
+    // A fresh nonce approves the same payload again
+    let signature = sign_request(&client, &key, &payload, 2, expiry);
+    client.verify_signed_request(&payload, &2, &expiry, &signature, &public_key);
+}
+
+#[test]
+fn test_signed_request_rejections() {
+    let (env, client, owner, _user, _recipient) = setup_with_balance(1000);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let payload = Bytes::from_slice(&env, b"withdraw 100");
+    let expiry = env.ledger().sequence() + 100;
+    let signature = sign_request(&client, &key, &payload, 1, expiry);
+
+    // Not a trusted signer yet
+    assert_eq!(
+        client.try_verify_signed_request(&payload, &1, &expiry, &signature, &public_key),
+        Err(Ok(ValidationError::InvalidSignature))
+    );
+    client.add_signer(&owner, &public_key);
+
+    // A tampered payload or nonce does not match the signature and traps
+    let tampered = Bytes::from_slice(&env, b"withdraw 900");
+    assert!(client
+        .try_verify_signed_request(&tampered, &1, &expiry, &signature, &public_key)
+        .is_err());
+    assert!(client
+        .try_verify_signed_request(&payload, &9, &expiry, &signature, &public_key)
+        .is_err());
+
+    // Too late
+    env.ledger().set_sequence_number(expiry + 1);
+    assert_eq!(
+        client.try_verify_signed_request(&payload, &1, &expiry, &signature, &public_key),
+        Err(Ok(ValidationError::ExpiredSignature))
+    );
+
+    // Removed keys are no longer trusted
+    client.remove_signer(&owner, &public_key);
+    assert!(!client.is_signer(&public_key));
+    let expiry = env.ledger().sequence() + 100;
+    let signature = sign_request(&client, &key, &payload, 1, expiry);
+    assert_eq!(
+        client.try_verify_signed_request(&payload, &1, &expiry, &signature, &public_key),
+        Err(Ok(ValidationError::InvalidSignature))
+    );
+}
+
+#[test]
+fn test_signed_request_is_bound_to_contract() {
+    let (env, client, owner, _user, _recipient) = setup_with_balance(1000);
+    let other = ValidationContractClient::new(&env, &env.register_contract(None, ValidationContract));
+    other.initialize(&owner);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    client.add_signer(&owner, &public_key);
+    other.add_signer(&owner, &public_key);
+
+    let payload = Bytes::from_slice(&env, b"withdraw 100");
+    let expiry = env.ledger().sequence() + 100;
+    assert_ne!(
+        client.signed_request_message(&payload, &1, &expiry),
+        other.signed_request_message(&payload, &1, &expiry)
+    );
+    let signature = sign_request(&other, &key, &payload, 1, expiry);
+    assert!(client
+        .try_verify_signed_request(&payload, &1, &expiry, &signature, &public_key)
+        .is_err());
+}
+
+#[test]
+fn test_string_charset_validation() {
+    let env = Env::default();
+    let check = |text: &str, charset: Charset| {
+        ValidationContract::validate_string_charset(String::from_str(&env, text), charset)
+    };
+
+    // Identifiers: letters, digits and underscore only
+    assert_eq!(check("user_42", Charset::Identifier), Ok(()));
+    assert_eq!(check("", Charset::Identifier), Ok(()));
+    for bad in ["user-42", "user 42", "user.42", "user\n"] {
+        assert_eq!(check(bad, Charset::Identifier), Err(ValidationError::InvalidString), "{}", bad);
+    }
+
+    // Printable ASCII allows spaces and punctuation, but no control characters
+    assert_eq!(check("Hello, World! ~{}", Charset::PrintableAscii), Ok(()));
+    for bad in ["line\nbreak", "tab\there", "nul\0", "del\x7f", "bell\x07"] {
+        assert_eq!(check(bad, Charset::PrintableAscii), Err(ValidationError::InvalidString), "{:?}", bad);
+    }
+
+    // Multi-byte UTF-8 fails both, whatever the script
+    for bad in ["héllo", "名前", "emoji_🦀", "ñ"] {
+        assert_eq!(check(bad, Charset::Identifier), Err(ValidationError::InvalidString), "{}", bad);
+        assert_eq!(check(bad, Charset::PrintableAscii), Err(ValidationError::InvalidString), "{}", bad);
+    }
+
+    // Input longer than the stack buffer is refused before copying
+    let mut long = [b'a'; MAX_SANITIZED_LEN as usize + 1];
+    let text = |bytes: &[u8]| String::from_bytes(&env, bytes);
+    assert_eq!(
+        ValidationContract::validate_string_charset(text(&long[..MAX_SANITIZED_LEN as usize]), Charset::Identifier),
+        Ok(())
+    );
+    assert_eq!(
+        ValidationContract::validate_string_charset(text(&long), Charset::Identifier),
+        Err(ValidationError::StringTooLong)
+    );
+
+    // A host string need not be valid UTF-8; stray bytes are still rejected
+    long[0] = 0xff;
+    assert_eq!(
+        ValidationContract::validate_string_charset(text(&long[..4]), Charset::PrintableAscii),
+        Err(ValidationError::InvalidString)
+    );
+}
+
+#[test]
+fn test_email_validation() {
+    let env = Env::default();
+    let check = |text: &str| ValidationContract::validate_email(String::from_str(&env, text));
+
+    for good in ["a@b.co", "first.last+tag@mail.example.org", "x_1@sub.domain.io"] {
+        assert_eq!(check(good), Ok(()), "{}", good);
+    }
+    for bad in [
+        "",
+        "plainaddress",
+        "@example.com",
+        "user@",
+        "user@localhost",
+        "user@@example.com",
+        "user@exa@mple.com",
+        "user@.example.com",
+        "user@example.com.",
+        "user@example..com",
+        "user name@example.com",
+        "user@exam\tple.com",
+        "üser@example.com",
+        "user@exämple.com",
+    ] {
+        assert_eq!(check(bad), Err(ValidationError::InvalidEmail), "{:?}", bad);
+    }
+}
+
+#[test]
+fn test_validator_returns_first_failure() {
+    let (env, client, owner, user, _recipient) = setup_with_balance(1000);
+    client.pause_contract(&owner);
+
+    env.as_contract(&client.address, || {
+        // Two failing checks: whichever comes first in the chain is reported
+        assert_eq!(
+            Validator::new(&env)
+                .amount(0, 1, 100)
+                .state(ContractState::Active)
+                .finish(),
+            Err(ValidationError::InvalidAmount)
+        );
+        assert_eq!(
+            Validator::new(&env)
+                .state(ContractState::Active)
+                .amount(0, 1, 100)
+                .finish(),
+            Err(ValidationError::ContractPaused)
+        );
+
+        // Passing checks before a failure do not hide it
+        assert_eq!(
+            Validator::new(&env)
+                .amount(50, 1, 100)
+                .address(&user)
+                .balance(&user, 1)
+                .role(&owner, UserRole::Owner)
+                .finish(),
+            Err(ValidationError::InsufficientBalance)
+        );
+
+        // An empty chain, or one where everything passes, is Ok
+        assert_eq!(Validator::new(&env).finish(), Ok(()));
+        assert_eq!(
+            Validator::new(&env)
+                .amount(50, 1, 100)
+                .balance(&owner, 1000)
+                .cooldown(&owner, 60)
+                .access_list(&user)
+                .finish(),
+            Ok(())
+        );
+    });
+}
+
+#[test]
+fn test_validator_skips_checks_after_failure() {
+    let env = Env::default();
+    let calls = core::cell::Cell::new(0);
+    let counted = |result: Result<(), ValidationError>| {
+        calls.set(calls.get() + 1);
+        result
+    };
+
+    let result = Validator::new(&env)
+        .check(|| counted(Ok(())))
+        .check(|| counted(Err(ValidationError::NotOwner)))
+        .check(|| counted(Err(ValidationError::NotAdmin)))
+        .check(|| counted(Ok(())))
+        .finish();
+    assert_eq!(result, Err(ValidationError::NotOwner));
+    // The checks after the failing one never ran
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn test_validated_transfer_reports_first_failing_check() {
+    let (env, client, owner, _user, recipient) = setup_with_balance(1000);
+    client.pause_contract(&owner);
+
+    // Amount (parameter) is checked before state
+    assert_eq!(
+        client.try_validated_transfer(&owner, &recipient, &0, &None),
+        Err(Ok(ValidationError::InvalidAmount))
+    );
+    // A valid amount reaches the state check
+    assert_eq!(
+        client.try_validated_transfer(&owner, &recipient, &100, &None),
+        Err(Ok(ValidationError::ContractPaused))
+    );
+
+    // Active again: the sender has no role yet
+    client.resume_contract(&owner);
+    assert_eq!(
+        client.try_validated_transfer(&owner, &recipient, &100, &None),
+        Err(Ok(ValidationError::InsufficientRole))
+    );
+
+    // A message that fails is reported before anything stateful
+    client.pause_contract(&owner);
+    assert_eq!(
+        client.try_validated_transfer(&owner, &recipient, &100, &Some(String::from_str(&env, ""))),
+        Err(Ok(ValidationError::InvalidString))
+    );
+}
@@ -0,0 +1,98 @@
+//! A chainable validator
+//!
+//! Each method runs one of the contract's `validate_*` checks and records
+//! its result. Once a check has failed, every later method is skipped, so
+//! `finish` returns the first failure in the order the chain was written,
+//! exactly as a sequence of `?` would, and no storage is read for the
+//! skipped checks:
+//!
+//! ```ignore
+//! Validator::new(&env)
+//!     .amount(amount, 1, 1_000_000)
+//!     .state(ContractState::Active)
+//!     .balance(&from, amount)
+//!     .role(&from, UserRole::User)
+//!     .cooldown(&from, 60)
+//!     .finish()?;
+//! ```
+//!
+//! The arguments are evaluated when the chain is built, but the checks are
+//! not. Put anything expensive to compute behind `check`, whose closure only
+//! runs if every earlier check passed.
+
+use soroban_sdk::{Address, Env, String};
+
+use crate::{ContractState, UserRole, ValidationContract, ValidationError};
+
+/// Runs checks in order and keeps the first failure
+pub struct Validator<'a> {
+    env: &'a Env,
+    result: Result<(), ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        Self { env, result: Ok(()) }
+    }
+
+    /// Run `f` unless an earlier check has failed
+    pub fn check<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce() -> Result<(), ValidationError>,
+    {
+        if self.result.is_ok() {
+            self.result = f();
+        }
+        self
+    }
+
+    /// See `ValidationContract::validate_amount_parameters`
+    pub fn amount(self, amount: i128, min: i128, max: i128) -> Self {
+        self.check(|| ValidationContract::validate_amount_parameters(amount, min, max))
+    }
+
+    /// See `ValidationContract::validate_string_parameters`
+    pub fn string(self, text: &String, min_length: u32, max_length: u32) -> Self {
+        self.check(|| ValidationContract::validate_string_parameters(text.clone(), min_length, max_length))
+    }
+
+    /// See `ValidationContract::validate_address`
+    pub fn address(self, address: &Address) -> Self {
+        self.check(|| ValidationContract::validate_address(address.clone()))
+    }
+
+    /// See `ValidationContract::validate_contract_state`
+    pub fn state(self, required: ContractState) -> Self {
+        let env = self.env;
+        self.check(|| ValidationContract::validate_contract_state(env, required))
+    }
+
+    /// See `ValidationContract::validate_balance`
+    pub fn balance(self, address: &Address, required: i128) -> Self {
+        let env = self.env;
+        self.check(|| ValidationContract::validate_balance(env, address.clone(), required))
+    }
+
+    /// See `ValidationContract::validate_role`
+    pub fn role(self, address: &Address, required: UserRole) -> Self {
+        let env = self.env;
+        self.check(|| ValidationContract::validate_role(env, address.clone(), required))
+    }
+
+    /// See `ValidationContract::validate_access_list`
+    pub fn access_list(self, address: &Address) -> Self {
+        let env = self.env;
+        self.check(|| ValidationContract::validate_access_list(env, address.clone()))
+    }
+
+    /// See `ValidationContract::validate_cooldown`
+    pub fn cooldown(self, address: &Address, seconds: u64) -> Self {
+        let env = self.env;
+        self.check(|| ValidationContract::validate_cooldown(env, address.clone(), seconds))
+    }
+
+    /// The first failure, or `Ok` if every check passed
+    pub fn finish(self) -> Result<(), ValidationError> {
+        self.result
+    }
+}
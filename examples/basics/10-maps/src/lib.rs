@@ -0,0 +1,215 @@
+//! # Maps
+//!
+//! `soroban_sdk::Map<K, V>` is the host's ordered key-value collection. This
+//! example uses a small leaderboard to show:
+//!
+//! - **Creation and insertion** – `Map::new`, `map!`, `set`
+//! - **Lookup and removal** – `get`, `contains_key`, `remove`
+//! - **Iteration** – entries come back sorted by key, not by insertion order
+//! - **Keys and values** – `keys()` / `values()` as `Vec`s
+//! - **Nested maps** – `Map<Symbol, Map<Symbol, u32>>` for per-team scores
+//! - **Storage layout** – one stored map vs. one entry per key
+//!
+//! ## Map vs. per-key entries
+//!
+//! ```text
+//! DataKey::Scores            → Map { alice: 7, bob: 3, ... }   (one entry)
+//! DataKey::Score(alice)      → 7                              (one entry per key)
+//! DataKey::Score(bob)        → 3
+//! ```
+//!
+//! A stored map is read and written as a whole: updating one score
+//! deserialises and re-serialises every score, so the cost of a single
+//! update grows with the size of the map, and the entry can never grow past
+//! the ledger's entry size limit. Per-key entries cost the same no matter
+//! how many keys exist, but the contract can no longer list or iterate them
+//! without keeping a separate index.
+//!
+//! Use a stored map for small, bounded collections that are usually read
+//! together (config, a handful of roles). Flatten to per-key entries for
+//! anything that grows with the number of users. The benchmark tests in
+//! `test.rs` measure the difference.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Env, Map, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The whole leaderboard, stored as a single `Map<Symbol, u32>`.
+    Scores,
+    /// One player's score, flattened into its own entry.
+    Score(Symbol),
+    /// Per-team leaderboards: `Map<Symbol, Map<Symbol, u32>>`.
+    Teams,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MapError {
+    /// `keys` and `values` passed to `from_pairs` differ in length.
+    LengthMismatch = 1,
+}
+
+#[contract]
+pub struct MapsContract;
+
+#[contractimpl]
+impl MapsContract {
+    // ==================== CREATION ====================
+
+    /// Build a map from parallel key and value vectors.
+    ///
+    /// Inserting a key twice keeps the last value: `set` overwrites.
+    pub fn from_pairs(
+        env: Env,
+        keys: Vec<Symbol>,
+        values: Vec<u32>,
+    ) -> Result<Map<Symbol, u32>, MapError> {
+        if keys.len() != values.len() {
+            return Err(MapError::LengthMismatch);
+        }
+        let mut map = Map::new(&env);
+        for (key, value) in keys.iter().zip(values.iter()) {
+            map.set(key, value);
+        }
+        Ok(map)
+    }
+
+    /// Combine two maps; on a shared key the value from `overrides` wins.
+    pub fn merge(
+        _env: Env,
+        base: Map<Symbol, u32>,
+        overrides: Map<Symbol, u32>,
+    ) -> Map<Symbol, u32> {
+        let mut merged = base;
+        for (key, value) in overrides.iter() {
+            merged.set(key, value);
+        }
+        merged
+    }
+
+    // ==================== MAP IN ONE STORAGE ENTRY ====================
+
+    /// Set `player`'s score in the stored leaderboard map.
+    pub fn set_score(env: Env, player: Symbol, score: u32) {
+        let mut scores = Self::scores(env.clone());
+        scores.set(player, score);
+        env.storage().persistent().set(&DataKey::Scores, &scores);
+    }
+
+    /// Remove `player` and return the score they had, if any.
+    pub fn remove_score(env: Env, player: Symbol) -> Option<u32> {
+        let mut scores = Self::scores(env.clone());
+        let removed = scores.get(player.clone());
+        if removed.is_some() {
+            scores.remove(player);
+            env.storage().persistent().set(&DataKey::Scores, &scores);
+        }
+        removed
+    }
+
+    pub fn get_score(env: Env, player: Symbol) -> Option<u32> {
+        Self::scores(env).get(player)
+    }
+
+    pub fn has_player(env: Env, player: Symbol) -> bool {
+        Self::scores(env).contains_key(player)
+    }
+
+    /// The whole leaderboard. An empty map if nothing was stored yet.
+    pub fn scores(env: Env) -> Map<Symbol, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scores)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Player names, in key order.
+    pub fn players(env: Env) -> Vec<Symbol> {
+        Self::scores(env).keys()
+    }
+
+    /// Scores, in the same order as `players`.
+    pub fn score_values(env: Env) -> Vec<u32> {
+        Self::scores(env).values()
+    }
+
+    /// Sum of all scores, computed by iterating the map.
+    pub fn total_score(env: Env) -> u64 {
+        Self::scores(env)
+            .iter()
+            .map(|(_, score)| score as u64)
+            .sum()
+    }
+
+    /// The highest score and who holds it. On a tie the player whose name
+    /// sorts first wins, because iteration follows key order.
+    pub fn top_player(env: Env) -> Option<(Symbol, u32)> {
+        let mut top: Option<(Symbol, u32)> = None;
+        for (player, score) in Self::scores(env).iter() {
+            if top.as_ref().is_none_or(|(_, best)| score > *best) {
+                top = Some((player, score));
+            }
+        }
+        top
+    }
+
+    // ==================== FLATTENED: ONE ENTRY PER KEY ====================
+
+    /// Set `player`'s score in its own storage entry.
+    pub fn set_score_flat(env: Env, player: Symbol, score: u32) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Score(player), &score);
+    }
+
+    pub fn remove_score_flat(env: Env, player: Symbol) -> Option<u32> {
+        let key = DataKey::Score(player);
+        let removed = env.storage().persistent().get(&key);
+        env.storage().persistent().remove(&key);
+        removed
+    }
+
+    pub fn get_score_flat(env: Env, player: Symbol) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::Score(player))
+    }
+
+    // ==================== NESTED MAPS ====================
+
+    /// Set `player`'s score inside `team`, creating the team on first use.
+    pub fn set_team_score(env: Env, team: Symbol, player: Symbol, score: u32) {
+        let mut teams = Self::teams(env.clone());
+        let mut members = teams.get(team.clone()).unwrap_or(Map::new(&env));
+        members.set(player, score);
+        // The inner map is a value: the change only sticks once it is put back.
+        teams.set(team, members);
+        env.storage().persistent().set(&DataKey::Teams, &teams);
+    }
+
+    /// Scores of one team; empty if the team does not exist.
+    pub fn team_scores(env: Env, team: Symbol) -> Map<Symbol, u32> {
+        Self::teams(env.clone()).get(team).unwrap_or(Map::new(&env))
+    }
+
+    /// Each team's total score.
+    pub fn team_totals(env: Env) -> Map<Symbol, u64> {
+        let mut totals = Map::new(&env);
+        for (team, members) in Self::teams(env.clone()).iter() {
+            let total: u64 = members.values().iter().map(|s| s as u64).sum();
+            totals.set(team, total);
+        }
+        totals
+    }
+
+    pub fn teams(env: Env) -> Map<Symbol, Map<Symbol, u32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Teams)
+            .unwrap_or(Map::new(&env))
+    }
+}
+
+mod test;
@@ -0,0 +1,236 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{map, symbol_short, vec, xdr::ToXdr, IntoVal, Val};
+
+fn setup() -> (Env, MapsContractClient<'static>) {
+    let env = Env::default();
+    let client = MapsContractClient::new(&env, &env.register_contract(None, MapsContract));
+    (env, client)
+}
+
+fn player(env: &Env, i: u32) -> Symbol {
+    Symbol::new(env, &std::format!("player{i}"))
+}
+
+// ==================== CREATION ====================
+
+#[test]
+fn test_from_pairs() {
+    let (env, client) = setup();
+    let keys = vec![&env, symbol_short!("bob"), symbol_short!("alice")];
+    let map = client.from_pairs(&keys, &vec![&env, 3, 7]);
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(symbol_short!("alice")), Some(7));
+    // Same contents built with the `map!` macro compare equal.
+    assert_eq!(
+        map,
+        map![&env, (symbol_short!("alice"), 7), (symbol_short!("bob"), 3)]
+    );
+    assert_eq!(
+        client.try_from_pairs(&keys, &vec![&env, 1]),
+        Err(Ok(MapError::LengthMismatch))
+    );
+}
+
+#[test]
+fn test_duplicate_key_keeps_last_value() {
+    let (env, client) = setup();
+    let a = symbol_short!("a");
+    let map = client.from_pairs(&vec![&env, a.clone(), a.clone()], &vec![&env, 1, 2]);
+    assert_eq!(map, map![&env, (a, 2)]);
+}
+
+#[test]
+fn test_merge_prefers_overrides() {
+    let (env, client) = setup();
+    let base = map![&env, (symbol_short!("a"), 1), (symbol_short!("b"), 2)];
+    let overrides = map![&env, (symbol_short!("b"), 20), (symbol_short!("c"), 30)];
+    assert_eq!(
+        client.merge(&base, &overrides),
+        map![
+            &env,
+            (symbol_short!("a"), 1),
+            (symbol_short!("b"), 20),
+            (symbol_short!("c"), 30)
+        ]
+    );
+}
+
+// ==================== STORED MAP ====================
+
+#[test]
+fn test_insert_get_remove() {
+    let (_env, client) = setup();
+    let alice = symbol_short!("alice");
+
+    assert_eq!(client.get_score(&alice), None);
+    client.set_score(&alice, &7);
+    client.set_score(&alice, &9);
+    assert_eq!(client.get_score(&alice), Some(9));
+    assert!(client.has_player(&alice));
+
+    assert_eq!(client.remove_score(&alice), Some(9));
+    assert_eq!(client.remove_score(&alice), None);
+    assert!(!client.has_player(&alice));
+    assert!(client.scores().is_empty());
+}
+
+#[test]
+fn test_iteration_is_sorted_by_key() {
+    let (env, client) = setup();
+    client.set_score(&symbol_short!("carol"), &5);
+    client.set_score(&symbol_short!("alice"), &7);
+    client.set_score(&symbol_short!("bob"), &3);
+
+    assert_eq!(
+        client.players(),
+        vec![
+            &env,
+            symbol_short!("alice"),
+            symbol_short!("bob"),
+            symbol_short!("carol")
+        ]
+    );
+    assert_eq!(client.score_values(), vec![&env, 7, 3, 5]);
+    assert_eq!(client.total_score(), 15);
+}
+
+#[test]
+fn test_top_player() {
+    let (_env, client) = setup();
+    assert_eq!(client.top_player(), None);
+
+    client.set_score(&symbol_short!("bob"), &9);
+    client.set_score(&symbol_short!("carol"), &4);
+    assert_eq!(client.top_player(), Some((symbol_short!("bob"), 9)));
+
+    // Ties go to the name that sorts first.
+    client.set_score(&symbol_short!("alice"), &9);
+    assert_eq!(client.top_player(), Some((symbol_short!("alice"), 9)));
+}
+
+// ==================== FLATTENED ====================
+
+#[test]
+fn test_flat_entries() {
+    let (_env, client) = setup();
+    let alice = symbol_short!("alice");
+
+    client.set_score_flat(&alice, &7);
+    assert_eq!(client.get_score_flat(&alice), Some(7));
+    // Flat entries are separate from the stored map.
+    assert_eq!(client.get_score(&alice), None);
+    assert_eq!(client.remove_score_flat(&alice), Some(7));
+    assert_eq!(client.get_score_flat(&alice), None);
+}
+
+// ==================== NESTED ====================
+
+#[test]
+fn test_nested_maps() {
+    let (env, client) = setup();
+    let red = symbol_short!("red");
+    let blue = symbol_short!("blue");
+
+    client.set_team_score(&red, &symbol_short!("alice"), &7);
+    client.set_team_score(&red, &symbol_short!("bob"), &3);
+    client.set_team_score(&blue, &symbol_short!("carol"), &5);
+    client.set_team_score(&red, &symbol_short!("alice"), &8);
+
+    assert_eq!(
+        client.team_scores(&red),
+        map![&env, (symbol_short!("alice"), 8), (symbol_short!("bob"), 3)]
+    );
+    assert!(client.team_scores(&symbol_short!("green")).is_empty());
+    assert_eq!(
+        client.team_totals(),
+        map![&env, (blue.clone(), 5u64), (red.clone(), 11u64)]
+    );
+    assert_eq!(client.teams().keys(), vec![&env, blue, red]);
+}
+
+// ==================== COST BENCHMARKS ====================
+//
+// Ledger I/O is what a layout changes: every call pays for the bytes of each
+// entry it reads and writes. These tests measure those bytes directly, plus
+// the host memory an update of a stored map uses. CPU is not compared: the
+// test host keeps every entry of the contract in one in-memory map, so even
+// flattened writes get slower as entries pile up here, while on-chain a call
+// only loads the entries in its footprint.
+
+/// Per-entry size limit for contract data (64 KiB on mainnet).
+const MAX_ENTRY_BYTES: u32 = 64 * 1024;
+
+/// XDR size of a storage entry's key plus value.
+fn entry_bytes(env: &Env, key: DataKey, value: Val) -> u32 {
+    key.to_xdr(env).len() + value.to_xdr(env).len()
+}
+
+fn fill(client: &MapsContractClient, env: &Env, players: u32, flat: bool) {
+    for i in 0..players {
+        if flat {
+            client.set_score_flat(&player(env, i), &i);
+        } else {
+            client.set_score(&player(env, i), &i);
+        }
+    }
+}
+
+/// Bytes an update reads and then writes back, with `players` stored.
+fn update_io_bytes(players: u32, flat: bool) -> u32 {
+    let (env, client) = setup();
+    fill(&client, &env, players, flat);
+    if flat {
+        let p = player(&env, 0);
+        entry_bytes(
+            &env,
+            DataKey::Score(p.clone()),
+            client.get_score_flat(&p).into_val(&env),
+        )
+    } else {
+        entry_bytes(&env, DataKey::Scores, client.scores().into_val(&env))
+    }
+}
+
+/// Host memory used by one `set_score` into a stored map of `players` keys.
+fn map_update_memory(players: u32) -> u64 {
+    let (env, client) = setup();
+    fill(&client, &env, players, false);
+    env.budget().reset_default();
+    client.set_score(&player(&env, players), &1);
+    env.budget().memory_bytes_cost()
+}
+
+/// A stored map is one entry that grows with every key, so each update
+/// moves the whole map. Per-key bytes stay roughly constant, which puts a
+/// hard ceiling on how many keys the map can ever hold.
+#[test]
+fn test_bench_stored_map_entry_grows_with_size() {
+    let small = update_io_bytes(10, false);
+    let large = update_io_bytes(100, false);
+    assert!(large > 5 * small, "{small} vs {large}");
+    assert!(large < MAX_ENTRY_BYTES / 10, "100 keys used {large} B");
+}
+
+/// A flattened entry holds one key, so an update moves the same few bytes
+/// however many other keys exist.
+#[test]
+fn test_bench_flat_entry_is_constant() {
+    let small = update_io_bytes(10, true);
+    let large = update_io_bytes(100, true);
+    assert_eq!(small, large);
+    assert!(large * 20 < update_io_bytes(100, false));
+}
+
+/// Decoding and re-encoding a stored map also costs host memory in
+/// proportion to its size.
+#[test]
+fn test_bench_stored_map_update_memory() {
+    let small = map_update_memory(5);
+    let large = map_update_memory(100);
+    assert!(large > 4 * small, "{small} vs {large}");
+}
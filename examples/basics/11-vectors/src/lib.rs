@@ -0,0 +1,200 @@
+//! # Vectors
+//!
+//! `soroban_sdk::Vec<T>` is the host's growable list. This example keeps two
+//! stored lists to show the common patterns:
+//!
+//! - **A queue** – `push_back` / `push_front`, `pop_front` / `pop_back`
+//! - **Slicing** – `slice(start..end)` returns a new `Vec` of that range
+//! - **Pagination** – `get_page(offset, limit)` with a capped page size
+//! - **A sorted list** – `binary_search` to find, insert and remove in order
+//! - **Bounded length** – every write checks `MAX_LEN` first
+//!
+//! ## Why bound the length?
+//!
+//! A stored `Vec` is one ledger entry. Each call that touches it reads and
+//! writes the whole list, and the entry cannot grow past the network's
+//! entry size limit. A list anyone can append to without a bound therefore
+//! gets more expensive with every push and eventually cannot be written at
+//! all, locking the contract. Capping the length keeps the cost of every
+//! call predictable; lists that must grow with users belong in per-item
+//! entries (see the `10-maps` example for the same trade-off with maps).
+//!
+//! Reads are bounded the same way: `get_page` caps `limit` at `PAGE_LIMIT`
+//! so a caller cannot ask for a response larger than the contract intends
+//! to serve.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Env, Vec};
+
+/// Maximum number of items either stored list may hold.
+pub const MAX_LEN: u32 = 100;
+
+/// Maximum number of items returned by one `get_page` call.
+pub const PAGE_LIMIT: u32 = 20;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The queue, in insertion order.
+    Queue,
+    /// A list kept in ascending order without duplicates.
+    Sorted,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VecError {
+    /// The list already holds `MAX_LEN` items.
+    ListFull = 1,
+    /// `start > end` or `end > len` in `slice`.
+    InvalidRange = 2,
+    /// The value is already in the sorted list.
+    Duplicate = 3,
+}
+
+#[contract]
+pub struct VectorsContract;
+
+#[contractimpl]
+impl VectorsContract {
+    // ==================== QUEUE ====================
+
+    /// Append `value` and return the new length.
+    pub fn push_back(env: Env, value: u32) -> Result<u32, VecError> {
+        let mut queue = Self::queue(env.clone());
+        Self::ensure_room(&queue)?;
+        queue.push_back(value);
+        Self::save(&env, &DataKey::Queue, &queue);
+        Ok(queue.len())
+    }
+
+    /// Prepend `value` and return the new length.
+    pub fn push_front(env: Env, value: u32) -> Result<u32, VecError> {
+        let mut queue = Self::queue(env.clone());
+        Self::ensure_room(&queue)?;
+        queue.push_front(value);
+        Self::save(&env, &DataKey::Queue, &queue);
+        Ok(queue.len())
+    }
+
+    /// Remove and return the first item, if any.
+    pub fn pop_front(env: Env) -> Option<u32> {
+        let mut queue = Self::queue(env.clone());
+        let value = queue.pop_front();
+        if value.is_some() {
+            Self::save(&env, &DataKey::Queue, &queue);
+        }
+        value
+    }
+
+    /// Remove and return the last item, if any.
+    pub fn pop_back(env: Env) -> Option<u32> {
+        let mut queue = Self::queue(env.clone());
+        let value = queue.pop_back();
+        if value.is_some() {
+            Self::save(&env, &DataKey::Queue, &queue);
+        }
+        value
+    }
+
+    pub fn queue(env: Env) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Queue)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn queue_len(env: Env) -> u32 {
+        Self::queue(env).len()
+    }
+
+    /// The item at `index`, or `None` past the end.
+    pub fn get(env: Env, index: u32) -> Option<u32> {
+        Self::queue(env).get(index)
+    }
+
+    // ==================== SLICING AND PAGINATION ====================
+
+    /// Items `start..end` of the queue.
+    ///
+    /// Unlike `get_page`, a slice is exact: an out-of-range request is an
+    /// error rather than a shorter result.
+    pub fn slice(env: Env, start: u32, end: u32) -> Result<Vec<u32>, VecError> {
+        let queue = Self::queue(env);
+        if start > end || end > queue.len() {
+            return Err(VecError::InvalidRange);
+        }
+        Ok(queue.slice(start..end))
+    }
+
+    /// Up to `limit` items starting at `offset`, capped at `PAGE_LIMIT`.
+    ///
+    /// An `offset` at or past the end returns an empty page, so callers can
+    /// loop until they get one. The next page starts at `offset + page.len()`.
+    pub fn get_page(env: Env, offset: u32, limit: u32) -> Vec<u32> {
+        let queue = Self::queue(env);
+        let start = offset.min(queue.len());
+        let end = queue.len().min(start.saturating_add(limit.min(PAGE_LIMIT)));
+        queue.slice(start..end)
+    }
+
+    // ==================== SORTED LIST ====================
+
+    /// Insert `value` at its sorted position and return that position.
+    ///
+    /// `binary_search` returns `Err(pos)` for a missing value: `pos` is
+    /// where it has to go to keep the list sorted.
+    pub fn insert_sorted(env: Env, value: u32) -> Result<u32, VecError> {
+        let mut sorted = Self::sorted(env.clone());
+        let pos = match sorted.binary_search(value) {
+            Ok(_) => return Err(VecError::Duplicate),
+            Err(pos) => pos,
+        };
+        Self::ensure_room(&sorted)?;
+        sorted.insert(pos, value);
+        Self::save(&env, &DataKey::Sorted, &sorted);
+        Ok(pos)
+    }
+
+    /// Remove `value` from the sorted list. Returns whether it was present.
+    pub fn remove_sorted(env: Env, value: u32) -> bool {
+        let mut sorted = Self::sorted(env.clone());
+        match sorted.binary_search(value) {
+            Ok(pos) => {
+                sorted.remove(pos);
+                Self::save(&env, &DataKey::Sorted, &sorted);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Position of `value` in the sorted list, found in O(log n).
+    pub fn index_of_sorted(env: Env, value: u32) -> Option<u32> {
+        Self::sorted(env).binary_search(value).ok()
+    }
+
+    pub fn sorted(env: Env) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Sorted)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ==================== HELPERS ====================
+
+    fn ensure_room(list: &Vec<u32>) -> Result<(), VecError> {
+        if list.len() >= MAX_LEN {
+            return Err(VecError::ListFull);
+        }
+        Ok(())
+    }
+
+    fn save(env: &Env, key: &DataKey, list: &Vec<u32>) {
+        env.storage().persistent().set(key, list);
+    }
+}
+
+mod test;
@@ -0,0 +1,157 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::vec;
+
+fn setup() -> (Env, VectorsContractClient<'static>) {
+    let env = Env::default();
+    let client = VectorsContractClient::new(&env, &env.register_contract(None, VectorsContract));
+    (env, client)
+}
+
+/// Fill the queue with `0..count`.
+fn fill(client: &VectorsContractClient, count: u32) {
+    for i in 0..count {
+        client.push_back(&i);
+    }
+}
+
+// ==================== QUEUE ====================
+
+#[test]
+fn test_push_front_and_back() {
+    let (env, client) = setup();
+    assert_eq!(client.push_back(&2), 1);
+    assert_eq!(client.push_back(&3), 2);
+    assert_eq!(client.push_front(&1), 3);
+
+    assert_eq!(client.queue(), vec![&env, 1, 2, 3]);
+    assert_eq!(client.get(&0), Some(1));
+    assert_eq!(client.get(&3), None);
+}
+
+#[test]
+fn test_pop_both_ends() {
+    let (env, client) = setup();
+    fill(&client, 4);
+
+    assert_eq!(client.pop_front(), Some(0));
+    assert_eq!(client.pop_back(), Some(3));
+    assert_eq!(client.queue(), vec![&env, 1, 2]);
+}
+
+#[test]
+fn test_empty_queue() {
+    let (_env, client) = setup();
+    assert_eq!(client.queue_len(), 0);
+    assert_eq!(client.pop_front(), None);
+    assert_eq!(client.pop_back(), None);
+    assert!(client.get_page(&0, &10).is_empty());
+    assert!(client.slice(&0, &0).is_empty());
+}
+
+#[test]
+fn test_full_queue_rejects_pushes() {
+    let (_env, client) = setup();
+    fill(&client, MAX_LEN);
+
+    assert_eq!(client.try_push_back(&0), Err(Ok(VecError::ListFull)));
+    assert_eq!(client.try_push_front(&0), Err(Ok(VecError::ListFull)));
+    assert_eq!(client.queue_len(), MAX_LEN);
+
+    // Making room lets pushes through again.
+    client.pop_front();
+    assert_eq!(client.push_back(&7), MAX_LEN);
+}
+
+// ==================== SLICING AND PAGINATION ====================
+
+#[test]
+fn test_slice() {
+    let (env, client) = setup();
+    fill(&client, 5);
+
+    assert_eq!(client.slice(&1, &4), vec![&env, 1, 2, 3]);
+    assert_eq!(client.slice(&0, &5), client.queue());
+    assert!(client.slice(&5, &5).is_empty());
+    assert_eq!(client.try_slice(&0, &6), Err(Ok(VecError::InvalidRange)));
+    assert_eq!(client.try_slice(&3, &2), Err(Ok(VecError::InvalidRange)));
+}
+
+#[test]
+fn test_get_page_boundaries() {
+    let (env, client) = setup();
+    fill(&client, 7);
+
+    assert_eq!(client.get_page(&0, &3), vec![&env, 0, 1, 2]);
+    // The last page is partial; past the end is empty.
+    assert_eq!(client.get_page(&6, &3), vec![&env, 6]);
+    assert!(client.get_page(&7, &3).is_empty());
+    assert!(client.get_page(&u32::MAX, &u32::MAX).is_empty());
+    assert!(client.get_page(&0, &0).is_empty());
+}
+
+#[test]
+fn test_get_page_caps_limit() {
+    let (_env, client) = setup();
+    fill(&client, MAX_LEN);
+
+    assert_eq!(client.get_page(&0, &1_000).len(), PAGE_LIMIT);
+
+    // Walking every page returns the whole queue in order.
+    let mut offset = 0;
+    loop {
+        let page = client.get_page(&offset, &PAGE_LIMIT);
+        if page.is_empty() {
+            break;
+        }
+        for (i, value) in page.iter().enumerate() {
+            assert_eq!(value, offset + i as u32);
+        }
+        offset += page.len();
+    }
+    assert_eq!(offset, MAX_LEN);
+}
+
+// ==================== SORTED LIST ====================
+
+#[test]
+fn test_insert_sorted_keeps_order() {
+    let (env, client) = setup();
+    assert_eq!(client.insert_sorted(&50), 0);
+    assert_eq!(client.insert_sorted(&10), 0);
+    assert_eq!(client.insert_sorted(&30), 1);
+    assert_eq!(client.insert_sorted(&60), 3);
+
+    assert_eq!(client.sorted(), vec![&env, 10, 30, 50, 60]);
+    assert_eq!(client.try_insert_sorted(&30), Err(Ok(VecError::Duplicate)));
+}
+
+#[test]
+fn test_binary_search_lookup_and_remove() {
+    let (env, client) = setup();
+    for value in [40, 20, 10, 30] {
+        client.insert_sorted(&value);
+    }
+
+    assert_eq!(client.index_of_sorted(&10), Some(0));
+    assert_eq!(client.index_of_sorted(&40), Some(3));
+    assert_eq!(client.index_of_sorted(&25), None);
+
+    assert!(client.remove_sorted(&20));
+    assert!(!client.remove_sorted(&20));
+    assert_eq!(client.sorted(), vec![&env, 10, 30, 40]);
+    assert_eq!(client.index_of_sorted(&30), Some(1));
+}
+
+#[test]
+fn test_sorted_list_bounded() {
+    let (_env, client) = setup();
+    assert_eq!(client.index_of_sorted(&1), None);
+    for value in 0..MAX_LEN {
+        client.insert_sorted(&(value * 2));
+    }
+    assert_eq!(client.try_insert_sorted(&1), Err(Ok(VecError::ListFull)));
+    // Duplicates are reported as such even when the list is full.
+    assert_eq!(client.try_insert_sorted(&0), Err(Ok(VecError::Duplicate)));
+}
@@ -0,0 +1,210 @@
+//! # Bytes and BytesN
+//!
+//! `Bytes` is a growable byte array living in the host; `BytesN<N>` is the
+//! same thing with its length fixed in the type (hashes, public keys,
+//! signatures). This example covers the everyday operations:
+//!
+//! - **Building** – `append`, `extend_from_slice`, `push_back`
+//! - **Slicing** – `slice(start..end)`, prefix checks
+//! - **Hashing** – `sha256` / `keccak256` return `BytesN<32>`
+//! - **Fixed ↔ dynamic** – `BytesN::try_from(Bytes)` and `Bytes::from(BytesN)`
+//! - **Hex encoding** – bytes to lowercase ASCII hex and back
+//! - **Text** – converting to and from `String` and `Symbol`
+//! - **Comparison** – equality and lexicographic ordering
+//!
+//! ## Copying out of the host
+//!
+//! Contract code cannot borrow the host's bytes directly. To inspect them
+//! as a `&[u8]` (UTF-8 checks, building a `Symbol`) they are copied into a
+//! stack buffer first, which needs a fixed maximum size: the conversions
+//! below reject input longer than `MAX_TEXT_LEN` instead of allocating.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, xdr::ToXdr, Bytes, BytesN, Env, String, Symbol,
+};
+
+/// Longest input the text conversions copy into their stack buffer.
+pub const MAX_TEXT_LEN: u32 = 64;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BytesError {
+    /// `start > end` or `end > len` in `slice`.
+    InvalidRange = 1,
+    /// The input does not have the length a `BytesN` needs.
+    WrongLength = 2,
+    /// Odd length or a character outside `0-9a-fA-F`.
+    InvalidHex = 3,
+    /// Longer than `MAX_TEXT_LEN`.
+    TooLong = 4,
+    /// Not valid `Symbol` text: up to 32 characters of `a-zA-Z0-9_`.
+    InvalidSymbol = 5,
+    InvalidUtf8 = 6,
+}
+
+#[contract]
+pub struct BytesContract;
+
+#[contractimpl]
+impl BytesContract {
+    // ==================== BUILDING AND SLICING ====================
+
+    /// `a` followed by `b`.
+    pub fn concat(_env: Env, a: Bytes, b: Bytes) -> Bytes {
+        let mut out = a;
+        out.append(&b);
+        out
+    }
+
+    /// Bytes `start..end` of `data`.
+    pub fn slice(_env: Env, data: Bytes, start: u32, end: u32) -> Result<Bytes, BytesError> {
+        if start > end || end > data.len() {
+            return Err(BytesError::InvalidRange);
+        }
+        Ok(data.slice(start..end))
+    }
+
+    pub fn starts_with(_env: Env, data: Bytes, prefix: Bytes) -> bool {
+        prefix.len() <= data.len() && data.slice(..prefix.len()) == prefix
+    }
+
+    // ==================== HASHES AND BytesN ====================
+
+    pub fn sha256(env: Env, data: Bytes) -> BytesN<32> {
+        env.crypto().sha256(&data).into()
+    }
+
+    pub fn keccak256(env: Env, data: Bytes) -> BytesN<32> {
+        env.crypto().keccak256(&data).into()
+    }
+
+    /// Whether `data` hashes to `expected` (e.g. a commit-reveal check).
+    pub fn matches_hash(env: Env, data: Bytes, expected: BytesN<32>) -> bool {
+        Self::sha256(env, data) == expected
+    }
+
+    /// Fix the length of `data` at 32 bytes; fails unless it is exactly 32.
+    pub fn to_fixed(_env: Env, data: Bytes) -> Result<BytesN<32>, BytesError> {
+        BytesN::try_from(data).map_err(|_| BytesError::WrongLength)
+    }
+
+    /// Drop the fixed length, e.g. to append to a hash.
+    pub fn from_fixed(_env: Env, data: BytesN<32>) -> Bytes {
+        data.into()
+    }
+
+    // ==================== HEX ====================
+
+    /// Lowercase ASCII hex of `data`, two characters per byte.
+    pub fn to_hex(env: Env, data: Bytes) -> Bytes {
+        let mut hex = Bytes::new(&env);
+        for byte in data.iter() {
+            hex.push_back(HEX_DIGITS[(byte >> 4) as usize]);
+            hex.push_back(HEX_DIGITS[(byte & 0x0f) as usize]);
+        }
+        hex
+    }
+
+    /// Decode ASCII hex (either case) back into bytes.
+    pub fn from_hex(env: Env, hex: Bytes) -> Result<Bytes, BytesError> {
+        if !hex.len().is_multiple_of(2) {
+            return Err(BytesError::InvalidHex);
+        }
+        let mut out = Bytes::new(&env);
+        let mut i = 0;
+        while i < hex.len() {
+            let high = Self::hex_value(hex.get_unchecked(i))?;
+            let low = Self::hex_value(hex.get_unchecked(i + 1))?;
+            out.push_back(high << 4 | low);
+            i += 2;
+        }
+        Ok(out)
+    }
+
+    // ==================== TEXT ====================
+
+    /// The UTF-8 bytes of `text`.
+    pub fn string_to_bytes(env: Env, text: String) -> Result<Bytes, BytesError> {
+        let len = Self::check_len(text.len())?;
+        let mut buf = [0u8; MAX_TEXT_LEN as usize];
+        text.copy_into_slice(&mut buf[..len]);
+        Ok(Bytes::from_slice(&env, &buf[..len]))
+    }
+
+    /// `data` as a `String`. Fails on invalid UTF-8.
+    pub fn bytes_to_string(env: Env, data: Bytes) -> Result<String, BytesError> {
+        let mut buf = [0u8; MAX_TEXT_LEN as usize];
+        let text = Self::utf8(&data, &mut buf)?;
+        Ok(String::from_str(&env, text))
+    }
+
+    /// The characters of `symbol`.
+    ///
+    /// `Symbol` has no accessor for its text, but its XDR encoding is an
+    /// 8-byte header (type tag, then length) followed by the characters
+    /// padded to a multiple of 4.
+    pub fn symbol_to_bytes(env: Env, symbol: Symbol) -> Bytes {
+        let xdr = symbol.to_xdr(&env);
+        let mut len = [0u8; 4];
+        xdr.slice(4..8).copy_into_slice(&mut len);
+        xdr.slice(8..8 + u32::from_be_bytes(len))
+    }
+
+    /// A `Symbol` from its characters. Checked first, because `Symbol::new`
+    /// panics on invalid input.
+    pub fn bytes_to_symbol(env: Env, data: Bytes) -> Result<Symbol, BytesError> {
+        let mut buf = [0u8; MAX_TEXT_LEN as usize];
+        let text = Self::utf8(&data, &mut buf).map_err(|_| BytesError::InvalidSymbol)?;
+        let valid =
+            text.len() <= 32 && text.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_');
+        if !valid {
+            return Err(BytesError::InvalidSymbol);
+        }
+        Ok(Symbol::new(&env, text))
+    }
+
+    // ==================== COMPARISON ====================
+
+    /// -1, 0 or 1 as `a` sorts before, equal to or after `b`.
+    ///
+    /// Ordering is lexicographic by byte, and a prefix sorts first.
+    pub fn compare(_env: Env, a: Bytes, b: Bytes) -> i32 {
+        match a.cmp(&b) {
+            core::cmp::Ordering::Less => -1,
+            core::cmp::Ordering::Equal => 0,
+            core::cmp::Ordering::Greater => 1,
+        }
+    }
+
+    // ==================== HELPERS ====================
+
+    fn check_len(len: u32) -> Result<usize, BytesError> {
+        if len > MAX_TEXT_LEN {
+            return Err(BytesError::TooLong);
+        }
+        Ok(len as usize)
+    }
+
+    /// Copy `data` into `buf` and view it as UTF-8.
+    fn utf8<'a>(data: &Bytes, buf: &'a mut [u8]) -> Result<&'a str, BytesError> {
+        let len = Self::check_len(data.len())?;
+        data.copy_into_slice(&mut buf[..len]);
+        core::str::from_utf8(&buf[..len]).map_err(|_| BytesError::InvalidUtf8)
+    }
+
+    fn hex_value(c: u8) -> Result<u8, BytesError> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(BytesError::InvalidHex),
+        }
+    }
+}
+
+mod test;
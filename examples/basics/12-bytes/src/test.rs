@@ -0,0 +1,245 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{bytes, bytesn, symbol_short};
+
+fn setup() -> (Env, BytesContractClient<'static>) {
+    let env = Env::default();
+    let client = BytesContractClient::new(&env, &env.register_contract(None, BytesContract));
+    (env, client)
+}
+
+fn b(env: &Env, s: &str) -> Bytes {
+    Bytes::from_slice(env, s.as_bytes())
+}
+
+// ==================== BUILDING AND SLICING ====================
+
+#[test]
+fn test_concat() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.concat(&b(&env, "sor"), &b(&env, "oban")),
+        b(&env, "soroban")
+    );
+    assert_eq!(
+        client.concat(&Bytes::new(&env), &b(&env, "x")),
+        b(&env, "x")
+    );
+    assert!(client
+        .concat(&Bytes::new(&env), &Bytes::new(&env))
+        .is_empty());
+}
+
+#[test]
+fn test_slice() {
+    let (env, client) = setup();
+    let data = b(&env, "soroban");
+
+    assert_eq!(client.slice(&data, &0, &3), b(&env, "sor"));
+    assert_eq!(client.slice(&data, &3, &7), b(&env, "oban"));
+    assert!(client.slice(&data, &7, &7).is_empty());
+    assert_eq!(
+        client.try_slice(&data, &0, &8),
+        Err(Ok(BytesError::InvalidRange))
+    );
+    assert_eq!(
+        client.try_slice(&data, &4, &3),
+        Err(Ok(BytesError::InvalidRange))
+    );
+}
+
+#[test]
+fn test_starts_with() {
+    let (env, client) = setup();
+    let data = b(&env, "soroban");
+    assert!(client.starts_with(&data, &b(&env, "soro")));
+    assert!(client.starts_with(&data, &data));
+    assert!(client.starts_with(&data, &Bytes::new(&env)));
+    assert!(!client.starts_with(&data, &b(&env, "oban")));
+    assert!(!client.starts_with(&b(&env, "so"), &data));
+}
+
+// ==================== HASHES AND BytesN ====================
+
+#[test]
+fn test_sha256_known_vector() {
+    let (env, client) = setup();
+    // sha256("abc")
+    let expected = bytesn!(
+        &env,
+        0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+    );
+    assert_eq!(client.sha256(&b(&env, "abc")), expected);
+    assert!(client.matches_hash(&b(&env, "abc"), &expected));
+    assert!(!client.matches_hash(&b(&env, "abd"), &expected));
+}
+
+#[test]
+fn test_keccak256_known_vector() {
+    let (env, client) = setup();
+    // keccak256("")
+    assert_eq!(
+        client.keccak256(&Bytes::new(&env)),
+        bytesn!(
+            &env,
+            0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+        )
+    );
+}
+
+#[test]
+fn test_fixed_round_trip() {
+    let (env, client) = setup();
+    let hash = client.sha256(&b(&env, "abc"));
+    let dynamic = client.from_fixed(&hash);
+    assert_eq!(dynamic.len(), 32);
+    assert_eq!(client.to_fixed(&dynamic), hash);
+
+    assert_eq!(
+        client.try_to_fixed(&dynamic.slice(..31)),
+        Err(Ok(BytesError::WrongLength))
+    );
+    assert_eq!(
+        client.try_to_fixed(&client.concat(&dynamic, &b(&env, "!"))),
+        Err(Ok(BytesError::WrongLength))
+    );
+}
+
+// ==================== HEX ====================
+
+#[test]
+fn test_to_hex() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.to_hex(&bytes!(&env, 0x00ff10ab)),
+        b(&env, "00ff10ab")
+    );
+    assert!(client.to_hex(&Bytes::new(&env)).is_empty());
+}
+
+#[test]
+fn test_hex_round_trip() {
+    let (env, client) = setup();
+    let hash = client.from_fixed(&client.sha256(&b(&env, "abc")));
+    let hex = client.to_hex(&hash);
+    assert_eq!(hex.len(), 64);
+    assert_eq!(client.from_hex(&hex), hash);
+    // Uppercase decodes too.
+    assert_eq!(
+        client.from_hex(&b(&env, "DEADbeef")),
+        bytes!(&env, 0xdeadbeef)
+    );
+}
+
+#[test]
+fn test_invalid_hex() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.try_from_hex(&b(&env, "abc")),
+        Err(Ok(BytesError::InvalidHex))
+    );
+    assert_eq!(
+        client.try_from_hex(&b(&env, "0g")),
+        Err(Ok(BytesError::InvalidHex))
+    );
+    assert!(client.from_hex(&Bytes::new(&env)).is_empty());
+}
+
+// ==================== TEXT ====================
+
+#[test]
+fn test_string_round_trip() {
+    let (env, client) = setup();
+    let text = String::from_str(&env, "héllo wörld");
+    let bytes = client.string_to_bytes(&text);
+    // Non-ASCII characters take two bytes each in UTF-8.
+    assert_eq!(bytes.len(), 13);
+    assert_eq!(bytes, b(&env, "héllo wörld"));
+    assert_eq!(client.bytes_to_string(&bytes), text);
+}
+
+#[test]
+fn test_text_length_limit() {
+    let (env, client) = setup();
+    let max = [b'a'; MAX_TEXT_LEN as usize];
+    let max_str = core::str::from_utf8(&max).unwrap();
+    client.string_to_bytes(&String::from_str(&env, max_str));
+
+    let long = [b'a'; MAX_TEXT_LEN as usize + 1];
+    let long_str = core::str::from_utf8(&long).unwrap();
+    assert_eq!(
+        client.try_string_to_bytes(&String::from_str(&env, long_str)),
+        Err(Ok(BytesError::TooLong))
+    );
+    assert_eq!(
+        client.try_bytes_to_string(&Bytes::from_slice(&env, &long)),
+        Err(Ok(BytesError::TooLong))
+    );
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.try_bytes_to_string(&bytes!(&env, 0xc328)),
+        Err(Ok(BytesError::InvalidUtf8))
+    );
+}
+
+#[test]
+fn test_symbol_round_trip() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.symbol_to_bytes(&symbol_short!("transfer")),
+        b(&env, "transfer")
+    );
+    // Longer symbols use a different host representation; same bytes.
+    let long = Symbol::new(&env, "a_much_longer_symbol_name_123");
+    let bytes = client.symbol_to_bytes(&long);
+    assert_eq!(bytes, b(&env, "a_much_longer_symbol_name_123"));
+    assert_eq!(client.bytes_to_symbol(&bytes), long);
+    assert!(client.symbol_to_bytes(&Symbol::new(&env, "")).is_empty());
+}
+
+#[test]
+fn test_invalid_symbol() {
+    let (env, client) = setup();
+    for bad in ["has space", "dash-ed", "ünicode"] {
+        assert_eq!(
+            client.try_bytes_to_symbol(&b(&env, bad)),
+            Err(Ok(BytesError::InvalidSymbol))
+        );
+    }
+    let too_long = [b'a'; 33];
+    assert_eq!(
+        client.try_bytes_to_symbol(&Bytes::from_slice(&env, &too_long)),
+        Err(Ok(BytesError::InvalidSymbol))
+    );
+}
+
+// ==================== COMPARISON ====================
+
+#[test]
+fn test_compare() {
+    let (env, client) = setup();
+    assert_eq!(client.compare(&b(&env, "abc"), &b(&env, "abc")), 0);
+    assert_eq!(client.compare(&b(&env, "abc"), &b(&env, "abd")), -1);
+    assert_eq!(client.compare(&b(&env, "b"), &b(&env, "abc")), 1);
+    // A prefix sorts before the longer value.
+    assert_eq!(client.compare(&b(&env, "ab"), &b(&env, "abc")), -1);
+    assert_eq!(client.compare(&Bytes::new(&env), &b(&env, "a")), -1);
+}
+
+#[test]
+fn test_equality_across_types() {
+    let (env, client) = setup();
+    let hash = client.sha256(&b(&env, "abc"));
+    // A BytesN and the Bytes it converts to hold the same bytes.
+    assert_eq!(client.from_fixed(&hash), Bytes::from(hash.clone()));
+    assert_eq!(
+        Bytes::from_array(&env, &hash.to_array()),
+        client.from_fixed(&hash)
+    );
+    assert_ne!(client.sha256(&b(&env, "abd")), hash);
+}
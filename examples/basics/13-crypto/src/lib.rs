@@ -0,0 +1,178 @@
+//! # Cryptographic Primitives
+//!
+//! `env.crypto()` exposes the host's hash functions and signature checks.
+//! They run natively in the host, so they cost far less than the same code
+//! compiled to Wasm:
+//!
+//! - **Hashing** – `sha256` and `keccak256` return a 32-byte `Hash<32>`
+//! - **ed25519** – `ed25519_verify` checks a signature (Stellar keys)
+//! - **secp256k1** – `secp256k1_recover` recovers the signer's public key
+//!   from a signature over a digest (Ethereum keys)
+//! - **Commit-reveal** – a sha256 commitment hides a value until it is
+//!   revealed, and binds the committer to it
+//!
+//! ## Failure modes
+//!
+//! `ed25519_verify` does not return a bool: an invalid signature traps and
+//! aborts the whole invocation. That is usually what a contract wants, and
+//! it is why `verify_ed25519` below has no error path of its own.
+//! `secp256k1_recover` also traps on malformed input, but a well-formed
+//! signature by the wrong key succeeds and simply recovers a different key,
+//! so the caller must compare the result against the key it expects.
+//!
+//! ## `Hash<32>` vs `BytesN<32>`
+//!
+//! The hash functions return `Hash<32>`, which can only be created by
+//! hashing. `secp256k1_recover` takes a `Hash<32>` for the digest, so a
+//! caller cannot pass arbitrary bytes and claim they are a message hash.
+//! Convert to `BytesN<32>` with `.into()` or `to_bytes()` to store or
+//! return it.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// A player's commitment, until revealed.
+    Commitment(Address),
+    /// A player's revealed value.
+    Revealed(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CryptoError {
+    /// The player already has a commitment or a revealed value.
+    AlreadyCommitted = 1,
+    NoCommitment = 2,
+    /// The revealed value and salt do not hash to the commitment.
+    CommitmentMismatch = 3,
+    /// secp256k1 recovery ids are 0 to 3.
+    InvalidRecoveryId = 4,
+}
+
+#[contract]
+pub struct CryptoContract;
+
+#[contractimpl]
+impl CryptoContract {
+    // ==================== HASHING ====================
+
+    pub fn sha256(env: Env, data: Bytes) -> BytesN<32> {
+        env.crypto().sha256(&data).into()
+    }
+
+    pub fn keccak256(env: Env, data: Bytes) -> BytesN<32> {
+        env.crypto().keccak256(&data).into()
+    }
+
+    // ==================== SIGNATURES ====================
+
+    /// Check an ed25519 `signature` of `message` by `public_key`.
+    ///
+    /// Traps if the signature is invalid.
+    pub fn verify_ed25519(env: Env, public_key: BytesN<32>, message: Bytes, signature: BytesN<64>) {
+        env.crypto()
+            .ed25519_verify(&public_key, &message, &signature);
+    }
+
+    /// The uncompressed SEC-1 public key (`0x04 || x || y`) that signed the
+    /// keccak256 digest of `message`.
+    pub fn secp256k1_recover(
+        env: Env,
+        message: Bytes,
+        signature: BytesN<64>,
+        recovery_id: u32,
+    ) -> Result<BytesN<65>, CryptoError> {
+        if recovery_id > 3 {
+            return Err(CryptoError::InvalidRecoveryId);
+        }
+        let digest = env.crypto().keccak256(&message);
+        Ok(env
+            .crypto()
+            .secp256k1_recover(&digest, &signature, recovery_id))
+    }
+
+    /// The Ethereum address that signed `message`: the last 20 bytes of the
+    /// keccak256 of the recovered public key, without its `0x04` prefix.
+    pub fn recover_eth_address(
+        env: Env,
+        message: Bytes,
+        signature: BytesN<64>,
+        recovery_id: u32,
+    ) -> Result<BytesN<20>, CryptoError> {
+        let public_key: Bytes =
+            Self::secp256k1_recover(env.clone(), message, signature, recovery_id)?.into();
+        let hash: Bytes = env.crypto().keccak256(&public_key.slice(1..)).into();
+        Ok(hash.slice(12..).try_into().unwrap())
+    }
+
+    // ==================== COMMIT-REVEAL ====================
+
+    /// The commitment to `value` for `player`:
+    /// `sha256(value || salt || player)`.
+    ///
+    /// Clients normally compute this off-chain; it is exposed so they can
+    /// check their encoding. The random 32-byte `salt` stops anyone from
+    /// guessing a small value by hashing every candidate, and including the
+    /// player's address stops a copied commitment from being revealed by
+    /// anyone else.
+    pub fn commitment(env: Env, player: Address, value: Bytes, salt: BytesN<32>) -> BytesN<32> {
+        let mut preimage = value;
+        preimage.append(&salt.into());
+        preimage.append(&player.to_xdr(&env));
+        env.crypto().sha256(&preimage).into()
+    }
+
+    /// Record `player`'s commitment. One per player.
+    ///
+    /// Emits `(commit, player)` with the commitment.
+    pub fn commit(env: Env, player: Address, commitment: BytesN<32>) -> Result<(), CryptoError> {
+        player.require_auth();
+        let storage = env.storage().persistent();
+        if storage.has(&DataKey::Commitment(player.clone()))
+            || storage.has(&DataKey::Revealed(player.clone()))
+        {
+            return Err(CryptoError::AlreadyCommitted);
+        }
+        storage.set(&DataKey::Commitment(player.clone()), &commitment);
+        env.events()
+            .publish((symbol_short!("commit"), player), commitment);
+        Ok(())
+    }
+
+    /// Reveal the value behind `player`'s commitment.
+    ///
+    /// Emits `(reveal, player)` with the value.
+    pub fn reveal(
+        env: Env,
+        player: Address,
+        value: Bytes,
+        salt: BytesN<32>,
+    ) -> Result<(), CryptoError> {
+        let storage = env.storage().persistent();
+        let commitment: BytesN<32> = storage
+            .get(&DataKey::Commitment(player.clone()))
+            .ok_or(CryptoError::NoCommitment)?;
+        if Self::commitment(env.clone(), player.clone(), value.clone(), salt) != commitment {
+            return Err(CryptoError::CommitmentMismatch);
+        }
+        storage.remove(&DataKey::Commitment(player.clone()));
+        storage.set(&DataKey::Revealed(player.clone()), &value);
+        env.events()
+            .publish((symbol_short!("reveal"), player), value);
+        Ok(())
+    }
+
+    pub fn revealed(env: Env, player: Address) -> Option<Bytes> {
+        env.storage().persistent().get(&DataKey::Revealed(player))
+    }
+}
+
+mod test;
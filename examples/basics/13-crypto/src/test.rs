@@ -0,0 +1,200 @@
+#![cfg(test)]
+
+use super::*;
+use ed25519_dalek::{Signer as _, SigningKey};
+use k256::ecdsa::SigningKey as Secp256k1Key;
+use soroban_sdk::{bytesn, testutils::Address as _};
+
+fn setup() -> (Env, CryptoContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = CryptoContractClient::new(&env, &env.register_contract(None, CryptoContract));
+    (env, client)
+}
+
+fn b(env: &Env, s: &str) -> Bytes {
+    Bytes::from_slice(env, s.as_bytes())
+}
+
+/// Sign the keccak256 digest of `message`, as Ethereum wallets do.
+fn secp256k1_sign(env: &Env, key: &Secp256k1Key, message: &str) -> (BytesN<64>, u32) {
+    let digest = env.crypto().keccak256(&b(env, message)).to_array();
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+    (
+        BytesN::from_array(env, &signature.to_bytes().into()),
+        recovery_id.to_byte() as u32,
+    )
+}
+
+fn secp256k1_public_key(env: &Env, key: &Secp256k1Key) -> BytesN<65> {
+    let point = key.verifying_key().to_encoded_point(false);
+    BytesN::from_array(env, point.as_bytes().try_into().unwrap())
+}
+
+// ==================== HASHING ====================
+
+#[test]
+fn test_hash_known_vectors() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.sha256(&b(&env, "abc")),
+        bytesn!(
+            &env,
+            0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+        )
+    );
+    assert_eq!(
+        client.keccak256(&b(&env, "abc")),
+        bytesn!(
+            &env,
+            0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45
+        )
+    );
+}
+
+// ==================== ED25519 ====================
+
+#[test]
+fn test_ed25519_valid_signature() {
+    let (env, client) = setup();
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let message = b"transfer 100 to bob";
+    let signature = key.sign(message);
+
+    client.verify_ed25519(
+        &BytesN::from_array(&env, &key.verifying_key().to_bytes()),
+        &Bytes::from_slice(&env, message),
+        &BytesN::from_array(&env, &signature.to_bytes()),
+    );
+}
+
+#[test]
+fn test_ed25519_invalid_signature_traps() {
+    let (env, client) = setup();
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let signature = BytesN::from_array(&env, &key.sign(b"transfer 100 to bob").to_bytes());
+
+    // Tampered message.
+    assert!(client
+        .try_verify_ed25519(&public_key, &b(&env, "transfer 900 to bob"), &signature)
+        .is_err());
+
+    // Right message, different key.
+    let other = SigningKey::from_bytes(&[8; 32]);
+    assert!(client
+        .try_verify_ed25519(
+            &BytesN::from_array(&env, &other.verifying_key().to_bytes()),
+            &b(&env, "transfer 100 to bob"),
+            &signature,
+        )
+        .is_err());
+}
+
+// ==================== SECP256K1 ====================
+
+#[test]
+fn test_secp256k1_recover() {
+    let (env, client) = setup();
+    let key = Secp256k1Key::from_slice(&[9; 32]).unwrap();
+    let (signature, recovery_id) = secp256k1_sign(&env, &key, "hello");
+
+    assert_eq!(
+        client.secp256k1_recover(&b(&env, "hello"), &signature, &recovery_id),
+        secp256k1_public_key(&env, &key)
+    );
+    // A different message recovers some other key rather than failing.
+    assert_ne!(
+        client.secp256k1_recover(&b(&env, "hellO"), &signature, &recovery_id),
+        secp256k1_public_key(&env, &key)
+    );
+    assert_eq!(
+        client.try_secp256k1_recover(&b(&env, "hello"), &signature, &4),
+        Err(Ok(CryptoError::InvalidRecoveryId))
+    );
+}
+
+#[test]
+fn test_recover_eth_address() {
+    let (env, client) = setup();
+    let key = Secp256k1Key::from_slice(&[9; 32]).unwrap();
+    let (signature, recovery_id) = secp256k1_sign(&env, &key, "hello");
+
+    let public_key: Bytes = secp256k1_public_key(&env, &key).into();
+    let hash: Bytes = env.crypto().keccak256(&public_key.slice(1..)).into();
+    let expected: BytesN<20> = hash.slice(12..).try_into().unwrap();
+    assert_eq!(
+        client.recover_eth_address(&b(&env, "hello"), &signature, &recovery_id),
+        expected
+    );
+}
+
+// ==================== COMMIT-REVEAL ====================
+
+#[test]
+fn test_commit_reveal() {
+    let (env, client) = setup();
+    let player = Address::generate(&env);
+    let value = b(&env, "rock");
+    let salt = BytesN::from_array(&env, &[42; 32]);
+
+    let commitment = client.commitment(&player, &value, &salt);
+    client.commit(&player, &commitment);
+    assert_eq!(client.revealed(&player), None);
+
+    client.reveal(&player, &value, &salt);
+    assert_eq!(client.revealed(&player), Some(value));
+    assert_eq!(
+        client.try_commit(&player, &commitment),
+        Err(Ok(CryptoError::AlreadyCommitted))
+    );
+}
+
+#[test]
+fn test_reveal_must_match() {
+    let (env, client) = setup();
+    let player = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[42; 32]);
+    client.commit(
+        &player,
+        &client.commitment(&player, &b(&env, "rock"), &salt),
+    );
+
+    assert_eq!(
+        client.try_reveal(&player, &b(&env, "paper"), &salt),
+        Err(Ok(CryptoError::CommitmentMismatch))
+    );
+    assert_eq!(
+        client.try_reveal(
+            &player,
+            &b(&env, "rock"),
+            &BytesN::from_array(&env, &[0; 32])
+        ),
+        Err(Ok(CryptoError::CommitmentMismatch))
+    );
+    assert_eq!(
+        client.try_reveal(&Address::generate(&env), &b(&env, "rock"), &salt),
+        Err(Ok(CryptoError::NoCommitment))
+    );
+}
+
+#[test]
+fn test_copied_commitment_cannot_be_revealed() {
+    let (env, client) = setup();
+    let alice = Address::generate(&env);
+    let mallory = Address::generate(&env);
+    let value = b(&env, "rock");
+    let salt = BytesN::from_array(&env, &[42; 32]);
+
+    // Mallory copies Alice's commitment and later learns her value and salt.
+    let commitment = client.commitment(&alice, &value, &salt);
+    client.commit(&alice, &commitment);
+    client.commit(&mallory, &commitment);
+    client.reveal(&alice, &value, &salt);
+
+    // The commitment is bound to Alice's address, so it is useless to Mallory.
+    assert_eq!(
+        client.try_reveal(&mallory, &value, &salt),
+        Err(Ok(CryptoError::CommitmentMismatch))
+    );
+}
@@ -0,0 +1,78 @@
+//! # Strings
+//!
+//! `soroban_sdk::String` is a host-managed byte string. Contracts cannot use
+//! `format!`, `alloc::string::String` or `&str` methods on it directly; the
+//! hello-world example builds its greeting by copying into one stack buffer.
+//! This example packages that technique as reusable helpers in [`strings`]:
+//!
+//! - **Concatenation** – `concat` and `join` over a `Vec<String>`
+//! - **Substrings** – byte ranges that must land on character boundaries
+//! - **Comparison** – ASCII case-insensitive equality
+//! - **Validation** – character counts, not byte lengths
+//! - **Conversions** – `String` ↔ `Bytes` and `String` ↔ `Symbol`
+//!
+//! The contract below only forwards to the helpers so they can be called
+//! and tested through a client. Copy `strings.rs` into another contract to
+//! reuse them.
+//!
+//! ## Bytes vs characters
+//!
+//! `String::len` counts bytes. "héllo" is 5 characters but 6 bytes, because
+//! `é` takes two bytes in UTF-8. Limits meant for people ("a name of 3 to 20
+//! characters") should use `char_len`; limits meant for storage cost should
+//! use `len`.
+
+#![no_std]
+
+pub mod strings;
+
+use soroban_sdk::{contract, contractimpl, Bytes, Env, String, Symbol, Vec};
+pub use strings::{StringError, MAX_LEN};
+
+#[contract]
+pub struct StringsContract;
+
+#[contractimpl]
+impl StringsContract {
+    pub fn concat(env: Env, parts: Vec<String>) -> Result<String, StringError> {
+        strings::concat(&env, &parts)
+    }
+
+    pub fn join(env: Env, parts: Vec<String>, separator: String) -> Result<String, StringError> {
+        strings::join(&env, &parts, &separator)
+    }
+
+    pub fn substring(env: Env, s: String, start: u32, end: u32) -> Result<String, StringError> {
+        strings::substring(&env, &s, start, end)
+    }
+
+    pub fn eq_ignore_case(_env: Env, a: String, b: String) -> Result<bool, StringError> {
+        strings::eq_ignore_case(&a, &b)
+    }
+
+    pub fn char_len(_env: Env, s: String) -> Result<u32, StringError> {
+        strings::char_len(&s)
+    }
+
+    pub fn check_char_len(_env: Env, s: String, min: u32, max: u32) -> Result<u32, StringError> {
+        strings::check_char_len(&s, min, max)
+    }
+
+    pub fn to_bytes(env: Env, s: String) -> Result<Bytes, StringError> {
+        strings::to_bytes(&env, &s)
+    }
+
+    pub fn from_bytes(env: Env, bytes: Bytes) -> Result<String, StringError> {
+        strings::from_bytes(&env, &bytes)
+    }
+
+    pub fn to_symbol(env: Env, s: String) -> Result<Symbol, StringError> {
+        strings::to_symbol(&env, &s)
+    }
+
+    pub fn from_symbol(env: Env, symbol: Symbol) -> String {
+        strings::from_symbol(&env, &symbol)
+    }
+}
+
+mod test;
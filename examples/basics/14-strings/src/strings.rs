@@ -0,0 +1,165 @@
+//! Reusable `no_std` helpers for `soroban_sdk::String`.
+//!
+//! A `String` lives in the host; the contract only sees an opaque handle.
+//! Anything beyond `len`, equality and ordering means copying the bytes into
+//! a stack buffer, working on them as a `&str`, and copying the result back
+//! with `String::from_bytes`. Every helper here follows that shape, with a
+//! buffer of `MAX_LEN` bytes.
+//!
+//! Host strings are not guaranteed to be valid UTF-8 — `String::from_bytes`
+//! accepts any bytes — so helpers that need characters validate first and
+//! return `InvalidUtf8` rather than misbehave.
+
+use soroban_sdk::{contracterror, Bytes, Env, String, Symbol, SymbolStr, TryFromVal, Vec};
+
+/// Size of the stack buffer every helper copies into, in bytes.
+pub const MAX_LEN: usize = 256;
+
+/// Longest `Symbol` the host accepts.
+const MAX_SYMBOL_LEN: usize = 32;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StringError {
+    /// The input or result is longer than `MAX_LEN` bytes.
+    TooLong = 1,
+    /// `start > end`, `end > len`, or a bound inside a multi-byte character.
+    InvalidRange = 2,
+    InvalidUtf8 = 3,
+    /// Not valid `Symbol` text: up to 32 characters of `a-zA-Z0-9_`.
+    InvalidSymbol = 4,
+    /// The character count is outside the allowed range.
+    InvalidLength = 5,
+}
+
+/// Copy `s` into `buf` and return the filled part.
+fn copy<'a>(s: &String, buf: &'a mut [u8; MAX_LEN]) -> Result<&'a [u8], StringError> {
+    let len = s.len() as usize;
+    if len > MAX_LEN {
+        return Err(StringError::TooLong);
+    }
+    s.copy_into_slice(&mut buf[..len]);
+    Ok(&buf[..len])
+}
+
+/// Copy `s` into `buf` and view it as UTF-8.
+fn as_str<'a>(s: &String, buf: &'a mut [u8; MAX_LEN]) -> Result<&'a str, StringError> {
+    core::str::from_utf8(copy(s, buf)?).map_err(|_| StringError::InvalidUtf8)
+}
+
+/// All of `parts`, one after another.
+pub fn concat(env: &Env, parts: &Vec<String>) -> Result<String, StringError> {
+    join(env, parts, &String::from_str(env, ""))
+}
+
+/// All of `parts` joined with `separator` between them.
+pub fn join(env: &Env, parts: &Vec<String>, separator: &String) -> Result<String, StringError> {
+    let mut out = [0u8; MAX_LEN];
+    let mut len = 0;
+    let mut part_buf = [0u8; MAX_LEN];
+    let mut sep_buf = [0u8; MAX_LEN];
+    let sep = copy(separator, &mut sep_buf)?;
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            len = append(&mut out, len, sep)?;
+        }
+        len = append(&mut out, len, copy(&part, &mut part_buf)?)?;
+    }
+    Ok(String::from_bytes(env, &out[..len]))
+}
+
+/// Write `bytes` into `out` at `len` and return the new length.
+fn append(out: &mut [u8; MAX_LEN], len: usize, bytes: &[u8]) -> Result<usize, StringError> {
+    let end = len + bytes.len();
+    if end > MAX_LEN {
+        return Err(StringError::TooLong);
+    }
+    out[len..end].copy_from_slice(bytes);
+    Ok(end)
+}
+
+/// Bytes `start..end` of `s`.
+///
+/// Offsets are in bytes, like `String::len`, and must fall on character
+/// boundaries so the result is still valid UTF-8.
+pub fn substring(env: &Env, s: &String, start: u32, end: u32) -> Result<String, StringError> {
+    let mut buf = [0u8; MAX_LEN];
+    let text = as_str(s, &mut buf)?;
+    let (start, end) = (start as usize, end as usize);
+    if start > end || end > text.len() {
+        return Err(StringError::InvalidRange);
+    }
+    let sub = text.get(start..end).ok_or(StringError::InvalidRange)?;
+    Ok(String::from_str(env, sub))
+}
+
+/// Whether `a` and `b` are equal ignoring ASCII case.
+///
+/// Only `A-Z` / `a-z` are folded; other characters must match exactly.
+/// Full Unicode case folding needs tables far too large for a contract.
+pub fn eq_ignore_case(a: &String, b: &String) -> Result<bool, StringError> {
+    if a.len() != b.len() {
+        return Ok(false);
+    }
+    let mut a_buf = [0u8; MAX_LEN];
+    let mut b_buf = [0u8; MAX_LEN];
+    Ok(copy(a, &mut a_buf)?.eq_ignore_ascii_case(copy(b, &mut b_buf)?))
+}
+
+/// Number of characters in `s`, which can be less than `s.len()` bytes.
+pub fn char_len(s: &String) -> Result<u32, StringError> {
+    let mut buf = [0u8; MAX_LEN];
+    Ok(as_str(s, &mut buf)?.chars().count() as u32)
+}
+
+/// `char_len(s)`, or `InvalidLength` unless it is within `min..=max`.
+pub fn check_char_len(s: &String, min: u32, max: u32) -> Result<u32, StringError> {
+    let len = char_len(s)?;
+    if len < min || len > max {
+        return Err(StringError::InvalidLength);
+    }
+    Ok(len)
+}
+
+// ==================== CONVERSIONS ====================
+
+pub fn to_bytes(env: &Env, s: &String) -> Result<Bytes, StringError> {
+    let mut buf = [0u8; MAX_LEN];
+    Ok(Bytes::from_slice(env, copy(s, &mut buf)?))
+}
+
+/// `bytes` as a `String`, refusing invalid UTF-8.
+pub fn from_bytes(env: &Env, bytes: &Bytes) -> Result<String, StringError> {
+    let len = bytes.len() as usize;
+    if len > MAX_LEN {
+        return Err(StringError::TooLong);
+    }
+    let mut buf = [0u8; MAX_LEN];
+    bytes.copy_into_slice(&mut buf[..len]);
+    let text = core::str::from_utf8(&buf[..len]).map_err(|_| StringError::InvalidUtf8)?;
+    Ok(String::from_str(env, text))
+}
+
+/// `s` as a `Symbol`. Checked first, because `Symbol::new` panics on
+/// invalid input.
+pub fn to_symbol(env: &Env, s: &String) -> Result<Symbol, StringError> {
+    let mut buf = [0u8; MAX_LEN];
+    let text = as_str(s, &mut buf).map_err(|_| StringError::InvalidSymbol)?;
+    let valid = text.len() <= MAX_SYMBOL_LEN
+        && text.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_');
+    if !valid {
+        return Err(StringError::InvalidSymbol);
+    }
+    Ok(Symbol::new(env, text))
+}
+
+/// The characters of `symbol` as a `String`.
+///
+/// `SymbolStr` decodes both the small inline symbols (up to 9 characters)
+/// and the larger host-object ones into a stack buffer.
+pub fn from_symbol(env: &Env, symbol: &Symbol) -> String {
+    let text = SymbolStr::try_from_val(env, &symbol.to_symbol_val())
+        .unwrap_or_else(|_| panic!("symbol conversion failed"));
+    String::from_str(env, text.as_ref())
+}
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{bytes, symbol_short, vec};
+
+fn setup() -> (Env, StringsContractClient<'static>) {
+    let env = Env::default();
+    let client = StringsContractClient::new(&env, &env.register_contract(None, StringsContract));
+    (env, client)
+}
+
+fn s(env: &Env, text: &str) -> String {
+    String::from_str(env, text)
+}
+
+// ==================== CONCATENATION ====================
+
+#[test]
+fn test_concat() {
+    let (env, client) = setup();
+    let parts = vec![&env, s(&env, "Hello"), s(&env, ", "), s(&env, "wörld!")];
+    assert_eq!(client.concat(&parts), s(&env, "Hello, wörld!"));
+    assert_eq!(client.concat(&Vec::new(&env)), s(&env, ""));
+    assert_eq!(
+        client.concat(&vec![&env, s(&env, ""), s(&env, "a"), s(&env, "")]),
+        s(&env, "a")
+    );
+}
+
+#[test]
+fn test_join() {
+    let (env, client) = setup();
+    let parts = vec![&env, s(&env, "alice"), s(&env, "bob"), s(&env, "carol")];
+    assert_eq!(
+        client.join(&parts, &s(&env, ", ")),
+        s(&env, "alice, bob, carol")
+    );
+    assert_eq!(
+        client.join(&vec![&env, s(&env, "solo")], &s(&env, "-")),
+        s(&env, "solo")
+    );
+}
+
+#[test]
+fn test_concat_too_long() {
+    let (env, client) = setup();
+    let half = [b'x'; MAX_LEN / 2];
+    let half = s(&env, core::str::from_utf8(&half).unwrap());
+    // Exactly MAX_LEN bytes fits; one more does not.
+    assert_eq!(
+        client.concat(&vec![&env, half.clone(), half.clone()]).len() as usize,
+        MAX_LEN
+    );
+    assert_eq!(
+        client.try_concat(&vec![&env, half.clone(), half, s(&env, "!")]),
+        Err(Ok(StringError::TooLong))
+    );
+}
+
+// ==================== SUBSTRINGS ====================
+
+#[test]
+fn test_substring() {
+    let (env, client) = setup();
+    let text = s(&env, "soroban");
+    assert_eq!(client.substring(&text, &0, &4), s(&env, "soro"));
+    assert_eq!(client.substring(&text, &4, &7), s(&env, "ban"));
+    assert_eq!(client.substring(&text, &3, &3), s(&env, ""));
+    assert_eq!(
+        client.try_substring(&text, &0, &8),
+        Err(Ok(StringError::InvalidRange))
+    );
+    assert_eq!(
+        client.try_substring(&text, &5, &4),
+        Err(Ok(StringError::InvalidRange))
+    );
+}
+
+#[test]
+fn test_substring_respects_char_boundaries() {
+    let (env, client) = setup();
+    // "é" occupies bytes 1..3.
+    let text = s(&env, "héllo");
+    assert_eq!(client.substring(&text, &1, &3), s(&env, "é"));
+    assert_eq!(
+        client.try_substring(&text, &0, &2),
+        Err(Ok(StringError::InvalidRange))
+    );
+    assert_eq!(
+        client.try_substring(&text, &2, &6),
+        Err(Ok(StringError::InvalidRange))
+    );
+}
+
+// ==================== COMPARISON AND LENGTH ====================
+
+#[test]
+fn test_eq_ignore_case() {
+    let (env, client) = setup();
+    assert!(client.eq_ignore_case(&s(&env, "Soroban"), &s(&env, "sOROBAN")));
+    assert!(!client.eq_ignore_case(&s(&env, "Soroban"), &s(&env, "Sorobax")));
+    assert!(!client.eq_ignore_case(&s(&env, "abc"), &s(&env, "abcd")));
+    // Only ASCII letters are folded.
+    assert!(!client.eq_ignore_case(&s(&env, "É"), &s(&env, "é")));
+    assert!(client.eq_ignore_case(&s(&env, "é_1"), &s(&env, "é_1")));
+}
+
+#[test]
+fn test_char_len_counts_characters() {
+    let (env, client) = setup();
+    let text = s(&env, "héllo wörld");
+    assert_eq!(text.len(), 13);
+    assert_eq!(client.char_len(&text), 11);
+    assert_eq!(client.char_len(&s(&env, "")), 0);
+    // A 4-byte emoji is one character.
+    assert_eq!(client.char_len(&s(&env, "gm 🌞")), 4);
+}
+
+#[test]
+fn test_check_char_len() {
+    let (env, client) = setup();
+    // Six characters (seven bytes) passes a 3..=6 character limit.
+    assert_eq!(client.check_char_len(&s(&env, "héllos"), &3, &6), 6);
+    assert_eq!(client.check_char_len(&s(&env, "abc"), &3, &6), 3);
+    assert_eq!(
+        client.try_check_char_len(&s(&env, "ab"), &3, &6),
+        Err(Ok(StringError::InvalidLength))
+    );
+    assert_eq!(
+        client.try_check_char_len(&s(&env, "abcdefg"), &3, &6),
+        Err(Ok(StringError::InvalidLength))
+    );
+}
+
+#[test]
+fn test_invalid_utf8_is_rejected() {
+    let (env, client) = setup();
+    // The host accepts any bytes as a String.
+    let bad = String::from_bytes(&env, &[0x68, 0xc3, 0x28]);
+    assert_eq!(client.try_char_len(&bad), Err(Ok(StringError::InvalidUtf8)));
+    assert_eq!(
+        client.try_substring(&bad, &0, &1),
+        Err(Ok(StringError::InvalidUtf8))
+    );
+    assert_eq!(
+        client.try_from_bytes(&bytes!(&env, 0x68c328)),
+        Err(Ok(StringError::InvalidUtf8))
+    );
+}
+
+// ==================== CONVERSIONS ====================
+
+#[test]
+fn test_bytes_round_trip() {
+    let (env, client) = setup();
+    let text = s(&env, "wörld");
+    let bytes = client.to_bytes(&text);
+    assert_eq!(bytes, Bytes::from_slice(&env, "wörld".as_bytes()));
+    assert_eq!(client.from_bytes(&bytes), text);
+}
+
+#[test]
+fn test_symbol_round_trip() {
+    let (env, client) = setup();
+    assert_eq!(
+        client.to_symbol(&s(&env, "transfer")),
+        symbol_short!("transfer")
+    );
+    assert_eq!(
+        client.from_symbol(&symbol_short!("transfer")),
+        s(&env, "transfer")
+    );
+    // Longer than 9 characters: stored as a host object, same result.
+    let long = s(&env, "a_much_longer_symbol_name");
+    assert_eq!(client.from_symbol(&client.to_symbol(&long)), long);
+}
+
+#[test]
+fn test_invalid_symbol() {
+    let (env, client) = setup();
+    for bad in [
+        "has space",
+        "dash-ed",
+        "wörld",
+        "a_symbol_longer_than_32_characters",
+    ] {
+        assert_eq!(
+            client.try_to_symbol(&s(&env, bad)),
+            Err(Ok(StringError::InvalidSymbol)),
+            "{bad}"
+        );
+    }
+}
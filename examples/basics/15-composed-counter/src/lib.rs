@@ -0,0 +1,246 @@
+//! # Composed Counter
+//!
+//! The earlier basics each show one piece in isolation. This contract puts
+//! them together in one small, realistic counter:
+//!
+//! | Piece | Recipe | Here |
+//! |-------|--------|------|
+//! | One-time admin init | `03-authentication` | `initialize` |
+//! | Role gating | `03-authentication` | `Role::Member` counts, `Role::Moderator` also resets |
+//! | Persistent per-user data | `02-storage-patterns` | `DataKey::Count(user)` |
+//! | Instance config | `02-storage-patterns` | `DataKey::MaxIncrement`, `DataKey::Total` |
+//! | TTL upkeep | `ttl-patterns` | every write extends its entry |
+//! | Events | `04-events` | one event per change |
+//! | Typed errors | `03-custom-errors` | `CounterError` |
+//!
+//! Every state-changing function follows the same order, so a failed check
+//! never leaves a partial write or a stray event behind:
+//!
+//! 1. authenticate the caller (`require_auth`);
+//! 2. authorize them (role lookup);
+//! 3. validate the arguments against the config;
+//! 4. write storage;
+//! 5. publish the event.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+// TTL constants (in ledgers; ~1 ledger ≈ 5 s on Stellar mainnet)
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+pub const INSTANCE_EXTEND_TO: u32 = 7 * DAY_IN_LEDGERS;
+pub const INSTANCE_THRESHOLD: u32 = INSTANCE_EXTEND_TO - DAY_IN_LEDGERS;
+pub const RECORD_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+pub const RECORD_THRESHOLD: u32 = RECORD_EXTEND_TO - 7 * DAY_IN_LEDGERS;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Instance: who manages roles and config.
+    Admin,
+    /// Instance: the largest step a single `increment` may take.
+    MaxIncrement,
+    /// Instance: the sum of every user's count.
+    Total,
+    /// Persistent: a user's role; absent for users without one.
+    Role(Address),
+    /// Persistent: a user's count; absent until their first increment.
+    Count(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// May increment their own counter.
+    Member,
+    /// May also reset anyone's counter.
+    Moderator,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CounterError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The caller lacks the role the function requires.
+    NotPermitted = 3,
+    /// A step or limit of zero.
+    InvalidAmount = 4,
+    /// A step above the configured maximum.
+    ExceedsMaxIncrement = 5,
+    /// The count or total would not fit in a `u32`.
+    Overflow = 6,
+}
+
+#[contract]
+pub struct ComposedCounter;
+
+#[contractimpl]
+impl ComposedCounter {
+    // ==================== ADMIN ====================
+
+    /// Set the admin and the maximum step, once.
+    pub fn initialize(env: Env, admin: Address, max_increment: u32) -> Result<(), CounterError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(CounterError::AlreadyInitialized);
+        }
+        if max_increment == 0 {
+            return Err(CounterError::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxIncrement, &max_increment);
+        env.storage().instance().set(&DataKey::Total, &0u32);
+        extend_instance(&env);
+
+        env.events()
+            .publish((symbol_short!("init"),), (admin, max_increment));
+        Ok(())
+    }
+
+    /// Change the maximum step. Publishes the old and new values.
+    pub fn set_max_increment(env: Env, max_increment: u32) -> Result<(), CounterError> {
+        admin(&env)?.require_auth();
+        if max_increment == 0 {
+            return Err(CounterError::InvalidAmount);
+        }
+        let old: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxIncrement)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxIncrement, &max_increment);
+        extend_instance(&env);
+
+        env.events()
+            .publish((symbol_short!("config"),), (old, max_increment));
+        Ok(())
+    }
+
+    /// Grant `user` a role, or revoke it with `None`.
+    pub fn set_role(env: Env, user: Address, role: Option<Role>) -> Result<(), CounterError> {
+        admin(&env)?.require_auth();
+        let key = DataKey::Role(user.clone());
+        match role {
+            Some(role) => {
+                env.storage().persistent().set(&key, &role);
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, RECORD_THRESHOLD, RECORD_EXTEND_TO);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events().publish((symbol_short!("role"), user), role);
+        Ok(())
+    }
+
+    // ==================== COUNTING ====================
+
+    /// Add `by` to `user`'s counter and return the new count.
+    pub fn increment(env: Env, user: Address, by: u32) -> Result<u32, CounterError> {
+        user.require_auth();
+        require_role(&env, &user, Role::Member)?;
+        if by == 0 {
+            return Err(CounterError::InvalidAmount);
+        }
+        if by > Self::max_increment(env.clone())? {
+            return Err(CounterError::ExceedsMaxIncrement);
+        }
+
+        let count = Self::count(env.clone(), user.clone())
+            .checked_add(by)
+            .ok_or(CounterError::Overflow)?;
+        let total = Self::total(env.clone())
+            .checked_add(by)
+            .ok_or(CounterError::Overflow)?;
+        set_count(&env, &user, count);
+        env.storage().instance().set(&DataKey::Total, &total);
+        extend_instance(&env);
+
+        env.events()
+            .publish((symbol_short!("incr"), user), (by, count));
+        Ok(count)
+    }
+
+    /// Reset `user`'s counter to zero. Moderators only.
+    pub fn reset(env: Env, moderator: Address, user: Address) -> Result<(), CounterError> {
+        moderator.require_auth();
+        require_role(&env, &moderator, Role::Moderator)?;
+
+        let old = Self::count(env.clone(), user.clone());
+        let total = Self::total(env.clone()) - old;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Count(user.clone()));
+        env.storage().instance().set(&DataKey::Total, &total);
+        extend_instance(&env);
+
+        env.events()
+            .publish((symbol_short!("reset"), user), (moderator, old));
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn count(env: Env, user: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Count(user))
+            .unwrap_or(0)
+    }
+
+    pub fn total(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Total).unwrap_or(0)
+    }
+
+    pub fn max_increment(env: Env) -> Result<u32, CounterError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxIncrement)
+            .ok_or(CounterError::NotInitialized)
+    }
+
+    pub fn role(env: Env, user: Address) -> Option<Role> {
+        env.storage().persistent().get(&DataKey::Role(user))
+    }
+}
+
+// ==================== HELPERS ====================
+
+fn admin(env: &Env) -> Result<Address, CounterError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(CounterError::NotInitialized)
+}
+
+/// `Moderator` includes everything `Member` may do.
+fn require_role(env: &Env, user: &Address, required: Role) -> Result<(), CounterError> {
+    match (ComposedCounter::role(env.clone(), user.clone()), required) {
+        (Some(Role::Moderator), _) | (Some(Role::Member), Role::Member) => Ok(()),
+        _ => Err(CounterError::NotPermitted),
+    }
+}
+
+fn set_count(env: &Env, user: &Address, count: u32) {
+    let key = DataKey::Count(user.clone());
+    env.storage().persistent().set(&key, &count);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, RECORD_THRESHOLD, RECORD_EXTEND_TO);
+}
+
+fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_THRESHOLD, INSTANCE_EXTEND_TO);
+}
+
+mod test;
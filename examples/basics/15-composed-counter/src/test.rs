@@ -0,0 +1,237 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Events},
+    vec, IntoVal,
+};
+
+struct Setup {
+    env: Env,
+    client: ComposedCounterClient<'static>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ComposedCounterClient::new(&env, &env.register_contract(None, ComposedCounter));
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &10);
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        env,
+        client,
+        admin,
+    }
+}
+
+// ==================== END TO END ====================
+
+/// Walks through every recipe in order: init, roles, counting within the
+/// config, a reset, a config change, and the events each step leaves.
+#[test]
+fn test_end_to_end() {
+    let t = setup();
+    let moderator = Address::generate(&t.env);
+
+    // Roles: Alice and Bob count, the moderator also resets.
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.client.set_role(&t.bob, &Some(Role::Member));
+    t.client.set_role(&moderator, &Some(Role::Moderator));
+
+    // Per-user counters in persistent storage, a total in instance storage.
+    assert_eq!(t.client.increment(&t.alice, &3), 3);
+    assert_eq!(t.client.increment(&t.alice, &10), 13);
+    assert_eq!(t.client.increment(&t.bob, &4), 4);
+    assert_eq!(t.client.total(), 17);
+
+    // The instance config bounds every step.
+    assert_eq!(
+        t.client.try_increment(&t.bob, &11),
+        Err(Ok(CounterError::ExceedsMaxIncrement))
+    );
+    t.client.set_max_increment(&20);
+    assert_eq!(t.client.increment(&t.bob, &11), 15);
+
+    // A moderator resets Alice; the total follows.
+    t.client.reset(&moderator, &t.alice);
+    assert_eq!(t.client.count(&t.alice), 0);
+    assert_eq!(t.client.count(&t.bob), 15);
+    assert_eq!(t.client.total(), 15);
+
+    // One event per change, with the last three shown here.
+    let events = t.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 3..),
+        vec![
+            &t.env,
+            (
+                t.client.address.clone(),
+                (symbol_short!("config"),).into_val(&t.env),
+                (10u32, 20u32).into_val(&t.env),
+            ),
+            (
+                t.client.address.clone(),
+                (symbol_short!("incr"), t.bob.clone()).into_val(&t.env),
+                (11u32, 15u32).into_val(&t.env),
+            ),
+            (
+                t.client.address.clone(),
+                (symbol_short!("reset"), t.alice.clone()).into_val(&t.env),
+                (moderator, 13u32).into_val(&t.env),
+            ),
+        ]
+    );
+}
+
+// ==================== INIT AND CONFIG ====================
+
+#[test]
+fn test_initialize_only_once() {
+    let t = setup();
+    assert_eq!(
+        t.client.try_initialize(&t.admin, &5),
+        Err(Ok(CounterError::AlreadyInitialized))
+    );
+    assert_eq!(t.client.max_increment(), 10);
+}
+
+#[test]
+fn test_uninitialized_contract_rejects_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = ComposedCounterClient::new(&env, &env.register_contract(None, ComposedCounter));
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_role(&user, &Some(Role::Member)),
+        Err(Ok(CounterError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_max_increment(),
+        Err(Ok(CounterError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_initialize(&user, &0),
+        Err(Ok(CounterError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_set_max_increment_rejects_zero() {
+    let t = setup();
+    assert_eq!(
+        t.client.try_set_max_increment(&0),
+        Err(Ok(CounterError::InvalidAmount))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_role_requires_admin_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client.set_role(&t.alice, &Some(Role::Member));
+}
+
+// ==================== ROLES ====================
+
+#[test]
+fn test_increment_requires_role() {
+    let t = setup();
+    assert_eq!(
+        t.client.try_increment(&t.alice, &1),
+        Err(Ok(CounterError::NotPermitted))
+    );
+
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.client.increment(&t.alice, &1);
+    assert_eq!(t.client.role(&t.alice), Some(Role::Member));
+
+    // Revoking the role keeps the count but stops further increments.
+    t.client.set_role(&t.alice, &None);
+    assert_eq!(t.client.role(&t.alice), None);
+    assert_eq!(t.client.count(&t.alice), 1);
+    assert_eq!(
+        t.client.try_increment(&t.alice, &1),
+        Err(Ok(CounterError::NotPermitted))
+    );
+}
+
+#[test]
+fn test_only_moderators_reset() {
+    let t = setup();
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.client.set_role(&t.bob, &Some(Role::Moderator));
+    t.client.increment(&t.alice, &5);
+
+    assert_eq!(
+        t.client.try_reset(&t.alice, &t.alice),
+        Err(Ok(CounterError::NotPermitted))
+    );
+    // A moderator may count too.
+    t.client.increment(&t.bob, &2);
+    t.client.reset(&t.bob, &t.alice);
+    assert_eq!(t.client.count(&t.alice), 0);
+    assert_eq!(t.client.total(), 2);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_increment_requires_user_auth() {
+    let t = setup();
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.env.set_auths(&[]);
+    t.client.increment(&t.alice, &1);
+}
+
+// ==================== VALIDATION AND STORAGE ====================
+
+#[test]
+fn test_failed_increment_changes_nothing() {
+    let t = setup();
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.client.increment(&t.alice, &10);
+    let events = t.env.events().all().len();
+
+    assert_eq!(
+        t.client.try_increment(&t.alice, &0),
+        Err(Ok(CounterError::InvalidAmount))
+    );
+    assert_eq!(
+        t.client.try_increment(&t.alice, &11),
+        Err(Ok(CounterError::ExceedsMaxIncrement))
+    );
+    assert_eq!(t.client.count(&t.alice), 10);
+    assert_eq!(t.client.total(), 10);
+    assert_eq!(t.env.events().all().len(), events);
+}
+
+#[test]
+fn test_overflow_is_an_error() {
+    let t = setup();
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.client.set_max_increment(&u32::MAX);
+    t.client.increment(&t.alice, &u32::MAX);
+    assert_eq!(
+        t.client.try_increment(&t.alice, &1),
+        Err(Ok(CounterError::Overflow))
+    );
+}
+
+#[test]
+fn test_counts_are_persistent_with_extended_ttl() {
+    let t = setup();
+    t.client.set_role(&t.alice, &Some(Role::Member));
+    t.client.increment(&t.alice, &1);
+
+    t.env.as_contract(&t.client.address, || {
+        let key = DataKey::Count(t.alice.clone());
+        assert!(t.env.storage().persistent().has(&key));
+        assert_eq!(t.env.storage().persistent().get_ttl(&key), RECORD_EXTEND_TO);
+    });
+}
@@ -0,0 +1,138 @@
+//! # Symbols
+//!
+//! A `Symbol` is a short identifier: up to 32 characters from `a-zA-Z0-9_`.
+//! There are two ways to make one, and the difference is the length:
+//!
+//! | Length  | Constructor              | Representation                   |
+//! |---------|--------------------------|----------------------------------|
+//! | 0 – 9   | `symbol_short!("name")`  | packed into the value itself     |
+//! | 10 – 32 | `Symbol::new(&env, "…")` | a host object                    |
+//!
+//! `symbol_short!` runs at compile time, so a name over 9 characters does
+//! not build. `Symbol::new` checks at run time and panics on a name over 32
+//! characters or with any other character, which aborts the invocation.
+//! Both produce equal symbols for the same text, so the choice is only
+//! about cost: short symbols need no host object.
+//!
+//! `Symbol::try_from_val(&env, &text)` looks like the graceful version, but
+//! in this SDK any text that does not fit a short symbol is handed to the
+//! host, and the host traps on invalid input just like `Symbol::new`. To
+//! turn arbitrary text into an error instead of a trap, check the length
+//! and characters first, as `parse_symbol` below does.
+//!
+//! ## Symbol or String?
+//!
+//! - **Symbol** for names the contract defines: storage keys, event topics,
+//!   setting names, function names. They are cheap to compare and to store.
+//! - **String** for text users supply: display names, descriptions, URLs.
+//!   Users type spaces, dashes and accents, none of which a Symbol accepts.
+//!
+//! Here a user's settings are stored under `Symbol` keys and their display
+//! name as a `String`. See the tests for each rule.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol,
+};
+
+/// Longest name `symbol_short!` accepts.
+pub const MAX_SHORT_LEN: u32 = 9;
+/// Longest name any `Symbol` can have.
+pub const MAX_SYMBOL_LEN: u32 = 32;
+/// Longest display name, in bytes.
+pub const MAX_DISPLAY_NAME_LEN: u32 = 64;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Persistent: a user's setting, named by a contract-defined symbol.
+    Setting(Address, Symbol),
+    /// Persistent: a user's display name, free text.
+    DisplayName(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SymbolError {
+    /// More than `MAX_SYMBOL_LEN` characters.
+    TooLong = 1,
+    /// A character outside `a-zA-Z0-9_`.
+    InvalidCharacter = 2,
+    /// A display name over `MAX_DISPLAY_NAME_LEN` bytes.
+    NameTooLong = 3,
+}
+
+#[contract]
+pub struct SymbolsContract;
+
+#[contractimpl]
+impl SymbolsContract {
+    // ==================== CONVERSION ====================
+
+    /// Turn user-supplied text into a `Symbol`, returning an error where
+    /// `Symbol::new` would trap. The limit counts bytes, so any non-ASCII
+    /// character is rejected before it could matter.
+    pub fn parse_symbol(env: Env, text: String) -> Result<Symbol, SymbolError> {
+        if text.len() > MAX_SYMBOL_LEN {
+            return Err(SymbolError::TooLong);
+        }
+        let mut buf = [0u8; MAX_SYMBOL_LEN as usize];
+        let buf = &mut buf[..text.len() as usize];
+        text.copy_into_slice(buf);
+        if !buf.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_') {
+            return Err(SymbolError::InvalidCharacter);
+        }
+        // All ASCII, so valid UTF-8.
+        let text = core::str::from_utf8(buf).map_err(|_| SymbolError::InvalidCharacter)?;
+        Ok(Symbol::new(&env, text))
+    }
+
+    // ==================== SYMBOL KEYS ====================
+
+    /// Store one of `user`'s settings under `name`.
+    ///
+    /// Emits `(setting, user)` with `(name, value)`. The topic is a short
+    /// symbol; `name` goes in the data because it varies.
+    pub fn set_setting(env: Env, user: Address, name: Symbol, value: u32) {
+        user.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Setting(user.clone(), name.clone()), &value);
+        env.events()
+            .publish((symbol_short!("setting"), user), (name, value));
+    }
+
+    pub fn setting(env: Env, user: Address, name: Symbol) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Setting(user, name))
+    }
+
+    // ==================== STRING VALUES ====================
+
+    /// Set `user`'s display name. Any UTF-8 text up to
+    /// `MAX_DISPLAY_NAME_LEN` bytes.
+    ///
+    /// Emits `(display_name_changed, user)` with the name. The topic is
+    /// over 9 characters, so it is built with `Symbol::new`.
+    pub fn set_display_name(env: Env, user: Address, name: String) -> Result<(), SymbolError> {
+        user.require_auth();
+        if name.len() > MAX_DISPLAY_NAME_LEN {
+            return Err(SymbolError::NameTooLong);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::DisplayName(user.clone()), &name);
+        env.events()
+            .publish((Symbol::new(&env, "display_name_changed"), user), name);
+        Ok(())
+    }
+
+    pub fn display_name(env: Env, user: Address) -> Option<String> {
+        env.storage().persistent().get(&DataKey::DisplayName(user))
+    }
+}
+
+mod test;
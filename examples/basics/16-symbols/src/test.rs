@@ -0,0 +1,195 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, IntoVal, TryFromVal,
+};
+use std::string::ToString;
+
+struct Setup {
+    env: Env,
+    client: SymbolsContractClient<'static>,
+    alice: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = SymbolsContractClient::new(&env, &env.register_contract(None, SymbolsContract));
+    Setup {
+        alice: Address::generate(&env),
+        env,
+        client,
+    }
+}
+
+fn s(env: &Env, text: &str) -> String {
+    String::from_str(env, text)
+}
+
+/// 32 characters: the longest possible symbol.
+const LONGEST: &str = "abcdefghijklmnopqrstuvwxyz_01234";
+
+// ==================== LIMITS ====================
+
+#[test]
+fn test_short_and_long_constructors_agree() {
+    let env = Env::default();
+    // Up to 9 characters either constructor works, and the results are equal.
+    assert_eq!(symbol_short!("transfer"), Symbol::new(&env, "transfer"));
+    assert_eq!(symbol_short!("123456789"), Symbol::new(&env, "123456789"));
+    assert_eq!(
+        Symbol::new(&env, "123456789").to_string().len(),
+        MAX_SHORT_LEN as usize
+    );
+    // `symbol_short!("1234567890")` does not compile; use `Symbol::new`.
+    assert_eq!(Symbol::new(&env, "1234567890").to_string(), "1234567890");
+}
+
+#[test]
+fn test_symbol_new_accepts_32_characters() {
+    let env = Env::default();
+    assert_eq!(LONGEST.len(), MAX_SYMBOL_LEN as usize);
+    assert_eq!(Symbol::new(&env, LONGEST).to_string(), LONGEST);
+}
+
+#[test]
+#[should_panic(expected = "Error(Value, InvalidInput)")]
+fn test_symbol_new_panics_over_32_characters() {
+    let env = Env::default();
+    Symbol::new(&env, "abcdefghijklmnopqrstuvwxyz_012345");
+}
+
+#[test]
+#[should_panic(expected = "Error(Value, InvalidInput)")]
+fn test_symbol_new_panics_on_invalid_character() {
+    let env = Env::default();
+    Symbol::new(&env, "display-name");
+}
+
+#[test]
+fn test_try_from_val_accepts_valid_text() {
+    let env = Env::default();
+    assert!(Symbol::try_from_val(&env, &"fee_bps").is_ok());
+    assert!(Symbol::try_from_val(&env, &LONGEST).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Error(Value, InvalidInput)")]
+fn test_try_from_val_traps_on_invalid_text() {
+    let env = Env::default();
+    // Not an `Err`: invalid text reaches the host, which traps.
+    let _ = Symbol::try_from_val(&env, &"dash-ed");
+}
+
+#[test]
+fn test_parse_symbol() {
+    let t = setup();
+    assert_eq!(
+        t.client.parse_symbol(&s(&t.env, "fee_bps")),
+        symbol_short!("fee_bps")
+    );
+    assert_eq!(
+        t.client.parse_symbol(&s(&t.env, LONGEST)),
+        Symbol::new(&t.env, LONGEST)
+    );
+    assert_eq!(
+        t.client
+            .try_parse_symbol(&s(&t.env, "abcdefghijklmnopqrstuvwxyz_012345")),
+        Err(Ok(SymbolError::TooLong))
+    );
+    assert_eq!(
+        t.client.try_parse_symbol(&s(&t.env, "fee bps")),
+        Err(Ok(SymbolError::InvalidCharacter))
+    );
+    // 16 characters but 17 bytes: the limit counts bytes.
+    assert_eq!(
+        t.client.try_parse_symbol(&s(&t.env, "é_and_more_text_")),
+        Err(Ok(SymbolError::InvalidCharacter))
+    );
+    // The empty symbol is valid.
+    assert_eq!(t.client.parse_symbol(&s(&t.env, "")), symbol_short!(""));
+}
+
+// ==================== GUIDANCE ====================
+
+#[test]
+fn test_symbol_keys_for_contract_defined_names() {
+    let t = setup();
+    let long_name = Symbol::new(&t.env, "max_withdrawal_per_day");
+    t.client
+        .set_setting(&t.alice, &symbol_short!("fee_bps"), &30);
+    t.client.set_setting(&t.alice, &long_name, &5_000);
+
+    assert_eq!(
+        t.client.setting(&t.alice, &symbol_short!("fee_bps")),
+        Some(30)
+    );
+    assert_eq!(t.client.setting(&t.alice, &long_name), Some(5_000));
+    // Symbols compare exactly: keys are case-sensitive.
+    assert_eq!(t.client.setting(&t.alice, &symbol_short!("FEE_BPS")), None);
+
+    // A short topic, with the varying name in the data.
+    assert_eq!(
+        t.env.events().all().slice(1..),
+        vec![
+            &t.env,
+            (
+                t.client.address.clone(),
+                (symbol_short!("setting"), t.alice.clone()).into_val(&t.env),
+                (long_name, 5_000u32).into_val(&t.env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_string_values_for_user_text() {
+    let t = setup();
+    let name = s(&t.env, "Alice Møller-Smith");
+    t.client.set_display_name(&t.alice, &name);
+    assert_eq!(t.client.display_name(&t.alice), Some(name.clone()));
+
+    // The same text could never have been a Symbol.
+    assert_eq!(
+        t.client.try_parse_symbol(&name),
+        Err(Ok(SymbolError::InvalidCharacter))
+    );
+
+    // A topic over 9 characters is built with `Symbol::new`.
+    assert_eq!(
+        t.env.events().all(),
+        vec![
+            &t.env,
+            (
+                t.client.address.clone(),
+                (Symbol::new(&t.env, "display_name_changed"), t.alice.clone()).into_val(&t.env),
+                name.into_val(&t.env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_display_name_length_is_bounded() {
+    let t = setup();
+    let max = "n".repeat(MAX_DISPLAY_NAME_LEN as usize);
+    t.client.set_display_name(&t.alice, &s(&t.env, &max));
+    assert_eq!(
+        t.client
+            .try_set_display_name(&t.alice, &s(&t.env, &(max + "n"))),
+        Err(Ok(SymbolError::NameTooLong))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_setting_requires_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client
+        .set_setting(&t.alice, &symbol_short!("fee_bps"), &30);
+}
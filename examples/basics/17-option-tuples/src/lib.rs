@@ -0,0 +1,180 @@
+//! # Options and Tuples
+//!
+//! `Option<T>`, tuples and `Vec`s of them cross the contract boundary like
+//! any other value, but a few rules are easy to get wrong:
+//!
+//! - **`None` is `Void`.** `Some(x)` is encoded as plain `x`. So a
+//!   `Some(None)` is encoded as `Void` too, and arrives as `None`:
+//!   `Option<Option<T>>` cannot tell the two apart.
+//! - **Absent is not empty.** `Option<Vec<T>>` can distinguish "never set"
+//!   (`None`) from "set to nothing" (`Some(vec![])`), which a bare `Vec`
+//!   cannot.
+//! - **Tuples are `Vec`s.** `(A, B)` is encoded as a two-element vector, so
+//!   it can be an argument, a return value or an `Option`'s content.
+//! - **Optional fields hold SDK types.** A `#[contracttype]` field can be
+//!   `Option<Address>` or `Option<Vec<u32>>`, but with the `testutils`
+//!   feature on, `Option<MyStruct>` or `Option<(A, B)>` does not compile:
+//!   the XDR conversion the test snapshots need is missing. Return such
+//!   values directly instead, as `best` does.
+//! - **Storage already returns an `Option`.** `get` yields `None` for a
+//!   missing key. To clear an optional value, `remove` the key rather than
+//!   storing `None`: the key would then exist and hold `Void`, which a
+//!   reader expecting a plain `T` fails on.
+//!
+//! The generated client takes every argument by reference, `Option`s and
+//! tuples included: `client.set_score(&user, &Some(7))`, `&None`. Return
+//! values come back owned, and the `try_` variant wraps them as usual:
+//! `Ok(Ok(Some((1, 9))))`.
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Persistent: a user's score; absent until one is set.
+    Score(Address),
+    /// Persistent: a user's tags, possibly empty; absent until set.
+    Tags(Address),
+}
+
+/// A user and their score.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Best {
+    pub user: Address,
+    pub score: u32,
+}
+
+/// A summary with an optional field.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Summary {
+    /// Users with a score.
+    pub scored: u32,
+    /// Who holds the highest score, if anyone is scored.
+    pub leader: Option<Address>,
+}
+
+#[contract]
+pub struct OptionTuplesContract;
+
+#[contractimpl]
+impl OptionTuplesContract {
+    // ==================== OPTION ARGUMENTS ====================
+
+    /// Set `user`'s score, or clear it with `None`.
+    pub fn set_score(env: Env, user: Address, score: Option<u32>) {
+        user.require_auth();
+        let key = DataKey::Score(user);
+        match score {
+            Some(score) => env.storage().persistent().set(&key, &score),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn score(env: Env, user: Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::Score(user))
+    }
+
+    /// One entry per user, `None` where no score is set. The result always
+    /// has as many entries as `users`.
+    pub fn scores(env: Env, users: Vec<Address>) -> Vec<Option<u32>> {
+        let mut scores = Vec::new(&env);
+        for user in users.iter() {
+            scores.push_back(Self::score(env.clone(), user));
+        }
+        scores
+    }
+
+    /// The first `Some` in `values`, if there is one.
+    pub fn first_some(_env: Env, values: Vec<Option<u32>>) -> Option<u32> {
+        values.iter().flatten().next()
+    }
+
+    // ==================== OPTION OF COLLECTIONS ====================
+
+    /// Set `user`'s tags. An empty list is kept, and differs from never
+    /// having set any.
+    pub fn set_tags(env: Env, user: Address, tags: Vec<Symbol>) {
+        user.require_auth();
+        env.storage().persistent().set(&DataKey::Tags(user), &tags);
+    }
+
+    /// `None` if tags were never set, `Some(vec![])` if they were set to
+    /// nothing.
+    pub fn tags(env: Env, user: Address) -> Option<Vec<Symbol>> {
+        env.storage().persistent().get(&DataKey::Tags(user))
+    }
+
+    // ==================== TUPLES ====================
+
+    /// The smallest and largest value, or `None` for an empty list.
+    pub fn min_max(_env: Env, values: Vec<i128>) -> Option<(i128, i128)> {
+        let first = values.first()?;
+        Some(
+            values
+                .iter()
+                .fold((first, first), |(min, max), v| (min.min(v), max.max(v))),
+        )
+    }
+
+    /// The values below `pivot` and the rest, in order.
+    pub fn partition(env: Env, values: Vec<i128>, pivot: i128) -> (Vec<i128>, Vec<i128>) {
+        let mut below = Vec::new(&env);
+        let mut rest = Vec::new(&env);
+        for v in values.iter() {
+            if v < pivot {
+                below.push_back(v);
+            } else {
+                rest.push_back(v);
+            }
+        }
+        (below, rest)
+    }
+
+    /// Swap the halves of a pair; tuples are accepted as arguments too.
+    pub fn swap(_env: Env, pair: (Address, u32)) -> (u32, Address) {
+        (pair.1, pair.0)
+    }
+
+    /// The highest score among `users` and who holds it, or `None` if
+    /// none of them is scored.
+    pub fn best(env: Env, users: Vec<Address>) -> Option<Best> {
+        let mut best: Option<Best> = None;
+        for user in users.iter() {
+            let Some(score) = Self::score(env.clone(), user.clone()) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|best| score > best.score) {
+                best = Some(Best { user, score });
+            }
+        }
+        best
+    }
+
+    /// How many of `users` have a score, and who leads.
+    pub fn summary(env: Env, users: Vec<Address>) -> Summary {
+        let mut scored = 0;
+        for user in users.iter() {
+            if Self::score(env.clone(), user).is_some() {
+                scored += 1;
+            }
+        }
+        Summary {
+            scored,
+            leader: Self::best(env, users).map(|best| best.user),
+        }
+    }
+
+    // ==================== NESTED OPTIONS ====================
+
+    /// Return the argument unchanged. `Some(None)` comes back as `None`,
+    /// because both are encoded as `Void`.
+    pub fn echo_nested(_env: Env, value: Option<Option<u32>>) -> Option<Option<u32>> {
+        value
+    }
+}
+
+mod test;
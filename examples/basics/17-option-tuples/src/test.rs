@@ -0,0 +1,195 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, IntoVal, Val};
+
+struct Setup {
+    env: Env,
+    client: OptionTuplesContractClient<'static>,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client =
+        OptionTuplesContractClient::new(&env, &env.register_contract(None, OptionTuplesContract));
+    Setup {
+        alice: Address::generate(&env),
+        bob: Address::generate(&env),
+        env,
+        client,
+    }
+}
+
+// ==================== OPTION ARGUMENTS ====================
+
+#[test]
+fn test_option_argument_sets_and_clears() {
+    let t = setup();
+    assert_eq!(t.client.score(&t.alice), None);
+
+    // Options are passed by reference like every other argument.
+    t.client.set_score(&t.alice, &Some(7));
+    assert_eq!(t.client.score(&t.alice), Some(7));
+
+    // `None` clears: the key is removed, not set to `Void`.
+    t.client.set_score(&t.alice, &None);
+    assert_eq!(t.client.score(&t.alice), None);
+    t.env.as_contract(&t.client.address, || {
+        assert!(!t
+            .env
+            .storage()
+            .persistent()
+            .has(&DataKey::Score(t.alice.clone())));
+    });
+}
+
+#[test]
+fn test_vec_of_options_keeps_positions() {
+    let t = setup();
+    t.client.set_score(&t.bob, &Some(3));
+
+    assert_eq!(
+        t.client
+            .scores(&vec![&t.env, t.alice.clone(), t.bob.clone()]),
+        vec![&t.env, None, Some(3)]
+    );
+    assert_eq!(t.client.scores(&vec![&t.env]), vec![&t.env]);
+
+    assert_eq!(
+        t.client.first_some(&vec![&t.env, None, Some(4), Some(5)]),
+        Some(4)
+    );
+    assert_eq!(t.client.first_some(&vec![&t.env, None, None]), None);
+    assert_eq!(t.client.first_some(&vec![&t.env]), None);
+}
+
+// ==================== OPTION OF COLLECTIONS ====================
+
+#[test]
+fn test_absent_differs_from_empty() {
+    let t = setup();
+    assert_eq!(t.client.tags(&t.alice), None);
+
+    t.client.set_tags(&t.alice, &vec![&t.env]);
+    assert_eq!(t.client.tags(&t.alice), Some(vec![&t.env]));
+
+    t.client
+        .set_tags(&t.alice, &vec![&t.env, symbol_short!("admin")]);
+    assert_eq!(
+        t.client.tags(&t.alice),
+        Some(vec![&t.env, symbol_short!("admin")])
+    );
+}
+
+// ==================== TUPLES ====================
+
+#[test]
+fn test_option_of_tuple() {
+    let t = setup();
+    assert_eq!(t.client.min_max(&vec![&t.env, 4, -2, 9, 0]), Some((-2, 9)));
+    assert_eq!(t.client.min_max(&vec![&t.env, 5]), Some((5, 5)));
+    assert_eq!(t.client.min_max(&vec![&t.env]), None);
+
+    // The `try_` variant wraps the same value.
+    assert_eq!(
+        t.client.try_min_max(&vec![&t.env, 1, 9]),
+        Ok(Ok(Some((1, 9))))
+    );
+}
+
+#[test]
+fn test_tuple_of_vecs() {
+    let t = setup();
+    let (below, rest) = t.client.partition(&vec![&t.env, 5, 1, 8, 3], &4);
+    assert_eq!(below, vec![&t.env, 1, 3]);
+    assert_eq!(rest, vec![&t.env, 5, 8]);
+
+    // Either side may be empty.
+    let (below, rest) = t.client.partition(&vec![&t.env, 5, 8], &4);
+    assert_eq!(below, vec![&t.env]);
+    assert_eq!(rest, vec![&t.env, 5, 8]);
+    assert_eq!(
+        t.client.partition(&vec![&t.env], &4),
+        (vec![&t.env], vec![&t.env])
+    );
+}
+
+#[test]
+fn test_tuple_argument() {
+    let t = setup();
+    assert_eq!(t.client.swap(&(t.alice.clone(), 7)), (7, t.alice.clone()));
+
+    // On the wire a tuple is a vector of its elements.
+    let pair: Val = (t.alice.clone(), 7u32).into_val(&t.env);
+    let as_vec: Vec<Val> = pair.into_val(&t.env);
+    assert_eq!(as_vec.len(), 2);
+}
+
+#[test]
+fn test_optional_struct_and_field() {
+    let t = setup();
+    let users = vec![&t.env, t.alice.clone(), t.bob.clone()];
+    assert_eq!(t.client.best(&users), None);
+    assert_eq!(
+        t.client.summary(&users),
+        Summary {
+            scored: 0,
+            leader: None
+        }
+    );
+
+    t.client.set_score(&t.alice, &Some(4));
+    t.client.set_score(&t.bob, &Some(9));
+    assert_eq!(
+        t.client.best(&users),
+        Some(Best {
+            user: t.bob.clone(),
+            score: 9
+        })
+    );
+    assert_eq!(
+        t.client.summary(&users),
+        Summary {
+            scored: 2,
+            leader: Some(t.bob.clone())
+        }
+    );
+}
+
+// ==================== NESTED OPTIONS ====================
+
+#[test]
+fn test_nested_option_collapses() {
+    let t = setup();
+    assert_eq!(t.client.echo_nested(&Some(Some(3))), Some(Some(3)));
+    assert_eq!(t.client.echo_nested(&None), None);
+    // `Some(None)` and `None` are both `Void` on the wire.
+    assert_eq!(t.client.echo_nested(&Some(None)), None);
+}
+
+#[test]
+fn test_storing_none_breaks_plain_reads() {
+    let t = setup();
+    let key = DataKey::Score(t.alice.clone());
+    t.env.as_contract(&t.client.address, || {
+        let storage = t.env.storage().persistent();
+        storage.set(&key, &None::<u32>);
+        // The key exists, holding `Void`.
+        assert!(storage.has(&key));
+        assert_eq!(storage.get::<_, Option<u32>>(&key), Some(None));
+        assert_eq!(storage.get::<_, Val>(&key).map(|v| v.is_void()), Some(true));
+    });
+    // `score` reads a plain `u32`, and `Void` is not one: the call fails.
+    assert!(t.client.try_score(&t.alice).is_err());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_score_requires_auth() {
+    let t = setup();
+    t.env.set_auths(&[]);
+    t.client.set_score(&t.alice, &Some(7));
+}
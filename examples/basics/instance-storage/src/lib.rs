@@ -14,7 +14,8 @@
 //!
 //! | Property                    | Persistent        | Instance          | Temporary        |
 //! |-----------------------------|-------------------|-------------------|------------------|
-//! | Survives contract upgrade   | ✅ Yes            | ❌ No             | ❌ No            |
+//! | Survives Wasm upgrade       | ✅ Yes            | ✅ Yes            | ✅ Until TTL     |
+//! | Loaded on every call        | ❌ No             | ✅ Yes (all keys) | ❌ No            |
 //! | TTL management              | Per-key           | Per-instance      | Per-key          |
 //! | Relative cost               | Highest           | Medium            | Lowest           |
 //! | Use when data is…           | Critical / long   | Instance-lifetime | Single-ledger    |
@@ -22,24 +23,79 @@
 //! ## When to Prefer Instance Over Persistent
 //!
 //! Choose instance storage when:
-//! - The data is important during the life of the instance but does *not* need
-//!   to outlive a contract upgrade (e.g. a transaction counter that resets is OK).
+//! - The data is small and contract-wide (admin, config, counters), so loading
+//!   all of it on every call is cheap.
 //! - You want cheaper rent than persistent while still keeping data across calls.
 //! - You're managing shared state that should expire with the instance as a whole.
 //!
 //! Avoid instance storage when:
-//! - The data MUST survive a `upgrade()` call (use persistent instead).
+//! - The data grows with the number of users (use persistent, one key each).
 //! - The data is only needed for a single invocation (use temporary instead).
 //!
+//! Note that `update_current_contract_wasm` replaces only the code: instance
+//! entries are still there afterwards, just like persistent ones. See
+//! `examples/advanced/upgradeable-contract`.
+//!
 //! ## TTL Notes
 //!
 //! `extend_ttl(min_ledgers, max_ledgers)` keeps the entire instance alive.
 //! Call this whenever you read or write instance data so the instance never
 //! expires unexpectedly.
+//!
+//! ## Batching
+//!
+//! `set_many`, `get_many` and `remove_many` handle any number of raw keys
+//! in one invocation with one `extend_ttl`. Each invocation loads and writes
+//! back the whole instance entry, so ten single-key calls pay that overhead
+//! ten times and one batch pays it once.
+//!
+//! ## Admin-Gated Config
+//!
+//! `set_config`, `cancel_pending`, `set_bounds` and `set_config_typed`
+//! require the auth of the admin stored by `initialize`, and fail with
+//! `NotInitialized` before it. Every `set_config` emits `(cfg_upd, key)`
+//! with the old and new value, so indexers can follow config history
+//! without reading storage.
+//!
+//! `set_config` is the only writer of `Config(key)`, so the bounds and the
+//! activation delay below cannot be sidestepped. The generic `set_instance`
+//! / `set_many` / `remove_many` helpers stay open because they demonstrate
+//! the raw storage API, and they write `Raw(key)`, a separate namespace that
+//! `get_config` never reads.
+//!
+//! ## Delayed Config Activation
+//!
+//! Changing a parameter such as `fee_bps` instantly gives users no chance to
+//! react. `set_config` therefore *schedules* a change: the new value sits in
+//! `InstanceKey::PendingConfig` until `CONFIG_ACTIVATION_DELAY` has passed,
+//! and `get_config` keeps returning the old value until then. A scheduled
+//! change can be withdrawn with `cancel_pending`. The active value lives in
+//! `Config(key)`, which only `set_config` writes, so nothing can change it
+//! ahead of the delay.
+//!
+//! ## Parameter Bounds
+//!
+//! The admin can register a `[min, max]` range per key with `set_bounds`.
+//! Every `set_config` is checked against it and fails with
+//! `ConfigError::OutOfBounds`, so a typo such as `fee_bps = 100000` never
+//! reaches storage. `set_config` is the one write path for config values,
+//! so the check covers every set. Keys without bounds accept any value.
+//!
+//! ## Typed Config
+//!
+//! `Config(key)` only holds a `u64`. `set_config_typed` stores a
+//! `ConfigValue` instead (`u64`, `i128`, `bool`, `Address` or `Symbol`)
+//! under `TypedConfig(key)`, a separate namespace. A key keeps the type of
+//! its first value. Writing another type, or reading with
+//! `get_config_typed` as another type, fails with
+//! `ConfigError::TypeMismatch` rather than misreading the value.
 
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Env, Symbol};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+    Vec,
+};
 
 // ────────────────────────────────────────────────────────────────────────────
 // Storage key enum
@@ -55,8 +111,91 @@ pub enum InstanceKey {
     /// Running count of successful invocations. Use case 1: transaction counter.
     TxCounter,
 
-    /// Arbitrary named configuration value. Use case 2: cached / runtime config.
+    /// Value written by the open generic helpers (`set_instance`,
+    /// `set_many`, …). Never read as config.
+    Raw(Symbol),
+
+    /// Active named configuration value. Use case 2: cached / runtime config.
+    /// Written only by `set_config`.
     Config(Symbol),
+
+    /// A scheduled `Config` change that is not yet active.
+    PendingConfig(Symbol),
+
+    /// Address allowed to change config values and bounds.
+    Admin,
+
+    /// Allowed range for a `Config` key.
+    Bounds(Symbol),
+
+    /// Configuration value of any `ConfigValue` type.
+    TypedConfig(Symbol),
+}
+
+/// Inclusive range a config value must fall in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bounds {
+    pub min: u64,
+    pub max: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ConfigError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The value lies outside the bounds registered for its key.
+    OutOfBounds = 3,
+    /// `set_bounds` was given `min > max`.
+    InvalidBounds = 4,
+    /// A typed config key holds a different type than the one requested.
+    TypeMismatch = 5,
+}
+
+/// A config value of one of the supported types.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigValue {
+    U64(u64),
+    I128(i128),
+    Bool(bool),
+    Addr(Address),
+    Sym(Symbol),
+}
+
+/// The type of a `ConfigValue`, used to ask for one.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ConfigType {
+    U64 = 0,
+    I128 = 1,
+    Bool = 2,
+    Addr = 3,
+    Sym = 4,
+}
+
+impl ConfigValue {
+    pub fn config_type(&self) -> ConfigType {
+        match self {
+            ConfigValue::U64(_) => ConfigType::U64,
+            ConfigValue::I128(_) => ConfigType::I128,
+            ConfigValue::Bool(_) => ConfigType::Bool,
+            ConfigValue::Addr(_) => ConfigType::Addr,
+            ConfigValue::Sym(_) => ConfigType::Sym,
+        }
+    }
+}
+
+/// A config value waiting for its activation time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingConfig {
+    pub value: u64,
+    /// Ledger timestamp from which `value` is returned by `get_config`.
+    pub activates_at: u64,
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -69,6 +208,9 @@ const TTL_THRESHOLD: u32 = 1_000;
 /// Extend up to this many ledgers from the current ledger.
 const TTL_EXTEND_TO: u32 = 10_000;
 
+/// Seconds between scheduling a config change and it taking effect (24 h).
+pub const CONFIG_ACTIVATION_DELAY: u64 = 24 * 60 * 60;
+
 // ────────────────────────────────────────────────────────────────────────────
 // Contract
 // ────────────────────────────────────────────────────────────────────────────
@@ -78,15 +220,27 @@ pub struct InstanceStorageContract;
 
 #[contractimpl]
 impl InstanceStorageContract {
+    /// Stores the admin that manages config values and bounds.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), ConfigError> {
+        if env.storage().instance().has(&InstanceKey::Admin) {
+            return Err(ConfigError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&InstanceKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
     // ── Generic key/value helpers ──────────────────────────────────────────
 
-    /// Stores any `u64` value under a named config key in instance storage.
+    /// Stores any `u64` value under `Raw(key)` in instance storage.
     ///
     /// Because instance TTL is shared, a single `extend_ttl` call here
     /// refreshes the lifetime of *all* instance keys at once — unlike
     /// persistent storage where each key must be extended individually.
     pub fn set_instance(env: Env, key: Symbol, value: u64) {
-        let storage_key = InstanceKey::Config(key);
+        let storage_key = InstanceKey::Raw(key);
         env.storage().instance().set(&storage_key, &value);
 
         // One call covers the entire instance — no per-key TTL bookkeeping.
@@ -95,9 +249,9 @@ impl InstanceStorageContract {
             .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
     }
 
-    /// Returns the `u64` stored under `key`, or `None` if not set.
+    /// Returns the `u64` stored under `Raw(key)`, or `None` if not set.
     pub fn get_instance(env: Env, key: Symbol) -> Option<u64> {
-        let storage_key = InstanceKey::Config(key);
+        let storage_key = InstanceKey::Raw(key);
         // Extend TTL on reads too — any access should keep the instance alive.
         env.storage()
             .instance()
@@ -105,13 +259,63 @@ impl InstanceStorageContract {
         env.storage().instance().get(&storage_key)
     }
 
+    // ── Batch operations ───────────────────────────────────────────────────
+    //
+    // Every invocation pays a fixed overhead: loading the instance entry,
+    // writing it back, and one TTL check. Setting ten keys with
+    // `set_instance` pays it ten times; `set_many` pays it once, with a
+    // single `extend_ttl` for the whole batch.
+
+    /// Stores every `(key, value)` pair under `Raw(key)`. Later pairs win
+    /// when a key repeats.
+    pub fn set_many(env: Env, entries: Vec<(Symbol, u64)>) {
+        for (key, value) in entries.iter() {
+            env.storage().instance().set(&InstanceKey::Raw(key), &value);
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+
+    /// Returns the values `set_instance` / `set_many` stored under `keys`.
+    /// Keys that are not set are left out of the map. Like `get_instance`,
+    /// this reads `Raw(key)`; config values are read with `get_config`.
+    pub fn get_many(env: Env, keys: Vec<Symbol>) -> Map<Symbol, u64> {
+        let mut values = Map::new(&env);
+        for key in keys.iter() {
+            if let Some(value) = env.storage().instance().get(&InstanceKey::Raw(key.clone())) {
+                values.set(key, value);
+            }
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        values
+    }
+
+    /// Removes `Raw(key)` for every key and returns how many were set.
+    pub fn remove_many(env: Env, keys: Vec<Symbol>) -> u32 {
+        let mut removed = 0;
+        for key in keys.iter() {
+            let storage_key = InstanceKey::Raw(key);
+            if env.storage().instance().has(&storage_key) {
+                env.storage().instance().remove(&storage_key);
+                removed += 1;
+            }
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        removed
+    }
+
     // ── Use case 1: Transaction counter ───────────────────────────────────
     //
     // A transaction counter is a classic instance-storage candidate:
     //   • It's per-instance state (each deployed address has its own count).
     //   • It changes on every call — cheap per-instance rent beats per-key rent.
-    //   • It does NOT need to survive a contract upgrade; if the contract is
-    //     replaced we're happy to start the counter fresh.
+    //   • It is a single small value, so keeping it in the always-loaded
+    //     instance entry costs almost nothing.
 
     /// Increments the invocation counter and returns the new value.
     ///
@@ -129,7 +333,7 @@ impl InstanceStorageContract {
             .instance()
             .set(&InstanceKey::TxCounter, &count);
 
-        // Shared TTL refresh — covers TxCounter AND all Config(…) keys.
+        // Shared TTL refresh — covers TxCounter AND all other instance keys.
         env.storage()
             .instance()
             .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
@@ -154,26 +358,185 @@ impl InstanceStorageContract {
     // cooldown periods) that:
     //   • Change moderately often (not every call, but not never).
     //   • Are shared across all invocations of this instance.
-    //   • Can be reset on upgrade if the operator decides to re-configure.
+    //   • Are few and small, so they fit comfortably in the instance entry.
     //
     // Instance storage is ideal here:
-    //   • Cheaper than persistent for data that doesn't need upgrade durability.
+    //   • Cheaper than persistent for a handful of contract-wide values.
     //   • Simpler TTL management than per-key persistent rent.
 
-    /// Persists a named runtime configuration value.
+    /// Schedules a named runtime configuration value and returns the
+    /// timestamp at which it becomes active.
+    ///
+    /// The first value for a key applies immediately — there is no old value
+    /// anyone relies on. Later changes wait `CONFIG_ACTIVATION_DELAY` seconds
+    /// and replace any change still pending for the same key.
+    ///
+    /// Admin only. Fails with `OutOfBounds` if `value` lies outside the
+    /// key's bounds.
+    ///
+    /// Example: `set_config(env, symbol_short!("fee_bps"), 30)` schedules 30 bps.
     ///
-    /// Example: `set_config(env, symbol_short!("fee_bps"), 30)` stores 30 bps.
-    pub fn set_config(env: Env, key: Symbol, value: u64) {
-        // Reuse the generic helper — both use cases share the same TTL refresh.
-        Self::set_instance(env, key, value);
+    /// Emits `(cfg_upd, key)` with `(old, new)`, where `old` is the value
+    /// active before the call (`None` for a new key). A delayed change also
+    /// emits `(cfg_sched, key)` with `(value, activates_at)`.
+    pub fn set_config(env: Env, key: Symbol, value: u64) -> Result<u64, ConfigError> {
+        Self::require_admin(&env)?;
+        if let Some(bounds) = Self::get_bounds(env.clone(), key.clone()) {
+            if value < bounds.min || value > bounds.max {
+                return Err(ConfigError::OutOfBounds);
+            }
+        }
+
+        Self::settle_config(&env, &key);
+        let now = env.ledger().timestamp();
+        let old = Self::read_config(&env, &key);
+        env.events()
+            .publish((symbol_short!("cfg_upd"), key.clone()), (old, value));
+
+        if old.is_none() {
+            Self::write_config(&env, &key, value);
+            return Ok(now);
+        }
+
+        let activates_at = now + CONFIG_ACTIVATION_DELAY;
+        env.storage().instance().set(
+            &InstanceKey::PendingConfig(key.clone()),
+            &PendingConfig {
+                value,
+                activates_at,
+            },
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        env.events()
+            .publish((symbol_short!("cfg_sched"), key), (value, activates_at));
+        Ok(activates_at)
     }
 
-    /// Retrieves a named runtime configuration value.
+    /// Retrieves the currently active value of a named runtime configuration.
     ///
-    /// Returns `None` when the key has never been set, so callers can fall back
-    /// to compile-time defaults without panicking.
+    /// A pending change is returned only once its activation time has been
+    /// reached. Returns `None` when the key has never been set, so callers can
+    /// fall back to compile-time defaults without panicking.
     pub fn get_config(env: Env, key: Symbol) -> Option<u64> {
-        Self::get_instance(env, key)
+        match Self::get_pending_config(env.clone(), key.clone()) {
+            Some(pending) if env.ledger().timestamp() >= pending.activates_at => {
+                Some(pending.value)
+            }
+            _ => {
+                env.storage()
+                    .instance()
+                    .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+                Self::read_config(&env, &key)
+            }
+        }
+    }
+
+    /// Returns the change scheduled for `key`, if it has not yet taken effect.
+    pub fn get_pending_config(env: Env, key: Symbol) -> Option<PendingConfig> {
+        env.storage()
+            .instance()
+            .get(&InstanceKey::PendingConfig(key))
+    }
+
+    /// Withdraws the change scheduled for `key` before it activates.
+    ///
+    /// Admin only. Returns `false` when nothing is pending or the change is
+    /// already active.
+    ///
+    /// Emits `(cfg_cncl, key)` with the cancelled value.
+    pub fn cancel_pending(env: Env, key: Symbol) -> Result<bool, ConfigError> {
+        Self::require_admin(&env)?;
+        Self::settle_config(&env, &key);
+        let storage_key = InstanceKey::PendingConfig(key.clone());
+        let Some(pending) = env
+            .storage()
+            .instance()
+            .get::<_, PendingConfig>(&storage_key)
+        else {
+            return Ok(false);
+        };
+        env.storage().instance().remove(&storage_key);
+        env.events()
+            .publish((symbol_short!("cfg_cncl"), key), pending.value);
+        Ok(true)
+    }
+
+    /// Registers the inclusive range future `set_config` values for `key` must
+    /// fall in. Admin only.
+    ///
+    /// Values already stored or pending are not re-checked.
+    ///
+    /// Emits `(cfg_bnds, key)` with `(min, max)`.
+    pub fn set_bounds(env: Env, key: Symbol, min: u64, max: u64) -> Result<(), ConfigError> {
+        Self::require_admin(&env)?;
+        if min > max {
+            return Err(ConfigError::InvalidBounds);
+        }
+
+        env.storage()
+            .instance()
+            .set(&InstanceKey::Bounds(key.clone()), &Bounds { min, max });
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        env.events()
+            .publish((symbol_short!("cfg_bnds"), key), (min, max));
+        Ok(())
+    }
+
+    /// Returns the bounds registered for `key`, or `None` if it is unbounded.
+    pub fn get_bounds(env: Env, key: Symbol) -> Option<Bounds> {
+        env.storage().instance().get(&InstanceKey::Bounds(key))
+    }
+
+    // ── Typed configuration ────────────────────────────────────────────────
+    //
+    // Not every parameter is a number: a treasury address, a pause switch or
+    // a quote asset symbol belong in config too. `ConfigValue` carries the
+    // value together with its type, so one key namespace can hold them all.
+
+    /// Stores `value` under `TypedConfig(key)`. Admin only.
+    ///
+    /// Fails with `TypeMismatch` if the key already holds a value of another
+    /// type: callers reading it expect the type it was created with.
+    pub fn set_config_typed(env: Env, key: Symbol, value: ConfigValue) -> Result<(), ConfigError> {
+        Self::require_admin(&env)?;
+        let storage_key = InstanceKey::TypedConfig(key);
+        if let Some(current) = env.storage().instance().get::<_, ConfigValue>(&storage_key) {
+            if current.config_type() != value.config_type() {
+                return Err(ConfigError::TypeMismatch);
+            }
+        }
+        env.storage().instance().set(&storage_key, &value);
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
+    /// Returns the value under `TypedConfig(key)`, or `None` if not set.
+    ///
+    /// Fails with `TypeMismatch` if the stored value is not of type `expected`.
+    pub fn get_config_typed(
+        env: Env,
+        key: Symbol,
+        expected: ConfigType,
+    ) -> Result<Option<ConfigValue>, ConfigError> {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        match env
+            .storage()
+            .instance()
+            .get::<_, ConfigValue>(&InstanceKey::TypedConfig(key))
+        {
+            Some(value) if value.config_type() != expected => Err(ConfigError::TypeMismatch),
+            value => Ok(value),
+        }
     }
 
     // ── TTL management ─────────────────────────────────────────────────────
@@ -192,6 +555,53 @@ impl InstanceStorageContract {
             .instance()
             .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
     }
+
+    // ── Internal helpers ───────────────────────────────────────────────────
+
+    /// Requires the stored admin's authorization.
+    fn require_admin(env: &Env) -> Result<(), ConfigError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&InstanceKey::Admin)
+            .ok_or(ConfigError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Moves a pending change whose activation time has passed into `Config`,
+    /// so writers always start from the value readers currently see.
+    fn settle_config(env: &Env, key: &Symbol) {
+        let storage_key = InstanceKey::PendingConfig(key.clone());
+        if let Some(pending) = env
+            .storage()
+            .instance()
+            .get::<_, PendingConfig>(&storage_key)
+        {
+            if env.ledger().timestamp() >= pending.activates_at {
+                env.storage().instance().remove(&storage_key);
+                Self::write_config(env, key, pending.value);
+            }
+        }
+    }
+
+    /// The active `Config(key)` value, ignoring any pending change.
+    fn read_config(env: &Env, key: &Symbol) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&InstanceKey::Config(key.clone()))
+    }
+
+    /// The single write path for `Config(key)`; callers have already
+    /// checked the admin and the bounds.
+    fn write_config(env: &Env, key: &Symbol, value: u64) {
+        env.storage()
+            .instance()
+            .set(&InstanceKey::Config(key.clone()), &value);
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
 }
 
 mod test;
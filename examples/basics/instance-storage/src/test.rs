@@ -5,8 +5,34 @@
 
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
-use soroban_sdk::{symbol_short, Env};
+use soroban_sdk::{
+    map, symbol_short,
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger, MockAuth,
+        MockAuthInvoke,
+    },
+    vec, Address, Env, IntoVal,
+};
+
+/// Moves the ledger clock past `CONFIG_ACTIVATION_DELAY`.
+fn pass_activation_delay(env: &Env) {
+    env.ledger()
+        .with_mut(|l| l.timestamp += CONFIG_ACTIVATION_DELAY);
+}
+
+/// Registers the contract and initializes it with a generated admin whose
+/// auth is mocked.
+fn setup_with_admin() -> (Env, InstanceStorageContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+    client.initialize(&Address::generate(&env));
+    (env, client)
+}
 
 // ── Generic set_instance / get_instance ───────────────────────────────────
 
@@ -51,6 +77,108 @@ fn test_different_keys_independent() {
     assert_eq!(client.get_instance(&k2), Some(20));
 }
 
+// ── Batch operations ──────────────────────────────────────────────────────
+
+fn keys(env: &Env, n: u32) -> Vec<Symbol> {
+    let mut keys = Vec::new(env);
+    for i in 0..n {
+        keys.push_back(Symbol::new(env, &std::format!("key{i}")));
+    }
+    keys
+}
+
+#[test]
+fn test_set_many_and_get_many() {
+    let env = Env::default();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+
+    let (a, b, c) = (symbol_short!("a"), symbol_short!("b"), symbol_short!("c"));
+    client.set_many(&vec![&env, (a.clone(), 1), (b.clone(), 2), (a.clone(), 3)]);
+
+    // A repeated key keeps its last value; an unset key is left out.
+    assert_eq!(
+        client.get_many(&vec![&env, a.clone(), b.clone(), c]),
+        map![&env, (a.clone(), 3), (b.clone(), 2)]
+    );
+    // Batch writes are the same raw values `set_instance` writes, and
+    // never config.
+    assert_eq!(client.get_instance(&a), Some(3));
+    assert_eq!(client.get_config(&b), None);
+}
+
+#[test]
+fn test_remove_many() {
+    let env = Env::default();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+
+    client.set_many(&vec![
+        &env,
+        (symbol_short!("a"), 1),
+        (symbol_short!("b"), 2),
+        (symbol_short!("c"), 3),
+    ]);
+    let removed = client.remove_many(&vec![
+        &env,
+        symbol_short!("a"),
+        symbol_short!("c"),
+        symbol_short!("zzz"),
+    ]);
+    assert_eq!(removed, 2, "missing keys are not counted");
+    assert_eq!(
+        client.get_many(&vec![
+            &env,
+            symbol_short!("a"),
+            symbol_short!("b"),
+            symbol_short!("c")
+        ]),
+        map![&env, (symbol_short!("b"), 2)]
+    );
+}
+
+#[test]
+fn test_empty_batches() {
+    let env = Env::default();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+
+    client.set_many(&Vec::new(&env));
+    assert_eq!(client.get_many(&Vec::new(&env)), Map::new(&env));
+    assert_eq!(client.remove_many(&Vec::new(&env)), 0);
+}
+
+/// CPU instructions to write `n` keys, either with `n` `set_instance`
+/// invocations or with one `set_many` invocation.
+fn write_cost(n: u32, batch: bool) -> u64 {
+    let env = Env::default();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+    let keys = keys(&env, n);
+
+    env.budget().reset_default();
+    if batch {
+        let mut entries = Vec::new(&env);
+        for (i, key) in keys.iter().enumerate() {
+            entries.push_back((key, i as u64));
+        }
+        client.set_many(&entries);
+    } else {
+        for (i, key) in keys.iter().enumerate() {
+            client.set_instance(&key, &(i as u64));
+        }
+    }
+    env.budget().cpu_instruction_cost()
+}
+
+#[test]
+fn test_bench_batch_vs_single_calls() {
+    let single_cpu = write_cost(10, false);
+    let batch_cpu = write_cost(10, true);
+    // The per-invocation overhead dominates a single small write.
+    assert!(batch_cpu * 2 < single_cpu, "{batch_cpu} vs {single_cpu}");
+}
+
 // ── Use case 1: Transaction counter ───────────────────────────────────────
 
 #[test]
@@ -102,9 +230,7 @@ fn test_config_unset_returns_none() {
 
 #[test]
 fn test_config_set_and_get() {
-    let env = Env::default();
-    let id = env.register_contract(None, InstanceStorageContract);
-    let client = InstanceStorageContractClient::new(&env, &id);
+    let (_env, client) = setup_with_admin();
 
     let fee_key = symbol_short!("fee_bps");
     let limit_key = symbol_short!("max_amt");
@@ -118,13 +244,18 @@ fn test_config_set_and_get() {
 
 #[test]
 fn test_config_update() {
-    let env = Env::default();
-    let id = env.register_contract(None, InstanceStorageContract);
-    let client = InstanceStorageContractClient::new(&env, &id);
+    let (env, client) = setup_with_admin();
 
     let key = symbol_short!("fee_bps");
     client.set_config(&key, &30);
     client.set_config(&key, &50); // operator raises the fee
+    assert_eq!(
+        client.get_config(&key),
+        Some(30),
+        "change is only scheduled"
+    );
+
+    pass_activation_delay(&env);
     assert_eq!(client.get_config(&key), Some(50));
 }
 
@@ -133,9 +264,7 @@ fn test_config_update() {
 #[test]
 fn test_extend_ttl_does_not_corrupt_data() {
     // Ensure that calling extend_ttl doesn't clear or corrupt stored data.
-    let env = Env::default();
-    let id = env.register_contract(None, InstanceStorageContract);
-    let client = InstanceStorageContractClient::new(&env, &id);
+    let (_env, client) = setup_with_admin();
 
     client.increment_counter();
     client.set_config(&symbol_short!("fee_bps"), &25);
@@ -150,9 +279,7 @@ fn test_extend_ttl_does_not_corrupt_data() {
 
 #[test]
 fn test_counter_and_config_coexist() {
-    let env = Env::default();
-    let id = env.register_contract(None, InstanceStorageContract);
-    let client = InstanceStorageContractClient::new(&env, &id);
+    let (env, client) = setup_with_admin();
 
     // Interleave counter increments and config updates.
     client.increment_counter();
@@ -160,8 +287,457 @@ fn test_counter_and_config_coexist() {
     client.increment_counter();
     client.set_config(&symbol_short!("rate"), &10);
     client.increment_counter();
+    pass_activation_delay(&env);
 
     // Neither should interfere with the other.
     assert_eq!(client.get_counter(), 3);
     assert_eq!(client.get_config(&symbol_short!("rate")), Some(10));
 }
+
+// ── Delayed config activation ─────────────────────────────────────────────
+
+#[test]
+fn test_config_change_activates_after_delay() {
+    let (env, client) = setup_with_admin();
+
+    let key = symbol_short!("fee_bps");
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    assert_eq!(
+        client.set_config(&key, &30),
+        1_000,
+        "first value is immediate"
+    );
+
+    let activates_at = client.set_config(&key, &50);
+    assert_eq!(activates_at, 1_000 + CONFIG_ACTIVATION_DELAY);
+    assert_eq!(
+        client.get_pending_config(&key),
+        Some(PendingConfig {
+            value: 50,
+            activates_at
+        })
+    );
+
+    env.ledger().with_mut(|l| l.timestamp = activates_at - 1);
+    assert_eq!(client.get_config(&key), Some(30));
+    env.ledger().with_mut(|l| l.timestamp = activates_at);
+    assert_eq!(client.get_config(&key), Some(50));
+}
+
+#[test]
+fn test_schedule_emits_event() {
+    let (env, client) = setup_with_admin();
+
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    let activates_at = client.set_config(&key, &50);
+
+    let (_id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("cfg_sched"), key).into_val(&env));
+    let (value, at): (u64, u64) = data.into_val(&env);
+    assert_eq!((value, at), (50, activates_at));
+}
+
+#[test]
+fn test_cancel_pending_keeps_active_value() {
+    let (env, client) = setup_with_admin();
+
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    client.set_config(&key, &50);
+
+    assert!(client.cancel_pending(&key));
+    assert_eq!(client.get_pending_config(&key), None);
+    assert!(!client.cancel_pending(&key), "nothing left to cancel");
+
+    pass_activation_delay(&env);
+    assert_eq!(client.get_config(&key), Some(30));
+}
+
+#[test]
+fn test_cannot_cancel_active_change() {
+    let (env, client) = setup_with_admin();
+
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    client.set_config(&key, &50);
+    pass_activation_delay(&env);
+
+    assert!(!client.cancel_pending(&key));
+    assert_eq!(client.get_config(&key), Some(50));
+}
+
+#[test]
+fn test_reschedule_replaces_pending_change() {
+    let (env, client) = setup_with_admin();
+
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    client.set_config(&key, &50);
+    client.set_config(&key, &40);
+
+    pass_activation_delay(&env);
+    assert_eq!(client.get_config(&key), Some(40));
+
+    // A change made after the previous one activated builds on it.
+    client.set_config(&key, &20);
+    assert_eq!(client.get_config(&key), Some(40));
+    pass_activation_delay(&env);
+    assert_eq!(client.get_config(&key), Some(20));
+}
+
+/// While a change is pending, `get_config` returns the active value; no
+/// other call can replace that value ahead of the delay.
+#[test]
+fn test_pending_change_cannot_be_bypassed() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    client.set_config(&key, &50);
+
+    client.set_instance(&key, &99);
+    client.set_many(&vec![&env, (key.clone(), 99)]);
+    assert_eq!(client.get_config(&key), Some(30));
+
+    pass_activation_delay(&env);
+    assert_eq!(client.get_config(&key), Some(50));
+}
+
+// ── Parameter bounds ──────────────────────────────────────────────────────
+
+#[test]
+fn test_set_config_enforces_bounds() {
+    let (_env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    client.set_bounds(&key, &0, &10_000);
+
+    assert_eq!(
+        client.try_set_config(&key, &100_000),
+        Err(Ok(ConfigError::OutOfBounds))
+    );
+    assert_eq!(client.get_config(&key), None);
+
+    // Both ends of the range are accepted.
+    client.set_config(&key, &0);
+    client.set_config(&key, &10_000);
+    assert_eq!(
+        client.get_pending_config(&key).map(|p| p.value),
+        Some(10_000)
+    );
+}
+
+#[test]
+fn test_out_of_bounds_does_not_replace_pending_change() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    client.set_bounds(&key, &10, &500);
+    client.set_config(&key, &30);
+    client.set_config(&key, &50);
+
+    assert_eq!(
+        client.try_set_config(&key, &5),
+        Err(Ok(ConfigError::OutOfBounds))
+    );
+    pass_activation_delay(&env);
+    assert_eq!(client.get_config(&key), Some(50));
+}
+
+/// Bounds hold on every path that could write a config value.
+#[test]
+fn test_bounds_cannot_be_bypassed() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    client.set_bounds(&key, &0, &10_000);
+
+    client.set_instance(&key, &100_000);
+    client.set_many(&vec![&env, (key.clone(), 100_000)]);
+    assert_eq!(client.get_config(&key), None);
+    assert_eq!(
+        client.try_set_config(&key, &100_000),
+        Err(Ok(ConfigError::OutOfBounds))
+    );
+}
+
+#[test]
+fn test_unbounded_keys_accept_any_value() {
+    let (_env, client) = setup_with_admin();
+    client.set_bounds(&symbol_short!("fee_bps"), &0, &10_000);
+    client.set_config(&symbol_short!("max_amt"), &u64::MAX);
+    assert_eq!(client.get_bounds(&symbol_short!("max_amt")), None);
+}
+
+#[test]
+fn test_set_bounds_validation_and_event() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    assert_eq!(
+        client.try_set_bounds(&key, &100, &10),
+        Err(Ok(ConfigError::InvalidBounds))
+    );
+
+    client.set_bounds(&key, &1, &300);
+    assert_eq!(client.get_bounds(&key), Some(Bounds { min: 1, max: 300 }));
+    let (_id, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("cfg_bnds"), key).into_val(&env));
+    let range: (u64, u64) = data.into_val(&env);
+    assert_eq!(range, (1, 300));
+}
+
+#[test]
+fn test_set_bounds_requires_initialize() {
+    let env = Env::default();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+    assert_eq!(
+        client.try_set_bounds(&symbol_short!("fee_bps"), &0, &1),
+        Err(Ok(ConfigError::NotInitialized))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_bounds_requires_admin_auth() {
+    let (env, client) = setup_with_admin();
+    env.set_auths(&[]);
+    client.set_bounds(&symbol_short!("fee_bps"), &0, &1);
+}
+
+// ── Typed configuration ───────────────────────────────────────────────────
+
+/// One value of every `ConfigValue` variant.
+fn typed_values(env: &Env) -> std::vec::Vec<ConfigValue> {
+    std::vec![
+        ConfigValue::U64(u64::MAX),
+        ConfigValue::I128(-1_000_000),
+        ConfigValue::Bool(true),
+        ConfigValue::Addr(Address::generate(env)),
+        ConfigValue::Sym(symbol_short!("USDC")),
+    ]
+}
+
+#[test]
+fn test_typed_config_round_trips_every_variant() {
+    let (env, client) = setup_with_admin();
+
+    let values = typed_values(&env);
+    let keys = keys(&env, values.len() as u32);
+    for (key, value) in keys.iter().zip(&values) {
+        assert_eq!(client.get_config_typed(&key, &value.config_type()), None);
+        client.set_config_typed(&key, value);
+    }
+    for (key, value) in keys.iter().zip(&values) {
+        assert_eq!(
+            client.get_config_typed(&key, &value.config_type()),
+            Some(value.clone())
+        );
+    }
+}
+
+#[test]
+fn test_typed_config_overwrite_same_type() {
+    let (_env, client) = setup_with_admin();
+
+    let key = symbol_short!("paused");
+    client.set_config_typed(&key, &ConfigValue::Bool(true));
+    client.set_config_typed(&key, &ConfigValue::Bool(false));
+    assert_eq!(
+        client.get_config_typed(&key, &ConfigType::Bool),
+        Some(ConfigValue::Bool(false))
+    );
+}
+
+#[test]
+fn test_typed_config_type_mismatch() {
+    let (env, client) = setup_with_admin();
+    let id = client.address.clone();
+
+    let values = typed_values(&env);
+    let key = symbol_short!("param");
+    for stored in &values {
+        client.set_config_typed(&key, stored);
+        for other in values
+            .iter()
+            .filter(|v| v.config_type() != stored.config_type())
+        {
+            assert_eq!(
+                client.try_get_config_typed(&key, &other.config_type()),
+                Err(Ok(ConfigError::TypeMismatch))
+            );
+            assert_eq!(
+                client.try_set_config_typed(&key, other),
+                Err(Ok(ConfigError::TypeMismatch))
+            );
+        }
+        // Rejected writes left the value alone.
+        assert_eq!(
+            client.get_config_typed(&key, &stored.config_type()),
+            Some(stored.clone())
+        );
+        env.as_contract(&id, || {
+            env.storage()
+                .instance()
+                .remove(&InstanceKey::TypedConfig(key.clone()))
+        });
+    }
+}
+
+#[test]
+fn test_typed_config_separate_from_u64_config() {
+    let (_env, client) = setup_with_admin();
+
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    assert_eq!(client.get_config_typed(&key, &ConfigType::U64), None);
+    client.set_config_typed(&key, &ConfigValue::Sym(symbol_short!("x")));
+    assert_eq!(client.get_config(&key), Some(30));
+}
+
+// ── Admin-gated config ────────────────────────────────────────────────────
+
+#[test]
+fn test_set_config_requires_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+    let key = symbol_short!("fee_bps");
+    assert_eq!(
+        client.try_set_config(&key, &30),
+        Err(Ok(ConfigError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_cancel_pending(&key),
+        Err(Ok(ConfigError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_set_config_authorized_by_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin,
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    id,
+                    Symbol::new(&env, "set_config"),
+                    (key, 30u64).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_set_config_rejects_other_signer() {
+    let (env, client) = setup_with_admin();
+    let attacker = Address::generate(&env);
+    let key = symbol_short!("fee_bps");
+    env.mock_auths(&[MockAuth {
+        address: &attacker,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "set_config",
+            args: (key.clone(), 10_000u64).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_set_config(&key, &10_000).is_err());
+    assert_eq!(client.get_config(&key), None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_config_requires_admin_auth() {
+    let (env, client) = setup_with_admin();
+    env.set_auths(&[]);
+    client.set_config(&symbol_short!("fee_bps"), &30);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_cancel_pending_requires_admin_auth() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    client.set_config(&key, &30);
+    client.set_config(&key, &50);
+    env.set_auths(&[]);
+    client.cancel_pending(&key);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_set_config_typed_requires_admin_auth() {
+    let (env, client) = setup_with_admin();
+    env.set_auths(&[]);
+    client.set_config_typed(&symbol_short!("paused"), &ConfigValue::Bool(true));
+}
+
+#[test]
+fn test_set_config_typed_requires_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register_contract(None, InstanceStorageContract);
+    let client = InstanceStorageContractClient::new(&env, &id);
+    assert_eq!(
+        client.try_set_config_typed(&symbol_short!("paused"), &ConfigValue::Bool(true)),
+        Err(Ok(ConfigError::NotInitialized))
+    );
+}
+
+/// The open raw helpers need no auth, but none of them reaches config:
+/// an unauthorized caller cannot overwrite, add or remove a config value
+/// through them.
+#[test]
+fn test_raw_helpers_cannot_write_config() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    let other = symbol_short!("max_amt");
+    client.set_config(&key, &30);
+    env.set_auths(&[]);
+
+    client.set_instance(&key, &10_000);
+    client.set_many(&vec![&env, (key.clone(), 10_000), (other.clone(), 1)]);
+    assert_eq!(client.get_config(&key), Some(30));
+    assert_eq!(client.get_config(&other), None);
+
+    assert_eq!(client.remove_many(&vec![&env, key.clone()]), 1);
+    assert_eq!(client.get_config(&key), Some(30));
+    assert_eq!(client.get_pending_config(&key), None);
+}
+
+#[test]
+fn test_set_config_emits_update_event() {
+    let (env, client) = setup_with_admin();
+    let key = symbol_short!("fee_bps");
+    // The latest `(cfg_upd, key)` event's `(old, new)` data.
+    let update = |env: &Env| -> (Option<u64>, u64) {
+        let topics = (symbol_short!("cfg_upd"), key.clone()).into_val(env);
+        let (_id, _topics, data) = env
+            .events()
+            .all()
+            .iter()
+            .rfind(|(_, t, _)| *t == topics)
+            .unwrap();
+        data.into_val(env)
+    };
+
+    client.set_config(&key, &30);
+    assert_eq!(update(&env), (None, 30));
+    client.set_config(&key, &50);
+    assert_eq!(update(&env), (Some(30), 50));
+
+    // `old` is the active value, not the one still pending.
+    client.set_config(&key, &40);
+    assert_eq!(update(&env), (Some(30), 40));
+}
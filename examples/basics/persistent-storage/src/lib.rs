@@ -1,21 +1,57 @@
 #![no_std]
 // Removed unused Symbol and symbol_short to clear the warnings
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Vec};
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     Counter,
+    /// Checkpoint taken at this ledger sequence. Each one is its own entry
+    /// with its own TTL.
+    Checkpoint(u32),
+    /// Ledger sequence of the newest checkpoint.
+    LatestCheckpoint,
 }
 
+/// The counter's value at one ledger. Checkpoints form a list from newest
+/// to oldest through `prev`, so no entry has to hold the whole history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub timestamp: u64,
+    pub value: u64,
+    /// Ledger sequence of the previous checkpoint.
+    pub prev: Option<u32>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StorageError {
+    AdminNotSet = 1,
+    /// The caller passed an address other than the stored admin.
+    NotAdmin = 2,
+}
+
+/// Each history entry is extended on its own, when written and when read. The
+/// threshold is above the default TTL of a new entry, so a fresh one is
+/// always extended.
+const CHECKPOINT_TTL_THRESHOLD: u32 = 5_000;
+const CHECKPOINT_TTL_EXTEND_TO: u32 = 20_000;
+
 #[contract]
 pub struct PersistentStorageContract;
 
 #[contractimpl]
 impl PersistentStorageContract {
+    /// Sets the admin. Replacing an existing admin needs their auth.
     pub fn set_admin(env: Env, address: Address) {
         let key = DataKey::Admin;
+        if let Some(current) = env.storage().persistent().get::<_, Address>(&key) {
+            current.require_auth();
+        }
         env.storage().persistent().set(&key, &address);
         env.storage().persistent().extend_ttl(&key, 2000, 10000);
     }
@@ -44,6 +80,86 @@ impl PersistentStorageContract {
             .get(&DataKey::Counter)
             .unwrap_or(0)
     }
+
+    /// Records the current counter value under `Checkpoint(sequence)`.
+    /// A second snapshot in the same ledger replaces the first.
+    pub fn snapshot_counter(env: Env) -> Checkpoint {
+        let ledger = env.ledger().sequence();
+        let latest: Option<u32> = env.storage().persistent().get(&DataKey::LatestCheckpoint);
+        let prev = match latest {
+            // Same key: keep pointing at the checkpoint before it.
+            Some(seq) if seq == ledger => Self::checkpoint(&env, seq).prev,
+            other => other,
+        };
+        let checkpoint = Checkpoint {
+            ledger,
+            timestamp: env.ledger().timestamp(),
+            value: Self::get_counter(env.clone()),
+            prev,
+        };
+
+        let key = DataKey::Checkpoint(ledger);
+        env.storage().persistent().set(&key, &checkpoint);
+        Self::extend_history(&env, &key);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LatestCheckpoint, &ledger);
+        Self::extend_history(&env, &DataKey::LatestCheckpoint);
+        checkpoint
+    }
+
+    /// Returns up to `limit` checkpoints, newest first.
+    ///
+    /// Reading a checkpoint extends its TTL. One that nobody read for long
+    /// enough is archived, and must be restored before the walk can pass it.
+    pub fn get_history(env: Env, limit: u32) -> Vec<Checkpoint> {
+        let mut history = Vec::new(&env);
+        let mut next: Option<u32> = env.storage().persistent().get(&DataKey::LatestCheckpoint);
+        if next.is_some() {
+            Self::extend_history(&env, &DataKey::LatestCheckpoint);
+        }
+        while let Some(ledger) = next {
+            if history.len() == limit {
+                break;
+            }
+            let checkpoint = Self::checkpoint(&env, ledger);
+            Self::extend_history(&env, &DataKey::Checkpoint(ledger));
+            next = checkpoint.prev;
+            history.push_back(checkpoint);
+        }
+        history
+    }
+
+    /// Sets the counter back to zero. `admin` must be the stored admin and
+    /// authorize the call. Checkpoints are kept, so the history still shows
+    /// the values from before the reset.
+    pub fn reset(env: Env, admin: Address) -> Result<(), StorageError> {
+        let stored = Self::get_admin(env.clone()).ok_or(StorageError::AdminNotSet)?;
+        if admin != stored {
+            return Err(StorageError::NotAdmin);
+        }
+        admin.require_auth();
+
+        let key = DataKey::Counter;
+        env.storage().persistent().set(&key, &0u64);
+        env.storage().persistent().extend_ttl(&key, 2000, 10000);
+        Ok(())
+    }
+
+    fn extend_history(env: &Env, key: &DataKey) {
+        env.storage().persistent().extend_ttl(
+            key,
+            CHECKPOINT_TTL_THRESHOLD,
+            CHECKPOINT_TTL_EXTEND_TO,
+        );
+    }
+
+    fn checkpoint(env: &Env, ledger: u32) -> Checkpoint {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Checkpoint(ledger))
+            .unwrap()
+    }
 }
 
 mod test;
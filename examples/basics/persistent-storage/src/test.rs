@@ -1,7 +1,9 @@
 #![cfg(test)]
+extern crate std;
+
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{storage::Persistent as _, Address as _, Ledger},
     Address, Env,
 };
 
@@ -58,3 +60,149 @@ fn test_storage_durability_and_ttl() {
     // Persistent storage should still be accessible!
     assert_eq!(client.get_counter(), 1);
 }
+
+fn set_sequence(env: &Env, sequence_number: u32) {
+    env.ledger()
+        .with_mut(|li| li.sequence_number = sequence_number);
+}
+
+#[test]
+fn test_snapshot_and_history() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, PersistentStorageContract);
+    let client = PersistentStorageContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.get_history(&10).len(), 0);
+
+    set_sequence(&env, 100);
+    client.increment();
+    let first = client.snapshot_counter();
+    assert_eq!(first.ledger, 100);
+    assert_eq!(first.prev, None);
+
+    set_sequence(&env, 200);
+    client.increment();
+    client.increment();
+    client.snapshot_counter();
+
+    set_sequence(&env, 300);
+    client.increment();
+    client.snapshot_counter();
+
+    let values: std::vec::Vec<(u32, u64)> = client
+        .get_history(&10)
+        .iter()
+        .map(|c| (c.ledger, c.value))
+        .collect();
+    assert_eq!(values, std::vec![(300, 4), (200, 3), (100, 1)]);
+
+    let latest = client.get_history(&2);
+    assert_eq!(latest.len(), 2);
+    assert_eq!(latest.get_unchecked(1).prev, Some(100));
+    assert_eq!(client.get_history(&0).len(), 0);
+}
+
+#[test]
+fn test_snapshot_twice_in_one_ledger_replaces() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, PersistentStorageContract);
+    let client = PersistentStorageContractClient::new(&env, &contract_id);
+
+    set_sequence(&env, 100);
+    client.snapshot_counter();
+    set_sequence(&env, 200);
+    client.snapshot_counter();
+    client.increment();
+    let replaced = client.snapshot_counter();
+    assert_eq!(replaced.value, 1);
+    assert_eq!(replaced.prev, Some(100));
+    assert_eq!(client.get_history(&10).len(), 2);
+}
+
+#[test]
+fn test_checkpoints_have_their_own_ttl() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, PersistentStorageContract);
+    let client = PersistentStorageContractClient::new(&env, &contract_id);
+
+    set_sequence(&env, 100);
+    client.increment();
+    client.snapshot_counter();
+    set_sequence(&env, 600);
+    client.snapshot_counter();
+
+    env.as_contract(&contract_id, || {
+        let ttl = |ledger| {
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Checkpoint(ledger))
+        };
+        // Each checkpoint was extended when it was written, so the older
+        // one has less time left.
+        assert_eq!(ttl(600), 20_000);
+        assert_eq!(ttl(100), 19_500);
+    });
+
+    // Keep the contract itself alive across the jump, as above.
+    env.as_contract(&contract_id, || {
+        env.storage().instance().extend_ttl(20_000, 20_000);
+    });
+
+    // Reading the history extends every checkpoint below the threshold.
+    set_sequence(&env, 16_000);
+    client.get_history(&10);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Checkpoint(100)),
+            20_000
+        );
+    });
+}
+
+#[test]
+fn test_reset_requires_stored_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, PersistentStorageContract);
+    let client = PersistentStorageContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    assert_eq!(client.try_reset(&admin), Err(Ok(StorageError::AdminNotSet)));
+
+    client.set_admin(&admin);
+    client.increment();
+    client.increment();
+    client.snapshot_counter();
+    assert_eq!(client.try_reset(&other), Err(Ok(StorageError::NotAdmin)));
+    assert_eq!(client.get_counter(), 2);
+
+    client.reset(&admin);
+    assert_eq!(client.get_counter(), 0);
+    assert_eq!(client.increment(), 1);
+    // The checkpoint from before the reset is still there.
+    assert_eq!(client.get_history(&1).get_unchecked(0).value, 2);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_reset_requires_admin_auth() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, PersistentStorageContract);
+    let client = PersistentStorageContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    client.reset(&admin);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_replacing_admin_requires_current_admin_auth() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, PersistentStorageContract);
+    let client = PersistentStorageContractClient::new(&env, &contract_id);
+    client.set_admin(&Address::generate(&env));
+    client.set_admin(&Address::generate(&env));
+}
@@ -1,11 +1,27 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Env};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+/// Length of one rate-limit window, in ledgers (~50 seconds).
+pub const WINDOW_LEDGERS: u32 = 10;
+/// Calls each address may make per window.
+pub const MAX_REQUESTS: u32 = 3;
 
 #[contracttype]
 #[derive(Clone)]
 pub enum TempKey {
-    ReentrancyGuard, // Flag to prevent recursive calls
-    InternalResult,  // Store intermediate calculation
+    ReentrancyGuard,            // Flag to prevent recursive calls
+    InternalResult,             // Store intermediate calculation
+    RequestCount(Address, u32), // Calls by an address in one window
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RateLimitError {
+    /// The caller used all `MAX_REQUESTS` calls of the current window.
+    RateLimited = 1,
 }
 
 #[contract]
@@ -16,34 +32,52 @@ impl TemporaryStorageContract {
     /// Demonstrates a Reentrancy Guard using temporary storage.
     /// This is a classic use case: we only need to know if a function
     /// is currently executing within the SAME transaction.
+    /// The body runs inside `with_guard`, the nonreentrant pattern.
     pub fn guarded_function(env: Env) {
-        let key = TempKey::ReentrancyGuard;
-
-        // 1. Check if the flag exists
-        if env.storage().temporary().has(&key) {
-            panic!("Reentrancy forbidden");
-        }
-
-        // 2. Set the flag (cheapest storage write possible)
-        env.storage().temporary().set(&key, &true);
+        with_guard(&env, || {
+            // ... logic of the contract ...
+        })
+    }
 
-        // ... logic of the contract ...
+    /// Demonstrates a guarded external call: `target.callback(self)`.
+    /// While the callback runs, every guarded function here is locked.
+    /// The host already refuses a contract calling back into one that is
+    /// still running; the guard also catches nesting inside the contract,
+    /// which the host cannot see.
+    pub fn guarded_call(env: Env, target: Address) {
+        with_guard(&env, || {
+            let args = vec![&env, env.current_contract_address().into_val(&env)];
+            env.invoke_contract::<()>(&target, &Symbol::new(&env, "callback"), args);
+        })
+    }
 
-        // 3. Remove it (optional, but good practice)
-        env.storage().temporary().remove(&key);
+    /// Demonstrates why the guard must clear on every return path.
+    /// Each value is doubled in its own guarded step; a step that
+    /// overflows returns early and the batch moves on.
+    /// Returns how many values were processed.
+    pub fn process_batch(env: Env, values: Vec<u64>) -> u32 {
+        let mut processed = 0;
+        for value in values.iter() {
+            if process_one(&env, value).is_some() {
+                processed += 1;
+            }
+        }
+        processed
     }
 
     /// Demonstrates storing an intermediate result.
     /// Useful for cross-function state within a single invocation.
     pub fn start_calculation(env: Env, value: u64) {
-        let key = TempKey::InternalResult;
+        with_guard(&env, || {
+            let key = TempKey::InternalResult;
 
-        // Storing a temporary value for a later step in the same ledger
-        env.storage().temporary().set(&key, &value);
+            // Storing a temporary value for a later step in the same ledger
+            env.storage().temporary().set(&key, &value);
 
-        // Even temporary storage has a TTL, but it's very short by default.
-        // We extend it slightly to ensure it lasts the current ledger cycle.
-        env.storage().temporary().extend_ttl(&key, 16, 32);
+            // Even temporary storage has a TTL, but it's very short by default.
+            // We extend it slightly to ensure it lasts the current ledger cycle.
+            env.storage().temporary().extend_ttl(&key, 16, 32);
+        })
     }
 
     pub fn get_temp_value(env: Env) -> u64 {
@@ -52,6 +86,84 @@ impl TemporaryStorageContract {
             .get(&TempKey::InternalResult)
             .unwrap_or(0)
     }
+
+    /// Demonstrates a rate limiter: at most `MAX_REQUESTS` calls per
+    /// address per window of `WINDOW_LEDGERS` ledgers.
+    /// Returns how many calls the caller has left in this window.
+    ///
+    /// The counter is keyed by `(address, window)`, so a new window starts
+    /// from zero without any reset: the old counter is simply never read
+    /// again, and the network deletes it once its TTL runs out. Temporary
+    /// storage is the cheapest place for data that is worthless later.
+    ///
+    /// Windows are fixed, not sliding, so a caller can make up to twice
+    /// `MAX_REQUESTS` calls around a window boundary.
+    pub fn rate_limited_request(env: Env, caller: Address) -> Result<u32, RateLimitError> {
+        caller.require_auth();
+        with_guard(&env, || {
+            // 1. Find the counter for the current window
+            let key = TempKey::RequestCount(caller, current_window(&env));
+            let count: u32 = env.storage().temporary().get(&key).unwrap_or(0);
+
+            // 2. Refuse once the window's budget is used up
+            if count >= MAX_REQUESTS {
+                return Err(RateLimitError::RateLimited);
+            }
+
+            // 3. Count the call. The entry only has to outlive its window.
+            env.storage().temporary().set(&key, &(count + 1));
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, WINDOW_LEDGERS, WINDOW_LEDGERS);
+
+            // ... logic of the contract ...
+
+            Ok(MAX_REQUESTS - count - 1)
+        })
+    }
+
+    /// Calls `caller` has left in the current window.
+    pub fn requests_remaining(env: Env, caller: Address) -> u32 {
+        let key = TempKey::RequestCount(caller, current_window(&env));
+        let count: u32 = env.storage().temporary().get(&key).unwrap_or(0);
+        MAX_REQUESTS - count
+    }
+}
+
+/// Runs `f` with the reentrancy flag set, and clears the flag however
+/// `f` returns, including an early `return` or `?` inside it.
+///
+/// A panic needs no handling: it aborts the invocation, and the host
+/// rolls back every storage write, the flag included.
+fn with_guard<T>(env: &Env, f: impl FnOnce() -> T) -> T {
+    let key = TempKey::ReentrancyGuard;
+
+    // 1. Check if the flag exists
+    if env.storage().temporary().has(&key) {
+        panic!("Reentrancy forbidden");
+    }
+
+    // 2. Set the flag (cheapest storage write possible)
+    env.storage().temporary().set(&key, &true);
+
+    // 3. Run the body, then always remove the flag
+    let result = f();
+    env.storage().temporary().remove(&key);
+    result
+}
+
+fn process_one(env: &Env, value: u64) -> Option<()> {
+    with_guard(env, || {
+        let doubled = value.checked_mul(2)?;
+        env.storage()
+            .temporary()
+            .set(&TempKey::InternalResult, &doubled);
+        Some(())
+    })
+}
+
+fn current_window(env: &Env) -> u32 {
+    env.ledger().sequence() / WINDOW_LEDGERS
 }
 
 mod test;
@@ -0,0 +1,186 @@
+//! # TTL Patterns
+//!
+//! Every ledger entry has a *time to live*: the number of ledgers left
+//! before it stops being live. What happens then depends on the storage type:
+//!
+//! | Storage    | TTL scope           | When the TTL runs out                      |
+//! |------------|---------------------|--------------------------------------------|
+//! | Instance   | The whole instance  | Archived with the contract; restorable     |
+//! | Persistent | Per key             | Archived; unreadable until restored        |
+//! | Temporary  | Per key             | Deleted for good                           |
+//!
+//! Nothing extends a TTL automatically. Contracts call
+//! `extend_ttl(threshold, extend_to)`, which does nothing while the TTL is
+//! above `threshold` and otherwise raises it to `extend_to` ledgers. The gap
+//! between the two is the design decision this example is about:
+//!
+//! - **Lazy bump** (`threshold < extend_to`) – most calls find the TTL above
+//!   the threshold and write nothing; roughly once per `extend_to - threshold`
+//!   ledgers a call pays for the extension. The default for anything touched
+//!   often.
+//! - **Eager bump** (`threshold == extend_to`) – every call on a later ledger
+//!   pays to top the TTL back up to the maximum. Worth it only for entries
+//!   that are rarely touched but must not lapse between touches.
+//!
+//! Entries nobody touches for a long time still need someone to pay their
+//! rent. `keep_alive` is the admin endpoint for that: a scheduled job calls
+//! it with the keys that must survive.
+//!
+//! Temporary entries are the opposite case: their expiry is the feature. A
+//! session here lives for `SESSION_LEDGERS` and is never bumped on read, so
+//! it ends on schedule without any cleanup call.
+//!
+//! Contracts cannot read a TTL; tests can, through the `get_ttl` test
+//! utilities. See `src/test.rs`.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Vec};
+
+// ────────────────────────────────────────────────────────────────────────────
+// TTL constants (in ledgers; ~1 ledger ≈ 5 s on Stellar mainnet)
+// ────────────────────────────────────────────────────────────────────────────
+
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// The instance is extended to a week, at most once a day.
+pub const INSTANCE_EXTEND_TO: u32 = 7 * DAY_IN_LEDGERS;
+pub const INSTANCE_THRESHOLD: u32 = INSTANCE_EXTEND_TO - DAY_IN_LEDGERS;
+
+/// Records are extended to 30 days; a lazy bump writes at most once a week.
+pub const RECORD_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+pub const RECORD_THRESHOLD: u32 = RECORD_EXTEND_TO - 7 * DAY_IN_LEDGERS;
+
+/// A session lasts about an hour and is never extended.
+pub const SESSION_LEDGERS: u32 = 720;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Instance.
+    Admin,
+    /// Persistent, one per user.
+    Record(Address),
+    /// Temporary, one per user.
+    Session(Address),
+}
+
+/// How a read or write extends the entry it touches.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Bump {
+    /// Extend only once the TTL is down to `RECORD_THRESHOLD`.
+    Lazy,
+    /// Extend back to `RECORD_EXTEND_TO` on every call.
+    Eager,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TtlError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+}
+
+#[contract]
+pub struct TtlPatterns;
+
+#[contractimpl]
+impl TtlPatterns {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), TtlError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(TtlError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        bump_instance(&env);
+        Ok(())
+    }
+
+    // ── Persistent: lazy vs eager ──────────────────────────────────────────
+
+    pub fn write(env: Env, user: Address, value: u64, bump: Bump) {
+        user.require_auth();
+        let key = DataKey::Record(user);
+        env.storage().persistent().set(&key, &value);
+        bump_record(&env, &key, bump);
+        bump_instance(&env);
+    }
+
+    /// Reads extend too: an entry that is read often but written rarely
+    /// would otherwise lapse while still in use.
+    pub fn read(env: Env, user: Address, bump: Bump) -> Option<u64> {
+        let key = DataKey::Record(user);
+        let value = env.storage().persistent().get(&key);
+        if value.is_some() {
+            bump_record(&env, &key, bump);
+        }
+        bump_instance(&env);
+        value
+    }
+
+    // ── Temporary: expiry as a feature ─────────────────────────────────────
+
+    /// Start (or restart) a session that ends `SESSION_LEDGERS` from now.
+    pub fn start_session(env: Env, user: Address) {
+        user.require_auth();
+        let key = DataKey::Session(user);
+        env.storage()
+            .temporary()
+            .set(&key, &env.ledger().sequence());
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, SESSION_LEDGERS, SESSION_LEDGERS);
+        bump_instance(&env);
+    }
+
+    /// Whether `user` has a live session. Deliberately does not extend it.
+    pub fn has_session(env: Env, user: Address) -> bool {
+        env.storage().temporary().has(&DataKey::Session(user))
+    }
+
+    // ── Keep-alive ─────────────────────────────────────────────────────────
+
+    /// Extend the instance, the contract code and the records of `users` to
+    /// the network maximum. Users without a record are skipped. Returns the
+    /// number of records extended.
+    pub fn keep_alive(env: Env, users: Vec<Address>) -> Result<u32, TtlError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TtlError::NotInitialized)?;
+        admin.require_auth();
+
+        let max = env.storage().max_ttl();
+        // Extends the contract code along with the instance.
+        env.storage().instance().extend_ttl(max, max);
+        let mut extended = 0;
+        for user in users.iter() {
+            let key = DataKey::Record(user);
+            if env.storage().persistent().has(&key) {
+                env.storage().persistent().extend_ttl(&key, max, max);
+                extended += 1;
+            }
+        }
+        Ok(extended)
+    }
+}
+
+fn bump_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_THRESHOLD, INSTANCE_EXTEND_TO);
+}
+
+fn bump_record(env: &Env, key: &DataKey, bump: Bump) {
+    let threshold = match bump {
+        Bump::Lazy => RECORD_THRESHOLD,
+        Bump::Eager => RECORD_EXTEND_TO,
+    };
+    env.storage()
+        .persistent()
+        .extend_ttl(key, threshold, RECORD_EXTEND_TO);
+}
+
+mod test;
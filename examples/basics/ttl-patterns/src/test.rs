@@ -0,0 +1,195 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{
+        storage::{Instance as _, Persistent as _, Temporary as _},
+        Address as _, Ledger,
+    },
+    vec,
+};
+
+struct Setup {
+    env: Env,
+    client: TtlPatternsClient<'static>,
+    alice: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = TtlPatternsClient::new(&env, &env.register_contract(None, TtlPatterns));
+    client.initialize(&Address::generate(&env));
+    Setup {
+        alice: Address::generate(&env),
+        env,
+        client,
+    }
+}
+
+impl Setup {
+    /// Move the ledger forward with `set_sequence_number`. TTLs count
+    /// ledgers, so this is all it takes to age every entry.
+    fn advance(&self, ledgers: u32) {
+        let now = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(now + ledgers);
+    }
+
+    fn instance_ttl(&self) -> u32 {
+        self.env.as_contract(&self.client.address, || {
+            self.env.storage().instance().get_ttl()
+        })
+    }
+
+    fn record_ttl(&self, user: &Address) -> u32 {
+        let key = DataKey::Record(user.clone());
+        self.env.as_contract(&self.client.address, || {
+            self.env.storage().persistent().get_ttl(&key)
+        })
+    }
+
+    fn session_ttl(&self, user: &Address) -> u32 {
+        let key = DataKey::Session(user.clone());
+        self.env.as_contract(&self.client.address, || {
+            self.env.storage().temporary().get_ttl(&key)
+        })
+    }
+}
+
+// ── instance ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_instance_lazy_bump() {
+    let s = setup();
+    assert_eq!(s.instance_ttl(), INSTANCE_EXTEND_TO);
+
+    // Just under a day later the TTL is still above the threshold.
+    s.advance(DAY_IN_LEDGERS - 1);
+    s.client.read(&s.alice, &Bump::Lazy);
+    assert_eq!(s.instance_ttl(), INSTANCE_THRESHOLD + 1);
+
+    // One more ledger and it is at the threshold: the next call extends it.
+    s.advance(1);
+    s.client.read(&s.alice, &Bump::Lazy);
+    assert_eq!(s.instance_ttl(), INSTANCE_EXTEND_TO);
+}
+
+// ── persistent ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_record_lazy_bump() {
+    let s = setup();
+    s.client.write(&s.alice, &7, &Bump::Lazy);
+    assert_eq!(s.record_ttl(&s.alice), RECORD_EXTEND_TO);
+
+    // Reads within the first week leave the TTL alone.
+    s.advance(7 * DAY_IN_LEDGERS - 1);
+    assert_eq!(s.client.read(&s.alice, &Bump::Lazy), Some(7));
+    assert_eq!(s.record_ttl(&s.alice), RECORD_THRESHOLD + 1);
+
+    s.advance(1);
+    s.client.read(&s.alice, &Bump::Lazy);
+    assert_eq!(s.record_ttl(&s.alice), RECORD_EXTEND_TO);
+}
+
+#[test]
+fn test_record_eager_bump() {
+    let s = setup();
+    s.client.write(&s.alice, &7, &Bump::Eager);
+
+    s.advance(1);
+    s.client.read(&s.alice, &Bump::Lazy);
+    assert_eq!(s.record_ttl(&s.alice), RECORD_EXTEND_TO - 1);
+
+    // Eager tops it up again after a single ledger.
+    s.client.read(&s.alice, &Bump::Eager);
+    assert_eq!(s.record_ttl(&s.alice), RECORD_EXTEND_TO);
+}
+
+#[test]
+fn test_read_missing_record() {
+    let s = setup();
+    // Nothing to extend; must not fail.
+    assert_eq!(s.client.read(&s.alice, &Bump::Eager), None);
+}
+
+#[test]
+fn test_record_live_through_last_ledger() {
+    let s = setup();
+    s.client.write(&s.alice, &7, &Bump::Lazy);
+    // Keep the instance alive, but not the record.
+    s.client.keep_alive(&vec![&s.env]);
+
+    s.advance(RECORD_EXTEND_TO);
+    assert_eq!(s.record_ttl(&s.alice), 0);
+    assert_eq!(s.client.read(&s.alice, &Bump::Lazy), Some(7));
+    // Just in time: the read extended it.
+    assert_eq!(s.record_ttl(&s.alice), RECORD_EXTEND_TO);
+}
+
+/// Past its TTL a persistent entry is archived, not deleted. Reading it does
+/// not return `None`; the host refuses to touch it until it is restored, and
+/// the whole call fails.
+#[test]
+#[should_panic(expected = "Error(Storage, InternalError)")]
+fn test_untouched_record_is_archived() {
+    let s = setup();
+    s.client.write(&s.alice, &7, &Bump::Lazy);
+    s.client.keep_alive(&vec![&s.env]);
+
+    s.advance(RECORD_EXTEND_TO + 1);
+    s.client.read(&s.alice, &Bump::Lazy);
+}
+
+// ── temporary ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_session_expires() {
+    let s = setup();
+    s.client.start_session(&s.alice);
+    assert_eq!(s.session_ttl(&s.alice), SESSION_LEDGERS);
+
+    // Checking the session does not extend it.
+    s.advance(SESSION_LEDGERS - 1);
+    assert!(s.client.has_session(&s.alice));
+    assert_eq!(s.session_ttl(&s.alice), 1);
+
+    // Live through its last ledger, gone after it.
+    s.advance(1);
+    assert!(s.client.has_session(&s.alice));
+    s.advance(1);
+    assert!(!s.client.has_session(&s.alice));
+
+    // An expired temporary entry is simply absent: a new one can be
+    // created under the same key.
+    s.client.start_session(&s.alice);
+    assert!(s.client.has_session(&s.alice));
+    assert_eq!(s.session_ttl(&s.alice), SESSION_LEDGERS);
+}
+
+// ── keep-alive ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_keep_alive_extends_to_max() {
+    let s = setup();
+    let bob = Address::generate(&s.env);
+    s.client.write(&s.alice, &7, &Bump::Lazy);
+
+    let extended = s.client.keep_alive(&vec![&s.env, s.alice.clone(), bob]);
+    assert_eq!(extended, 1, "bob has no record");
+    let max = s.env.storage().max_ttl();
+    assert_eq!(s.instance_ttl(), max);
+    assert_eq!(s.record_ttl(&s.alice), max);
+
+    // Long after a lazily bumped record would have lapsed, it is still live.
+    s.advance(2 * RECORD_EXTEND_TO);
+    assert_eq!(s.client.read(&s.alice, &Bump::Lazy), Some(7));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_keep_alive_requires_admin() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    s.client.keep_alive(&vec![&s.env]);
+}
@@ -0,0 +1,182 @@
+//! # Batch Mint
+//!
+//! A minimal NFT collection whose admin can mint many tokens in one call,
+//! with the batch size capped so a call never runs into the transaction's
+//! resource limits:
+//!
+//! ```text
+//! mint_batch(to, 20)   ids 120..140 → to      one balance write
+//!                                             one `(mint, batch)` event
+//!                                             20 owner entries
+//! ```
+//!
+//! Every minted token costs one persistent `Owner(id)` write and a little
+//! CPU for the key and value encoding. Everything else in the call (auth,
+//! the balance update, the event) is paid once per batch. So the cost grows
+//! linearly with `count`, and `MAX_MINT_BATCH` is chosen from the measured
+//! per-item cost, with a wide margin under the per-transaction limits. The
+//! tests measure that cost and fail if a full batch stops fitting.
+//!
+//! Callers that need more tokens mint in chunks of at most
+//! `MAX_MINT_BATCH`; each call returns the id range it minted, so the next
+//! chunk needs no extra read.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+};
+
+/// Largest `count` accepted by `mint_batch`.
+///
+/// CPU is not what binds. `test_budget_per_item` measures ~15k instructions
+/// per extra token natively (several times that in Wasm), so even hundreds
+/// of tokens fit the 100M instruction limit. Ledger writes bind first: each
+/// token is one written entry, and the call also writes the balance, the
+/// contract instance and the admin's auth nonce. 20 tokens keep the total
+/// under the 25 write entries per transaction Soroban launched with.
+pub const MAX_MINT_BATCH: u32 = 20;
+
+/// Ids `start..end` (end exclusive) minted by one call.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MintRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    MaxSupply,
+    NextId,
+    Owner(u32),
+    Balance(Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NftError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// `count` is zero or above `MAX_MINT_BATCH`.
+    InvalidBatchSize = 3,
+    SupplyExceeded = 4,
+    NotFound = 5,
+    NotOwner = 6,
+}
+
+#[contract]
+pub struct BatchMintNft;
+
+#[contractimpl]
+impl BatchMintNft {
+    pub fn initialize(env: Env, admin: Address, max_supply: u32) -> Result<(), NftError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NftError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxSupply, &max_supply);
+        Ok(())
+    }
+
+    /// Mint a single token to `to`.
+    pub fn mint(env: Env, to: Address) -> Result<u32, NftError> {
+        Ok(Self::mint_batch(env, to, 1)?.start)
+    }
+
+    /// Mint `count` consecutive tokens to `to` and return their id range.
+    ///
+    /// Emits one `(mint, batch)` event with `(to, start, end)`.
+    pub fn mint_batch(env: Env, to: Address, count: u32) -> Result<MintRange, NftError> {
+        Self::require_admin(&env)?;
+        if count == 0 || count > MAX_MINT_BATCH {
+            return Err(NftError::InvalidBatchSize);
+        }
+        let start = Self::total_supply(env.clone());
+        let end = start + count;
+        let max_supply: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxSupply)
+            .ok_or(NftError::NotInitialized)?;
+        if end > max_supply {
+            return Err(NftError::SupplyExceeded);
+        }
+
+        for id in start..end {
+            env.storage().persistent().set(&DataKey::Owner(id), &to);
+        }
+        let balance = Self::balance_of(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to.clone()), &(balance + count));
+        env.storage().instance().set(&DataKey::NextId, &end);
+
+        env.events().publish(
+            (symbol_short!("mint"), symbol_short!("batch")),
+            (to, start, end),
+        );
+        Ok(MintRange { start, end })
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) -> Result<(), NftError> {
+        from.require_auth();
+        if Self::owner_of(env.clone(), token_id)? != from {
+            return Err(NftError::NotOwner);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owner(token_id), &to);
+        let from_balance = Self::balance_of(env.clone(), from.clone());
+        let to_balance = Self::balance_of(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(from.clone()), &(from_balance - 1));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(to.clone()), &(to_balance + 1));
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), token_id);
+        Ok(())
+    }
+
+    // ==================== VIEWS ====================
+
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owner(token_id))
+            .ok_or(NftError::NotFound)
+    }
+
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(owner))
+            .unwrap_or(0)
+    }
+
+    /// Number of tokens minted so far; also the next id.
+    pub fn total_supply(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::NextId).unwrap_or(0)
+    }
+
+    // ==================== HELPERS ====================
+
+    fn require_admin(env: &Env) -> Result<(), NftError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(NftError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+mod test;
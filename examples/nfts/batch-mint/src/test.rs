@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::Address as _;
+
+/// Default per-transaction CPU instruction limit in the test budget.
+const TX_CPU_LIMIT: u64 = 100_000_000;
+
+struct Setup<'a> {
+    env: Env,
+    client: BatchMintNftClient<'a>,
+}
+
+fn setup(max_supply: u32) -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = BatchMintNftClient::new(&env, &env.register_contract(None, BatchMintNft));
+    client.initialize(&Address::generate(&env), &max_supply);
+    Setup { env, client }
+}
+
+/// CPU instructions used by `mint_batch(to, count)` on a fresh contract.
+/// A fresh environment per measurement keeps earlier mints from inflating
+/// the storage the call sees, as it would in a real transaction footprint.
+fn mint_cost(count: u32) -> u64 {
+    let s = setup(1_000);
+    let to = Address::generate(&s.env);
+    s.env.budget().reset_default();
+    s.client.mint_batch(&to, &count);
+    s.env.budget().cpu_instruction_cost()
+}
+
+#[test]
+fn test_mint_batch_returns_range() {
+    let s = setup(100);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client.mint_batch(&alice, &3),
+        MintRange { start: 0, end: 3 }
+    );
+    assert_eq!(
+        s.client.mint_batch(&bob, &2),
+        MintRange { start: 3, end: 5 }
+    );
+    assert_eq!(s.client.mint(&alice), 5);
+
+    assert_eq!(s.client.owner_of(&2), alice);
+    assert_eq!(s.client.owner_of(&4), bob);
+    assert_eq!(s.client.balance_of(&alice), 4);
+    assert_eq!(s.client.total_supply(), 6);
+}
+
+#[test]
+fn test_batch_size_bounds() {
+    let s = setup(100);
+    let to = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_mint_batch(&to, &0),
+        Err(Ok(NftError::InvalidBatchSize))
+    );
+    assert_eq!(
+        s.client.try_mint_batch(&to, &(MAX_MINT_BATCH + 1)),
+        Err(Ok(NftError::InvalidBatchSize))
+    );
+    s.client.mint_batch(&to, &MAX_MINT_BATCH);
+}
+
+#[test]
+fn test_supply_cap() {
+    let s = setup(10);
+    let to = Address::generate(&s.env);
+    s.client.mint_batch(&to, &8);
+    assert_eq!(
+        s.client.try_mint_batch(&to, &3),
+        Err(Ok(NftError::SupplyExceeded))
+    );
+    assert_eq!(s.client.mint_batch(&to, &2).end, 10);
+}
+
+#[test]
+fn test_large_mint_in_chunks() {
+    let s = setup(1_000);
+    let to = Address::generate(&s.env);
+    let wanted = 3 * MAX_MINT_BATCH + 7;
+
+    let mut minted = 0;
+    while minted < wanted {
+        let chunk = (wanted - minted).min(MAX_MINT_BATCH);
+        let range = s.client.mint_batch(&to, &chunk);
+        assert_eq!(range.start, minted);
+        minted = range.end;
+    }
+    assert_eq!(s.client.balance_of(&to), wanted);
+    assert_eq!(s.client.owner_of(&(wanted - 1)), to);
+}
+
+#[test]
+fn test_transfer() {
+    let s = setup(10);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.client.mint_batch(&alice, &2);
+
+    s.client.transfer(&alice, &bob, &1);
+    assert_eq!(s.client.owner_of(&1), bob);
+    assert_eq!(s.client.balance_of(&alice), 1);
+    assert_eq!(
+        s.client.try_transfer(&alice, &bob, &1),
+        Err(Ok(NftError::NotOwner))
+    );
+}
+
+/// Documents how a batch's cost grows: a fixed part per call plus a roughly
+/// constant part per token. `MAX_MINT_BATCH` is derived from the per-token
+/// part. Natively registered contracts skip Wasm execution, so on-chain
+/// numbers are higher; the margins below leave room for that.
+#[test]
+fn test_budget_per_item() {
+    let one = mint_cost(1);
+    let ten = mint_cost(10);
+    // Linear: twenty tokens add about twice what ten tokens add.
+    let twenty = mint_cost(20);
+    assert!(twenty - one <= 3 * (ten - one), "{one} / {ten} / {twenty}");
+
+    // A full batch stays under a hundredth of the transaction CPU limit.
+    let full = mint_cost(MAX_MINT_BATCH);
+    assert!(full < TX_CPU_LIMIT / 100, "full batch used {full} cpu");
+}
@@ -5,7 +5,11 @@
 
 #![cfg(test)]
 
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Bytes, Env, IntoVal, String, Symbol, Vec};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Bytes, Env, IntoVal, String, Symbol, Val, Vec,
+};
 
 /// Test 1: Multi-Contract Workflow - Hello World + Storage + Events
 /// 
@@ -521,3 +525,384 @@ fn test_coordinated_state_management() {
     );
     assert!(has_audit);
 }
+
+/// Test 7: Governed Upgrade - Multisig + Timelock + Upgradeable Contract
+///
+/// Scenario: A 2-of-3 multisig administers a timelock, and the timelock is the
+/// only address allowed to upgrade an application contract. The upgrade is
+/// proposed, approved and queued, then executed after the delay.
+#[test]
+fn test_governed_upgrade_workflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let multisig_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/multi_party_auth.wasm"));
+    let multisig_id = env.register_contract_wasm(None, multisig_wasm);
+
+    let timelock_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/timelock.wasm"));
+    let timelock_id = env.register_contract_wasm(None, timelock_wasm);
+
+    let app_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/governed_upgrade.wasm"));
+    let app_id = env.register_contract_wasm(None, app_wasm);
+
+    // The v2 Wasm only needs to be uploaded; `upgrade` refers to it by hash.
+    let v2_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/governed_upgrade_v2.wasm"));
+    let v2_hash = env.deployer().upload_contract_wasm(v2_wasm);
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let signer3 = Address::generate(&env);
+
+    // Wire the pipeline: multisig -> timelock -> app
+    env.invoke_contract::<()>(
+        &timelock_id,
+        &Symbol::new(&env, "initialize"),
+        Vec::from_array(&env, [multisig_id.into_val(&env)]),
+    );
+    env.invoke_contract::<()>(
+        &app_id,
+        &Symbol::new(&env, "initialize"),
+        Vec::from_array(&env, [timelock_id.into_val(&env)]),
+    );
+
+    let version: u32 = env.invoke_contract(&app_id, &Symbol::new(&env, "version"), Vec::new(&env));
+    assert_eq!(version, 1);
+
+    // Step 1: Propose - register a 2-of-3 proposal and derive the operation id
+    let proposal_id = Symbol::new(&env, "upgrade");
+    env.invoke_contract::<()>(
+        &multisig_id,
+        &Symbol::new(&env, "setup_proposal"),
+        Vec::from_array(&env, [
+            proposal_id.into_val(&env),
+            2u32.into_val(&env),
+            Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]).into_val(&env),
+        ]),
+    );
+
+    let upgrade_fn = Symbol::new(&env, "upgrade");
+    let upgrade_args: Vec<Val> = Vec::from_array(&env, [v2_hash.into_val(&env)]);
+    let operation_id: Bytes = env.invoke_contract(
+        &timelock_id,
+        &Symbol::new(&env, "call_id"),
+        Vec::from_array(&env, [
+            app_id.into_val(&env),
+            upgrade_fn.into_val(&env),
+            upgrade_args.into_val(&env),
+        ]),
+    );
+
+    // Step 2 + 3: Approve and queue - two signers make the multisig queue the call
+    env.invoke_contract::<Val>(
+        &multisig_id,
+        &Symbol::new(&env, "approve_and_call"),
+        Vec::from_array(&env, [
+            proposal_id.into_val(&env),
+            Vec::from_array(&env, [signer1.clone(), signer2.clone()]).into_val(&env),
+            timelock_id.into_val(&env),
+            Symbol::new(&env, "queue").into_val(&env),
+            Vec::<Val>::from_array(&env, [operation_id.into_val(&env), 60u64.into_val(&env)]).into_val(&env),
+        ]),
+    );
+
+    // Step 4: Wait out the delay
+    env.ledger().with_mut(|l| l.timestamp += 60);
+
+    // Step 5: Execute - the timelock calls `upgrade` on the app as itself
+    env.invoke_contract::<Val>(
+        &multisig_id,
+        &Symbol::new(&env, "approve_and_call"),
+        Vec::from_array(&env, [
+            proposal_id.into_val(&env),
+            Vec::from_array(&env, [signer1, signer3]).into_val(&env),
+            timelock_id.into_val(&env),
+            Symbol::new(&env, "execute_call").into_val(&env),
+            Vec::<Val>::from_array(&env, [
+                app_id.into_val(&env),
+                upgrade_fn.into_val(&env),
+                upgrade_args.into_val(&env),
+            ])
+            .into_val(&env),
+        ]),
+    );
+
+    // Verify the new Wasm is active and governance is still wired up
+    let version: u32 = env.invoke_contract(&app_id, &Symbol::new(&env, "version"), Vec::new(&env));
+    assert_eq!(version, 2);
+
+    let timelock: Address = env.invoke_contract(&app_id, &Symbol::new(&env, "timelock"), Vec::new(&env));
+    assert_eq!(timelock, timelock_id);
+}
+
+/// Test 8: Counterfactual Deposits - Factory + Wallet + Token
+///
+/// Scenario: A factory predicts a wallet address for an owner, a payer funds
+/// that address before any contract exists there, then the factory deploys
+/// the wallet and the owner sweeps the early deposit.
+#[test]
+fn test_counterfactual_deposit_workflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // The wallet Wasm only needs to be uploaded; the factory deploys it by hash.
+    let wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/counterfactual_deposits.wasm"));
+    let wallet_hash = env.deployer().upload_contract_wasm(wasm.clone());
+    let factory_id = env.register_contract_wasm(None, wasm);
+    env.invoke_contract::<()>(
+        &factory_id,
+        &Symbol::new(&env, "initialize"),
+        Vec::from_array(&env, [wallet_hash.into_val(&env)]),
+    );
+
+    let token_id = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let token = soroban_sdk::token::Client::new(&env, &token_id);
+
+    let owner = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let salt = soroban_sdk::BytesN::from_array(&env, &[7; 32]);
+
+    // Step 1: Predict - nothing is deployed yet
+    let predicted: Address = env.invoke_contract(
+        &factory_id,
+        &Symbol::new(&env, "predict_address"),
+        Vec::from_array(&env, [owner.into_val(&env), salt.into_val(&env)]),
+    );
+
+    // Step 2: Deposit to the empty address
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&payer, &500);
+    token.transfer(&payer, &predicted, &500);
+
+    // Step 3: Deploy - the wallet lands exactly at the predicted address
+    let deployed: Address = env.invoke_contract(
+        &factory_id,
+        &Symbol::new(&env, "deploy_wallet"),
+        Vec::from_array(&env, [owner.into_val(&env), salt.into_val(&env)]),
+    );
+    assert_eq!(deployed, predicted);
+
+    // Step 4: Sweep the early deposit
+    let dest = Address::generate(&env);
+    let swept: i128 = env.invoke_contract(
+        &deployed,
+        &Symbol::new(&env, "sweep"),
+        Vec::from_array(&env, [token_id.into_val(&env), dest.into_val(&env)]),
+    );
+    assert_eq!(swept, 500);
+    assert_eq!(token.balance(&dest), 500);
+}
+
+/// Test 9: Upgradeable Contract - v1 Wasm upgraded in place to v2
+///
+/// Scenario: v1 accumulates persistent balances and an instance counter. The
+/// admin upgrades to v2, which still sees both, migrates the counter to its
+/// new layout, and keeps serving the same balances.
+#[test]
+fn test_upgradeable_contract_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let v1_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/upgradeable_contract.wasm"));
+    let contract_id = env.register_contract_wasm(None, v1_wasm);
+    let v2_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/upgradeable_contract_v2.wasm"));
+    let v2_hash = env.deployer().upload_contract_wasm(v2_wasm);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.invoke_contract::<()>(
+        &contract_id,
+        &Symbol::new(&env, "initialize"),
+        Vec::from_array(&env, [admin.into_val(&env)]),
+    );
+
+    // Step 1: Use v1 - one persistent balance, instance counter at 2
+    for amount in [100i128, 50] {
+        env.invoke_contract::<i128>(
+            &contract_id,
+            &Symbol::new(&env, "deposit"),
+            Vec::from_array(&env, [user.into_val(&env), amount.into_val(&env)]),
+        );
+    }
+
+    // Step 2: Upgrade
+    env.invoke_contract::<()>(
+        &contract_id,
+        &Symbol::new(&env, "upgrade"),
+        Vec::from_array(&env, [v2_hash.into_val(&env)]),
+    );
+    let version: u32 = env.invoke_contract(&contract_id, &Symbol::new(&env, "version"), Vec::new(&env));
+    assert_eq!(version, 2);
+
+    // Step 3: Both storage types survived the code swap
+    let balance: i128 = env.invoke_contract(
+        &contract_id,
+        &Symbol::new(&env, "balance"),
+        Vec::from_array(&env, [user.into_val(&env)]),
+    );
+    assert_eq!(balance, 150);
+    let migrated: u64 = env.invoke_contract(&contract_id, &Symbol::new(&env, "migrate"), Vec::new(&env));
+    assert_eq!(migrated, 2, "v1's instance counter was still there");
+
+    // Step 4: v2 keeps working on the migrated state
+    let balance: i128 = env.invoke_contract(
+        &contract_id,
+        &Symbol::new(&env, "deposit"),
+        Vec::from_array(&env, [user.into_val(&env), 25i128.into_val(&env)]),
+    );
+    assert_eq!(balance, 175);
+    let calls: u64 = env.invoke_contract(&contract_id, &Symbol::new(&env, "calls"), Vec::new(&env));
+    assert_eq!(calls, 3);
+}
+
+/// Test 10: Deployer - on-chain deployment of a child contract
+///
+/// Scenario: The deployer contract deploys two instances of an uploaded
+/// child Wasm under different salts, initializing each with its own
+/// arguments, and both land at the addresses predicted beforehand.
+#[test]
+fn test_deployer_workflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let deployer_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/deployer.wasm"));
+    let deployer_id = env.register_contract_wasm(None, deployer_wasm);
+    let child_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/deployer_child.wasm"));
+    let child_hash = env.deployer().upload_contract_wasm(child_wasm);
+
+    let admin = Address::generate(&env);
+    env.invoke_contract::<()>(
+        &deployer_id,
+        &Symbol::new(&env, "initialize"),
+        Vec::from_array(&env, [admin.into_val(&env)]),
+    );
+
+    // Step 1-2: Predict, then deploy two children with different salts
+    let mut children: std::vec::Vec<Address> = std::vec::Vec::new();
+    for (byte, value) in [(1u8, 10u32), (2, 20)] {
+        let salt = soroban_sdk::BytesN::from_array(&env, &[byte; 32]);
+        let predicted: Address = env.invoke_contract(
+            &deployer_id,
+            &Symbol::new(&env, "deployed_address"),
+            Vec::from_array(&env, [salt.into_val(&env)]),
+        );
+
+        let owner = Address::generate(&env);
+        let init_args: Vec<Val> = Vec::from_array(&env, [owner.into_val(&env), value.into_val(&env)]);
+        let deployed: Address = env.invoke_contract(
+            &deployer_id,
+            &Symbol::new(&env, "deploy"),
+            Vec::from_array(
+                &env,
+                [
+                    child_hash.into_val(&env),
+                    salt.into_val(&env),
+                    Symbol::new(&env, "init").into_val(&env),
+                    init_args.into_val(&env),
+                ],
+            ),
+        );
+        assert_eq!(deployed, predicted);
+
+        // Step 3: The child was initialized with its own arguments
+        let stored_owner: Address = env.invoke_contract(&deployed, &Symbol::new(&env, "owner"), Vec::new(&env));
+        let stored_value: u32 = env.invoke_contract(&deployed, &Symbol::new(&env, "value"), Vec::new(&env));
+        assert_eq!(stored_owner, owner);
+        assert_eq!(stored_value, value);
+        children.push(deployed);
+    }
+    assert_ne!(children[0], children[1]);
+
+    // Step 4: Both deployments are recorded
+    let count: u32 = env.invoke_contract(&deployer_id, &Symbol::new(&env, "deployment_count"), Vec::new(&env));
+    assert_eq!(count, 2);
+}
+
+/// Test 11: Upgradeable Token - v1 token upgraded to v2 with burn and fees
+///
+/// Scenario: v1 mints and transfers with no fees. The admin approves the
+/// v2 hash, upgrades, and migrates in the fee config. Holders' balances
+/// carry over untouched, and v2's burn and fees work on top of them.
+#[test]
+fn test_upgradeable_token_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let v1_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/upgradeable_token.wasm"));
+    let token_id = env.register_contract_wasm(None, v1_wasm);
+    let v2_wasm = Bytes::from_slice(&env, include_bytes!("../../../target/wasm32-unknown-unknown/release/upgradeable_token_v2.wasm"));
+    let v2_hash = env.deployer().upload_contract_wasm(v2_wasm);
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let collector = Address::generate(&env);
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "initialize"),
+        Vec::from_array(
+            &env,
+            [
+                admin.into_val(&env),
+                7u32.into_val(&env),
+                String::from_str(&env, "Cookbook").into_val(&env),
+                String::from_str(&env, "COOK").into_val(&env),
+            ],
+        ),
+    );
+    let balance = |id: &Address| -> i128 {
+        env.invoke_contract(&token_id, &Symbol::new(&env, "balance"), Vec::from_array(&env, [id.into_val(&env)]))
+    };
+
+    // Step 1: Use v1 - mint and a fee-free transfer
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "mint"),
+        Vec::from_array(&env, [alice.into_val(&env), 1_000i128.into_val(&env)]),
+    );
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "transfer"),
+        Vec::from_array(&env, [alice.into_val(&env), bob.into_val(&env), 400i128.into_val(&env)]),
+    );
+
+    // Step 2: Approve the v2 hash, then upgrade
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "approve_wasm"),
+        Vec::from_array(&env, [v2_hash.into_val(&env)]),
+    );
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "upgrade"),
+        Vec::from_array(&env, [v2_hash.into_val(&env)]),
+    );
+    let version: u32 = env.invoke_contract(&token_id, &Symbol::new(&env, "version"), Vec::new(&env));
+    assert_eq!(version, 2);
+
+    // Step 3: Balances and supply survived the code swap
+    assert_eq!(balance(&alice), 600);
+    assert_eq!(balance(&bob), 400);
+    let supply: i128 = env.invoke_contract(&token_id, &Symbol::new(&env, "total_supply"), Vec::new(&env));
+    assert_eq!(supply, 1_000);
+
+    // Step 4: Migrate in a 2.5% fee, then use v2's fee and burn
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "migrate"),
+        Vec::from_array(&env, [collector.into_val(&env), 250u32.into_val(&env)]),
+    );
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "transfer"),
+        Vec::from_array(&env, [alice.into_val(&env), bob.into_val(&env), 200i128.into_val(&env)]),
+    );
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "burn"),
+        Vec::from_array(&env, [bob.into_val(&env), 100i128.into_val(&env)]),
+    );
+    assert_eq!(balance(&alice), 400);
+    assert_eq!(balance(&bob), 495);
+    assert_eq!(balance(&collector), 5);
+    let supply: i128 = env.invoke_contract(&token_id, &Symbol::new(&env, "total_supply"), Vec::new(&env));
+    assert_eq!(supply, 900);
+}